@@ -3,12 +3,9 @@
 // SPDX-License-Identifier: BSD-2-Clause-Patent
 
 use fuzzlib::{
-    fake_device_io::FakeSpdmDeviceIo,
-    req_create_info, spdmlib,
-    spdmlib::protocol::MAX_SPDM_VERSION_COUNT,
-    spdmlib::{protocol::SpdmVersion, requester::RequesterContext},
-    time::SPDM_TIME_IMPL,
-    PciDoeTransportEncap, SharedBuffer, SECRET_ASYM_IMPL_INSTANCE,
+    fake_device_io::FakeSpdmDeviceIo, req_create_info, spdmlib,
+    spdmlib::requester::RequesterContext, time::SPDM_TIME_IMPL, PciDoeTransportEncap, SharedBuffer,
+    SECRET_ASYM_IMPL_INSTANCE,
 };
 
 #[allow(unused)]
@@ -45,9 +42,7 @@ fn fuzz_send_receive_spdm_version(fuzzdata: &[u8]) {
     // -
     {
         let (mut req_config_info, req_provision_info) = req_create_info();
-        for i in 0..MAX_SPDM_VERSION_COUNT {
-            req_config_info.spdm_version[i] = SpdmVersion::default();
-        }
+        req_config_info.spdm_version = &[];
 
         let shared_buffer = SharedBuffer::new();
 
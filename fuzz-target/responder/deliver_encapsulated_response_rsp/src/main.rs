@@ -45,7 +45,7 @@ fn fuzz_handle_deliver_encapsulated_reponse(data: &[u8]) {
         context.common.peer_info.peer_cert_chain_temp = Some(SpdmCertChainBuffer::default());
 
         let _ = context
-            .handle_deliver_encapsulated_reponse(4294836221, data)
+            .handle_deliver_encapsulated_reponse(Some(4294836221), data)
             .is_err();
     }
     // TCD:
@@ -83,7 +83,7 @@ fn fuzz_handle_deliver_encapsulated_reponse(data: &[u8]) {
         context.common.peer_info.peer_cert_chain_temp = Some(SpdmCertChainBuffer::default());
 
         let _ = context
-            .handle_deliver_encapsulated_reponse(4294836221, data)
+            .handle_deliver_encapsulated_reponse(Some(4294836221), data)
             .is_err();
     }
     // TCD:
@@ -119,7 +119,7 @@ fn fuzz_handle_deliver_encapsulated_reponse(data: &[u8]) {
         context.common.peer_info.peer_cert_chain_temp = Some(SpdmCertChainBuffer::default());
 
         let _ = context
-            .handle_deliver_encapsulated_reponse(4294836221, data)
+            .handle_deliver_encapsulated_reponse(Some(4294836221), data)
             .is_err();
     }
 }
@@ -44,7 +44,7 @@ fn fuzz_handle_get_encapsulated_request(data: &[u8]) {
         context.common.peer_info.peer_cert_chain_temp = Some(SpdmCertChainBuffer::default());
 
         let _ = context
-            .handle_get_encapsulated_request(4294836221, data)
+            .handle_get_encapsulated_request(Some(4294836221), data)
             .is_err();
     }
 }
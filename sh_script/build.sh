@@ -32,10 +32,13 @@ check() {
     cargo fmt --all -- --check
     cargo clippy -- -D warnings -A clippy::only-used-in-recursion -A clippy::result-large-err
     
-    pushd spdmlib_crypto_mbedtls
-    cargo check
-    cargo clippy -- -D warnings -A clippy::only-used-in-recursion -A clippy::result-large-err
-    popd
+    for crate in spdmlib_crypto_mbedtls spdmlib_crypto_rustcrypto spdmlib_crypto_openssl spdmlib_crypto_sm spdmlib_crypto_pqc; do
+        pushd $crate
+        cargo check
+        cargo fmt -- --check
+        cargo clippy -- -D warnings -A clippy::only-used-in-recursion -A clippy::result-large-err
+        popd
+    done
     set +x
 }
 
@@ -102,6 +105,9 @@ build() {
     echo "Building Rust-SPDM with spdm-ring,hashed-transcript-data,mut-auth feature..."
     echo_command cargo build --release --no-default-features --features=spdm-ring,hashed-transcript-data,mut-auth
 
+    echo "Building Rust-SPDM with spdm-ring,hashed-transcript-data,pqc feature..."
+    echo_command cargo build --release --no-default-features --features=spdm-ring,hashed-transcript-data,pqc
+
     if [ -z "$RUSTFLAGS" ]; then
         echo "Building Rust-SPDM in no std with no-default-features..."
         echo_command cargo build -Z build-std=core,alloc,compiler_builtins --target x86_64-unknown-none --release --no-default-features
@@ -117,18 +123,50 @@ build() {
     fi
 
     popd
+
+    pushd spdmlib_crypto_rustcrypto
+    echo "Building RustCrypto backend..."
+    echo_command cargo build --release
+    echo_command cargo build --release --no-default-features
+    popd
+
+    pushd spdmlib_crypto_sm
+    echo "Building SM backend..."
+    echo_command cargo build --release
+    echo_command cargo build --release --no-default-features
+    popd
+
+    pushd spdmlib_crypto_pqc
+    echo "Building PQC backend..."
+    echo_command cargo build --release
+    popd
+
+    if [ "${RUNNER_OS:-Linux}" == "Linux" ]; then
+        pushd spdmlib_crypto_openssl
+        echo "Building OpenSSL backend..."
+        echo_command cargo build --release
+        echo_command cargo build --release --no-default-features
+        popd
+    fi
     
     echo "Building spdm-requester-emu..."
     echo_command cargo build -p spdm-requester-emu
     
     echo "Building spdm-responder-emu..."
     echo_command cargo build -p spdm-responder-emu
+
+    if [ "${RUNNER_OS:-Linux}" == "Linux" ]; then
+        echo "Building spdm-requester-emu and spdm-responder-emu with spdm-openssl feature..."
+        echo_command cargo build -p spdm-requester-emu -p spdm-responder-emu --no-default-features --features=spdm-openssl,hashed-transcript-data,spdm-openssl-hashed-transcript-data
+    fi
 }
 
 RUN_REQUESTER_FEATURES=${RUN_REQUESTER_FEATURES:-spdm-ring,hashed-transcript-data}
 RUN_RESPONDER_FEATURES=${RUN_RESPONDER_FEATURES:-spdm-ring,hashed-transcript-data}
 RUN_REQUESTER_MUTAUTH_FEATURES="${RUN_REQUESTER_FEATURES},mut-auth"
 RUN_RESPONDER_MUTAUTH_FEATURES="${RUN_RESPONDER_FEATURES},mut-auth"
+RUN_REQUESTER_PUBKEY_FEATURES="${RUN_REQUESTER_FEATURES},pub-key-id"
+RUN_RESPONDER_PUBKEY_FEATURES="${RUN_RESPONDER_FEATURES},pub-key-id"
 
 run_with_spdm_emu() {
     echo "Running with spdm-emu..."
@@ -175,8 +213,34 @@ run_basic_test() {
     echo "Running spdmlib-test..."
     pushd test/spdmlib-test
     echo_command cargo test -- --test-threads=1
-    echo_command cargo test --no-default-features -- --test-threads=1
+    echo_command cargo test --no-default-features --features spdm-ring -- --test-threads=1
+    echo_command cargo test --no-default-features --features spdm-ring,mut-auth -- --test-threads=1
+    echo_command cargo test --no-default-features --features rustcrypto,hashed-transcript-data,mut-auth -- --test-threads=1
+    echo_command cargo test --no-default-features --features rustcrypto -- --test-threads=1
+    echo_command cargo test --features pqc -- --test-threads=1
+    popd
+
+    echo "Running RustCrypto backend tests..."
+    pushd spdmlib_crypto_rustcrypto
+    echo_command cargo test
+    popd
+
+    echo "Running SM backend tests..."
+    pushd spdmlib_crypto_sm
+    echo_command cargo test
+    popd
+
+    echo "Running PQC backend tests..."
+    pushd spdmlib_crypto_pqc
+    echo_command cargo test
     popd
+
+    if [ "${RUNNER_OS:-Linux}" == "Linux" ]; then
+        echo "Running OpenSSL backend tests..."
+        pushd spdmlib_crypto_openssl
+        echo_command cargo test
+        popd
+    fi
 }
 
 run_rust_spdm_emu() {
@@ -196,10 +260,19 @@ run_rust_spdm_emu_mut_auth() {
     cleanup
 }
 
+run_rust_spdm_emu_pub_key_id() {
+    echo "Running requester and responder with provisioned public key..."
+    echo_command cargo run -p spdm-responder-emu --no-default-features --features="$RUN_REQUESTER_PUBKEY_FEATURES" &
+    sleep 5
+    echo_command cargo run -p spdm-requester-emu --no-default-features --features="$RUN_RESPONDER_PUBKEY_FEATURES"
+    cleanup
+}
+
 run() {
     run_basic_test
     run_rust_spdm_emu
     run_rust_spdm_emu_mut_auth
+    run_rust_spdm_emu_pub_key_id
 }
 
 CHECK_OPTION=false
@@ -25,7 +25,8 @@ impl SpdmConfig {
         // All rust fixed-size arrays require non-negative compile-time constant sizes.
         // This will be checked by the compiler thus no need to check again here.
 
-        // We dont support chunking now.
+        // MinDataTransferSize is 42, larger messages are transferred
+        // with CHUNK_SEND/CHUNK_GET if both sides support chunking.
         assert!(self.max_spdm_msg_size >= 42);
 
         // Reserve some space for transport overhead.
@@ -44,6 +45,7 @@ impl SpdmConfig {
         );
         assert!(self.measurement_config.max_measurement_val_len >= 32);
         assert!(self.measurement_config.max_measurement_record_size < self.max_spdm_msg_size);
+        assert!(self.measurement_config.max_measurement_extension_log_size <= 0xFFFFFFFF);
 
         assert!(self.psk_config.max_psk_context_size >= 32);
         assert!(self.psk_config.max_psk_context_size <= 0xFFFF);
@@ -66,6 +68,7 @@ struct SpdmCertConfig {
 struct SpdmMeasurementConfig {
     max_measurement_record_size: usize,
     max_measurement_val_len: usize,
+    max_measurement_extension_log_size: usize,
 }
 
 #[derive(Debug, PartialEq, Deserialize)]
@@ -100,6 +103,9 @@ pub const MAX_SPDM_MEASUREMENT_RECORD_SIZE: usize = {meas_rec_sz}; // 0x1000
 /// It should be MAX (MAX MEASUREMENT_MANIFEST_LEN, MAX supported DIGEST SIZE)
 pub const MAX_SPDM_MEASUREMENT_VALUE_LEN: usize = {meas_val_len}; // 0x400
 
+/// This is used in SpdmMeasurementExtensionLog, the MEL returned by GET_MEASUREMENT_EXTENSION_LOG
+pub const MAX_SPDM_MEASUREMENT_EXTENSION_LOG_SIZE: usize = {mel_sz}; // 0x1000
+
 /// This is used in SpdmPskExchangeRequestPayload / SpdmPskExchangeResponsePayload
 /// It should be no smaller than negoatiated DIGEST SIZE.
 pub const MAX_SPDM_PSK_CONTEXT_SIZE: usize = {psk_ctx_sz};
@@ -176,6 +182,9 @@ fn main() {
         cert_chain_data_sz = spdm_config.cert_config.max_cert_chain_data_size,
         meas_rec_sz = spdm_config.measurement_config.max_measurement_record_size,
         meas_val_len = spdm_config.measurement_config.max_measurement_val_len,
+        mel_sz = spdm_config
+            .measurement_config
+            .max_measurement_extension_log_size,
         psk_ctx_sz = spdm_config.psk_config.max_psk_context_size,
         psk_hint_sz = spdm_config.psk_config.max_psk_hint_size,
         session_cnt = spdm_config.max_session_count,
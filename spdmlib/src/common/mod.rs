@@ -7,7 +7,10 @@ pub mod opaque;
 pub mod session;
 pub mod spdm_codec;
 
-use crate::message::SpdmRequestResponseCode;
+use crate::message::{
+    SpdmDigestsResponsePayload, SpdmRequestResponseCode, SPDM_CERT_MODEL_ALIAS_CERT,
+    SPDM_CERT_MODEL_DEVICE_CERT, SPDM_CERT_MODEL_MASK,
+};
 use crate::{crypto, protocol::*};
 
 pub use opaque::*;
@@ -16,8 +19,8 @@ pub use spdm_codec::SpdmCodec;
 use crate::config::{self, MAX_SPDM_SESSION_COUNT};
 use crate::error::{
     SpdmResult, SPDM_STATUS_BUFFER_FULL, SPDM_STATUS_CRYPTO_ERROR, SPDM_STATUS_DECAP_FAIL,
-    SPDM_STATUS_INVALID_PARAMETER, SPDM_STATUS_INVALID_STATE_LOCAL,
-    SPDM_STATUS_SESSION_NUMBER_EXCEED,
+    SPDM_STATUS_INVALID_PARAMETER, SPDM_STATUS_INVALID_STATE_LOCAL, SPDM_STATUS_NEGOTIATION_FAIL,
+    SPDM_STATUS_SESSION_NUMBER_EXCEED, SPDM_STATUS_UNSUPPORTED_CAP,
 };
 
 use codec::enum_builder;
@@ -140,6 +143,8 @@ pub struct SpdmContext<'a> {
     #[cfg(feature = "mut-auth")]
     pub encap_context: SpdmEncapContext,
 
+    pub chunk_context: SpdmChunkContext,
+
     pub session: [SpdmSession; config::MAX_SPDM_SESSION_COUNT],
 }
 
@@ -160,6 +165,7 @@ impl<'a> SpdmContext<'a> {
             peer_info: SpdmPeerInfo::default(),
             #[cfg(feature = "mut-auth")]
             encap_context: SpdmEncapContext::default(),
+            chunk_context: SpdmChunkContext::default(),
             session: gen_array(config::MAX_SPDM_SESSION_COUNT),
         }
     }
@@ -168,12 +174,138 @@ impl<'a> SpdmContext<'a> {
         self.negotiate_info.base_hash_sel.get_size()
     }
     pub fn get_asym_key_size(&self) -> u16 {
-        self.negotiate_info.base_asym_sel.get_size()
+        if !self.negotiate_info.pqc_asym_sel.is_empty() {
+            return self.negotiate_info.pqc_asym_sel.get_size();
+        }
+        match self.negotiate_info.ext_asym_sel {
+            Some(ext_asym_sel) => crypto::ext_asym::get_signature_size(ext_asym_sel),
+            None => self.negotiate_info.base_asym_sel.get_size(),
+        }
     }
+    /// Size of the requester exchange data of KEY_EXCHANGE.
     pub fn get_dhe_key_size(&self) -> u16 {
+        if !self.negotiate_info.kem_sel.is_empty() {
+            return self.negotiate_info.kem_sel.get_size();
+        }
+        self.negotiate_info.dhe_sel.get_size()
+    }
+    /// Size of the responder exchange data of KEY_EXCHANGE_RSP, which is the
+    /// cipher text when a KEM is selected.
+    pub fn get_rsp_dhe_key_size(&self) -> u16 {
+        if !self.negotiate_info.kem_sel.is_empty() {
+            return self.negotiate_info.kem_sel.get_cipher_text_size();
+        }
         self.negotiate_info.dhe_sel.get_size()
     }
 
+    /// The PQC algorithms are negotiated from SPDM 1.4, and only when the
+    /// buffers are sized for them by the pqc feature.
+    pub fn is_pqc_supported(&self) -> bool {
+        cfg!(feature = "pqc")
+            && self.negotiate_info.spdm_version_sel.get_u8() >= SpdmVersion::SpdmVersion14.get_u8()
+    }
+
+    /// The configured base asymmetric algorithms which can be negotiated.
+    /// SM2 is left out unless an SM2 verifier is registered.
+    pub fn get_supported_base_asym_algo(&self) -> SpdmBaseAsymAlgo {
+        let mut base_asym_algo = self.config_info.base_asym_algo;
+        if !crypto::sm2_verify::is_supported() {
+            base_asym_algo.remove(SpdmBaseAsymAlgo::TPM_ALG_SM2_ECC_SM2_P256);
+        }
+        base_asym_algo
+    }
+
+    /// Same as get_supported_base_asym_algo, for the requester asymmetric
+    /// algorithms.
+    pub fn get_supported_req_asym_algo(&self) -> SpdmReqAsymAlgo {
+        let mut req_asym_algo = self.config_info.req_asym_algo;
+        if !crypto::sm2_verify::is_supported() {
+            req_asym_algo.remove(SpdmReqAsymAlgo::TPM_ALG_SM2_ECC_SM2_P256);
+        }
+        req_asym_algo
+    }
+
+    pub fn is_chunk_supported(&self) -> bool {
+        self.negotiate_info.spdm_version_sel.get_u8() >= SpdmVersion::SpdmVersion12.get_u8()
+            && self
+                .negotiate_info
+                .req_capabilities_sel
+                .contains(SpdmRequestCapabilityFlags::CHUNK_CAP)
+            && self
+                .negotiate_info
+                .rsp_capabilities_sel
+                .contains(SpdmResponseCapabilityFlags::CHUNK_CAP)
+    }
+
+    /// Save the negotiated state, to resume the connection after a reset
+    /// without GET_VERSION, GET_CAPABILITIES and NEGOTIATE_ALGORITHMS.
+    pub fn get_cached_negotiate_state(&self) -> SpdmResult<SpdmCachedNegotiateState> {
+        // The requester does not track the connection state, so check that
+        // the algorithms have been negotiated.
+        if self.runtime_info.message_a.as_ref().is_empty()
+            || self.negotiate_info.base_hash_sel.is_empty()
+        {
+            return Err(SPDM_STATUS_INVALID_STATE_LOCAL);
+        }
+        if !self
+            .negotiate_info
+            .rsp_capabilities_sel
+            .contains(SpdmResponseCapabilityFlags::CACHE_CAP)
+        {
+            return Err(SPDM_STATUS_UNSUPPORTED_CAP);
+        }
+
+        Ok(SpdmCachedNegotiateState {
+            negotiate_info: self.negotiate_info.clone(),
+            message_a: self.runtime_info.message_a.clone(),
+        })
+    }
+
+    /// Restore a negotiated state saved by get_cached_negotiate_state. The
+    /// connection is left untouched if the state does not match the local
+    /// algorithms. The capabilities are checked by the requester and responder.
+    pub(crate) fn restore_cached_negotiate_state(
+        &mut self,
+        cached_state: &SpdmCachedNegotiateState,
+    ) -> SpdmResult {
+        if !self.is_cached_negotiate_state_valid(cached_state) {
+            return Err(SPDM_STATUS_NEGOTIATION_FAIL);
+        }
+
+        self.reset_context();
+        self.negotiate_info = cached_state.negotiate_info.clone();
+        self.runtime_info.message_a = cached_state.message_a.clone();
+        self.runtime_info
+            .set_connection_state(SpdmConnectionState::SpdmConnectionNegotiated);
+        Ok(())
+    }
+
+    fn is_cached_negotiate_state_valid(&self, cached_state: &SpdmCachedNegotiateState) -> bool {
+        let info = &cached_state.negotiate_info;
+        let config = &self.config_info;
+
+        config.spdm_version.contains(&info.spdm_version_sel)
+            && info
+                .rsp_capabilities_sel
+                .contains(SpdmResponseCapabilityFlags::CACHE_CAP)
+            && config.base_hash_algo.contains(info.base_hash_sel)
+            && config.base_asym_algo.contains(info.base_asym_sel)
+            && config.pqc_asym_algo.contains(info.pqc_asym_sel)
+            && config
+                .measurement_hash_algo
+                .contains(info.measurement_hash_sel)
+            && config.dhe_algo.contains(info.dhe_sel)
+            && config.kem_algo.contains(info.kem_sel)
+            && config.aead_algo.contains(info.aead_sel)
+            && config.req_asym_algo.contains(info.req_asym_sel)
+            && config.key_schedule_algo.contains(info.key_schedule_sel)
+            && info
+                .ext_asym_sel
+                .map_or(true, |sel| config.ext_asym_algo.contains(&Some(sel)))
+            && info.ext_hash_sel.is_none()
+            && !cached_state.message_a.as_ref().is_empty()
+    }
+
     pub fn reset_runtime_info(&mut self) {
         self.runtime_info = SpdmRuntimeInfo::default();
     }
@@ -186,10 +318,18 @@ impl<'a> SpdmContext<'a> {
         self.peer_info = SpdmPeerInfo::default();
     }
 
+    pub fn reset_chunk_context(&mut self) {
+        self.chunk_context.chunk_status = SpdmChunkStatus::SpdmChunkIdle;
+        self.chunk_context.chunk_seq_num = 0;
+        self.chunk_context.chunk_message_size = 0;
+        self.chunk_context.chunk_message_offset = 0;
+    }
+
     pub fn reset_context(&mut self) {
         self.reset_runtime_info();
         self.reset_negotiate_info();
         self.reset_peer_info();
+        self.reset_chunk_context();
 
         for s in &mut self.session {
             s.set_default();
@@ -377,6 +517,98 @@ impl<'a> SpdmContext<'a> {
         }
     }
 
+    #[cfg(feature = "mut-auth")]
+    pub fn append_message_mut_b(&mut self, new_message: &[u8]) -> SpdmResult {
+        #[cfg(not(feature = "hashed-transcript-data"))]
+        {
+            self.runtime_info
+                .message_mut_b
+                .append_message(new_message)
+                .ok_or(SPDM_STATUS_BUFFER_FULL)?;
+        }
+
+        #[cfg(feature = "hashed-transcript-data")]
+        {
+            if self.runtime_info.digest_context_mut_m1m2.is_none() {
+                self.runtime_info.digest_context_mut_m1m2 =
+                    crypto::hash::hash_ctx_init(self.negotiate_info.base_hash_sel);
+                if self.runtime_info.digest_context_mut_m1m2.is_none() {
+                    return Err(SPDM_STATUS_CRYPTO_ERROR);
+                }
+
+                crypto::hash::hash_ctx_update(
+                    self.runtime_info.digest_context_mut_m1m2.as_mut().unwrap(),
+                    self.runtime_info.message_a.as_ref(),
+                )?;
+            }
+
+            crypto::hash::hash_ctx_update(
+                self.runtime_info.digest_context_mut_m1m2.as_mut().unwrap(),
+                new_message,
+            )?;
+        }
+
+        Ok(())
+    }
+    #[cfg(feature = "mut-auth")]
+    pub fn reset_message_mut_b(&mut self) {
+        #[cfg(not(feature = "hashed-transcript-data"))]
+        {
+            self.runtime_info.message_mut_b.reset_message();
+        }
+
+        #[cfg(feature = "hashed-transcript-data")]
+        {
+            self.runtime_info.digest_context_mut_m1m2 = None;
+        }
+    }
+
+    #[cfg(feature = "mut-auth")]
+    pub fn append_message_mut_c(&mut self, new_message: &[u8]) -> SpdmResult {
+        #[cfg(not(feature = "hashed-transcript-data"))]
+        {
+            self.runtime_info
+                .message_mut_c
+                .append_message(new_message)
+                .ok_or(SPDM_STATUS_BUFFER_FULL)?;
+        }
+
+        #[cfg(feature = "hashed-transcript-data")]
+        {
+            if self.runtime_info.digest_context_mut_m1m2.is_none() {
+                self.runtime_info.digest_context_mut_m1m2 =
+                    crypto::hash::hash_ctx_init(self.negotiate_info.base_hash_sel);
+                if self.runtime_info.digest_context_mut_m1m2.is_none() {
+                    return Err(SPDM_STATUS_CRYPTO_ERROR);
+                }
+
+                crypto::hash::hash_ctx_update(
+                    self.runtime_info.digest_context_mut_m1m2.as_mut().unwrap(),
+                    self.runtime_info.message_a.as_ref(),
+                )?;
+            }
+
+            crypto::hash::hash_ctx_update(
+                self.runtime_info.digest_context_mut_m1m2.as_mut().unwrap(),
+                new_message,
+            )?;
+        }
+
+        Ok(())
+    }
+    #[cfg(feature = "mut-auth")]
+    pub fn reset_message_mut_c(&mut self) {
+        #[cfg(not(feature = "hashed-transcript-data"))]
+        {
+            self.runtime_info.message_mut_c.reset_message();
+        }
+
+        #[cfg(feature = "hashed-transcript-data")]
+        {
+            self.runtime_info.digest_context_mut_m1m2 = None;
+        }
+    }
+
     pub fn append_message_m(&mut self, session_id: Option<u32>, new_message: &[u8]) -> SpdmResult {
         #[cfg(not(feature = "hashed-transcript-data"))]
         match session_id {
@@ -492,6 +724,163 @@ impl<'a> SpdmContext<'a> {
         }
     }
 
+    pub fn append_message_e(&mut self, session_id: Option<u32>, new_message: &[u8]) -> SpdmResult {
+        #[cfg(not(feature = "hashed-transcript-data"))]
+        match session_id {
+            None => self
+                .runtime_info
+                .message_e
+                .append_message(new_message)
+                .ok_or(SPDM_STATUS_BUFFER_FULL)?,
+            Some(session_id) => {
+                let session = if let Some(s) = self.get_session_via_id(session_id) {
+                    s
+                } else {
+                    return Err(SPDM_STATUS_INVALID_STATE_LOCAL);
+                };
+                session
+                    .runtime_info
+                    .message_e
+                    .append_message(new_message)
+                    .ok_or(SPDM_STATUS_BUFFER_FULL)?
+            }
+        };
+
+        // GET_ENDPOINT_INFO is a SPDM 1.3 message, IL1/IL2 always starts with VCA.
+        #[cfg(feature = "hashed-transcript-data")]
+        {
+            match session_id {
+                Some(session_id) => {
+                    let base_hash_sel = self.negotiate_info.base_hash_sel;
+                    let message_a = self.runtime_info.message_a.clone();
+
+                    let session = if let Some(s) = self.get_session_via_id(session_id) {
+                        s
+                    } else {
+                        return Err(SPDM_STATUS_INVALID_STATE_LOCAL);
+                    };
+                    if session.runtime_info.digest_context_il1il2.is_none() {
+                        session.runtime_info.digest_context_il1il2 =
+                            crypto::hash::hash_ctx_init(base_hash_sel);
+                        if session.runtime_info.digest_context_il1il2.is_none() {
+                            return Err(SPDM_STATUS_CRYPTO_ERROR);
+                        }
+
+                        crypto::hash::hash_ctx_update(
+                            session.runtime_info.digest_context_il1il2.as_mut().unwrap(),
+                            message_a.as_ref(),
+                        )?;
+                    }
+
+                    crypto::hash::hash_ctx_update(
+                        session.runtime_info.digest_context_il1il2.as_mut().unwrap(),
+                        new_message,
+                    )?;
+                }
+                None => {
+                    if self.runtime_info.digest_context_il1il2.is_none() {
+                        self.runtime_info.digest_context_il1il2 =
+                            crypto::hash::hash_ctx_init(self.negotiate_info.base_hash_sel);
+                        if self.runtime_info.digest_context_il1il2.is_none() {
+                            return Err(SPDM_STATUS_CRYPTO_ERROR);
+                        }
+
+                        crypto::hash::hash_ctx_update(
+                            self.runtime_info.digest_context_il1il2.as_mut().unwrap(),
+                            self.runtime_info.message_a.as_ref(),
+                        )?;
+                    }
+
+                    crypto::hash::hash_ctx_update(
+                        self.runtime_info.digest_context_il1il2.as_mut().unwrap(),
+                        new_message,
+                    )?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+    pub fn reset_message_e(&mut self, session_id: Option<u32>) {
+        #[cfg(not(feature = "hashed-transcript-data"))]
+        match session_id {
+            None => self.runtime_info.message_e.reset_message(),
+            Some(session_id) => {
+                let session = if let Some(s) = self.get_session_via_id(session_id) {
+                    s
+                } else {
+                    return;
+                };
+                session.runtime_info.message_e.reset_message();
+            }
+        }
+
+        #[cfg(feature = "hashed-transcript-data")]
+        {
+            match session_id {
+                Some(session_id) => {
+                    let session = if let Some(s) = self.get_session_via_id(session_id) {
+                        s
+                    } else {
+                        return;
+                    };
+                    session.runtime_info.digest_context_il1il2 = None;
+                }
+                None => {
+                    self.runtime_info.digest_context_il1il2 = None;
+                }
+            }
+        }
+    }
+
+    #[cfg(not(feature = "hashed-transcript-data"))]
+    pub fn calc_message_il1il2_hash(
+        &self,
+        session_id: Option<u32>,
+    ) -> SpdmResult<SpdmDigestStruct> {
+        let mut message_il1il2 = ManagedBufferIL1IL2::default();
+        message_il1il2
+            .append_message(self.runtime_info.message_a.as_ref())
+            .ok_or(SPDM_STATUS_BUFFER_FULL)?;
+        match session_id {
+            None => {
+                message_il1il2
+                    .append_message(self.runtime_info.message_e.as_ref())
+                    .ok_or(SPDM_STATUS_BUFFER_FULL)?;
+            }
+            Some(session_id) => {
+                let session = self
+                    .get_immutable_session_via_id(session_id)
+                    .ok_or(SPDM_STATUS_INVALID_PARAMETER)?;
+                message_il1il2
+                    .append_message(session.runtime_info.message_e.as_ref())
+                    .ok_or(SPDM_STATUS_BUFFER_FULL)?;
+            }
+        }
+        debug!("message_il1il2 - {:02x?}", message_il1il2.as_ref());
+
+        crypto::hash::hash_all(self.negotiate_info.base_hash_sel, message_il1il2.as_ref())
+            .ok_or(SPDM_STATUS_CRYPTO_ERROR)
+    }
+
+    #[cfg(feature = "hashed-transcript-data")]
+    pub fn calc_message_il1il2_hash(
+        &self,
+        session_id: Option<u32>,
+    ) -> SpdmResult<SpdmDigestStruct> {
+        let ctx = match session_id {
+            None => self.runtime_info.digest_context_il1il2.as_ref(),
+            Some(session_id) => self
+                .get_immutable_session_via_id(session_id)
+                .ok_or(SPDM_STATUS_INVALID_PARAMETER)?
+                .runtime_info
+                .digest_context_il1il2
+                .as_ref(),
+        };
+        let ctx = ctx.cloned().ok_or(SPDM_STATUS_INVALID_STATE_LOCAL)?;
+        crypto::hash::hash_ctx_finalize(ctx).ok_or(SPDM_STATUS_CRYPTO_ERROR)
+    }
+
     pub fn append_message_k(&mut self, session_id: u32, new_message: &[u8]) -> SpdmResult {
         let session = self.get_session_via_id(session_id).unwrap();
 
@@ -580,39 +969,26 @@ impl<'a> SpdmContext<'a> {
         }
 
         if !session.runtime_info.message_f_initialized {
-            let mut_cert_digest = if !session.get_use_psk()
-                && !session.get_mut_auth_requested().is_empty()
-            {
-                if is_requester {
-                    let slot_id = self.runtime_info.get_local_used_cert_chain_slot_id();
-                    if let Some(cert_chain) = &self.provision_info.my_cert_chain[slot_id as usize] {
-                        Some(
-                            crypto::hash::hash_all(
-                                self.negotiate_info.base_hash_sel,
-                                &cert_chain.data[..cert_chain.data_size as usize],
-                            )
-                            .ok_or(SPDM_STATUS_CRYPTO_ERROR)?,
-                        )
+            let mut_cert_digest =
+                if !session.get_use_psk() && !session.get_mut_auth_requested().is_empty() {
+                    let cert_chain = if is_requester {
+                        let slot_id = self.runtime_info.get_local_used_cert_chain_slot_id();
+                        self.get_my_cert_chain_or_public_key(slot_id)
                     } else {
-                        return Err(SPDM_STATUS_INVALID_STATE_LOCAL);
-                    }
-                } else {
-                    let slot_id = self.runtime_info.get_peer_used_cert_chain_slot_id();
-                    if let Some(cert_chain) = &self.peer_info.peer_cert_chain[slot_id as usize] {
+                        let slot_id = self.runtime_info.get_peer_used_cert_chain_slot_id();
+                        self.get_peer_cert_chain_or_public_key(slot_id)
+                    };
+                    if let Some(cert_chain) = cert_chain {
                         Some(
-                            crypto::hash::hash_all(
-                                self.negotiate_info.base_hash_sel,
-                                cert_chain.as_ref(),
-                            )
-                            .ok_or(SPDM_STATUS_CRYPTO_ERROR)?,
+                            crypto::hash::hash_all(self.negotiate_info.base_hash_sel, cert_chain)
+                                .ok_or(SPDM_STATUS_CRYPTO_ERROR)?,
                         )
                     } else {
                         return Err(SPDM_STATUS_INVALID_STATE_LOCAL);
                     }
-                }
-            } else {
-                None
-            };
+                } else {
+                    None
+                };
 
             if let Some(mut_cert_digest) = mut_cert_digest {
                 let session = self.get_session_via_id(session_id).unwrap();
@@ -663,18 +1039,13 @@ impl<'a> SpdmContext<'a> {
         debug!("message_a - {:02x?}", self.runtime_info.message_a.as_ref());
 
         if !use_psk {
-            if self.peer_info.peer_cert_chain[slot_id as usize].is_none() {
-                error!("peer_cert_chain is not populated!\n");
-                return Err(SPDM_STATUS_INVALID_PARAMETER);
-            }
-
-            let cert_chain_data = &self.peer_info.peer_cert_chain[slot_id as usize]
-                .as_ref()
-                .ok_or(SPDM_STATUS_INVALID_PARAMETER)?
-                .data[..(self.peer_info.peer_cert_chain[slot_id as usize]
-                .as_ref()
-                .ok_or(SPDM_STATUS_INVALID_PARAMETER)?
-                .data_size as usize)];
+            let cert_chain_data =
+                if let Some(cert_chain_data) = self.get_peer_cert_chain_or_public_key(slot_id) {
+                    cert_chain_data
+                } else {
+                    error!("peer_cert_chain is not populated!\n");
+                    return Err(SPDM_STATUS_INVALID_PARAMETER);
+                };
             let cert_chain_hash =
                 crypto::hash::hash_all(self.negotiate_info.base_hash_sel, cert_chain_data)
                     .ok_or(SPDM_STATUS_CRYPTO_ERROR)?;
@@ -690,18 +1061,13 @@ impl<'a> SpdmContext<'a> {
 
         if !use_psk && is_mut_auth {
             let slot_id = self.runtime_info.get_local_used_cert_chain_slot_id();
-            if self.provision_info.my_cert_chain[slot_id as usize].is_none() {
-                error!("mut cert_chain is not populated!\n");
-                return Err(SPDM_STATUS_INVALID_PARAMETER);
-            }
-
-            let cert_chain_data = &self.provision_info.my_cert_chain[slot_id as usize]
-                .as_ref()
-                .ok_or(SPDM_STATUS_INVALID_PARAMETER)?
-                .data[..(self.provision_info.my_cert_chain[slot_id as usize]
-                .as_ref()
-                .ok_or(SPDM_STATUS_INVALID_PARAMETER)?
-                .data_size as usize)];
+            let cert_chain_data =
+                if let Some(cert_chain_data) = self.get_my_cert_chain_or_public_key(slot_id) {
+                    cert_chain_data
+                } else {
+                    error!("mut cert_chain is not populated!\n");
+                    return Err(SPDM_STATUS_INVALID_PARAMETER);
+                };
             let cert_chain_hash =
                 crypto::hash::hash_all(self.negotiate_info.base_hash_sel, cert_chain_data)
                     .ok_or(SPDM_STATUS_CRYPTO_ERROR)?;
@@ -736,15 +1102,13 @@ impl<'a> SpdmContext<'a> {
             .ok_or(SPDM_STATUS_BUFFER_FULL)?;
         debug!("message_a - {:02x?}", self.runtime_info.message_a.as_ref());
         if !use_psk {
-            if self.provision_info.my_cert_chain[slot_id as usize].is_none() {
-                error!("my_cert_chain is not populated!\n");
-                return Err(SPDM_STATUS_INVALID_STATE_LOCAL);
-            }
-
-            let my_cert_chain_data = self.provision_info.my_cert_chain[slot_id as usize]
-                .as_ref()
-                .ok_or(SPDM_STATUS_INVALID_STATE_LOCAL)?;
-            let cert_chain_data = my_cert_chain_data.as_ref();
+            let cert_chain_data =
+                if let Some(cert_chain_data) = self.get_my_cert_chain_or_public_key(slot_id) {
+                    cert_chain_data
+                } else {
+                    error!("my_cert_chain is not populated!\n");
+                    return Err(SPDM_STATUS_INVALID_STATE_LOCAL);
+                };
             let cert_chain_hash =
                 crypto::hash::hash_all(self.negotiate_info.base_hash_sel, cert_chain_data)
                     .ok_or(SPDM_STATUS_CRYPTO_ERROR)?;
@@ -761,15 +1125,13 @@ impl<'a> SpdmContext<'a> {
 
         if !use_psk && is_mut_auth {
             let slot_id = self.runtime_info.get_peer_used_cert_chain_slot_id();
-            if self.peer_info.peer_cert_chain[slot_id as usize].is_none() {
-                error!("peer_cert_chain is not populated!\n");
-                return Err(SPDM_STATUS_INVALID_PARAMETER);
-            }
-
-            let cert_chain_data = &self.peer_info.peer_cert_chain[slot_id as usize]
-                .as_ref()
-                .ok_or(SPDM_STATUS_INVALID_PARAMETER)?
-                .as_ref();
+            let cert_chain_data =
+                if let Some(cert_chain_data) = self.get_peer_cert_chain_or_public_key(slot_id) {
+                    cert_chain_data
+                } else {
+                    error!("peer_cert_chain is not populated!\n");
+                    return Err(SPDM_STATUS_INVALID_PARAMETER);
+                };
             let cert_chain_hash =
                 crypto::hash::hash_all(self.negotiate_info.base_hash_sel, cert_chain_data)
                     .ok_or(SPDM_STATUS_CRYPTO_ERROR)?;
@@ -867,21 +1229,16 @@ impl<'a> SpdmContext<'a> {
         Ok(transcript_hash)
     }
 
-    pub fn get_certchain_hash_local(
-        &self,
-        use_psk: bool,
-        slot_id: usize,
-    ) -> Option<SpdmDigestStruct> {
+    pub fn get_certchain_hash_local(&self, use_psk: bool, slot_id: u8) -> Option<SpdmDigestStruct> {
         if !use_psk {
-            if self.provision_info.my_cert_chain[slot_id].is_none() {
+            let cert_chain_data = self.get_my_cert_chain_or_public_key(slot_id);
+            if cert_chain_data.is_none() {
                 error!("my_cert_chain is not populated!\n");
                 return None;
             }
 
-            let my_cert_chain_data = self.provision_info.my_cert_chain[slot_id].as_ref()?;
-            let cert_chain_data = my_cert_chain_data.as_ref();
             let cert_chain_hash =
-                crypto::hash::hash_all(self.negotiate_info.base_hash_sel, cert_chain_data)
+                crypto::hash::hash_all(self.negotiate_info.base_hash_sel, cert_chain_data?)
                     .ok_or(None::<SpdmDigestStruct>);
             if let Ok(hash) = cert_chain_hash {
                 Some(SpdmDigestStruct::from(hash.as_ref()))
@@ -893,21 +1250,16 @@ impl<'a> SpdmContext<'a> {
         }
     }
 
-    pub fn get_certchain_hash_peer(
-        &self,
-        use_psk: bool,
-        slot_id: usize,
-    ) -> Option<SpdmDigestStruct> {
+    pub fn get_certchain_hash_peer(&self, use_psk: bool, slot_id: u8) -> Option<SpdmDigestStruct> {
         if !use_psk {
-            if self.peer_info.peer_cert_chain[slot_id].is_none() {
+            let cert_chain_data = self.get_peer_cert_chain_or_public_key(slot_id);
+            if cert_chain_data.is_none() {
                 error!("peer_cert_chain is not populated!\n");
                 return None;
             }
 
-            let cert_chain_data = &self.peer_info.peer_cert_chain[slot_id].as_ref()?.data
-                [..(self.peer_info.peer_cert_chain[slot_id].as_ref()?.data_size as usize)];
             let cert_chain_hash =
-                crypto::hash::hash_all(self.negotiate_info.base_hash_sel, cert_chain_data)
+                crypto::hash::hash_all(self.negotiate_info.base_hash_sel, cert_chain_data?)
                     .ok_or(None::<SpdmDigestStruct>);
 
             if let Ok(hash) = cert_chain_hash {
@@ -920,6 +1272,294 @@ impl<'a> SpdmContext<'a> {
         }
     }
 
+    /// The certificate chain in slot_id, or the provisioned public key
+    /// for SPDM_PUBLIC_KEY_SLOT_ID, as it is hashed into the transcript.
+    pub fn get_my_cert_chain_or_public_key(&self, slot_id: u8) -> Option<&[u8]> {
+        if slot_id == SPDM_PUBLIC_KEY_SLOT_ID {
+            Some(self.provision_info.my_public_key.as_ref()?.as_ref())
+        } else {
+            Some(
+                self.provision_info
+                    .my_cert_chain
+                    .get(slot_id as usize)?
+                    .as_ref()?
+                    .as_ref(),
+            )
+        }
+    }
+
+    /// The key pair that signs with the local certificate chain in slot_id.
+    pub fn get_my_key_pair_id(&self, slot_id: u8) -> u8 {
+        self.provision_info
+            .my_key_pair_id
+            .get(slot_id as usize)
+            .copied()
+            .unwrap_or(SPDM_DEFAULT_KEY_PAIR_ID)
+    }
+
+    /// The DIGESTS of the local certificate slots, with the key pair bound to
+    /// each slot for a multi-key connection.
+    pub fn get_my_digests(&self) -> Option<SpdmDigestsResponsePayload> {
+        let mut digests = SpdmDigestsResponsePayload::default();
+        let mut count = 0usize;
+        for slot_id in 0..SPDM_MAX_SLOT_NUMBER {
+            if let Some(my_cert_chain) = &self.provision_info.my_cert_chain[slot_id] {
+                digests.slot_mask |= (1 << slot_id) as u8;
+                digests.digests[count] = crypto::hash::hash_all(
+                    self.negotiate_info.base_hash_sel,
+                    my_cert_chain.as_ref(),
+                )?;
+                if self.negotiate_info.local_multi_key_conn {
+                    let key_pair_id = self.get_my_key_pair_id(slot_id as u8);
+                    digests.key_pair_id[count] = key_pair_id;
+                    digests.certificate_info[count] = SPDM_CERT_MODEL_DEVICE_CERT;
+                    if key_pair_id != SPDM_DEFAULT_KEY_PAIR_ID {
+                        digests.key_usage_mask[count] =
+                            crate::secret::key_pair::get_key_pair_info(key_pair_id)?
+                                .current_key_usage;
+                    }
+                }
+                count += 1;
+            }
+        }
+        Some(digests)
+    }
+
+    /// Keep the key pair of each peer certificate slot reported in DIGESTS.
+    pub fn set_peer_key_pairs(&mut self, digests: &SpdmDigestsResponsePayload) {
+        let mut count = 0usize;
+        for slot_id in 0..SPDM_MAX_SLOT_NUMBER {
+            if digests.slot_mask & (1 << slot_id) != 0 {
+                self.peer_info.peer_key_pair_id[slot_id] = digests.key_pair_id[count];
+                self.peer_info.peer_key_usage_mask[slot_id] = digests.key_usage_mask[count];
+                self.peer_info.peer_certificate_info[slot_id] = digests.certificate_info[count];
+                count += 1;
+            } else {
+                self.peer_info.peer_key_pair_id[slot_id] = SPDM_DEFAULT_KEY_PAIR_ID;
+                self.peer_info.peer_key_usage_mask[slot_id] = SpdmKeyUsageMask::empty();
+                self.peer_info.peer_certificate_info[slot_id] = 0;
+            }
+        }
+    }
+
+    /// Whether the responder certificate chain in slot_id follows the alias
+    /// certificate model: ALIAS_CERT_CAP is negotiated and the slot, if its
+    /// certificate model is reported in DIGESTS, holds an alias certificate.
+    pub fn is_peer_alias_cert_model(&self, slot_id: u8) -> bool {
+        if !self
+            .negotiate_info
+            .rsp_capabilities_sel
+            .contains(SpdmResponseCapabilityFlags::ALIAS_CERT_CAP)
+        {
+            return false;
+        }
+        matches!(
+            self.peer_info
+                .peer_certificate_info
+                .get(slot_id as usize)
+                .map(|certificate_info| certificate_info & SPDM_CERT_MODEL_MASK),
+            Some(0) | Some(SPDM_CERT_MODEL_ALIAS_CERT)
+        )
+    }
+
+    /// The peer certificate chain in slot_id, or the provisioned peer public
+    /// key for SPDM_PUBLIC_KEY_SLOT_ID, as it is hashed into the transcript.
+    pub fn get_peer_cert_chain_or_public_key(&self, slot_id: u8) -> Option<&[u8]> {
+        if slot_id == SPDM_PUBLIC_KEY_SLOT_ID {
+            Some(self.provision_info.peer_public_key.as_ref()?.as_ref())
+        } else {
+            Some(
+                self.peer_info
+                    .peer_cert_chain
+                    .get(slot_id as usize)?
+                    .as_ref()?
+                    .as_ref(),
+            )
+        }
+    }
+
+    /// The SM2 signing ID of GB/T 32918.2 for a signature with the signing
+    /// context string sign_context: the context string since SPDM 1.2, and
+    /// empty before.
+    fn get_sm2_id<'b>(&self, sign_context: &'b [u8]) -> &'b [u8] {
+        if self.negotiate_info.spdm_version_sel.get_u8() >= SpdmVersion::SpdmVersion12.get_u8() {
+            sign_context
+        } else {
+            &[]
+        }
+    }
+
+    /// Verify a signature of the base asymmetric algorithm with the leaf
+    /// certificate of public_cert_der. sign_context is the signing context
+    /// string of the signature, used as the SM2 signing ID.
+    pub fn verify_base_asym_signature(
+        &self,
+        public_cert_der: &[u8],
+        sign_context: &[u8],
+        data: &[u8],
+        signature: &SpdmSignatureStruct,
+    ) -> SpdmResult {
+        if self.negotiate_info.base_asym_sel == SpdmBaseAsymAlgo::TPM_ALG_SM2_ECC_SM2_P256 {
+            return crypto::sm2_verify::verify(
+                self.negotiate_info.base_hash_sel,
+                self.get_sm2_id(sign_context),
+                public_cert_der,
+                data,
+                signature,
+            );
+        }
+        crypto::asym_verify::verify(
+            self.negotiate_info.base_hash_sel,
+            self.negotiate_info.base_asym_sel,
+            public_cert_der,
+            data,
+            signature,
+        )
+    }
+
+    /// Same as verify_base_asym_signature, with the DER encoded
+    /// SubjectPublicKeyInfo public_key_der.
+    pub fn verify_base_asym_signature_with_public_key(
+        &self,
+        public_key_der: &[u8],
+        sign_context: &[u8],
+        data: &[u8],
+        signature: &SpdmSignatureStruct,
+    ) -> SpdmResult {
+        if self.negotiate_info.base_asym_sel == SpdmBaseAsymAlgo::TPM_ALG_SM2_ECC_SM2_P256 {
+            return crypto::sm2_verify::verify_with_public_key(
+                self.negotiate_info.base_hash_sel,
+                self.get_sm2_id(sign_context),
+                public_key_der,
+                data,
+                signature,
+            );
+        }
+        crypto::asym_verify::verify_with_public_key(
+            self.negotiate_info.base_hash_sel,
+            self.negotiate_info.base_asym_sel,
+            public_key_der,
+            data,
+            signature,
+        )
+    }
+
+    /// Verify a signature of the peer with the leaf certificate of the peer
+    /// certificate chain in slot_id, or with the provisioned peer public key
+    /// for SPDM_PUBLIC_KEY_SLOT_ID.
+    pub fn verify_peer_signature(
+        &self,
+        slot_id: u8,
+        sign_context: &[u8],
+        data: &[u8],
+        signature: &SpdmSignatureStruct,
+    ) -> SpdmResult {
+        if slot_id == SPDM_PUBLIC_KEY_SLOT_ID {
+            if self.negotiate_info.ext_asym_sel.is_some()
+                || !self.negotiate_info.pqc_asym_sel.is_empty()
+            {
+                return Err(SPDM_STATUS_UNSUPPORTED_CAP);
+            }
+            let public_key = self
+                .provision_info
+                .peer_public_key
+                .as_ref()
+                .ok_or(SPDM_STATUS_INVALID_PARAMETER)?;
+            self.verify_base_asym_signature_with_public_key(
+                public_key.as_ref(),
+                sign_context,
+                data,
+                signature,
+            )
+        } else {
+            let cert_chain = self
+                .get_peer_cert_chain_or_public_key(slot_id)
+                .ok_or(SPDM_STATUS_INVALID_PARAMETER)?;
+            // skip Length, Reserved and RootHash
+            let header_size = 4usize + self.negotiate_info.base_hash_sel.get_size() as usize;
+            if cert_chain.len() <= header_size {
+                return Err(SPDM_STATUS_INVALID_PARAMETER);
+            }
+            if !self.negotiate_info.pqc_asym_sel.is_empty() {
+                return crypto::asym_verify::pqc_verify(
+                    self.negotiate_info.base_hash_sel,
+                    self.negotiate_info.pqc_asym_sel,
+                    &cert_chain[header_size..],
+                    data,
+                    signature,
+                );
+            }
+            if let Some(ext_asym_sel) = self.negotiate_info.ext_asym_sel {
+                return crypto::ext_asym::verify(
+                    self.negotiate_info.base_hash_sel,
+                    ext_asym_sel,
+                    &cert_chain[header_size..],
+                    data,
+                    signature,
+                );
+            }
+            self.verify_base_asym_signature(
+                &cert_chain[header_size..],
+                sign_context,
+                data,
+                signature,
+            )
+        }
+    }
+
+    /// Sign data with the base asymmetric algorithm and the key pair
+    /// key_pair_id. sign_context is the signing context string of the
+    /// signature, used as the SM2 signing ID.
+    pub fn sign_with_base_asym(
+        &self,
+        key_pair_id: u8,
+        sign_context: &[u8],
+        data: &[u8],
+    ) -> crate::secret::SpdmSecretResult<SpdmSignatureStruct> {
+        if self.negotiate_info.base_asym_sel == SpdmBaseAsymAlgo::TPM_ALG_SM2_ECC_SM2_P256 {
+            return crate::secret::sm2_sign::sign(
+                self.negotiate_info.base_hash_sel,
+                key_pair_id,
+                self.get_sm2_id(sign_context),
+                data,
+            );
+        }
+        crate::secret::asym_sign::sign(
+            self.negotiate_info.base_hash_sel,
+            self.negotiate_info.base_asym_sel,
+            key_pair_id,
+            data,
+        )
+    }
+
+    /// Sign data with the key pair bound to the local slot_id, using the
+    /// PQC or extended asymmetric algorithm if one is selected.
+    pub fn sign_with_my_key_pair(
+        &self,
+        slot_id: u8,
+        sign_context: &[u8],
+        data: &[u8],
+    ) -> crate::secret::SpdmSecretResult<SpdmSignatureStruct> {
+        let key_pair_id = self.get_my_key_pair_id(slot_id);
+        if !self.negotiate_info.pqc_asym_sel.is_empty() {
+            return crate::secret::pqc_asym_sign::sign(
+                self.negotiate_info.base_hash_sel,
+                self.negotiate_info.pqc_asym_sel,
+                key_pair_id,
+                data,
+            );
+        }
+        match self.negotiate_info.ext_asym_sel {
+            Some(ext_asym_sel) => crate::secret::ext_asym_sign::sign(
+                self.negotiate_info.base_hash_sel,
+                ext_asym_sel,
+                key_pair_id,
+                data,
+            ),
+            None => self.sign_with_base_asym(key_pair_id, sign_context, data),
+        }
+    }
+
     pub fn reset_buffer_via_request_code(
         &mut self,
         opcode: SpdmRequestResponseCode,
@@ -928,6 +1568,9 @@ impl<'a> SpdmContext<'a> {
         if opcode != SpdmRequestResponseCode::SpdmRequestGetMeasurements {
             self.reset_message_m(session_id)
         }
+        if opcode != SpdmRequestResponseCode::SpdmRequestGetEndpointInfo {
+            self.reset_message_e(session_id)
+        }
         match opcode {
             SpdmRequestResponseCode::SpdmRequestGetMeasurements
             | SpdmRequestResponseCode::SpdmRequestKeyExchange
@@ -1036,7 +1679,7 @@ impl<'a> SpdmContext<'a> {
 
 #[derive(Debug, Default)]
 pub struct SpdmConfigInfo {
-    pub spdm_version: [SpdmVersion; MAX_SPDM_VERSION_COUNT],
+    pub spdm_version: &'static [SpdmVersion],
     pub req_capabilities: SpdmRequestCapabilityFlags,
     pub rsp_capabilities: SpdmResponseCapabilityFlags,
     pub req_ct_exponent: u8,
@@ -1050,6 +1693,11 @@ pub struct SpdmConfigInfo {
     pub req_asym_algo: SpdmReqAsymAlgo,
     pub key_schedule_algo: SpdmKeyScheduleAlgo,
     pub opaque_support: SpdmOpaqueSupport,
+    pub mel_specification: SpdmMelSpecification, // spdm 1.3
+    pub pqc_asym_algo: SpdmPqcAsymAlgo,          // spdm 1.4
+    pub kem_algo: SpdmKemAlgo,                   // spdm 1.4
+    pub ext_asym_algo: [Option<SpdmExtAlgStruct>; SPDM_MAX_EXT_ALG_COUNT], // in priority order
+    pub ext_hash_algo: [Option<SpdmExtAlgStruct>; SPDM_MAX_EXT_ALG_COUNT], // advertised only, never selected
     pub session_policy: u8,
     pub runtime_content_change_support: bool,
     pub data_transfer_size: u32,
@@ -1058,7 +1706,7 @@ pub struct SpdmConfigInfo {
     pub secure_spdm_version: [u8; MAX_SECURE_SPDM_VERSION_COUNT], // used by responder only
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct SpdmNegotiateInfo {
     pub spdm_version_sel: SpdmVersion,
     pub req_capabilities_sel: SpdmRequestCapabilityFlags,
@@ -1074,6 +1722,13 @@ pub struct SpdmNegotiateInfo {
     pub req_asym_sel: SpdmReqAsymAlgo,
     pub key_schedule_sel: SpdmKeyScheduleAlgo,
     pub opaque_data_support: SpdmOpaqueSupport,
+    pub mel_specification_sel: SpdmMelSpecification, // spdm 1.3
+    pub ext_asym_sel: Option<SpdmExtAlgStruct>,      // replaces base_asym_sel when selected
+    pub ext_hash_sel: Option<SpdmExtAlgStruct>,
+    pub pqc_asym_sel: SpdmPqcAsymAlgo, // spdm 1.4, replaces base_asym_sel when selected
+    pub kem_sel: SpdmKemAlgo,          // spdm 1.4, replaces dhe_sel when selected
+    pub local_multi_key_conn: bool, // spdm 1.3, the local certificate slots are bound to key pairs
+    pub peer_multi_key_conn: bool,  // spdm 1.3, the peer certificate slots are bound to key pairs
     pub termination_policy_set: bool, // used by responder to take action when code or configuration changed.
     pub req_data_transfer_size_sel: u32, // spdm 1.2
     pub req_max_spdm_msg_size_sel: u32, // spdm 1.2
@@ -1081,9 +1736,114 @@ pub struct SpdmNegotiateInfo {
     pub rsp_max_spdm_msg_size_sel: u32, // spdm 1.2
 }
 
-const MAX_MANAGED_BUFFER_A_SIZE: usize = 150 + 2 * MAX_SPDM_VERSION_COUNT;
+/// The negotiated state and message A (VCA) of a connection with a responder
+/// supporting CACHE_CAP. It is kept by both sides across a reset, so that the
+/// requester can resume with GET_DIGESTS, CHALLENGE or KEY_EXCHANGE directly.
+#[derive(Debug, Clone, Default)]
+pub struct SpdmCachedNegotiateState {
+    pub negotiate_info: SpdmNegotiateInfo,
+    pub message_a: ManagedBufferA,
+}
+
+impl Codec for SpdmCachedNegotiateState {
+    fn encode(&self, bytes: &mut Writer) -> Result<usize, codec::EncodeErr> {
+        let info = &self.negotiate_info;
+        let mut cnt = 0usize;
+        cnt += info.spdm_version_sel.encode(bytes)?;
+        cnt += info.req_capabilities_sel.encode(bytes)?;
+        cnt += info.rsp_capabilities_sel.encode(bytes)?;
+        cnt += info.req_ct_exponent_sel.encode(bytes)?;
+        cnt += info.rsp_ct_exponent_sel.encode(bytes)?;
+        cnt += info.measurement_specification_sel.encode(bytes)?;
+        cnt += info.measurement_hash_sel.encode(bytes)?;
+        cnt += info.base_hash_sel.encode(bytes)?;
+        cnt += info.base_asym_sel.encode(bytes)?;
+        cnt += info.dhe_sel.encode(bytes)?;
+        cnt += info.aead_sel.encode(bytes)?;
+        cnt += info.req_asym_sel.encode(bytes)?;
+        cnt += info.key_schedule_sel.encode(bytes)?;
+        cnt += info.opaque_data_support.encode(bytes)?;
+        cnt += info.mel_specification_sel.encode(bytes)?;
+        for ext_sel in [&info.ext_asym_sel, &info.ext_hash_sel] {
+            match ext_sel {
+                Some(ext_sel) => {
+                    cnt += 1u8.encode(bytes)?;
+                    cnt += ext_sel.encode(bytes)?;
+                }
+                None => cnt += 0u8.encode(bytes)?,
+            }
+        }
+        cnt += info.pqc_asym_sel.encode(bytes)?;
+        cnt += info.kem_sel.encode(bytes)?;
+        cnt += (info.local_multi_key_conn as u8).encode(bytes)?;
+        cnt += (info.peer_multi_key_conn as u8).encode(bytes)?;
+        cnt += (info.termination_policy_set as u8).encode(bytes)?;
+        cnt += info.req_data_transfer_size_sel.encode(bytes)?;
+        cnt += info.req_max_spdm_msg_size_sel.encode(bytes)?;
+        cnt += info.rsp_data_transfer_size_sel.encode(bytes)?;
+        cnt += info.rsp_max_spdm_msg_size_sel.encode(bytes)?;
+
+        let message_a = self.message_a.as_ref();
+        cnt += (message_a.len() as u16).encode(bytes)?;
+        for d in message_a {
+            cnt += d.encode(bytes)?;
+        }
+        Ok(cnt)
+    }
+
+    fn read(r: &mut Reader) -> Option<SpdmCachedNegotiateState> {
+        let negotiate_info = SpdmNegotiateInfo {
+            spdm_version_sel: SpdmVersion::read(r)?,
+            req_capabilities_sel: SpdmRequestCapabilityFlags::read(r)?,
+            rsp_capabilities_sel: SpdmResponseCapabilityFlags::read(r)?,
+            req_ct_exponent_sel: u8::read(r)?,
+            rsp_ct_exponent_sel: u8::read(r)?,
+            measurement_specification_sel: SpdmMeasurementSpecification::read(r)?,
+            measurement_hash_sel: SpdmMeasurementHashAlgo::read(r)?,
+            base_hash_sel: SpdmBaseHashAlgo::read(r)?,
+            base_asym_sel: SpdmBaseAsymAlgo::read(r)?,
+            dhe_sel: SpdmDheAlgo::read(r)?,
+            aead_sel: SpdmAeadAlgo::read(r)?,
+            req_asym_sel: SpdmReqAsymAlgo::read(r)?,
+            key_schedule_sel: SpdmKeyScheduleAlgo::read(r)?,
+            opaque_data_support: SpdmOpaqueSupport::read(r)?,
+            mel_specification_sel: SpdmMelSpecification::read(r)?,
+            ext_asym_sel: read_optional_ext_alg(r)?,
+            ext_hash_sel: read_optional_ext_alg(r)?,
+            pqc_asym_sel: SpdmPqcAsymAlgo::read(r)?,
+            kem_sel: SpdmKemAlgo::read(r)?,
+            local_multi_key_conn: u8::read(r)? != 0,
+            peer_multi_key_conn: u8::read(r)? != 0,
+            termination_policy_set: u8::read(r)? != 0,
+            req_data_transfer_size_sel: u32::read(r)?,
+            req_max_spdm_msg_size_sel: u32::read(r)?,
+            rsp_data_transfer_size_sel: u32::read(r)?,
+            rsp_max_spdm_msg_size_sel: u32::read(r)?,
+        };
+
+        let message_a_size = u16::read(r)? as usize;
+        let mut message_a = ManagedBufferA::default();
+        message_a.append_message(r.take(message_a_size)?)?;
+
+        Some(SpdmCachedNegotiateState {
+            negotiate_info,
+            message_a,
+        })
+    }
+}
+
+fn read_optional_ext_alg(r: &mut Reader) -> Option<Option<SpdmExtAlgStruct>> {
+    match u8::read(r)? {
+        0 => Some(None),
+        1 => Some(Some(SpdmExtAlgStruct::read(r)?)),
+        _ => None,
+    }
+}
+
+const MAX_MANAGED_BUFFER_A_SIZE: usize =
+    158 + 2 * MAX_SPDM_VERSION_COUNT + 4 * (SPDM_MAX_EXT_ALG_COUNT + 2);
 const MAX_MANAGED_BUFFER_B_SIZE: usize =
-    24 + SPDM_MAX_HASH_SIZE * SPDM_MAX_SLOT_NUMBER + config::MAX_SPDM_CERT_CHAIN_DATA_SIZE;
+    24 + (SPDM_MAX_HASH_SIZE + 4) * SPDM_MAX_SLOT_NUMBER + config::MAX_SPDM_CERT_CHAIN_DATA_SIZE;
 const MAX_MANAGED_BUFFER_C_SIZE: usize =
     78 + SPDM_MAX_HASH_SIZE * 2 + SPDM_MAX_ASYM_KEY_SIZE + MAX_SPDM_OPAQUE_SIZE;
 const MAX_MANAGED_BUFFER_M_SIZE: usize = 47
@@ -1091,6 +1851,7 @@ const MAX_MANAGED_BUFFER_M_SIZE: usize = 47
     + config::MAX_SPDM_MEASUREMENT_RECORD_SIZE
     + SPDM_MAX_ASYM_KEY_SIZE
     + MAX_SPDM_OPAQUE_SIZE;
+const MAX_MANAGED_BUFFER_E_SIZE: usize = 52 + SPDM_NONCE_SIZE * 2 + SPDM_MAX_ENDPOINT_INFO_SIZE;
 const MAX_MANAGED_BUFFER_K_SIZE: usize = 84
     + SPDM_MAX_DHE_KEY_SIZE * 2
     + SPDM_MAX_HASH_SIZE * 2
@@ -1100,6 +1861,7 @@ const MAX_MANAGED_BUFFER_F_SIZE: usize = 8 + SPDM_MAX_HASH_SIZE * 2 + SPDM_MAX_A
 const MAX_MANAGED_BUFFER_M1M2_SIZE: usize =
     MAX_MANAGED_BUFFER_A_SIZE + MAX_MANAGED_BUFFER_B_SIZE + MAX_MANAGED_BUFFER_C_SIZE;
 const MAX_MANAGED_BUFFER_L1L2_SIZE: usize = MAX_MANAGED_BUFFER_A_SIZE + MAX_MANAGED_BUFFER_M_SIZE;
+const MAX_MANAGED_BUFFER_IL1IL2_SIZE: usize = MAX_MANAGED_BUFFER_A_SIZE + MAX_MANAGED_BUFFER_E_SIZE;
 const MAX_MANAGED_BUFFER_TH_SIZE: usize = MAX_MANAGED_BUFFER_A_SIZE
     + config::MAX_SPDM_CERT_CHAIN_DATA_SIZE
     + MAX_MANAGED_BUFFER_K_SIZE
@@ -1224,6 +1986,34 @@ impl Default for ManagedBufferM {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct ManagedBufferE(usize, [u8; MAX_MANAGED_BUFFER_E_SIZE]);
+
+impl ManagedBufferE {
+    pub fn append_message(&mut self, bytes: &[u8]) -> Option<usize> {
+        let used = self.0;
+        let mut writer = Writer::init(&mut self.1[used..]);
+        let write_len = writer.extend_from_slice(bytes)?;
+        self.0 = used + write_len;
+        Some(writer.used())
+    }
+    pub fn reset_message(&mut self) {
+        self.0 = 0;
+    }
+}
+
+impl AsRef<[u8]> for ManagedBufferE {
+    fn as_ref(&self) -> &[u8] {
+        &self.1[0..self.0]
+    }
+}
+
+impl Default for ManagedBufferE {
+    fn default() -> Self {
+        ManagedBufferE(0usize, [0u8; MAX_MANAGED_BUFFER_E_SIZE])
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ManagedBufferK(usize, [u8; MAX_MANAGED_BUFFER_K_SIZE]);
 
@@ -1336,6 +2126,34 @@ impl Default for ManagedBufferL1L2 {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct ManagedBufferIL1IL2(usize, [u8; MAX_MANAGED_BUFFER_IL1IL2_SIZE]);
+
+impl ManagedBufferIL1IL2 {
+    pub fn append_message(&mut self, bytes: &[u8]) -> Option<usize> {
+        let used = self.0;
+        let mut writer = Writer::init(&mut self.1[used..]);
+        let write_len = writer.extend_from_slice(bytes)?;
+        self.0 = used + write_len;
+        Some(writer.used())
+    }
+    pub fn reset_message(&mut self) {
+        self.0 = 0;
+    }
+}
+
+impl AsRef<[u8]> for ManagedBufferIL1IL2 {
+    fn as_ref(&self) -> &[u8] {
+        &self.1[0..self.0]
+    }
+}
+
+impl Default for ManagedBufferIL1IL2 {
+    fn default() -> Self {
+        ManagedBufferIL1IL2(0usize, [0u8; MAX_MANAGED_BUFFER_IL1IL2_SIZE])
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ManagedBufferTH(usize, [u8; MAX_MANAGED_BUFFER_TH_SIZE]);
 
@@ -1410,10 +2228,16 @@ pub struct SpdmRuntimeInfo {
     peer_used_cert_chain_slot_id: u8,
     pub need_measurement_summary_hash: bool,
     pub need_measurement_signature: bool,
+    pub need_endpoint_info_signature: bool,
     pub message_a: ManagedBufferA,
     pub message_b: ManagedBufferB,
     pub message_c: ManagedBufferC,
+    #[cfg(feature = "mut-auth")]
+    pub message_mut_b: ManagedBufferB, // for encapsulated GET_DIGESTS/GET_CERTIFICATE out of session
+    #[cfg(feature = "mut-auth")]
+    pub message_mut_c: ManagedBufferC, // for encapsulated CHALLENGE
     pub message_m: ManagedBufferM,
+    pub message_e: ManagedBufferE,
     pub content_changed: SpdmMeasurementContentChanged, // used by responder, set when content changed and spdm version is 1.2.
                                                         // used by requester, consume when measurement response report content changed.
 }
@@ -1427,9 +2251,13 @@ pub struct SpdmRuntimeInfo {
     peer_used_cert_chain_slot_id: u8,
     pub need_measurement_summary_hash: bool,
     pub need_measurement_signature: bool,
+    pub need_endpoint_info_signature: bool,
     pub message_a: ManagedBufferA,
     pub digest_context_m1m2: Option<SpdmHashCtx>, // for M1/M2
+    #[cfg(feature = "mut-auth")]
+    pub digest_context_mut_m1m2: Option<SpdmHashCtx>, // for mutual M1/M2 of encapsulated CHALLENGE
     pub digest_context_l1l2: Option<SpdmHashCtx>, // for out of session get measurement/measurement
+    pub digest_context_il1il2: Option<SpdmHashCtx>, // for out of session get endpoint info/endpoint info
     pub content_changed: SpdmMeasurementContentChanged, // used by responder, set when content changed and spdm version is 1.2.
                                                         // used by requester, consume when measurement response report content changed.
 }
@@ -1473,18 +2301,84 @@ pub struct SpdmProvisionInfo {
     pub my_cert_chain_data: [Option<SpdmCertChainData>; SPDM_MAX_SLOT_NUMBER],
     pub my_cert_chain: [Option<SpdmCertChainBuffer>; SPDM_MAX_SLOT_NUMBER],
     pub peer_root_cert_data: Option<SpdmCertChainData>,
+    // Provisioned public keys, used in place of certificate chains with
+    // SlotID SPDM_PUBLIC_KEY_SLOT_ID when PUB_KEY_ID_CAP is negotiated.
+    pub my_public_key: Option<SpdmPublicKeyData>,
+    pub peer_public_key: Option<SpdmPublicKeyData>,
+    // The key pair each local certificate slot is bound to,
+    // SPDM_DEFAULT_KEY_PAIR_ID when the slot uses the default key.
+    pub my_key_pair_id: [u8; SPDM_MAX_SLOT_NUMBER],
 }
 
 #[derive(Default)]
 pub struct SpdmPeerInfo {
     pub peer_cert_chain: [Option<SpdmCertChainBuffer>; SPDM_MAX_SLOT_NUMBER],
     pub peer_cert_chain_temp: Option<SpdmCertChainBuffer>,
+    // Reported in DIGESTS when the peer uses a multi-key connection.
+    pub peer_key_pair_id: [u8; SPDM_MAX_SLOT_NUMBER],
+    pub peer_key_usage_mask: [SpdmKeyUsageMask; SPDM_MAX_SLOT_NUMBER],
+    // The certificate model of each slot, 0 when not reported.
+    pub peer_certificate_info: [u8; SPDM_MAX_SLOT_NUMBER],
+}
+
+enum_builder! {
+    @U8
+    EnumName: SpdmChunkStatus;
+    EnumVal{
+        // No large message transfer in progress
+        SpdmChunkIdle => 0x0,
+        // Responder is collecting a large request via CHUNK_SEND
+        SpdmChunkSendInProgress => 0x1,
+        // Responder is processing the reassembled large request,
+        // its response goes into the last CHUNK_SEND_ACK
+        SpdmChunkSendAckPending => 0x2,
+        // Responder holds a large response to be retrieved via CHUNK_GET
+        SpdmChunkGetInProgress => 0x3,
+        // Requester holds the response carried by the last CHUNK_SEND_ACK
+        SpdmChunkResponsePending => 0x4
+    }
+}
+impl Default for SpdmChunkStatus {
+    fn default() -> SpdmChunkStatus {
+        SpdmChunkStatus::SpdmChunkIdle
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SpdmChunkContext {
+    pub chunk_status: SpdmChunkStatus,
+    pub chunk_handle: u8,
+    pub chunk_seq_num: u16,
+    pub chunk_message_size: usize,
+    pub chunk_message_offset: usize,
+    pub chunk_message_data: [u8; config::MAX_SPDM_MSG_SIZE],
+}
+
+impl Default for SpdmChunkContext {
+    fn default() -> SpdmChunkContext {
+        SpdmChunkContext {
+            chunk_status: SpdmChunkStatus::SpdmChunkIdle,
+            chunk_handle: 0,
+            chunk_seq_num: 0,
+            chunk_message_size: 0,
+            chunk_message_offset: 0,
+            chunk_message_data: [0u8; config::MAX_SPDM_MSG_SIZE],
+        }
+    }
 }
 
 #[cfg(feature = "mut-auth")]
 #[derive(Default)]
 pub struct SpdmEncapContext {
+    // Set by the responder to ask for basic mutual authentication in CHALLENGE_AUTH.
+    // The requester certificate is then retrieved and challenged out of session.
+    pub basic_mut_auth_requested: bool,
     pub req_slot_id: u8,
     pub request_id: u8,
     pub encap_cert_size: u16,
+    // Set by the responder to rotate the session keys with an encapsulated KEY_UPDATE.
+    // It moves to VerifyNewKey once the new key is acknowledged by the requester.
+    pub key_update_op: Option<crate::message::SpdmKeyUpdateOperation>,
+    // The new key is derived right after the encapsulated KEY_UPDATE is sent.
+    pub key_update_pending: bool,
 }
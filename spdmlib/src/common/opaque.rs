@@ -606,6 +606,7 @@ bitflags! {
     #[derive(Default)]
     pub struct SpdmOpaqueSupport: u8 {
         const OPAQUE_DATA_FMT1 = 0b0000_0010;
+        const MULTI_KEY_CONN = 0b0010_0000; // spdm 1.3
         const VALID_MASK = Self::OPAQUE_DATA_FMT1.bits;
     }
 }
@@ -625,8 +626,10 @@ impl Codec for SpdmOpaqueSupport {
 impl SpdmOpaqueSupport {
     /// return true if no more than one is selected
     /// return false if two or more is selected
+    /// MULTI_KEY_CONN is not an opaque data format and is ignored here
     pub fn is_no_more_than_one_selected(&self) -> bool {
-        self.bits() == 0 || self.bits() & (self.bits() - 1) == 0
+        let bits = self.bits() & !Self::MULTI_KEY_CONN.bits;
+        bits == 0 || bits & (bits - 1) == 0
     }
 
     pub fn is_valid(&self) -> bool {
@@ -12,6 +12,10 @@ use crate::error::SPDM_STATUS_DECODE_AEAD_FAIL;
 use crate::error::SPDM_STATUS_INVALID_STATE_LOCAL;
 use crate::error::SPDM_STATUS_SEQUENCE_NUMBER_OVERFLOW;
 use crate::message::SpdmKeyExchangeMutAuthAttributes;
+use crate::message::{
+    SpdmEventData, SpdmEventGroup, SpdmEventGroupId, MAX_SPDM_EVENT_COUNT,
+    MAX_SPDM_EVENT_GROUP_COUNT,
+};
 
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
@@ -96,6 +100,7 @@ pub struct SpdmSessionRuntimeInfo {
     pub message_k: ManagedBufferK,
     pub message_f: ManagedBufferF,
     pub message_m: ManagedBufferM,
+    pub message_e: ManagedBufferE,
 }
 
 #[derive(Clone, Default)]
@@ -108,6 +113,27 @@ pub struct SpdmSessionRuntimeInfo {
     pub req_cert_hash: Option<SpdmDigestStruct>,
     pub digest_context_th: Option<SpdmHashCtx>,
     pub digest_context_l1l2: Option<SpdmHashCtx>,
+    pub digest_context_il1il2: Option<SpdmHashCtx>,
+}
+
+// spdm 1.3: event subscription of the session and the events pending delivery.
+#[derive(Debug, Clone, Default)]
+pub struct SpdmSessionEventInfo {
+    pub subscribed_group_count: u8,
+    pub subscribed_groups: [SpdmEventGroup; MAX_SPDM_EVENT_GROUP_COUNT],
+    pub event_count: u32,
+    pub events: [SpdmEventData; MAX_SPDM_EVENT_COUNT],
+    pub sent_event_count: u32,
+    pub next_event_instance_id: u32,
+}
+
+impl SpdmSessionEventInfo {
+    pub fn is_subscribed(&self, group_id: &SpdmEventGroupId, event_type_id: u16) -> bool {
+        self.subscribed_groups
+            .iter()
+            .take(self.subscribed_group_count as usize)
+            .any(|g| g.group_id == *group_id && g.contains_event_type(event_type_id))
+    }
 }
 
 #[derive(Clone)]
@@ -127,6 +153,7 @@ pub struct SpdmSession {
     slot_id: u8,
     pub heartbeat_period: u8, // valid only when HEARTBEAT cap set
     pub secure_spdm_version_sel: u8,
+    pub event_info: SpdmSessionEventInfo,
 }
 
 impl Default for SpdmSession {
@@ -152,6 +179,7 @@ impl SpdmSession {
             slot_id: 0,
             heartbeat_period: 0,
             secure_spdm_version_sel: DMTF_SECURE_SPDM_VERSION_11,
+            event_info: SpdmSessionEventInfo::default(),
             mut_auth_requested: SpdmKeyExchangeMutAuthAttributes::default(),
         }
     }
@@ -187,6 +215,7 @@ impl SpdmSession {
         self.heartbeat_period = 0;
         self.secure_spdm_version_sel = DMTF_SECURE_SPDM_VERSION_11;
         self.mut_auth_requested = SpdmKeyExchangeMutAuthAttributes::empty();
+        self.event_info = SpdmSessionEventInfo::default();
     }
 
     pub fn get_session_id(&self) -> u32 {
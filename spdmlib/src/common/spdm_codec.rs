@@ -169,13 +169,27 @@ impl SpdmCodec for SpdmDheExchangeStruct {
         Ok(self.data_size as usize)
     }
     fn spdm_read(context: &mut SpdmContext, r: &mut Reader) -> Option<SpdmDheExchangeStruct> {
-        let data_size = context.get_dhe_key_size();
-        let mut data = [0u8; SPDM_MAX_DHE_KEY_SIZE];
-        for d in data.iter_mut().take(data_size as usize) {
-            *d = u8::read(r)?;
-        }
-        Some(SpdmDheExchangeStruct { data_size, data })
+        read_dhe_exchange(context.get_dhe_key_size(), r)
+    }
+}
+
+impl SpdmDheExchangeStruct {
+    /// Read the ExchangeData of KEY_EXCHANGE_RSP, which is the cipher text
+    /// instead of a public key when a KEM is selected.
+    pub fn spdm_read_rsp(
+        context: &mut SpdmContext,
+        r: &mut Reader,
+    ) -> Option<SpdmDheExchangeStruct> {
+        read_dhe_exchange(context.get_rsp_dhe_key_size(), r)
+    }
+}
+
+fn read_dhe_exchange(data_size: u16, r: &mut Reader) -> Option<SpdmDheExchangeStruct> {
+    let mut data = [0u8; SPDM_MAX_DHE_KEY_SIZE];
+    for d in data.iter_mut().take(data_size as usize) {
+        *d = u8::read(r)?;
     }
+    Some(SpdmDheExchangeStruct { data_size, data })
 }
 
 impl SpdmCodec for SpdmDmtfMeasurementStructure {
@@ -9,9 +9,9 @@ use alloc::boxed::Box;
 
 use crate::protocol::{
     SpdmAeadAlgo, SpdmAeadIvStruct, SpdmAeadKeyStruct, SpdmBaseAsymAlgo, SpdmBaseHashAlgo,
-    SpdmDheAlgo, SpdmDheExchangeStruct, SpdmDheFinalKeyStruct, SpdmDigestStruct,
+    SpdmDheAlgo, SpdmDheExchangeStruct, SpdmDheFinalKeyStruct, SpdmDigestStruct, SpdmExtAlgStruct,
     SpdmHkdfInputKeyingMaterial, SpdmHkdfOutputKeyingMaterial, SpdmHkdfPseudoRandomKey,
-    SpdmSignatureStruct,
+    SpdmKemAlgo, SpdmPqcAsymAlgo, SpdmSignatureStruct,
 };
 
 #[cfg(not(feature = "hashed-transcript-data"))]
@@ -79,6 +79,50 @@ pub struct SpdmAsymVerify {
         data: &[u8],
         signature: &SpdmSignatureStruct,
     ) -> SpdmResult,
+    pub verify_with_public_key_cb: fn(
+        base_hash_algo: SpdmBaseHashAlgo,
+        base_asym_algo: SpdmBaseAsymAlgo,
+        public_key_der: &[u8],
+        data: &[u8],
+        signature: &SpdmSignatureStruct,
+    ) -> SpdmResult,
+    pub pqc_verify_cb: fn(
+        base_hash_algo: SpdmBaseHashAlgo,
+        pqc_asym_algo: SpdmPqcAsymAlgo,
+        public_cert_der: &[u8],
+        data: &[u8],
+        signature: &SpdmSignatureStruct,
+    ) -> SpdmResult,
+}
+
+type Sm2VerifyCb = fn(
+    base_hash_algo: SpdmBaseHashAlgo,
+    sm2_id: &[u8],
+    public_der: &[u8],
+    data: &[u8],
+    signature: &SpdmSignatureStruct,
+) -> SpdmResult;
+
+/// SM2 signature verification, with the signing ID of GB/T 32918.2.
+#[derive(Clone)]
+pub struct SpdmSm2Verify {
+    /// public_der is the certificate chain of the signer.
+    pub verify_cb: Sm2VerifyCb,
+
+    /// public_der is the DER encoded SubjectPublicKeyInfo of the signer.
+    pub verify_with_public_key_cb: Sm2VerifyCb,
+}
+
+#[derive(Clone)]
+pub struct SpdmExtAsym {
+    pub get_signature_size_cb: fn(ext_asym_algo: SpdmExtAlgStruct) -> u16,
+    pub verify_cb: fn(
+        base_hash_algo: SpdmBaseHashAlgo,
+        ext_asym_algo: SpdmExtAlgStruct,
+        public_cert_der: &[u8],
+        data: &[u8],
+        signature: &SpdmSignatureStruct,
+    ) -> SpdmResult,
 }
 
 #[derive(Clone)]
@@ -98,19 +142,41 @@ pub struct SpdmHkdf {
 
 type GetCertFromCertChainCb = fn(cert_chain: &[u8], index: isize) -> SpdmResult<(usize, usize)>;
 
+// The leaf shall allow the EKU of its role, the requester or the responder.
+// Only in the alias certificate model may the chain be partial, ending at
+// the device certificate.
+type VerifyCertChainCb =
+    fn(cert_chain: &[u8], is_requester_cert: bool, is_alias_cert_model: bool) -> SpdmResult;
+
 #[derive(Clone)]
 pub struct SpdmCertOperation {
     pub get_cert_from_cert_chain_cb: GetCertFromCertChainCb,
 
-    pub verify_cert_chain_cb: fn(cert_chain: &[u8]) -> SpdmResult,
+    pub verify_cert_chain_cb: VerifyCertChainCb,
 }
 
 type GenerateKeyPairCb =
     fn(dhe_algo: SpdmDheAlgo) -> Option<(SpdmDheExchangeStruct, Box<dyn SpdmDheKeyExchange>)>;
 
+type KemGenerateKeyPairCb =
+    fn(kem_algo: SpdmKemAlgo) -> Option<(SpdmDheExchangeStruct, Box<dyn SpdmDheKeyExchange>)>;
+
+type KemEncapsulateCb = fn(
+    kem_algo: SpdmKemAlgo,
+    peer_encap_key: &SpdmDheExchangeStruct,
+) -> Option<(SpdmDheExchangeStruct, SpdmDheFinalKeyStruct)>;
+
 #[derive(Clone)]
 pub struct SpdmDhe {
     pub generate_key_pair_cb: GenerateKeyPairCb,
+
+    /// The requester generates an encapsulation key. compute_final_key of the
+    /// returned key exchange decapsulates the cipher text of the responder.
+    pub kem_generate_key_pair_cb: KemGenerateKeyPairCb,
+
+    /// The responder encapsulates a shared secret to the encapsulation key of
+    /// the requester, and returns the cipher text and the shared secret.
+    pub kem_encapsulate_cb: KemEncapsulateCb,
 }
 
 pub trait SpdmDheKeyExchange {
@@ -5,8 +5,9 @@
 use super::aead::{decrypt, encrypt};
 #[cfg(feature = "hashed-transcript-data")]
 use super::hash;
-#[cfg(feature = "hashed-transcript-data")]
-use crate::protocol::SpdmBaseHashAlgo;
+use super::sm2_verify;
+use crate::error::SPDM_STATUS_UNSUPPORTED_CAP;
+use crate::protocol::{SpdmBaseHashAlgo, SpdmSignatureStruct};
 use crate::{
     protocol::SpdmAeadAlgo,
     protocol::{
@@ -153,6 +154,27 @@ fn test_case_chacha20_poly1305() {
     assert_eq!(out_plain_text_len, plain_text.len());
 }
 
+#[test]
+fn test_case_sm2_verify_unsupported() {
+    // No SM2 verifier is registered in this test binary.
+    assert!(!sm2_verify::is_supported());
+    let signature = SpdmSignatureStruct::default();
+    assert_eq!(
+        sm2_verify::verify(SpdmBaseHashAlgo::TPM_ALG_SM3_256, b"", b"", b"", &signature),
+        Err(SPDM_STATUS_UNSUPPORTED_CAP)
+    );
+    assert_eq!(
+        sm2_verify::verify_with_public_key(
+            SpdmBaseHashAlgo::TPM_ALG_SM3_256,
+            b"",
+            b"",
+            b"",
+            &signature
+        ),
+        Err(SPDM_STATUS_UNSUPPORTED_CAP)
+    );
+}
+
 fn from_hex(hex_str: &str) -> Result<Vec<u8>, String> {
     if hex_str.len() % 2 != 0 {
         return Err(String::from(
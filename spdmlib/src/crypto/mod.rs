@@ -12,7 +12,7 @@ mod spdm_ring;
 
 pub use crypto_callbacks::{
     SpdmAead, SpdmAsymVerify, SpdmCertOperation, SpdmCryptoRandom, SpdmDhe, SpdmDheKeyExchange,
-    SpdmHash, SpdmHkdf, SpdmHmac,
+    SpdmExtAsym, SpdmHash, SpdmHkdf, SpdmHmac, SpdmSm2Verify,
 };
 
 #[cfg(feature = "hashed-transcript-data")]
@@ -24,6 +24,8 @@ static CRYPTO_HASH: OnceCell<SpdmHash> = OnceCell::uninit();
 static CRYPTO_HMAC: OnceCell<SpdmHmac> = OnceCell::uninit();
 static CRYPTO_AEAD: OnceCell<SpdmAead> = OnceCell::uninit();
 static CRYPTO_ASYM_VERIFY: OnceCell<SpdmAsymVerify> = OnceCell::uninit();
+static CRYPTO_SM2_VERIFY: OnceCell<SpdmSm2Verify> = OnceCell::uninit();
+static CRYPTO_EXT_ASYM: OnceCell<SpdmExtAsym> = OnceCell::uninit();
 static CRYPTO_DHE: OnceCell<SpdmDhe> = OnceCell::uninit();
 static CRYPTO_CERT_OPERATION: OnceCell<SpdmCertOperation> = OnceCell::uninit();
 static CRYPTO_HKDF: OnceCell<SpdmHkdf> = OnceCell::uninit();
@@ -199,7 +201,9 @@ pub mod asym_verify {
     use super::CRYPTO_ASYM_VERIFY;
     use crate::crypto::SpdmAsymVerify;
     use crate::error::{SpdmResult, SPDM_STATUS_INVALID_STATE_LOCAL};
-    use crate::protocol::{SpdmBaseAsymAlgo, SpdmBaseHashAlgo, SpdmSignatureStruct};
+    use crate::protocol::{
+        SpdmBaseAsymAlgo, SpdmBaseHashAlgo, SpdmPqcAsymAlgo, SpdmSignatureStruct,
+    };
 
     #[cfg(not(any(feature = "spdm-ring")))]
     static DEFAULT: SpdmAsymVerify = SpdmAsymVerify {
@@ -209,6 +213,18 @@ pub mod asym_verify {
                     _data: &[u8],
                     _signature: &SpdmSignatureStruct|
          -> SpdmResult { unimplemented!() },
+        verify_with_public_key_cb: |_base_hash_algo: SpdmBaseHashAlgo,
+                                    _base_asym_algo: SpdmBaseAsymAlgo,
+                                    _public_key_der: &[u8],
+                                    _data: &[u8],
+                                    _signature: &SpdmSignatureStruct|
+         -> SpdmResult { unimplemented!() },
+        pqc_verify_cb: |_base_hash_algo: SpdmBaseHashAlgo,
+                        _pqc_asym_algo: SpdmPqcAsymAlgo,
+                        _public_cert_der: &[u8],
+                        _data: &[u8],
+                        _signature: &SpdmSignatureStruct|
+         -> SpdmResult { unimplemented!() },
     };
 
     #[cfg(feature = "spdm-ring")]
@@ -236,6 +252,168 @@ pub mod asym_verify {
             signature,
         )
     }
+
+    /// public_key_der is the DER encoded SubjectPublicKeyInfo.
+    pub fn verify_with_public_key(
+        base_hash_algo: SpdmBaseHashAlgo,
+        base_asym_algo: SpdmBaseAsymAlgo,
+        public_key_der: &[u8],
+        data: &[u8],
+        signature: &SpdmSignatureStruct,
+    ) -> SpdmResult {
+        (CRYPTO_ASYM_VERIFY
+            .try_get_or_init(|| DEFAULT.clone())
+            .map_err(|_| SPDM_STATUS_INVALID_STATE_LOCAL)?
+            .verify_with_public_key_cb)(
+            base_hash_algo,
+            base_asym_algo,
+            public_key_der,
+            data,
+            signature,
+        )
+    }
+
+    /// Verify a signature of a PQC asymmetric algorithm selected through
+    /// PqcAsymSel (SPDM 1.4).
+    pub fn pqc_verify(
+        base_hash_algo: SpdmBaseHashAlgo,
+        pqc_asym_algo: SpdmPqcAsymAlgo,
+        public_cert_der: &[u8],
+        data: &[u8],
+        signature: &SpdmSignatureStruct,
+    ) -> SpdmResult {
+        (CRYPTO_ASYM_VERIFY
+            .try_get_or_init(|| DEFAULT.clone())
+            .map_err(|_| SPDM_STATUS_INVALID_STATE_LOCAL)?
+            .pqc_verify_cb)(
+            base_hash_algo,
+            pqc_asym_algo,
+            public_cert_der,
+            data,
+            signature,
+        )
+    }
+}
+
+/// SM2 signatures, which sign with an ID. No built-in implementation is
+/// provided.
+pub mod sm2_verify {
+    use super::CRYPTO_SM2_VERIFY;
+    use crate::crypto::SpdmSm2Verify;
+    use crate::error::{SpdmResult, SPDM_STATUS_UNSUPPORTED_CAP};
+    use crate::protocol::{SpdmBaseHashAlgo, SpdmSignatureStruct};
+
+    // SM2 is not supported until a verifier is registered.
+    static DEFAULT: SpdmSm2Verify = SpdmSm2Verify {
+        verify_cb: |_base_hash_algo: SpdmBaseHashAlgo,
+                    _sm2_id: &[u8],
+                    _public_cert_der: &[u8],
+                    _data: &[u8],
+                    _signature: &SpdmSignatureStruct|
+         -> SpdmResult { Err(SPDM_STATUS_UNSUPPORTED_CAP) },
+        verify_with_public_key_cb: |_base_hash_algo: SpdmBaseHashAlgo,
+                                    _sm2_id: &[u8],
+                                    _public_key_der: &[u8],
+                                    _data: &[u8],
+                                    _signature: &SpdmSignatureStruct|
+         -> SpdmResult { Err(SPDM_STATUS_UNSUPPORTED_CAP) },
+    };
+
+    pub fn register(context: SpdmSm2Verify) -> bool {
+        CRYPTO_SM2_VERIFY.try_init_once(|| context).is_ok()
+    }
+
+    /// Whether an SM2 verifier is registered. SM2 is neither offered nor
+    /// selected in NEGOTIATE_ALGORITHMS otherwise.
+    pub fn is_supported() -> bool {
+        CRYPTO_SM2_VERIFY.try_get().is_ok()
+    }
+
+    // The default is not installed, so that it never stands in for a
+    // verifier registered later.
+    fn get() -> &'static SpdmSm2Verify {
+        CRYPTO_SM2_VERIFY.try_get().unwrap_or(&DEFAULT)
+    }
+
+    pub fn verify(
+        base_hash_algo: SpdmBaseHashAlgo,
+        sm2_id: &[u8],
+        public_cert_der: &[u8],
+        data: &[u8],
+        signature: &SpdmSignatureStruct,
+    ) -> SpdmResult {
+        (get().verify_cb)(base_hash_algo, sm2_id, public_cert_der, data, signature)
+    }
+
+    /// public_key_der is the DER encoded SubjectPublicKeyInfo.
+    pub fn verify_with_public_key(
+        base_hash_algo: SpdmBaseHashAlgo,
+        sm2_id: &[u8],
+        public_key_der: &[u8],
+        data: &[u8],
+        signature: &SpdmSignatureStruct,
+    ) -> SpdmResult {
+        (get().verify_with_public_key_cb)(base_hash_algo, sm2_id, public_key_der, data, signature)
+    }
+}
+
+/// Extended (vendor defined) asymmetric algorithms, selected through
+/// ExtAsymSel of ALGORITHMS. No built-in implementation is provided.
+pub mod ext_asym {
+    use super::CRYPTO_EXT_ASYM;
+    use crate::crypto::SpdmExtAsym;
+    use crate::error::{SpdmResult, SPDM_STATUS_INVALID_STATE_LOCAL};
+    use crate::protocol::{
+        SpdmBaseHashAlgo, SpdmExtAlgStruct, SpdmSignatureStruct, SPDM_MAX_ASYM_KEY_SIZE,
+    };
+
+    // No extended algorithm is supported by default.
+    static DEFAULT: SpdmExtAsym = SpdmExtAsym {
+        get_signature_size_cb: |_ext_asym_algo: SpdmExtAlgStruct| -> u16 { 0 },
+        verify_cb: |_base_hash_algo: SpdmBaseHashAlgo,
+                    _ext_asym_algo: SpdmExtAlgStruct,
+                    _public_cert_der: &[u8],
+                    _data: &[u8],
+                    _signature: &SpdmSignatureStruct|
+         -> SpdmResult { unimplemented!() },
+    };
+
+    pub fn register(context: SpdmExtAsym) -> bool {
+        CRYPTO_EXT_ASYM.try_init_once(|| context).is_ok()
+    }
+
+    pub fn get_signature_size(ext_asym_algo: SpdmExtAlgStruct) -> u16 {
+        match CRYPTO_EXT_ASYM.try_get_or_init(|| DEFAULT.clone()) {
+            Ok(ext_asym) => (ext_asym.get_signature_size_cb)(ext_asym_algo),
+            Err(_) => 0,
+        }
+    }
+
+    /// Whether the signature of `ext_asym_algo` has a known size which fits
+    /// in SpdmSignatureStruct.
+    pub fn is_supported(ext_asym_algo: SpdmExtAlgStruct) -> bool {
+        let signature_size = get_signature_size(ext_asym_algo) as usize;
+        signature_size != 0 && signature_size <= SPDM_MAX_ASYM_KEY_SIZE
+    }
+
+    pub fn verify(
+        base_hash_algo: SpdmBaseHashAlgo,
+        ext_asym_algo: SpdmExtAlgStruct,
+        public_cert_der: &[u8],
+        data: &[u8],
+        signature: &SpdmSignatureStruct,
+    ) -> SpdmResult {
+        (CRYPTO_EXT_ASYM
+            .try_get_or_init(|| DEFAULT.clone())
+            .map_err(|_| SPDM_STATUS_INVALID_STATE_LOCAL)?
+            .verify_cb)(
+            base_hash_algo,
+            ext_asym_algo,
+            public_cert_der,
+            data,
+            signature,
+        )
+    }
 }
 
 pub mod dhe {
@@ -244,7 +422,7 @@ pub mod dhe {
 
     use super::CRYPTO_DHE;
     use crate::crypto::{SpdmDhe, SpdmDheKeyExchange};
-    use crate::protocol::{SpdmDheAlgo, SpdmDheExchangeStruct};
+    use crate::protocol::{SpdmDheAlgo, SpdmDheExchangeStruct, SpdmDheFinalKeyStruct, SpdmKemAlgo};
 
     #[cfg(not(any(feature = "spdm-ring")))]
     static DEFAULT: SpdmDhe =
@@ -253,6 +431,16 @@ pub mod dhe {
                 SpdmDheExchangeStruct,
                 Box<dyn SpdmDheKeyExchange>,
             )> { unimplemented!() },
+            kem_generate_key_pair_cb: |_kem_algo: SpdmKemAlgo| -> Option<(
+                SpdmDheExchangeStruct,
+                Box<dyn SpdmDheKeyExchange>,
+            )> { unimplemented!() },
+            kem_encapsulate_cb:
+                |_kem_algo: SpdmKemAlgo,
+                 _peer_encap_key: &SpdmDheExchangeStruct|
+                 -> Option<(SpdmDheExchangeStruct, SpdmDheFinalKeyStruct)> {
+                    unimplemented!()
+                },
         };
     #[cfg(feature = "spdm-ring")]
     use super::spdm_ring::dhe_impl::DEFAULT;
@@ -269,6 +457,25 @@ pub mod dhe {
             .ok()?
             .generate_key_pair_cb)(dhe_algo)
     }
+
+    pub fn kem_generate_key_pair(
+        kem_algo: SpdmKemAlgo,
+    ) -> Option<(SpdmDheExchangeStruct, Box<dyn SpdmDheKeyExchange>)> {
+        (CRYPTO_DHE
+            .try_get_or_init(|| DEFAULT.clone())
+            .ok()?
+            .kem_generate_key_pair_cb)(kem_algo)
+    }
+
+    pub fn kem_encapsulate(
+        kem_algo: SpdmKemAlgo,
+        peer_encap_key: &SpdmDheExchangeStruct,
+    ) -> Option<(SpdmDheExchangeStruct, SpdmDheFinalKeyStruct)> {
+        (CRYPTO_DHE
+            .try_get_or_init(|| DEFAULT.clone())
+            .ok()?
+            .kem_encapsulate_cb)(kem_algo, peer_encap_key)
+    }
 }
 
 pub mod cert_operation {
@@ -281,7 +488,10 @@ pub mod cert_operation {
         get_cert_from_cert_chain_cb: |_cert_chain: &[u8],
                                       _index: isize|
          -> SpdmResult<(usize, usize)> { unimplemented!() },
-        verify_cert_chain_cb: |_cert_chain: &[u8]| -> SpdmResult { unimplemented!() },
+        verify_cert_chain_cb: |_cert_chain: &[u8],
+                               _is_requester_cert: bool,
+                               _is_alias_cert_model: bool|
+         -> SpdmResult { unimplemented!() },
     };
 
     #[cfg(feature = "spdm-ring")]
@@ -298,11 +508,15 @@ pub mod cert_operation {
             .get_cert_from_cert_chain_cb)(cert_chain, index)
     }
 
-    pub fn verify_cert_chain(cert_chain: &[u8]) -> SpdmResult {
+    pub fn verify_cert_chain(
+        cert_chain: &[u8],
+        is_requester_cert: bool,
+        is_alias_cert_model: bool,
+    ) -> SpdmResult {
         (CRYPTO_CERT_OPERATION
             .try_get_or_init(|| DEFAULT.clone())
             .map_err(|_| SPDM_STATUS_INVALID_STATE_LOCAL)?
-            .verify_cert_chain_cb)(cert_chain)
+            .verify_cert_chain_cb)(cert_chain, is_requester_cert, is_alias_cert_model)
     }
 }
 
@@ -3,12 +3,16 @@
 // SPDX-License-Identifier: BSD-2-Clause-Patent
 
 use crate::crypto::{x509v3, SpdmAsymVerify};
-use crate::error::{SpdmResult, SPDM_STATUS_INVALID_CERT, SPDM_STATUS_VERIF_FAIL};
-use crate::protocol::{SpdmBaseAsymAlgo, SpdmBaseHashAlgo, SpdmSignatureStruct};
+use crate::error::{
+    SpdmResult, SPDM_STATUS_INVALID_CERT, SPDM_STATUS_UNSUPPORTED_CAP, SPDM_STATUS_VERIF_FAIL,
+};
+use crate::protocol::{SpdmBaseAsymAlgo, SpdmBaseHashAlgo, SpdmPqcAsymAlgo, SpdmSignatureStruct};
 use core::convert::TryFrom;
 
 pub static DEFAULT: SpdmAsymVerify = SpdmAsymVerify {
     verify_cb: asym_verify,
+    verify_with_public_key_cb: asym_verify_with_public_key,
+    pqc_verify_cb: pqc_asym_verify,
 };
 
 fn asym_verify(
@@ -65,6 +69,10 @@ fn asym_verify(
         | (SpdmBaseHashAlgo::TPM_ALG_SHA_512, SpdmBaseAsymAlgo::TPM_ALG_RSAPSS_4096) => {
             &webpki::RSA_PSS_2048_8192_SHA512_LEGACY_KEY
         }
+        // EdDSA signs the message itself, so the hash algorithm doesn't matter.
+        (_, SpdmBaseAsymAlgo::EDDSA_ED25519) => &webpki::ED25519,
+        // ring doesn't implement Ed448.
+        (_, SpdmBaseAsymAlgo::EDDSA_ED448) => return Err(SPDM_STATUS_VERIF_FAIL),
         _ => {
             panic!();
         }
@@ -110,6 +118,13 @@ fn asym_verify(
                         Err(_) => Err(SPDM_STATUS_VERIF_FAIL),
                     }
                 }
+                SpdmBaseAsymAlgo::EDDSA_ED25519 => {
+                    // EdDSA signature is R || S, as used by SPDM.
+                    match cert.verify_signature(algorithm, data, signature.as_ref()) {
+                        Ok(()) => Ok(()),
+                        Err(_) => Err(SPDM_STATUS_VERIF_FAIL),
+                    }
+                }
                 _ => Err(SPDM_STATUS_VERIF_FAIL),
             }
         }
@@ -117,6 +132,95 @@ fn asym_verify(
     }
 }
 
+fn asym_verify_with_public_key(
+    base_hash_algo: SpdmBaseHashAlgo,
+    base_asym_algo: SpdmBaseAsymAlgo,
+    public_key_der: &[u8],
+    data: &[u8],
+    signature: &SpdmSignatureStruct,
+) -> SpdmResult {
+    if signature.data_size != base_asym_algo.get_size() {
+        return Err(SPDM_STATUS_VERIF_FAIL);
+    }
+
+    let algorithm: &'static dyn ring::signature::VerificationAlgorithm =
+        match (base_hash_algo, base_asym_algo) {
+            (SpdmBaseHashAlgo::TPM_ALG_SHA_256, SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P256) => {
+                &ring::signature::ECDSA_P256_SHA256_ASN1
+            }
+            (SpdmBaseHashAlgo::TPM_ALG_SHA_256, SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384) => {
+                &ring::signature::ECDSA_P384_SHA256_ASN1
+            }
+            (SpdmBaseHashAlgo::TPM_ALG_SHA_384, SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P256) => {
+                &ring::signature::ECDSA_P256_SHA384_ASN1
+            }
+            (SpdmBaseHashAlgo::TPM_ALG_SHA_384, SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384) => {
+                &ring::signature::ECDSA_P384_SHA384_ASN1
+            }
+            (SpdmBaseHashAlgo::TPM_ALG_SHA_256, SpdmBaseAsymAlgo::TPM_ALG_RSASSA_2048)
+            | (SpdmBaseHashAlgo::TPM_ALG_SHA_256, SpdmBaseAsymAlgo::TPM_ALG_RSASSA_3072)
+            | (SpdmBaseHashAlgo::TPM_ALG_SHA_256, SpdmBaseAsymAlgo::TPM_ALG_RSASSA_4096) => {
+                &ring::signature::RSA_PKCS1_2048_8192_SHA256
+            }
+            (SpdmBaseHashAlgo::TPM_ALG_SHA_256, SpdmBaseAsymAlgo::TPM_ALG_RSAPSS_2048)
+            | (SpdmBaseHashAlgo::TPM_ALG_SHA_256, SpdmBaseAsymAlgo::TPM_ALG_RSAPSS_3072)
+            | (SpdmBaseHashAlgo::TPM_ALG_SHA_256, SpdmBaseAsymAlgo::TPM_ALG_RSAPSS_4096) => {
+                &ring::signature::RSA_PSS_2048_8192_SHA256
+            }
+            (SpdmBaseHashAlgo::TPM_ALG_SHA_384, SpdmBaseAsymAlgo::TPM_ALG_RSASSA_2048)
+            | (SpdmBaseHashAlgo::TPM_ALG_SHA_384, SpdmBaseAsymAlgo::TPM_ALG_RSASSA_3072)
+            | (SpdmBaseHashAlgo::TPM_ALG_SHA_384, SpdmBaseAsymAlgo::TPM_ALG_RSASSA_4096) => {
+                &ring::signature::RSA_PKCS1_2048_8192_SHA384
+            }
+            (SpdmBaseHashAlgo::TPM_ALG_SHA_384, SpdmBaseAsymAlgo::TPM_ALG_RSAPSS_2048)
+            | (SpdmBaseHashAlgo::TPM_ALG_SHA_384, SpdmBaseAsymAlgo::TPM_ALG_RSAPSS_3072)
+            | (SpdmBaseHashAlgo::TPM_ALG_SHA_384, SpdmBaseAsymAlgo::TPM_ALG_RSAPSS_4096) => {
+                &ring::signature::RSA_PSS_2048_8192_SHA384
+            }
+            (SpdmBaseHashAlgo::TPM_ALG_SHA_512, SpdmBaseAsymAlgo::TPM_ALG_RSASSA_2048)
+            | (SpdmBaseHashAlgo::TPM_ALG_SHA_512, SpdmBaseAsymAlgo::TPM_ALG_RSASSA_3072)
+            | (SpdmBaseHashAlgo::TPM_ALG_SHA_512, SpdmBaseAsymAlgo::TPM_ALG_RSASSA_4096) => {
+                &ring::signature::RSA_PKCS1_2048_8192_SHA512
+            }
+            (SpdmBaseHashAlgo::TPM_ALG_SHA_512, SpdmBaseAsymAlgo::TPM_ALG_RSAPSS_2048)
+            | (SpdmBaseHashAlgo::TPM_ALG_SHA_512, SpdmBaseAsymAlgo::TPM_ALG_RSAPSS_3072)
+            | (SpdmBaseHashAlgo::TPM_ALG_SHA_512, SpdmBaseAsymAlgo::TPM_ALG_RSAPSS_4096) => {
+                &ring::signature::RSA_PSS_2048_8192_SHA512
+            }
+            (_, SpdmBaseAsymAlgo::EDDSA_ED25519) => &ring::signature::ED25519,
+            _ => return Err(SPDM_STATUS_VERIF_FAIL),
+        };
+
+    // ring takes the subjectPublicKey of the SubjectPublicKeyInfo, i.e. the
+    // uncompressed EC point or the DER encoded RSAPublicKey.
+    let public_key = x509v3::get_public_key_from_spki(public_key_der)?;
+    let public_key = ring::signature::UnparsedPublicKey::new(algorithm, public_key);
+
+    match base_asym_algo {
+        SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P256
+        | SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384 => {
+            let mut der_signature = [0u8; crate::protocol::ECDSA_ECC_NIST_P384_KEY_SIZE + 8];
+            let der_sign_size = ecc_signature_bin_to_der(signature.as_ref(), &mut der_signature)?;
+            public_key
+                .verify(data, &der_signature[..der_sign_size])
+                .map_err(|_| SPDM_STATUS_VERIF_FAIL)
+        }
+        _ => public_key
+            .verify(data, signature.as_ref())
+            .map_err(|_| SPDM_STATUS_VERIF_FAIL),
+    }
+}
+
+fn pqc_asym_verify(
+    _base_hash_algo: SpdmBaseHashAlgo,
+    _pqc_asym_algo: SpdmPqcAsymAlgo,
+    _public_cert_der: &[u8],
+    _data: &[u8],
+    _signature: &SpdmSignatureStruct,
+) -> SpdmResult {
+    Err(SPDM_STATUS_UNSUPPORTED_CAP)
+}
+
 // add ASN.1 for the ECDSA binary signature
 fn ecc_signature_bin_to_der(signature: &[u8], der_signature: &mut [u8]) -> SpdmResult<usize> {
     let sign_size = signature.len();
@@ -335,4 +439,107 @@ mod tests {
         );
         assert!(asym_verify.is_err());
     }
+    #[test]
+    fn test_case0_asym_verify_with_public_key() {
+        let base_hash_algo = SpdmBaseHashAlgo::TPM_ALG_SHA_384;
+        let base_asym_algo = SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384;
+        let key_pair = ring::signature::EcdsaKeyPair::from_pkcs8(
+            &ring::signature::ECDSA_P384_SHA384_FIXED_SIGNING,
+            &include_bytes!("../../../../test_key/ecp384/end_responder.key.p8")[..],
+        )
+        .unwrap();
+        let public_key_der =
+            &include_bytes!("../../../../test_key/ecp384/end_responder.key.pub.der")[..];
+        let data = &mut [0x10u8; 4096];
+
+        let rng = ring::rand::SystemRandom::new();
+        let sign = key_pair.sign(&rng, data).unwrap();
+        let mut signature = SpdmSignatureStruct {
+            data_size: sign.as_ref().len() as u16,
+            data: [0x00u8; crate::protocol::SPDM_MAX_ASYM_KEY_SIZE],
+        };
+        signature.data[..sign.as_ref().len()].copy_from_slice(sign.as_ref());
+
+        assert!(asym_verify_with_public_key(
+            base_hash_algo,
+            base_asym_algo,
+            public_key_der,
+            data,
+            &signature,
+        )
+        .is_ok());
+
+        data[0] = 0x20;
+        assert!(asym_verify_with_public_key(
+            base_hash_algo,
+            base_asym_algo,
+            public_key_der,
+            data,
+            &signature,
+        )
+        .is_err());
+
+        // a certificate is not a SubjectPublicKeyInfo
+        let public_cert_der = &include_bytes!("public_cert.der")[..];
+        assert!(asym_verify_with_public_key(
+            base_hash_algo,
+            base_asym_algo,
+            public_cert_der,
+            data,
+            &signature,
+        )
+        .is_err());
+    }
+    #[test]
+    fn test_case4_asym_verify() {
+        let base_asym_algo = SpdmBaseAsymAlgo::EDDSA_ED25519;
+        let key_pair = ring::signature::Ed25519KeyPair::from_pkcs8_maybe_unchecked(
+            &include_bytes!("../../../../test_key/ed25519/end_responder.key.p8")[..],
+        )
+        .unwrap();
+        let public_cert_der =
+            &include_bytes!("../../../../test_key/ed25519/bundle_responder.certchain.der")[..];
+        let public_key_der =
+            &include_bytes!("../../../../test_key/ed25519/end_responder.key.pub.der")[..];
+        let data = &mut [0x10u8; 4096];
+
+        let sign = key_pair.sign(data);
+        let mut signature = SpdmSignatureStruct {
+            data_size: sign.as_ref().len() as u16,
+            data: [0x00u8; crate::protocol::SPDM_MAX_ASYM_KEY_SIZE],
+        };
+        signature.data[..sign.as_ref().len()].copy_from_slice(sign.as_ref());
+
+        for base_hash_algo in [
+            SpdmBaseHashAlgo::TPM_ALG_SHA_256,
+            SpdmBaseHashAlgo::TPM_ALG_SHA_384,
+        ] {
+            assert!(asym_verify(
+                base_hash_algo,
+                base_asym_algo,
+                public_cert_der,
+                data,
+                &signature
+            )
+            .is_ok());
+            assert!(asym_verify_with_public_key(
+                base_hash_algo,
+                base_asym_algo,
+                public_key_der,
+                data,
+                &signature,
+            )
+            .is_ok());
+        }
+
+        data[0] = 0x20;
+        assert!(asym_verify(
+            SpdmBaseHashAlgo::TPM_ALG_SHA_384,
+            base_asym_algo,
+            public_cert_der,
+            data,
+            &signature
+        )
+        .is_err());
+    }
 }
@@ -7,7 +7,7 @@ use alloc::vec;
 use alloc::vec::Vec;
 use core::convert::TryFrom;
 
-use crate::crypto::SpdmCertOperation;
+use crate::crypto::{x509v3, SpdmCertOperation};
 use crate::error::{SpdmResult, SPDM_STATUS_INVALID_CERT, SPDM_STATUS_INVALID_STATE_LOCAL};
 use ring::io::der;
 
@@ -45,8 +45,17 @@ fn get_cert_from_cert_chain(cert_chain: &[u8], index: isize) -> SpdmResult<(usiz
     }
 }
 
-fn verify_cert_chain(cert_chain: &[u8]) -> SpdmResult {
-    static EKU_SPDM_RESPONDER_AUTH: &[u8] = &[40 + 3, 6, 1, 5, 5, 7, 3, 1];
+fn verify_cert_chain(
+    cert_chain: &[u8],
+    is_requester_cert: bool,
+    is_alias_cert_model: bool,
+) -> SpdmResult {
+    // id-kp-serverAuth, accepted for both roles
+    static EKU_SPDM_AUTH: &[u8] = &[40 + 3, 6, 1, 5, 5, 7, 3, 1];
+    // id-DMTF-eku-responder-auth 1.3.6.1.4.1.412.274.3.1
+    static EKU_DMTF_RESPONDER_AUTH: &[u8] = &[40 + 3, 6, 1, 4, 1, 0x83, 0x1c, 0x82, 0x12, 3, 1];
+    // id-DMTF-eku-requester-auth 1.3.6.1.4.1.412.274.3.2
+    static EKU_DMTF_REQUESTER_AUTH: &[u8] = &[40 + 3, 6, 1, 4, 1, 0x83, 0x1c, 0x82, 0x12, 3, 2];
 
     static ALL_SIGALGS: &[&webpki::SignatureAlgorithm] = &[
         &webpki::RSA_PKCS1_2048_8192_SHA256,
@@ -84,12 +93,6 @@ fn verify_cert_chain(cert_chain: &[u8]) -> SpdmResult {
         n => (certs[0], &certs[1..(n - 1)], certs[n - 1]),
     };
 
-    let anchors = if let Ok(ta) = webpki::TrustAnchor::try_from_cert_der(ca) {
-        vec![ta]
-    } else {
-        return Err(SPDM_STATUS_INVALID_CERT);
-    };
-
     #[cfg(any(target_os = "uefi", target_os = "none"))]
     let timestamp = {
         if let Some(ts) = sys_time::get_sys_time() {
@@ -107,6 +110,23 @@ fn verify_cert_chain(cert_chain: &[u8]) -> SpdmResult {
             return Err(SPDM_STATUS_INVALID_STATE_LOCAL);
         }
     };
+
+    // A partial chain in the alias certificate model ends at the device
+    // certificate, which is a CA that webpki refuses as the end entity.
+    if x509v3::is_ca_certificate(ee)? {
+        if !is_alias_cert_model {
+            error!("Cert verification Fail - partial chain\n");
+            return Err(SPDM_STATUS_INVALID_CERT);
+        }
+        return verify_partial_cert_chain(&certs, ALL_SIGALGS, timestamp);
+    }
+
+    let anchors = if let Ok(ta) = webpki::TrustAnchor::try_from_cert_der(ca) {
+        vec![ta]
+    } else {
+        return Err(SPDM_STATUS_INVALID_CERT);
+    };
+
     let time = webpki::Time::from_seconds_since_unix_epoch(timestamp);
 
     let cert = if let Ok(eec) = webpki::EndEntityCert::try_from(ee) {
@@ -115,18 +135,17 @@ fn verify_cert_chain(cert_chain: &[u8]) -> SpdmResult {
         return Err(SPDM_STATUS_INVALID_CERT);
     };
 
+    let ekus = if is_requester_cert {
+        [EKU_DMTF_REQUESTER_AUTH, EKU_SPDM_AUTH]
+    } else {
+        [EKU_DMTF_RESPONDER_AUTH, EKU_SPDM_AUTH]
+    };
+
     // we cannot call verify_is_valid_tls_server_cert because it will check verify_cert::EKU_SERVER_AUTH.
-    if cert
-        .verify_cert_chain_with_eku(
-            EKU_SPDM_RESPONDER_AUTH,
-            ALL_SIGALGS,
-            &anchors,
-            inters,
-            time,
-            0,
-        )
-        .is_ok()
-    {
+    if ekus.iter().any(|eku| {
+        cert.verify_cert_chain_with_eku(eku, ALL_SIGALGS, &anchors, inters, time, 0)
+            .is_ok()
+    }) {
         info!("Cert verification Pass\n");
         Ok(())
     } else {
@@ -134,6 +153,66 @@ fn verify_cert_chain(cert_chain: &[u8]) -> SpdmResult {
         Err(SPDM_STATUS_INVALID_CERT)
     }
 }
+
+// webpki cannot build a chain ending at a CA, so the chain is checked here:
+// the root is self-signed, every certificate is signed by its predecessor,
+// which shall be a CA, and is valid now.
+fn verify_partial_cert_chain(
+    certs: &[&[u8]],
+    sig_algs: &[&webpki::SignatureAlgorithm],
+    timestamp: u64,
+) -> SpdmResult {
+    let root = certs.first().ok_or(SPDM_STATUS_INVALID_CERT)?;
+    let issuers = core::iter::once(root).chain(certs.iter());
+    for (issuer_der, cert) in issuers.zip(certs.iter()) {
+        if !x509v3::is_ca_certificate(issuer_der)? {
+            error!("Partial cert verification Fail - issuer is not a CA\n");
+            return Err(SPDM_STATUS_INVALID_CERT);
+        }
+        let (not_before, not_after) = x509v3::get_validity(cert)?;
+        if timestamp < not_before || timestamp > not_after {
+            error!("Partial cert verification Fail - expired\n");
+            return Err(SPDM_STATUS_INVALID_CERT);
+        }
+
+        let issuer =
+            webpki::EndEntityCert::try_from(*issuer_der).map_err(|_| SPDM_STATUS_INVALID_CERT)?;
+        let (tbs, signature) = get_tbs_and_signature(cert)?;
+        if !sig_algs
+            .iter()
+            .any(|alg| issuer.verify_signature(alg, tbs, signature).is_ok())
+        {
+            error!("Partial cert verification Fail\n");
+            return Err(SPDM_STATUS_INVALID_CERT);
+        }
+    }
+
+    info!("Partial cert verification Pass\n");
+    Ok(())
+}
+
+fn get_tbs_and_signature(cert: &[u8]) -> SpdmResult<(&[u8], &[u8])> {
+    untrusted::Input::from(cert).read_all(SPDM_STATUS_INVALID_CERT, |reader| {
+        der::nested(
+            reader,
+            der::Tag::Sequence,
+            SPDM_STATUS_INVALID_CERT,
+            |cert| {
+                let start = cert.mark();
+                der::expect_tag_and_get_value(cert, der::Tag::Sequence)
+                    .map_err(|_| SPDM_STATUS_INVALID_CERT)?;
+                let tbs = cert
+                    .get_input_between_marks(start, cert.mark())
+                    .map_err(|_| SPDM_STATUS_INVALID_CERT)?;
+                der::expect_tag_and_get_value(cert, der::Tag::Sequence)
+                    .map_err(|_| SPDM_STATUS_INVALID_CERT)?;
+                let signature = der::bit_string_with_no_unused_bits(cert)
+                    .map_err(|_| SPDM_STATUS_INVALID_CERT)?;
+                Ok((tbs.as_slice_less_safe(), signature.as_slice_less_safe()))
+            },
+        )
+    })
+}
 #[cfg(all(test,))]
 mod tests {
     use super::*;
@@ -177,7 +256,7 @@ mod tests {
         let status = get_cert_from_cert_chain(cert_chain, -1).is_ok();
         assert!(status);
 
-        let status = verify_cert_chain(cert_chain).is_ok();
+        let status = verify_cert_chain(cert_chain, false, false).is_ok();
         assert!(status);
     }
 
@@ -186,30 +265,89 @@ mod tests {
     fn test_verify_cert_chain_case1() {
         let bundle_certs_der =
             &include_bytes!("../../../../test_key/crypto_chains/ca_selfsigned.crt.der")[..];
-        assert!(verify_cert_chain(bundle_certs_der).is_ok());
+        // A lone CA certificate is only a valid partial chain in the alias
+        // certificate model.
+        assert!(verify_cert_chain(bundle_certs_der, false, false).is_err());
+        let ca = &include_bytes!("../../../../test_key/ecp384/ca.cert.der")[..];
+        assert!(verify_cert_chain(ca, false, false).is_err());
+        assert!(verify_cert_chain(ca, false, true).is_ok());
 
         let bundle_certs_der =
             &include_bytes!("../../../../test_key/crypto_chains/bundle_two_level_cert.der")[..];
-        assert!(verify_cert_chain(bundle_certs_der).is_ok());
+        assert!(verify_cert_chain(bundle_certs_der, false, false).is_ok());
 
         let bundle_certs_der =
             &include_bytes!("../../../../test_key/ecp384/bundle_requester.certchain.der")[..];
-        assert!(verify_cert_chain(bundle_certs_der).is_ok());
+        assert!(verify_cert_chain(bundle_certs_der, true, false).is_ok());
 
         let bundle_certs_der =
             &include_bytes!("../../../../test_key/crypto_chains/bundle_cert.der")[..];
-        assert!(verify_cert_chain(bundle_certs_der).is_ok());
+        assert!(verify_cert_chain(bundle_certs_der, false, false).is_ok());
 
         // Flipping bits to test signature hash is invalid.
         let mut cert_chain = bundle_certs_der.to_vec();
         // offset 3140 is in signature range.
         cert_chain[3140] ^= 0xFE;
-        assert!(verify_cert_chain(&cert_chain).is_err());
+        assert!(verify_cert_chain(&cert_chain, false, false).is_err());
 
         // Invalid Intermediate cert
         let mut cert_chain = bundle_certs_der.to_vec();
         // Change intermediate cert data
         cert_chain[1380] = 0xFF;
-        assert!(verify_cert_chain(&cert_chain).is_err());
+        assert!(verify_cert_chain(&cert_chain, false, false).is_err());
+    }
+
+    #[test]
+    fn test_verify_cert_chain_eku() {
+        let requester_cert_chain = &include_bytes!(
+            "../../../../test_key/crypto_chains/bundle_dmtf_requester_cert.der"
+        )[..];
+        assert!(verify_cert_chain(requester_cert_chain, true, false).is_ok());
+        assert!(verify_cert_chain(requester_cert_chain, false, false).is_err());
+
+        let responder_cert_chain = &include_bytes!(
+            "../../../../test_key/crypto_chains/bundle_dmtf_responder_cert.der"
+        )[..];
+        assert!(verify_cert_chain(responder_cert_chain, false, false).is_ok());
+        assert!(verify_cert_chain(responder_cert_chain, true, false).is_err());
+    }
+
+    #[test]
+    fn test_verify_partial_cert_chain() {
+        let ca = &include_bytes!("../../../../test_key/ecp384/ca.cert.der")[..];
+        let inter = &include_bytes!("../../../../test_key/ecp384/inter.cert.der")[..];
+        let partial_chain = [ca, inter].concat();
+        assert!(verify_cert_chain(&partial_chain, false, true).is_ok());
+        // Only the alias certificate model allows a partial chain.
+        assert!(verify_cert_chain(&partial_chain, false, false).is_err());
+
+        // Flipping bits in the intermediate signature.
+        let mut cert_chain = partial_chain.clone();
+        let last = cert_chain.len() - 1;
+        cert_chain[last - 8] ^= 0xFE;
+        assert!(verify_cert_chain(&cert_chain, false, true).is_err());
+
+        // The issuer of the device certificate shall be a CA.
+        let leaf = &include_bytes!("../../../../test_key/ecp384/end_responder.cert.der")[..];
+        assert!(verify_cert_chain(&[leaf, inter].concat(), false, true).is_err());
+    }
+
+    #[test]
+    fn test_verify_partial_cert_chain_validity() {
+        let ca = &include_bytes!("../../../../test_key/ecp384/ca.cert.der")[..];
+        let inter = &include_bytes!("../../../../test_key/ecp384/inter.cert.der")[..];
+        let certs = [ca, inter];
+        let (not_before, not_after) = x509v3::get_validity(inter).unwrap();
+        assert!(
+            verify_partial_cert_chain(&certs, &[&webpki::ECDSA_P384_SHA384], not_before).is_ok()
+        );
+        assert!(
+            verify_partial_cert_chain(&certs, &[&webpki::ECDSA_P384_SHA384], not_before - 1)
+                .is_err()
+        );
+        assert!(
+            verify_partial_cert_chain(&certs, &[&webpki::ECDSA_P384_SHA384], not_after + 1)
+                .is_err()
+        );
     }
 }
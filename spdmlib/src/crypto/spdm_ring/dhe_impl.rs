@@ -7,11 +7,13 @@ use alloc::boxed::Box;
 
 use crate::crypto::bytes_mut_scrubbed::BytesMutStrubbed;
 use crate::crypto::{SpdmDhe, SpdmDheKeyExchange};
-use crate::protocol::{SpdmDheAlgo, SpdmDheExchangeStruct, SpdmDheFinalKeyStruct};
+use crate::protocol::{SpdmDheAlgo, SpdmDheExchangeStruct, SpdmDheFinalKeyStruct, SpdmKemAlgo};
 use bytes::{BufMut, BytesMut};
 
 pub static DEFAULT: SpdmDhe = SpdmDhe {
     generate_key_pair_cb: generate_key_pair,
+    kem_generate_key_pair_cb: kem_generate_key_pair,
+    kem_encapsulate_cb: kem_encapsulate,
 };
 
 fn generate_key_pair(
@@ -24,6 +26,19 @@ fn generate_key_pair(
     }
 }
 
+fn kem_generate_key_pair(
+    _kem_algo: SpdmKemAlgo,
+) -> Option<(SpdmDheExchangeStruct, Box<dyn SpdmDheKeyExchange>)> {
+    None
+}
+
+fn kem_encapsulate(
+    _kem_algo: SpdmKemAlgo,
+    _peer_encap_key: &SpdmDheExchangeStruct,
+) -> Option<(SpdmDheExchangeStruct, SpdmDheFinalKeyStruct)> {
+    None
+}
+
 struct SpdmDheKeyExchangeP256(ring::agreement::EphemeralPrivateKey);
 
 impl SpdmDheKeyExchange for SpdmDheKeyExchangeP256 {
@@ -4,6 +4,7 @@
 
 extern crate alloc;
 
+use super::sha3_impl;
 use crate::crypto::SpdmHash;
 
 use crate::protocol::{SpdmBaseHashAlgo, SpdmDigestStruct};
@@ -22,6 +23,9 @@ pub static DEFAULT: SpdmHash = SpdmHash {
 };
 
 fn hash_all(base_hash_algo: SpdmBaseHashAlgo, data: &[u8]) -> Option<SpdmDigestStruct> {
+    if sha3_impl::is_sha3(base_hash_algo) {
+        return sha3_impl::hash_all(base_hash_algo, data);
+    }
     let algorithm = match base_hash_algo {
         SpdmBaseHashAlgo::TPM_ALG_SHA_256 => &ring::digest::SHA256,
         SpdmBaseHashAlgo::TPM_ALG_SHA_384 => &ring::digest::SHA384,
@@ -40,7 +44,13 @@ mod hash_ext {
     use lazy_static::lazy_static;
     use spin::Mutex;
 
-    pub type HashCtxConcrete = ring::digest::Context;
+    use super::sha3_impl::Sha3Context;
+
+    #[derive(Clone)]
+    pub enum HashCtxConcrete {
+        Ring(ring::digest::Context),
+        Sha3(Sha3Context),
+    }
 
     lazy_static! {
         static ref HASH_CTX_TABLE: Mutex<BTreeMap<usize, Box<HashCtxConcrete>>> =
@@ -51,14 +61,22 @@ mod hash_ext {
     pub fn hash_ctx_update(handle: usize, data: &[u8]) -> SpdmResult {
         let mut table = HASH_CTX_TABLE.lock();
         let ctx = table.get_mut(&handle).ok_or(SPDM_STATUS_CRYPTO_ERROR)?;
-        ctx.update(data);
+        match ctx.as_mut() {
+            HashCtxConcrete::Ring(ctx) => ctx.update(data),
+            HashCtxConcrete::Sha3(ctx) => ctx.update(data),
+        }
         Ok(())
     }
 
     pub fn hash_ctx_finalize(handle: usize) -> Option<SpdmDigestStruct> {
         let ctx = HASH_CTX_TABLE.lock().remove(&handle)?;
-        let digest_value = ctx.finish();
-        Some(SpdmDigestStruct::from(digest_value.as_ref()))
+        match *ctx {
+            HashCtxConcrete::Ring(ctx) => {
+                let digest_value = ctx.finish();
+                Some(SpdmDigestStruct::from(digest_value.as_ref()))
+            }
+            HashCtxConcrete::Sha3(ctx) => Some(ctx.finish()),
+        }
     }
 
     pub fn hash_ctx_dup(handle: usize) -> Option<usize> {
@@ -72,13 +90,16 @@ mod hash_ext {
     }
 
     pub fn hash_ctx_init(base_hash_algo: SpdmBaseHashAlgo) -> Option<usize> {
+        if let Some(ctx) = Sha3Context::new(base_hash_algo) {
+            return Some(insert_to_table(Box::new(HashCtxConcrete::Sha3(ctx))));
+        }
         let algorithm = match base_hash_algo {
             SpdmBaseHashAlgo::TPM_ALG_SHA_256 => &ring::digest::SHA256,
             SpdmBaseHashAlgo::TPM_ALG_SHA_384 => &ring::digest::SHA384,
             SpdmBaseHashAlgo::TPM_ALG_SHA_512 => &ring::digest::SHA512,
             _ => return None,
         };
-        let ctx = Box::new(HashCtxConcrete::new(algorithm));
+        let ctx = Box::new(HashCtxConcrete::Ring(ring::digest::Context::new(algorithm)));
         Some(insert_to_table(ctx))
     }
 
@@ -2,6 +2,7 @@
 //
 // SPDX-License-Identifier: BSD-2-Clause-Patent
 
+use super::sha3_impl;
 use crate::crypto::SpdmHkdf;
 use crate::protocol::{
     SpdmBaseHashAlgo, SpdmHkdfInputKeyingMaterial, SpdmHkdfOutputKeyingMaterial,
@@ -18,6 +19,9 @@ fn hkdf_extract(
     salt: &[u8],
     ikm: &SpdmHkdfInputKeyingMaterial,
 ) -> Option<SpdmHkdfPseudoRandomKey> {
+    if sha3_impl::is_sha3(hash_algo) {
+        return sha3_impl::hkdf_extract(hash_algo, salt, ikm.as_ref());
+    }
     let algorithm = match hash_algo {
         SpdmBaseHashAlgo::TPM_ALG_SHA_256 => ring::hmac::HMAC_SHA256,
         SpdmBaseHashAlgo::TPM_ALG_SHA_384 => ring::hmac::HMAC_SHA384,
@@ -43,6 +47,10 @@ fn hkdf_expand(
         return None;
     }
 
+    if sha3_impl::is_sha3(hash_algo) {
+        return sha3_impl::hkdf_expand(hash_algo, prk, info, out_size);
+    }
+
     let algo = match hash_algo {
         SpdmBaseHashAlgo::TPM_ALG_SHA_256 => Some(ring::hkdf::HKDF_SHA256),
         SpdmBaseHashAlgo::TPM_ALG_SHA_384 => Some(ring::hkdf::HKDF_SHA384),
@@ -2,6 +2,7 @@
 //
 // SPDX-License-Identifier: BSD-2-Clause-Patent
 
+use super::sha3_impl;
 use crate::crypto::SpdmHmac;
 use crate::error::{SpdmResult, SPDM_STATUS_VERIF_FAIL};
 use crate::protocol::{SpdmBaseHashAlgo, SpdmDigestStruct};
@@ -12,6 +13,9 @@ pub static DEFAULT: SpdmHmac = SpdmHmac {
 };
 
 fn hmac(base_hash_algo: SpdmBaseHashAlgo, key: &[u8], data: &[u8]) -> Option<SpdmDigestStruct> {
+    if sha3_impl::is_sha3(base_hash_algo) {
+        return sha3_impl::hmac(base_hash_algo, key, data);
+    }
     let algorithm = match base_hash_algo {
         SpdmBaseHashAlgo::TPM_ALG_SHA_256 => ring::hmac::HMAC_SHA256,
         SpdmBaseHashAlgo::TPM_ALG_SHA_384 => ring::hmac::HMAC_SHA384,
@@ -33,6 +37,9 @@ fn hmac_verify(
     data: &[u8],
     hmac: &SpdmDigestStruct,
 ) -> SpdmResult {
+    if sha3_impl::is_sha3(base_hash_algo) {
+        return sha3_impl::hmac_verify(base_hash_algo, key, data, hmac);
+    }
     let algorithm = match base_hash_algo {
         SpdmBaseHashAlgo::TPM_ALG_SHA_256 => ring::hmac::HMAC_SHA256,
         SpdmBaseHashAlgo::TPM_ALG_SHA_384 => ring::hmac::HMAC_SHA384,
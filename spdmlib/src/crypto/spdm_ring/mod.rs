@@ -10,3 +10,4 @@ pub mod hash_impl;
 pub mod hkdf_impl;
 pub mod hmac_impl;
 pub mod rand_impl;
+pub mod sha3_impl;
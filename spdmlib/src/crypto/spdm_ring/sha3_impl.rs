@@ -0,0 +1,246 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+// ring only implements SHA-2, the SHA3 hash, HMAC and HKDF used by the
+// ring backend are provided by the RustCrypto sha3, hmac and hkdf crates.
+
+use crate::error::{SpdmResult, SPDM_STATUS_VERIF_FAIL};
+use crate::protocol::{
+    SpdmBaseHashAlgo, SpdmDigestStruct, SpdmHkdfOutputKeyingMaterial, SpdmHkdfPseudoRandomKey,
+    SPDM_MAX_HKDF_OKM_SIZE,
+};
+use ::hkdf::SimpleHkdf;
+use ::hmac::{Mac, SimpleHmac};
+use sha3::digest::core_api::BlockSizeUser;
+use sha3::{Digest, Sha3_256, Sha3_384, Sha3_512};
+
+pub fn is_sha3(base_hash_algo: SpdmBaseHashAlgo) -> bool {
+    matches!(
+        base_hash_algo,
+        SpdmBaseHashAlgo::TPM_ALG_SHA3_256
+            | SpdmBaseHashAlgo::TPM_ALG_SHA3_384
+            | SpdmBaseHashAlgo::TPM_ALG_SHA3_512
+    )
+}
+
+#[derive(Clone)]
+pub enum Sha3Context {
+    Sha3_256(Sha3_256),
+    Sha3_384(Sha3_384),
+    Sha3_512(Sha3_512),
+}
+
+impl Sha3Context {
+    pub fn new(base_hash_algo: SpdmBaseHashAlgo) -> Option<Self> {
+        match base_hash_algo {
+            SpdmBaseHashAlgo::TPM_ALG_SHA3_256 => Some(Sha3Context::Sha3_256(Sha3_256::new())),
+            SpdmBaseHashAlgo::TPM_ALG_SHA3_384 => Some(Sha3Context::Sha3_384(Sha3_384::new())),
+            SpdmBaseHashAlgo::TPM_ALG_SHA3_512 => Some(Sha3Context::Sha3_512(Sha3_512::new())),
+            _ => None,
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        match self {
+            Sha3Context::Sha3_256(ctx) => ctx.update(data),
+            Sha3Context::Sha3_384(ctx) => ctx.update(data),
+            Sha3Context::Sha3_512(ctx) => ctx.update(data),
+        }
+    }
+
+    pub fn finish(self) -> SpdmDigestStruct {
+        match self {
+            Sha3Context::Sha3_256(ctx) => SpdmDigestStruct::from(ctx.finalize().as_slice()),
+            Sha3Context::Sha3_384(ctx) => SpdmDigestStruct::from(ctx.finalize().as_slice()),
+            Sha3Context::Sha3_512(ctx) => SpdmDigestStruct::from(ctx.finalize().as_slice()),
+        }
+    }
+}
+
+pub fn hash_all(base_hash_algo: SpdmBaseHashAlgo, data: &[u8]) -> Option<SpdmDigestStruct> {
+    let mut ctx = Sha3Context::new(base_hash_algo)?;
+    ctx.update(data);
+    Some(ctx.finish())
+}
+
+fn new_hmac<D: Digest + BlockSizeUser>(key: &[u8]) -> SimpleHmac<D> {
+    // HMAC accepts keys of any length.
+    <SimpleHmac<D> as Mac>::new_from_slice(key).unwrap()
+}
+
+pub fn hmac(base_hash_algo: SpdmBaseHashAlgo, key: &[u8], data: &[u8]) -> Option<SpdmDigestStruct> {
+    let tag = match base_hash_algo {
+        SpdmBaseHashAlgo::TPM_ALG_SHA3_256 => {
+            let mut mac = new_hmac::<Sha3_256>(key);
+            mac.update(data);
+            SpdmDigestStruct::from(mac.finalize().into_bytes().as_slice())
+        }
+        SpdmBaseHashAlgo::TPM_ALG_SHA3_384 => {
+            let mut mac = new_hmac::<Sha3_384>(key);
+            mac.update(data);
+            SpdmDigestStruct::from(mac.finalize().into_bytes().as_slice())
+        }
+        SpdmBaseHashAlgo::TPM_ALG_SHA3_512 => {
+            let mut mac = new_hmac::<Sha3_512>(key);
+            mac.update(data);
+            SpdmDigestStruct::from(mac.finalize().into_bytes().as_slice())
+        }
+        _ => return None,
+    };
+    Some(tag)
+}
+
+pub fn hmac_verify(
+    base_hash_algo: SpdmBaseHashAlgo,
+    key: &[u8],
+    data: &[u8],
+    hmac: &SpdmDigestStruct,
+) -> SpdmResult {
+    let tag = hmac.as_ref();
+    let res = match base_hash_algo {
+        SpdmBaseHashAlgo::TPM_ALG_SHA3_256 => {
+            let mut mac = new_hmac::<Sha3_256>(key);
+            mac.update(data);
+            mac.verify_slice(tag)
+        }
+        SpdmBaseHashAlgo::TPM_ALG_SHA3_384 => {
+            let mut mac = new_hmac::<Sha3_384>(key);
+            mac.update(data);
+            mac.verify_slice(tag)
+        }
+        SpdmBaseHashAlgo::TPM_ALG_SHA3_512 => {
+            let mut mac = new_hmac::<Sha3_512>(key);
+            mac.update(data);
+            mac.verify_slice(tag)
+        }
+        _ => return Err(SPDM_STATUS_VERIF_FAIL),
+    };
+    res.map_err(|_| SPDM_STATUS_VERIF_FAIL)
+}
+
+pub fn hkdf_extract(
+    base_hash_algo: SpdmBaseHashAlgo,
+    salt: &[u8],
+    ikm: &[u8],
+) -> Option<SpdmHkdfPseudoRandomKey> {
+    // HKDF-Extract(salt, IKM) is HMAC-Hash(salt, IKM)
+    let prk = hmac(base_hash_algo, salt, ikm)?;
+    Some(SpdmHkdfPseudoRandomKey::from(prk.as_ref()))
+}
+
+fn expand<D: Digest + BlockSizeUser + Clone>(
+    prk: &[u8],
+    info: &[u8],
+    okm: &mut [u8],
+) -> Option<()> {
+    let hkdf = SimpleHkdf::<D>::from_prk(prk).ok()?;
+    hkdf.expand(info, okm).ok()
+}
+
+pub fn hkdf_expand(
+    base_hash_algo: SpdmBaseHashAlgo,
+    prk: &SpdmHkdfPseudoRandomKey,
+    info: &[u8],
+    out_size: u16,
+) -> Option<SpdmHkdfOutputKeyingMaterial> {
+    if out_size as usize > SPDM_MAX_HKDF_OKM_SIZE || prk.data_size != base_hash_algo.get_size() {
+        return None;
+    }
+
+    let mut ret = SpdmHkdfOutputKeyingMaterial::default();
+    let okm = &mut ret.data[..out_size as usize];
+    match base_hash_algo {
+        SpdmBaseHashAlgo::TPM_ALG_SHA3_256 => expand::<Sha3_256>(prk.as_ref(), info, okm)?,
+        SpdmBaseHashAlgo::TPM_ALG_SHA3_384 => expand::<Sha3_384>(prk.as_ref(), info, okm)?,
+        SpdmBaseHashAlgo::TPM_ALG_SHA3_512 => expand::<Sha3_512>(prk.as_ref(), info, okm)?,
+        _ => return None,
+    }
+    ret.data_size = out_size;
+    Some(ret)
+}
+
+#[cfg(all(test,))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_case0_sha3_hash_all() {
+        // FIPS 202 test vectors for "abc"
+        let digest = hash_all(SpdmBaseHashAlgo::TPM_ALG_SHA3_256, b"abc").unwrap();
+        assert_eq!(
+            digest.as_ref(),
+            &[
+                0x3a, 0x98, 0x5d, 0xa7, 0x4f, 0xe2, 0x25, 0xb2, 0x04, 0x5c, 0x17, 0x2d, 0x6b, 0xd3,
+                0x90, 0xbd, 0x85, 0x5f, 0x08, 0x6e, 0x3e, 0x9d, 0x52, 0x5b, 0x46, 0xbf, 0xe2, 0x45,
+                0x11, 0x43, 0x15, 0x32
+            ]
+        );
+        let digest = hash_all(SpdmBaseHashAlgo::TPM_ALG_SHA3_384, b"abc").unwrap();
+        assert_eq!(digest.data_size, 48);
+        let digest = hash_all(SpdmBaseHashAlgo::TPM_ALG_SHA3_512, b"abc").unwrap();
+        assert_eq!(digest.data_size, 64);
+        assert!(hash_all(SpdmBaseHashAlgo::TPM_ALG_SHA_256, b"abc").is_none());
+    }
+
+    #[test]
+    fn test_case0_sha3_context() {
+        let mut ctx = Sha3Context::new(SpdmBaseHashAlgo::TPM_ALG_SHA3_384).unwrap();
+        ctx.update(b"hello");
+        ctx.update(b", ");
+        let mut ctx_d = ctx.clone();
+        ctx_d.update(b"buddy");
+        ctx.update(b"world");
+        assert_eq!(
+            ctx.finish().as_ref(),
+            hash_all(SpdmBaseHashAlgo::TPM_ALG_SHA3_384, b"hello, world")
+                .unwrap()
+                .as_ref()
+        );
+        assert_eq!(
+            ctx_d.finish().as_ref(),
+            hash_all(SpdmBaseHashAlgo::TPM_ALG_SHA3_384, b"hello, buddy")
+                .unwrap()
+                .as_ref()
+        );
+    }
+
+    #[test]
+    fn test_case0_sha3_hmac() {
+        for base_hash_algo in [
+            SpdmBaseHashAlgo::TPM_ALG_SHA3_256,
+            SpdmBaseHashAlgo::TPM_ALG_SHA3_384,
+            SpdmBaseHashAlgo::TPM_ALG_SHA3_512,
+        ] {
+            let key = &[0x5au8; 200];
+            let data = &[0xa5u8; 100];
+            let tag = hmac(base_hash_algo, key, data).unwrap();
+            assert_eq!(tag.data_size, base_hash_algo.get_size());
+            assert!(hmac_verify(base_hash_algo, key, data, &tag).is_ok());
+            assert!(hmac_verify(base_hash_algo, key, &data[1..], &tag).is_err());
+        }
+    }
+
+    #[test]
+    fn test_case0_sha3_hkdf() {
+        for base_hash_algo in [
+            SpdmBaseHashAlgo::TPM_ALG_SHA3_256,
+            SpdmBaseHashAlgo::TPM_ALG_SHA3_384,
+            SpdmBaseHashAlgo::TPM_ALG_SHA3_512,
+        ] {
+            let prk = hkdf_extract(base_hash_algo, &[0u8; 64], &[0x0bu8; 22]).unwrap();
+            assert_eq!(prk.data_size, base_hash_algo.get_size());
+            let okm = hkdf_expand(base_hash_algo, &prk, b"info", 42).unwrap();
+            assert_eq!(okm.data_size, 42);
+            let okm_long = hkdf_expand(base_hash_algo, &prk, b"info", 64).unwrap();
+            assert_eq!(okm.as_ref(), &okm_long.as_ref()[..42]);
+            assert!(hkdf_expand(
+                base_hash_algo,
+                &prk,
+                b"info",
+                SPDM_MAX_HKDF_OKM_SIZE as u16 + 1
+            )
+            .is_none());
+        }
+    }
+}
@@ -12,12 +12,19 @@ const ASN1_TAG_CLASS_CONTEXT_SPECIFIC_MASK: u8 = 0x80;
 
 const ASN1_FORM_CONSTRUCTED_MASK: u8 = 0x20;
 
+const ASN1_TAG_NUMBER_BOOLEAN: u8 = 0x1;
 const ASN1_TAG_NUMBER_INTEGER: u8 = 0x2;
+const ASN1_TAG_NUMBER_BIT_STRING: u8 = 0x3;
+const ASN1_TAG_NUMBER_OCTET_STRING: u8 = 0x4;
 const ASN1_TAG_NUMBER_OBJECT_IDENTIFIER: u8 = 0x6;
+const ASN1_TAG_NUMBER_UTC_TIME: u8 = 0x17;
+const ASN1_TAG_NUMBER_GENERALIZED_TIME: u8 = 0x18;
 const ASN1_TAG_NUMBER_SEQUENCE: u8 = 0x10;
 
 const ASN1_TAG_SEQUENCE: u8 =
     ASN1_TAG_CLASS_UNIVERSAL_MASK | ASN1_FORM_CONSTRUCTED_MASK | ASN1_TAG_NUMBER_SEQUENCE;
+const ASN1_TAG_EXTENSIONS: u8 =
+    ASN1_TAG_CLASS_CONTEXT_SPECIFIC_MASK | ASN1_FORM_CONSTRUCTED_MASK | 0x3;
 
 const ASN1_LENGTH_MULTI_OCTET_MASK: u8 = 0x80;
 
@@ -27,6 +34,19 @@ const OID_RSA_SHA384RSA: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x0
 const OID_RSA_SHA512RSA: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0du8];
 const OID_ECDSA_SHA256: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03, 0x02u8];
 const OID_ECDSA_SHA384: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03, 0x03u8];
+const OID_ECDSA_SHA512: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03, 0x04u8];
+const OID_ED25519: &[u8] = &[0x2b, 0x65, 0x70u8];
+const OID_ED448: &[u8] = &[0x2b, 0x65, 0x71u8];
+// SM2-with-SM3 1.2.156.10197.1.501
+const OID_SM2_SM3: &[u8] = &[0x2a, 0x81, 0x1c, 0xcf, 0x55, 0x01, 0x83, 0x75u8];
+const OID_BASIC_CONSTRAINTS: &[u8] = &[0x55, 0x1d, 0x13u8];
+const OID_EXT_KEY_USAGE: &[u8] = &[0x55, 0x1d, 0x25u8];
+// id-DMTF-spdm-extension 1.3.6.1.4.1.412.274.6
+const OID_DMTF_SPDM_EXTENSION: &[u8] =
+    &[0x2b, 0x06, 0x01, 0x04, 0x01, 0x83, 0x1c, 0x82, 0x12, 0x06u8];
+// id-DMTF-hardware-identity 1.3.6.1.4.1.412.274.2
+const OID_DMTF_HARDWARE_IDENTITY: &[u8] =
+    &[0x2b, 0x06, 0x01, 0x04, 0x01, 0x83, 0x1c, 0x82, 0x12, 0x02u8];
 
 // reference: https://www.rfc-editor.org/rfc/rfc5280.txt
 // IN DER encoded certificate chain slice
@@ -56,6 +76,317 @@ pub fn check_cert_chain_format(
     }
 }
 
+// reference: https://www.rfc-editor.org/rfc/rfc5280.txt
+// IN DER encoded SubjectPublicKeyInfo slice
+// OUT Ok subjectPublicKey, without the unused bits octet of the BIT STRING
+// OUT Error Mulformed SubjectPublicKeyInfo found
+pub fn get_public_key_from_spki(spki: &[u8]) -> SpdmResult<&[u8]> {
+    if check_and_skip_common_sequence(spki)? != spki.len() {
+        return Err(SPDM_STATUS_VERIF_FAIL);
+    }
+    let (_, bytes_consumed) = check_length(&spki[1..])?;
+    let data = &spki[1 + bytes_consumed..];
+
+    // algorithm AlgorithmIdentifier
+    let data = &data[check_and_skip_common_sequence(data)?..];
+
+    // subjectPublicKey BIT STRING
+    if data.is_empty() || data[0] != ASN1_TAG_NUMBER_BIT_STRING {
+        return Err(SPDM_STATUS_VERIF_FAIL);
+    }
+    let (payload_length, bytes_consumed) = check_length(&data[1..])?;
+    if data.len() != 1 + bytes_consumed + payload_length
+        || payload_length < 2
+        || data[1 + bytes_consumed] != 0
+    {
+        return Err(SPDM_STATUS_VERIF_FAIL);
+    }
+    Ok(&data[2 + bytes_consumed..])
+}
+
+// reference: DSP0274 1.2, device certificate and alias certificate models
+// IN DER encoded certificate chain slice
+// IN true if the chain follows the alias certificate model (ALIAS_CERT_CAP)
+// OUT Ok true if the chain is partial, ending at the device certificate
+// OUT Error the certificate chain does not follow the certificate model
+// checked:
+// 1. the leaf certificate shall not be a CA, except for the device certificate
+//    ending a partial chain in the alias certificate model.
+// 2. in the alias certificate model, the leaf certificate is an alias certificate
+//    whose key is generated by the device, so it shall not carry the hardware identity.
+pub fn check_cert_chain_model(cert_chain: &[u8], is_alias_cert_model: bool) -> SpdmResult<bool> {
+    let leaf_cert = get_leaf_cert(cert_chain)?;
+
+    if is_ca_certificate(leaf_cert)? {
+        if is_alias_cert_model {
+            Ok(true)
+        } else {
+            Err(SPDM_STATUS_VERIF_FAIL)
+        }
+    } else if is_alias_cert_model && has_hardware_identity(leaf_cert)? {
+        Err(SPDM_STATUS_VERIF_FAIL)
+    } else {
+        Ok(false)
+    }
+}
+
+// reference: https://www.rfc-editor.org/rfc/rfc5280.txt
+// IN DER encoded certificate slice
+// OUT Ok true if the basicConstraints extension sets cA
+// OUT Error Mulformed certificate found
+pub fn is_ca_certificate(cert: &[u8]) -> SpdmResult<bool> {
+    let basic_constraints =
+        if let Some(basic_constraints) = find_extension(cert, OID_BASIC_CONSTRAINTS)? {
+            basic_constraints
+        } else {
+            return Ok(false);
+        };
+
+    // BasicConstraints ::= SEQUENCE {
+    //      cA                      BOOLEAN DEFAULT FALSE,
+    //      pathLenConstraint       INTEGER (0..MAX) OPTIONAL }
+    if check_and_skip_common_sequence(basic_constraints)? != basic_constraints.len() {
+        return Err(SPDM_STATUS_VERIF_FAIL);
+    }
+    let (_, bytes_consumed) = check_length(&basic_constraints[1..])?;
+    let data = &basic_constraints[1 + bytes_consumed..];
+
+    Ok(data.len() >= 3 && data[0] == ASN1_TAG_NUMBER_BOOLEAN && data[1] == 1 && data[2] != 0)
+}
+
+// reference: https://www.rfc-editor.org/rfc/rfc5280.txt
+// IN DER encoded certificate slice
+// IN contents of the KeyPurposeId OIDs, any of which is accepted
+// OUT Ok true if the extKeyUsage extension is absent or lists one of the OIDs
+// OUT Error Mulformed certificate found
+pub fn check_extended_key_usage(cert: &[u8], key_purpose_ids: &[&[u8]]) -> SpdmResult<bool> {
+    let ext_key_usage = if let Some(ext_key_usage) = find_extension(cert, OID_EXT_KEY_USAGE)? {
+        ext_key_usage
+    } else {
+        return Ok(true);
+    };
+
+    // ExtKeyUsageSyntax ::= SEQUENCE SIZE (1..MAX) OF KeyPurposeId
+    // KeyPurposeId ::= OBJECT IDENTIFIER
+    if check_and_skip_common_sequence(ext_key_usage)? != ext_key_usage.len() {
+        return Err(SPDM_STATUS_VERIF_FAIL);
+    }
+    let (_, bytes_consumed) = check_length(&ext_key_usage[1..])?;
+    let mut data = &ext_key_usage[1 + bytes_consumed..];
+
+    while !data.is_empty() {
+        let oid_size = check_object_identifier(data, None)?;
+        if key_purpose_ids
+            .iter()
+            .any(|oid| object_identifiers_are_same(&data[2..oid_size], oid))
+        {
+            return Ok(true);
+        }
+        data = &data[oid_size..];
+    }
+
+    Ok(false)
+}
+
+// reference: https://www.rfc-editor.org/rfc/rfc5280.txt
+// IN DER encoded certificate slice
+// OUT Ok (notBefore, notAfter) in seconds since the UNIX epoch
+// OUT Error Mulformed certificate found
+pub fn get_validity(cert: &[u8]) -> SpdmResult<(u64, u64)> {
+    check_tag_is_sequence(cert)?;
+    let (_, bytes_consumed) = check_length(&cert[1..])?;
+    let data = &cert[1 + bytes_consumed..];
+
+    let tbs_size = check_and_skip_common_sequence(data)?;
+    let (_, bytes_consumed) = check_length(&data[1..])?;
+    let data = &data[..tbs_size];
+    let mut t_walker = 1 + bytes_consumed;
+
+    // version         [0]  EXPLICIT Version DEFAULT v1,
+    t_walker += check_version(&data[t_walker..])?;
+
+    // serialNumber, signature, issuer
+    for _ in 0..3 {
+        t_walker += check_and_skip_common_tag(&data[t_walker..])?;
+    }
+
+    // Validity ::= SEQUENCE {
+    //      notBefore      Time,
+    //      notAfter       Time }
+    let validity_size = check_validity(&data[t_walker..])?;
+    let (_, bytes_consumed) = check_length(&data[t_walker + 1..])?;
+    let validity = &data[t_walker + 1 + bytes_consumed..t_walker + validity_size];
+    let not_before_size = check_and_skip_common_tag(validity)?;
+    let not_before = get_time(&validity[..not_before_size])?;
+    let not_after = get_time(&validity[not_before_size..])?;
+
+    Ok((not_before, not_after))
+}
+
+// Time ::= CHOICE {
+//      utcTime        UTCTime,         -- YYMMDDHHMMSSZ
+//      generalTime    GeneralizedTime } -- YYYYMMDDHHMMSSZ
+fn get_time(data: &[u8]) -> SpdmResult<u64> {
+    let time_size = check_and_skip_common_tag(data)?;
+    let (_, bytes_consumed) = check_length(&data[1..])?;
+    let time = &data[1 + bytes_consumed..time_size];
+    if time.last() != Some(&b'Z') || time[..time.len() - 1].iter().any(|c| !c.is_ascii_digit()) {
+        return Err(SPDM_STATUS_VERIF_FAIL);
+    }
+    let digits = |from: usize| (time[from] - b'0') as u64 * 10 + (time[from + 1] - b'0') as u64;
+
+    let (year, rest) = match (data[0], time.len()) {
+        (ASN1_TAG_NUMBER_UTC_TIME, 13) => {
+            // RFC 5280: YY >= 50 is 19YY, otherwise 20YY
+            let yy = digits(0);
+            (if yy >= 50 { 1900 + yy } else { 2000 + yy }, 2)
+        }
+        (ASN1_TAG_NUMBER_GENERALIZED_TIME, 15) => (digits(0) * 100 + digits(2), 4),
+        _ => return Err(SPDM_STATUS_VERIF_FAIL),
+    };
+    let (month, day) = (digits(rest), digits(rest + 2));
+    let (hour, minute, second) = (digits(rest + 4), digits(rest + 6), digits(rest + 8));
+    if year < 1970
+        || !(1..=12).contains(&month)
+        || !(1..=31).contains(&day)
+        || hour > 23
+        || minute > 59
+        || second > 59
+    {
+        return Err(SPDM_STATUS_VERIF_FAIL);
+    }
+
+    // days from the civil date, counting the years from March
+    let (year, month) = if month <= 2 {
+        (year - 1, month + 9)
+    } else {
+        (year, month - 3)
+    };
+    let day_of_year = (153 * month + 2) / 5 + day - 1;
+    let days = year * 365 + year / 4 - year / 100 + year / 400 + day_of_year - 719_468;
+
+    Ok(days * 86400 + hour * 3600 + minute * 60 + second)
+}
+
+// IN DER encoded certificate chain slice
+// OUT Ok the last certificate of the chain
+// OUT Error Mulformed certificate chain found
+fn get_leaf_cert(cert_chain: &[u8]) -> SpdmResult<&[u8]> {
+    let mut cc_walker = 0usize;
+    let mut leaf_cert = None;
+
+    while cc_walker < cert_chain.len() {
+        let cert_size = check_and_skip_common_sequence(&cert_chain[cc_walker..])?;
+        leaf_cert = Some(&cert_chain[cc_walker..cc_walker + cert_size]);
+        cc_walker += cert_size;
+    }
+
+    leaf_cert.ok_or(SPDM_STATUS_VERIF_FAIL)
+}
+
+// The hardware identity OID may be anywhere in the SPDM extension.
+fn has_hardware_identity(cert: &[u8]) -> SpdmResult<bool> {
+    let spdm_extension =
+        if let Some(spdm_extension) = find_extension(cert, OID_DMTF_SPDM_EXTENSION)? {
+            spdm_extension
+        } else {
+            return Ok(false);
+        };
+
+    let oid_length = OID_DMTF_HARDWARE_IDENTITY.len();
+    Ok(spdm_extension.windows(oid_length + 2).any(|w| {
+        w[0] == ASN1_TAG_NUMBER_OBJECT_IDENTIFIER
+            && w[1] as usize == oid_length
+            && object_identifiers_are_same(&w[2..], OID_DMTF_HARDWARE_IDENTITY)
+    }))
+}
+
+// reference: https://www.rfc-editor.org/rfc/rfc5280.txt
+// IN DER encoded certificate slice
+// OUT Ok the extnValue content of the extension, if present
+// OUT Error Mulformed certificate found
+fn find_extension<'a>(cert: &'a [u8], oid: &[u8]) -> SpdmResult<Option<&'a [u8]>> {
+    let extensions = if let Some(extensions) = get_extensions(cert)? {
+        extensions
+    } else {
+        return Ok(None);
+    };
+
+    // Extensions  ::=  SEQUENCE SIZE (1..MAX) OF Extension
+    let extensions_size = check_and_skip_common_sequence(extensions)?;
+    let (_, bytes_consumed) = check_length(&extensions[1..])?;
+    let mut data = &extensions[1 + bytes_consumed..extensions_size];
+
+    while !data.is_empty() {
+        // Extension  ::=  SEQUENCE  {
+        //      extnID      OBJECT IDENTIFIER,
+        //      critical    BOOLEAN DEFAULT FALSE,
+        //      extnValue   OCTET STRING }
+        let extension_size = check_and_skip_common_sequence(data)?;
+        let (_, bytes_consumed) = check_length(&data[1..])?;
+        let extension = &data[1 + bytes_consumed..extension_size];
+        data = &data[extension_size..];
+
+        let oid_size = check_object_identifier(extension, None)?;
+        if !object_identifiers_are_same(&extension[2..oid_size], oid) {
+            continue;
+        }
+
+        let mut e_walker = oid_size;
+        if extension.len() > e_walker && extension[e_walker] == ASN1_TAG_NUMBER_BOOLEAN {
+            e_walker += check_and_skip_common_tag(&extension[e_walker..])?;
+        }
+        if extension.len() <= e_walker || extension[e_walker] != ASN1_TAG_NUMBER_OCTET_STRING {
+            return Err(SPDM_STATUS_VERIF_FAIL);
+        }
+        let value_size = check_and_skip_common_tag(&extension[e_walker..])?;
+        let (_, bytes_consumed) = check_length(&extension[e_walker + 1..])?;
+        return Ok(Some(
+            &extension[e_walker + 1 + bytes_consumed..e_walker + value_size],
+        ));
+    }
+
+    Ok(None)
+}
+
+// IN DER encoded certificate slice
+// OUT Ok the content of the extensions field, if present
+// OUT Error Mulformed certificate found
+fn get_extensions(cert: &[u8]) -> SpdmResult<Option<&[u8]>> {
+    check_tag_is_sequence(cert)?;
+    let (_, bytes_consumed) = check_length(&cert[1..])?;
+    let data = &cert[1 + bytes_consumed..];
+
+    let tbs_size = check_and_skip_common_sequence(data)?;
+    let (_, bytes_consumed) = check_length(&data[1..])?;
+    let data = &data[..tbs_size];
+    let mut t_walker = 1 + bytes_consumed;
+
+    // version         [0]  EXPLICIT Version DEFAULT v1,
+    t_walker += check_version(&data[t_walker..])?;
+
+    // serialNumber, signature, issuer, validity, subject, subjectPublicKeyInfo
+    for _ in 0..6 {
+        t_walker += check_and_skip_common_tag(&data[t_walker..])?;
+    }
+
+    // issuerUniqueID  [1]  IMPLICIT UniqueIdentifier OPTIONAL,
+    // subjectUniqueID [2]  IMPLICIT UniqueIdentifier OPTIONAL,
+    // extensions      [3]  EXPLICIT Extensions OPTIONAL
+    while t_walker < data.len() {
+        let field_size = check_and_skip_common_tag(&data[t_walker..])?;
+        if data[t_walker] == ASN1_TAG_EXTENSIONS {
+            let (_, bytes_consumed) = check_length(&data[t_walker + 1..])?;
+            return Ok(Some(
+                &data[t_walker + 1 + bytes_consumed..t_walker + field_size],
+            ));
+        }
+        t_walker += field_size;
+    }
+
+    Ok(None)
+}
+
 // IN DER encoded certificate slice
 // OUT Ok cert size
 // OUT Error Mulformed certificate found
@@ -295,6 +626,10 @@ fn get_oid_by_base_asym_algo(base_asym_algo: SpdmBaseAsymAlgo) -> Option<&'stati
         SpdmBaseAsymAlgo::TPM_ALG_RSASSA_4096 => Some(OID_RSA_SHA512RSA),
         SpdmBaseAsymAlgo::TPM_ALG_RSAPSS_4096 => Some(OID_RSA_SHA512RSA),
         SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384 => Some(OID_ECDSA_SHA384),
+        SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P521 => Some(OID_ECDSA_SHA512),
+        SpdmBaseAsymAlgo::EDDSA_ED25519 => Some(OID_ED25519),
+        SpdmBaseAsymAlgo::EDDSA_ED448 => Some(OID_ED448),
+        SpdmBaseAsymAlgo::TPM_ALG_SM2_ECC_SM2_P256 => Some(OID_SM2_SM3),
         _ => None,
     }
 }
@@ -340,6 +675,22 @@ mod tests {
             get_oid_by_base_asym_algo(SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P256),
             Some(OID_ECDSA_SHA256)
         );
+        assert_eq!(
+            get_oid_by_base_asym_algo(SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P521),
+            Some(OID_ECDSA_SHA512)
+        );
+        assert_eq!(
+            get_oid_by_base_asym_algo(SpdmBaseAsymAlgo::EDDSA_ED25519),
+            Some(OID_ED25519)
+        );
+        assert_eq!(
+            get_oid_by_base_asym_algo(SpdmBaseAsymAlgo::EDDSA_ED448),
+            Some(OID_ED448)
+        );
+        assert_eq!(
+            get_oid_by_base_asym_algo(SpdmBaseAsymAlgo::TPM_ALG_SM2_ECC_SM2_P256),
+            Some(OID_SM2_SM3)
+        );
     }
 
     #[test]
@@ -368,6 +719,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_case0_get_public_key_from_spki() {
+        let spki = std::fs::read("../test_key/ecp384/end_responder.key.pub.der")
+            .expect("unable to read public key!");
+        let public_key = get_public_key_from_spki(&spki).unwrap();
+        assert_eq!(public_key.len(), 97);
+        assert_eq!(public_key[0], 0x04);
+
+        assert_eq!(
+            get_public_key_from_spki(&spki[..spki.len() - 1]),
+            Err(SPDM_STATUS_VERIF_FAIL)
+        );
+        let mut spki_wrong = spki.clone();
+        spki_wrong[20] = 0x04;
+        assert_eq!(
+            get_public_key_from_spki(&spki_wrong),
+            Err(SPDM_STATUS_VERIF_FAIL)
+        );
+    }
+
     #[test]
     fn test_case0_check_and_skip_common_sequence() {
         let sq1 = [
@@ -692,4 +1063,110 @@ mod tests {
             Err(SPDM_STATUS_VERIF_FAIL)
         );
     }
+
+    #[test]
+    fn test_case0_is_ca_certificate() {
+        let c1 = std::fs::read("../test_key/ecp384/ca.cert.der").expect("unable to read ca cert!");
+        let c2 =
+            std::fs::read("../test_key/ecp384/inter.cert.der").expect("unable to read inter cert!");
+        let c3 = std::fs::read("../test_key/ecp384/end_responder.cert.der")
+            .expect("unable to read leaf cert!");
+
+        assert_eq!(is_ca_certificate(&c1), Ok(true));
+        assert_eq!(is_ca_certificate(&c2), Ok(true));
+        assert_eq!(is_ca_certificate(&c3), Ok(false));
+    }
+
+    #[test]
+    fn test_case0_check_extended_key_usage() {
+        // id-kp-serverAuth 1.3.6.1.5.5.7.3.1
+        let server_auth: &[u8] = &[0x2b, 0x06, 0x01, 0x05, 0x05, 0x07, 0x03, 0x01];
+        // id-DMTF-eku-requester-auth 1.3.6.1.4.1.412.274.3.2
+        let requester_auth: &[u8] = &[
+            0x2b, 0x06, 0x01, 0x04, 0x01, 0x83, 0x1c, 0x82, 0x12, 0x03, 0x02,
+        ];
+        let c1 = std::fs::read("../test_key/ecp384/ca.cert.der").expect("unable to read ca cert!");
+        let c3 = std::fs::read("../test_key/ecp384/end_responder.cert.der")
+            .expect("unable to read leaf cert!");
+
+        assert_eq!(check_extended_key_usage(&c1, &[requester_auth]), Ok(true));
+        assert_eq!(
+            check_extended_key_usage(&c3, &[requester_auth, server_auth]),
+            Ok(true)
+        );
+        assert_eq!(check_extended_key_usage(&c3, &[requester_auth]), Ok(false));
+    }
+
+    #[test]
+    fn test_case0_get_validity() {
+        let c1 = std::fs::read("../test_key/ecp384/ca.cert.der").expect("unable to read ca cert!");
+        // Apr  3 05:55:53 2023 GMT, Mar 31 05:55:53 2033 GMT
+        assert_eq!(get_validity(&c1), Ok((1680501353, 1995861353)));
+        assert_eq!(get_validity(&c1[..100]), Err(SPDM_STATUS_VERIF_FAIL));
+    }
+
+    #[test]
+    fn test_case0_get_time() {
+        assert_eq!(get_time(b"\x17\x0d700101000000Z"), Ok(0));
+        assert_eq!(get_time(b"\x17\x0d000229235959Z"), Ok(951868799));
+        assert_eq!(get_time(b"\x18\x0f20500101000000Z"), Ok(2524608000));
+        assert_eq!(
+            get_time(b"\x17\x0d700101000000+"),
+            Err(SPDM_STATUS_VERIF_FAIL)
+        );
+        assert_eq!(
+            get_time(b"\x18\x0d700101000000Z"),
+            Err(SPDM_STATUS_VERIF_FAIL)
+        );
+        assert_eq!(
+            get_time(b"\x17\x0d701301000000Z"),
+            Err(SPDM_STATUS_VERIF_FAIL)
+        );
+    }
+
+    #[test]
+    fn test_case0_check_cert_chain_model() {
+        let c1 = std::fs::read("../test_key/ecp384/ca.cert.der").expect("unable to read ca cert!");
+        let c2 =
+            std::fs::read("../test_key/ecp384/inter.cert.der").expect("unable to read inter cert!");
+        let full_chain = std::fs::read("../test_key/ecp384/bundle_responder.certchain.der")
+            .expect("unable to read cert chain!");
+        let partial_chain = [c1, c2].concat();
+
+        assert_eq!(check_cert_chain_model(&full_chain, false), Ok(false));
+        // The leaf carries the hardware identity, so it is not an alias certificate.
+        assert_eq!(
+            check_cert_chain_model(&full_chain, true),
+            Err(SPDM_STATUS_VERIF_FAIL)
+        );
+        assert_eq!(check_cert_chain_model(&partial_chain, true), Ok(true));
+        assert_eq!(
+            check_cert_chain_model(&partial_chain, false),
+            Err(SPDM_STATUS_VERIF_FAIL)
+        );
+        assert_eq!(
+            check_cert_chain_model(&[], false),
+            Err(SPDM_STATUS_VERIF_FAIL)
+        );
+    }
+
+    #[test]
+    fn test_case1_check_cert_chain_format() {
+        for (base_asym_algo, dir) in [
+            (SpdmBaseAsymAlgo::EDDSA_ED25519, "ed25519"),
+            (SpdmBaseAsymAlgo::EDDSA_ED448, "ed448"),
+            (SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P521, "ecp521"),
+        ] {
+            let cert_chain = std::fs::read(format!(
+                "../test_key/{}/bundle_responder.certchain.der",
+                dir
+            ))
+            .expect("unable to read cert chain!");
+            assert_eq!(check_cert_chain_format(&cert_chain, base_asym_algo), Ok(3));
+            assert_eq!(
+                check_cert_chain_format(&cert_chain, SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384),
+                Err(SPDM_STATUS_VERIF_FAIL)
+            );
+        }
+    }
 }
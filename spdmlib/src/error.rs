@@ -59,6 +59,7 @@ pub enum StatusCodeCore {
     SESSION_MSG_ERROR = 15,
     ACQUIRE_FAIL = 16,
     SESSION_TRY_DISCARD_KEY_UPDATE = 17,
+    RESET_REQUIRED_PEER = 18,
 
     // only in Rust-SPDM
     DECODE_AEAD_FAIL = 0xFE,
@@ -87,6 +88,7 @@ impl TryFrom<u16> for StatusCodeCore {
             15 => Ok(Self::SESSION_MSG_ERROR),
             16 => Ok(Self::ACQUIRE_FAIL),
             17 => Ok(Self::SESSION_TRY_DISCARD_KEY_UPDATE),
+            18 => Ok(Self::RESET_REQUIRED_PEER),
             0xFE => Ok(Self::DECODE_AEAD_FAIL),
             _ => Err(()),
         }
@@ -515,6 +517,12 @@ pub const SPDM_STATUS_SESSION_TRY_DISCARD_KEY_UPDATE: SpdmStatus = spdm_return_s
     StatusCode::CORE(StatusCodeCore::SESSION_TRY_DISCARD_KEY_UPDATE)
 );
 
+/* Received a ResetRequired error message. */
+pub const SPDM_STATUS_RESET_REQUIRED_PEER: SpdmStatus = spdm_return_status!(
+    StatusSeverity::ERROR,
+    StatusCode::CORE(StatusCodeCore::RESET_REQUIRED_PEER)
+);
+
 /*  Failed to decode AEAD. */
 pub const SPDM_STATUS_DECODE_AEAD_FAIL: SpdmStatus = spdm_return_status!(
     StatusSeverity::ERROR,
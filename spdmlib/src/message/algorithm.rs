@@ -17,8 +17,14 @@ pub struct SpdmNegotiateAlgorithmsRequestPayload {
     pub other_params_support: SpdmOpaqueSupport,
     pub base_asym_algo: SpdmBaseAsymAlgo,
     pub base_hash_algo: SpdmBaseHashAlgo,
+    pub pqc_asym_algo: SpdmPqcAsymAlgo,
+    pub mel_specification: SpdmMelSpecification,
+    pub ext_asym_count: u8,
+    pub ext_asym: [SpdmExtAlgStruct; SPDM_MAX_EXT_ALG_COUNT],
+    pub ext_hash_count: u8,
+    pub ext_hash: [SpdmExtAlgStruct; SPDM_MAX_EXT_ALG_COUNT],
     pub alg_struct_count: u8,
-    pub alg_struct: [SpdmAlgStruct; 4],
+    pub alg_struct: [SpdmAlgStruct; SPDM_MAX_ALG_STRUCT_COUNT],
 }
 
 impl SpdmCodec for SpdmNegotiateAlgorithmsRequestPayload {
@@ -40,7 +46,7 @@ impl SpdmCodec for SpdmNegotiateAlgorithmsRequestPayload {
 
         cnt += 0u8.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param2
 
-        let mut length: u16 = 32;
+        let mut length: u16 = 32 + 4 * (self.ext_asym_count + self.ext_hash_count) as u16;
         if context.negotiate_info.spdm_version_sel.get_u8() >= SpdmVersion::SpdmVersion11.get_u8() {
             let alg_fixed_count = 2u8;
             length += ((2 + alg_fixed_count) * self.alg_struct_count) as u16;
@@ -69,15 +75,49 @@ impl SpdmCodec for SpdmNegotiateAlgorithmsRequestPayload {
             .base_hash_algo
             .encode(bytes)
             .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
-        for _i in 0..12 {
+        if context.negotiate_info.spdm_version_sel.get_u8() >= SpdmVersion::SpdmVersion14.get_u8() {
+            cnt += self
+                .pqc_asym_algo
+                .encode(bytes)
+                .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        } else {
+            cnt += 0u32.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        }
+        for _i in 0..8 {
             cnt += 0u8.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // reserved2
         }
 
-        cnt += 0u8.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // ext_asym_count
+        cnt += self
+            .ext_asym_count
+            .encode(bytes)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+
+        cnt += self
+            .ext_hash_count
+            .encode(bytes)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
 
-        cnt += 0u8.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // ext_hash_count
+        cnt += 0u8.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // reserved3
 
-        cnt += 0u16.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // reserved3
+        if context.negotiate_info.spdm_version_sel.get_u8() >= SpdmVersion::SpdmVersion13.get_u8() {
+            cnt += self
+                .mel_specification
+                .encode(bytes)
+                .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        } else {
+            cnt += 0u8.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        }
+
+        for ext_asym in self.ext_asym.iter().take(self.ext_asym_count as usize) {
+            cnt += ext_asym
+                .encode(bytes)
+                .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        }
+        for ext_hash in self.ext_hash.iter().take(self.ext_hash_count as usize) {
+            cnt += ext_hash
+                .encode(bytes)
+                .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        }
 
         if context.negotiate_info.spdm_version_sel.get_u8() >= SpdmVersion::SpdmVersion11.get_u8() {
             for algo in self.alg_struct.iter().take(self.alg_struct_count as usize) {
@@ -94,7 +134,7 @@ impl SpdmCodec for SpdmNegotiateAlgorithmsRequestPayload {
         let mut alg_struct_count = 0;
         if context.negotiate_info.spdm_version_sel.get_u8() >= SpdmVersion::SpdmVersion11.get_u8() {
             alg_struct_count = u8::read(r)?; // param1
-            if alg_struct_count > 4 {
+            if alg_struct_count as usize > SPDM_MAX_ALG_STRUCT_COUNT {
                 return None;
             }
         } else {
@@ -105,7 +145,7 @@ impl SpdmCodec for SpdmNegotiateAlgorithmsRequestPayload {
         let length = u16::read(r)?;
         let measurement_specification = SpdmMeasurementSpecification::read(r)?;
 
-        let other_params_support = if context.negotiate_info.spdm_version_sel.get_u8()
+        let mut other_params_support = if context.negotiate_info.spdm_version_sel.get_u8()
             >= SpdmVersion::SpdmVersion12.get_u8()
         {
             SpdmOpaqueSupport::read(r)?
@@ -113,32 +153,59 @@ impl SpdmCodec for SpdmNegotiateAlgorithmsRequestPayload {
             u8::read(r)?;
             SpdmOpaqueSupport::default()
         };
+        if context.negotiate_info.spdm_version_sel.get_u8() < SpdmVersion::SpdmVersion13.get_u8() {
+            other_params_support.remove(SpdmOpaqueSupport::MULTI_KEY_CONN);
+        }
 
         let base_asym_algo = SpdmBaseAsymAlgo::read(r)?;
         let base_hash_algo = SpdmBaseHashAlgo::read(r)?;
 
-        for _i in 0..12 {
+        let pqc_asym_algo = if context.negotiate_info.spdm_version_sel.get_u8()
+            >= SpdmVersion::SpdmVersion14.get_u8()
+        {
+            SpdmPqcAsymAlgo::read(r)?
+        } else {
+            u32::read(r)?;
+            SpdmPqcAsymAlgo::default()
+        };
+
+        for _i in 0..8 {
             u8::read(r)?; // reserved2
         }
 
         let ext_asym_count = u8::read(r)?;
-        if ext_asym_count != 0 {
-            return None;
-        }
-
         let ext_hash_count = u8::read(r)?;
-        if ext_hash_count != 0 {
+        if ext_asym_count as usize + ext_hash_count as usize > SPDM_MAX_EXT_ALG_COUNT {
             return None;
         }
 
-        u16::read(r)?; // reserved3
+        u8::read(r)?; // reserved3
+
+        let mel_specification = if context.negotiate_info.spdm_version_sel.get_u8()
+            >= SpdmVersion::SpdmVersion13.get_u8()
+        {
+            SpdmMelSpecification::read(r)?
+        } else {
+            u8::read(r)?;
+            SpdmMelSpecification::default()
+        };
+
+        let mut ext_asym = [SpdmExtAlgStruct::default(); SPDM_MAX_EXT_ALG_COUNT];
+        for ext_asym in ext_asym.iter_mut().take(ext_asym_count as usize) {
+            *ext_asym = SpdmExtAlgStruct::read(r)?;
+        }
+        let mut ext_hash = [SpdmExtAlgStruct::default(); SPDM_MAX_EXT_ALG_COUNT];
+        for ext_hash in ext_hash.iter_mut().take(ext_hash_count as usize) {
+            *ext_hash = SpdmExtAlgStruct::read(r)?;
+        }
 
-        let mut alg_struct = gen_array_clone(SpdmAlgStruct::default(), 4);
+        let mut alg_struct = gen_array_clone(SpdmAlgStruct::default(), SPDM_MAX_ALG_STRUCT_COUNT);
         if context.negotiate_info.spdm_version_sel.get_u8() >= SpdmVersion::SpdmVersion11.get_u8() {
             let mut dhe_present = false;
             let mut aead_present = false;
             let mut req_asym_present = false;
             let mut key_schedule_present = false;
+            let mut kem_present = false;
             let mut current_type = SpdmAlgType::Unknown(0);
             for algo in alg_struct.iter_mut().take(alg_struct_count as usize) {
                 let alg = SpdmAlgStruct::read(r)?;
@@ -171,6 +238,15 @@ impl SpdmCodec for SpdmNegotiateAlgorithmsRequestPayload {
                         }
                         key_schedule_present = true;
                     }
+                    SpdmAlg::SpdmAlgoKem(_) => {
+                        if kem_present
+                            || context.negotiate_info.spdm_version_sel.get_u8()
+                                < SpdmVersion::SpdmVersion14.get_u8()
+                        {
+                            return None;
+                        }
+                        kem_present = true;
+                    }
                     SpdmAlg::SpdmAlgoUnknown(_) => {
                         return None;
                     }
@@ -182,7 +258,7 @@ impl SpdmCodec for SpdmNegotiateAlgorithmsRequestPayload {
         //
         // check length
         //
-        let mut calc_length: u16 = 32;
+        let mut calc_length: u16 = 32 + 4 * (ext_asym_count + ext_hash_count) as u16;
         if context.negotiate_info.spdm_version_sel.get_u8() >= SpdmVersion::SpdmVersion11.get_u8() {
             let alg_fixed_count = 2u8;
             calc_length += ((2 + alg_fixed_count) * alg_struct_count) as u16;
@@ -197,6 +273,12 @@ impl SpdmCodec for SpdmNegotiateAlgorithmsRequestPayload {
             other_params_support,
             base_asym_algo,
             base_hash_algo,
+            pqc_asym_algo,
+            mel_specification,
+            ext_asym_count,
+            ext_asym,
+            ext_hash_count,
+            ext_hash,
             alg_struct_count,
             alg_struct,
         })
@@ -210,8 +292,12 @@ pub struct SpdmAlgorithmsResponsePayload {
     pub measurement_hash_algo: SpdmMeasurementHashAlgo,
     pub base_asym_sel: SpdmBaseAsymAlgo,
     pub base_hash_sel: SpdmBaseHashAlgo,
+    pub pqc_asym_sel: SpdmPqcAsymAlgo,
+    pub mel_specification_sel: SpdmMelSpecification,
+    pub ext_asym_sel: Option<SpdmExtAlgStruct>,
+    pub ext_hash_sel: Option<SpdmExtAlgStruct>,
     pub alg_struct_count: u8,
-    pub alg_struct: [SpdmAlgStruct; 4],
+    pub alg_struct: [SpdmAlgStruct; SPDM_MAX_ALG_STRUCT_COUNT],
 }
 
 impl SpdmCodec for SpdmAlgorithmsResponsePayload {
@@ -233,7 +319,9 @@ impl SpdmCodec for SpdmAlgorithmsResponsePayload {
 
         cnt += 0u8.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param2
 
-        let mut length: u16 = 36;
+        let ext_asym_sel_count = self.ext_asym_sel.is_some() as u8;
+        let ext_hash_sel_count = self.ext_hash_sel.is_some() as u8;
+        let mut length: u16 = 36 + 4 * (ext_asym_sel_count + ext_hash_sel_count) as u16;
         if context.negotiate_info.spdm_version_sel.get_u8() >= SpdmVersion::SpdmVersion11.get_u8() {
             let alg_fixed_count = 2u8;
             length += ((2 + alg_fixed_count) * self.alg_struct_count) as u16;
@@ -266,16 +354,48 @@ impl SpdmCodec for SpdmAlgorithmsResponsePayload {
             .base_hash_sel
             .encode(bytes)
             .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
-        for _i in 0..12 {
+        if context.negotiate_info.spdm_version_sel.get_u8() >= SpdmVersion::SpdmVersion14.get_u8() {
+            cnt += self
+                .pqc_asym_sel
+                .encode(bytes)
+                .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        } else {
+            cnt += 0u32.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        }
+        for _i in 0..7 {
             cnt += 0u8.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // reserved2
         }
 
-        cnt += 0u8.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // ext_asym_count
+        if context.negotiate_info.spdm_version_sel.get_u8() >= SpdmVersion::SpdmVersion13.get_u8() {
+            cnt += self
+                .mel_specification_sel
+                .encode(bytes)
+                .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        } else {
+            cnt += 0u8.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        }
 
-        cnt += 0u8.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // ext_hash_count
+        cnt += ext_asym_sel_count
+            .encode(bytes)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+
+        cnt += ext_hash_sel_count
+            .encode(bytes)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
 
         cnt += 0u16.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // reserved3
 
+        if let Some(ext_asym_sel) = &self.ext_asym_sel {
+            cnt += ext_asym_sel
+                .encode(bytes)
+                .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        }
+        if let Some(ext_hash_sel) = &self.ext_hash_sel {
+            cnt += ext_hash_sel
+                .encode(bytes)
+                .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        }
+
         if context.negotiate_info.spdm_version_sel.get_u8() >= SpdmVersion::SpdmVersion11.get_u8() {
             for algo in self.alg_struct.iter().take(self.alg_struct_count as usize) {
                 cnt += algo.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
@@ -291,7 +411,7 @@ impl SpdmCodec for SpdmAlgorithmsResponsePayload {
         let mut alg_struct_count = 0;
         if context.negotiate_info.spdm_version_sel.get_u8() >= SpdmVersion::SpdmVersion11.get_u8() {
             alg_struct_count = u8::read(r)?; // param1
-            if alg_struct_count > 4 {
+            if alg_struct_count as usize > SPDM_MAX_ALG_STRUCT_COUNT {
                 return None;
             }
         } else {
@@ -318,7 +438,7 @@ impl SpdmCodec for SpdmAlgorithmsResponsePayload {
             return None;
         }
 
-        let other_params_selection = if context.negotiate_info.spdm_version_sel.get_u8()
+        let mut other_params_selection = if context.negotiate_info.spdm_version_sel.get_u8()
             >= SpdmVersion::SpdmVersion12.get_u8()
         {
             SpdmOpaqueSupport::read(r)?
@@ -326,6 +446,9 @@ impl SpdmCodec for SpdmAlgorithmsResponsePayload {
             u8::read(r)?;
             SpdmOpaqueSupport::default()
         };
+        if context.negotiate_info.spdm_version_sel.get_u8() < SpdmVersion::SpdmVersion13.get_u8() {
+            other_params_selection.remove(SpdmOpaqueSupport::MULTI_KEY_CONN);
+        }
         if !other_params_selection.is_no_more_than_one_selected() {
             return None;
         }
@@ -368,7 +491,7 @@ impl SpdmCodec for SpdmAlgorithmsResponsePayload {
         if !base_asym_sel.is_no_more_than_one_selected() {
             return None;
         }
-        if (context
+        let base_asym_required = context
             .negotiate_info
             .rsp_capabilities_sel
             .contains(SpdmResponseCapabilityFlags::CERT_CAP)
@@ -387,17 +510,13 @@ impl SpdmCodec for SpdmAlgorithmsResponsePayload {
                 && context
                     .negotiate_info
                     .req_capabilities_sel
-                    .contains(SpdmRequestCapabilityFlags::KEY_EX_CAP)))
-            && !base_asym_sel.is_valid_one_select()
-        {
-            return None;
-        }
+                    .contains(SpdmRequestCapabilityFlags::KEY_EX_CAP));
 
         let base_hash_sel = SpdmBaseHashAlgo::read(r)?;
         if !base_hash_sel.is_no_more_than_one_selected() {
             return None;
         }
-        if (context
+        let base_hash_required = context
             .negotiate_info
             .rsp_capabilities_sel
             .contains(SpdmResponseCapabilityFlags::CERT_CAP)
@@ -428,34 +547,99 @@ impl SpdmCodec for SpdmAlgorithmsResponsePayload {
                 && context
                     .negotiate_info
                     .req_capabilities_sel
-                    .contains(SpdmRequestCapabilityFlags::PSK_CAP)))
-            && !base_hash_sel.is_valid_one_select()
+                    .contains(SpdmRequestCapabilityFlags::PSK_CAP));
+
+        let pqc_asym_sel = if context.negotiate_info.spdm_version_sel.get_u8()
+            >= SpdmVersion::SpdmVersion14.get_u8()
+        {
+            SpdmPqcAsymAlgo::read(r)?
+        } else {
+            u32::read(r)?;
+            SpdmPqcAsymAlgo::default()
+        };
+        if !pqc_asym_sel.is_no_more_than_one_selected()
+            || (!context.is_pqc_supported() && pqc_asym_sel.bits() != 0)
         {
             return None;
         }
 
-        for _i in 0..12 {
+        for _i in 0..7 {
             u8::read(r)?; // reserved2
         }
 
-        let ext_asym_count = u8::read(r)?;
-        if ext_asym_count != 0 {
+        let mel_specification_sel = if context.negotiate_info.spdm_version_sel.get_u8()
+            >= SpdmVersion::SpdmVersion13.get_u8()
+        {
+            SpdmMelSpecification::read(r)?
+        } else {
+            u8::read(r)?;
+            SpdmMelSpecification::default()
+        };
+        if !mel_specification_sel.is_no_more_than_one_selected() {
+            return None;
+        }
+        if context
+            .negotiate_info
+            .rsp_capabilities_sel
+            .contains(SpdmResponseCapabilityFlags::MEL_CAP)
+            && !mel_specification_sel.is_valid_one_select()
+        {
             return None;
         }
 
-        let ext_hash_count = u8::read(r)?;
-        if ext_hash_count != 0 {
+        let ext_asym_sel_count = u8::read(r)?;
+        if ext_asym_sel_count > 1 {
+            return None;
+        }
+
+        let ext_hash_sel_count = u8::read(r)?;
+        if ext_hash_sel_count > 1 {
             return None;
         }
 
         u16::read(r)?; // reserved3
 
-        let mut alg_struct = gen_array_clone(SpdmAlgStruct::default(), 4);
+        let ext_asym_sel = if ext_asym_sel_count == 1 {
+            Some(SpdmExtAlgStruct::read(r)?)
+        } else {
+            None
+        };
+        let ext_hash_sel = if ext_hash_sel_count == 1 {
+            Some(SpdmExtAlgStruct::read(r)?)
+        } else {
+            None
+        };
+
+        // An extended or PQC algorithm selection replaces the base algorithm selection.
+        if ext_asym_sel.is_some() && base_asym_sel.bits() != 0 {
+            return None;
+        }
+        if pqc_asym_sel.bits() != 0 && (ext_asym_sel.is_some() || base_asym_sel.bits() != 0) {
+            return None;
+        }
+        if base_asym_required
+            && ext_asym_sel.is_none()
+            && pqc_asym_sel.bits() == 0
+            && !base_asym_sel.is_valid_one_select()
+        {
+            return None;
+        }
+        if ext_hash_sel.is_some() && base_hash_sel.bits() != 0 {
+            return None;
+        }
+        if base_hash_required && ext_hash_sel.is_none() && !base_hash_sel.is_valid_one_select() {
+            return None;
+        }
+
+        let mut alg_struct = gen_array_clone(SpdmAlgStruct::default(), SPDM_MAX_ALG_STRUCT_COUNT);
         if context.negotiate_info.spdm_version_sel.get_u8() >= SpdmVersion::SpdmVersion11.get_u8() {
             let mut dhe_present = false;
             let mut aead_present = false;
             let mut req_asym_present = false;
             let mut key_schedule_present = false;
+            let mut kem_present = false;
+            let mut dhe_sel = SpdmDheAlgo::empty();
+            let mut kem_sel = SpdmKemAlgo::empty();
             let mut current_type = SpdmAlgType::Unknown(0);
             for algo in alg_struct.iter_mut().take(alg_struct_count as usize) {
                 let alg = SpdmAlgStruct::read(r)?;
@@ -469,22 +653,10 @@ impl SpdmCodec for SpdmAlgorithmsResponsePayload {
                             return None;
                         }
                         dhe_present = true;
-                        let dhe_sel = v;
+                        dhe_sel = v;
                         if !dhe_sel.is_no_more_than_one_selected() {
                             return None;
                         }
-                        if (context
-                            .negotiate_info
-                            .rsp_capabilities_sel
-                            .contains(SpdmResponseCapabilityFlags::KEY_EX_CAP)
-                            && context
-                                .negotiate_info
-                                .req_capabilities_sel
-                                .contains(SpdmRequestCapabilityFlags::KEY_EX_CAP))
-                            && !dhe_sel.is_valid_one_select()
-                        {
-                            return None;
-                        }
                     }
                     SpdmAlg::SpdmAlgoAead(v) => {
                         if aead_present {
@@ -572,15 +744,49 @@ impl SpdmCodec for SpdmAlgorithmsResponsePayload {
                             return None;
                         }
                     }
+                    SpdmAlg::SpdmAlgoKem(v) => {
+                        if kem_present
+                            || context.negotiate_info.spdm_version_sel.get_u8()
+                                < SpdmVersion::SpdmVersion14.get_u8()
+                        {
+                            return None;
+                        }
+                        kem_present = true;
+                        kem_sel = v;
+                        if !kem_sel.is_no_more_than_one_selected()
+                            || (!context.is_pqc_supported() && kem_sel.bits() != 0)
+                        {
+                            return None;
+                        }
+                    }
                     SpdmAlg::SpdmAlgoUnknown(_v) => {
                         return None;
                     }
                 }
                 *algo = alg;
             }
+
+            // A KEM selection replaces the DHE selection.
+            if dhe_sel.bits() != 0 && kem_sel.bits() != 0 {
+                return None;
+            }
+            if (dhe_present || kem_present)
+                && (context
+                    .negotiate_info
+                    .rsp_capabilities_sel
+                    .contains(SpdmResponseCapabilityFlags::KEY_EX_CAP)
+                    && context
+                        .negotiate_info
+                        .req_capabilities_sel
+                        .contains(SpdmRequestCapabilityFlags::KEY_EX_CAP))
+                && !dhe_sel.is_valid_one_select()
+                && !kem_sel.is_valid_one_select()
+            {
+                return None;
+            }
         }
 
-        let mut calc_length: u16 = 36;
+        let mut calc_length: u16 = 36 + 4 * (ext_asym_sel_count + ext_hash_sel_count) as u16;
         if context.negotiate_info.spdm_version_sel.get_u8() >= SpdmVersion::SpdmVersion11.get_u8() {
             let alg_fixed_count = 2u8;
             calc_length += ((2 + alg_fixed_count) * alg_struct_count) as u16;
@@ -596,6 +802,10 @@ impl SpdmCodec for SpdmAlgorithmsResponsePayload {
             measurement_hash_algo,
             base_asym_sel,
             base_hash_sel,
+            pqc_asym_sel,
+            mel_specification_sel,
+            ext_asym_sel,
+            ext_hash_sel,
             alg_struct_count,
             alg_struct,
         })
@@ -621,6 +831,12 @@ mod tests {
             other_params_support: SpdmOpaqueSupport::empty(),
             base_asym_algo: SpdmBaseAsymAlgo::TPM_ALG_RSASSA_2048,
             base_hash_algo: SpdmBaseHashAlgo::TPM_ALG_SHA_256,
+            pqc_asym_algo: SpdmPqcAsymAlgo::empty(),
+            mel_specification: SpdmMelSpecification::empty(),
+            ext_asym_count: 0,
+            ext_asym: [SpdmExtAlgStruct::default(); SPDM_MAX_EXT_ALG_COUNT],
+            ext_hash_count: 0,
+            ext_hash: [SpdmExtAlgStruct::default(); SPDM_MAX_EXT_ALG_COUNT],
             alg_struct_count: 4,
             alg_struct: [
                 SpdmAlgStruct {
@@ -643,6 +859,8 @@ mod tests {
                         SpdmKeyScheduleAlgo::SPDM_KEY_SCHEDULE,
                     ),
                 },
+                #[cfg(feature = "pqc")]
+                SpdmAlgStruct::default(),
             ],
         };
         let transport_encap = &mut TransportEncap {};
@@ -714,8 +932,14 @@ mod tests {
             other_params_support: SpdmOpaqueSupport::empty(),
             base_asym_algo: SpdmBaseAsymAlgo::empty(),
             base_hash_algo: SpdmBaseHashAlgo::empty(),
+            pqc_asym_algo: SpdmPqcAsymAlgo::empty(),
+            mel_specification: SpdmMelSpecification::empty(),
+            ext_asym_count: 0,
+            ext_asym: [SpdmExtAlgStruct::default(); SPDM_MAX_EXT_ALG_COUNT],
+            ext_hash_count: 0,
+            ext_hash: [SpdmExtAlgStruct::default(); SPDM_MAX_EXT_ALG_COUNT],
             alg_struct_count: 0,
-            alg_struct: gen_array_clone(SpdmAlgStruct::default(), 4),
+            alg_struct: gen_array_clone(SpdmAlgStruct::default(), SPDM_MAX_ALG_STRUCT_COUNT),
         };
 
         let transport_encap = &mut TransportEncap {};
@@ -748,8 +972,14 @@ mod tests {
             other_params_support: SpdmOpaqueSupport::empty(),
             base_asym_algo: SpdmBaseAsymAlgo::TPM_ALG_RSASSA_2048,
             base_hash_algo: SpdmBaseHashAlgo::TPM_ALG_SHA_256,
+            pqc_asym_algo: SpdmPqcAsymAlgo::empty(),
+            mel_specification: SpdmMelSpecification::empty(),
+            ext_asym_count: 0,
+            ext_asym: [SpdmExtAlgStruct::default(); SPDM_MAX_EXT_ALG_COUNT],
+            ext_hash_count: 0,
+            ext_hash: [SpdmExtAlgStruct::default(); SPDM_MAX_EXT_ALG_COUNT],
             alg_struct_count: 0,
-            alg_struct: gen_array_clone(SpdmAlgStruct::default(), 4),
+            alg_struct: gen_array_clone(SpdmAlgStruct::default(), SPDM_MAX_ALG_STRUCT_COUNT),
         };
 
         let transport_encap = &mut TransportEncap {};
@@ -778,6 +1008,10 @@ mod tests {
             measurement_hash_algo: SpdmMeasurementHashAlgo::RAW_BIT_STREAM,
             base_asym_sel: SpdmBaseAsymAlgo::TPM_ALG_RSASSA_2048,
             base_hash_sel: SpdmBaseHashAlgo::TPM_ALG_SHA_256,
+            pqc_asym_sel: SpdmPqcAsymAlgo::empty(),
+            mel_specification_sel: SpdmMelSpecification::empty(),
+            ext_asym_sel: None,
+            ext_hash_sel: None,
             alg_struct_count: 4,
             alg_struct: [
                 SpdmAlgStruct {
@@ -800,6 +1034,8 @@ mod tests {
                         SpdmKeyScheduleAlgo::SPDM_KEY_SCHEDULE,
                     ),
                 },
+                #[cfg(feature = "pqc")]
+                SpdmAlgStruct::default(),
             ],
         };
 
@@ -881,8 +1117,12 @@ mod tests {
             measurement_hash_algo: SpdmMeasurementHashAlgo::RAW_BIT_STREAM,
             base_asym_sel: SpdmBaseAsymAlgo::TPM_ALG_RSASSA_2048,
             base_hash_sel: SpdmBaseHashAlgo::TPM_ALG_SHA_256,
+            pqc_asym_sel: SpdmPqcAsymAlgo::empty(),
+            mel_specification_sel: SpdmMelSpecification::empty(),
+            ext_asym_sel: None,
+            ext_hash_sel: None,
             alg_struct_count: 0,
-            alg_struct: gen_array_clone(SpdmAlgStruct::default(), 4),
+            alg_struct: gen_array_clone(SpdmAlgStruct::default(), SPDM_MAX_ALG_STRUCT_COUNT),
         };
 
         create_spdm_context!(context);
@@ -909,8 +1149,12 @@ mod tests {
             measurement_hash_algo: SpdmMeasurementHashAlgo::empty(),
             base_asym_sel: SpdmBaseAsymAlgo::empty(),
             base_hash_sel: SpdmBaseHashAlgo::empty(),
+            pqc_asym_sel: SpdmPqcAsymAlgo::empty(),
+            mel_specification_sel: SpdmMelSpecification::empty(),
+            ext_asym_sel: None,
+            ext_hash_sel: None,
             alg_struct_count: 0,
-            alg_struct: gen_array_clone(SpdmAlgStruct::default(), 4),
+            alg_struct: gen_array_clone(SpdmAlgStruct::default(), SPDM_MAX_ALG_STRUCT_COUNT),
         };
 
         let transport_encap = &mut TransportEncap {};
@@ -938,6 +1182,157 @@ mod tests {
         assert_eq!(spdm_sturct_data.alg_struct_count, 0);
         assert_eq!(16, reader.left());
     }
+    #[test]
+    fn test_case3_spdm_negotiate_algorithms_request_payload() {
+        let u8_slice = &mut [0u8; 48];
+        let mut writer = Writer::init(u8_slice);
+        let ext_alg = SpdmExtAlgStruct {
+            registry_id: SpdmStandardId::SpdmStandardIdIANA,
+            algorithm_id: 0x1234,
+        };
+        let mut ext_asym = [SpdmExtAlgStruct::default(); SPDM_MAX_EXT_ALG_COUNT];
+        ext_asym[0] = ext_alg;
+        ext_asym[1] = SpdmExtAlgStruct {
+            registry_id: SpdmStandardId::SpdmStandardIdTCG,
+            algorithm_id: 0x5678,
+        };
+        let mut ext_hash = [SpdmExtAlgStruct::default(); SPDM_MAX_EXT_ALG_COUNT];
+        ext_hash[0] = ext_alg;
+        let value = SpdmNegotiateAlgorithmsRequestPayload {
+            measurement_specification: SpdmMeasurementSpecification::DMTF,
+            other_params_support: SpdmOpaqueSupport::empty(),
+            base_asym_algo: SpdmBaseAsymAlgo::empty(),
+            base_hash_algo: SpdmBaseHashAlgo::TPM_ALG_SHA_256,
+            pqc_asym_algo: SpdmPqcAsymAlgo::empty(),
+            mel_specification: SpdmMelSpecification::empty(),
+            ext_asym_count: 2,
+            ext_asym,
+            ext_hash_count: 1,
+            ext_hash,
+            alg_struct_count: 0,
+            alg_struct: gen_array_clone(SpdmAlgStruct::default(), SPDM_MAX_ALG_STRUCT_COUNT),
+        };
+        create_spdm_context!(context);
+        context.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion10;
+
+        assert!(value.spdm_encode(&mut context, &mut writer).is_ok());
+        assert_eq!(42, writer.used());
+        let mut reader = Reader::init(u8_slice);
+        let spdm_sturct_data =
+            SpdmNegotiateAlgorithmsRequestPayload::spdm_read(&mut context, &mut reader).unwrap();
+        assert_eq!(spdm_sturct_data.ext_asym_count, 2);
+        assert_eq!(spdm_sturct_data.ext_asym[..2], value.ext_asym[..2]);
+        assert_eq!(spdm_sturct_data.ext_hash_count, 1);
+        assert_eq!(spdm_sturct_data.ext_hash[0], ext_alg);
+        assert_eq!(reader.used(), 42);
+    }
+    #[test]
+    fn test_case3_spdm_algorithms_response_payload() {
+        let u8_slice = &mut [0u8; 48];
+        let ext_alg = SpdmExtAlgStruct {
+            registry_id: SpdmStandardId::SpdmStandardIdIANA,
+            algorithm_id: 0x1234,
+        };
+        let mut value = SpdmAlgorithmsResponsePayload {
+            measurement_specification_sel: SpdmMeasurementSpecification::DMTF,
+            other_params_selection: SpdmOpaqueSupport::empty(),
+            measurement_hash_algo: SpdmMeasurementHashAlgo::TPM_ALG_SHA_256,
+            base_asym_sel: SpdmBaseAsymAlgo::empty(),
+            base_hash_sel: SpdmBaseHashAlgo::TPM_ALG_SHA_256,
+            pqc_asym_sel: SpdmPqcAsymAlgo::empty(),
+            mel_specification_sel: SpdmMelSpecification::empty(),
+            ext_asym_sel: Some(ext_alg),
+            ext_hash_sel: None,
+            alg_struct_count: 0,
+            alg_struct: gen_array_clone(SpdmAlgStruct::default(), SPDM_MAX_ALG_STRUCT_COUNT),
+        };
+        create_spdm_context!(context);
+        context.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion10;
+        context.negotiate_info.rsp_capabilities_sel = SpdmResponseCapabilityFlags::CHAL_CAP;
+
+        let mut writer = Writer::init(u8_slice);
+        assert!(value.spdm_encode(&mut context, &mut writer).is_ok());
+        assert_eq!(38, writer.used());
+        let mut reader = Reader::init(u8_slice);
+        let spdm_sturct_data =
+            SpdmAlgorithmsResponsePayload::spdm_read(&mut context, &mut reader).unwrap();
+        assert_eq!(spdm_sturct_data.ext_asym_sel, Some(ext_alg));
+        assert_eq!(spdm_sturct_data.ext_hash_sel, None);
+        assert_eq!(reader.used(), 38);
+
+        // An extended selection can't be combined with a base selection.
+        value.base_asym_sel = SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384;
+        let mut writer = Writer::init(u8_slice);
+        assert!(value.spdm_encode(&mut context, &mut writer).is_ok());
+        let mut reader = Reader::init(u8_slice);
+        assert!(SpdmAlgorithmsResponsePayload::spdm_read(&mut context, &mut reader).is_none());
+    }
+    #[test]
+    #[cfg(feature = "pqc")]
+    fn test_case4_spdm_algorithms_response_payload() {
+        let u8_slice = &mut [0u8; 64];
+        let mut alg_struct = gen_array_clone(SpdmAlgStruct::default(), SPDM_MAX_ALG_STRUCT_COUNT);
+        alg_struct[0] = SpdmAlgStruct {
+            alg_type: SpdmAlgType::SpdmAlgTypeAEAD,
+            alg_supported: SpdmAlg::SpdmAlgoAead(SpdmAeadAlgo::AES_256_GCM),
+        };
+        alg_struct[1] = SpdmAlgStruct {
+            alg_type: SpdmAlgType::SpdmAlgTypeKeySchedule,
+            alg_supported: SpdmAlg::SpdmAlgoKeySchedule(SpdmKeyScheduleAlgo::SPDM_KEY_SCHEDULE),
+        };
+        alg_struct[2] = SpdmAlgStruct {
+            alg_type: SpdmAlgType::SpdmAlgTypeKEM,
+            alg_supported: SpdmAlg::SpdmAlgoKem(SpdmKemAlgo::ML_KEM_768),
+        };
+        let mut value = SpdmAlgorithmsResponsePayload {
+            measurement_specification_sel: SpdmMeasurementSpecification::DMTF,
+            other_params_selection: SpdmOpaqueSupport::OPAQUE_DATA_FMT1,
+            measurement_hash_algo: SpdmMeasurementHashAlgo::TPM_ALG_SHA_384,
+            base_asym_sel: SpdmBaseAsymAlgo::empty(),
+            base_hash_sel: SpdmBaseHashAlgo::TPM_ALG_SHA_384,
+            pqc_asym_sel: SpdmPqcAsymAlgo::ML_DSA_65,
+            mel_specification_sel: SpdmMelSpecification::empty(),
+            ext_asym_sel: None,
+            ext_hash_sel: None,
+            alg_struct_count: 3,
+            alg_struct,
+        };
+        create_spdm_context!(context);
+        context.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion14;
+        context.negotiate_info.req_capabilities_sel = SpdmRequestCapabilityFlags::KEY_EX_CAP;
+        context.negotiate_info.rsp_capabilities_sel =
+            SpdmResponseCapabilityFlags::CHAL_CAP | SpdmResponseCapabilityFlags::KEY_EX_CAP;
+
+        let mut writer = Writer::init(u8_slice);
+        assert!(value.spdm_encode(&mut context, &mut writer).is_ok());
+        let used = writer.used();
+        let mut reader = Reader::init(u8_slice);
+        let spdm_sturct_data =
+            SpdmAlgorithmsResponsePayload::spdm_read(&mut context, &mut reader).unwrap();
+        assert_eq!(spdm_sturct_data.pqc_asym_sel, SpdmPqcAsymAlgo::ML_DSA_65);
+        assert_eq!(spdm_sturct_data.base_asym_sel, SpdmBaseAsymAlgo::empty());
+        assert_eq!(spdm_sturct_data.alg_struct_count, 3);
+        assert_eq!(
+            spdm_sturct_data.alg_struct[2].alg_supported,
+            SpdmAlg::SpdmAlgoKem(SpdmKemAlgo::ML_KEM_768)
+        );
+        assert_eq!(reader.used(), used);
+
+        // The KEM algorithm structure is only defined since SPDM 1.4.
+        context.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion13;
+        let mut writer = Writer::init(u8_slice);
+        assert!(value.spdm_encode(&mut context, &mut writer).is_ok());
+        let mut reader = Reader::init(u8_slice);
+        assert!(SpdmAlgorithmsResponsePayload::spdm_read(&mut context, &mut reader).is_none());
+
+        // A PQC selection can't be combined with a base selection.
+        context.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion14;
+        value.base_asym_sel = SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384;
+        let mut writer = Writer::init(u8_slice);
+        assert!(value.spdm_encode(&mut context, &mut writer).is_ok());
+        let mut reader = Reader::init(u8_slice);
+        assert!(SpdmAlgorithmsResponsePayload::spdm_read(&mut context, &mut reader).is_none());
+    }
 }
 
 #[cfg(all(test,))]
@@ -8,6 +8,26 @@ use crate::message::*;
 use crate::{common, error::SpdmStatus};
 use codec::{Codec, Reader, Writer};
 
+// Capability bits that are reserved before SpdmVersion13
+fn spdm13_request_capability_flags() -> SpdmRequestCapabilityFlags {
+    SpdmRequestCapabilityFlags::EP_INFO_CAP_NO_SIG
+        | SpdmRequestCapabilityFlags::EP_INFO_CAP_SIG
+        | SpdmRequestCapabilityFlags::EVENT_CAP
+        | SpdmRequestCapabilityFlags::MULTI_KEY_CAP_ONLY
+        | SpdmRequestCapabilityFlags::MULTI_KEY_CAP_NEG
+}
+
+fn spdm13_response_capability_flags() -> SpdmResponseCapabilityFlags {
+    SpdmResponseCapabilityFlags::EP_INFO_CAP_NO_SIG
+        | SpdmResponseCapabilityFlags::EP_INFO_CAP_SIG
+        | SpdmResponseCapabilityFlags::MEL_CAP
+        | SpdmResponseCapabilityFlags::EVENT_CAP
+        | SpdmResponseCapabilityFlags::MULTI_KEY_CAP_ONLY
+        | SpdmResponseCapabilityFlags::MULTI_KEY_CAP_NEG
+        | SpdmResponseCapabilityFlags::GET_KEY_PAIR_INFO_CAP
+        | SpdmResponseCapabilityFlags::SET_KEY_PAIR_INFO_CAP
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct SpdmGetCapabilitiesRequestPayload {
     pub ct_exponent: u8,
@@ -34,10 +54,13 @@ impl SpdmCodec for SpdmGetCapabilitiesRequestPayload {
                 .encode(bytes)
                 .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
             cnt += 0u16.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // reserved2
-            cnt += self
-                .flags
-                .encode(bytes)
-                .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+            let mut flags = self.flags;
+            if context.negotiate_info.spdm_version_sel.get_u8()
+                < SpdmVersion::SpdmVersion13.get_u8()
+            {
+                flags.remove(spdm13_request_capability_flags());
+            }
+            cnt += flags.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
         }
 
         if context.negotiate_info.spdm_version_sel.get_u8() >= SpdmVersion::SpdmVersion12.get_u8() {
@@ -118,6 +141,25 @@ impl SpdmCodec for SpdmGetCapabilitiesRequestPayload {
             {
                 return None;
             }
+
+            if context.negotiate_info.spdm_version_sel.get_u8()
+                < SpdmVersion::SpdmVersion13.get_u8()
+            {
+                flags.remove(spdm13_request_capability_flags());
+            } else {
+                if flags.contains(
+                    SpdmRequestCapabilityFlags::EP_INFO_CAP_NO_SIG
+                        | SpdmRequestCapabilityFlags::EP_INFO_CAP_SIG,
+                ) {
+                    return None;
+                }
+                if flags.contains(
+                    SpdmRequestCapabilityFlags::MULTI_KEY_CAP_ONLY
+                        | SpdmRequestCapabilityFlags::MULTI_KEY_CAP_NEG,
+                ) {
+                    return None;
+                }
+            }
         }
 
         let mut data_transfer_size = 0;
@@ -166,10 +208,11 @@ impl SpdmCodec for SpdmCapabilitiesResponsePayload {
             .encode(bytes)
             .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
         cnt += 0u16.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // reserved2
-        cnt += self
-            .flags
-            .encode(bytes)
-            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        let mut flags = self.flags;
+        if context.negotiate_info.spdm_version_sel.get_u8() < SpdmVersion::SpdmVersion13.get_u8() {
+            flags.remove(spdm13_response_capability_flags());
+        }
+        cnt += flags.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
 
         if context.negotiate_info.spdm_version_sel.get_u8() >= SpdmVersion::SpdmVersion12.get_u8() {
             cnt += self
@@ -195,7 +238,7 @@ impl SpdmCodec for SpdmCapabilitiesResponsePayload {
         u8::read(r)?; // reserved
         let ct_exponent = u8::read(r)?;
         u16::read(r)?; // reserved2
-        let flags = SpdmResponseCapabilityFlags::read(r)?;
+        let mut flags = SpdmResponseCapabilityFlags::read(r)?;
 
         // check rsp_capability
         if flags.contains(SpdmResponseCapabilityFlags::MEAS_CAP_NO_SIG)
@@ -294,6 +337,27 @@ impl SpdmCodec for SpdmCapabilitiesResponsePayload {
                 return None;
             }
         }
+        if context.negotiate_info.spdm_version_sel.get_u8() < SpdmVersion::SpdmVersion13.get_u8() {
+            flags.remove(spdm13_response_capability_flags());
+        } else {
+            if flags.contains(
+                SpdmResponseCapabilityFlags::EP_INFO_CAP_NO_SIG
+                    | SpdmResponseCapabilityFlags::EP_INFO_CAP_SIG,
+            ) {
+                return None;
+            }
+            if flags.contains(
+                SpdmResponseCapabilityFlags::MULTI_KEY_CAP_ONLY
+                    | SpdmResponseCapabilityFlags::MULTI_KEY_CAP_NEG,
+            ) {
+                return None;
+            }
+            if flags.contains(SpdmResponseCapabilityFlags::SET_KEY_PAIR_INFO_CAP)
+                && !flags.contains(SpdmResponseCapabilityFlags::GET_KEY_PAIR_INFO_CAP)
+            {
+                return None;
+            }
+        }
 
         if context.negotiate_info.spdm_version_sel.get_u8() >= SpdmVersion::SpdmVersion12.get_u8() {
             let data_transfer_size = u32::read(r)?;
@@ -557,6 +621,57 @@ mod tests {
         );
         assert_eq!(2, reader.left());
     }
+    #[test]
+    fn test_case3_spdm_capabilities_response_payload() {
+        let u8_slice = &mut [0u8; 20];
+        let mut writer = Writer::init(u8_slice);
+        let value = SpdmCapabilitiesResponsePayload {
+            ct_exponent: 0,
+            flags: SpdmResponseCapabilityFlags::CERT_CAP
+                | SpdmResponseCapabilityFlags::CHAL_CAP
+                | SpdmResponseCapabilityFlags::MEL_CAP
+                | SpdmResponseCapabilityFlags::EP_INFO_CAP_SIG,
+            data_transfer_size: 0x1200,
+            max_spdm_msg_size: 0x1200,
+        };
+
+        create_spdm_context!(context);
+        context.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion13;
+
+        assert!(value.spdm_encode(&mut context, &mut writer).is_ok());
+        let mut reader = Reader::init(u8_slice);
+        let spdm_capabilities_response_payload =
+            SpdmCapabilitiesResponsePayload::spdm_read(&mut context, &mut reader).unwrap();
+        assert_eq!(spdm_capabilities_response_payload.flags, value.flags);
+
+        context.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion12;
+        let mut reader = Reader::init(u8_slice);
+        let spdm_capabilities_response_payload =
+            SpdmCapabilitiesResponsePayload::spdm_read(&mut context, &mut reader).unwrap();
+        assert_eq!(
+            spdm_capabilities_response_payload.flags,
+            SpdmResponseCapabilityFlags::CERT_CAP | SpdmResponseCapabilityFlags::CHAL_CAP
+        );
+    }
+    #[test]
+    fn test_case4_spdm_capabilities_response_payload() {
+        let u8_slice = &mut [0u8; 20];
+        let mut writer = Writer::init(u8_slice);
+        let value = SpdmCapabilitiesResponsePayload {
+            ct_exponent: 0,
+            flags: SpdmResponseCapabilityFlags::EP_INFO_CAP_NO_SIG
+                | SpdmResponseCapabilityFlags::EP_INFO_CAP_SIG,
+            data_transfer_size: 0x1200,
+            max_spdm_msg_size: 0x1200,
+        };
+
+        create_spdm_context!(context);
+        context.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion13;
+
+        assert!(value.spdm_encode(&mut context, &mut writer).is_ok());
+        let mut reader = Reader::init(u8_slice);
+        assert!(SpdmCapabilitiesResponsePayload::spdm_read(&mut context, &mut reader).is_none());
+    }
 
     fn new_spdm_response_capability_flags(value: SpdmResponseCapabilityFlags) {
         let u8_slice = &mut [0u8; 4];
@@ -201,7 +201,7 @@ mod tests {
             + SPDM_MAX_HASH_SIZE
             + 2
             + MAX_SPDM_OPAQUE_SIZE
-            + SPDM_MAX_ASYM_KEY_SIZE];
+            + RSASSA_4096_KEY_SIZE];
         let mut writer = Writer::init(u8_slice);
         let value = SpdmChallengeAuthResponsePayload {
             slot_id: 0x0f,
@@ -223,7 +223,7 @@ mod tests {
                 data: [0xAAu8; MAX_SPDM_OPAQUE_SIZE],
             },
             signature: SpdmSignatureStruct {
-                data_size: SPDM_MAX_ASYM_KEY_SIZE as u16,
+                data_size: RSASSA_4096_KEY_SIZE as u16,
                 data: [0x55u8; SPDM_MAX_ASYM_KEY_SIZE],
             },
         };
@@ -244,7 +244,7 @@ mod tests {
                 + SPDM_MAX_HASH_SIZE
                 + 2
                 + MAX_SPDM_OPAQUE_SIZE
-                + SPDM_MAX_ASYM_KEY_SIZE,
+                + RSASSA_4096_KEY_SIZE,
             reader.left()
         );
         let spdm_read_data =
@@ -294,7 +294,7 @@ mod tests {
             + SPDM_NONCE_SIZE
             + 2
             + MAX_SPDM_OPAQUE_SIZE
-            + SPDM_MAX_ASYM_KEY_SIZE];
+            + RSASSA_4096_KEY_SIZE];
         let mut writer = Writer::init(u8_slice);
         let value = SpdmChallengeAuthResponsePayload {
             slot_id: 0x0f,
@@ -313,7 +313,7 @@ mod tests {
                 data: [0xAAu8; MAX_SPDM_OPAQUE_SIZE],
             },
             signature: SpdmSignatureStruct {
-                data_size: SPDM_MAX_ASYM_KEY_SIZE as u16,
+                data_size: RSASSA_4096_KEY_SIZE as u16,
                 data: [0x55u8; SPDM_MAX_ASYM_KEY_SIZE],
             },
         };
@@ -329,7 +329,7 @@ mod tests {
                 + SPDM_NONCE_SIZE
                 + 2
                 + MAX_SPDM_OPAQUE_SIZE
-                + SPDM_MAX_ASYM_KEY_SIZE,
+                + RSASSA_4096_KEY_SIZE,
             writer.left()
         );
         assert!(value.spdm_encode(&mut context, &mut writer).is_ok());
@@ -342,7 +342,7 @@ mod tests {
                 + SPDM_NONCE_SIZE
                 + 2
                 + MAX_SPDM_OPAQUE_SIZE
-                + SPDM_MAX_ASYM_KEY_SIZE,
+                + RSASSA_4096_KEY_SIZE,
             reader.left()
         );
         let spdm_read_data =
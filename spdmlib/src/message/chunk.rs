@@ -0,0 +1,368 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use crate::common::spdm_codec::SpdmCodec;
+use crate::config;
+use crate::error::SPDM_STATUS_BUFFER_FULL;
+use crate::{common, error::SpdmStatus};
+use codec::{Codec, Reader, Writer};
+
+// SPDMVersion (1) + RequestResponseCode (1) + Param1 (1) + Param2 (1)
+// + ChunkSeqNo (2) + Reserved (2) + ChunkSize (4)
+pub const SPDM_CHUNK_HEADER_SIZE: usize = 12;
+// LargeMessageSize (4), only present in the chunk with ChunkSeqNo 0.
+pub const SPDM_CHUNK_LARGE_MESSAGE_SIZE_FIELD_SIZE: usize = 4;
+// SPDMVersion (1) + RequestResponseCode (1) + Param1 (1) + Param2 (1) + ChunkSeqNo (2)
+pub const SPDM_CHUNK_SEND_ACK_HEADER_SIZE: usize = 6;
+
+pub const MAX_SPDM_CHUNK_SIZE: usize = config::MAX_SPDM_MSG_SIZE - SPDM_CHUNK_HEADER_SIZE;
+
+bitflags! {
+    #[derive(Default)]
+    pub struct SpdmChunkSenderAttributes: u8 {
+        const LAST_CHUNK = 0b0000_0001;
+    }
+}
+
+impl Codec for SpdmChunkSenderAttributes {
+    fn encode(&self, bytes: &mut Writer) -> Result<usize, codec::EncodeErr> {
+        self.bits().encode(bytes)
+    }
+
+    fn read(r: &mut Reader) -> Option<SpdmChunkSenderAttributes> {
+        let bits = u8::read(r)?;
+
+        SpdmChunkSenderAttributes::from_bits(bits)
+    }
+}
+
+bitflags! {
+    #[derive(Default)]
+    pub struct SpdmChunkReceiverAttributes: u8 {
+        const EARLY_ERROR_DETECTED = 0b0000_0001;
+    }
+}
+
+impl Codec for SpdmChunkReceiverAttributes {
+    fn encode(&self, bytes: &mut Writer) -> Result<usize, codec::EncodeErr> {
+        self.bits().encode(bytes)
+    }
+
+    fn read(r: &mut Reader) -> Option<SpdmChunkReceiverAttributes> {
+        let bits = u8::read(r)?;
+
+        SpdmChunkReceiverAttributes::from_bits(bits)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SpdmChunkSendRequestPayload {
+    pub request_attributes: SpdmChunkSenderAttributes,
+    pub handle: u8,
+    pub chunk_seq_num: u16,
+    pub chunk_size: u32,
+    pub large_message_size: u32, // only valid when chunk_seq_num is 0
+    pub chunk: [u8; MAX_SPDM_CHUNK_SIZE],
+}
+
+impl Default for SpdmChunkSendRequestPayload {
+    fn default() -> SpdmChunkSendRequestPayload {
+        SpdmChunkSendRequestPayload {
+            request_attributes: SpdmChunkSenderAttributes::default(),
+            handle: 0,
+            chunk_seq_num: 0,
+            chunk_size: 0,
+            large_message_size: 0,
+            chunk: [0u8; MAX_SPDM_CHUNK_SIZE],
+        }
+    }
+}
+
+impl SpdmCodec for SpdmChunkSendRequestPayload {
+    fn spdm_encode(
+        &self,
+        _context: &mut common::SpdmContext,
+        bytes: &mut Writer,
+    ) -> Result<usize, SpdmStatus> {
+        if self.chunk_size as usize > MAX_SPDM_CHUNK_SIZE {
+            return Err(SPDM_STATUS_BUFFER_FULL);
+        }
+        let mut cnt = 0usize;
+        cnt += self
+            .request_attributes
+            .encode(bytes)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param1
+        cnt += self
+            .handle
+            .encode(bytes)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param2
+        cnt += self
+            .chunk_seq_num
+            .encode(bytes)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        cnt += 0u16.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // reserved
+        cnt += self
+            .chunk_size
+            .encode(bytes)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        if self.chunk_seq_num == 0 {
+            cnt += self
+                .large_message_size
+                .encode(bytes)
+                .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        }
+        cnt += bytes
+            .extend_from_slice(&self.chunk[..self.chunk_size as usize])
+            .ok_or(SPDM_STATUS_BUFFER_FULL)?;
+        Ok(cnt)
+    }
+
+    fn spdm_read(
+        _context: &mut common::SpdmContext,
+        r: &mut Reader,
+    ) -> Option<SpdmChunkSendRequestPayload> {
+        let request_attributes = SpdmChunkSenderAttributes::read(r)?; // param1
+        let handle = u8::read(r)?; // param2
+        let chunk_seq_num = u16::read(r)?;
+        u16::read(r)?; // reserved
+        let chunk_size = u32::read(r)?;
+        let large_message_size = if chunk_seq_num == 0 { u32::read(r)? } else { 0 };
+        if chunk_size as usize > MAX_SPDM_CHUNK_SIZE {
+            return None;
+        }
+
+        let mut request = SpdmChunkSendRequestPayload {
+            request_attributes,
+            handle,
+            chunk_seq_num,
+            chunk_size,
+            large_message_size,
+            ..Default::default()
+        };
+        for data in request.chunk.iter_mut().take(chunk_size as usize) {
+            *data = u8::read(r)?;
+        }
+        Some(request)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SpdmChunkSendAckResponsePayload {
+    pub response_attributes: SpdmChunkReceiverAttributes,
+    pub handle: u8,
+    pub chunk_seq_num: u16,
+    pub response_to_large_request_size: u32,
+    pub response_to_large_request: [u8; config::MAX_SPDM_MSG_SIZE],
+}
+
+impl Default for SpdmChunkSendAckResponsePayload {
+    fn default() -> SpdmChunkSendAckResponsePayload {
+        SpdmChunkSendAckResponsePayload {
+            response_attributes: SpdmChunkReceiverAttributes::default(),
+            handle: 0,
+            chunk_seq_num: 0,
+            response_to_large_request_size: 0,
+            response_to_large_request: [0u8; config::MAX_SPDM_MSG_SIZE],
+        }
+    }
+}
+
+impl SpdmCodec for SpdmChunkSendAckResponsePayload {
+    fn spdm_encode(
+        &self,
+        _context: &mut common::SpdmContext,
+        bytes: &mut Writer,
+    ) -> Result<usize, SpdmStatus> {
+        if self.response_to_large_request_size as usize > config::MAX_SPDM_MSG_SIZE {
+            return Err(SPDM_STATUS_BUFFER_FULL);
+        }
+        let mut cnt = 0usize;
+        cnt += self
+            .response_attributes
+            .encode(bytes)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param1
+        cnt += self
+            .handle
+            .encode(bytes)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param2
+        cnt += self
+            .chunk_seq_num
+            .encode(bytes)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        cnt += bytes
+            .extend_from_slice(
+                &self.response_to_large_request[..self.response_to_large_request_size as usize],
+            )
+            .ok_or(SPDM_STATUS_BUFFER_FULL)?;
+        Ok(cnt)
+    }
+
+    fn spdm_read(
+        _context: &mut common::SpdmContext,
+        r: &mut Reader,
+    ) -> Option<SpdmChunkSendAckResponsePayload> {
+        let response_attributes = SpdmChunkReceiverAttributes::read(r)?; // param1
+        let handle = u8::read(r)?; // param2
+        let chunk_seq_num = u16::read(r)?;
+        let response_to_large_request_size = r.left();
+        if response_to_large_request_size > config::MAX_SPDM_MSG_SIZE {
+            return None;
+        }
+
+        let mut response = SpdmChunkSendAckResponsePayload {
+            response_attributes,
+            handle,
+            chunk_seq_num,
+            response_to_large_request_size: response_to_large_request_size as u32,
+            ..Default::default()
+        };
+        for data in response
+            .response_to_large_request
+            .iter_mut()
+            .take(response_to_large_request_size)
+        {
+            *data = u8::read(r)?;
+        }
+        Some(response)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SpdmChunkGetRequestPayload {
+    pub handle: u8,
+    pub chunk_seq_num: u16,
+}
+
+impl SpdmCodec for SpdmChunkGetRequestPayload {
+    fn spdm_encode(
+        &self,
+        _context: &mut common::SpdmContext,
+        bytes: &mut Writer,
+    ) -> Result<usize, SpdmStatus> {
+        let mut cnt = 0usize;
+        cnt += 0u8.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param1
+        cnt += self
+            .handle
+            .encode(bytes)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param2
+        cnt += self
+            .chunk_seq_num
+            .encode(bytes)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        Ok(cnt)
+    }
+
+    fn spdm_read(
+        _context: &mut common::SpdmContext,
+        r: &mut Reader,
+    ) -> Option<SpdmChunkGetRequestPayload> {
+        u8::read(r)?; // param1
+        let handle = u8::read(r)?; // param2
+        let chunk_seq_num = u16::read(r)?;
+
+        Some(SpdmChunkGetRequestPayload {
+            handle,
+            chunk_seq_num,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SpdmChunkResponsePayload {
+    pub response_attributes: SpdmChunkSenderAttributes,
+    pub handle: u8,
+    pub chunk_seq_num: u16,
+    pub chunk_size: u32,
+    pub large_message_size: u32, // only valid when chunk_seq_num is 0
+    pub chunk: [u8; MAX_SPDM_CHUNK_SIZE],
+}
+
+impl Default for SpdmChunkResponsePayload {
+    fn default() -> SpdmChunkResponsePayload {
+        SpdmChunkResponsePayload {
+            response_attributes: SpdmChunkSenderAttributes::default(),
+            handle: 0,
+            chunk_seq_num: 0,
+            chunk_size: 0,
+            large_message_size: 0,
+            chunk: [0u8; MAX_SPDM_CHUNK_SIZE],
+        }
+    }
+}
+
+impl SpdmCodec for SpdmChunkResponsePayload {
+    fn spdm_encode(
+        &self,
+        _context: &mut common::SpdmContext,
+        bytes: &mut Writer,
+    ) -> Result<usize, SpdmStatus> {
+        if self.chunk_size as usize > MAX_SPDM_CHUNK_SIZE {
+            return Err(SPDM_STATUS_BUFFER_FULL);
+        }
+        let mut cnt = 0usize;
+        cnt += self
+            .response_attributes
+            .encode(bytes)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param1
+        cnt += self
+            .handle
+            .encode(bytes)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param2
+        cnt += self
+            .chunk_seq_num
+            .encode(bytes)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        cnt += 0u16.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // reserved
+        cnt += self
+            .chunk_size
+            .encode(bytes)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        if self.chunk_seq_num == 0 {
+            cnt += self
+                .large_message_size
+                .encode(bytes)
+                .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        }
+        cnt += bytes
+            .extend_from_slice(&self.chunk[..self.chunk_size as usize])
+            .ok_or(SPDM_STATUS_BUFFER_FULL)?;
+        Ok(cnt)
+    }
+
+    fn spdm_read(
+        _context: &mut common::SpdmContext,
+        r: &mut Reader,
+    ) -> Option<SpdmChunkResponsePayload> {
+        let response_attributes = SpdmChunkSenderAttributes::read(r)?; // param1
+        let handle = u8::read(r)?; // param2
+        let chunk_seq_num = u16::read(r)?;
+        u16::read(r)?; // reserved
+        let chunk_size = u32::read(r)?;
+        let large_message_size = if chunk_seq_num == 0 { u32::read(r)? } else { 0 };
+        if chunk_size as usize > MAX_SPDM_CHUNK_SIZE {
+            return None;
+        }
+
+        let mut response = SpdmChunkResponsePayload {
+            response_attributes,
+            handle,
+            chunk_seq_num,
+            chunk_size,
+            large_message_size,
+            ..Default::default()
+        };
+        for data in response.chunk.iter_mut().take(chunk_size as usize) {
+            *data = u8::read(r)?;
+        }
+        Some(response)
+    }
+}
+
+#[cfg(all(test,))]
+#[path = "mod_test.common.inc.rs"]
+mod testlib;
+
+#[cfg(all(test,))]
+#[path = "chunk_test.rs"]
+mod chunk_test;
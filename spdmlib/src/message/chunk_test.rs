@@ -0,0 +1,130 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use super::*;
+use crate::common::{SpdmCodec, SpdmConfigInfo, SpdmContext, SpdmProvisionInfo};
+use testlib::{create_spdm_context, DeviceIO, TransportEncap};
+
+#[test]
+fn test_chunk_send_struct() {
+    create_spdm_context!(context);
+    let context = &mut context;
+
+    // 1. Validate the first CHUNK_SEND carries LargeMessageSize.
+    let mut value = SpdmChunkSendRequestPayload {
+        request_attributes: SpdmChunkSenderAttributes::empty(),
+        handle: 0x5a,
+        chunk_seq_num: 0,
+        chunk_size: 8,
+        large_message_size: 0x100,
+        ..Default::default()
+    };
+    value.chunk[..8].copy_from_slice(&[1u8, 2, 3, 4, 5, 6, 7, 8]);
+    let u8_slice = &mut [0u8; 64];
+    let mut writer = Writer::init(u8_slice);
+    assert_eq!(value.spdm_encode(context, &mut writer), Ok(22));
+
+    let reader = &mut Reader::init(&u8_slice[..22]);
+    let ret = SpdmChunkSendRequestPayload::spdm_read(context, reader).unwrap();
+    assert_eq!(reader.left(), 0);
+    assert_eq!(ret.handle, 0x5a);
+    assert_eq!(ret.chunk_seq_num, 0);
+    assert_eq!(ret.chunk_size, 8);
+    assert_eq!(ret.large_message_size, 0x100);
+    assert_eq!(&ret.chunk[..8], &[1u8, 2, 3, 4, 5, 6, 7, 8]);
+
+    // 2. Validate a later CHUNK_SEND omits LargeMessageSize.
+    value.request_attributes = SpdmChunkSenderAttributes::LAST_CHUNK;
+    value.chunk_seq_num = 1;
+    let u8_slice = &mut [0u8; 64];
+    let mut writer = Writer::init(u8_slice);
+    assert_eq!(value.spdm_encode(context, &mut writer), Ok(18));
+
+    let reader = &mut Reader::init(&u8_slice[..18]);
+    let ret = SpdmChunkSendRequestPayload::spdm_read(context, reader).unwrap();
+    assert_eq!(reader.left(), 0);
+    assert!(ret
+        .request_attributes
+        .contains(SpdmChunkSenderAttributes::LAST_CHUNK));
+    assert_eq!(ret.large_message_size, 0);
+
+    // 3. Validate a truncated chunk is rejected.
+    let reader = &mut Reader::init(&u8_slice[..17]);
+    let ret = SpdmChunkSendRequestPayload::spdm_read(context, reader);
+    assert!(ret.is_none());
+}
+
+#[test]
+fn test_chunk_send_ack_struct() {
+    create_spdm_context!(context);
+    let context = &mut context;
+
+    // 1. Validate CHUNK_SEND_ACK without ResponseToLargeRequest length is 6.
+    let u8_slice = &mut [0u8; 6];
+    let reader = &mut Reader::init(&u8_slice[2..]);
+    let ret = SpdmChunkSendAckResponsePayload::spdm_read(context, reader).unwrap();
+    assert_eq!(reader.left(), 0);
+    assert_eq!(ret.response_to_large_request_size, 0);
+
+    // 2. Validate ResponseToLargeRequest takes the rest of the message.
+    let mut value = SpdmChunkSendAckResponsePayload {
+        response_attributes: SpdmChunkReceiverAttributes::EARLY_ERROR_DETECTED,
+        handle: 1,
+        chunk_seq_num: 3,
+        response_to_large_request_size: 4,
+        ..Default::default()
+    };
+    value.response_to_large_request[..4].copy_from_slice(&[0x12, 0x7f, 0x01, 0x00]);
+    let u8_slice = &mut [0u8; 16];
+    let mut writer = Writer::init(u8_slice);
+    assert_eq!(value.spdm_encode(context, &mut writer), Ok(8));
+
+    let reader = &mut Reader::init(&u8_slice[..8]);
+    let ret = SpdmChunkSendAckResponsePayload::spdm_read(context, reader).unwrap();
+    assert_eq!(reader.left(), 0);
+    assert!(ret
+        .response_attributes
+        .contains(SpdmChunkReceiverAttributes::EARLY_ERROR_DETECTED));
+    assert_eq!(ret.handle, 1);
+    assert_eq!(ret.chunk_seq_num, 3);
+    assert_eq!(ret.response_to_large_request_size, 4);
+    assert_eq!(
+        &ret.response_to_large_request[..4],
+        &[0x12, 0x7f, 0x01, 0x00]
+    );
+}
+
+#[test]
+fn test_chunk_get_struct() {
+    create_spdm_context!(context);
+    let context = &mut context;
+
+    // 1. Validate CHUNK_GET request length is 6.
+    let u8_slice = &mut [0u8; 6];
+    let reader = &mut Reader::init(&u8_slice[2..]);
+    let ret = SpdmChunkGetRequestPayload::spdm_read(context, reader);
+    assert!(ret.is_some());
+    assert_eq!(reader.left(), 0);
+
+    // 2. Validate CHUNK_RESPONSE round trip.
+    let mut value = SpdmChunkResponsePayload {
+        response_attributes: SpdmChunkSenderAttributes::LAST_CHUNK,
+        handle: 2,
+        chunk_seq_num: 0,
+        chunk_size: 3,
+        large_message_size: 3,
+        ..Default::default()
+    };
+    value.chunk[..3].copy_from_slice(&[0xaa, 0xbb, 0xcc]);
+    let u8_slice = &mut [0u8; 32];
+    let mut writer = Writer::init(u8_slice);
+    assert_eq!(value.spdm_encode(context, &mut writer), Ok(17));
+
+    let reader = &mut Reader::init(&u8_slice[..17]);
+    let ret = SpdmChunkResponsePayload::spdm_read(context, reader).unwrap();
+    assert_eq!(reader.left(), 0);
+    assert_eq!(ret.handle, 2);
+    assert_eq!(ret.large_message_size, 3);
+    assert_eq!(&ret.chunk[..3], &[0xaa, 0xbb, 0xcc]);
+}
@@ -0,0 +1,155 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use crate::common;
+use crate::common::opaque::{SpdmOpaqueStruct, MAX_SPDM_OPAQUE_SIZE};
+use crate::common::spdm_codec::SpdmCodec;
+use crate::config;
+use crate::error::{SpdmStatus, SPDM_STATUS_BUFFER_FULL};
+use crate::protocol::{SpdmCsrStruct, SPDM_MAX_CSR_SIZE};
+use codec::{Codec, Reader, Writer};
+
+// config::MAX_SPDM_MSG_SIZE - 8
+// SPDM0274 1.2.1: Table 60 GET_CSR request message format
+pub const MAX_SPDM_CSR_REQUESTER_INFO_SIZE: usize = config::MAX_SPDM_MSG_SIZE - 8;
+
+#[derive(Debug, Clone)]
+pub struct SpdmGetCsrRequestPayload {
+    pub requester_info_length: u16,
+    pub requester_info: [u8; MAX_SPDM_CSR_REQUESTER_INFO_SIZE],
+    pub opaque: SpdmOpaqueStruct,
+}
+
+impl Default for SpdmGetCsrRequestPayload {
+    fn default() -> Self {
+        SpdmGetCsrRequestPayload {
+            requester_info_length: 0,
+            requester_info: [0u8; MAX_SPDM_CSR_REQUESTER_INFO_SIZE],
+            opaque: SpdmOpaqueStruct::default(),
+        }
+    }
+}
+
+impl SpdmCodec for SpdmGetCsrRequestPayload {
+    fn spdm_encode(
+        &self,
+        _context: &mut common::SpdmContext,
+        bytes: &mut Writer,
+    ) -> Result<usize, SpdmStatus> {
+        let mut cnt = 0usize;
+        cnt += 0u8.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param1
+        cnt += 0u8.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param2
+        cnt += self
+            .requester_info_length
+            .encode(bytes)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        cnt += self
+            .opaque
+            .data_size
+            .encode(bytes)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        for d in self
+            .requester_info
+            .iter()
+            .take(self.requester_info_length as usize)
+        {
+            cnt += d.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        }
+        for d in self.opaque.data.iter().take(self.opaque.data_size as usize) {
+            cnt += d.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        }
+        Ok(cnt)
+    }
+
+    fn spdm_read(
+        _context: &mut common::SpdmContext,
+        r: &mut Reader,
+    ) -> Option<SpdmGetCsrRequestPayload> {
+        u8::read(r)?; // param1
+        u8::read(r)?; // param2
+        let requester_info_length = u16::read(r)?;
+        let mut opaque = SpdmOpaqueStruct {
+            data_size: u16::read(r)?,
+            ..Default::default()
+        };
+        if requester_info_length as usize > MAX_SPDM_CSR_REQUESTER_INFO_SIZE
+            || opaque.data_size as usize > MAX_SPDM_OPAQUE_SIZE
+        {
+            return None;
+        }
+
+        let mut requester_info = [0u8; MAX_SPDM_CSR_REQUESTER_INFO_SIZE];
+        for d in requester_info
+            .iter_mut()
+            .take(requester_info_length as usize)
+        {
+            *d = u8::read(r)?;
+        }
+        for d in opaque.data.iter_mut().take(opaque.data_size as usize) {
+            *d = u8::read(r)?;
+        }
+
+        Some(SpdmGetCsrRequestPayload {
+            requester_info_length,
+            requester_info,
+            opaque,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SpdmCsrResponsePayload {
+    pub csr: SpdmCsrStruct,
+}
+
+impl SpdmCodec for SpdmCsrResponsePayload {
+    fn spdm_encode(
+        &self,
+        _context: &mut common::SpdmContext,
+        bytes: &mut Writer,
+    ) -> Result<usize, SpdmStatus> {
+        let mut cnt = 0usize;
+        cnt += 0u8.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param1
+        cnt += 0u8.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param2
+        cnt += self
+            .csr
+            .data_size
+            .encode(bytes)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        cnt += 0u16.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // reserved
+        for d in self.csr.data.iter().take(self.csr.data_size as usize) {
+            cnt += d.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        }
+        Ok(cnt)
+    }
+
+    fn spdm_read(
+        _context: &mut common::SpdmContext,
+        r: &mut Reader,
+    ) -> Option<SpdmCsrResponsePayload> {
+        u8::read(r)?; // param1
+        u8::read(r)?; // param2
+        let mut csr = SpdmCsrStruct {
+            data_size: u16::read(r)?,
+            ..Default::default()
+        };
+        u16::read(r)?; // reserved
+        if csr.data_size as usize > SPDM_MAX_CSR_SIZE {
+            return None;
+        }
+        for d in csr.data.iter_mut().take(csr.data_size as usize) {
+            *d = u8::read(r)?;
+        }
+
+        Some(SpdmCsrResponsePayload { csr })
+    }
+}
+
+#[cfg(all(test,))]
+#[path = "mod_test.common.inc.rs"]
+mod testlib;
+
+#[cfg(all(test,))]
+#[path = "csr_test.rs"]
+mod csr_test;
@@ -0,0 +1,70 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use super::*;
+use crate::common::{SpdmCodec, SpdmConfigInfo, SpdmContext, SpdmProvisionInfo};
+use testlib::{create_spdm_context, DeviceIO, TransportEncap};
+
+#[test]
+fn test_get_csr_struct() {
+    create_spdm_context!(context);
+    let context = &mut context;
+
+    // 1. Validate RequesterInfo and OpaqueData follow both length fields.
+    let mut value = SpdmGetCsrRequestPayload {
+        requester_info_length: 4,
+        ..Default::default()
+    };
+    value.requester_info[..4].copy_from_slice(&[0x30u8, 0x02, 0x05, 0x00]);
+    value.opaque.data_size = 2;
+    value.opaque.data[..2].copy_from_slice(&[0xaau8, 0x55]);
+    let u8_slice = &mut [0u8; 32];
+    let mut writer = Writer::init(u8_slice);
+    assert_eq!(value.spdm_encode(context, &mut writer), Ok(12));
+    assert_eq!(
+        &u8_slice[..12],
+        &[0u8, 0, 4, 0, 2, 0, 0x30, 0x02, 0x05, 0x00, 0xaa, 0x55]
+    );
+
+    let reader = &mut Reader::init(&u8_slice[..12]);
+    let ret = SpdmGetCsrRequestPayload::spdm_read(context, reader).unwrap();
+    assert_eq!(reader.left(), 0);
+    assert_eq!(ret.requester_info_length, 4);
+    assert_eq!(&ret.requester_info[..4], &[0x30u8, 0x02, 0x05, 0x00]);
+    assert_eq!(ret.opaque.data_size, 2);
+    assert_eq!(&ret.opaque.data[..2], &[0xaau8, 0x55]);
+
+    // 2. Validate a truncated request is rejected.
+    let reader = &mut Reader::init(&u8_slice[..11]);
+    assert!(SpdmGetCsrRequestPayload::spdm_read(context, reader).is_none());
+
+    // 3. Validate an oversized OpaqueDataLength is rejected.
+    let u8_slice = &mut [0u8, 0, 0, 0, 0x01, 0x04];
+    let reader = &mut Reader::init(u8_slice);
+    assert!(SpdmGetCsrRequestPayload::spdm_read(context, reader).is_none());
+}
+
+#[test]
+fn test_csr_struct() {
+    create_spdm_context!(context);
+    let context = &mut context;
+
+    // 1. Validate CSRLength is followed by a reserved field.
+    let mut value = SpdmCsrResponsePayload::default();
+    value.csr.data_size = 3;
+    value.csr.data[..3].copy_from_slice(&[1u8, 2, 3]);
+    let u8_slice = &mut [0u8; 16];
+    let mut writer = Writer::init(u8_slice);
+    assert_eq!(value.spdm_encode(context, &mut writer), Ok(9));
+    assert_eq!(&u8_slice[..9], &[0u8, 0, 3, 0, 0, 0, 1, 2, 3]);
+
+    let reader = &mut Reader::init(&u8_slice[..9]);
+    let ret = SpdmCsrResponsePayload::spdm_read(context, reader).unwrap();
+    assert_eq!(reader.left(), 0);
+    assert_eq!(ret.csr.as_ref(), &[1u8, 2, 3]);
+
+    // 2. Validate a truncated CSR is rejected.
+    let reader = &mut Reader::init(&u8_slice[..8]);
+    assert!(SpdmCsrResponsePayload::spdm_read(context, reader).is_none());
+}
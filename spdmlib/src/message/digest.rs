@@ -5,9 +5,17 @@
 use crate::common;
 use crate::common::spdm_codec::SpdmCodec;
 use crate::error::{SpdmStatus, SPDM_STATUS_BUFFER_FULL};
-use crate::protocol::{gen_array_clone, SpdmDigestStruct, SPDM_MAX_SLOT_NUMBER};
+use crate::protocol::{
+    gen_array_clone, SpdmDigestStruct, SpdmKeyUsageMask, SpdmVersion, SPDM_MAX_SLOT_NUMBER,
+};
 use codec::{Codec, Reader, Writer};
 
+// CertModel in CertificateInfo
+pub const SPDM_CERT_MODEL_MASK: u8 = 0x7;
+pub const SPDM_CERT_MODEL_DEVICE_CERT: u8 = 0x1;
+pub const SPDM_CERT_MODEL_ALIAS_CERT: u8 = 0x2;
+pub const SPDM_CERT_MODEL_GENERIC_CERT: u8 = 0x3;
+
 #[derive(Debug, Clone, Default)]
 pub struct SpdmGetDigestsRequestPayload {}
 
@@ -38,6 +46,11 @@ impl SpdmCodec for SpdmGetDigestsRequestPayload {
 pub struct SpdmDigestsResponsePayload {
     pub slot_mask: u8,
     pub digests: [SpdmDigestStruct; SPDM_MAX_SLOT_NUMBER],
+    // spdm 1.3, present for a multi-key connection of the sender, in the
+    // same order as the digests.
+    pub key_pair_id: [u8; SPDM_MAX_SLOT_NUMBER],
+    pub certificate_info: [u8; SPDM_MAX_SLOT_NUMBER],
+    pub key_usage_mask: [SpdmKeyUsageMask; SPDM_MAX_SLOT_NUMBER],
 }
 
 impl SpdmCodec for SpdmDigestsResponsePayload {
@@ -63,6 +76,26 @@ impl SpdmCodec for SpdmDigestsResponsePayload {
         for digest in self.digests.iter().take(count as usize) {
             cnt += digest.spdm_encode(context, bytes)?;
         }
+
+        if context.negotiate_info.spdm_version_sel.get_u8() >= SpdmVersion::SpdmVersion13.get_u8()
+            && context.negotiate_info.local_multi_key_conn
+        {
+            for key_pair_id in self.key_pair_id.iter().take(count as usize) {
+                cnt += key_pair_id
+                    .encode(bytes)
+                    .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+            }
+            for certificate_info in self.certificate_info.iter().take(count as usize) {
+                cnt += certificate_info
+                    .encode(bytes)
+                    .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+            }
+            for key_usage_mask in self.key_usage_mask.iter().take(count as usize) {
+                cnt += key_usage_mask
+                    .encode(bytes)
+                    .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+            }
+        }
         Ok(cnt)
     }
 
@@ -84,7 +117,30 @@ impl SpdmCodec for SpdmDigestsResponsePayload {
         for digest in digests.iter_mut().take(slot_count as usize) {
             *digest = SpdmDigestStruct::spdm_read(context, r)?;
         }
-        Some(SpdmDigestsResponsePayload { slot_mask, digests })
+        let mut response = SpdmDigestsResponsePayload {
+            slot_mask,
+            digests,
+            ..Default::default()
+        };
+
+        if context.negotiate_info.spdm_version_sel.get_u8() >= SpdmVersion::SpdmVersion13.get_u8()
+            && context.negotiate_info.peer_multi_key_conn
+        {
+            for key_pair_id in response.key_pair_id.iter_mut().take(slot_count as usize) {
+                *key_pair_id = u8::read(r)?;
+            }
+            for certificate_info in response
+                .certificate_info
+                .iter_mut()
+                .take(slot_count as usize)
+            {
+                *certificate_info = u8::read(r)?;
+            }
+            for key_usage_mask in response.key_usage_mask.iter_mut().take(slot_count as usize) {
+                *key_usage_mask = SpdmKeyUsageMask::read(r)?;
+            }
+        }
+        Some(response)
     }
 }
 
@@ -113,6 +169,7 @@ mod tests {
                 },
                 SPDM_MAX_SLOT_NUMBER,
             ),
+            ..Default::default()
         };
         for i in 0..SPDM_MAX_SLOT_NUMBER {
             for j in 0..SPDM_MAX_HASH_SIZE {
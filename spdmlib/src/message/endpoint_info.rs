@@ -0,0 +1,203 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use crate::common;
+use crate::common::spdm_codec::SpdmCodec;
+use crate::error::{SpdmStatus, SPDM_STATUS_BUFFER_FULL};
+use crate::protocol::{
+    SpdmEndpointInfo, SpdmEndpointInfoSubCode, SpdmNonceStruct, SpdmSignatureStruct,
+    SPDM_MAX_ENDPOINT_INFO_SIZE,
+};
+use codec::{Codec, Reader, Writer};
+
+pub const ENDPOINT_INFO_SLOT_ID_MASK: u8 = 0b0000_1111;
+
+bitflags! {
+    #[derive(Default)]
+    pub struct SpdmEndpointInfoRequestAttributes: u8 {
+        const SIGNATURE_REQUESTED = 0b0000_0001;
+        const VALID_MASK = Self::SIGNATURE_REQUESTED.bits;
+    }
+}
+
+impl Codec for SpdmEndpointInfoRequestAttributes {
+    fn encode(&self, bytes: &mut Writer) -> Result<usize, codec::EncodeErr> {
+        self.bits().encode(bytes)
+    }
+
+    fn read(r: &mut Reader) -> Option<SpdmEndpointInfoRequestAttributes> {
+        let bits = u8::read(r)?;
+
+        SpdmEndpointInfoRequestAttributes::from_bits(
+            bits & SpdmEndpointInfoRequestAttributes::VALID_MASK.bits,
+        )
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SpdmGetEndpointInfoRequestPayload {
+    pub sub_code: SpdmEndpointInfoSubCode,
+    pub request_attributes: SpdmEndpointInfoRequestAttributes,
+    pub slot_id: u8,
+    // Only sent when a signature is requested.
+    pub nonce: SpdmNonceStruct,
+}
+
+impl SpdmCodec for SpdmGetEndpointInfoRequestPayload {
+    fn spdm_encode(
+        &self,
+        _context: &mut common::SpdmContext,
+        bytes: &mut Writer,
+    ) -> Result<usize, SpdmStatus> {
+        let mut cnt = 0usize;
+        cnt += self
+            .sub_code
+            .encode(bytes)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param1
+        cnt += self
+            .request_attributes
+            .encode(bytes)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param2
+        cnt += (self.slot_id & ENDPOINT_INFO_SLOT_ID_MASK)
+            .encode(bytes)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        for _i in 0..3 {
+            cnt += 0u8.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // reserved
+        }
+        if self
+            .request_attributes
+            .contains(SpdmEndpointInfoRequestAttributes::SIGNATURE_REQUESTED)
+        {
+            cnt += self
+                .nonce
+                .encode(bytes)
+                .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        }
+        Ok(cnt)
+    }
+
+    fn spdm_read(
+        _context: &mut common::SpdmContext,
+        r: &mut Reader,
+    ) -> Option<SpdmGetEndpointInfoRequestPayload> {
+        let sub_code = SpdmEndpointInfoSubCode::read(r)?; // param1
+        if let SpdmEndpointInfoSubCode::Unknown(_) = sub_code {
+            return None;
+        }
+        let request_attributes = SpdmEndpointInfoRequestAttributes::read(r)?; // param2
+        let slot_id = u8::read(r)? & ENDPOINT_INFO_SLOT_ID_MASK;
+        for _i in 0..3 {
+            u8::read(r)?; // reserved
+        }
+        let nonce = if request_attributes
+            .contains(SpdmEndpointInfoRequestAttributes::SIGNATURE_REQUESTED)
+        {
+            SpdmNonceStruct::read(r)?
+        } else {
+            SpdmNonceStruct::default()
+        };
+
+        Some(SpdmGetEndpointInfoRequestPayload {
+            sub_code,
+            request_attributes,
+            slot_id,
+            nonce,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SpdmEndpointInfoResponsePayload {
+    pub slot_id: u8,
+    // The nonce and the signature are only present when a signature is requested.
+    pub nonce: SpdmNonceStruct,
+    pub endpoint_info: SpdmEndpointInfo,
+    pub signature: SpdmSignatureStruct,
+}
+
+impl SpdmCodec for SpdmEndpointInfoResponsePayload {
+    fn spdm_encode(
+        &self,
+        context: &mut common::SpdmContext,
+        bytes: &mut Writer,
+    ) -> Result<usize, SpdmStatus> {
+        let mut cnt = 0usize;
+        cnt += 0u8.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param1
+        if context.runtime_info.need_endpoint_info_signature {
+            cnt += (self.slot_id & ENDPOINT_INFO_SLOT_ID_MASK)
+                .encode(bytes)
+                .map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param2
+        } else {
+            cnt += 0u8.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param2
+        }
+        for _i in 0..4 {
+            cnt += 0u8.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // reserved
+        }
+        if context.runtime_info.need_endpoint_info_signature {
+            cnt += self
+                .nonce
+                .encode(bytes)
+                .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        }
+        cnt += self
+            .endpoint_info
+            .data_size
+            .encode(bytes)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        for d in self.endpoint_info.as_ref() {
+            cnt += d.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        }
+        if context.runtime_info.need_endpoint_info_signature {
+            cnt += self.signature.spdm_encode(context, bytes)?;
+        }
+        Ok(cnt)
+    }
+
+    fn spdm_read(
+        context: &mut common::SpdmContext,
+        r: &mut Reader,
+    ) -> Option<SpdmEndpointInfoResponsePayload> {
+        u8::read(r)?; // param1
+        let slot_id = u8::read(r)? & ENDPOINT_INFO_SLOT_ID_MASK; // param2
+        for _i in 0..4 {
+            u8::read(r)?; // reserved
+        }
+        let nonce = if context.runtime_info.need_endpoint_info_signature {
+            SpdmNonceStruct::read(r)?
+        } else {
+            SpdmNonceStruct::default()
+        };
+        let data_size = u32::read(r)?;
+        if data_size as usize > SPDM_MAX_ENDPOINT_INFO_SIZE {
+            return None;
+        }
+        let mut endpoint_info = SpdmEndpointInfo {
+            data_size,
+            ..Default::default()
+        };
+        for d in endpoint_info.data.iter_mut().take(data_size as usize) {
+            *d = u8::read(r)?;
+        }
+        let signature = if context.runtime_info.need_endpoint_info_signature {
+            SpdmSignatureStruct::spdm_read(context, r)?
+        } else {
+            SpdmSignatureStruct::default()
+        };
+
+        Some(SpdmEndpointInfoResponsePayload {
+            slot_id,
+            nonce,
+            endpoint_info,
+            signature,
+        })
+    }
+}
+
+#[cfg(all(test,))]
+#[path = "mod_test.common.inc.rs"]
+mod testlib;
+
+#[cfg(all(test,))]
+#[path = "endpoint_info_test.rs"]
+mod endpoint_info_test;
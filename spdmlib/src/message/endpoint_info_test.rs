@@ -0,0 +1,125 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use super::*;
+use crate::common::{SpdmCodec, SpdmConfigInfo, SpdmContext, SpdmProvisionInfo};
+use crate::protocol::*;
+use testlib::{create_spdm_context, DeviceIO, TransportEncap};
+
+#[test]
+fn test_case0_spdm_get_endpoint_info_request_payload() {
+    let u8_slice = &mut [0u8; 6 + SPDM_NONCE_SIZE];
+    let mut writer = Writer::init(u8_slice);
+    let value = SpdmGetEndpointInfoRequestPayload {
+        sub_code: SpdmEndpointInfoSubCode::SpdmEndpointInfoSubCodeDeviceClassIdentifier,
+        request_attributes: SpdmEndpointInfoRequestAttributes::SIGNATURE_REQUESTED,
+        slot_id: 1,
+        nonce: SpdmNonceStruct {
+            data: [0xaau8; SPDM_NONCE_SIZE],
+        },
+    };
+
+    create_spdm_context!(context);
+
+    assert_eq!(
+        value.spdm_encode(&mut context, &mut writer),
+        Ok(6 + SPDM_NONCE_SIZE)
+    );
+    let mut reader = Reader::init(u8_slice);
+    let ret = SpdmGetEndpointInfoRequestPayload::spdm_read(&mut context, &mut reader).unwrap();
+    assert_eq!(
+        ret.sub_code,
+        SpdmEndpointInfoSubCode::SpdmEndpointInfoSubCodeDeviceClassIdentifier
+    );
+    assert_eq!(
+        ret.request_attributes,
+        SpdmEndpointInfoRequestAttributes::SIGNATURE_REQUESTED
+    );
+    assert_eq!(ret.slot_id, 1);
+    assert_eq!(ret.nonce.data, [0xaau8; SPDM_NONCE_SIZE]);
+    assert_eq!(0, reader.left());
+
+    // Only DeviceClassIdentifier is defined.
+    u8_slice[0] = 2;
+    let mut reader = Reader::init(u8_slice);
+    assert!(SpdmGetEndpointInfoRequestPayload::spdm_read(&mut context, &mut reader).is_none());
+}
+
+#[test]
+fn test_case1_spdm_get_endpoint_info_request_payload() {
+    let u8_slice = &mut [0u8; 6];
+    let mut writer = Writer::init(u8_slice);
+    let value = SpdmGetEndpointInfoRequestPayload {
+        sub_code: SpdmEndpointInfoSubCode::SpdmEndpointInfoSubCodeDeviceClassIdentifier,
+        ..Default::default()
+    };
+
+    create_spdm_context!(context);
+
+    // No nonce without signature.
+    assert_eq!(value.spdm_encode(&mut context, &mut writer), Ok(6));
+    let mut reader = Reader::init(u8_slice);
+    let ret = SpdmGetEndpointInfoRequestPayload::spdm_read(&mut context, &mut reader).unwrap();
+    assert!(ret.request_attributes.is_empty());
+    assert_eq!(0, reader.left());
+}
+
+#[test]
+fn test_case0_spdm_endpoint_info_response_payload() {
+    let u8_slice = &mut [0u8; 10 + SPDM_NONCE_SIZE + 4 + ECDSA_ECC_NIST_P384_KEY_SIZE];
+    let mut writer = Writer::init(u8_slice);
+    let mut value = SpdmEndpointInfoResponsePayload {
+        slot_id: 2,
+        nonce: SpdmNonceStruct {
+            data: [0x55u8; SPDM_NONCE_SIZE],
+        },
+        signature: SpdmSignatureStruct {
+            data_size: ECDSA_ECC_NIST_P384_KEY_SIZE as u16,
+            data: [0xa5u8; SPDM_MAX_ASYM_KEY_SIZE],
+        },
+        ..Default::default()
+    };
+    value.endpoint_info.data_size = 4;
+    value.endpoint_info.data[..4].copy_from_slice(&[1, 2, 3, 4]);
+
+    create_spdm_context!(context);
+    context.negotiate_info.base_asym_sel = SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384;
+    context.runtime_info.need_endpoint_info_signature = true;
+
+    assert!(value.spdm_encode(&mut context, &mut writer).is_ok());
+    let mut reader = Reader::init(u8_slice);
+    let ret = SpdmEndpointInfoResponsePayload::spdm_read(&mut context, &mut reader).unwrap();
+    assert_eq!(ret.slot_id, 2);
+    assert_eq!(ret.nonce.data, [0x55u8; SPDM_NONCE_SIZE]);
+    assert_eq!(ret.endpoint_info.as_ref(), &[1, 2, 3, 4]);
+    assert_eq!(
+        ret.signature.as_ref(),
+        &[0xa5u8; ECDSA_ECC_NIST_P384_KEY_SIZE][..]
+    );
+    assert_eq!(0, reader.left());
+}
+
+#[test]
+fn test_case1_spdm_endpoint_info_response_payload() {
+    let u8_slice = &mut [0u8; 10 + 4];
+    let mut writer = Writer::init(u8_slice);
+    let mut value = SpdmEndpointInfoResponsePayload::default();
+    value.endpoint_info.data_size = 4;
+    value.endpoint_info.data[..4].copy_from_slice(&[1, 2, 3, 4]);
+
+    create_spdm_context!(context);
+    context.runtime_info.need_endpoint_info_signature = false;
+
+    // Neither nonce nor signature without signature.
+    assert_eq!(value.spdm_encode(&mut context, &mut writer), Ok(10 + 4));
+    let mut reader = Reader::init(u8_slice);
+    let ret = SpdmEndpointInfoResponsePayload::spdm_read(&mut context, &mut reader).unwrap();
+    assert_eq!(ret.endpoint_info.as_ref(), &[1, 2, 3, 4]);
+    assert_eq!(0, reader.left());
+
+    // EPInfoLen larger than the supported endpoint information.
+    u8_slice[6..10].copy_from_slice(&(SPDM_MAX_ENDPOINT_INFO_SIZE as u32 + 1).to_le_bytes());
+    let mut reader = Reader::init(u8_slice);
+    assert!(SpdmEndpointInfoResponsePayload::spdm_read(&mut context, &mut reader).is_none());
+}
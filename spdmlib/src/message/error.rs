@@ -30,6 +30,7 @@ enum_builder! {
         SpdmErrorVersionMismatch => 0x41,
         SpdmErrorResponseNotReady => 0x42,
         SpdmErrorRequestResynch => 0x43,
+        SpdmErrorOperationFailed => 0x44,
         SpdmErrorVendorDefined => 0xFF
     }
 }
@@ -0,0 +1,468 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use crate::common::spdm_codec::SpdmCodec;
+use crate::error::SPDM_STATUS_BUFFER_FULL;
+use crate::{common, error::SpdmStatus};
+use codec::{enum_builder, Codec, Reader, Writer};
+
+pub const MAX_SPDM_EVENT_GROUP_COUNT: usize = 4;
+pub const MAX_SPDM_EVENT_TYPE_COUNT: usize = 8;
+pub const MAX_SPDM_EVENT_COUNT: usize = 4;
+pub const MAX_SPDM_EVENT_DETAIL_SIZE: usize = 64;
+pub const MAX_SPDM_EVENT_VENDOR_ID_LEN: usize = 4;
+
+pub const SPDM_DMTF_EVENT_GROUP_VERSION: u8 = 1;
+
+// The event group is identified by the standards body / vendor header:
+// ID (1), VendorIDLen (1), VendorID (VendorIDLen).
+pub const SPDM_DMTF_EVENT_GROUP_ID: SpdmEventGroupId = SpdmEventGroupId {
+    id: 0,
+    vendor_id_len: 0,
+    vendor_id: [0u8; MAX_SPDM_EVENT_VENDOR_ID_LEN],
+};
+
+enum_builder! {
+    @U16
+    EnumName: SpdmDmtfEventType;
+    EnumVal{
+        EventLost => 0x0001,
+        MeasurementChanged => 0x0002,
+        MeasurementPreUpdate => 0x0003,
+        CertificateChanged => 0x0004
+    }
+}
+impl Default for SpdmDmtfEventType {
+    fn default() -> SpdmDmtfEventType {
+        SpdmDmtfEventType::Unknown(0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SpdmEventGroupId {
+    pub id: u8,
+    pub vendor_id_len: u8,
+    pub vendor_id: [u8; MAX_SPDM_EVENT_VENDOR_ID_LEN],
+}
+
+impl SpdmEventGroupId {
+    pub fn encoded_size(&self) -> usize {
+        2 + self.vendor_id_len as usize
+    }
+}
+
+impl Codec for SpdmEventGroupId {
+    fn encode(&self, bytes: &mut Writer) -> Result<usize, codec::EncodeErr> {
+        let mut cnt = 0usize;
+        cnt += self.id.encode(bytes)?;
+        cnt += self.vendor_id_len.encode(bytes)?;
+        for d in self.vendor_id.iter().take(self.vendor_id_len as usize) {
+            cnt += d.encode(bytes)?;
+        }
+        Ok(cnt)
+    }
+
+    fn read(r: &mut Reader) -> Option<SpdmEventGroupId> {
+        let id = u8::read(r)?;
+        let vendor_id_len = u8::read(r)?;
+        if vendor_id_len as usize > MAX_SPDM_EVENT_VENDOR_ID_LEN {
+            return None;
+        }
+        let mut vendor_id = [0u8; MAX_SPDM_EVENT_VENDOR_ID_LEN];
+        for d in vendor_id.iter_mut().take(vendor_id_len as usize) {
+            *d = u8::read(r)?;
+        }
+        Some(SpdmEventGroupId {
+            id,
+            vendor_id_len,
+            vendor_id,
+        })
+    }
+}
+
+// Event group: EventGroupID, EventGroupVer (1), EventTypeCount (1),
+// EventTypeID (2) * EventTypeCount.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SpdmEventGroup {
+    pub group_id: SpdmEventGroupId,
+    pub group_version: u8,
+    pub event_type_count: u8,
+    pub event_types: [u16; MAX_SPDM_EVENT_TYPE_COUNT],
+}
+
+impl SpdmEventGroup {
+    // The DMTF event group with all the event types defined by the specification.
+    pub fn new_dmtf() -> Self {
+        let mut event_types = [0u16; MAX_SPDM_EVENT_TYPE_COUNT];
+        event_types[0] = SpdmDmtfEventType::EventLost.get_u16();
+        event_types[1] = SpdmDmtfEventType::MeasurementChanged.get_u16();
+        event_types[2] = SpdmDmtfEventType::MeasurementPreUpdate.get_u16();
+        event_types[3] = SpdmDmtfEventType::CertificateChanged.get_u16();
+        SpdmEventGroup {
+            group_id: SPDM_DMTF_EVENT_GROUP_ID,
+            group_version: SPDM_DMTF_EVENT_GROUP_VERSION,
+            event_type_count: 4,
+            event_types,
+        }
+    }
+
+    pub fn encoded_size(&self) -> usize {
+        self.group_id.encoded_size() + 2 + 2 * self.event_type_count as usize
+    }
+
+    pub fn contains_event_type(&self, event_type_id: u16) -> bool {
+        self.event_types
+            .iter()
+            .take(self.event_type_count as usize)
+            .any(|t| *t == event_type_id)
+    }
+}
+
+impl Codec for SpdmEventGroup {
+    fn encode(&self, bytes: &mut Writer) -> Result<usize, codec::EncodeErr> {
+        let mut cnt = 0usize;
+        cnt += self.group_id.encode(bytes)?;
+        cnt += self.group_version.encode(bytes)?;
+        cnt += self.event_type_count.encode(bytes)?;
+        for t in self.event_types.iter().take(self.event_type_count as usize) {
+            cnt += t.encode(bytes)?;
+        }
+        Ok(cnt)
+    }
+
+    fn read(r: &mut Reader) -> Option<SpdmEventGroup> {
+        let group_id = SpdmEventGroupId::read(r)?;
+        let group_version = u8::read(r)?;
+        let event_type_count = u8::read(r)?;
+        if event_type_count as usize > MAX_SPDM_EVENT_TYPE_COUNT {
+            return None;
+        }
+        let mut event_types = [0u16; MAX_SPDM_EVENT_TYPE_COUNT];
+        for t in event_types.iter_mut().take(event_type_count as usize) {
+            *t = u16::read(r)?;
+        }
+        Some(SpdmEventGroup {
+            group_id,
+            group_version,
+            event_type_count,
+            event_types,
+        })
+    }
+}
+
+fn encode_event_group_list(
+    event_group_count: u8,
+    event_groups: &[SpdmEventGroup; MAX_SPDM_EVENT_GROUP_COUNT],
+    bytes: &mut Writer,
+) -> Result<usize, SpdmStatus> {
+    let mut cnt = 0usize;
+    let list_len: usize = event_groups
+        .iter()
+        .take(event_group_count as usize)
+        .map(|g| g.encoded_size())
+        .sum();
+    cnt += (list_len as u32)
+        .encode(bytes)
+        .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+    for group in event_groups.iter().take(event_group_count as usize) {
+        cnt += group.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+    }
+    Ok(cnt)
+}
+
+fn read_event_group_list(
+    event_group_count: u8,
+    r: &mut Reader,
+) -> Option<[SpdmEventGroup; MAX_SPDM_EVENT_GROUP_COUNT]> {
+    if event_group_count as usize > MAX_SPDM_EVENT_GROUP_COUNT {
+        return None;
+    }
+    let list_len = u32::read(r)? as usize;
+    let mut list_reader = r.sub(list_len)?;
+    let mut event_groups = [SpdmEventGroup::default(); MAX_SPDM_EVENT_GROUP_COUNT];
+    for group in event_groups.iter_mut().take(event_group_count as usize) {
+        *group = SpdmEventGroup::read(&mut list_reader)?;
+    }
+    if list_reader.any_left() {
+        return None;
+    }
+    Some(event_groups)
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SpdmGetSupportedEventTypesRequestPayload {}
+
+impl SpdmCodec for SpdmGetSupportedEventTypesRequestPayload {
+    fn spdm_encode(
+        &self,
+        _context: &mut common::SpdmContext,
+        bytes: &mut Writer,
+    ) -> Result<usize, SpdmStatus> {
+        let mut cnt = 0usize;
+        cnt += 0u8.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param1
+        cnt += 0u8.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param2
+        Ok(cnt)
+    }
+
+    fn spdm_read(
+        _context: &mut common::SpdmContext,
+        r: &mut Reader,
+    ) -> Option<SpdmGetSupportedEventTypesRequestPayload> {
+        u8::read(r)?; // param1
+        u8::read(r)?; // param2
+
+        Some(SpdmGetSupportedEventTypesRequestPayload {})
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SpdmSupportedEventTypesResponsePayload {
+    pub event_group_count: u8,
+    pub event_groups: [SpdmEventGroup; MAX_SPDM_EVENT_GROUP_COUNT],
+}
+
+impl SpdmCodec for SpdmSupportedEventTypesResponsePayload {
+    fn spdm_encode(
+        &self,
+        _context: &mut common::SpdmContext,
+        bytes: &mut Writer,
+    ) -> Result<usize, SpdmStatus> {
+        let mut cnt = 0usize;
+        cnt += self
+            .event_group_count
+            .encode(bytes)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param1
+        cnt += 0u8.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param2
+        cnt += encode_event_group_list(self.event_group_count, &self.event_groups, bytes)?;
+        Ok(cnt)
+    }
+
+    fn spdm_read(
+        _context: &mut common::SpdmContext,
+        r: &mut Reader,
+    ) -> Option<SpdmSupportedEventTypesResponsePayload> {
+        let event_group_count = u8::read(r)?; // param1
+        u8::read(r)?; // param2
+        if event_group_count == 0 {
+            return None;
+        }
+        let event_groups = read_event_group_list(event_group_count, r)?;
+
+        Some(SpdmSupportedEventTypesResponsePayload {
+            event_group_count,
+            event_groups,
+        })
+    }
+}
+
+// A SubscribeEventGroupCount of 0 clears all the subscriptions of the session.
+#[derive(Debug, Clone, Default)]
+pub struct SpdmSubscribeEventTypesRequestPayload {
+    pub event_group_count: u8,
+    pub event_groups: [SpdmEventGroup; MAX_SPDM_EVENT_GROUP_COUNT],
+}
+
+impl SpdmCodec for SpdmSubscribeEventTypesRequestPayload {
+    fn spdm_encode(
+        &self,
+        _context: &mut common::SpdmContext,
+        bytes: &mut Writer,
+    ) -> Result<usize, SpdmStatus> {
+        let mut cnt = 0usize;
+        cnt += self
+            .event_group_count
+            .encode(bytes)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param1
+        cnt += 0u8.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param2
+        cnt += encode_event_group_list(self.event_group_count, &self.event_groups, bytes)?;
+        Ok(cnt)
+    }
+
+    fn spdm_read(
+        _context: &mut common::SpdmContext,
+        r: &mut Reader,
+    ) -> Option<SpdmSubscribeEventTypesRequestPayload> {
+        let event_group_count = u8::read(r)?; // param1
+        u8::read(r)?; // param2
+        let event_groups = read_event_group_list(event_group_count, r)?;
+
+        Some(SpdmSubscribeEventTypesRequestPayload {
+            event_group_count,
+            event_groups,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SpdmSubscribeEventTypesAckResponsePayload {}
+
+impl SpdmCodec for SpdmSubscribeEventTypesAckResponsePayload {
+    fn spdm_encode(
+        &self,
+        _context: &mut common::SpdmContext,
+        bytes: &mut Writer,
+    ) -> Result<usize, SpdmStatus> {
+        let mut cnt = 0usize;
+        cnt += 0u8.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param1
+        cnt += 0u8.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param2
+        Ok(cnt)
+    }
+
+    fn spdm_read(
+        _context: &mut common::SpdmContext,
+        r: &mut Reader,
+    ) -> Option<SpdmSubscribeEventTypesAckResponsePayload> {
+        u8::read(r)?; // param1
+        u8::read(r)?; // param2
+
+        Some(SpdmSubscribeEventTypesAckResponsePayload {})
+    }
+}
+
+// Event data: EventInstanceID (4), Reserved (4), EventGroupID, EventTypeID (2),
+// EventDetailLen (2), EventDetail (EventDetailLen).
+#[derive(Debug, Clone, Copy)]
+pub struct SpdmEventData {
+    pub event_instance_id: u32,
+    pub group_id: SpdmEventGroupId,
+    pub event_type_id: u16,
+    pub event_detail_len: u16,
+    pub event_detail: [u8; MAX_SPDM_EVENT_DETAIL_SIZE],
+}
+
+impl Default for SpdmEventData {
+    fn default() -> SpdmEventData {
+        SpdmEventData {
+            event_instance_id: 0,
+            group_id: SpdmEventGroupId::default(),
+            event_type_id: 0,
+            event_detail_len: 0,
+            event_detail: [0u8; MAX_SPDM_EVENT_DETAIL_SIZE],
+        }
+    }
+}
+
+impl SpdmEventData {
+    pub fn event_detail(&self) -> &[u8] {
+        &self.event_detail[..self.event_detail_len as usize]
+    }
+}
+
+impl Codec for SpdmEventData {
+    fn encode(&self, bytes: &mut Writer) -> Result<usize, codec::EncodeErr> {
+        let mut cnt = 0usize;
+        cnt += self.event_instance_id.encode(bytes)?;
+        cnt += 0u32.encode(bytes)?; // reserved
+        cnt += self.group_id.encode(bytes)?;
+        cnt += self.event_type_id.encode(bytes)?;
+        cnt += self.event_detail_len.encode(bytes)?;
+        for d in self.event_detail() {
+            cnt += d.encode(bytes)?;
+        }
+        Ok(cnt)
+    }
+
+    fn read(r: &mut Reader) -> Option<SpdmEventData> {
+        let event_instance_id = u32::read(r)?;
+        u32::read(r)?; // reserved
+        let group_id = SpdmEventGroupId::read(r)?;
+        let event_type_id = u16::read(r)?;
+        let event_detail_len = u16::read(r)?;
+        if event_detail_len as usize > MAX_SPDM_EVENT_DETAIL_SIZE {
+            return None;
+        }
+        let mut event_detail = [0u8; MAX_SPDM_EVENT_DETAIL_SIZE];
+        for d in event_detail.iter_mut().take(event_detail_len as usize) {
+            *d = u8::read(r)?;
+        }
+        Some(SpdmEventData {
+            event_instance_id,
+            group_id,
+            event_type_id,
+            event_detail_len,
+            event_detail,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SpdmSendEventRequestPayload {
+    pub event_count: u32,
+    pub events: [SpdmEventData; MAX_SPDM_EVENT_COUNT],
+}
+
+impl SpdmCodec for SpdmSendEventRequestPayload {
+    fn spdm_encode(
+        &self,
+        _context: &mut common::SpdmContext,
+        bytes: &mut Writer,
+    ) -> Result<usize, SpdmStatus> {
+        let mut cnt = 0usize;
+        cnt += 0u8.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param1
+        cnt += 0u8.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param2
+        cnt += self
+            .event_count
+            .encode(bytes)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        for event in self.events.iter().take(self.event_count as usize) {
+            cnt += event.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        }
+        Ok(cnt)
+    }
+
+    fn spdm_read(
+        _context: &mut common::SpdmContext,
+        r: &mut Reader,
+    ) -> Option<SpdmSendEventRequestPayload> {
+        u8::read(r)?; // param1
+        u8::read(r)?; // param2
+        let event_count = u32::read(r)?;
+        if event_count == 0 || event_count as usize > MAX_SPDM_EVENT_COUNT {
+            return None;
+        }
+        let mut events = [SpdmEventData::default(); MAX_SPDM_EVENT_COUNT];
+        for event in events.iter_mut().take(event_count as usize) {
+            *event = SpdmEventData::read(r)?;
+        }
+
+        Some(SpdmSendEventRequestPayload {
+            event_count,
+            events,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SpdmEventAckResponsePayload {}
+
+impl SpdmCodec for SpdmEventAckResponsePayload {
+    fn spdm_encode(
+        &self,
+        _context: &mut common::SpdmContext,
+        bytes: &mut Writer,
+    ) -> Result<usize, SpdmStatus> {
+        let mut cnt = 0usize;
+        cnt += 0u8.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param1
+        cnt += 0u8.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param2
+        Ok(cnt)
+    }
+
+    fn spdm_read(
+        _context: &mut common::SpdmContext,
+        r: &mut Reader,
+    ) -> Option<SpdmEventAckResponsePayload> {
+        u8::read(r)?; // param1
+        u8::read(r)?; // param2
+
+        Some(SpdmEventAckResponsePayload {})
+    }
+}
+
+#[cfg(all(test,))]
+#[path = "mod_test.common.inc.rs"]
+mod testlib;
+
+#[cfg(all(test,))]
+#[path = "event_test.rs"]
+mod event_test;
@@ -0,0 +1,123 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use super::*;
+use crate::common::{SpdmCodec, SpdmConfigInfo, SpdmContext, SpdmProvisionInfo};
+use testlib::{create_spdm_context, DeviceIO, TransportEncap};
+
+fn dmtf_event_group() -> SpdmEventGroup {
+    let mut event_types = [0u16; MAX_SPDM_EVENT_TYPE_COUNT];
+    event_types[0] = SpdmDmtfEventType::MeasurementChanged.get_u16();
+    event_types[1] = SpdmDmtfEventType::CertificateChanged.get_u16();
+    SpdmEventGroup {
+        group_id: SPDM_DMTF_EVENT_GROUP_ID,
+        group_version: SPDM_DMTF_EVENT_GROUP_VERSION,
+        event_type_count: 2,
+        event_types,
+    }
+}
+
+#[test]
+fn test_case0_spdm_supported_event_types_response_payload() {
+    let u8_slice = &mut [0u8; 64];
+    let mut writer = Writer::init(u8_slice);
+    let mut event_groups = [SpdmEventGroup::default(); MAX_SPDM_EVENT_GROUP_COUNT];
+    event_groups[0] = dmtf_event_group();
+    let value = SpdmSupportedEventTypesResponsePayload {
+        event_group_count: 1,
+        event_groups,
+    };
+
+    create_spdm_context!(context);
+
+    // param1, param2, list length (4), group (2 + 1 + 1 + 2 * 2)
+    assert_eq!(value.spdm_encode(&mut context, &mut writer), Ok(14));
+    let mut reader = Reader::init(u8_slice);
+    let ret = SpdmSupportedEventTypesResponsePayload::spdm_read(&mut context, &mut reader).unwrap();
+    assert_eq!(ret.event_group_count, 1);
+    assert_eq!(ret.event_groups[0].group_id, SPDM_DMTF_EVENT_GROUP_ID);
+    assert_eq!(ret.event_groups[0].event_type_count, 2);
+    assert!(ret.event_groups[0].contains_event_type(0x0002));
+    assert!(ret.event_groups[0].contains_event_type(0x0004));
+    assert!(!ret.event_groups[0].contains_event_type(0x0001));
+}
+
+#[test]
+fn test_case1_spdm_subscribe_event_types_request_payload() {
+    create_spdm_context!(context);
+
+    // Unsubscribe all carries an empty list.
+    let u8_slice = &mut [0u8; 64];
+    let mut writer = Writer::init(u8_slice);
+    let value = SpdmSubscribeEventTypesRequestPayload::default();
+    assert_eq!(value.spdm_encode(&mut context, &mut writer), Ok(6));
+    let mut reader = Reader::init(u8_slice);
+    let ret = SpdmSubscribeEventTypesRequestPayload::spdm_read(&mut context, &mut reader).unwrap();
+    assert_eq!(ret.event_group_count, 0);
+
+    // The group list must consume exactly the advertised length.
+    let u8_slice = &mut [0u8; 64];
+    let mut writer = Writer::init(u8_slice);
+    let mut event_groups = [SpdmEventGroup::default(); MAX_SPDM_EVENT_GROUP_COUNT];
+    event_groups[0] = dmtf_event_group();
+    let value = SpdmSubscribeEventTypesRequestPayload {
+        event_group_count: 1,
+        event_groups,
+    };
+    assert_eq!(value.spdm_encode(&mut context, &mut writer), Ok(14));
+    u8_slice[2] = 9;
+    let mut reader = Reader::init(u8_slice);
+    assert!(SpdmSubscribeEventTypesRequestPayload::spdm_read(&mut context, &mut reader).is_none());
+}
+
+#[test]
+fn test_case2_spdm_send_event_request_payload() {
+    let u8_slice = &mut [0u8; 128];
+    let mut writer = Writer::init(u8_slice);
+    let mut events = [SpdmEventData::default(); MAX_SPDM_EVENT_COUNT];
+    events[0].event_instance_id = 7;
+    events[0].group_id = SPDM_DMTF_EVENT_GROUP_ID;
+    events[0].event_type_id = SpdmDmtfEventType::MeasurementChanged.get_u16();
+    events[0].event_detail_len = 3;
+    events[0].event_detail[..3].copy_from_slice(&[1, 2, 3]);
+    let value = SpdmSendEventRequestPayload {
+        event_count: 1,
+        events,
+    };
+
+    create_spdm_context!(context);
+
+    // param1, param2, event count (4), event (4 + 4 + 2 + 2 + 2 + 3)
+    assert_eq!(value.spdm_encode(&mut context, &mut writer), Ok(23));
+    let mut reader = Reader::init(u8_slice);
+    let ret = SpdmSendEventRequestPayload::spdm_read(&mut context, &mut reader).unwrap();
+    assert_eq!(ret.event_count, 1);
+    assert_eq!(ret.events[0].event_instance_id, 7);
+    assert_eq!(ret.events[0].event_type_id, 0x0002);
+    assert_eq!(ret.events[0].event_detail(), &[1, 2, 3]);
+
+    // An empty event list is invalid.
+    let u8_slice = &mut [0u8; 8];
+    let mut reader = Reader::init(u8_slice);
+    assert!(SpdmSendEventRequestPayload::spdm_read(&mut context, &mut reader).is_none());
+}
+
+#[test]
+fn test_case3_spdm_event_ack_payloads() {
+    create_spdm_context!(context);
+    let context = &mut context;
+
+    let u8_slice = &mut [0u8; 4];
+    let reader = &mut Reader::init(&u8_slice[2..]);
+    assert!(SpdmGetSupportedEventTypesRequestPayload::spdm_read(context, reader).is_some());
+    assert_eq!(reader.left(), 0);
+
+    let reader = &mut Reader::init(&u8_slice[2..]);
+    assert!(SpdmSubscribeEventTypesAckResponsePayload::spdm_read(context, reader).is_some());
+    assert_eq!(reader.left(), 0);
+
+    let reader = &mut Reader::init(&u8_slice[2..]);
+    assert!(SpdmEventAckResponsePayload::spdm_read(context, reader).is_some());
+    assert_eq!(reader.left(), 0);
+}
@@ -150,13 +150,13 @@ mod tests {
 
     #[test]
     fn test_case0_spdm_finish_request_payload() {
-        let u8_slice = &mut [0u8; 2 + SPDM_MAX_ASYM_KEY_SIZE + SPDM_MAX_HASH_SIZE];
+        let u8_slice = &mut [0u8; 2 + RSASSA_4096_KEY_SIZE + SPDM_MAX_HASH_SIZE];
         let mut writer = Writer::init(u8_slice);
         let value = SpdmFinishRequestPayload {
             finish_request_attributes: SpdmFinishRequestAttributes::SIGNATURE_INCLUDED,
             req_slot_id: 100,
             signature: SpdmSignatureStruct {
-                data_size: SPDM_MAX_ASYM_KEY_SIZE as u16,
+                data_size: RSASSA_4096_KEY_SIZE as u16,
                 data: [0xa5u8; SPDM_MAX_ASYM_KEY_SIZE],
             },
             verify_data: SpdmDigestStruct {
@@ -172,10 +172,7 @@ mod tests {
 
         assert!(value.spdm_encode(&mut context, &mut writer).is_ok());
         let mut reader = Reader::init(u8_slice);
-        assert_eq!(
-            2 + SPDM_MAX_ASYM_KEY_SIZE + SPDM_MAX_HASH_SIZE,
-            reader.left()
-        );
+        assert_eq!(2 + RSASSA_4096_KEY_SIZE + SPDM_MAX_HASH_SIZE, reader.left());
         let spdm_finish_request_payload =
             SpdmFinishRequestPayload::spdm_read(&mut context, &mut reader).unwrap();
         assert_eq!(
@@ -206,7 +203,7 @@ mod tests {
             finish_request_attributes: SpdmFinishRequestAttributes::empty(),
             req_slot_id: 100,
             signature: SpdmSignatureStruct {
-                data_size: SPDM_MAX_ASYM_KEY_SIZE as u16,
+                data_size: RSASSA_4096_KEY_SIZE as u16,
                 data: [0xa5u8; SPDM_MAX_ASYM_KEY_SIZE],
             },
             verify_data: SpdmDigestStruct {
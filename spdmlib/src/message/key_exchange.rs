@@ -217,7 +217,7 @@ impl SpdmCodec for SpdmKeyExchangeResponsePayload {
         }
 
         let random = SpdmRandomStruct::read(r)?;
-        let exchange = SpdmDheExchangeStruct::spdm_read(context, r)?;
+        let exchange = SpdmDheExchangeStruct::spdm_read_rsp(context, r)?;
         let measurement_summary_hash = if context.runtime_info.need_measurement_summary_hash {
             SpdmDigestStruct::spdm_read(context, r)?
         } else {
@@ -283,8 +283,11 @@ mod tests {
     }
     #[test]
     fn test_case0_spdm_key_exchange_request_payload() {
-        let u8_slice =
-            &mut [0u8; 6 + SPDM_RANDOM_SIZE + SPDM_MAX_DHE_KEY_SIZE + 2 + MAX_SPDM_OPAQUE_SIZE];
+        let u8_slice = &mut [0u8; 6
+            + SPDM_RANDOM_SIZE
+            + ECDSA_ECC_NIST_P384_KEY_SIZE
+            + 2
+            + MAX_SPDM_OPAQUE_SIZE];
         let mut writer = Writer::init(u8_slice);
         let value = SpdmKeyExchangeRequestPayload {
             measurement_summary_hash_type:
@@ -296,7 +299,7 @@ mod tests {
                 data: [100u8; SPDM_RANDOM_SIZE],
             },
             exchange: SpdmDheExchangeStruct {
-                data_size: SPDM_MAX_DHE_KEY_SIZE as u16,
+                data_size: ECDSA_ECC_NIST_P384_KEY_SIZE as u16,
                 data: [100u8; SPDM_MAX_DHE_KEY_SIZE],
             },
             opaque: SpdmOpaqueStruct {
@@ -312,7 +315,7 @@ mod tests {
         assert!(value.spdm_encode(&mut context, &mut writer).is_ok());
         let mut reader = Reader::init(u8_slice);
         assert_eq!(
-            6 + SPDM_RANDOM_SIZE + SPDM_MAX_DHE_KEY_SIZE + 2 + MAX_SPDM_OPAQUE_SIZE,
+            6 + SPDM_RANDOM_SIZE + ECDSA_ECC_NIST_P384_KEY_SIZE + 2 + MAX_SPDM_OPAQUE_SIZE,
             reader.left()
         );
         let exchange_request_payload =
@@ -342,15 +345,76 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_case1_spdm_key_exchange_request_payload() {
+        let u8_slice =
+            &mut [0u8; 6 + SPDM_RANDOM_SIZE + FFDHE_4096_KEY_SIZE + 2 + MAX_SPDM_OPAQUE_SIZE];
+        let mut writer = Writer::init(u8_slice);
+        let value = SpdmKeyExchangeRequestPayload {
+            measurement_summary_hash_type:
+                SpdmMeasurementSummaryHashType::SpdmMeasurementSummaryHashTypeNone,
+            slot_id: 100u8,
+            req_session_id: 100u16,
+            session_policy: 1,
+            random: SpdmRandomStruct {
+                data: [100u8; SPDM_RANDOM_SIZE],
+            },
+            exchange: SpdmDheExchangeStruct {
+                data_size: FFDHE_4096_KEY_SIZE as u16,
+                data: [100u8; SPDM_MAX_DHE_KEY_SIZE],
+            },
+            opaque: SpdmOpaqueStruct {
+                data_size: MAX_SPDM_OPAQUE_SIZE as u16,
+                data: [100u8; MAX_SPDM_OPAQUE_SIZE],
+            },
+        };
+
+        create_spdm_context!(context);
+
+        context.negotiate_info.dhe_sel = SpdmDheAlgo::FFDHE_4096;
+
+        assert!(value.spdm_encode(&mut context, &mut writer).is_ok());
+        let mut reader = Reader::init(u8_slice);
+        assert_eq!(
+            6 + SPDM_RANDOM_SIZE + FFDHE_4096_KEY_SIZE + 2 + MAX_SPDM_OPAQUE_SIZE,
+            reader.left()
+        );
+        let exchange_request_payload =
+            SpdmKeyExchangeRequestPayload::spdm_read(&mut context, &mut reader).unwrap();
+
+        assert_eq!(
+            exchange_request_payload.measurement_summary_hash_type,
+            SpdmMeasurementSummaryHashType::SpdmMeasurementSummaryHashTypeNone
+        );
+        assert_eq!(exchange_request_payload.slot_id, 100);
+        for i in 0..SPDM_RANDOM_SIZE {
+            assert_eq!(exchange_request_payload.random.data[i], 100);
+        }
+        assert_eq!(
+            exchange_request_payload.exchange.data_size,
+            FFDHE_4096_KEY_SIZE as u16
+        );
+        for i in 0..FFDHE_4096_KEY_SIZE {
+            assert_eq!(exchange_request_payload.exchange.data[i], 100);
+        }
+        assert_eq!(
+            exchange_request_payload.opaque.data_size,
+            MAX_SPDM_OPAQUE_SIZE as u16
+        );
+        for i in 0..MAX_SPDM_OPAQUE_SIZE {
+            assert_eq!(exchange_request_payload.opaque.data[i], 100);
+        }
+    }
+
     #[test]
     fn test_case0_spdm_key_exchange_response_payload() {
         let u8_slice = &mut [0u8; 6
             + SPDM_RANDOM_SIZE
-            + SPDM_MAX_DHE_KEY_SIZE
+            + ECDSA_ECC_NIST_P384_KEY_SIZE
             + SPDM_MAX_HASH_SIZE
             + 2
             + MAX_SPDM_OPAQUE_SIZE
-            + SPDM_MAX_ASYM_KEY_SIZE
+            + RSAPSS_4096_KEY_SIZE
             + SPDM_MAX_HASH_SIZE];
         let mut writer = Writer::init(u8_slice);
         let value = SpdmKeyExchangeResponsePayload {
@@ -362,7 +426,7 @@ mod tests {
                 data: [100u8; SPDM_RANDOM_SIZE],
             },
             exchange: SpdmDheExchangeStruct {
-                data_size: SPDM_MAX_DHE_KEY_SIZE as u16,
+                data_size: ECDSA_ECC_NIST_P384_KEY_SIZE as u16,
                 data: [0xa5u8; SPDM_MAX_DHE_KEY_SIZE],
             },
             measurement_summary_hash: SpdmDigestStruct {
@@ -374,7 +438,7 @@ mod tests {
                 data: [0x22u8; MAX_SPDM_OPAQUE_SIZE],
             },
             signature: SpdmSignatureStruct {
-                data_size: SPDM_MAX_ASYM_KEY_SIZE as u16,
+                data_size: RSAPSS_4096_KEY_SIZE as u16,
                 data: [0x5au8; SPDM_MAX_ASYM_KEY_SIZE],
             },
             verify_data: SpdmDigestStruct {
@@ -394,11 +458,11 @@ mod tests {
         let mut reader = Reader::init(u8_slice);
         assert_eq!(
             6 + SPDM_RANDOM_SIZE
-                + SPDM_MAX_DHE_KEY_SIZE
+                + ECDSA_ECC_NIST_P384_KEY_SIZE
                 + SPDM_MAX_HASH_SIZE
                 + 2
                 + MAX_SPDM_OPAQUE_SIZE
-                + SPDM_MAX_ASYM_KEY_SIZE
+                + RSAPSS_4096_KEY_SIZE
                 + SPDM_MAX_HASH_SIZE,
             reader.left()
         );
@@ -462,10 +526,10 @@ mod tests {
     fn test_case1_spdm_key_exchange_response_payload() {
         let u8_slice = &mut [0u8; 6
             + SPDM_RANDOM_SIZE
-            + SPDM_MAX_DHE_KEY_SIZE
+            + ECDSA_ECC_NIST_P384_KEY_SIZE
             + 2
             + MAX_SPDM_OPAQUE_SIZE
-            + SPDM_MAX_ASYM_KEY_SIZE
+            + RSAPSS_4096_KEY_SIZE
             + SPDM_MAX_HASH_SIZE];
         let mut writer = Writer::init(u8_slice);
         let value = SpdmKeyExchangeResponsePayload {
@@ -477,7 +541,7 @@ mod tests {
                 data: [100u8; SPDM_RANDOM_SIZE],
             },
             exchange: SpdmDheExchangeStruct {
-                data_size: SPDM_MAX_DHE_KEY_SIZE as u16,
+                data_size: ECDSA_ECC_NIST_P384_KEY_SIZE as u16,
                 data: [0xa5u8; SPDM_MAX_DHE_KEY_SIZE],
             },
             measurement_summary_hash: SpdmDigestStruct::default(),
@@ -486,7 +550,7 @@ mod tests {
                 data: [0x22u8; MAX_SPDM_OPAQUE_SIZE],
             },
             signature: SpdmSignatureStruct {
-                data_size: SPDM_MAX_ASYM_KEY_SIZE as u16,
+                data_size: RSAPSS_4096_KEY_SIZE as u16,
                 data: [0x5au8; SPDM_MAX_ASYM_KEY_SIZE],
             },
             verify_data: SpdmDigestStruct {
@@ -506,10 +570,10 @@ mod tests {
         let mut reader = Reader::init(u8_slice);
         assert_eq!(
             6 + SPDM_RANDOM_SIZE
-                + SPDM_MAX_DHE_KEY_SIZE
+                + ECDSA_ECC_NIST_P384_KEY_SIZE
                 + 2
                 + MAX_SPDM_OPAQUE_SIZE
-                + SPDM_MAX_ASYM_KEY_SIZE
+                + RSAPSS_4096_KEY_SIZE
                 + SPDM_MAX_HASH_SIZE,
             reader.left()
         );
@@ -566,6 +630,123 @@ mod tests {
         }
         assert_eq!(0, reader.left());
     }
+
+    #[test]
+    fn test_case2_spdm_key_exchange_response_payload() {
+        let u8_slice = &mut [0u8; 6
+            + SPDM_RANDOM_SIZE
+            + FFDHE_4096_KEY_SIZE
+            + SPDM_MAX_HASH_SIZE
+            + 2
+            + MAX_SPDM_OPAQUE_SIZE
+            + RSAPSS_4096_KEY_SIZE
+            + SPDM_MAX_HASH_SIZE];
+        let mut writer = Writer::init(u8_slice);
+        let value = SpdmKeyExchangeResponsePayload {
+            heartbeat_period: 100u8,
+            rsp_session_id: 100u16,
+            mut_auth_req: SpdmKeyExchangeMutAuthAttributes::MUT_AUTH_REQ,
+            req_slot_id: 100u8,
+            random: SpdmRandomStruct {
+                data: [100u8; SPDM_RANDOM_SIZE],
+            },
+            exchange: SpdmDheExchangeStruct {
+                data_size: FFDHE_4096_KEY_SIZE as u16,
+                data: [0xa5u8; SPDM_MAX_DHE_KEY_SIZE],
+            },
+            measurement_summary_hash: SpdmDigestStruct {
+                data_size: SPDM_MAX_HASH_SIZE as u16,
+                data: Box::new([0x11u8; SPDM_MAX_HASH_SIZE]),
+            },
+            opaque: SpdmOpaqueStruct {
+                data_size: MAX_SPDM_OPAQUE_SIZE as u16,
+                data: [0x22u8; MAX_SPDM_OPAQUE_SIZE],
+            },
+            signature: SpdmSignatureStruct {
+                data_size: RSAPSS_4096_KEY_SIZE as u16,
+                data: [0x5au8; SPDM_MAX_ASYM_KEY_SIZE],
+            },
+            verify_data: SpdmDigestStruct {
+                data_size: SPDM_MAX_HASH_SIZE as u16,
+                data: Box::new([0x33u8; SPDM_MAX_HASH_SIZE]),
+            },
+        };
+
+        create_spdm_context!(context);
+
+        context.negotiate_info.dhe_sel = SpdmDheAlgo::FFDHE_4096;
+        context.negotiate_info.base_hash_sel = SpdmBaseHashAlgo::TPM_ALG_SHA_512;
+        context.negotiate_info.base_asym_sel = SpdmBaseAsymAlgo::TPM_ALG_RSAPSS_4096;
+        context.runtime_info.need_measurement_summary_hash = true;
+
+        assert!(value.spdm_encode(&mut context, &mut writer).is_ok());
+        let mut reader = Reader::init(u8_slice);
+        assert_eq!(
+            6 + SPDM_RANDOM_SIZE
+                + FFDHE_4096_KEY_SIZE
+                + SPDM_MAX_HASH_SIZE
+                + 2
+                + MAX_SPDM_OPAQUE_SIZE
+                + RSAPSS_4096_KEY_SIZE
+                + SPDM_MAX_HASH_SIZE,
+            reader.left()
+        );
+        let exchange_request_payload =
+            SpdmKeyExchangeResponsePayload::spdm_read(&mut context, &mut reader).unwrap();
+
+        assert_eq!(exchange_request_payload.heartbeat_period, 100);
+        assert_eq!(exchange_request_payload.rsp_session_id, 100);
+        assert_eq!(
+            exchange_request_payload.mut_auth_req,
+            SpdmKeyExchangeMutAuthAttributes::MUT_AUTH_REQ
+        );
+        assert_eq!(exchange_request_payload.req_slot_id, 100);
+        for i in 0..SPDM_RANDOM_SIZE {
+            assert_eq!(exchange_request_payload.random.data[i], 100);
+        }
+
+        assert_eq!(
+            exchange_request_payload.exchange.data_size,
+            FFDHE_4096_KEY_SIZE as u16
+        );
+        for i in 0..FFDHE_4096_KEY_SIZE {
+            assert_eq!(exchange_request_payload.exchange.data[i], 0xa5);
+        }
+
+        assert_eq!(
+            exchange_request_payload.signature.data_size,
+            RSAPSS_4096_KEY_SIZE as u16
+        );
+        for i in 0..RSAPSS_4096_KEY_SIZE {
+            assert_eq!(exchange_request_payload.signature.data[i], 0x5a);
+        }
+
+        assert_eq!(
+            exchange_request_payload.measurement_summary_hash.data_size,
+            SHA512_DIGEST_SIZE as u16
+        );
+        assert_eq!(
+            exchange_request_payload.verify_data.data_size,
+            SHA512_DIGEST_SIZE as u16
+        );
+        assert_eq!(
+            exchange_request_payload.opaque.data_size,
+            MAX_SPDM_OPAQUE_SIZE as u16
+        );
+        for i in 0..SHA512_DIGEST_SIZE {
+            assert_eq!(
+                exchange_request_payload.measurement_summary_hash.data[i],
+                0x11
+            );
+        }
+        for i in 0..MAX_SPDM_OPAQUE_SIZE {
+            assert_eq!(exchange_request_payload.opaque.data[i], 0x22);
+        }
+        for i in 0..SHA512_DIGEST_SIZE {
+            assert_eq!(exchange_request_payload.verify_data.data[i], 0x33);
+        }
+        assert_eq!(0, reader.left());
+    }
 }
 
 #[cfg(all(test,))]
@@ -0,0 +1,271 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use crate::common::spdm_codec::SpdmCodec;
+use crate::error::SPDM_STATUS_BUFFER_FULL;
+use crate::protocol::{
+    SpdmKeyPairAsymAlgo, SpdmKeyPairCapabilities, SpdmKeyPairInfo, SpdmKeyUsageMask,
+    SpdmSetKeyPairInfoOperation, SPDM_DEFAULT_KEY_PAIR_ID, SPDM_MAX_PUBLIC_KEY_SIZE,
+};
+use crate::{common, error::SpdmStatus};
+use codec::{Codec, Reader, Writer};
+
+#[derive(Debug, Clone, Default)]
+pub struct SpdmGetKeyPairInfoRequestPayload {
+    pub key_pair_id: u8,
+}
+
+impl SpdmCodec for SpdmGetKeyPairInfoRequestPayload {
+    fn spdm_encode(
+        &self,
+        _context: &mut common::SpdmContext,
+        bytes: &mut Writer,
+    ) -> Result<usize, SpdmStatus> {
+        let mut cnt = 0usize;
+        cnt += 0u8.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param1
+        cnt += 0u8.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param2
+        cnt += self
+            .key_pair_id
+            .encode(bytes)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        Ok(cnt)
+    }
+
+    fn spdm_read(
+        _context: &mut common::SpdmContext,
+        r: &mut Reader,
+    ) -> Option<SpdmGetKeyPairInfoRequestPayload> {
+        u8::read(r)?; // param1
+        u8::read(r)?; // param2
+        let key_pair_id = u8::read(r)?;
+
+        Some(SpdmGetKeyPairInfoRequestPayload { key_pair_id })
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SpdmKeyPairInfoResponsePayload {
+    pub key_pair_id: u8,
+    pub key_pair_info: SpdmKeyPairInfo,
+    pub assoc_cert_slot_mask: u8,
+}
+
+impl SpdmCodec for SpdmKeyPairInfoResponsePayload {
+    fn spdm_encode(
+        &self,
+        _context: &mut common::SpdmContext,
+        bytes: &mut Writer,
+    ) -> Result<usize, SpdmStatus> {
+        let info = &self.key_pair_info;
+        let mut cnt = 0usize;
+        cnt += 0u8.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param1
+        cnt += 0u8.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param2
+        cnt += info
+            .total_key_pairs
+            .encode(bytes)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        cnt += self
+            .key_pair_id
+            .encode(bytes)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        cnt += info
+            .capabilities
+            .encode(bytes)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        cnt += info
+            .key_usage_capabilities
+            .encode(bytes)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        cnt += info
+            .current_key_usage
+            .encode(bytes)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        cnt += info
+            .asym_algo_capabilities
+            .encode(bytes)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        cnt += info
+            .current_asym_algo
+            .encode(bytes)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        cnt += info
+            .public_key_info
+            .data_size
+            .encode(bytes)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        cnt += self
+            .assoc_cert_slot_mask
+            .encode(bytes)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        for d in info.public_key_info.as_ref() {
+            cnt += d.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        }
+        Ok(cnt)
+    }
+
+    fn spdm_read(
+        _context: &mut common::SpdmContext,
+        r: &mut Reader,
+    ) -> Option<SpdmKeyPairInfoResponsePayload> {
+        u8::read(r)?; // param1
+        u8::read(r)?; // param2
+        let total_key_pairs = u8::read(r)?;
+        let key_pair_id = u8::read(r)?;
+        if key_pair_id == SPDM_DEFAULT_KEY_PAIR_ID || key_pair_id > total_key_pairs {
+            return None;
+        }
+        let capabilities = SpdmKeyPairCapabilities::read(r)?;
+        let key_usage_capabilities = SpdmKeyUsageMask::read(r)?;
+        let current_key_usage = SpdmKeyUsageMask::read(r)?;
+        if !key_usage_capabilities.contains(current_key_usage) {
+            return None;
+        }
+        let asym_algo_capabilities = SpdmKeyPairAsymAlgo::read(r)?;
+        let current_asym_algo = SpdmKeyPairAsymAlgo::read(r)?;
+        if !current_asym_algo.is_no_more_than_one_selected()
+            || !asym_algo_capabilities.contains(current_asym_algo)
+        {
+            return None;
+        }
+        let public_key_info_len = u16::read(r)?;
+        if public_key_info_len as usize > SPDM_MAX_PUBLIC_KEY_SIZE {
+            return None;
+        }
+        let assoc_cert_slot_mask = u8::read(r)?;
+
+        let mut response = SpdmKeyPairInfoResponsePayload {
+            key_pair_id,
+            key_pair_info: SpdmKeyPairInfo {
+                total_key_pairs,
+                capabilities,
+                key_usage_capabilities,
+                current_key_usage,
+                asym_algo_capabilities,
+                current_asym_algo,
+                ..Default::default()
+            },
+            assoc_cert_slot_mask,
+        };
+        response.key_pair_info.public_key_info.data_size = public_key_info_len;
+        for d in response
+            .key_pair_info
+            .public_key_info
+            .data
+            .iter_mut()
+            .take(public_key_info_len as usize)
+        {
+            *d = u8::read(r)?;
+        }
+        Some(response)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SpdmSetKeyPairInfoRequestPayload {
+    pub operation: SpdmSetKeyPairInfoOperation,
+    pub key_pair_id: u8,
+    // Not sent for the erase operation.
+    pub desired_key_usage: SpdmKeyUsageMask,
+    pub desired_asym_algo: SpdmKeyPairAsymAlgo,
+    pub desired_assoc_cert_slot_mask: u8,
+}
+
+impl SpdmCodec for SpdmSetKeyPairInfoRequestPayload {
+    fn spdm_encode(
+        &self,
+        _context: &mut common::SpdmContext,
+        bytes: &mut Writer,
+    ) -> Result<usize, SpdmStatus> {
+        let mut cnt = 0usize;
+        cnt += self
+            .operation
+            .encode(bytes)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param1
+        cnt += 0u8.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param2
+        cnt += 0u8.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // reserved
+        cnt += self
+            .key_pair_id
+            .encode(bytes)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        if self.operation != SpdmSetKeyPairInfoOperation::SpdmSetKeyPairInfoOperationErase {
+            cnt += self
+                .desired_key_usage
+                .encode(bytes)
+                .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+            cnt += self
+                .desired_asym_algo
+                .encode(bytes)
+                .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+            cnt += self
+                .desired_assoc_cert_slot_mask
+                .encode(bytes)
+                .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        }
+        Ok(cnt)
+    }
+
+    fn spdm_read(
+        _context: &mut common::SpdmContext,
+        r: &mut Reader,
+    ) -> Option<SpdmSetKeyPairInfoRequestPayload> {
+        let operation = SpdmSetKeyPairInfoOperation::read(r)?; // param1
+        u8::read(r)?; // param2
+        u8::read(r)?; // reserved
+        let key_pair_id = u8::read(r)?;
+
+        let mut request = SpdmSetKeyPairInfoRequestPayload {
+            operation,
+            key_pair_id,
+            ..Default::default()
+        };
+        match operation {
+            SpdmSetKeyPairInfoOperation::SpdmSetKeyPairInfoOperationErase => {}
+            SpdmSetKeyPairInfoOperation::SpdmSetKeyPairInfoOperationChange
+            | SpdmSetKeyPairInfoOperation::SpdmSetKeyPairInfoOperationGenerate => {
+                request.desired_key_usage = SpdmKeyUsageMask::read(r)?;
+                request.desired_asym_algo = SpdmKeyPairAsymAlgo::read(r)?;
+                if !request.desired_asym_algo.is_no_more_than_one_selected() {
+                    return None;
+                }
+                request.desired_assoc_cert_slot_mask = u8::read(r)?;
+            }
+            SpdmSetKeyPairInfoOperation::Unknown(_) => return None,
+        }
+        Some(request)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SpdmSetKeyPairInfoAckResponsePayload {}
+
+impl SpdmCodec for SpdmSetKeyPairInfoAckResponsePayload {
+    fn spdm_encode(
+        &self,
+        _context: &mut common::SpdmContext,
+        bytes: &mut Writer,
+    ) -> Result<usize, SpdmStatus> {
+        let mut cnt = 0usize;
+        cnt += 0u8.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param1
+        cnt += 0u8.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param2
+        Ok(cnt)
+    }
+
+    fn spdm_read(
+        _context: &mut common::SpdmContext,
+        r: &mut Reader,
+    ) -> Option<SpdmSetKeyPairInfoAckResponsePayload> {
+        u8::read(r)?; // param1
+        u8::read(r)?; // param2
+
+        Some(SpdmSetKeyPairInfoAckResponsePayload {})
+    }
+}
+
+#[cfg(all(test,))]
+#[path = "mod_test.common.inc.rs"]
+mod testlib;
+
+#[cfg(all(test,))]
+#[path = "key_pair_info_test.rs"]
+mod key_pair_info_test;
@@ -0,0 +1,141 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use super::*;
+use crate::common::{SpdmCodec, SpdmConfigInfo, SpdmContext, SpdmProvisionInfo};
+use testlib::{create_spdm_context, DeviceIO, TransportEncap};
+
+#[test]
+fn test_case0_spdm_get_key_pair_info_request_payload() {
+    let u8_slice = &mut [0u8; 3];
+    let mut writer = Writer::init(u8_slice);
+    let value = SpdmGetKeyPairInfoRequestPayload { key_pair_id: 2 };
+
+    create_spdm_context!(context);
+
+    assert!(value.spdm_encode(&mut context, &mut writer).is_ok());
+    let mut reader = Reader::init(u8_slice);
+    let ret = SpdmGetKeyPairInfoRequestPayload::spdm_read(&mut context, &mut reader).unwrap();
+    assert_eq!(ret.key_pair_id, 2);
+    assert_eq!(0, reader.left());
+}
+
+#[test]
+fn test_case0_spdm_key_pair_info_response_payload() {
+    let u8_slice = &mut [0u8; 21 + 4];
+    let mut writer = Writer::init(u8_slice);
+    let mut value = SpdmKeyPairInfoResponsePayload {
+        key_pair_id: 2,
+        key_pair_info: SpdmKeyPairInfo {
+            total_key_pairs: 3,
+            capabilities: SpdmKeyPairCapabilities::CERT_ASSOC_CAP
+                | SpdmKeyPairCapabilities::KEY_USAGE_CAP,
+            key_usage_capabilities: SpdmKeyUsageMask::KEY_EX_USE
+                | SpdmKeyUsageMask::CHALLENGE_USE
+                | SpdmKeyUsageMask::MEASUREMENT_USE,
+            current_key_usage: SpdmKeyUsageMask::CHALLENGE_USE,
+            asym_algo_capabilities: SpdmKeyPairAsymAlgo::ECC256 | SpdmKeyPairAsymAlgo::ECC384,
+            current_asym_algo: SpdmKeyPairAsymAlgo::ECC384,
+            ..Default::default()
+        },
+        assoc_cert_slot_mask: 0b0000_0110,
+    };
+    value.key_pair_info.public_key_info.data_size = 4;
+    value.key_pair_info.public_key_info.data[..4].copy_from_slice(&[0x30, 0x02, 0x05, 0x00]);
+
+    create_spdm_context!(context);
+
+    assert!(value.spdm_encode(&mut context, &mut writer).is_ok());
+    let mut reader = Reader::init(u8_slice);
+    let ret = SpdmKeyPairInfoResponsePayload::spdm_read(&mut context, &mut reader).unwrap();
+    assert_eq!(ret.key_pair_id, 2);
+    assert_eq!(ret.key_pair_info.total_key_pairs, 3);
+    assert_eq!(
+        ret.key_pair_info.capabilities,
+        SpdmKeyPairCapabilities::CERT_ASSOC_CAP | SpdmKeyPairCapabilities::KEY_USAGE_CAP
+    );
+    assert_eq!(
+        ret.key_pair_info.current_key_usage,
+        SpdmKeyUsageMask::CHALLENGE_USE
+    );
+    assert_eq!(
+        ret.key_pair_info.current_asym_algo,
+        SpdmKeyPairAsymAlgo::ECC384
+    );
+    assert_eq!(ret.assoc_cert_slot_mask, 0b0000_0110);
+    assert_eq!(
+        ret.key_pair_info.public_key_info.as_ref(),
+        &[0x30, 0x02, 0x05, 0x00]
+    );
+    assert_eq!(0, reader.left());
+
+    // KeyPairID is 1 based and no larger than TotalKeyPairs.
+    u8_slice[3] = 0;
+    let mut reader = Reader::init(u8_slice);
+    assert!(SpdmKeyPairInfoResponsePayload::spdm_read(&mut context, &mut reader).is_none());
+    u8_slice[3] = 4;
+    let mut reader = Reader::init(u8_slice);
+    assert!(SpdmKeyPairInfoResponsePayload::spdm_read(&mut context, &mut reader).is_none());
+}
+
+#[test]
+fn test_case0_spdm_set_key_pair_info_request_payload() {
+    let u8_slice = &mut [0u8; 11];
+    let mut writer = Writer::init(u8_slice);
+    let value = SpdmSetKeyPairInfoRequestPayload {
+        operation: SpdmSetKeyPairInfoOperation::SpdmSetKeyPairInfoOperationChange,
+        key_pair_id: 1,
+        desired_key_usage: SpdmKeyUsageMask::KEY_EX_USE,
+        desired_asym_algo: SpdmKeyPairAsymAlgo::ECC384,
+        desired_assoc_cert_slot_mask: 0b0000_0001,
+    };
+
+    create_spdm_context!(context);
+
+    assert_eq!(value.spdm_encode(&mut context, &mut writer), Ok(11));
+    let mut reader = Reader::init(u8_slice);
+    let ret = SpdmSetKeyPairInfoRequestPayload::spdm_read(&mut context, &mut reader).unwrap();
+    assert_eq!(
+        ret.operation,
+        SpdmSetKeyPairInfoOperation::SpdmSetKeyPairInfoOperationChange
+    );
+    assert_eq!(ret.key_pair_id, 1);
+    assert_eq!(ret.desired_key_usage, SpdmKeyUsageMask::KEY_EX_USE);
+    assert_eq!(ret.desired_asym_algo, SpdmKeyPairAsymAlgo::ECC384);
+    assert_eq!(ret.desired_assoc_cert_slot_mask, 0b0000_0001);
+    assert_eq!(0, reader.left());
+
+    // Only one DesiredAsymAlgo can be selected.
+    u8_slice[6] = (SpdmKeyPairAsymAlgo::ECC256 | SpdmKeyPairAsymAlgo::ECC384).bits() as u8;
+    let mut reader = Reader::init(u8_slice);
+    assert!(SpdmSetKeyPairInfoRequestPayload::spdm_read(&mut context, &mut reader).is_none());
+}
+
+#[test]
+fn test_case1_spdm_set_key_pair_info_request_payload() {
+    let u8_slice = &mut [0u8; 4];
+    let mut writer = Writer::init(u8_slice);
+    let value = SpdmSetKeyPairInfoRequestPayload {
+        operation: SpdmSetKeyPairInfoOperation::SpdmSetKeyPairInfoOperationErase,
+        key_pair_id: 3,
+        ..Default::default()
+    };
+
+    create_spdm_context!(context);
+
+    // The erase operation ends after KeyPairID.
+    assert_eq!(value.spdm_encode(&mut context, &mut writer), Ok(4));
+    let mut reader = Reader::init(u8_slice);
+    let ret = SpdmSetKeyPairInfoRequestPayload::spdm_read(&mut context, &mut reader).unwrap();
+    assert_eq!(
+        ret.operation,
+        SpdmSetKeyPairInfoOperation::SpdmSetKeyPairInfoOperationErase
+    );
+    assert_eq!(ret.key_pair_id, 3);
+    assert_eq!(0, reader.left());
+
+    u8_slice[0] = 3;
+    let mut reader = Reader::init(u8_slice);
+    assert!(SpdmSetKeyPairInfoRequestPayload::spdm_read(&mut context, &mut reader).is_none());
+}
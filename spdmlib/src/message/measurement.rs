@@ -45,6 +45,18 @@ enum_builder! {
         SpdmMeasurementRequestAll => 0xFF
     }
 }
+impl SpdmMeasurementOperation {
+    /// Operation for Param2 of GET_MEASUREMENTS. Indices 1..=0xFE request a
+    /// single measurement block and are carried as `Unknown(index)`.
+    pub fn from_index(index: u8) -> SpdmMeasurementOperation {
+        match index {
+            0x0 => SpdmMeasurementOperation::SpdmMeasurementQueryTotalNumber,
+            0xFF => SpdmMeasurementOperation::SpdmMeasurementRequestAll,
+            _ => SpdmMeasurementOperation::Unknown(index),
+        }
+    }
+}
+
 impl Default for SpdmMeasurementOperation {
     fn default() -> SpdmMeasurementOperation {
         SpdmMeasurementOperation::SpdmMeasurementQueryTotalNumber
@@ -149,14 +161,10 @@ impl SpdmCodec for SpdmMeasurementsResponsePayload {
         //When Param2 in the requested measurement operation is 0 , this
         //parameter shall return the total number of measurement indices on
         //the device. Otherwise, this field is reserved.
-        if self.number_of_measurement == 1 {
-            cnt += 0_u8.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param1
-        } else {
-            cnt += self
-                .number_of_measurement
-                .encode(bytes)
-                .map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param1
-        }
+        cnt += self
+            .number_of_measurement
+            .encode(bytes)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param1
         if context.negotiate_info.spdm_version_sel.get_u8() >= SpdmVersion::SpdmVersion12.get_u8()
             && context.runtime_info.need_measurement_signature
         {
@@ -322,7 +330,7 @@ mod tests {
             + SPDM_NONCE_SIZE
             + 2
             + MAX_SPDM_OPAQUE_SIZE
-            + SPDM_MAX_ASYM_KEY_SIZE];
+            + RSASSA_4096_KEY_SIZE];
         let mut writer = Writer::init(u8_slice);
         let mut spdm_measurement_block_structure = SpdmMeasurementBlockStructure {
             index: 1u8,
@@ -360,7 +368,7 @@ mod tests {
                 data: [100u8; MAX_SPDM_OPAQUE_SIZE],
             },
             signature: SpdmSignatureStruct {
-                data_size: SPDM_MAX_ASYM_KEY_SIZE as u16,
+                data_size: RSASSA_4096_KEY_SIZE as u16,
                 data: [100u8; SPDM_MAX_ASYM_KEY_SIZE],
             },
         };
@@ -379,7 +387,7 @@ mod tests {
                 + SPDM_NONCE_SIZE
                 + 2
                 + MAX_SPDM_OPAQUE_SIZE
-                + SPDM_MAX_ASYM_KEY_SIZE,
+                + RSASSA_4096_KEY_SIZE,
             reader.left()
         );
         let mut measurements_response =
@@ -62,3 +62,20 @@ fn test_measurement_response() {
     let ret = SpdmMeasurementsResponsePayload::spdm_read(context, reader);
     assert!(ret.is_none())
 }
+
+#[test]
+fn test_measurement_operation_from_index() {
+    assert_eq!(
+        SpdmMeasurementOperation::from_index(0),
+        SpdmMeasurementOperation::SpdmMeasurementQueryTotalNumber
+    );
+    assert_eq!(
+        SpdmMeasurementOperation::from_index(0xFF),
+        SpdmMeasurementOperation::SpdmMeasurementRequestAll
+    );
+    assert_eq!(
+        SpdmMeasurementOperation::from_index(5),
+        SpdmMeasurementOperation::Unknown(5)
+    );
+    assert_eq!(SpdmMeasurementOperation::from_index(0xFE).get_u8(), 0xFE);
+}
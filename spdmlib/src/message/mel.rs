@@ -0,0 +1,129 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use crate::common::spdm_codec::SpdmCodec;
+use crate::error::SPDM_STATUS_BUFFER_FULL;
+use crate::{common, error::SpdmStatus};
+use codec::{Codec, Reader, Writer};
+
+pub(crate) const MAX_SPDM_MEL_PORTION_LEN: usize = 512;
+
+#[derive(Debug, Clone, Default)]
+pub struct SpdmGetMeasurementExtensionLogRequestPayload {
+    pub offset: u32,
+    pub length: u32,
+}
+
+impl SpdmCodec for SpdmGetMeasurementExtensionLogRequestPayload {
+    fn spdm_encode(
+        &self,
+        _context: &mut common::SpdmContext,
+        bytes: &mut Writer,
+    ) -> Result<usize, SpdmStatus> {
+        let mut cnt = 0usize;
+        cnt += 0u8.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param1
+        cnt += 0u8.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param2
+        cnt += self
+            .offset
+            .encode(bytes)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        cnt += self
+            .length
+            .encode(bytes)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        Ok(cnt)
+    }
+
+    fn spdm_read(
+        _context: &mut common::SpdmContext,
+        r: &mut Reader,
+    ) -> Option<SpdmGetMeasurementExtensionLogRequestPayload> {
+        u8::read(r)?; // param1
+        u8::read(r)?; // param2
+        let offset = u32::read(r)?;
+        let length = u32::read(r)?;
+
+        Some(SpdmGetMeasurementExtensionLogRequestPayload { offset, length })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SpdmMeasurementExtensionLogResponsePayload {
+    pub portion_length: u32,
+    pub remainder_length: u32,
+    pub measurement_extension_log: [u8; MAX_SPDM_MEL_PORTION_LEN],
+}
+impl Default for SpdmMeasurementExtensionLogResponsePayload {
+    fn default() -> SpdmMeasurementExtensionLogResponsePayload {
+        SpdmMeasurementExtensionLogResponsePayload {
+            portion_length: 0,
+            remainder_length: 0,
+            measurement_extension_log: [0u8; MAX_SPDM_MEL_PORTION_LEN],
+        }
+    }
+}
+
+impl SpdmCodec for SpdmMeasurementExtensionLogResponsePayload {
+    fn spdm_encode(
+        &self,
+        _context: &mut common::SpdmContext,
+        bytes: &mut Writer,
+    ) -> Result<usize, SpdmStatus> {
+        let mut cnt = 0usize;
+        cnt += 0u8.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param1
+        cnt += 0u8.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param2
+        cnt += self
+            .portion_length
+            .encode(bytes)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        cnt += self
+            .remainder_length
+            .encode(bytes)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+
+        for d in self
+            .measurement_extension_log
+            .iter()
+            .take(self.portion_length as usize)
+        {
+            cnt += d.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        }
+        Ok(cnt)
+    }
+
+    fn spdm_read(
+        _context: &mut common::SpdmContext,
+        r: &mut Reader,
+    ) -> Option<SpdmMeasurementExtensionLogResponsePayload> {
+        u8::read(r)?; // param1
+        u8::read(r)?; // param2
+        let portion_length = u32::read(r)?;
+        let remainder_length = u32::read(r)?;
+        if portion_length as usize > MAX_SPDM_MEL_PORTION_LEN {
+            return None;
+        }
+        let mut response = SpdmMeasurementExtensionLogResponsePayload {
+            portion_length,
+            remainder_length,
+            ..Default::default()
+        };
+
+        for data in response
+            .measurement_extension_log
+            .iter_mut()
+            .take(portion_length as usize)
+        {
+            *data = u8::read(r)?;
+        }
+        Some(response)
+    }
+}
+
+#[cfg(all(test,))]
+#[path = "mod_test.common.inc.rs"]
+mod testlib;
+
+#[cfg(all(test,))]
+#[path = "mel_test.rs"]
+mod mel_test;
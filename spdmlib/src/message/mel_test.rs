@@ -0,0 +1,58 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use super::*;
+use crate::common::{SpdmCodec, SpdmConfigInfo, SpdmContext, SpdmProvisionInfo};
+use testlib::{create_spdm_context, DeviceIO, TransportEncap};
+
+#[test]
+fn test_case0_spdm_get_measurement_extension_log_request_payload() {
+    let u8_slice = &mut [0u8; 10];
+    let mut writer = Writer::init(u8_slice);
+    let value = SpdmGetMeasurementExtensionLogRequestPayload {
+        offset: 0x200,
+        length: MAX_SPDM_MEL_PORTION_LEN as u32,
+    };
+
+    create_spdm_context!(context);
+
+    assert!(value.spdm_encode(&mut context, &mut writer).is_ok());
+    let mut reader = Reader::init(u8_slice);
+    assert_eq!(10, reader.left());
+    let ret =
+        SpdmGetMeasurementExtensionLogRequestPayload::spdm_read(&mut context, &mut reader).unwrap();
+    assert_eq!(ret.offset, 0x200);
+    assert_eq!(ret.length, MAX_SPDM_MEL_PORTION_LEN as u32);
+    assert_eq!(0, reader.left());
+}
+
+#[test]
+fn test_case0_spdm_measurement_extension_log_response_payload() {
+    let u8_slice = &mut [0u8; 10 + MAX_SPDM_MEL_PORTION_LEN];
+    let mut writer = Writer::init(u8_slice);
+    let mut value = SpdmMeasurementExtensionLogResponsePayload {
+        portion_length: MAX_SPDM_MEL_PORTION_LEN as u32,
+        remainder_length: 100,
+        ..Default::default()
+    };
+    value.measurement_extension_log = [0x5au8; MAX_SPDM_MEL_PORTION_LEN];
+
+    create_spdm_context!(context);
+
+    assert!(value.spdm_encode(&mut context, &mut writer).is_ok());
+    let mut reader = Reader::init(u8_slice);
+    let ret =
+        SpdmMeasurementExtensionLogResponsePayload::spdm_read(&mut context, &mut reader).unwrap();
+    assert_eq!(ret.portion_length, MAX_SPDM_MEL_PORTION_LEN as u32);
+    assert_eq!(ret.remainder_length, 100);
+    assert!(ret.measurement_extension_log.iter().all(|d| *d == 0x5a));
+    assert_eq!(0, reader.left());
+
+    // A portion larger than the supported portion length is rejected.
+    u8_slice[2..6].copy_from_slice(&(MAX_SPDM_MEL_PORTION_LEN as u32 + 1).to_le_bytes());
+    let mut reader = Reader::init(u8_slice);
+    assert!(
+        SpdmMeasurementExtensionLogResponsePayload::spdm_read(&mut context, &mut reader).is_none()
+    );
+}
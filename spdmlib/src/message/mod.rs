@@ -29,23 +29,39 @@ pub mod key_update;
 pub mod psk_exchange;
 pub mod psk_finish;
 pub mod respond_if_ready;
+// SPDM 1.2
+pub mod chunk;
+pub mod csr;
+pub mod set_certificate;
+// SPDM 1.3
+pub mod endpoint_info;
+pub mod event;
+pub mod key_pair_info;
+pub mod mel;
 
 pub use algorithm::*;
 pub use capability::*;
 pub use certificate::*;
 pub use challenge::*;
+pub use chunk::*;
+pub use csr::*;
 pub use digest::*;
 #[cfg(feature = "mut-auth")]
 pub use encapsulated::*;
 pub use end_session::*;
+pub use endpoint_info::*;
 pub use error::*;
+pub use event::*;
 pub use finish::*;
 pub use heartbeat::*;
 pub use key_exchange::*;
+pub use key_pair_info::*;
 pub use key_update::*;
 pub use measurement::*;
+pub use mel::*;
 pub use psk_exchange::*;
 pub use psk_finish::*;
+pub use set_certificate::*;
 pub use version::*;
 // Add new SPDM command here.
 pub use respond_if_ready::*;
@@ -75,6 +91,19 @@ enum_builder! {
         SpdmResponseEncapsulatedRequest => 0x6A,
         SpdmResponseEncapsulatedResponseAck => 0x6B,
         SpdmResponseEndSessionAck => 0x6C,
+        // 1.2 response
+        SpdmResponseChunkSendAck => 0x05,
+        SpdmResponseChunkResponse => 0x06,
+        SpdmResponseCsr => 0x6D,
+        SpdmResponseSetCertificateRsp => 0x6E,
+        // 1.3 response
+        SpdmResponseEndpointInfo => 0x07,
+        SpdmResponseSupportedEventTypes => 0x62,
+        SpdmResponseSubscribeEventTypesAck => 0x70,
+        SpdmResponseEventAck => 0x71,
+        SpdmResponseMeasurementExtensionLog => 0x6F,
+        SpdmResponseKeyPairInfo => 0x7C,
+        SpdmResponseSetKeyPairInfoAck => 0x7D,
 
         // 1.0 rerquest
         SpdmRequestGetDigests => 0x81,
@@ -95,7 +124,20 @@ enum_builder! {
         SpdmRequestKeyUpdate => 0xE9,
         SpdmRequestGetEncapsulatedRequest => 0xEA,
         SpdmRequestDeliverEncapsulatedResponse => 0xEB,
-        SpdmRequestEndSession => 0xEC
+        SpdmRequestEndSession => 0xEC,
+        // 1.2 request
+        SpdmRequestChunkSend => 0x85,
+        SpdmRequestChunkGet => 0x86,
+        SpdmRequestGetCsr => 0xED,
+        SpdmRequestSetCertificate => 0xEE,
+        // 1.3 request
+        SpdmRequestGetEndpointInfo => 0x87,
+        SpdmRequestGetSupportedEventTypes => 0xE2,
+        SpdmRequestSubscribeEventTypes => 0xF0,
+        SpdmRequestSendEvent => 0xF1,
+        SpdmRequestGetMeasurementExtensionLog => 0xEF,
+        SpdmRequestGetKeyPairInfo => 0xFC,
+        SpdmRequestSetKeyPairInfo => 0xFD
     }
 }
 impl Default for SpdmRequestResponseCode {
@@ -235,6 +277,41 @@ pub enum SpdmMessagePayload {
     SpdmEndSessionRequest(SpdmEndSessionRequestPayload),
     SpdmEndSessionResponse(SpdmEndSessionResponsePayload),
 
+    SpdmChunkSendRequest(SpdmChunkSendRequestPayload),
+    SpdmChunkSendAckResponse(SpdmChunkSendAckResponsePayload),
+
+    SpdmChunkGetRequest(SpdmChunkGetRequestPayload),
+    SpdmChunkResponse(SpdmChunkResponsePayload),
+
+    SpdmGetCsrRequest(SpdmGetCsrRequestPayload),
+    SpdmCsrResponse(SpdmCsrResponsePayload),
+
+    SpdmSetCertificateRequest(SpdmSetCertificateRequestPayload),
+    SpdmSetCertificateResponse(SpdmSetCertificateResponsePayload),
+
+    SpdmRespondIfReadyRequest(SpdmRespondIfReadyRequestPayload),
+
+    SpdmGetEndpointInfoRequest(SpdmGetEndpointInfoRequestPayload),
+    SpdmEndpointInfoResponse(SpdmEndpointInfoResponsePayload),
+
+    SpdmGetSupportedEventTypesRequest(SpdmGetSupportedEventTypesRequestPayload),
+    SpdmSupportedEventTypesResponse(SpdmSupportedEventTypesResponsePayload),
+
+    SpdmSubscribeEventTypesRequest(SpdmSubscribeEventTypesRequestPayload),
+    SpdmSubscribeEventTypesAckResponse(SpdmSubscribeEventTypesAckResponsePayload),
+
+    SpdmSendEventRequest(SpdmSendEventRequestPayload),
+    SpdmEventAckResponse(SpdmEventAckResponsePayload),
+
+    SpdmGetMeasurementExtensionLogRequest(SpdmGetMeasurementExtensionLogRequestPayload),
+    SpdmMeasurementExtensionLogResponse(SpdmMeasurementExtensionLogResponsePayload),
+
+    SpdmGetKeyPairInfoRequest(SpdmGetKeyPairInfoRequestPayload),
+    SpdmKeyPairInfoResponse(SpdmKeyPairInfoResponsePayload),
+
+    SpdmSetKeyPairInfoRequest(SpdmSetKeyPairInfoRequestPayload),
+    SpdmSetKeyPairInfoAckResponse(SpdmSetKeyPairInfoAckResponsePayload),
+
     // Add new SPDM command here.
     SpdmErrorResponse(SpdmErrorResponsePayload),
     SpdmVendorDefinedRequest(SpdmVendorDefinedRequestPayload),
@@ -403,6 +480,128 @@ impl SpdmMessage {
                 ))
             }
 
+            SpdmRequestResponseCode::SpdmResponseChunkSendAck => {
+                Some(SpdmMessagePayload::SpdmChunkSendAckResponse(
+                    SpdmChunkSendAckResponsePayload::spdm_read(context, r)?,
+                ))
+            }
+            SpdmRequestResponseCode::SpdmRequestChunkSend => {
+                Some(SpdmMessagePayload::SpdmChunkSendRequest(
+                    SpdmChunkSendRequestPayload::spdm_read(context, r)?,
+                ))
+            }
+
+            SpdmRequestResponseCode::SpdmResponseChunkResponse => {
+                Some(SpdmMessagePayload::SpdmChunkResponse(
+                    SpdmChunkResponsePayload::spdm_read(context, r)?,
+                ))
+            }
+            SpdmRequestResponseCode::SpdmRequestChunkGet => {
+                Some(SpdmMessagePayload::SpdmChunkGetRequest(
+                    SpdmChunkGetRequestPayload::spdm_read(context, r)?,
+                ))
+            }
+
+            SpdmRequestResponseCode::SpdmResponseCsr => Some(SpdmMessagePayload::SpdmCsrResponse(
+                SpdmCsrResponsePayload::spdm_read(context, r)?,
+            )),
+            SpdmRequestResponseCode::SpdmRequestGetCsr => {
+                Some(SpdmMessagePayload::SpdmGetCsrRequest(
+                    SpdmGetCsrRequestPayload::spdm_read(context, r)?,
+                ))
+            }
+
+            SpdmRequestResponseCode::SpdmResponseSetCertificateRsp => {
+                Some(SpdmMessagePayload::SpdmSetCertificateResponse(
+                    SpdmSetCertificateResponsePayload::spdm_read(context, r)?,
+                ))
+            }
+            SpdmRequestResponseCode::SpdmRequestSetCertificate => {
+                Some(SpdmMessagePayload::SpdmSetCertificateRequest(
+                    SpdmSetCertificateRequestPayload::spdm_read(context, r)?,
+                ))
+            }
+
+            SpdmRequestResponseCode::SpdmRequestResponseIfReady => {
+                Some(SpdmMessagePayload::SpdmRespondIfReadyRequest(
+                    SpdmRespondIfReadyRequestPayload::spdm_read(context, r)?,
+                ))
+            }
+
+            SpdmRequestResponseCode::SpdmRequestGetSupportedEventTypes => {
+                Some(SpdmMessagePayload::SpdmGetSupportedEventTypesRequest(
+                    SpdmGetSupportedEventTypesRequestPayload::spdm_read(context, r)?,
+                ))
+            }
+            SpdmRequestResponseCode::SpdmResponseSupportedEventTypes => {
+                Some(SpdmMessagePayload::SpdmSupportedEventTypesResponse(
+                    SpdmSupportedEventTypesResponsePayload::spdm_read(context, r)?,
+                ))
+            }
+            SpdmRequestResponseCode::SpdmRequestSubscribeEventTypes => {
+                Some(SpdmMessagePayload::SpdmSubscribeEventTypesRequest(
+                    SpdmSubscribeEventTypesRequestPayload::spdm_read(context, r)?,
+                ))
+            }
+            SpdmRequestResponseCode::SpdmResponseSubscribeEventTypesAck => {
+                Some(SpdmMessagePayload::SpdmSubscribeEventTypesAckResponse(
+                    SpdmSubscribeEventTypesAckResponsePayload::spdm_read(context, r)?,
+                ))
+            }
+            SpdmRequestResponseCode::SpdmRequestSendEvent => {
+                Some(SpdmMessagePayload::SpdmSendEventRequest(
+                    SpdmSendEventRequestPayload::spdm_read(context, r)?,
+                ))
+            }
+            SpdmRequestResponseCode::SpdmResponseEventAck => {
+                Some(SpdmMessagePayload::SpdmEventAckResponse(
+                    SpdmEventAckResponsePayload::spdm_read(context, r)?,
+                ))
+            }
+
+            SpdmRequestResponseCode::SpdmRequestGetEndpointInfo => {
+                Some(SpdmMessagePayload::SpdmGetEndpointInfoRequest(
+                    SpdmGetEndpointInfoRequestPayload::spdm_read(context, r)?,
+                ))
+            }
+            SpdmRequestResponseCode::SpdmResponseEndpointInfo => {
+                Some(SpdmMessagePayload::SpdmEndpointInfoResponse(
+                    SpdmEndpointInfoResponsePayload::spdm_read(context, r)?,
+                ))
+            }
+
+            SpdmRequestResponseCode::SpdmRequestGetMeasurementExtensionLog => {
+                Some(SpdmMessagePayload::SpdmGetMeasurementExtensionLogRequest(
+                    SpdmGetMeasurementExtensionLogRequestPayload::spdm_read(context, r)?,
+                ))
+            }
+            SpdmRequestResponseCode::SpdmResponseMeasurementExtensionLog => {
+                Some(SpdmMessagePayload::SpdmMeasurementExtensionLogResponse(
+                    SpdmMeasurementExtensionLogResponsePayload::spdm_read(context, r)?,
+                ))
+            }
+
+            SpdmRequestResponseCode::SpdmRequestGetKeyPairInfo => {
+                Some(SpdmMessagePayload::SpdmGetKeyPairInfoRequest(
+                    SpdmGetKeyPairInfoRequestPayload::spdm_read(context, r)?,
+                ))
+            }
+            SpdmRequestResponseCode::SpdmResponseKeyPairInfo => {
+                Some(SpdmMessagePayload::SpdmKeyPairInfoResponse(
+                    SpdmKeyPairInfoResponsePayload::spdm_read(context, r)?,
+                ))
+            }
+            SpdmRequestResponseCode::SpdmRequestSetKeyPairInfo => {
+                Some(SpdmMessagePayload::SpdmSetKeyPairInfoRequest(
+                    SpdmSetKeyPairInfoRequestPayload::spdm_read(context, r)?,
+                ))
+            }
+            SpdmRequestResponseCode::SpdmResponseSetKeyPairInfoAck => {
+                Some(SpdmMessagePayload::SpdmSetKeyPairInfoAckResponse(
+                    SpdmSetKeyPairInfoAckResponsePayload::spdm_read(context, r)?,
+                ))
+            }
+
             // Add new SPDM command here.
             SpdmRequestResponseCode::SpdmResponseError => {
                 Some(SpdmMessagePayload::SpdmErrorResponse(
@@ -547,6 +746,84 @@ impl SpdmCodec for SpdmMessage {
                 cnt += payload.spdm_encode(context, bytes)?;
             }
 
+            SpdmMessagePayload::SpdmChunkSendRequest(payload) => {
+                cnt += payload.spdm_encode(context, bytes)?;
+            }
+            SpdmMessagePayload::SpdmChunkSendAckResponse(payload) => {
+                cnt += payload.spdm_encode(context, bytes)?;
+            }
+
+            SpdmMessagePayload::SpdmChunkGetRequest(payload) => {
+                cnt += payload.spdm_encode(context, bytes)?;
+            }
+            SpdmMessagePayload::SpdmChunkResponse(payload) => {
+                cnt += payload.spdm_encode(context, bytes)?;
+            }
+
+            SpdmMessagePayload::SpdmGetCsrRequest(payload) => {
+                cnt += payload.spdm_encode(context, bytes)?;
+            }
+            SpdmMessagePayload::SpdmCsrResponse(payload) => {
+                cnt += payload.spdm_encode(context, bytes)?;
+            }
+
+            SpdmMessagePayload::SpdmSetCertificateRequest(payload) => {
+                cnt += payload.spdm_encode(context, bytes)?;
+            }
+            SpdmMessagePayload::SpdmSetCertificateResponse(payload) => {
+                cnt += payload.spdm_encode(context, bytes)?;
+            }
+
+            SpdmMessagePayload::SpdmRespondIfReadyRequest(payload) => {
+                cnt += payload.spdm_encode(context, bytes)?;
+            }
+
+            SpdmMessagePayload::SpdmGetSupportedEventTypesRequest(payload) => {
+                cnt += payload.spdm_encode(context, bytes)?;
+            }
+            SpdmMessagePayload::SpdmSupportedEventTypesResponse(payload) => {
+                cnt += payload.spdm_encode(context, bytes)?;
+            }
+            SpdmMessagePayload::SpdmSubscribeEventTypesRequest(payload) => {
+                cnt += payload.spdm_encode(context, bytes)?;
+            }
+            SpdmMessagePayload::SpdmSubscribeEventTypesAckResponse(payload) => {
+                cnt += payload.spdm_encode(context, bytes)?;
+            }
+            SpdmMessagePayload::SpdmSendEventRequest(payload) => {
+                cnt += payload.spdm_encode(context, bytes)?;
+            }
+            SpdmMessagePayload::SpdmEventAckResponse(payload) => {
+                cnt += payload.spdm_encode(context, bytes)?;
+            }
+
+            SpdmMessagePayload::SpdmGetEndpointInfoRequest(payload) => {
+                cnt += payload.spdm_encode(context, bytes)?;
+            }
+            SpdmMessagePayload::SpdmEndpointInfoResponse(payload) => {
+                cnt += payload.spdm_encode(context, bytes)?;
+            }
+
+            SpdmMessagePayload::SpdmGetMeasurementExtensionLogRequest(payload) => {
+                cnt += payload.spdm_encode(context, bytes)?;
+            }
+            SpdmMessagePayload::SpdmMeasurementExtensionLogResponse(payload) => {
+                cnt += payload.spdm_encode(context, bytes)?;
+            }
+
+            SpdmMessagePayload::SpdmGetKeyPairInfoRequest(payload) => {
+                cnt += payload.spdm_encode(context, bytes)?;
+            }
+            SpdmMessagePayload::SpdmKeyPairInfoResponse(payload) => {
+                cnt += payload.spdm_encode(context, bytes)?;
+            }
+            SpdmMessagePayload::SpdmSetKeyPairInfoRequest(payload) => {
+                cnt += payload.spdm_encode(context, bytes)?;
+            }
+            SpdmMessagePayload::SpdmSetKeyPairInfoAckResponse(payload) => {
+                cnt += payload.spdm_encode(context, bytes)?;
+            }
+
             // Add new SPDM command here.
             SpdmMessagePayload::SpdmErrorResponse(payload) => {
                 cnt += payload.spdm_encode(context, bytes)?;
@@ -712,6 +989,12 @@ mod tests {
                     other_params_support: SpdmOpaqueSupport::empty(),
                     base_asym_algo: SpdmBaseAsymAlgo::TPM_ALG_RSASSA_2048,
                     base_hash_algo: SpdmBaseHashAlgo::TPM_ALG_SHA_256,
+                    pqc_asym_algo: SpdmPqcAsymAlgo::empty(),
+                    mel_specification: SpdmMelSpecification::empty(),
+                    ext_asym_count: 0,
+                    ext_asym: [SpdmExtAlgStruct::default(); SPDM_MAX_EXT_ALG_COUNT],
+                    ext_hash_count: 0,
+                    ext_hash: [SpdmExtAlgStruct::default(); SPDM_MAX_EXT_ALG_COUNT],
                     alg_struct_count: 4,
                     alg_struct: [
                         SpdmAlgStruct {
@@ -734,6 +1017,8 @@ mod tests {
                                 SpdmKeyScheduleAlgo::SPDM_KEY_SCHEDULE,
                             ),
                         },
+                        #[cfg(feature = "pqc")]
+                        SpdmAlgStruct::default(),
                     ],
                 },
             ),
@@ -798,6 +1083,10 @@ mod tests {
                 measurement_hash_algo: SpdmMeasurementHashAlgo::RAW_BIT_STREAM,
                 base_asym_sel: SpdmBaseAsymAlgo::TPM_ALG_RSASSA_2048,
                 base_hash_sel: SpdmBaseHashAlgo::TPM_ALG_SHA_256,
+                pqc_asym_sel: SpdmPqcAsymAlgo::empty(),
+                mel_specification_sel: SpdmMelSpecification::empty(),
+                ext_asym_sel: None,
+                ext_hash_sel: None,
                 alg_struct_count: 4,
                 alg_struct: [
                     SpdmAlgStruct {
@@ -820,6 +1109,8 @@ mod tests {
                             SpdmKeyScheduleAlgo::SPDM_KEY_SCHEDULE,
                         ),
                     },
+                    #[cfg(feature = "pqc")]
+                    SpdmAlgStruct::default(),
                 ],
             }),
         };
@@ -969,7 +1260,7 @@ mod tests {
                         data: [0xAAu8; MAX_SPDM_OPAQUE_SIZE],
                     },
                     signature: SpdmSignatureStruct {
-                        data_size: SPDM_MAX_ASYM_KEY_SIZE as u16,
+                        data_size: RSASSA_4096_KEY_SIZE as u16,
                         data: [0x55u8; SPDM_MAX_ASYM_KEY_SIZE],
                     },
                 },
@@ -1103,7 +1394,7 @@ mod tests {
                         data: [100u8; MAX_SPDM_OPAQUE_SIZE],
                     },
                     signature: SpdmSignatureStruct {
-                        data_size: SPDM_MAX_ASYM_KEY_SIZE as u16,
+                        data_size: RSASSA_4096_KEY_SIZE as u16,
                         data: [100u8; SPDM_MAX_ASYM_KEY_SIZE],
                     },
                 },
@@ -1160,7 +1451,7 @@ mod tests {
                     data: [100u8; SPDM_RANDOM_SIZE],
                 },
                 exchange: SpdmDheExchangeStruct {
-                    data_size: SPDM_MAX_DHE_KEY_SIZE as u16,
+                    data_size: ECDSA_ECC_NIST_P384_KEY_SIZE as u16,
                     data: [100u8; SPDM_MAX_DHE_KEY_SIZE],
                 },
                 opaque: SpdmOpaqueStruct {
@@ -1210,7 +1501,7 @@ mod tests {
                 finish_request_attributes: SpdmFinishRequestAttributes::SIGNATURE_INCLUDED,
                 req_slot_id: 100,
                 signature: SpdmSignatureStruct {
-                    data_size: SPDM_MAX_ASYM_KEY_SIZE as u16,
+                    data_size: RSASSA_4096_KEY_SIZE as u16,
                     data: [0xa5u8; SPDM_MAX_ASYM_KEY_SIZE],
                 },
                 verify_data: SpdmDigestStruct {
@@ -1656,6 +1947,7 @@ mod tests {
                     },
                     SPDM_MAX_SLOT_NUMBER,
                 ),
+                ..Default::default()
             }),
         };
         create_spdm_context!(context);
@@ -1683,4 +1975,57 @@ mod tests {
         create_spdm_context!(context);
         new_spdm_message(value, context);
     }
+    #[test]
+    fn test_case29_spdm_message() {
+        let value = SpdmMessage {
+            header: SpdmMessageHeader {
+                version: SpdmVersion::SpdmVersion10,
+                request_response_code: SpdmRequestResponseCode::SpdmRequestKeyExchange,
+            },
+            payload: SpdmMessagePayload::SpdmKeyExchangeRequest(SpdmKeyExchangeRequestPayload {
+                measurement_summary_hash_type:
+                    SpdmMeasurementSummaryHashType::SpdmMeasurementSummaryHashTypeNone,
+                slot_id: 100u8,
+                req_session_id: 100u16,
+                session_policy: 1,
+                random: SpdmRandomStruct {
+                    data: [100u8; SPDM_RANDOM_SIZE],
+                },
+                exchange: SpdmDheExchangeStruct {
+                    data_size: FFDHE_4096_KEY_SIZE as u16,
+                    data: [100u8; SPDM_MAX_DHE_KEY_SIZE],
+                },
+                opaque: SpdmOpaqueStruct {
+                    data_size: MAX_SPDM_OPAQUE_SIZE as u16,
+                    data: [100u8; MAX_SPDM_OPAQUE_SIZE],
+                },
+            }),
+        };
+        create_spdm_context!(context);
+        context.negotiate_info.dhe_sel = SpdmDheAlgo::FFDHE_4096;
+        context.negotiate_info.base_hash_sel = SpdmBaseHashAlgo::TPM_ALG_SHA_512;
+        let spdm_message = new_spdm_message(value, context);
+        assert_eq!(
+            spdm_message.header.request_response_code,
+            SpdmRequestResponseCode::SpdmRequestKeyExchange
+        );
+        if let SpdmMessagePayload::SpdmKeyExchangeRequest(payload) = &spdm_message.payload {
+            assert_eq!(
+                payload.measurement_summary_hash_type,
+                SpdmMeasurementSummaryHashType::SpdmMeasurementSummaryHashTypeNone
+            );
+            assert_eq!(payload.slot_id, 100);
+            for i in 0..SPDM_RANDOM_SIZE {
+                assert_eq!(payload.random.data[i], 100);
+            }
+            assert_eq!(payload.exchange.data_size, FFDHE_4096_KEY_SIZE as u16);
+            for i in 0..FFDHE_4096_KEY_SIZE {
+                assert_eq!(payload.exchange.data[i], 100);
+            }
+            assert_eq!(payload.opaque.data_size, MAX_SPDM_OPAQUE_SIZE as u16);
+            for i in 0..MAX_SPDM_OPAQUE_SIZE {
+                assert_eq!(payload.opaque.data[i], 100);
+            }
+        }
+    }
 }
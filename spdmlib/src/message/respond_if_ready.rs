@@ -8,8 +8,14 @@ use crate::config;
 use crate::error::{SpdmStatus, SPDM_STATUS_BUFFER_FULL};
 use codec::{Codec, Reader, Writer};
 
+use super::SpdmRequestResponseCode;
+
 #[derive(Debug, Clone, Default)]
-pub struct SpdmRespondIfReadyRequestPayload {}
+pub struct SpdmRespondIfReadyRequestPayload {
+    // RequestCode and Token of the ResponseNotReady error.
+    pub request_code: SpdmRequestResponseCode,
+    pub token: u8,
+}
 
 impl SpdmCodec for SpdmRespondIfReadyRequestPayload {
     fn spdm_encode(
@@ -17,19 +23,29 @@ impl SpdmCodec for SpdmRespondIfReadyRequestPayload {
         _context: &mut common::SpdmContext,
         bytes: &mut Writer,
     ) -> Result<usize, SpdmStatus> {
-        0u8.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param1
-        0u8.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param2
-        Ok(2)
+        let mut cnt = 0usize;
+        cnt += self
+            .request_code
+            .encode(bytes)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param1
+        cnt += self
+            .token
+            .encode(bytes)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param2
+        Ok(cnt)
     }
 
     fn spdm_read(
         _context: &mut common::SpdmContext,
         r: &mut Reader,
     ) -> Option<SpdmRespondIfReadyRequestPayload> {
-        u8::read(r)?; // param1
-        u8::read(r)?; // param2
+        let request_code = SpdmRequestResponseCode::read(r)?; // param1
+        let token = u8::read(r)?; // param2
 
-        Some(SpdmRespondIfReadyRequestPayload {})
+        Some(SpdmRespondIfReadyRequestPayload {
+            request_code,
+            token,
+        })
     }
 }
 
@@ -63,3 +79,11 @@ pub struct ReceivedMessage {
     pub receive_buffer: [u8; config::MAX_SPDM_MSG_SIZE],
     pub used: usize,
 }
+
+#[cfg(all(test,))]
+#[path = "mod_test.common.inc.rs"]
+mod testlib;
+
+#[cfg(all(test,))]
+#[path = "respond_if_ready_test.rs"]
+mod respond_if_ready_test;
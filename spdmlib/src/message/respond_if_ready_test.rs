@@ -0,0 +1,30 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use super::*;
+use crate::common::{SpdmCodec, SpdmConfigInfo, SpdmContext, SpdmProvisionInfo};
+use testlib::{create_spdm_context, DeviceIO, TransportEncap};
+
+#[test]
+fn test_respond_if_ready_struct() {
+    create_spdm_context!(context);
+    let context = &mut context;
+
+    let value = SpdmRespondIfReadyRequestPayload {
+        request_code: SpdmRequestResponseCode::SpdmRequestChallenge,
+        token: 0x5a,
+    };
+    let u8_slice = &mut [0u8; 4];
+    let mut writer = Writer::init(u8_slice);
+    assert_eq!(value.spdm_encode(context, &mut writer), Ok(2));
+    assert_eq!(&u8_slice[..2], &[0x83u8, 0x5a]);
+
+    let reader = &mut Reader::init(&u8_slice[..2]);
+    let ret = SpdmRespondIfReadyRequestPayload::spdm_read(context, reader).unwrap();
+    assert_eq!(
+        ret.request_code,
+        SpdmRequestResponseCode::SpdmRequestChallenge
+    );
+    assert_eq!(ret.token, 0x5a);
+}
@@ -0,0 +1,101 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use crate::common::spdm_codec::SpdmCodec;
+use crate::error::SPDM_STATUS_BUFFER_FULL;
+use crate::protocol::SpdmCertChainBuffer;
+use crate::{common, error::SpdmStatus};
+use codec::{Codec, Reader, Writer};
+
+// SPDM0274 1.2.1: Table 67 SET_CERTIFICATE request message format
+// Param1 bit[3:0] is SlotID.
+pub const SPDM_SET_CERTIFICATE_SLOT_ID_MASK: u8 = 0x0F;
+
+#[derive(Debug, Clone, Default)]
+pub struct SpdmSetCertificateRequestPayload {
+    pub slot_id: u8,
+    // Certificate chain format: Length (2), Reserved (2), RootHash, Certificates
+    pub cert_chain: SpdmCertChainBuffer,
+}
+
+impl SpdmCodec for SpdmSetCertificateRequestPayload {
+    fn spdm_encode(
+        &self,
+        _context: &mut common::SpdmContext,
+        bytes: &mut Writer,
+    ) -> Result<usize, SpdmStatus> {
+        let mut cnt = 0usize;
+        cnt += (self.slot_id & SPDM_SET_CERTIFICATE_SLOT_ID_MASK)
+            .encode(bytes)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param1
+        cnt += 0u8.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param2
+        for d in self.cert_chain.as_ref() {
+            cnt += d.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        }
+        Ok(cnt)
+    }
+
+    fn spdm_read(
+        _context: &mut common::SpdmContext,
+        r: &mut Reader,
+    ) -> Option<SpdmSetCertificateRequestPayload> {
+        let slot_id = u8::read(r)? & SPDM_SET_CERTIFICATE_SLOT_ID_MASK; // param1
+        u8::read(r)?; // param2
+
+        // The Length field of the certificate chain tells where the message ends.
+        let mut cert_chain = SpdmCertChainBuffer::default();
+        let length = u16::read(r)?;
+        if (length as usize) < 4 || length as usize > cert_chain.data.len() {
+            return None;
+        }
+        cert_chain.data[..2].copy_from_slice(&length.to_le_bytes());
+        for d in cert_chain.data.iter_mut().take(length as usize).skip(2) {
+            *d = u8::read(r)?;
+        }
+        cert_chain.data_size = length;
+
+        Some(SpdmSetCertificateRequestPayload {
+            slot_id,
+            cert_chain,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SpdmSetCertificateResponsePayload {
+    pub slot_id: u8,
+}
+
+impl SpdmCodec for SpdmSetCertificateResponsePayload {
+    fn spdm_encode(
+        &self,
+        _context: &mut common::SpdmContext,
+        bytes: &mut Writer,
+    ) -> Result<usize, SpdmStatus> {
+        let mut cnt = 0usize;
+        cnt += (self.slot_id & SPDM_SET_CERTIFICATE_SLOT_ID_MASK)
+            .encode(bytes)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param1
+        cnt += 0u8.encode(bytes).map_err(|_| SPDM_STATUS_BUFFER_FULL)?; // param2
+        Ok(cnt)
+    }
+
+    fn spdm_read(
+        _context: &mut common::SpdmContext,
+        r: &mut Reader,
+    ) -> Option<SpdmSetCertificateResponsePayload> {
+        let slot_id = u8::read(r)? & SPDM_SET_CERTIFICATE_SLOT_ID_MASK; // param1
+        u8::read(r)?; // param2
+
+        Some(SpdmSetCertificateResponsePayload { slot_id })
+    }
+}
+
+#[cfg(all(test,))]
+#[path = "mod_test.common.inc.rs"]
+mod testlib;
+
+#[cfg(all(test,))]
+#[path = "set_certificate_test.rs"]
+mod set_certificate_test;
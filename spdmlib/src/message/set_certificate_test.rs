@@ -0,0 +1,56 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use super::*;
+use crate::common::{SpdmCodec, SpdmConfigInfo, SpdmContext, SpdmProvisionInfo};
+use testlib::{create_spdm_context, DeviceIO, TransportEncap};
+
+#[test]
+fn test_set_certificate_struct() {
+    create_spdm_context!(context);
+    let context = &mut context;
+
+    // 1. Validate the certificate chain is sent as is after SlotID.
+    let mut value = SpdmSetCertificateRequestPayload {
+        slot_id: 3,
+        ..Default::default()
+    };
+    value.cert_chain.data[..8].copy_from_slice(&[8u8, 0, 0, 0, 1, 2, 3, 4]);
+    value.cert_chain.data_size = 8;
+    let u8_slice = &mut [0u8; 16];
+    let mut writer = Writer::init(u8_slice);
+    assert_eq!(value.spdm_encode(context, &mut writer), Ok(10));
+    assert_eq!(&u8_slice[..10], &[3u8, 0, 8, 0, 0, 0, 1, 2, 3, 4]);
+
+    // 2. Validate trailing bytes after the certificate chain are not consumed.
+    let reader = &mut Reader::init(&u8_slice[..12]);
+    let ret = SpdmSetCertificateRequestPayload::spdm_read(context, reader).unwrap();
+    assert_eq!(reader.left(), 2);
+    assert_eq!(ret.slot_id, 3);
+    assert_eq!(ret.cert_chain.as_ref(), &[8u8, 0, 0, 0, 1, 2, 3, 4]);
+
+    // 3. Validate a truncated certificate chain is rejected.
+    let reader = &mut Reader::init(&u8_slice[..9]);
+    assert!(SpdmSetCertificateRequestPayload::spdm_read(context, reader).is_none());
+
+    // 4. Validate a Length smaller than the chain header is rejected.
+    let u8_slice = &mut [0u8, 0, 2, 0, 0, 0];
+    let reader = &mut Reader::init(u8_slice);
+    assert!(SpdmSetCertificateRequestPayload::spdm_read(context, reader).is_none());
+}
+
+#[test]
+fn test_set_certificate_rsp_struct() {
+    create_spdm_context!(context);
+    let context = &mut context;
+
+    let value = SpdmSetCertificateResponsePayload { slot_id: 7 };
+    let u8_slice = &mut [0u8; 4];
+    let mut writer = Writer::init(u8_slice);
+    assert_eq!(value.spdm_encode(context, &mut writer), Ok(2));
+
+    let reader = &mut Reader::init(&u8_slice[..2]);
+    let ret = SpdmSetCertificateResponsePayload::spdm_read(context, reader).unwrap();
+    assert_eq!(ret.slot_id, 7);
+}
@@ -193,6 +193,51 @@ mod tests {
         assert_eq!(0, reader.left());
     }
     #[test]
+    fn test_case1_spdm_version_response_payload() {
+        let u8_slice = &mut [0u8; 4 + 2 * MAX_SPDM_VERSION_COUNT];
+        let mut writer = Writer::init(u8_slice);
+        let spdm_versions = [
+            SpdmVersion::SpdmVersion10,
+            SpdmVersion::SpdmVersion11,
+            SpdmVersion::SpdmVersion12,
+            SpdmVersion::SpdmVersion13,
+            SpdmVersion::SpdmVersion14,
+        ];
+        let mut value = SpdmVersionResponsePayload {
+            version_number_entry_count: spdm_versions.len() as u8,
+            versions: gen_array_clone(SpdmVersionStruct::default(), MAX_SPDM_VERSION_COUNT),
+        };
+        for (i, version) in spdm_versions.iter().enumerate() {
+            value.versions[i] = SpdmVersionStruct {
+                update: 0,
+                version: *version,
+            };
+        }
+
+        create_spdm_context!(context);
+
+        assert!(value.spdm_encode(&mut context, &mut writer).is_ok());
+        let mut reader = Reader::init(u8_slice);
+        assert_eq!(4 + 2 * spdm_versions.len(), reader.left());
+        let version_response =
+            SpdmVersionResponsePayload::spdm_read(&mut context, &mut reader).unwrap();
+
+        assert_eq!(
+            version_response.version_number_entry_count,
+            spdm_versions.len() as u8
+        );
+        for (i, version) in spdm_versions.iter().enumerate() {
+            assert_eq!(version_response.versions[i].version, *version);
+        }
+        assert_eq!(0, reader.left());
+
+        // One more entry than MAX_SPDM_VERSION_COUNT is rejected.
+        let u8_slice = &mut [0u8; 4 + 2 * (MAX_SPDM_VERSION_COUNT + 1)];
+        u8_slice[3] = MAX_SPDM_VERSION_COUNT as u8 + 1;
+        let mut reader = Reader::init(u8_slice);
+        assert!(SpdmVersionResponsePayload::spdm_read(&mut context, &mut reader).is_none());
+    }
+    #[test]
     fn test_case0_spdm_get_version_request_payload() {
         let u8_slice = &mut [0u8; 8];
         let mut writer = Writer::init(u8_slice);
@@ -14,6 +14,10 @@ use zeroize::{Zeroize, ZeroizeOnDrop};
 pub const SHA256_DIGEST_SIZE: usize = 32;
 pub const SHA384_DIGEST_SIZE: usize = 48;
 pub const SHA512_DIGEST_SIZE: usize = 64;
+pub const SHA3_256_DIGEST_SIZE: usize = 32;
+pub const SHA3_384_DIGEST_SIZE: usize = 48;
+pub const SHA3_512_DIGEST_SIZE: usize = 64;
+pub const SM3_256_DIGEST_SIZE: usize = 32;
 
 pub const RSASSA_2048_KEY_SIZE: usize = 256;
 pub const RSASSA_3072_KEY_SIZE: usize = 384;
@@ -24,31 +28,61 @@ pub const RSAPSS_4096_KEY_SIZE: usize = 512;
 
 pub const ECDSA_ECC_NIST_P256_KEY_SIZE: usize = 32 * 2;
 pub const ECDSA_ECC_NIST_P384_KEY_SIZE: usize = 48 * 2;
+pub const ECDSA_ECC_NIST_P521_KEY_SIZE: usize = 66 * 2;
+
+pub const EDDSA_ED25519_KEY_SIZE: usize = 32 * 2;
+pub const EDDSA_ED448_KEY_SIZE: usize = 57 * 2;
+pub const SM2_ECC_SM2_P256_KEY_SIZE: usize = 32 * 2;
+pub const ML_DSA_44_SIGNATURE_SIZE: usize = 2420;
+pub const ML_DSA_65_SIGNATURE_SIZE: usize = 3309;
+pub const ML_DSA_87_SIGNATURE_SIZE: usize = 4627;
 
 pub const SECP_256_R1_KEY_SIZE: usize = 32 * 2;
 pub const SECP_384_R1_KEY_SIZE: usize = 48 * 2;
+pub const SECP_521_R1_KEY_SIZE: usize = 66 * 2;
+pub const SM2_P256_KEY_SIZE: usize = 32 * 2;
+pub const FFDHE_2048_KEY_SIZE: usize = 256;
+pub const FFDHE_3072_KEY_SIZE: usize = 384;
+pub const FFDHE_4096_KEY_SIZE: usize = 512;
+pub const ML_KEM_512_ENCAP_KEY_SIZE: usize = 800;
+pub const ML_KEM_768_ENCAP_KEY_SIZE: usize = 1184;
+pub const ML_KEM_1024_ENCAP_KEY_SIZE: usize = 1568;
+pub const ML_KEM_512_CIPHER_TEXT_SIZE: usize = 768;
+pub const ML_KEM_768_CIPHER_TEXT_SIZE: usize = 1088;
+pub const ML_KEM_1024_CIPHER_TEXT_SIZE: usize = 1568;
 
 pub const AEAD_AES_128_GCM_KEY_SIZE: usize = 16;
 pub const AEAD_AES_256_GCM_KEY_SIZE: usize = 32;
 pub const AEAD_CHACHA20_POLY1305_KEY_SIZE: usize = 32;
+pub const AEAD_SM4_GCM_KEY_SIZE: usize = 16;
 
 pub const AEAD_AES_128_GCM_BLOCK_SIZE: usize = 16;
 pub const AEAD_AES_256_GCM_BLOCK_SIZE: usize = 16;
 pub const AEAD_CHACHA20_POLY1305_BLOCK_SIZE: usize = 16;
+pub const AEAD_SM4_GCM_BLOCK_SIZE: usize = 16;
 
 pub const AEAD_AES_128_GCM_IV_SIZE: usize = 12;
 pub const AEAD_AES_256_GCM_IV_SIZE: usize = 12;
 pub const AEAD_CHACHA20_POLY1305_IV_SIZE: usize = 12;
+pub const AEAD_SM4_GCM_IV_SIZE: usize = 12;
 
 pub const AEAD_AES_128_GCM_TAG_SIZE: usize = 16;
 pub const AEAD_AES_256_GCM_TAG_SIZE: usize = 16;
 pub const AEAD_CHACHA20_POLY1305_TAG_SIZE: usize = 16;
+pub const AEAD_SM4_GCM_TAG_SIZE: usize = 16;
 
 pub const SPDM_NONCE_SIZE: usize = 32;
 pub const SPDM_RANDOM_SIZE: usize = 32;
 pub const SPDM_MAX_HASH_SIZE: usize = 64;
-pub const SPDM_MAX_ASYM_KEY_SIZE: usize = 512;
-pub const SPDM_MAX_DHE_KEY_SIZE: usize = SECP_384_R1_KEY_SIZE;
+// The ML-DSA signatures and ML-KEM keys are only buffered with the pqc feature.
+#[cfg(not(feature = "pqc"))]
+pub const SPDM_MAX_ASYM_KEY_SIZE: usize = RSASSA_4096_KEY_SIZE;
+#[cfg(feature = "pqc")]
+pub const SPDM_MAX_ASYM_KEY_SIZE: usize = ML_DSA_87_SIGNATURE_SIZE;
+#[cfg(not(feature = "pqc"))]
+pub const SPDM_MAX_DHE_KEY_SIZE: usize = FFDHE_4096_KEY_SIZE;
+#[cfg(feature = "pqc")]
+pub const SPDM_MAX_DHE_KEY_SIZE: usize = ML_KEM_1024_ENCAP_KEY_SIZE;
 pub const SPDM_MAX_AEAD_KEY_SIZE: usize = 32;
 pub const SPDM_MAX_AEAD_IV_SIZE: usize = 12;
 pub const SPDM_MAX_HKDF_OKM_SIZE: usize = SPDM_MAX_HASH_SIZE;
@@ -102,6 +136,53 @@ impl SpdmMeasurementSpecification {
     }
 }
 
+bitflags! {
+    #[derive(Default)]
+    pub struct SpdmMelSpecification: u8 {
+        const DMTF = 0b0000_0001;
+        const VALID_MASK = Self::DMTF.bits;
+    }
+}
+
+impl Codec for SpdmMelSpecification {
+    fn encode(&self, bytes: &mut Writer) -> Result<usize, codec::EncodeErr> {
+        self.bits().encode(bytes)
+    }
+
+    fn read(r: &mut Reader) -> Option<SpdmMelSpecification> {
+        let bits = u8::read(r)?;
+        SpdmMelSpecification::from_bits(bits & SpdmMelSpecification::VALID_MASK.bits)
+    }
+}
+impl SpdmMelSpecification {
+    pub fn prioritize(&mut self, peer: SpdmMelSpecification) {
+        let prio_table = [SpdmMelSpecification::DMTF];
+
+        *self &= peer;
+        for v in prio_table.iter() {
+            if self.bits() & v.bits() != 0 {
+                *self = *v;
+                return;
+            }
+        }
+        *self = SpdmMelSpecification::empty();
+    }
+
+    /// return true if no more than one is selected
+    /// return false if two or more is selected
+    pub fn is_no_more_than_one_selected(&self) -> bool {
+        self.bits() == 0 || self.bits() & (self.bits() - 1) == 0
+    }
+
+    pub fn is_valid(&self) -> bool {
+        (self.bits & Self::VALID_MASK.bits) != 0
+    }
+
+    pub fn is_valid_one_select(&self) -> bool {
+        self.is_no_more_than_one_selected() && self.is_valid()
+    }
+}
+
 bitflags! {
     #[derive(Default)]
     pub struct SpdmMeasurementHashAlgo: u32 {
@@ -118,8 +199,8 @@ bitflags! {
             | Self::TPM_ALG_SHA_384.bits
             | Self::TPM_ALG_SHA_512.bits
             | Self::TPM_ALG_SHA3_256.bits
-            | Self::TPM_ALG_SHA3_256.bits
-            | Self::TPM_ALG_SHA3_256.bits
+            | Self::TPM_ALG_SHA3_384.bits
+            | Self::TPM_ALG_SHA3_512.bits
             | Self::TPM_ALG_SM3.bits;
     }
 }
@@ -131,10 +212,10 @@ impl SpdmMeasurementHashAlgo {
             SpdmMeasurementHashAlgo::TPM_ALG_SHA_256 => SHA256_DIGEST_SIZE as u16,
             SpdmMeasurementHashAlgo::TPM_ALG_SHA_384 => SHA384_DIGEST_SIZE as u16,
             SpdmMeasurementHashAlgo::TPM_ALG_SHA_512 => SHA512_DIGEST_SIZE as u16,
-            SpdmMeasurementHashAlgo::TPM_ALG_SHA3_256 => 32,
-            SpdmMeasurementHashAlgo::TPM_ALG_SHA3_384 => 48,
-            SpdmMeasurementHashAlgo::TPM_ALG_SHA3_512 => 64,
-            SpdmMeasurementHashAlgo::TPM_ALG_SM3 => 32,
+            SpdmMeasurementHashAlgo::TPM_ALG_SHA3_256 => SHA3_256_DIGEST_SIZE as u16,
+            SpdmMeasurementHashAlgo::TPM_ALG_SHA3_384 => SHA3_384_DIGEST_SIZE as u16,
+            SpdmMeasurementHashAlgo::TPM_ALG_SHA3_512 => SHA3_512_DIGEST_SIZE as u16,
+            SpdmMeasurementHashAlgo::TPM_ALG_SM3 => SM3_256_DIGEST_SIZE as u16,
             _ => {
                 panic!("invalid MeasurementHashAlgo");
             }
@@ -178,6 +259,10 @@ bitflags! {
         const TPM_ALG_RSASSA_4096 = 0b0010_0000;
         const TPM_ALG_RSAPSS_4096 = 0b0100_0000;
         const TPM_ALG_ECDSA_ECC_NIST_P384 = 0b1000_0000;
+        const TPM_ALG_ECDSA_ECC_NIST_P521 = 0b0000_0001_0000_0000;
+        const EDDSA_ED25519 = 0b0000_0100_0000_0000;
+        const TPM_ALG_SM2_ECC_SM2_P256 = 0b0000_0010_0000_0000;
+        const EDDSA_ED448 = 0b0000_1000_0000_0000;
         const VALID_MASK = Self::TPM_ALG_RSASSA_2048.bits
             | Self::TPM_ALG_RSAPSS_2048.bits
             | Self::TPM_ALG_RSASSA_3072.bits
@@ -185,21 +270,29 @@ bitflags! {
             | Self::TPM_ALG_ECDSA_ECC_NIST_P256.bits
             | Self::TPM_ALG_RSASSA_4096.bits
             | Self::TPM_ALG_RSAPSS_4096.bits
-            | Self::TPM_ALG_ECDSA_ECC_NIST_P384.bits;
+            | Self::TPM_ALG_ECDSA_ECC_NIST_P384.bits
+            | Self::TPM_ALG_ECDSA_ECC_NIST_P521.bits
+            | Self::TPM_ALG_SM2_ECC_SM2_P256.bits
+            | Self::EDDSA_ED25519.bits
+            | Self::EDDSA_ED448.bits;
     }
 }
 
 impl SpdmBaseAsymAlgo {
     pub fn prioritize(&mut self, peer: SpdmBaseAsymAlgo) {
         let prio_table = [
+            SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P521,
             SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384,
             SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P256,
+            SpdmBaseAsymAlgo::EDDSA_ED25519,
+            SpdmBaseAsymAlgo::EDDSA_ED448,
             SpdmBaseAsymAlgo::TPM_ALG_RSAPSS_4096,
             SpdmBaseAsymAlgo::TPM_ALG_RSAPSS_3072,
             SpdmBaseAsymAlgo::TPM_ALG_RSAPSS_2048,
             SpdmBaseAsymAlgo::TPM_ALG_RSASSA_4096,
             SpdmBaseAsymAlgo::TPM_ALG_RSASSA_3072,
             SpdmBaseAsymAlgo::TPM_ALG_RSASSA_2048,
+            SpdmBaseAsymAlgo::TPM_ALG_SM2_ECC_SM2_P256,
         ];
 
         *self &= peer;
@@ -221,6 +314,10 @@ impl SpdmBaseAsymAlgo {
             SpdmBaseAsymAlgo::TPM_ALG_RSAPSS_4096 => RSAPSS_4096_KEY_SIZE as u16,
             SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P256 => ECDSA_ECC_NIST_P256_KEY_SIZE as u16,
             SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384 => ECDSA_ECC_NIST_P384_KEY_SIZE as u16,
+            SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P521 => ECDSA_ECC_NIST_P521_KEY_SIZE as u16,
+            SpdmBaseAsymAlgo::EDDSA_ED25519 => EDDSA_ED25519_KEY_SIZE as u16,
+            SpdmBaseAsymAlgo::EDDSA_ED448 => EDDSA_ED448_KEY_SIZE as u16,
+            SpdmBaseAsymAlgo::TPM_ALG_SM2_ECC_SM2_P256 => SM2_ECC_SM2_P256_KEY_SIZE as u16,
             _ => {
                 panic!("invalid AsymAlgo");
             }
@@ -254,15 +351,91 @@ impl Codec for SpdmBaseAsymAlgo {
     }
 }
 
+// PqcAsymAlgo of NEGOTIATE_ALGORITHMS, SPDM 1.4 (draft)
+bitflags! {
+    #[derive(Default)]
+    pub struct SpdmPqcAsymAlgo: u32 {
+        const ML_DSA_44 = 0b0000_0001;
+        const ML_DSA_65 = 0b0000_0010;
+        const ML_DSA_87 = 0b0000_0100;
+        const VALID_MASK = Self::ML_DSA_44.bits
+            | Self::ML_DSA_65.bits
+            | Self::ML_DSA_87.bits;
+    }
+}
+
+impl SpdmPqcAsymAlgo {
+    pub fn prioritize(&mut self, peer: SpdmPqcAsymAlgo) {
+        let prio_table = [
+            SpdmPqcAsymAlgo::ML_DSA_87,
+            SpdmPqcAsymAlgo::ML_DSA_65,
+            SpdmPqcAsymAlgo::ML_DSA_44,
+        ];
+
+        *self &= peer;
+        for v in prio_table.iter() {
+            if self.bits() & v.bits() != 0 {
+                *self = *v;
+                return;
+            }
+        }
+        *self = SpdmPqcAsymAlgo::empty();
+    }
+    pub fn get_size(&self) -> u16 {
+        match *self {
+            SpdmPqcAsymAlgo::ML_DSA_44 => ML_DSA_44_SIGNATURE_SIZE as u16,
+            SpdmPqcAsymAlgo::ML_DSA_65 => ML_DSA_65_SIGNATURE_SIZE as u16,
+            SpdmPqcAsymAlgo::ML_DSA_87 => ML_DSA_87_SIGNATURE_SIZE as u16,
+            _ => {
+                panic!("invalid PqcAsymAlgo");
+            }
+        }
+    }
+
+    /// return true if no more than one is selected
+    /// return false if two or more is selected
+    pub fn is_no_more_than_one_selected(&self) -> bool {
+        self.bits() == 0 || self.bits() & (self.bits() - 1) == 0
+    }
+
+    pub fn is_valid(&self) -> bool {
+        (self.bits & Self::VALID_MASK.bits) != 0
+    }
+
+    pub fn is_valid_one_select(&self) -> bool {
+        self.is_no_more_than_one_selected() && self.is_valid()
+    }
+}
+
+impl Codec for SpdmPqcAsymAlgo {
+    fn encode(&self, bytes: &mut Writer) -> Result<usize, codec::EncodeErr> {
+        self.bits().encode(bytes)
+    }
+
+    fn read(r: &mut Reader) -> Option<SpdmPqcAsymAlgo> {
+        let bits = u32::read(r)?;
+
+        SpdmPqcAsymAlgo::from_bits(bits & SpdmPqcAsymAlgo::VALID_MASK.bits)
+    }
+}
+
 bitflags! {
     #[derive(Default)]
     pub struct SpdmBaseHashAlgo: u32 {
         const TPM_ALG_SHA_256 = 0b0000_0001;
         const TPM_ALG_SHA_384 = 0b0000_0010;
         const TPM_ALG_SHA_512 = 0b0000_0100;
+        const TPM_ALG_SHA3_256 = 0b0000_1000;
+        const TPM_ALG_SHA3_384 = 0b0001_0000;
+        const TPM_ALG_SHA3_512 = 0b0010_0000;
+        const TPM_ALG_SM3_256 = 0b0100_0000;
         const VALID_MASK = Self::TPM_ALG_SHA_256.bits
             | Self::TPM_ALG_SHA_384.bits
-            | Self::TPM_ALG_SHA_512.bits;
+            | Self::TPM_ALG_SHA_512.bits
+            | Self::TPM_ALG_SHA3_256.bits
+            | Self::TPM_ALG_SHA3_384.bits
+            | Self::TPM_ALG_SHA3_512.bits
+            | Self::TPM_ALG_SM3_256.bits;
     }
 }
 
@@ -272,6 +445,10 @@ impl SpdmBaseHashAlgo {
             SpdmBaseHashAlgo::TPM_ALG_SHA_512,
             SpdmBaseHashAlgo::TPM_ALG_SHA_384,
             SpdmBaseHashAlgo::TPM_ALG_SHA_256,
+            SpdmBaseHashAlgo::TPM_ALG_SHA3_512,
+            SpdmBaseHashAlgo::TPM_ALG_SHA3_384,
+            SpdmBaseHashAlgo::TPM_ALG_SHA3_256,
+            SpdmBaseHashAlgo::TPM_ALG_SM3_256,
         ];
 
         *self &= peer;
@@ -288,6 +465,10 @@ impl SpdmBaseHashAlgo {
             SpdmBaseHashAlgo::TPM_ALG_SHA_256 => SHA256_DIGEST_SIZE as u16,
             SpdmBaseHashAlgo::TPM_ALG_SHA_384 => SHA384_DIGEST_SIZE as u16,
             SpdmBaseHashAlgo::TPM_ALG_SHA_512 => SHA512_DIGEST_SIZE as u16,
+            SpdmBaseHashAlgo::TPM_ALG_SHA3_256 => SHA3_256_DIGEST_SIZE as u16,
+            SpdmBaseHashAlgo::TPM_ALG_SHA3_384 => SHA3_384_DIGEST_SIZE as u16,
+            SpdmBaseHashAlgo::TPM_ALG_SHA3_512 => SHA3_512_DIGEST_SIZE as u16,
+            SpdmBaseHashAlgo::TPM_ALG_SM3_256 => SM3_256_DIGEST_SIZE as u16,
             _ => {
                 panic!("invalid HashAlgo");
             }
@@ -333,23 +514,48 @@ enum_builder! {
         SpdmStandardIdHDBaseT => 0x5,
         SpdmStandardIdMIPI => 0x6,
         SpdmStandardIdCXL => 0x7,
-        SpdmStandardIdJDEC => 0x8
+        SpdmStandardIdJDEC => 0x8,
+        SpdmStandardIdVESA => 0x9,
+        SpdmStandardIdIANACBOR => 0xa
+    }
+}
+impl Default for SpdmStandardId {
+    fn default() -> SpdmStandardId {
+        SpdmStandardId::SpdmStandardIdDMTF
     }
 }
 
 bitflags! {
     #[derive(Default)]
     pub struct SpdmDheAlgo: u16 {
+        const FFDHE_2048 = 0b0000_0001;
+        const FFDHE_3072 = 0b0000_0010;
+        const FFDHE_4096 = 0b0000_0100;
         const SECP_256_R1 = 0b0000_1000;
         const SECP_384_R1 = 0b0001_0000;
-        const VALID_MASK = Self::SECP_256_R1.bits
-            | Self::SECP_384_R1.bits;
+        const SECP_521_R1 = 0b0010_0000;
+        const SM2_P256 = 0b0100_0000;
+        const VALID_MASK = Self::FFDHE_2048.bits
+            | Self::FFDHE_3072.bits
+            | Self::FFDHE_4096.bits
+            | Self::SECP_256_R1.bits
+            | Self::SECP_384_R1.bits
+            | Self::SECP_521_R1.bits
+            | Self::SM2_P256.bits;
     }
 }
 
 impl SpdmDheAlgo {
     pub fn prioritize(&mut self, peer: SpdmDheAlgo) {
-        let prio_table = [SpdmDheAlgo::SECP_384_R1, SpdmDheAlgo::SECP_256_R1];
+        let prio_table = [
+            SpdmDheAlgo::SECP_521_R1,
+            SpdmDheAlgo::SECP_384_R1,
+            SpdmDheAlgo::SECP_256_R1,
+            SpdmDheAlgo::FFDHE_4096,
+            SpdmDheAlgo::FFDHE_3072,
+            SpdmDheAlgo::FFDHE_2048,
+            SpdmDheAlgo::SM2_P256,
+        ];
 
         *self &= peer;
         for v in prio_table.iter() {
@@ -362,8 +568,13 @@ impl SpdmDheAlgo {
     }
     pub fn get_size(&self) -> u16 {
         match *self {
+            SpdmDheAlgo::FFDHE_2048 => FFDHE_2048_KEY_SIZE as u16,
+            SpdmDheAlgo::FFDHE_3072 => FFDHE_3072_KEY_SIZE as u16,
+            SpdmDheAlgo::FFDHE_4096 => FFDHE_4096_KEY_SIZE as u16,
             SpdmDheAlgo::SECP_256_R1 => SECP_256_R1_KEY_SIZE as u16,
             SpdmDheAlgo::SECP_384_R1 => SECP_384_R1_KEY_SIZE as u16,
+            SpdmDheAlgo::SECP_521_R1 => SECP_521_R1_KEY_SIZE as u16,
+            SpdmDheAlgo::SM2_P256 => SM2_P256_KEY_SIZE as u16,
             _ => {
                 panic!("invalid DheAlgo");
             }
@@ -397,15 +608,97 @@ impl Codec for SpdmDheAlgo {
     }
 }
 
+// KEMAlg of the algorithm structure, SPDM 1.4 (draft)
+bitflags! {
+    #[derive(Default)]
+    pub struct SpdmKemAlgo: u16 {
+        const ML_KEM_512 = 0b0000_0001;
+        const ML_KEM_768 = 0b0000_0010;
+        const ML_KEM_1024 = 0b0000_0100;
+        const VALID_MASK = Self::ML_KEM_512.bits
+            | Self::ML_KEM_768.bits
+            | Self::ML_KEM_1024.bits;
+    }
+}
+
+impl SpdmKemAlgo {
+    pub fn prioritize(&mut self, peer: SpdmKemAlgo) {
+        let prio_table = [
+            SpdmKemAlgo::ML_KEM_1024,
+            SpdmKemAlgo::ML_KEM_768,
+            SpdmKemAlgo::ML_KEM_512,
+        ];
+
+        *self &= peer;
+        for v in prio_table.iter() {
+            if self.bits() & v.bits() != 0 {
+                *self = *v;
+                return;
+            }
+        }
+        *self = SpdmKemAlgo::empty();
+    }
+    /// Size of the encapsulation key sent in KEY_EXCHANGE.
+    pub fn get_size(&self) -> u16 {
+        match *self {
+            SpdmKemAlgo::ML_KEM_512 => ML_KEM_512_ENCAP_KEY_SIZE as u16,
+            SpdmKemAlgo::ML_KEM_768 => ML_KEM_768_ENCAP_KEY_SIZE as u16,
+            SpdmKemAlgo::ML_KEM_1024 => ML_KEM_1024_ENCAP_KEY_SIZE as u16,
+            _ => {
+                panic!("invalid KemAlgo");
+            }
+        }
+    }
+    /// Size of the cipher text returned in KEY_EXCHANGE_RSP.
+    pub fn get_cipher_text_size(&self) -> u16 {
+        match *self {
+            SpdmKemAlgo::ML_KEM_512 => ML_KEM_512_CIPHER_TEXT_SIZE as u16,
+            SpdmKemAlgo::ML_KEM_768 => ML_KEM_768_CIPHER_TEXT_SIZE as u16,
+            SpdmKemAlgo::ML_KEM_1024 => ML_KEM_1024_CIPHER_TEXT_SIZE as u16,
+            _ => {
+                panic!("invalid KemAlgo");
+            }
+        }
+    }
+
+    /// return true if no more than one is selected
+    /// return false if two or more is selected
+    pub fn is_no_more_than_one_selected(&self) -> bool {
+        self.bits() == 0 || self.bits() & (self.bits() - 1) == 0
+    }
+
+    pub fn is_valid(&self) -> bool {
+        (self.bits & Self::VALID_MASK.bits) != 0
+    }
+
+    pub fn is_valid_one_select(&self) -> bool {
+        self.is_no_more_than_one_selected() && self.is_valid()
+    }
+}
+
+impl Codec for SpdmKemAlgo {
+    fn encode(&self, bytes: &mut Writer) -> Result<usize, codec::EncodeErr> {
+        self.bits().encode(bytes)
+    }
+
+    fn read(r: &mut Reader) -> Option<SpdmKemAlgo> {
+        let bits = u16::read(r)?;
+
+        SpdmKemAlgo::from_bits(bits & SpdmKemAlgo::VALID_MASK.bits)
+    }
+}
+
 bitflags! {
     #[derive(Default)]
     pub struct SpdmAeadAlgo: u16 {
         const AES_128_GCM = 0b0000_0001;
         const AES_256_GCM = 0b0000_0010;
         const CHACHA20_POLY1305 = 0b0000_0100;
+        const SM4_GCM = 0b0000_1000;
         const VALID_MASK = Self::AES_128_GCM.bits
             | Self::AES_256_GCM.bits
-            | Self::CHACHA20_POLY1305.bits;
+            | Self::CHACHA20_POLY1305.bits
+            | Self::SM4_GCM.bits;
     }
 }
 
@@ -415,6 +708,7 @@ impl SpdmAeadAlgo {
             SpdmAeadAlgo::AES_256_GCM,
             SpdmAeadAlgo::AES_128_GCM,
             SpdmAeadAlgo::CHACHA20_POLY1305,
+            SpdmAeadAlgo::SM4_GCM,
         ];
 
         *self &= peer;
@@ -431,6 +725,7 @@ impl SpdmAeadAlgo {
             SpdmAeadAlgo::AES_128_GCM => AEAD_AES_128_GCM_KEY_SIZE as u16,
             SpdmAeadAlgo::AES_256_GCM => AEAD_AES_256_GCM_KEY_SIZE as u16,
             SpdmAeadAlgo::CHACHA20_POLY1305 => AEAD_CHACHA20_POLY1305_KEY_SIZE as u16,
+            SpdmAeadAlgo::SM4_GCM => AEAD_SM4_GCM_KEY_SIZE as u16,
             _ => {
                 panic!("invalid AeadAlgo");
             }
@@ -441,6 +736,7 @@ impl SpdmAeadAlgo {
             SpdmAeadAlgo::AES_128_GCM => AEAD_AES_128_GCM_IV_SIZE as u16,
             SpdmAeadAlgo::AES_256_GCM => AEAD_AES_256_GCM_IV_SIZE as u16,
             SpdmAeadAlgo::CHACHA20_POLY1305 => AEAD_CHACHA20_POLY1305_IV_SIZE as u16,
+            SpdmAeadAlgo::SM4_GCM => AEAD_SM4_GCM_IV_SIZE as u16,
             _ => {
                 panic!("invalid AeadAlgo");
             }
@@ -451,6 +747,7 @@ impl SpdmAeadAlgo {
             SpdmAeadAlgo::AES_128_GCM => AEAD_AES_128_GCM_TAG_SIZE as u16,
             SpdmAeadAlgo::AES_256_GCM => AEAD_AES_256_GCM_TAG_SIZE as u16,
             SpdmAeadAlgo::CHACHA20_POLY1305 => AEAD_CHACHA20_POLY1305_TAG_SIZE as u16,
+            SpdmAeadAlgo::SM4_GCM => AEAD_SM4_GCM_TAG_SIZE as u16,
             _ => {
                 panic!("invalid AeadAlgo");
             }
@@ -495,6 +792,10 @@ bitflags! {
         const TPM_ALG_RSASSA_4096 = 0b0010_0000;
         const TPM_ALG_RSAPSS_4096 = 0b0100_0000;
         const TPM_ALG_ECDSA_ECC_NIST_P384 = 0b1000_0000;
+        const TPM_ALG_ECDSA_ECC_NIST_P521 = 0b0000_0001_0000_0000;
+        const EDDSA_ED25519 = 0b0000_0100_0000_0000;
+        const TPM_ALG_SM2_ECC_SM2_P256 = 0b0000_0010_0000_0000;
+        const EDDSA_ED448 = 0b0000_1000_0000_0000;
         const VALID_MASK = Self::TPM_ALG_RSASSA_2048.bits
             | Self::TPM_ALG_RSAPSS_2048.bits
             | Self::TPM_ALG_RSASSA_3072.bits
@@ -502,21 +803,29 @@ bitflags! {
             | Self::TPM_ALG_ECDSA_ECC_NIST_P256.bits
             | Self::TPM_ALG_RSASSA_4096.bits
             | Self::TPM_ALG_RSAPSS_4096.bits
-            | Self::TPM_ALG_ECDSA_ECC_NIST_P384.bits;
+            | Self::TPM_ALG_ECDSA_ECC_NIST_P384.bits
+            | Self::TPM_ALG_ECDSA_ECC_NIST_P521.bits
+            | Self::TPM_ALG_SM2_ECC_SM2_P256.bits
+            | Self::EDDSA_ED25519.bits
+            | Self::EDDSA_ED448.bits;
     }
 }
 
 impl SpdmReqAsymAlgo {
     pub fn prioritize(&mut self, peer: SpdmReqAsymAlgo) {
         let prio_table = [
+            SpdmReqAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P521,
             SpdmReqAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384,
             SpdmReqAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P256,
+            SpdmReqAsymAlgo::EDDSA_ED25519,
+            SpdmReqAsymAlgo::EDDSA_ED448,
             SpdmReqAsymAlgo::TPM_ALG_RSAPSS_4096,
             SpdmReqAsymAlgo::TPM_ALG_RSAPSS_3072,
             SpdmReqAsymAlgo::TPM_ALG_RSAPSS_2048,
             SpdmReqAsymAlgo::TPM_ALG_RSASSA_4096,
             SpdmReqAsymAlgo::TPM_ALG_RSASSA_3072,
             SpdmReqAsymAlgo::TPM_ALG_RSASSA_2048,
+            SpdmReqAsymAlgo::TPM_ALG_SM2_ECC_SM2_P256,
         ];
 
         *self &= peer;
@@ -538,6 +847,10 @@ impl SpdmReqAsymAlgo {
             SpdmReqAsymAlgo::TPM_ALG_RSAPSS_4096 => RSAPSS_4096_KEY_SIZE as u16,
             SpdmReqAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P256 => ECDSA_ECC_NIST_P256_KEY_SIZE as u16,
             SpdmReqAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384 => ECDSA_ECC_NIST_P384_KEY_SIZE as u16,
+            SpdmReqAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P521 => ECDSA_ECC_NIST_P521_KEY_SIZE as u16,
+            SpdmReqAsymAlgo::EDDSA_ED25519 => EDDSA_ED25519_KEY_SIZE as u16,
+            SpdmReqAsymAlgo::EDDSA_ED448 => EDDSA_ED448_KEY_SIZE as u16,
+            SpdmReqAsymAlgo::TPM_ALG_SM2_ECC_SM2_P256 => SM2_ECC_SM2_P256_KEY_SIZE as u16,
             _ => {
                 panic!("invalid ReqAsymAlgo");
             }
@@ -639,7 +952,8 @@ enum_builder! {
         SpdmAlgTypeDHE => 0x2,
         SpdmAlgTypeAEAD => 0x3,
         SpdmAlgTypeReqAsym => 0x4,
-        SpdmAlgTypeKeySchedule => 0x5
+        SpdmAlgTypeKeySchedule => 0x5,
+        SpdmAlgTypeKEM => 0x7
     }
 }
 impl Default for SpdmAlgType {
@@ -654,6 +968,7 @@ pub enum SpdmAlg {
     SpdmAlgoAead(SpdmAeadAlgo),
     SpdmAlgoReqAsym(SpdmReqAsymAlgo),
     SpdmAlgoKeySchedule(SpdmKeyScheduleAlgo),
+    SpdmAlgoKem(SpdmKemAlgo),
     // TBD: Need consider how to handle this SpdmAlgoUnknown
     SpdmAlgoUnknown(SpdmUnknownAlgo),
 }
@@ -691,6 +1006,9 @@ impl Codec for SpdmAlgStruct {
             SpdmAlg::SpdmAlgoKeySchedule(alg_supported) => {
                 cnt += alg_supported.encode(bytes)?;
             }
+            SpdmAlg::SpdmAlgoKem(alg_supported) => {
+                cnt += alg_supported.encode(bytes)?;
+            }
             SpdmAlg::SpdmAlgoUnknown(alg_supported) => {
                 cnt += alg_supported.encode(bytes)?;
             }
@@ -719,6 +1037,7 @@ impl Codec for SpdmAlgStruct {
             SpdmAlgType::SpdmAlgTypeKeySchedule => {
                 Some(SpdmAlg::SpdmAlgoKeySchedule(SpdmKeyScheduleAlgo::read(r)?))
             }
+            SpdmAlgType::SpdmAlgTypeKEM => Some(SpdmAlg::SpdmAlgoKem(SpdmKemAlgo::read(r)?)),
             _ => return None,
         };
 
@@ -731,8 +1050,51 @@ impl Codec for SpdmAlgStruct {
     }
 }
 
+// ExtAsymCount + ExtHashCount in NEGOTIATE_ALGORITHMS
+pub const SPDM_MAX_EXT_ALG_COUNT: usize = 20;
+
+// DHE, AEAD, ReqBaseAsymAlg, KeySchedule and, with the pqc feature, KEM (SPDM 1.4)
+// algorithm structures
+#[cfg(not(feature = "pqc"))]
+pub const SPDM_MAX_ALG_STRUCT_COUNT: usize = 4;
+#[cfg(feature = "pqc")]
+pub const SPDM_MAX_ALG_STRUCT_COUNT: usize = 5;
+
+/// Extended algorithm, identified by its registry and the algorithm ID
+/// assigned by that registry. DSP0274 Table: Extended Algorithm field format.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SpdmExtAlgStruct {
+    pub registry_id: SpdmStandardId,
+    pub algorithm_id: u16,
+}
+
+impl Codec for SpdmExtAlgStruct {
+    fn encode(&self, bytes: &mut Writer) -> Result<usize, codec::EncodeErr> {
+        let mut cnt = 0usize;
+        cnt += self.registry_id.encode(bytes)?;
+        cnt += 0u8.encode(bytes)?; // reserved
+        cnt += self.algorithm_id.encode(bytes)?;
+        Ok(cnt)
+    }
+
+    fn read(r: &mut Reader) -> Option<SpdmExtAlgStruct> {
+        let registry_id = SpdmStandardId::read(r)?;
+        u8::read(r)?; // reserved
+        let algorithm_id = u16::read(r)?;
+        Some(SpdmExtAlgStruct {
+            registry_id,
+            algorithm_id,
+        })
+    }
+}
+
 pub const SPDM_MAX_SLOT_NUMBER: usize = 8;
 
+// SlotID 0xFF indicates the public key provisioned to the peer is used
+// instead of a certificate chain. It is 0xF in the 4-bit SlotID fields.
+pub const SPDM_PUBLIC_KEY_SLOT_ID: u8 = 0xFF;
+pub const SPDM_PUBLIC_KEY_SLOT_ID_PARAM: u8 = 0xF;
+
 enum_builder! {
     @U8
     EnumName: SpdmMeasurementSummaryHashType;
@@ -840,6 +1202,112 @@ impl AsRef<[u8]> for SpdmCertChainData {
     }
 }
 
+/// This is used in SpdmCsrStruct <- SpdmCsrResponsePayload
+/// CSR response header is 8 bytes: SPDM header (4) + CSRLength (2) + Reserved (2)
+pub const SPDM_MAX_CSR_SIZE: usize = config::MAX_SPDM_MSG_SIZE - 8;
+
+#[derive(Debug, Clone)]
+pub struct SpdmCsrStruct {
+    pub data_size: u16,
+    pub data: [u8; SPDM_MAX_CSR_SIZE],
+}
+
+impl Default for SpdmCsrStruct {
+    fn default() -> Self {
+        SpdmCsrStruct {
+            data_size: 0u16,
+            data: [0u8; SPDM_MAX_CSR_SIZE],
+        }
+    }
+}
+impl AsRef<[u8]> for SpdmCsrStruct {
+    fn as_ref(&self) -> &[u8] {
+        &self.data[0..(self.data_size as usize)]
+    }
+}
+
+/// The provisioned public key is a DER encoded SubjectPublicKeyInfo.
+pub const SPDM_MAX_PUBLIC_KEY_SIZE: usize = 1024;
+
+#[derive(Debug, Clone)]
+pub struct SpdmPublicKeyData {
+    pub data_size: u16,
+    pub data: [u8; SPDM_MAX_PUBLIC_KEY_SIZE],
+}
+
+impl Default for SpdmPublicKeyData {
+    fn default() -> Self {
+        SpdmPublicKeyData {
+            data_size: 0u16,
+            data: [0u8; SPDM_MAX_PUBLIC_KEY_SIZE],
+        }
+    }
+}
+impl AsRef<[u8]> for SpdmPublicKeyData {
+    fn as_ref(&self) -> &[u8] {
+        &self.data[0..(self.data_size as usize)]
+    }
+}
+
+/// The measurement extension log (MEL) in the format selected by
+/// SpdmMelSpecification.
+#[derive(Debug, Clone)]
+pub struct SpdmMeasurementExtensionLog {
+    pub data_size: u32,
+    pub data: [u8; config::MAX_SPDM_MEASUREMENT_EXTENSION_LOG_SIZE],
+}
+
+impl Default for SpdmMeasurementExtensionLog {
+    fn default() -> Self {
+        SpdmMeasurementExtensionLog {
+            data_size: 0u32,
+            data: [0u8; config::MAX_SPDM_MEASUREMENT_EXTENSION_LOG_SIZE],
+        }
+    }
+}
+impl AsRef<[u8]> for SpdmMeasurementExtensionLog {
+    fn as_ref(&self) -> &[u8] {
+        &self.data[0..(self.data_size as usize)]
+    }
+}
+
+enum_builder! {
+    @U8
+    EnumName: SpdmEndpointInfoSubCode;
+    EnumVal{
+        SpdmEndpointInfoSubCodeDeviceClassIdentifier => 0x1
+    }
+}
+impl Default for SpdmEndpointInfoSubCode {
+    fn default() -> SpdmEndpointInfoSubCode {
+        SpdmEndpointInfoSubCode::SpdmEndpointInfoSubCodeDeviceClassIdentifier
+    }
+}
+
+pub const SPDM_MAX_ENDPOINT_INFO_SIZE: usize = 1024;
+
+/// The endpoint information returned by ENDPOINT_INFO, in the format
+/// selected by SpdmEndpointInfoSubCode.
+#[derive(Debug, Clone)]
+pub struct SpdmEndpointInfo {
+    pub data_size: u32,
+    pub data: [u8; SPDM_MAX_ENDPOINT_INFO_SIZE],
+}
+
+impl Default for SpdmEndpointInfo {
+    fn default() -> Self {
+        SpdmEndpointInfo {
+            data_size: 0u32,
+            data: [0u8; SPDM_MAX_ENDPOINT_INFO_SIZE],
+        }
+    }
+}
+impl AsRef<[u8]> for SpdmEndpointInfo {
+    fn as_ref(&self) -> &[u8] {
+        &self.data[0..(self.data_size as usize)]
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct SpdmCertChainBuffer {
     pub data_size: u16,
@@ -1580,6 +2048,44 @@ mod tests {
         assert_eq!(0, reader.left());
     }
     #[test]
+    fn test_case1_spdm_base_hash_algo() {
+        for (value, size) in [
+            (SpdmBaseHashAlgo::TPM_ALG_SHA3_256, SHA3_256_DIGEST_SIZE),
+            (SpdmBaseHashAlgo::TPM_ALG_SHA3_384, SHA3_384_DIGEST_SIZE),
+            (SpdmBaseHashAlgo::TPM_ALG_SHA3_512, SHA3_512_DIGEST_SIZE),
+        ] {
+            assert_eq!(value.get_size(), size as u16);
+
+            let u8_slice = &mut [0u8; 4];
+            let mut writer = Writer::init(u8_slice);
+            assert!(value.encode(&mut writer).is_ok());
+            let mut reader = Reader::init(u8_slice);
+            assert_eq!(SpdmBaseHashAlgo::read(&mut reader).unwrap(), value);
+        }
+
+        let mut value = SpdmBaseHashAlgo::TPM_ALG_SHA3_384 | SpdmBaseHashAlgo::TPM_ALG_SHA3_256;
+        value.prioritize(SpdmBaseHashAlgo::VALID_MASK);
+        assert_eq!(value, SpdmBaseHashAlgo::TPM_ALG_SHA3_384);
+
+        let mut value = SpdmBaseHashAlgo::TPM_ALG_SHA_256 | SpdmBaseHashAlgo::TPM_ALG_SHA3_512;
+        value.prioritize(SpdmBaseHashAlgo::VALID_MASK);
+        assert_eq!(value, SpdmBaseHashAlgo::TPM_ALG_SHA_256);
+    }
+    #[test]
+    fn test_case2_spdm_measurement_hash_algo() {
+        for value in [
+            SpdmMeasurementHashAlgo::TPM_ALG_SHA3_256,
+            SpdmMeasurementHashAlgo::TPM_ALG_SHA3_384,
+            SpdmMeasurementHashAlgo::TPM_ALG_SHA3_512,
+        ] {
+            let u8_slice = &mut [0u8; 4];
+            let mut writer = Writer::init(u8_slice);
+            assert!(value.encode(&mut writer).is_ok());
+            let mut reader = Reader::init(u8_slice);
+            assert_eq!(SpdmMeasurementHashAlgo::read(&mut reader).unwrap(), value);
+        }
+    }
+    #[test]
     fn test_case0_spdm_dhe_algo() {
         let u8_slice = &mut [0u8; 4];
         let mut writer = Writer::init(u8_slice);
@@ -1750,6 +2256,15 @@ mod tests {
         value = SpdmMeasurementHashAlgo::TPM_ALG_SHA_512;
         assert_eq!(value.get_size(), SHA512_DIGEST_SIZE as u16);
 
+        value = SpdmMeasurementHashAlgo::TPM_ALG_SHA3_256;
+        assert_eq!(value.get_size(), SHA3_256_DIGEST_SIZE as u16);
+
+        value = SpdmMeasurementHashAlgo::TPM_ALG_SHA3_384;
+        assert_eq!(value.get_size(), SHA3_384_DIGEST_SIZE as u16);
+
+        value = SpdmMeasurementHashAlgo::TPM_ALG_SHA3_512;
+        assert_eq!(value.get_size(), SHA3_512_DIGEST_SIZE as u16);
+
         value = SpdmMeasurementHashAlgo::RAW_BIT_STREAM;
         assert_eq!(value.get_size(), 0u16);
 
@@ -1783,10 +2298,25 @@ mod tests {
         value = SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384;
         assert_eq!(value.get_size(), ECDSA_ECC_NIST_P384_KEY_SIZE as u16);
 
+        value = SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P521;
+        assert_eq!(value.get_size(), ECDSA_ECC_NIST_P521_KEY_SIZE as u16);
+
         value = SpdmBaseAsymAlgo::empty();
         value.get_size();
     }
     #[test]
+    fn test_case2_spdm_base_asym_algo() {
+        let mut value = SpdmBaseAsymAlgo::EDDSA_ED25519
+            | SpdmBaseAsymAlgo::EDDSA_ED448
+            | SpdmBaseAsymAlgo::TPM_ALG_RSAPSS_3072;
+        value.prioritize(SpdmBaseAsymAlgo::VALID_MASK);
+        assert_eq!(value, SpdmBaseAsymAlgo::EDDSA_ED25519);
+
+        let mut value = SpdmBaseAsymAlgo::EDDSA_ED448 | SpdmBaseAsymAlgo::TPM_ALG_RSAPSS_3072;
+        value.prioritize(SpdmBaseAsymAlgo::VALID_MASK);
+        assert_eq!(value, SpdmBaseAsymAlgo::EDDSA_ED448);
+    }
+    #[test]
     #[should_panic(expected = "invalid DheAlgo")]
     fn test_case1_spdm_dhe_algo() {
         let mut value = SpdmDheAlgo::SECP_256_R1;
@@ -1795,10 +2325,31 @@ mod tests {
         value = SpdmDheAlgo::SECP_384_R1;
         assert_eq!(value.get_size(), SECP_384_R1_KEY_SIZE as u16);
 
+        value = SpdmDheAlgo::SECP_521_R1;
+        assert_eq!(value.get_size(), SECP_521_R1_KEY_SIZE as u16);
+
+        value = SpdmDheAlgo::FFDHE_2048;
+        assert_eq!(value.get_size(), FFDHE_2048_KEY_SIZE as u16);
+
+        value = SpdmDheAlgo::FFDHE_4096;
+        assert_eq!(value.get_size(), FFDHE_4096_KEY_SIZE as u16);
+
         value = SpdmDheAlgo::empty();
         value.get_size();
     }
     #[test]
+    fn test_case2_spdm_dhe_algo() {
+        let mut value =
+            SpdmDheAlgo::FFDHE_2048 | SpdmDheAlgo::FFDHE_3072 | SpdmDheAlgo::SECP_384_R1;
+        value.prioritize(SpdmDheAlgo::FFDHE_3072 | SpdmDheAlgo::SECP_384_R1);
+        assert_eq!(value, SpdmDheAlgo::SECP_384_R1);
+
+        let mut value =
+            SpdmDheAlgo::FFDHE_2048 | SpdmDheAlgo::FFDHE_3072 | SpdmDheAlgo::SECP_384_R1;
+        value.prioritize(SpdmDheAlgo::FFDHE_2048 | SpdmDheAlgo::FFDHE_3072);
+        assert_eq!(value, SpdmDheAlgo::FFDHE_3072);
+    }
+    #[test]
     #[should_panic(expected = "invalid AeadAlgo")]
     fn test_case1_spdm_aead_algo() {
         let mut value = SpdmAeadAlgo::AES_128_GCM;
@@ -1885,10 +2436,25 @@ mod tests {
         value = SpdmReqAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384;
         assert_eq!(value.get_size(), ECDSA_ECC_NIST_P384_KEY_SIZE as u16);
 
+        value = SpdmReqAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P521;
+        assert_eq!(value.get_size(), ECDSA_ECC_NIST_P521_KEY_SIZE as u16);
+
         value = SpdmReqAsymAlgo::empty();
         value.get_size();
     }
     #[test]
+    fn test_case2_spdm_req_asym_algo() {
+        let mut value = SpdmReqAsymAlgo::EDDSA_ED25519
+            | SpdmReqAsymAlgo::EDDSA_ED448
+            | SpdmReqAsymAlgo::TPM_ALG_RSAPSS_3072;
+        value.prioritize(SpdmReqAsymAlgo::VALID_MASK);
+        assert_eq!(value, SpdmReqAsymAlgo::EDDSA_ED25519);
+
+        let mut value = SpdmReqAsymAlgo::EDDSA_ED448 | SpdmReqAsymAlgo::TPM_ALG_RSAPSS_3072;
+        value.prioritize(SpdmReqAsymAlgo::VALID_MASK);
+        assert_eq!(value, SpdmReqAsymAlgo::EDDSA_ED448);
+    }
+    #[test]
     fn test_case0_spdm_unknown_algo() {
         let u8_slice = &mut [0u8; 8];
         let mut writer = Writer::init(u8_slice);
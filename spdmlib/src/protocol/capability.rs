@@ -21,6 +21,11 @@ bitflags! {
         const HANDSHAKE_IN_THE_CLEAR_CAP = 0b1000_0000_0000_0000;
         const PUB_KEY_ID_CAP = 0b0000_0001_0000_0000_0000_0000;
         const CHUNK_CAP = 0b0000_0010_0000_0000_0000_0000;
+        const EP_INFO_CAP_NO_SIG = 0b0100_0000_0000_0000_0000_0000;
+        const EP_INFO_CAP_SIG = 0b1000_0000_0000_0000_0000_0000;
+        const EVENT_CAP = 0b0010_0000_0000_0000_0000_0000_0000;
+        const MULTI_KEY_CAP_ONLY = 0b0100_0000_0000_0000_0000_0000_0000;
+        const MULTI_KEY_CAP_NEG = 0b1000_0000_0000_0000_0000_0000_0000;
         const VALID_MASK = Self::CERT_CAP.bits
             | Self::CHAL_CAP.bits
             | Self::ENCRYPT_CAP.bits
@@ -34,7 +39,12 @@ bitflags! {
             | Self::KEY_UPD_CAP.bits
             | Self::HANDSHAKE_IN_THE_CLEAR_CAP.bits
             | Self::PUB_KEY_ID_CAP.bits
-            | Self::CHUNK_CAP.bits;
+            | Self::CHUNK_CAP.bits
+            | Self::EP_INFO_CAP_NO_SIG.bits
+            | Self::EP_INFO_CAP_SIG.bits
+            | Self::EVENT_CAP.bits
+            | Self::MULTI_KEY_CAP_ONLY.bits
+            | Self::MULTI_KEY_CAP_NEG.bits;
     }
 }
 
@@ -75,6 +85,14 @@ bitflags! {
         const SET_CERT_CAP = 0b0000_1000_0000_0000_0000_0000;
         const CSR_CAP = 0b0001_0000_0000_0000_0000_0000;
         const CERT_INSTALL_RESET_CAP = 0b0010_0000_0000_0000_0000_0000;
+        const EP_INFO_CAP_NO_SIG = 0b0100_0000_0000_0000_0000_0000;
+        const EP_INFO_CAP_SIG = 0b1000_0000_0000_0000_0000_0000;
+        const MEL_CAP = 0b0001_0000_0000_0000_0000_0000_0000;
+        const EVENT_CAP = 0b0010_0000_0000_0000_0000_0000_0000;
+        const MULTI_KEY_CAP_ONLY = 0b0100_0000_0000_0000_0000_0000_0000;
+        const MULTI_KEY_CAP_NEG = 0b1000_0000_0000_0000_0000_0000_0000;
+        const GET_KEY_PAIR_INFO_CAP = 0b0001_0000_0000_0000_0000_0000_0000_0000;
+        const SET_KEY_PAIR_INFO_CAP = 0b0010_0000_0000_0000_0000_0000_0000_0000;
         const VALID_MASK = Self::CACHE_CAP.bits
             | Self::CERT_CAP.bits
             | Self::CHAL_CAP.bits
@@ -96,7 +114,15 @@ bitflags! {
             | Self::ALIAS_CERT_CAP.bits
             | Self::SET_CERT_CAP.bits
             | Self::CSR_CAP.bits
-            | Self::CERT_INSTALL_RESET_CAP.bits;
+            | Self::CERT_INSTALL_RESET_CAP.bits
+            | Self::EP_INFO_CAP_NO_SIG.bits
+            | Self::EP_INFO_CAP_SIG.bits
+            | Self::MEL_CAP.bits
+            | Self::EVENT_CAP.bits
+            | Self::MULTI_KEY_CAP_ONLY.bits
+            | Self::MULTI_KEY_CAP_NEG.bits
+            | Self::GET_KEY_PAIR_INFO_CAP.bits
+            | Self::SET_KEY_PAIR_INFO_CAP.bits;
     }
 }
 
@@ -0,0 +1,144 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use super::SpdmPublicKeyData;
+use codec::{enum_builder, Codec, Reader, Writer};
+
+/// KeyPairID 0 is reserved, it selects the default key of a slot that is not
+/// associated with any key pair.
+pub const SPDM_DEFAULT_KEY_PAIR_ID: u8 = 0;
+
+bitflags! {
+    #[derive(Default)]
+    pub struct SpdmKeyPairCapabilities: u16 {
+        const GEN_KEY_CAP = 0b0000_0001;
+        const ERASABLE_CAP = 0b0000_0010;
+        const CERT_ASSOC_CAP = 0b0000_0100;
+        const KEY_USAGE_CAP = 0b0000_1000;
+        const ASYM_ALGO_CAP = 0b0001_0000;
+        const SHAREABLE_CAP = 0b0010_0000;
+        const VALID_MASK = Self::GEN_KEY_CAP.bits
+            | Self::ERASABLE_CAP.bits
+            | Self::CERT_ASSOC_CAP.bits
+            | Self::KEY_USAGE_CAP.bits
+            | Self::ASYM_ALGO_CAP.bits
+            | Self::SHAREABLE_CAP.bits;
+    }
+}
+
+impl Codec for SpdmKeyPairCapabilities {
+    fn encode(&self, bytes: &mut Writer) -> Result<usize, codec::EncodeErr> {
+        self.bits().encode(bytes)
+    }
+
+    fn read(r: &mut Reader) -> Option<SpdmKeyPairCapabilities> {
+        let bits = u16::read(r)?;
+
+        SpdmKeyPairCapabilities::from_bits(bits & SpdmKeyPairCapabilities::VALID_MASK.bits)
+    }
+}
+
+bitflags! {
+    #[derive(Default)]
+    pub struct SpdmKeyUsageMask: u16 {
+        const KEY_EX_USE = 0b0000_0001;
+        const CHALLENGE_USE = 0b0000_0010;
+        const MEASUREMENT_USE = 0b0000_0100;
+        const ENDPOINT_INFO_USE = 0b0000_1000;
+        const STANDARDS_KEY_USE = 0b0100_0000_0000_0000;
+        const VENDOR_KEY_USE = 0b1000_0000_0000_0000;
+        const VALID_MASK = Self::KEY_EX_USE.bits
+            | Self::CHALLENGE_USE.bits
+            | Self::MEASUREMENT_USE.bits
+            | Self::ENDPOINT_INFO_USE.bits
+            | Self::STANDARDS_KEY_USE.bits
+            | Self::VENDOR_KEY_USE.bits;
+    }
+}
+
+impl Codec for SpdmKeyUsageMask {
+    fn encode(&self, bytes: &mut Writer) -> Result<usize, codec::EncodeErr> {
+        self.bits().encode(bytes)
+    }
+
+    fn read(r: &mut Reader) -> Option<SpdmKeyUsageMask> {
+        let bits = u16::read(r)?;
+
+        SpdmKeyUsageMask::from_bits(bits & SpdmKeyUsageMask::VALID_MASK.bits)
+    }
+}
+
+bitflags! {
+    #[derive(Default)]
+    pub struct SpdmKeyPairAsymAlgo: u32 {
+        const RSA2048 = 0b0000_0001;
+        const RSA3072 = 0b0000_0010;
+        const RSA4096 = 0b0000_0100;
+        const ECC256 = 0b0000_1000;
+        const ECC384 = 0b0001_0000;
+        const ECC521 = 0b0010_0000;
+        const SM2 = 0b0100_0000;
+        const ED25519 = 0b1000_0000;
+        const ED448 = 0b0000_0001_0000_0000;
+        const VALID_MASK = Self::RSA2048.bits
+            | Self::RSA3072.bits
+            | Self::RSA4096.bits
+            | Self::ECC256.bits
+            | Self::ECC384.bits
+            | Self::ECC521.bits
+            | Self::SM2.bits
+            | Self::ED25519.bits
+            | Self::ED448.bits;
+    }
+}
+
+impl Codec for SpdmKeyPairAsymAlgo {
+    fn encode(&self, bytes: &mut Writer) -> Result<usize, codec::EncodeErr> {
+        self.bits().encode(bytes)
+    }
+
+    fn read(r: &mut Reader) -> Option<SpdmKeyPairAsymAlgo> {
+        let bits = u32::read(r)?;
+
+        SpdmKeyPairAsymAlgo::from_bits(bits & SpdmKeyPairAsymAlgo::VALID_MASK.bits)
+    }
+}
+
+impl SpdmKeyPairAsymAlgo {
+    /// return true if no more than one is selected
+    /// return false if two or more is selected
+    pub fn is_no_more_than_one_selected(&self) -> bool {
+        self.bits() == 0 || self.bits() & (self.bits() - 1) == 0
+    }
+}
+
+enum_builder! {
+    @U8
+    EnumName: SpdmSetKeyPairInfoOperation;
+    EnumVal{
+        SpdmSetKeyPairInfoOperationChange => 0x0,
+        SpdmSetKeyPairInfoOperationErase => 0x1,
+        SpdmSetKeyPairInfoOperationGenerate => 0x2
+    }
+}
+impl Default for SpdmSetKeyPairInfoOperation {
+    fn default() -> SpdmSetKeyPairInfoOperation {
+        SpdmSetKeyPairInfoOperation::SpdmSetKeyPairInfoOperationChange
+    }
+}
+
+/// The state of one key pair, as reported by KEY_PAIR_INFO.
+///
+/// The certificate slots associated with the key pair are not part of it,
+/// they are kept in SpdmProvisionInfo::my_key_pair_id.
+#[derive(Debug, Clone, Default)]
+pub struct SpdmKeyPairInfo {
+    pub total_key_pairs: u8,
+    pub capabilities: SpdmKeyPairCapabilities,
+    pub key_usage_capabilities: SpdmKeyUsageMask,
+    pub current_key_usage: SpdmKeyUsageMask,
+    pub asym_algo_capabilities: SpdmKeyPairAsymAlgo,
+    pub current_asym_algo: SpdmKeyPairAsymAlgo,
+    pub public_key_info: SpdmPublicKeyData,
+}
@@ -9,9 +9,11 @@ use core::convert::TryInto;
 
 mod algo;
 mod capability;
+mod key_pair;
 mod version;
 pub use algo::*;
 pub use capability::*;
+pub use key_pair::*;
 pub use version::*;
 
 // util function
@@ -21,7 +21,7 @@ impl<'a> RequesterContext<'a> {
     ) -> SpdmResult {
         info!("send spdm challenge\n");
 
-        if slot_id >= SPDM_MAX_SLOT_NUMBER as u8 {
+        if slot_id >= SPDM_MAX_SLOT_NUMBER as u8 && !self.is_rsp_public_key_slot_id(slot_id) {
             return Err(SPDM_STATUS_INVALID_PARAMETER);
         }
 
@@ -105,8 +105,7 @@ impl<'a> RequesterContext<'a> {
                             debug!("!!! challenge_auth : {:02x?}\n", challenge_auth);
 
                             // verify signature
-                            let base_asym_size =
-                                self.common.negotiate_info.base_asym_sel.get_size() as usize;
+                            let base_asym_size = self.common.get_asym_key_size() as usize;
                             let temp_used = used - base_asym_size;
 
                             self.common.append_message_c(send_buffer)?;
@@ -126,19 +125,35 @@ impl<'a> RequesterContext<'a> {
                                 info!("verify_challenge_auth_signature pass");
                             }
 
+                            #[cfg(feature = "mut-auth")]
+                            if challenge_auth
+                                .challenge_auth_attribute
+                                .contains(SpdmChallengeAuthAttribute::BASIC_MUT_AUTH_REQ)
+                            {
+                                self.basic_mutual_authenticate()?;
+                            }
+
                             Ok(())
                         } else {
                             error!("!!! challenge_auth : fail !!!\n");
                             Err(SPDM_STATUS_INVALID_MSG_FIELD)
                         }
                     }
-                    SpdmRequestResponseCode::SpdmResponseError => self
-                        .spdm_handle_error_response_main(
-                            Some(session_id),
+                    SpdmRequestResponseCode::SpdmResponseError => {
+                        let received = self.spdm_handle_error_response_respond_if_ready(
+                            None,
                             receive_buffer,
                             SpdmRequestResponseCode::SpdmRequestChallenge,
                             SpdmRequestResponseCode::SpdmResponseChallengeAuth,
-                        ),
+                        )?;
+                        self.handle_spdm_challenge_response(
+                            session_id,
+                            slot_id,
+                            measurement_summary_hash_type,
+                            send_buffer,
+                            &received.receive_buffer[..received.used],
+                        )
+                    }
                     _ => Err(SPDM_STATUS_ERROR_PEER),
                 }
             }
@@ -163,20 +178,15 @@ impl<'a> RequesterContext<'a> {
         .ok_or(SPDM_STATUS_CRYPTO_ERROR)?;
         debug!("message_m1m2_hash - {:02x?}", message_m1m2_hash.as_ref());
 
-        if self.common.peer_info.peer_cert_chain[slot_id as usize].is_none() {
+        if self
+            .common
+            .get_peer_cert_chain_or_public_key(slot_id)
+            .is_none()
+        {
             error!("peer_cert_chain is not populated!\n");
             return Err(SPDM_STATUS_INVALID_PARAMETER);
         }
 
-        let cert_chain_data = &self.common.peer_info.peer_cert_chain[slot_id as usize]
-            .as_ref()
-            .ok_or(SPDM_STATUS_INVALID_PARAMETER)?
-            .data[(4usize + self.common.negotiate_info.base_hash_sel.get_size() as usize)
-            ..(self.common.peer_info.peer_cert_chain[slot_id as usize]
-                .as_ref()
-                .ok_or(SPDM_STATUS_INVALID_PARAMETER)?
-                .data_size as usize)];
-
         let mut message_sign = ManagedBuffer12Sign::default();
 
         if self.common.negotiate_info.spdm_version_sel.get_u8()
@@ -184,7 +194,9 @@ impl<'a> RequesterContext<'a> {
         {
             message_sign.reset_message();
             message_sign
-                .append_message(&SPDM_VERSION_1_2_SIGNING_PREFIX_CONTEXT)
+                .append_message(spdm_signing_prefix_context(
+                    self.common.negotiate_info.spdm_version_sel,
+                ))
                 .ok_or(SPDM_STATUS_BUFFER_FULL)?;
             message_sign
                 .append_message(&SPDM_VERSION_1_2_SIGNING_CONTEXT_ZEROPAD_4)
@@ -200,10 +212,9 @@ impl<'a> RequesterContext<'a> {
             return Err(SPDM_STATUS_INVALID_STATE_LOCAL);
         }
 
-        crypto::asym_verify::verify(
-            self.common.negotiate_info.base_hash_sel,
-            self.common.negotiate_info.base_asym_sel,
-            cert_chain_data,
+        self.common.verify_peer_signature(
+            slot_id,
+            &SPDM_CHALLENGE_AUTH_SIGN_CONTEXT,
             message_sign.as_ref(),
             signature,
         )
@@ -235,26 +246,23 @@ impl<'a> RequesterContext<'a> {
         .ok_or(SPDM_STATUS_CRYPTO_ERROR)?;
         debug!("message_m1m2_hash - {:02x?}", message_m1m2_hash.as_ref());
 
-        if self.common.peer_info.peer_cert_chain[slot_id as usize].is_none() {
+        if self
+            .common
+            .get_peer_cert_chain_or_public_key(slot_id)
+            .is_none()
+        {
             error!("peer_cert_chain is not populated!\n");
             return Err(SPDM_STATUS_INVALID_PARAMETER);
         }
 
-        let cert_chain_data = &self.common.peer_info.peer_cert_chain[slot_id as usize]
-            .as_ref()
-            .ok_or(SPDM_STATUS_INVALID_PARAMETER)?
-            .data[(4usize + self.common.negotiate_info.base_hash_sel.get_size() as usize)
-            ..(self.common.peer_info.peer_cert_chain[slot_id as usize]
-                .as_ref()
-                .ok_or(SPDM_STATUS_INVALID_PARAMETER)?
-                .data_size as usize)];
-
         if self.common.negotiate_info.spdm_version_sel.get_u8()
             >= SpdmVersion::SpdmVersion12.get_u8()
         {
             message_m1m2.reset_message();
             message_m1m2
-                .append_message(&SPDM_VERSION_1_2_SIGNING_PREFIX_CONTEXT)
+                .append_message(spdm_signing_prefix_context(
+                    self.common.negotiate_info.spdm_version_sel,
+                ))
                 .ok_or(SPDM_STATUS_BUFFER_FULL)?;
             message_m1m2
                 .append_message(&SPDM_VERSION_1_2_SIGNING_CONTEXT_ZEROPAD_4)
@@ -267,10 +275,9 @@ impl<'a> RequesterContext<'a> {
                 .ok_or(SPDM_STATUS_BUFFER_FULL)?;
         }
 
-        crypto::asym_verify::verify(
-            self.common.negotiate_info.base_hash_sel,
-            self.common.negotiate_info.base_asym_sel,
-            cert_chain_data,
+        self.common.verify_peer_signature(
+            slot_id,
+            &SPDM_CHALLENGE_AUTH_SIGN_CONTEXT,
             message_m1m2.as_ref(),
             signature,
         )
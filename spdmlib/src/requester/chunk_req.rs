@@ -0,0 +1,305 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use crate::error::{
+    SpdmResult, SPDM_STATUS_ERROR_PEER, SPDM_STATUS_INVALID_MSG_FIELD,
+    SPDM_STATUS_INVALID_PARAMETER, SPDM_STATUS_SEND_FAIL,
+};
+use crate::message::*;
+use crate::requester::*;
+
+impl<'a> RequesterContext<'a> {
+    pub(crate) fn send_spdm_chunk_send(
+        &mut self,
+        session_id: Option<u32>,
+        large_request: &[u8],
+    ) -> SpdmResult {
+        info!("send spdm chunk send\n");
+
+        if self.common.negotiate_info.rsp_max_spdm_msg_size_sel != 0
+            && large_request.len() > self.common.negotiate_info.rsp_max_spdm_msg_size_sel as usize
+        {
+            return Err(SPDM_STATUS_SEND_FAIL);
+        }
+        let data_transfer_size = self.common.negotiate_info.rsp_data_transfer_size_sel as usize;
+
+        self.common.reset_chunk_context();
+        self.common.chunk_context.chunk_handle =
+            self.common.chunk_context.chunk_handle.wrapping_add(1);
+        let handle = self.common.chunk_context.chunk_handle;
+
+        let mut chunk_seq_num = 0u16;
+        let mut offset = 0usize;
+        loop {
+            let header_size = if chunk_seq_num == 0 {
+                SPDM_CHUNK_HEADER_SIZE + SPDM_CHUNK_LARGE_MESSAGE_SIZE_FIELD_SIZE
+            } else {
+                SPDM_CHUNK_HEADER_SIZE
+            };
+            if data_transfer_size <= header_size {
+                return Err(SPDM_STATUS_INVALID_PARAMETER);
+            }
+            let chunk_size = (data_transfer_size - header_size)
+                .min(MAX_SPDM_CHUNK_SIZE)
+                .min(large_request.len() - offset);
+            let last_chunk = offset + chunk_size == large_request.len();
+
+            let mut send_buffer = [0u8; config::MAX_SPDM_MSG_SIZE];
+            let send_used = self.encode_spdm_chunk_send(
+                handle,
+                chunk_seq_num,
+                last_chunk,
+                large_request.len(),
+                &large_request[offset..(offset + chunk_size)],
+                &mut send_buffer,
+            )?;
+            self.send_single_message(session_id, &send_buffer[..send_used])?;
+
+            // The last chunk completes the large request, so the responder
+            // may take as long as for the large request itself.
+            let mut receive_buffer = [0u8; config::MAX_SPDM_MSG_SIZE];
+            let used = self.receive_single_message(session_id, &mut receive_buffer, last_chunk)?;
+
+            if self.handle_spdm_chunk_send_ack_response(
+                handle,
+                chunk_seq_num,
+                last_chunk,
+                &receive_buffer[..used],
+            )? {
+                return Ok(());
+            }
+
+            offset += chunk_size;
+            chunk_seq_num = chunk_seq_num
+                .checked_add(1)
+                .ok_or(SPDM_STATUS_INVALID_PARAMETER)?;
+        }
+    }
+
+    pub fn encode_spdm_chunk_send(
+        &mut self,
+        handle: u8,
+        chunk_seq_num: u16,
+        last_chunk: bool,
+        large_message_size: usize,
+        chunk: &[u8],
+        buf: &mut [u8],
+    ) -> SpdmResult<usize> {
+        if chunk.len() > MAX_SPDM_CHUNK_SIZE {
+            return Err(SPDM_STATUS_INVALID_PARAMETER);
+        }
+        let mut payload = SpdmChunkSendRequestPayload {
+            request_attributes: if last_chunk {
+                SpdmChunkSenderAttributes::LAST_CHUNK
+            } else {
+                SpdmChunkSenderAttributes::empty()
+            },
+            handle,
+            chunk_seq_num,
+            chunk_size: chunk.len() as u32,
+            large_message_size: large_message_size as u32,
+            ..Default::default()
+        };
+        payload.chunk[..chunk.len()].copy_from_slice(chunk);
+
+        let mut writer = Writer::init(buf);
+        let request = SpdmMessage {
+            header: SpdmMessageHeader {
+                version: self.common.negotiate_info.spdm_version_sel,
+                request_response_code: SpdmRequestResponseCode::SpdmRequestChunkSend,
+            },
+            payload: SpdmMessagePayload::SpdmChunkSendRequest(payload),
+        };
+        request.spdm_encode(&mut self.common, &mut writer)
+    }
+
+    // Returns true when the response to the large request has been received.
+    // It is kept in the chunk context and handed out by the next receive.
+    pub fn handle_spdm_chunk_send_ack_response(
+        &mut self,
+        handle: u8,
+        chunk_seq_num: u16,
+        last_chunk: bool,
+        receive_buffer: &[u8],
+    ) -> SpdmResult<bool> {
+        let mut reader = Reader::init(receive_buffer);
+        match SpdmMessageHeader::read(&mut reader) {
+            Some(message_header) => {
+                if message_header.version != self.common.negotiate_info.spdm_version_sel {
+                    return Err(SPDM_STATUS_INVALID_MSG_FIELD);
+                }
+                match message_header.request_response_code {
+                    SpdmRequestResponseCode::SpdmResponseChunkSendAck => {
+                        let chunk_send_ack = SpdmChunkSendAckResponsePayload::spdm_read(
+                            &mut self.common,
+                            &mut reader,
+                        )
+                        .ok_or(SPDM_STATUS_INVALID_MSG_FIELD)?;
+                        debug!(
+                            "!!! chunk send ack : handle {:02x}, seq {}\n",
+                            chunk_send_ack.handle, chunk_send_ack.chunk_seq_num
+                        );
+
+                        if chunk_send_ack.handle != handle
+                            || chunk_send_ack.chunk_seq_num != chunk_seq_num
+                        {
+                            return Err(SPDM_STATUS_INVALID_MSG_FIELD);
+                        }
+
+                        let early_error = chunk_send_ack
+                            .response_attributes
+                            .contains(SpdmChunkReceiverAttributes::EARLY_ERROR_DETECTED);
+                        let response_size = chunk_send_ack.response_to_large_request_size as usize;
+                        if !early_error && !last_chunk {
+                            // Trailing bytes, if any, are transport padding.
+                            return Ok(false);
+                        }
+                        if response_size == 0 {
+                            return Err(SPDM_STATUS_INVALID_MSG_FIELD);
+                        }
+
+                        self.save_spdm_chunk_pending_response(
+                            &chunk_send_ack.response_to_large_request[..response_size],
+                        );
+                        Ok(true)
+                    }
+                    SpdmRequestResponseCode::SpdmResponseError => {
+                        // Let the handler of the large request deal with it.
+                        self.save_spdm_chunk_pending_response(receive_buffer);
+                        Ok(true)
+                    }
+                    _ => Err(SPDM_STATUS_ERROR_PEER),
+                }
+            }
+            None => Err(SPDM_STATUS_INVALID_MSG_FIELD),
+        }
+    }
+
+    fn save_spdm_chunk_pending_response(&mut self, response: &[u8]) {
+        let chunk_context = &mut self.common.chunk_context;
+        chunk_context.chunk_message_data[..response.len()].copy_from_slice(response);
+        chunk_context.chunk_message_size = response.len();
+        chunk_context.chunk_status = SpdmChunkStatus::SpdmChunkResponsePending;
+    }
+
+    // If the received message is ERROR(LargeResponse), retrieve the large
+    // response via CHUNK_GET and reassemble it into receive_buffer.
+    pub(crate) fn receive_spdm_large_response(
+        &mut self,
+        session_id: Option<u32>,
+        receive_buffer: &mut [u8],
+        used: usize,
+    ) -> SpdmResult<usize> {
+        if !self.common.is_chunk_supported() {
+            return Ok(used);
+        }
+
+        let mut reader = Reader::init(&receive_buffer[..used]);
+        let handle = match SpdmMessageHeader::read(&mut reader) {
+            Some(message_header)
+                if message_header.request_response_code
+                    == SpdmRequestResponseCode::SpdmResponseError =>
+            {
+                match SpdmMessageGeneralPayload::read(&mut reader) {
+                    Some(payload)
+                        if payload.param1 == SpdmErrorCode::SpdmErrorLargeResponse.get_u8() =>
+                    {
+                        payload.param2
+                    }
+                    _ => return Ok(used),
+                }
+            }
+            _ => return Ok(used),
+        };
+
+        info!("receive spdm large response via chunk get\n");
+
+        let max_size = receive_buffer
+            .len()
+            .min(self.common.config_info.max_spdm_msg_size as usize);
+        let mut large_response_size = 0usize;
+        let mut offset = 0usize;
+        let mut chunk_seq_num = 0u16;
+        loop {
+            let mut send_buffer = [0u8; config::MAX_SPDM_MSG_SIZE];
+            let send_used = self.encode_spdm_chunk_get(handle, chunk_seq_num, &mut send_buffer)?;
+            self.send_single_message(session_id, &send_buffer[..send_used])?;
+
+            let mut chunk_buffer = [0u8; config::MAX_SPDM_MSG_SIZE];
+            let chunk_used = self.receive_single_message(session_id, &mut chunk_buffer, false)?;
+
+            let mut reader = Reader::init(&chunk_buffer[..chunk_used]);
+            let message_header =
+                SpdmMessageHeader::read(&mut reader).ok_or(SPDM_STATUS_INVALID_MSG_FIELD)?;
+            if message_header.version != self.common.negotiate_info.spdm_version_sel {
+                return Err(SPDM_STATUS_INVALID_MSG_FIELD);
+            }
+            if message_header.request_response_code
+                != SpdmRequestResponseCode::SpdmResponseChunkResponse
+            {
+                return Err(SPDM_STATUS_ERROR_PEER);
+            }
+            let chunk_response = SpdmChunkResponsePayload::spdm_read(&mut self.common, &mut reader)
+                .ok_or(SPDM_STATUS_INVALID_MSG_FIELD)?;
+            debug!(
+                "!!! chunk response : handle {:02x}, seq {}, size {}\n",
+                chunk_response.handle, chunk_response.chunk_seq_num, chunk_response.chunk_size
+            );
+
+            if chunk_response.handle != handle || chunk_response.chunk_seq_num != chunk_seq_num {
+                return Err(SPDM_STATUS_INVALID_MSG_FIELD);
+            }
+            if chunk_seq_num == 0 {
+                large_response_size = chunk_response.large_message_size as usize;
+                if large_response_size > max_size {
+                    return Err(SPDM_STATUS_INVALID_MSG_FIELD);
+                }
+            }
+            let chunk_size = chunk_response.chunk_size as usize;
+            if chunk_size == 0 || chunk_size > large_response_size - offset {
+                return Err(SPDM_STATUS_INVALID_MSG_FIELD);
+            }
+            receive_buffer[offset..(offset + chunk_size)]
+                .copy_from_slice(&chunk_response.chunk[..chunk_size]);
+            offset += chunk_size;
+
+            if chunk_response
+                .response_attributes
+                .contains(SpdmChunkSenderAttributes::LAST_CHUNK)
+            {
+                if offset != large_response_size {
+                    return Err(SPDM_STATUS_INVALID_MSG_FIELD);
+                }
+                return Ok(large_response_size);
+            }
+            if offset == large_response_size {
+                return Err(SPDM_STATUS_INVALID_MSG_FIELD);
+            }
+
+            chunk_seq_num = chunk_seq_num
+                .checked_add(1)
+                .ok_or(SPDM_STATUS_INVALID_MSG_FIELD)?;
+        }
+    }
+
+    pub fn encode_spdm_chunk_get(
+        &mut self,
+        handle: u8,
+        chunk_seq_num: u16,
+        buf: &mut [u8],
+    ) -> SpdmResult<usize> {
+        let mut writer = Writer::init(buf);
+        let request = SpdmMessage {
+            header: SpdmMessageHeader {
+                version: self.common.negotiate_info.spdm_version_sel,
+                request_response_code: SpdmRequestResponseCode::SpdmRequestChunkGet,
+            },
+            payload: SpdmMessagePayload::SpdmChunkGetRequest(SpdmChunkGetRequestPayload {
+                handle,
+                chunk_seq_num,
+            }),
+        };
+        request.spdm_encode(&mut self.common, &mut writer)
+    }
+}
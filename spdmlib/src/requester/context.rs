@@ -3,7 +3,7 @@
 // SPDX-License-Identifier: BSD-2-Clause-Patent
 
 use crate::common::ST1;
-use crate::common::{self, SpdmDeviceIo, SpdmTransportEncap};
+use crate::common::{self, SpdmChunkStatus, SpdmDeviceIo, SpdmTransportEncap};
 use crate::config;
 use crate::error::{SpdmResult, SPDM_STATUS_RECEIVE_FAIL, SPDM_STATUS_SEND_FAIL};
 use crate::protocol::*;
@@ -29,12 +29,50 @@ impl<'a> RequesterContext<'a> {
         }
     }
 
+    // SlotID SPDM_PUBLIC_KEY_SLOT_ID selects the responder public key provisioned
+    // to the requester, if the responder supports PUB_KEY_ID_CAP.
+    pub(crate) fn is_rsp_public_key_slot_id(&self, slot_id: u8) -> bool {
+        slot_id == SPDM_PUBLIC_KEY_SLOT_ID
+            && self
+                .common
+                .negotiate_info
+                .rsp_capabilities_sel
+                .contains(SpdmResponseCapabilityFlags::PUB_KEY_ID_CAP)
+            && self.common.provision_info.peer_public_key.is_some()
+    }
+
     pub fn init_connection(&mut self) -> SpdmResult {
         self.send_receive_spdm_version()?;
         self.send_receive_spdm_capability()?;
         self.send_receive_spdm_algorithm()
     }
 
+    /// Resume the connection from a negotiated state cached before a reset.
+    /// GET_VERSION, GET_CAPABILITIES and NEGOTIATE_ALGORITHMS are sent again
+    /// if there is no cached state or if it does not match the local
+    /// configuration. If the responder did not keep its state, it answers the
+    /// next request with ERROR(RequestResynch) and init_connection shall be
+    /// called.
+    pub fn init_connection_with_cache(
+        &mut self,
+        cached_state: Option<&common::SpdmCachedNegotiateState>,
+    ) -> SpdmResult {
+        if let Some(cached_state) = cached_state {
+            if cached_state.negotiate_info.req_capabilities_sel
+                == self.common.config_info.req_capabilities
+                && self
+                    .common
+                    .restore_cached_negotiate_state(cached_state)
+                    .is_ok()
+            {
+                info!("connection resumed from cached state\n");
+                return Ok(());
+            }
+            info!("cached state mismatch, negotiate again\n");
+        }
+        self.init_connection()
+    }
+
     pub fn start_session(
         &mut self,
         use_psk: bool,
@@ -68,11 +106,12 @@ impl<'a> RequesterContext<'a> {
         if self.common.negotiate_info.rsp_data_transfer_size_sel != 0
             && send_buffer.len() > self.common.negotiate_info.rsp_data_transfer_size_sel as usize
         {
-            return Err(SPDM_STATUS_SEND_FAIL);
+            if !self.common.is_chunk_supported() {
+                return Err(SPDM_STATUS_SEND_FAIL);
+            }
+            return self.send_spdm_chunk_send(None, send_buffer);
         }
-        let mut transport_buffer = [0u8; config::SENDER_BUFFER_SIZE];
-        let used = self.common.encap(send_buffer, &mut transport_buffer)?;
-        self.common.device_io.send(&transport_buffer[..used])
+        self.send_single_message(None, send_buffer)
     }
 
     pub fn send_secured_message(
@@ -81,21 +120,26 @@ impl<'a> RequesterContext<'a> {
         send_buffer: &[u8],
         is_app_message: bool,
     ) -> SpdmResult {
-        if !is_app_message
-            && self.common.negotiate_info.rsp_data_transfer_size_sel != 0
+        if is_app_message {
+            let mut transport_buffer = [0u8; config::SENDER_BUFFER_SIZE];
+            let used = self.common.encode_secured_message(
+                session_id,
+                send_buffer,
+                &mut transport_buffer,
+                true,
+                true,
+            )?;
+            return self.common.device_io.send(&transport_buffer[..used]);
+        }
+        if self.common.negotiate_info.rsp_data_transfer_size_sel != 0
             && (send_buffer.len() > self.common.negotiate_info.rsp_data_transfer_size_sel as usize)
         {
-            return Err(SPDM_STATUS_SEND_FAIL);
+            if !self.common.is_chunk_supported() {
+                return Err(SPDM_STATUS_SEND_FAIL);
+            }
+            return self.send_spdm_chunk_send(Some(session_id), send_buffer);
         }
-        let mut transport_buffer = [0u8; config::SENDER_BUFFER_SIZE];
-        let used = self.common.encode_secured_message(
-            session_id,
-            send_buffer,
-            &mut transport_buffer,
-            true,
-            is_app_message,
-        )?;
-        self.common.device_io.send(&transport_buffer[..used])
+        self.send_single_message(Some(session_id), send_buffer)
     }
 
     pub fn receive_message(
@@ -105,20 +149,8 @@ impl<'a> RequesterContext<'a> {
     ) -> SpdmResult<usize> {
         info!("receive_message!\n");
 
-        let timeout: usize = if crypto_request {
-            2 << self.common.negotiate_info.rsp_ct_exponent_sel
-        } else {
-            ST1
-        };
-
-        let mut transport_buffer = [0u8; config::RECEIVER_BUFFER_SIZE];
-        let used = self
-            .common
-            .device_io
-            .receive(&mut transport_buffer, timeout)
-            .map_err(|_| SPDM_STATUS_RECEIVE_FAIL)?;
-
-        self.common.decap(&transport_buffer[..used], receive_buffer)
+        let used = self.receive_single_message(None, receive_buffer, crypto_request)?;
+        self.receive_spdm_large_response(None, receive_buffer, used)
     }
 
     pub fn receive_secured_message(
@@ -129,6 +161,49 @@ impl<'a> RequesterContext<'a> {
     ) -> SpdmResult<usize> {
         info!("receive_secured_message!\n");
 
+        let used = self.receive_single_message(Some(session_id), receive_buffer, crypto_request)?;
+        self.receive_spdm_large_response(Some(session_id), receive_buffer, used)
+    }
+
+    // Send one SPDM message which fits into the DataTransferSize of the responder.
+    pub(crate) fn send_single_message(
+        &mut self,
+        session_id: Option<u32>,
+        send_buffer: &[u8],
+    ) -> SpdmResult {
+        let mut transport_buffer = [0u8; config::SENDER_BUFFER_SIZE];
+        let used = match session_id {
+            Some(session_id) => self.common.encode_secured_message(
+                session_id,
+                send_buffer,
+                &mut transport_buffer,
+                true,
+                false,
+            )?,
+            None => self.common.encap(send_buffer, &mut transport_buffer)?,
+        };
+        self.common.device_io.send(&transport_buffer[..used])
+    }
+
+    // Receive one SPDM message from the transport, or pick up the response
+    // already carried by the last CHUNK_SEND_ACK.
+    pub(crate) fn receive_single_message(
+        &mut self,
+        session_id: Option<u32>,
+        receive_buffer: &mut [u8],
+        crypto_request: bool,
+    ) -> SpdmResult<usize> {
+        if self.common.chunk_context.chunk_status == SpdmChunkStatus::SpdmChunkResponsePending {
+            let used = self.common.chunk_context.chunk_message_size;
+            self.common.reset_chunk_context();
+            if used > receive_buffer.len() {
+                return Err(SPDM_STATUS_RECEIVE_FAIL);
+            }
+            receive_buffer[..used]
+                .copy_from_slice(&self.common.chunk_context.chunk_message_data[..used]);
+            return Ok(used);
+        }
+
         let timeout: usize = if crypto_request {
             2 << self.common.negotiate_info.rsp_ct_exponent_sel
         } else {
@@ -136,14 +211,19 @@ impl<'a> RequesterContext<'a> {
         };
 
         let mut transport_buffer = [0u8; config::RECEIVER_BUFFER_SIZE];
-
         let used = self
             .common
             .device_io
             .receive(&mut transport_buffer, timeout)
             .map_err(|_| SPDM_STATUS_RECEIVE_FAIL)?;
 
-        self.common
-            .decode_secured_message(session_id, &transport_buffer[..used], receive_buffer)
+        match session_id {
+            Some(session_id) => self.common.decode_secured_message(
+                session_id,
+                &transport_buffer[..used],
+                receive_buffer,
+            ),
+            None => self.common.decap(&transport_buffer[..used], receive_buffer),
+        }
     }
 }
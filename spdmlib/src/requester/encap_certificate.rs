@@ -134,7 +134,26 @@ impl<'a> RequesterContext<'a> {
                 cert_chain,
             }),
         };
-        let _ = response.spdm_encode(&mut self.common, encap_response);
+        let start = encap_response.used();
+        if response
+            .spdm_encode(&mut self.common, encap_response)
+            .is_err()
+            || self
+                .common
+                .append_message_mut_b(&encap_request[..reader.used()])
+                .is_err()
+            || self
+                .common
+                .append_message_mut_b(&encap_response.used_slice()[start..])
+                .is_err()
+        {
+            self.encode_encap_error_response(
+                SpdmErrorCode::SpdmErrorUnspecified,
+                0,
+                encap_response,
+            );
+            return;
+        }
 
         debug!("!!! encap get_certificate : complete\n");
     }
@@ -0,0 +1,311 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use codec::{Codec, Reader, Writer};
+
+use crate::common::opaque::SpdmOpaqueStruct;
+#[cfg(feature = "hashed-transcript-data")]
+use crate::common::ManagedBuffer12Sign;
+#[cfg(not(feature = "hashed-transcript-data"))]
+use crate::common::ManagedBufferM1M2;
+use crate::common::SpdmCodec;
+use crate::crypto;
+#[cfg(feature = "hashed-transcript-data")]
+use crate::error::SPDM_STATUS_INVALID_STATE_LOCAL;
+use crate::error::{SpdmResult, SPDM_STATUS_BUFFER_FULL, SPDM_STATUS_CRYPTO_ERROR};
+use crate::message::*;
+use crate::protocol::*;
+
+use super::RequesterContext;
+
+impl<'a> RequesterContext<'a> {
+    pub fn encap_handle_challenge(&mut self, encap_request: &[u8], encap_response: &mut Writer) {
+        let mut reader = Reader::init(encap_request);
+
+        if !self
+            .common
+            .negotiate_info
+            .req_capabilities_sel
+            .contains(SpdmRequestCapabilityFlags::CHAL_CAP)
+        {
+            self.encode_encap_error_response(
+                SpdmErrorCode::SpdmErrorUnsupportedRequest,
+                0,
+                encap_response,
+            );
+            return;
+        }
+
+        if let Some(message_header) = SpdmMessageHeader::read(&mut reader) {
+            if message_header.version != self.common.negotiate_info.spdm_version_sel {
+                self.encode_encap_error_response(
+                    SpdmErrorCode::SpdmErrorVersionMismatch,
+                    0,
+                    encap_response,
+                );
+                return;
+            }
+        } else {
+            self.encode_encap_error_response(
+                SpdmErrorCode::SpdmErrorInvalidRequest,
+                0,
+                encap_response,
+            );
+            return;
+        }
+
+        let challenge = if let Some(challenge) =
+            SpdmChallengeRequestPayload::spdm_read(&mut self.common, &mut reader)
+        {
+            debug!("!!! encap challenge : {:02x?}\n", challenge);
+            challenge
+        } else {
+            error!("!!! encap challenge : fail !!!\n");
+            self.encode_encap_error_response(
+                SpdmErrorCode::SpdmErrorInvalidRequest,
+                0,
+                encap_response,
+            );
+            return;
+        };
+
+        // The requester has no measurement to summarize.
+        let slot_id = challenge.slot_id;
+        if slot_id >= SPDM_MAX_SLOT_NUMBER as u8
+            || challenge.measurement_summary_hash_type
+                != SpdmMeasurementSummaryHashType::SpdmMeasurementSummaryHashTypeNone
+        {
+            self.encode_encap_error_response(
+                SpdmErrorCode::SpdmErrorInvalidRequest,
+                0,
+                encap_response,
+            );
+            return;
+        }
+        let cert_chain_hash =
+            if let Some(my_cert_chain) = self.common.get_my_cert_chain_or_public_key(slot_id) {
+                crypto::hash::hash_all(self.common.negotiate_info.base_hash_sel, my_cert_chain)
+            } else {
+                self.encode_encap_error_response(
+                    SpdmErrorCode::SpdmErrorInvalidRequest,
+                    0,
+                    encap_response,
+                );
+                return;
+            };
+        let cert_chain_hash = if let Some(cert_chain_hash) = cert_chain_hash {
+            cert_chain_hash
+        } else {
+            self.encode_encap_error_response(
+                SpdmErrorCode::SpdmErrorUnspecified,
+                0,
+                encap_response,
+            );
+            return;
+        };
+
+        if self
+            .common
+            .append_message_mut_c(&encap_request[..reader.used()])
+            .is_err()
+        {
+            self.encode_encap_error_response(
+                SpdmErrorCode::SpdmErrorUnspecified,
+                0,
+                encap_response,
+            );
+            return;
+        }
+
+        let mut nonce = [0u8; SPDM_NONCE_SIZE];
+        if crypto::rand::get_random(&mut nonce).is_err() {
+            self.encode_encap_error_response(
+                SpdmErrorCode::SpdmErrorUnspecified,
+                0,
+                encap_response,
+            );
+            return;
+        }
+
+        self.common.runtime_info.need_measurement_summary_hash = false;
+        let response = SpdmMessage {
+            header: SpdmMessageHeader {
+                version: self.common.negotiate_info.spdm_version_sel,
+                request_response_code: SpdmRequestResponseCode::SpdmResponseChallengeAuth,
+            },
+            payload: SpdmMessagePayload::SpdmChallengeAuthResponse(
+                SpdmChallengeAuthResponsePayload {
+                    slot_id,
+                    slot_mask: 1 << slot_id,
+                    challenge_auth_attribute: SpdmChallengeAuthAttribute::empty(),
+                    cert_chain_hash,
+                    nonce: SpdmNonceStruct { data: nonce },
+                    measurement_summary_hash: SpdmDigestStruct::default(),
+                    opaque: SpdmOpaqueStruct::default(),
+                    signature: SpdmSignatureStruct {
+                        data_size: self.common.negotiate_info.base_asym_sel.get_size(),
+                        data: [0xbb; SPDM_MAX_ASYM_KEY_SIZE],
+                    },
+                },
+            ),
+        };
+
+        let start = encap_response.used();
+        if response
+            .spdm_encode(&mut self.common, encap_response)
+            .is_err()
+        {
+            self.encode_encap_error_response(
+                SpdmErrorCode::SpdmErrorUnspecified,
+                0,
+                encap_response,
+            );
+            return;
+        }
+        let used = encap_response.used();
+
+        // generate signature
+        let base_asym_size = self.common.negotiate_info.base_asym_sel.get_size() as usize;
+        let temp_used = used - base_asym_size;
+        if self
+            .common
+            .append_message_mut_c(&encap_response.used_slice()[start..temp_used])
+            .is_err()
+        {
+            self.encode_encap_error_response(
+                SpdmErrorCode::SpdmErrorUnspecified,
+                0,
+                encap_response,
+            );
+            return;
+        }
+
+        let signature = self.generate_encap_challenge_auth_signature(slot_id);
+        self.common.reset_message_mut_b();
+        self.common.reset_message_mut_c();
+        let signature = if let Ok(signature) = signature {
+            signature
+        } else {
+            self.encode_encap_error_response(
+                SpdmErrorCode::SpdmErrorUnspecified,
+                0,
+                encap_response,
+            );
+            return;
+        };
+        // patch the message before send
+        encap_response.mut_used_slice()[temp_used..used].copy_from_slice(signature.as_ref());
+
+        debug!("!!! encap challenge : complete\n");
+    }
+
+    #[cfg(feature = "hashed-transcript-data")]
+    pub fn generate_encap_challenge_auth_signature(
+        &self,
+        slot_id: u8,
+    ) -> SpdmResult<SpdmSignatureStruct> {
+        let message_mut_m1m2_hash = crypto::hash::hash_ctx_finalize(
+            self.common
+                .runtime_info
+                .digest_context_mut_m1m2
+                .as_ref()
+                .cloned()
+                .ok_or(SPDM_STATUS_CRYPTO_ERROR)?,
+        )
+        .ok_or(SPDM_STATUS_CRYPTO_ERROR)?;
+        debug!(
+            "message_mut_m1m2_hash - {:02x?}",
+            message_mut_m1m2_hash.as_ref()
+        );
+
+        let mut message_sign = ManagedBuffer12Sign::default();
+        if self.common.negotiate_info.spdm_version_sel.get_u8()
+            >= SpdmVersion::SpdmVersion12.get_u8()
+        {
+            message_sign
+                .append_message(spdm_signing_prefix_context(
+                    self.common.negotiate_info.spdm_version_sel,
+                ))
+                .ok_or(SPDM_STATUS_BUFFER_FULL)?;
+            message_sign
+                .append_message(&SPDM_VERSION_1_2_SIGNING_CONTEXT_ZEROPAD_4)
+                .ok_or(SPDM_STATUS_BUFFER_FULL)?;
+            message_sign
+                .append_message(&SPDM_MUT_CHALLENGE_AUTH_SIGN_CONTEXT)
+                .ok_or(SPDM_STATUS_BUFFER_FULL)?;
+            message_sign
+                .append_message(message_mut_m1m2_hash.as_ref())
+                .ok_or(SPDM_STATUS_BUFFER_FULL)?;
+        } else {
+            error!("hashed-transcript-data is unsupported in SPDM 1.0/1.1 signing!\n");
+            return Err(SPDM_STATUS_INVALID_STATE_LOCAL);
+        }
+
+        self.common
+            .sign_with_base_asym(
+                self.common.get_my_key_pair_id(slot_id),
+                &SPDM_MUT_CHALLENGE_AUTH_SIGN_CONTEXT,
+                message_sign.as_ref(),
+            )
+            .ready()
+            .ok_or(SPDM_STATUS_CRYPTO_ERROR)
+    }
+
+    #[cfg(not(feature = "hashed-transcript-data"))]
+    pub fn generate_encap_challenge_auth_signature(
+        &self,
+        slot_id: u8,
+    ) -> SpdmResult<SpdmSignatureStruct> {
+        let mut message_mut_m1m2 = ManagedBufferM1M2::default();
+        message_mut_m1m2
+            .append_message(self.common.runtime_info.message_a.as_ref())
+            .ok_or(SPDM_STATUS_BUFFER_FULL)?;
+        message_mut_m1m2
+            .append_message(self.common.runtime_info.message_mut_b.as_ref())
+            .ok_or(SPDM_STATUS_BUFFER_FULL)?;
+        message_mut_m1m2
+            .append_message(self.common.runtime_info.message_mut_c.as_ref())
+            .ok_or(SPDM_STATUS_BUFFER_FULL)?;
+        // we dont need create message hash for verify
+        // we just print message hash for debug purpose
+        let message_mut_m1m2_hash = crypto::hash::hash_all(
+            self.common.negotiate_info.base_hash_sel,
+            message_mut_m1m2.as_ref(),
+        )
+        .ok_or(SPDM_STATUS_CRYPTO_ERROR)?;
+        debug!(
+            "message_mut_m1m2_hash - {:02x?}",
+            message_mut_m1m2_hash.as_ref()
+        );
+
+        if self.common.negotiate_info.spdm_version_sel.get_u8()
+            >= SpdmVersion::SpdmVersion12.get_u8()
+        {
+            message_mut_m1m2.reset_message();
+            message_mut_m1m2
+                .append_message(spdm_signing_prefix_context(
+                    self.common.negotiate_info.spdm_version_sel,
+                ))
+                .ok_or(SPDM_STATUS_BUFFER_FULL)?;
+            message_mut_m1m2
+                .append_message(&SPDM_VERSION_1_2_SIGNING_CONTEXT_ZEROPAD_4)
+                .ok_or(SPDM_STATUS_BUFFER_FULL)?;
+            message_mut_m1m2
+                .append_message(&SPDM_MUT_CHALLENGE_AUTH_SIGN_CONTEXT)
+                .ok_or(SPDM_STATUS_BUFFER_FULL)?;
+            message_mut_m1m2
+                .append_message(message_mut_m1m2_hash.as_ref())
+                .ok_or(SPDM_STATUS_BUFFER_FULL)?;
+        }
+
+        self.common
+            .sign_with_base_asym(
+                self.common.get_my_key_pair_id(slot_id),
+                &SPDM_MUT_CHALLENGE_AUTH_SIGN_CONTEXT,
+                message_mut_m1m2.as_ref(),
+            )
+            .ready()
+            .ok_or(SPDM_STATUS_CRYPTO_ERROR)
+    }
+}
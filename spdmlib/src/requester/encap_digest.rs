@@ -6,18 +6,12 @@ use codec::{Codec, Reader, Writer};
 
 use crate::{
     common::SpdmCodec,
-    crypto,
     message::{
-        SpdmDigestsResponsePayload, SpdmErrorCode, SpdmGetDigestsRequestPayload, SpdmMessage,
-        SpdmMessageHeader, SpdmMessagePayload, SpdmRequestResponseCode,
-    },
-    protocol::{
-        gen_array_clone, SpdmDigestStruct, SpdmRequestCapabilityFlags, SPDM_MAX_HASH_SIZE,
-        SPDM_MAX_SLOT_NUMBER,
+        SpdmErrorCode, SpdmGetDigestsRequestPayload, SpdmMessage, SpdmMessageHeader,
+        SpdmMessagePayload, SpdmRequestResponseCode,
     },
+    protocol::SpdmRequestCapabilityFlags,
 };
-extern crate alloc;
-use alloc::boxed::Box;
 
 use super::RequesterContext;
 
@@ -71,33 +65,37 @@ impl<'a> RequesterContext<'a> {
             return;
         }
 
-        let mut slot_mask = 0u8;
-        for slot_id in 0..SPDM_MAX_SLOT_NUMBER {
-            if self.common.provision_info.my_cert_chain[slot_id].is_some() {
-                slot_mask |= (1 << slot_id) as u8;
-            }
-        }
+        let digests = if let Some(digests) = self.common.get_my_digests() {
+            digests
+        } else {
+            self.encode_encap_error_response(
+                SpdmErrorCode::SpdmErrorUnspecified,
+                0,
+                encap_response,
+            );
+            return;
+        };
 
         let response = SpdmMessage {
             header: SpdmMessageHeader {
                 version: self.common.negotiate_info.spdm_version_sel,
                 request_response_code: SpdmRequestResponseCode::SpdmResponseDigests,
             },
-            payload: SpdmMessagePayload::SpdmDigestsResponse(SpdmDigestsResponsePayload {
-                slot_mask,
-                digests: gen_array_clone(
-                    SpdmDigestStruct {
-                        data_size: self.common.negotiate_info.base_hash_sel.get_size(),
-                        data: Box::new([0xffu8; SPDM_MAX_HASH_SIZE]),
-                    },
-                    SPDM_MAX_SLOT_NUMBER,
-                ),
-            }),
+            payload: SpdmMessagePayload::SpdmDigestsResponse(digests),
         };
 
+        let start = encap_response.used();
         if response
             .spdm_encode(&mut self.common, encap_response)
             .is_err()
+            || self
+                .common
+                .append_message_mut_b(&encap_request[..reader.used()])
+                .is_err()
+            || self
+                .common
+                .append_message_mut_b(&encap_response.used_slice()[start..])
+                .is_err()
         {
             self.encode_encap_error_response(
                 SpdmErrorCode::SpdmErrorUnspecified,
@@ -107,23 +105,6 @@ impl<'a> RequesterContext<'a> {
             return;
         }
 
-        for slot_id in 0..SPDM_MAX_SLOT_NUMBER {
-            if self.common.provision_info.my_cert_chain[slot_id].is_some() {
-                let my_cert_chain = self.common.provision_info.my_cert_chain[slot_id]
-                    .as_ref()
-                    .unwrap();
-                let cert_chain_hash = crypto::hash::hash_all(
-                    self.common.negotiate_info.base_hash_sel,
-                    my_cert_chain.as_ref(),
-                )
-                .unwrap();
-
-                // patch the message before send
-                let used = encap_response.used();
-                encap_response.mut_used_slice()[(used - cert_chain_hash.data_size as usize)..used]
-                    .copy_from_slice(cert_chain_hash.as_ref());
-            }
-        }
         debug!("!!! encap get_digests : complete\n");
     }
 }
@@ -0,0 +1,136 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use codec::{Codec, Reader, Writer};
+
+use crate::common::SpdmCodec;
+use crate::message::*;
+use crate::protocol::*;
+
+use super::RequesterContext;
+
+impl<'a> RequesterContext<'a> {
+    pub fn encap_handle_key_update(
+        &mut self,
+        session_id: u32,
+        encap_request: &[u8],
+        encap_response: &mut Writer,
+    ) {
+        let mut reader = Reader::init(encap_request);
+
+        if !self
+            .common
+            .negotiate_info
+            .req_capabilities_sel
+            .contains(SpdmRequestCapabilityFlags::KEY_UPD_CAP)
+        {
+            self.encode_encap_error_response(
+                SpdmErrorCode::SpdmErrorUnsupportedRequest,
+                0,
+                encap_response,
+            );
+            return;
+        }
+
+        if let Some(message_header) = SpdmMessageHeader::read(&mut reader) {
+            if message_header.version != self.common.negotiate_info.spdm_version_sel {
+                self.encode_encap_error_response(
+                    SpdmErrorCode::SpdmErrorVersionMismatch,
+                    0,
+                    encap_response,
+                );
+                return;
+            }
+        } else {
+            self.encode_encap_error_response(
+                SpdmErrorCode::SpdmErrorInvalidRequest,
+                0,
+                encap_response,
+            );
+            return;
+        }
+
+        let key_update_req = if let Some(key_update_req) =
+            SpdmKeyUpdateRequestPayload::spdm_read(&mut self.common, &mut reader)
+        {
+            debug!("!!! encap key_update req : {:02x?}\n", key_update_req);
+            key_update_req
+        } else {
+            error!("!!! encap key_update req : fail !!!\n");
+            self.encode_encap_error_response(
+                SpdmErrorCode::SpdmErrorInvalidRequest,
+                0,
+                encap_response,
+            );
+            return;
+        };
+
+        // The responder sends the KEY_UPDATE, so the response direction key
+        // is the one to update.
+        let spdm_version_sel = self.common.negotiate_info.spdm_version_sel;
+        let session = if let Some(session) = self.common.get_session_via_id(session_id) {
+            session
+        } else {
+            self.encode_encap_error_response(
+                SpdmErrorCode::SpdmErrorUnspecified,
+                0,
+                encap_response,
+            );
+            return;
+        };
+        let result = match key_update_req.key_update_operation {
+            SpdmKeyUpdateOperation::SpdmUpdateSingleKey => {
+                session.create_data_secret_update(spdm_version_sel, false, true)
+            }
+            SpdmKeyUpdateOperation::SpdmUpdateAllKeys => session
+                .create_data_secret_update(spdm_version_sel, true, true)
+                .and_then(|_| {
+                    session.activate_data_secret_update(spdm_version_sel, true, true, true)
+                }),
+            SpdmKeyUpdateOperation::SpdmVerifyNewKey => {
+                session.activate_data_secret_update(spdm_version_sel, false, true, true)
+            }
+            _ => {
+                self.encode_encap_error_response(
+                    SpdmErrorCode::SpdmErrorInvalidRequest,
+                    0,
+                    encap_response,
+                );
+                return;
+            }
+        };
+        if result.is_err() {
+            self.encode_encap_error_response(
+                SpdmErrorCode::SpdmErrorUnspecified,
+                0,
+                encap_response,
+            );
+            return;
+        }
+
+        let response = SpdmMessage {
+            header: SpdmMessageHeader {
+                version: self.common.negotiate_info.spdm_version_sel,
+                request_response_code: SpdmRequestResponseCode::SpdmResponseKeyUpdateAck,
+            },
+            payload: SpdmMessagePayload::SpdmKeyUpdateResponse(SpdmKeyUpdateResponsePayload {
+                key_update_operation: key_update_req.key_update_operation,
+                tag: key_update_req.tag,
+            }),
+        };
+        if response
+            .spdm_encode(&mut self.common, encap_response)
+            .is_err()
+        {
+            self.encode_encap_error_response(
+                SpdmErrorCode::SpdmErrorUnspecified,
+                0,
+                encap_response,
+            );
+            return;
+        }
+
+        debug!("!!! encap key_update : complete\n");
+    }
+}
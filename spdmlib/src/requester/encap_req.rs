@@ -16,7 +16,7 @@ use crate::{
         SpdmEncapsulatedResponseAckPayload, SpdmEncapsulatedResponseAckPayloadType, SpdmErrorCode,
         SpdmGetDigestsRequestPayload, SpdmGetEncapsulatedRequestPayload,
         SpdmKeyExchangeMutAuthAttributes, SpdmMessage, SpdmMessageHeader, SpdmMessagePayload,
-        SpdmRequestResponseCode, ENCAPSULATED_RESPONSE_ACK_HEADER_SIZE,
+        SpdmRequestResponseCode,
     },
     protocol::{
         SpdmRequestCapabilityFlags, SpdmResponseCapabilityFlags, SpdmVersion, SPDM_MAX_SLOT_NUMBER,
@@ -28,7 +28,7 @@ use super::RequesterContext;
 impl<'a> RequesterContext<'a> {
     pub fn get_encapsulated_request_response(
         &mut self,
-        session_id: u32,
+        session_id: Option<u32>,
         mut_auth_requested: SpdmKeyExchangeMutAuthAttributes,
     ) -> SpdmResult {
         if self.common.negotiate_info.spdm_version_sel.get_u8()
@@ -51,6 +51,9 @@ impl<'a> RequesterContext<'a> {
             return Err(SPDM_STATUS_UNSUPPORTED_CAP);
         }
 
+        self.common.reset_message_mut_b();
+        self.common.reset_message_mut_c();
+
         match mut_auth_requested {
             // Optimized session-based mutual authentication
             // When the Requester successfully receives a Session-Secrets-Exchange response with an included encapsulated
@@ -80,7 +83,16 @@ impl<'a> RequesterContext<'a> {
         Ok(())
     }
 
-    pub fn send_get_encapsulated_request(&mut self, session_id: u32) -> SpdmResult {
+    /// Let the responder issue its pending request in session, such as a
+    /// KEY_UPDATE to rotate the session keys.
+    pub fn send_receive_encapsulated_request(&mut self, session_id: u32) -> SpdmResult {
+        self.get_encapsulated_request_response(
+            Some(session_id),
+            SpdmKeyExchangeMutAuthAttributes::empty(),
+        )
+    }
+
+    pub fn send_get_encapsulated_request(&mut self, session_id: Option<u32>) -> SpdmResult {
         let mut send_buffer = [0u8; 4];
         let mut writer = Writer::init(&mut send_buffer);
         let get_encap_request = SpdmMessage {
@@ -94,12 +106,17 @@ impl<'a> RequesterContext<'a> {
         };
         let _ = get_encap_request.spdm_encode(&mut self.common, &mut writer)?;
 
-        self.send_secured_message(session_id, writer.mut_used_slice(), false)
+        match session_id {
+            Some(session_id) => {
+                self.send_secured_message(session_id, writer.mut_used_slice(), false)
+            }
+            None => self.send_message(writer.mut_used_slice()),
+        }
     }
 
-    pub fn receive_encapsulated_request(&mut self, session_id: u32) -> SpdmResult {
+    pub fn receive_encapsulated_request(&mut self, session_id: Option<u32>) -> SpdmResult {
         let mut receive_buffer = [0u8; config::MAX_SPDM_MSG_SIZE];
-        let _ = self.receive_secured_message(session_id, &mut receive_buffer, false)?;
+        let _ = self.receive_encap_message(session_id, &mut receive_buffer)?;
         let mut reader = Reader::init(&receive_buffer);
 
         let header = SpdmMessageHeader::read(&mut reader).ok_or(SPDM_STATUS_INVALID_MSG_SIZE)?;
@@ -121,9 +138,12 @@ impl<'a> RequesterContext<'a> {
         )
     }
 
-    pub fn receive_encapsulated_response_ack(&mut self, session_id: u32) -> SpdmResult<bool> {
+    pub fn receive_encapsulated_response_ack(
+        &mut self,
+        session_id: Option<u32>,
+    ) -> SpdmResult<bool> {
         let mut receive_buffer = [0u8; config::MAX_SPDM_MSG_SIZE];
-        let size = self.receive_secured_message(session_id, &mut receive_buffer, false)?;
+        let size = self.receive_encap_message(session_id, &mut receive_buffer)?;
         let mut reader = Reader::init(&receive_buffer);
 
         let header = SpdmMessageHeader::read(&mut reader).ok_or(SPDM_STATUS_INVALID_MSG_SIZE)?;
@@ -137,10 +157,12 @@ impl<'a> RequesterContext<'a> {
         let ack_header =
             SpdmEncapsulatedResponseAckPayload::spdm_read(&mut self.common, &mut reader)
                 .ok_or(SPDM_STATUS_INVALID_MSG_SIZE)?;
+        // The header of SPDM 1.1 has no AckRequestID.
+        let ack_header_size = reader.used();
 
         match ack_header.payload_type {
             SpdmEncapsulatedResponseAckPayloadType::Absent => {
-                if size == ENCAPSULATED_RESPONSE_ACK_HEADER_SIZE {
+                if size == ack_header_size {
                     return Ok(false);
                 } else {
                     return Err(SPDM_STATUS_INVALID_MSG_SIZE);
@@ -148,7 +170,7 @@ impl<'a> RequesterContext<'a> {
             }
             SpdmEncapsulatedResponseAckPayloadType::Present => {}
             SpdmEncapsulatedResponseAckPayloadType::ReqSlotNumber => {
-                if size == ENCAPSULATED_RESPONSE_ACK_HEADER_SIZE + 1 {
+                if size == ack_header_size + 1 {
                     let req_slot_id = u8::read(&mut reader).ok_or(SPDM_STATUS_INVALID_MSG_SIZE)?;
                     if req_slot_id >= SPDM_MAX_SLOT_NUMBER as u8 {
                         return Err(SPDM_STATUS_INVALID_MSG_FIELD);
@@ -175,7 +197,7 @@ impl<'a> RequesterContext<'a> {
 
     fn process_encapsulated_request(
         &mut self,
-        session_id: u32,
+        session_id: Option<u32>,
         request_id: u8,
         encap_request: &[u8],
     ) -> SpdmResult {
@@ -205,6 +227,23 @@ impl<'a> RequesterContext<'a> {
             crate::message::SpdmRequestResponseCode::SpdmRequestGetCertificate => {
                 self.encap_handle_get_certificate(encap_request, &mut writer)
             }
+            // CHALLENGE is not allowed in session.
+            crate::message::SpdmRequestResponseCode::SpdmRequestChallenge
+                if session_id.is_none() =>
+            {
+                self.encap_handle_challenge(encap_request, &mut writer)
+            }
+            // KEY_UPDATE is only allowed in session.
+            crate::message::SpdmRequestResponseCode::SpdmRequestKeyUpdate => match session_id {
+                Some(session_id) => {
+                    self.encap_handle_key_update(session_id, encap_request, &mut writer)
+                }
+                None => self.encode_encap_error_response(
+                    SpdmErrorCode::SpdmErrorUnexpectedRequest,
+                    0,
+                    &mut writer,
+                ),
+            },
             _ => self.encode_encap_error_response(
                 SpdmErrorCode::SpdmErrorUnexpectedRequest,
                 0,
@@ -212,6 +251,20 @@ impl<'a> RequesterContext<'a> {
             ),
         }
 
-        self.send_secured_message(session_id, writer.used_slice(), false)
+        match session_id {
+            Some(session_id) => self.send_secured_message(session_id, writer.used_slice(), false),
+            None => self.send_message(writer.used_slice()),
+        }
+    }
+
+    fn receive_encap_message(
+        &mut self,
+        session_id: Option<u32>,
+        receive_buffer: &mut [u8],
+    ) -> SpdmResult<usize> {
+        match session_id {
+            Some(session_id) => self.receive_secured_message(session_id, receive_buffer, false),
+            None => self.receive_message(receive_buffer, false),
+        }
     }
 }
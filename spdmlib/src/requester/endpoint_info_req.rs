@@ -0,0 +1,256 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use crate::common::ManagedBuffer12Sign;
+use crate::crypto;
+use crate::error::{
+    SpdmResult, SPDM_STATUS_BUFFER_FULL, SPDM_STATUS_ERROR_PEER, SPDM_STATUS_INVALID_MSG_FIELD,
+    SPDM_STATUS_INVALID_PARAMETER, SPDM_STATUS_UNSUPPORTED_CAP, SPDM_STATUS_VERIF_FAIL,
+};
+use crate::message::*;
+use crate::protocol::*;
+use crate::requester::*;
+
+impl<'a> RequesterContext<'a> {
+    pub fn send_receive_spdm_endpoint_info(
+        &mut self,
+        session_id: Option<u32>,
+        sub_code: SpdmEndpointInfoSubCode,
+        request_attributes: SpdmEndpointInfoRequestAttributes,
+        slot_id: u8,
+    ) -> SpdmResult<SpdmEndpointInfo> {
+        let rsp_capabilities_sel = self.common.negotiate_info.rsp_capabilities_sel;
+        let signature_requested =
+            request_attributes.contains(SpdmEndpointInfoRequestAttributes::SIGNATURE_REQUESTED);
+        if self.common.negotiate_info.spdm_version_sel.get_u8()
+            < SpdmVersion::SpdmVersion13.get_u8()
+            || !rsp_capabilities_sel.intersects(
+                SpdmResponseCapabilityFlags::EP_INFO_CAP_NO_SIG
+                    | SpdmResponseCapabilityFlags::EP_INFO_CAP_SIG,
+            )
+            || (signature_requested
+                && !rsp_capabilities_sel.contains(SpdmResponseCapabilityFlags::EP_INFO_CAP_SIG))
+        {
+            return Err(SPDM_STATUS_UNSUPPORTED_CAP);
+        }
+        if signature_requested
+            && slot_id >= SPDM_MAX_SLOT_NUMBER as u8
+            && !self.is_rsp_public_key_slot_id(slot_id)
+        {
+            return Err(SPDM_STATUS_INVALID_PARAMETER);
+        }
+
+        self.common.reset_buffer_via_request_code(
+            SpdmRequestResponseCode::SpdmRequestGetEndpointInfo,
+            session_id,
+        );
+
+        info!("send spdm get_endpoint_info\n");
+        let mut send_buffer = [0u8; config::MAX_SPDM_MSG_SIZE];
+        let send_used = self.encode_spdm_endpoint_info(
+            sub_code,
+            request_attributes,
+            slot_id,
+            &mut send_buffer,
+        )?;
+
+        match session_id {
+            Some(session_id) => {
+                self.send_secured_message(session_id, &send_buffer[..send_used], false)?;
+            }
+            None => {
+                self.send_message(&send_buffer[..send_used])?;
+            }
+        }
+
+        let mut receive_buffer = [0u8; config::MAX_SPDM_MSG_SIZE];
+        let used = match session_id {
+            Some(session_id) => {
+                self.receive_secured_message(session_id, &mut receive_buffer, false)?
+            }
+            None => self.receive_message(&mut receive_buffer, false)?,
+        };
+
+        self.handle_spdm_endpoint_info_response(
+            session_id,
+            request_attributes,
+            slot_id,
+            &send_buffer[..send_used],
+            &receive_buffer[..used],
+        )
+    }
+
+    pub fn encode_spdm_endpoint_info(
+        &mut self,
+        sub_code: SpdmEndpointInfoSubCode,
+        request_attributes: SpdmEndpointInfoRequestAttributes,
+        slot_id: u8,
+        buf: &mut [u8],
+    ) -> SpdmResult<usize> {
+        let mut writer = Writer::init(buf);
+        let mut nonce = [0u8; SPDM_NONCE_SIZE];
+        if request_attributes.contains(SpdmEndpointInfoRequestAttributes::SIGNATURE_REQUESTED) {
+            crypto::rand::get_random(&mut nonce)?;
+        }
+
+        // SlotID is 4 bits wide.
+        let slot_id = if slot_id == SPDM_PUBLIC_KEY_SLOT_ID {
+            SPDM_PUBLIC_KEY_SLOT_ID_PARAM
+        } else {
+            slot_id
+        };
+
+        let request = SpdmMessage {
+            header: SpdmMessageHeader {
+                version: self.common.negotiate_info.spdm_version_sel,
+                request_response_code: SpdmRequestResponseCode::SpdmRequestGetEndpointInfo,
+            },
+            payload: SpdmMessagePayload::SpdmGetEndpointInfoRequest(
+                SpdmGetEndpointInfoRequestPayload {
+                    sub_code,
+                    request_attributes,
+                    slot_id,
+                    nonce: SpdmNonceStruct { data: nonce },
+                },
+            ),
+        };
+        request.spdm_encode(&mut self.common, &mut writer)
+    }
+
+    pub fn handle_spdm_endpoint_info_response(
+        &mut self,
+        session_id: Option<u32>,
+        request_attributes: SpdmEndpointInfoRequestAttributes,
+        slot_id: u8,
+        send_buffer: &[u8],
+        receive_buffer: &[u8],
+    ) -> SpdmResult<SpdmEndpointInfo> {
+        let signature_requested =
+            request_attributes.contains(SpdmEndpointInfoRequestAttributes::SIGNATURE_REQUESTED);
+        self.common.runtime_info.need_endpoint_info_signature = signature_requested;
+
+        let mut reader = Reader::init(receive_buffer);
+        match SpdmMessageHeader::read(&mut reader) {
+            Some(message_header) => {
+                if message_header.version != self.common.negotiate_info.spdm_version_sel {
+                    return Err(SPDM_STATUS_INVALID_MSG_FIELD);
+                }
+                match message_header.request_response_code {
+                    SpdmRequestResponseCode::SpdmResponseEndpointInfo => {
+                        let endpoint_info = SpdmEndpointInfoResponsePayload::spdm_read(
+                            &mut self.common,
+                            &mut reader,
+                        );
+                        let used = reader.used();
+                        if let Some(endpoint_info) = endpoint_info {
+                            debug!("!!! endpoint_info : {:02x?}\n", endpoint_info);
+
+                            let base_asym_size = self.common.get_asym_key_size() as usize;
+                            let temp_used = used
+                                - if signature_requested {
+                                    base_asym_size
+                                } else {
+                                    0
+                                };
+
+                            self.common.append_message_e(session_id, send_buffer)?;
+                            self.common
+                                .append_message_e(session_id, &receive_buffer[..temp_used])?;
+
+                            if signature_requested {
+                                let slot_id_param = if slot_id == SPDM_PUBLIC_KEY_SLOT_ID {
+                                    SPDM_PUBLIC_KEY_SLOT_ID_PARAM
+                                } else {
+                                    slot_id
+                                };
+                                let result = if endpoint_info.slot_id != slot_id_param {
+                                    Err(SPDM_STATUS_INVALID_MSG_FIELD)
+                                } else {
+                                    self.verify_endpoint_info_signature(
+                                        slot_id,
+                                        session_id,
+                                        &endpoint_info.signature,
+                                    )
+                                    .map_err(|_| SPDM_STATUS_VERIF_FAIL)
+                                };
+                                self.common.reset_message_e(session_id);
+                                if let Err(status) = result {
+                                    error!("verify_endpoint_info_signature fail");
+                                    return Err(status);
+                                }
+                                info!("verify_endpoint_info_signature pass");
+                            }
+
+                            Ok(endpoint_info.endpoint_info)
+                        } else {
+                            error!("!!! endpoint_info : fail !!!\n");
+                            self.common.reset_message_e(session_id);
+                            Err(SPDM_STATUS_INVALID_MSG_FIELD)
+                        }
+                    }
+                    SpdmRequestResponseCode::SpdmResponseError => {
+                        self.common.reset_message_e(session_id);
+                        let status = self.spdm_handle_error_response_main(
+                            session_id,
+                            receive_buffer,
+                            SpdmRequestResponseCode::SpdmRequestGetEndpointInfo,
+                            SpdmRequestResponseCode::SpdmResponseEndpointInfo,
+                        );
+                        match status {
+                            Err(status) => Err(status),
+                            Ok(()) => Err(SPDM_STATUS_ERROR_PEER),
+                        }
+                    }
+                    _ => Err(SPDM_STATUS_ERROR_PEER),
+                }
+            }
+            None => Err(SPDM_STATUS_INVALID_MSG_FIELD),
+        }
+    }
+
+    pub fn verify_endpoint_info_signature(
+        &self,
+        slot_id: u8,
+        session_id: Option<u32>,
+        signature: &SpdmSignatureStruct,
+    ) -> SpdmResult {
+        let message_il1il2_hash = self.common.calc_message_il1il2_hash(session_id)?;
+        debug!(
+            "message_il1il2_hash - {:02x?}",
+            message_il1il2_hash.as_ref()
+        );
+
+        if self
+            .common
+            .get_peer_cert_chain_or_public_key(slot_id)
+            .is_none()
+        {
+            error!("peer_cert_chain is not populated!\n");
+            return Err(SPDM_STATUS_INVALID_PARAMETER);
+        }
+
+        let mut message_sign = ManagedBuffer12Sign::default();
+        message_sign
+            .append_message(spdm_signing_prefix_context(
+                self.common.negotiate_info.spdm_version_sel,
+            ))
+            .ok_or(SPDM_STATUS_BUFFER_FULL)?;
+        message_sign
+            .append_message(&SPDM_VERSION_1_2_SIGNING_CONTEXT_ZEROPAD_5)
+            .ok_or(SPDM_STATUS_BUFFER_FULL)?;
+        message_sign
+            .append_message(&SPDM_ENDPOINT_INFO_SIGN_CONTEXT)
+            .ok_or(SPDM_STATUS_BUFFER_FULL)?;
+        message_sign
+            .append_message(message_il1il2_hash.as_ref())
+            .ok_or(SPDM_STATUS_BUFFER_FULL)?;
+
+        self.common.verify_peer_signature(
+            slot_id,
+            &SPDM_ENDPOINT_INFO_SIGN_CONTEXT,
+            message_sign.as_ref(),
+            signature,
+        )
+    }
+}
@@ -0,0 +1,31 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use conquer_once::spin::OnceCell;
+
+use crate::error::SpdmResult;
+use crate::message::SpdmEventData;
+
+#[derive(Clone)]
+pub struct SpdmEventHandler {
+    pub handle_event_cb: fn(session_id: u32, event: &SpdmEventData) -> SpdmResult,
+}
+
+static SPDM_EVENT_HANDLER: OnceCell<SpdmEventHandler> = OnceCell::uninit();
+
+static DEFAULT: SpdmEventHandler = SpdmEventHandler {
+    handle_event_cb: |_session_id: u32, _event: &SpdmEventData| -> SpdmResult { Ok(()) },
+};
+
+#[allow(dead_code)]
+pub fn register(context: SpdmEventHandler) -> bool {
+    SPDM_EVENT_HANDLER.try_init_once(|| context).is_ok()
+}
+
+pub fn handle_event_cb(session_id: u32, event: &SpdmEventData) -> SpdmResult {
+    (SPDM_EVENT_HANDLER
+        .try_get_or_init(|| DEFAULT.clone())
+        .unwrap_or(&DEFAULT)
+        .handle_event_cb)(session_id, event)
+}
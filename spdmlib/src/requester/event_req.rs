@@ -0,0 +1,260 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use super::event_handler::handle_event_cb;
+use crate::error::{
+    SpdmResult, SPDM_STATUS_ERROR_PEER, SPDM_STATUS_INVALID_MSG_FIELD,
+    SPDM_STATUS_INVALID_PARAMETER, SPDM_STATUS_UNSUPPORTED_CAP,
+};
+use crate::message::*;
+use crate::protocol::*;
+use crate::requester::*;
+
+impl<'a> RequesterContext<'a> {
+    fn is_event_supported(&self) -> bool {
+        self.common.negotiate_info.spdm_version_sel.get_u8() >= SpdmVersion::SpdmVersion13.get_u8()
+            && self
+                .common
+                .negotiate_info
+                .rsp_capabilities_sel
+                .contains(SpdmResponseCapabilityFlags::EVENT_CAP)
+    }
+
+    pub fn send_receive_spdm_get_supported_event_types(
+        &mut self,
+        session_id: u32,
+    ) -> SpdmResult<SpdmSupportedEventTypesResponsePayload> {
+        info!("send spdm get_supported_event_types\n");
+
+        if !self.is_event_supported() {
+            return Err(SPDM_STATUS_UNSUPPORTED_CAP);
+        }
+
+        self.common.reset_buffer_via_request_code(
+            SpdmRequestResponseCode::SpdmRequestGetSupportedEventTypes,
+            Some(session_id),
+        );
+
+        let mut send_buffer = [0u8; config::MAX_SPDM_MSG_SIZE];
+        let used = self.encode_spdm_get_supported_event_types(&mut send_buffer)?;
+        self.send_secured_message(session_id, &send_buffer[..used], false)?;
+
+        let mut receive_buffer = [0u8; config::MAX_SPDM_MSG_SIZE];
+        let used = self.receive_secured_message(session_id, &mut receive_buffer, false)?;
+        self.handle_spdm_supported_event_types_response(session_id, &receive_buffer[..used])
+    }
+
+    pub fn encode_spdm_get_supported_event_types(&mut self, buf: &mut [u8]) -> SpdmResult<usize> {
+        let mut writer = Writer::init(buf);
+        let request = SpdmMessage {
+            header: SpdmMessageHeader {
+                version: self.common.negotiate_info.spdm_version_sel,
+                request_response_code: SpdmRequestResponseCode::SpdmRequestGetSupportedEventTypes,
+            },
+            payload: SpdmMessagePayload::SpdmGetSupportedEventTypesRequest(
+                SpdmGetSupportedEventTypesRequestPayload {},
+            ),
+        };
+        request.spdm_encode(&mut self.common, &mut writer)
+    }
+
+    pub fn handle_spdm_supported_event_types_response(
+        &mut self,
+        session_id: u32,
+        receive_buffer: &[u8],
+    ) -> SpdmResult<SpdmSupportedEventTypesResponsePayload> {
+        let mut reader = Reader::init(receive_buffer);
+        match SpdmMessageHeader::read(&mut reader) {
+            Some(message_header) => {
+                if message_header.version != self.common.negotiate_info.spdm_version_sel {
+                    return Err(SPDM_STATUS_INVALID_MSG_FIELD);
+                }
+                match message_header.request_response_code {
+                    SpdmRequestResponseCode::SpdmResponseSupportedEventTypes => {
+                        let supported_event_types =
+                            SpdmSupportedEventTypesResponsePayload::spdm_read(
+                                &mut self.common,
+                                &mut reader,
+                            );
+                        if let Some(supported_event_types) = supported_event_types {
+                            debug!(
+                                "!!! supported_event_types : {:02x?}\n",
+                                supported_event_types
+                            );
+                            Ok(supported_event_types)
+                        } else {
+                            error!("!!! supported_event_types : fail !!!\n");
+                            Err(SPDM_STATUS_INVALID_MSG_FIELD)
+                        }
+                    }
+                    SpdmRequestResponseCode::SpdmResponseError => {
+                        let status = self.spdm_handle_error_response_main(
+                            Some(session_id),
+                            receive_buffer,
+                            SpdmRequestResponseCode::SpdmRequestGetSupportedEventTypes,
+                            SpdmRequestResponseCode::SpdmResponseSupportedEventTypes,
+                        );
+                        match status {
+                            Err(status) => Err(status),
+                            Ok(()) => Err(SPDM_STATUS_ERROR_PEER),
+                        }
+                    }
+                    _ => Err(SPDM_STATUS_ERROR_PEER),
+                }
+            }
+            None => Err(SPDM_STATUS_INVALID_MSG_FIELD),
+        }
+    }
+
+    // An empty event_groups list clears all the subscriptions of the session.
+    pub fn send_receive_spdm_subscribe_event_types(
+        &mut self,
+        session_id: u32,
+        event_groups: &[SpdmEventGroup],
+    ) -> SpdmResult {
+        info!("send spdm subscribe_event_types\n");
+
+        if !self.is_event_supported() {
+            return Err(SPDM_STATUS_UNSUPPORTED_CAP);
+        }
+        if event_groups.len() > MAX_SPDM_EVENT_GROUP_COUNT {
+            return Err(SPDM_STATUS_INVALID_PARAMETER);
+        }
+
+        self.common.reset_buffer_via_request_code(
+            SpdmRequestResponseCode::SpdmRequestSubscribeEventTypes,
+            Some(session_id),
+        );
+
+        let mut send_buffer = [0u8; config::MAX_SPDM_MSG_SIZE];
+        let used = self.encode_spdm_subscribe_event_types(event_groups, &mut send_buffer)?;
+        self.send_secured_message(session_id, &send_buffer[..used], false)?;
+
+        let mut receive_buffer = [0u8; config::MAX_SPDM_MSG_SIZE];
+        let used = self.receive_secured_message(session_id, &mut receive_buffer, false)?;
+        self.handle_spdm_subscribe_event_types_ack_response(session_id, &receive_buffer[..used])
+    }
+
+    pub fn encode_spdm_subscribe_event_types(
+        &mut self,
+        event_groups: &[SpdmEventGroup],
+        buf: &mut [u8],
+    ) -> SpdmResult<usize> {
+        let mut writer = Writer::init(buf);
+        let mut payload = SpdmSubscribeEventTypesRequestPayload {
+            event_group_count: event_groups.len() as u8,
+            ..Default::default()
+        };
+        payload.event_groups[..event_groups.len()].copy_from_slice(event_groups);
+        let request = SpdmMessage {
+            header: SpdmMessageHeader {
+                version: self.common.negotiate_info.spdm_version_sel,
+                request_response_code: SpdmRequestResponseCode::SpdmRequestSubscribeEventTypes,
+            },
+            payload: SpdmMessagePayload::SpdmSubscribeEventTypesRequest(payload),
+        };
+        request.spdm_encode(&mut self.common, &mut writer)
+    }
+
+    pub fn handle_spdm_subscribe_event_types_ack_response(
+        &mut self,
+        session_id: u32,
+        receive_buffer: &[u8],
+    ) -> SpdmResult {
+        let mut reader = Reader::init(receive_buffer);
+        match SpdmMessageHeader::read(&mut reader) {
+            Some(message_header) => {
+                if message_header.version != self.common.negotiate_info.spdm_version_sel {
+                    return Err(SPDM_STATUS_INVALID_MSG_FIELD);
+                }
+                match message_header.request_response_code {
+                    SpdmRequestResponseCode::SpdmResponseSubscribeEventTypesAck => {
+                        let subscribe_event_types_ack =
+                            SpdmSubscribeEventTypesAckResponsePayload::spdm_read(
+                                &mut self.common,
+                                &mut reader,
+                            );
+                        if let Some(subscribe_event_types_ack) = subscribe_event_types_ack {
+                            debug!(
+                                "!!! subscribe_event_types_ack : {:02x?}\n",
+                                subscribe_event_types_ack
+                            );
+                            Ok(())
+                        } else {
+                            error!("!!! subscribe_event_types_ack : fail !!!\n");
+                            Err(SPDM_STATUS_INVALID_MSG_FIELD)
+                        }
+                    }
+                    SpdmRequestResponseCode::SpdmResponseError => self
+                        .spdm_handle_error_response_main(
+                            Some(session_id),
+                            receive_buffer,
+                            SpdmRequestResponseCode::SpdmRequestSubscribeEventTypes,
+                            SpdmRequestResponseCode::SpdmResponseSubscribeEventTypesAck,
+                        ),
+                    _ => Err(SPDM_STATUS_ERROR_PEER),
+                }
+            }
+            None => Err(SPDM_STATUS_INVALID_MSG_FIELD),
+        }
+    }
+
+    // Wait for SEND_EVENT from the responder, pass every event to the
+    // registered event handler and acknowledge them with EVENT_ACK.
+    pub fn receive_spdm_event(&mut self, session_id: u32) -> SpdmResult {
+        info!("receive spdm send_event\n");
+
+        if !self.is_event_supported() {
+            return Err(SPDM_STATUS_UNSUPPORTED_CAP);
+        }
+
+        let mut receive_buffer = [0u8; config::MAX_SPDM_MSG_SIZE];
+        let used = self.receive_secured_message(session_id, &mut receive_buffer, false)?;
+        self.handle_spdm_send_event(session_id, &receive_buffer[..used])?;
+
+        let mut send_buffer = [0u8; config::MAX_SPDM_MSG_SIZE];
+        let used = self.encode_spdm_event_ack(&mut send_buffer)?;
+        self.send_secured_message(session_id, &send_buffer[..used], false)
+    }
+
+    pub fn handle_spdm_send_event(&mut self, session_id: u32, receive_buffer: &[u8]) -> SpdmResult {
+        let mut reader = Reader::init(receive_buffer);
+        let message_header =
+            SpdmMessageHeader::read(&mut reader).ok_or(SPDM_STATUS_INVALID_MSG_FIELD)?;
+        if message_header.version != self.common.negotiate_info.spdm_version_sel
+            || message_header.request_response_code != SpdmRequestResponseCode::SpdmRequestSendEvent
+        {
+            return Err(SPDM_STATUS_INVALID_MSG_FIELD);
+        }
+        let send_event = SpdmSendEventRequestPayload::spdm_read(&mut self.common, &mut reader);
+        let send_event = if let Some(send_event) = send_event {
+            debug!("!!! send_event : {:02x?}\n", send_event);
+            send_event
+        } else {
+            error!("!!! send_event : fail !!!\n");
+            return Err(SPDM_STATUS_INVALID_MSG_FIELD);
+        };
+
+        for event in send_event
+            .events
+            .iter()
+            .take(send_event.event_count as usize)
+        {
+            handle_event_cb(session_id, event)?;
+        }
+        Ok(())
+    }
+
+    pub fn encode_spdm_event_ack(&mut self, buf: &mut [u8]) -> SpdmResult<usize> {
+        let mut writer = Writer::init(buf);
+        let response = SpdmMessage {
+            header: SpdmMessageHeader {
+                version: self.common.negotiate_info.spdm_version_sel,
+                request_response_code: SpdmRequestResponseCode::SpdmResponseEventAck,
+            },
+            payload: SpdmMessagePayload::SpdmEventAckResponse(SpdmEventAckResponsePayload {}),
+        };
+        response.spdm_encode(&mut self.common, &mut writer)
+    }
+}
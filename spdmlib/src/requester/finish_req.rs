@@ -30,10 +30,21 @@ impl<'a> RequesterContext<'a> {
         info!("in_clear_text {:?}\n", in_clear_text);
 
         let req_slot_id = if let Some(req_slot_id) = req_slot_id {
-            if req_slot_id >= SPDM_MAX_SLOT_NUMBER as u8 {
+            if req_slot_id >= SPDM_MAX_SLOT_NUMBER as u8
+                && !(req_slot_id == SPDM_PUBLIC_KEY_SLOT_ID
+                    && self
+                        .common
+                        .negotiate_info
+                        .req_capabilities_sel
+                        .contains(SpdmRequestCapabilityFlags::PUB_KEY_ID_CAP))
+            {
                 return Err(SPDM_STATUS_INVALID_STATE_LOCAL);
             }
-            if self.common.provision_info.my_cert_chain[req_slot_id as usize].is_none() {
+            if self
+                .common
+                .get_my_cert_chain_or_public_key(req_slot_id)
+                .is_none()
+            {
                 return Err(SPDM_STATUS_INVALID_STATE_LOCAL);
             }
             req_slot_id
@@ -150,7 +161,8 @@ impl<'a> RequesterContext<'a> {
             .get_immutable_session_via_id(session_id)
             .ok_or(SPDM_STATUS_INVALID_STATE_LOCAL)?;
         if !session.get_mut_auth_requested().is_empty() {
-            signature = self.generate_finish_req_signature(session.get_slot_id(), session)?;
+            signature =
+                self.generate_finish_req_signature(session.get_slot_id(), req_slot_id, session)?;
             // patch the signature
             buf[4..4 + signature.data_size as usize].copy_from_slice(signature.as_ref());
 
@@ -166,9 +178,12 @@ impl<'a> RequesterContext<'a> {
             .get_immutable_session_via_id(session_id)
             .unwrap();
 
-        let transcript_hash =
-            self.common
-                .calc_req_transcript_hash(false, req_slot_id, is_mut_auth, session)?;
+        let transcript_hash = self.common.calc_req_transcript_hash(
+            false,
+            session.get_slot_id(),
+            is_mut_auth,
+            session,
+        )?;
 
         let session = self.common.get_session_via_id(session_id).unwrap();
 
@@ -235,7 +250,7 @@ impl<'a> RequesterContext<'a> {
 
                             let transcript_hash = self.common.calc_req_transcript_hash(
                                 false,
-                                req_slot_id,
+                                session.get_slot_id(),
                                 is_mut_auth,
                                 session,
                             )?;
@@ -274,7 +289,7 @@ impl<'a> RequesterContext<'a> {
                         // generate the data secret
                         let th2 = self.common.calc_req_transcript_hash(
                             false,
-                            req_slot_id,
+                            session.get_slot_id(),
                             is_mut_auth,
                             session,
                         )?;
@@ -300,12 +315,23 @@ impl<'a> RequesterContext<'a> {
                         Err(SPDM_STATUS_INVALID_MSG_FIELD)
                     }
                 }
-                SpdmRequestResponseCode::SpdmResponseError => self.spdm_handle_error_response_main(
-                    Some(session_id),
-                    receive_buffer,
-                    SpdmRequestResponseCode::SpdmRequestFinish,
-                    SpdmRequestResponseCode::SpdmResponseFinishRsp,
-                ),
+                SpdmRequestResponseCode::SpdmResponseError => {
+                    let received = self.spdm_handle_error_response_respond_if_ready(
+                        if in_clear_text {
+                            None
+                        } else {
+                            Some(session_id)
+                        },
+                        receive_buffer,
+                        SpdmRequestResponseCode::SpdmRequestFinish,
+                        SpdmRequestResponseCode::SpdmResponseFinishRsp,
+                    )?;
+                    self.handle_spdm_finish_response(
+                        session_id,
+                        req_slot_id,
+                        &received.receive_buffer[..received.used],
+                    )
+                }
                 _ => Err(SPDM_STATUS_ERROR_PEER),
             },
             None => Err(SPDM_STATUS_INVALID_MSG_FIELD),
@@ -316,6 +342,7 @@ impl<'a> RequesterContext<'a> {
     fn generate_finish_req_signature(
         &self,
         slot_id: u8,
+        req_slot_id: u8,
         session: &SpdmSession,
     ) -> SpdmResult<SpdmSignatureStruct> {
         let transcript_data_hash = self
@@ -328,7 +355,9 @@ impl<'a> RequesterContext<'a> {
         {
             transcript_sign.reset_message();
             transcript_sign
-                .append_message(&SPDM_VERSION_1_2_SIGNING_PREFIX_CONTEXT)
+                .append_message(spdm_signing_prefix_context(
+                    self.common.negotiate_info.spdm_version_sel,
+                ))
                 .ok_or(SPDM_STATUS_BUFFER_FULL)?;
             transcript_sign
                 .append_message(&SPDM_VERSION_1_2_SIGNING_CONTEXT_ZEROPAD_12)
@@ -341,18 +370,21 @@ impl<'a> RequesterContext<'a> {
                 .ok_or(SPDM_STATUS_BUFFER_FULL)?;
         }
 
-        crate::secret::asym_sign::sign(
-            self.common.negotiate_info.base_hash_sel,
-            self.common.negotiate_info.base_asym_sel,
-            transcript_sign.as_ref(),
-        )
-        .ok_or(SPDM_STATUS_CRYPTO_ERROR)
+        self.common
+            .sign_with_base_asym(
+                self.common.get_my_key_pair_id(req_slot_id),
+                &SPDM_FINISH_SIGN_CONTEXT,
+                transcript_sign.as_ref(),
+            )
+            .ready()
+            .ok_or(SPDM_STATUS_CRYPTO_ERROR)
     }
 
     #[cfg(feature = "hashed-transcript-data")]
     fn generate_finish_req_signature(
         &self,
         _slot_id: u8,
+        req_slot_id: u8,
         session: &SpdmSession,
     ) -> SpdmResult<SpdmSignatureStruct> {
         let transcript_hash =
@@ -367,7 +399,9 @@ impl<'a> RequesterContext<'a> {
         {
             transcript_sign.reset_message();
             transcript_sign
-                .append_message(&SPDM_VERSION_1_2_SIGNING_PREFIX_CONTEXT)
+                .append_message(spdm_signing_prefix_context(
+                    self.common.negotiate_info.spdm_version_sel,
+                ))
                 .ok_or(SPDM_STATUS_BUFFER_FULL)?;
             transcript_sign
                 .append_message(&SPDM_VERSION_1_2_SIGNING_CONTEXT_ZEROPAD_12)
@@ -383,31 +417,52 @@ impl<'a> RequesterContext<'a> {
             return Err(SPDM_STATUS_INVALID_STATE_LOCAL);
         }
 
-        let signature = crate::secret::asym_sign::sign(
-            self.common.negotiate_info.base_hash_sel,
-            self.common.negotiate_info.base_asym_sel,
-            transcript_sign.as_ref(),
-        )
-        .ok_or(SPDM_STATUS_CRYPTO_ERROR)?;
-
-        let peer_slot_id = self.common.runtime_info.get_local_used_cert_chain_slot_id();
-        let peer_cert = &self.common.provision_info.my_cert_chain[peer_slot_id as usize]
-            .as_ref()
-            .ok_or(SPDM_STATUS_INVALID_PARAMETER)?
-            .data[(4usize + self.common.negotiate_info.base_hash_sel.get_size() as usize)
-            ..(self.common.peer_info.peer_cert_chain[peer_slot_id as usize]
+        let signature = self
+            .common
+            .sign_with_base_asym(
+                self.common.get_my_key_pair_id(req_slot_id),
+                &SPDM_FINISH_SIGN_CONTEXT,
+                transcript_sign.as_ref(),
+            )
+            .ready()
+            .ok_or(SPDM_STATUS_CRYPTO_ERROR)?;
+
+        let my_slot_id = self.common.runtime_info.get_local_used_cert_chain_slot_id();
+        if my_slot_id == SPDM_PUBLIC_KEY_SLOT_ID {
+            let my_public_key = self
+                .common
+                .provision_info
+                .my_public_key
+                .as_ref()
+                .ok_or(SPDM_STATUS_INVALID_PARAMETER)?;
+            self.common
+                .verify_base_asym_signature_with_public_key(
+                    my_public_key.as_ref(),
+                    &SPDM_FINISH_SIGN_CONTEXT,
+                    transcript_sign.as_ref(),
+                    &signature,
+                )
+                .unwrap();
+        } else {
+            let my_cert = &self.common.provision_info.my_cert_chain[my_slot_id as usize]
                 .as_ref()
                 .ok_or(SPDM_STATUS_INVALID_PARAMETER)?
-                .data_size as usize)];
-
-        crate::crypto::asym_verify::verify(
-            self.common.negotiate_info.base_hash_sel,
-            self.common.negotiate_info.base_asym_sel,
-            peer_cert,
-            transcript_sign.as_ref(),
-            &signature,
-        )
-        .unwrap();
+                .data[(4usize
+                + self.common.negotiate_info.base_hash_sel.get_size() as usize)
+                ..(self.common.provision_info.my_cert_chain[my_slot_id as usize]
+                    .as_ref()
+                    .ok_or(SPDM_STATUS_INVALID_PARAMETER)?
+                    .data_size as usize)];
+
+            self.common
+                .verify_base_asym_signature(
+                    my_cert,
+                    &SPDM_FINISH_SIGN_CONTEXT,
+                    transcript_sign.as_ref(),
+                    &signature,
+                )
+                .unwrap();
+        }
 
         Ok(signature)
     }
@@ -216,7 +216,7 @@ impl<'a> RequesterContext<'a> {
             return Err(SPDM_STATUS_INVALID_CERT);
         }
 
-        let result = self.verify_spdm_certificate_chain();
+        let result = self.verify_spdm_certificate_chain(slot_id);
         if result.is_ok() {
             self.common.peer_info.peer_cert_chain[slot_id as usize] =
                 self.common.peer_info.peer_cert_chain_temp.clone();
@@ -225,7 +225,7 @@ impl<'a> RequesterContext<'a> {
         result
     }
 
-    pub fn verify_spdm_certificate_chain(&mut self) -> SpdmResult {
+    pub fn verify_spdm_certificate_chain(&mut self, slot_id: u8) -> SpdmResult {
         //
         // 1. Verify the integrity of cert chain
         //
@@ -261,11 +261,28 @@ impl<'a> RequesterContext<'a> {
         let runtime_peer_cert_chain_data = SpdmCertChainData { data_size, data };
         info!("1. get runtime_peer_cert_chain_data!\n");
 
+        //
+        // 1.0 verify the chain follows the device or alias certificate model
+        //
+        let is_alias_cert_model = self.common.is_peer_alias_cert_model(slot_id);
+        if crypto::check_cert_chain_model(
+            &runtime_peer_cert_chain_data.data[..(runtime_peer_cert_chain_data.data_size as usize)],
+            is_alias_cert_model,
+        )
+        .is_err()
+        {
+            error!("cert_chain model - fail!\n");
+            return Err(SPDM_STATUS_INVALID_CERT);
+        }
+        info!("1.0. cert_chain model is verified!\n");
+
         //
         // 1.1 verify the integrity of the chain
         //
         if crypto::cert_operation::verify_cert_chain(
             &runtime_peer_cert_chain_data.data[..(runtime_peer_cert_chain_data.data_size as usize)],
+            false,
+            is_alias_cert_model,
         )
         .is_err()
         {
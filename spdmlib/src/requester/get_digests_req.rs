@@ -72,6 +72,7 @@ impl<'a> RequesterContext<'a> {
                         let used = reader.used();
                         if let Some(digests) = digests {
                             debug!("!!! digests : {:02x?}\n", digests);
+                            self.common.set_peer_key_pairs(&digests);
 
                             match session_id {
                                 None => {
@@ -24,7 +24,7 @@ impl<'a> RequesterContext<'a> {
     ) -> SpdmResult<u8> {
         info!("send spdm measurement\n");
 
-        if slot_id >= SPDM_MAX_SLOT_NUMBER as u8 {
+        if slot_id >= SPDM_MAX_SLOT_NUMBER as u8 && !self.is_rsp_public_key_slot_id(slot_id) {
             return Err(SPDM_STATUS_INVALID_PARAMETER);
         }
 
@@ -80,6 +80,13 @@ impl<'a> RequesterContext<'a> {
         let mut nonce = [0u8; SPDM_NONCE_SIZE];
         crypto::rand::get_random(&mut nonce)?;
 
+        // SlotIDParam is 4 bits wide.
+        let slot_id = if slot_id == SPDM_PUBLIC_KEY_SLOT_ID {
+            SPDM_PUBLIC_KEY_SLOT_ID_PARAM
+        } else {
+            slot_id
+        };
+
         let request = SpdmMessage {
             header: SpdmMessageHeader {
                 version: self.common.negotiate_info.spdm_version_sel,
@@ -130,6 +137,18 @@ impl<'a> RequesterContext<'a> {
                         if let Some(measurements) = measurements {
                             debug!("!!! measurements : {:02x?}\n", measurements);
 
+                            if let SpdmMeasurementOperation::Unknown(index) = measurement_operation
+                            {
+                                if !Self::check_measurement_record_index(
+                                    &measurements.measurement_record,
+                                    index,
+                                ) {
+                                    error!("!!! measurements : index mismatch !!!\n");
+                                    self.common.reset_message_m(session_id);
+                                    return Err(SPDM_STATUS_INVALID_MSG_FIELD);
+                                }
+                            }
+
                             if self.common.negotiate_info.spdm_version_sel.get_u8()
                                 >= SpdmVersion::SpdmVersion12.get_u8()
                             {
@@ -137,8 +156,7 @@ impl<'a> RequesterContext<'a> {
                                     measurements.content_changed;
                             }
 
-                            let base_asym_size =
-                                self.common.negotiate_info.base_asym_sel.get_size() as usize;
+                            let base_asym_size = self.common.get_asym_key_size() as usize;
                             let temp_used = used
                                 - if self.common.runtime_info.need_measurement_signature {
                                     base_asym_size
@@ -190,15 +208,28 @@ impl<'a> RequesterContext<'a> {
                         }
                     }
                     SpdmRequestResponseCode::SpdmResponseError => {
-                        let status = self.spdm_handle_error_response_main(
+                        // The responder discards L1/L2 when it returns an error,
+                        // but keeps them while the response is not ready.
+                        let received = self.spdm_handle_error_response_respond_if_ready(
                             session_id,
                             receive_buffer,
                             SpdmRequestResponseCode::SpdmRequestGetMeasurements,
                             SpdmRequestResponseCode::SpdmResponseMeasurements,
                         );
-                        match status {
-                            Err(status) => Err(status),
-                            Ok(()) => Err(SPDM_STATUS_ERROR_PEER),
+                        match received {
+                            Err(status) => {
+                                self.common.reset_message_m(session_id);
+                                Err(status)
+                            }
+                            Ok(received) => self.handle_spdm_measurement_record_response(
+                                session_id,
+                                slot_id,
+                                measurement_attributes,
+                                measurement_operation,
+                                spdm_measurement_record_structure,
+                                send_buffer,
+                                &received.receive_buffer[..received.used],
+                            ),
                         }
                     }
                     _ => Err(SPDM_STATUS_ERROR_PEER),
@@ -208,6 +239,25 @@ impl<'a> RequesterContext<'a> {
         }
     }
 
+    // A response to a single index request carries exactly the requested block.
+    fn check_measurement_record_index(
+        measurement_record: &SpdmMeasurementRecordStructure,
+        index: u8,
+    ) -> bool {
+        if measurement_record.number_of_blocks != 1 {
+            return false;
+        }
+        let record_length = measurement_record.measurement_record_length.get() as usize;
+        if record_length > measurement_record.measurement_record_data.len() {
+            return false;
+        }
+        let mut reader = Reader::init(&measurement_record.measurement_record_data[..record_length]);
+        match SpdmMeasurementBlockStructure::read(&mut reader) {
+            Some(block) => block.index == index,
+            None => false,
+        }
+    }
+
     pub fn send_receive_spdm_measurement(
         &mut self,
         session_id: Option<u32>,
@@ -265,27 +315,24 @@ impl<'a> RequesterContext<'a> {
 
         debug!("message_l1l2_hash - {:02x?}", message_l1l2_hash.as_ref());
 
-        if self.common.peer_info.peer_cert_chain[slot_id as usize].is_none() {
+        if self
+            .common
+            .get_peer_cert_chain_or_public_key(slot_id)
+            .is_none()
+        {
             error!("peer_cert_chain is not populated!\n");
             return Err(SPDM_STATUS_INVALID_PARAMETER);
         }
 
-        let cert_chain_data = &self.common.peer_info.peer_cert_chain[slot_id as usize]
-            .as_ref()
-            .ok_or(SPDM_STATUS_INVALID_PARAMETER)?
-            .data[(4usize + self.common.negotiate_info.base_hash_sel.get_size() as usize)
-            ..(self.common.peer_info.peer_cert_chain[slot_id as usize]
-                .as_ref()
-                .ok_or(SPDM_STATUS_INVALID_PARAMETER)?
-                .data_size as usize)];
-
         let mut message_sign = ManagedBuffer12Sign::default();
         if self.common.negotiate_info.spdm_version_sel.get_u8()
             >= SpdmVersion::SpdmVersion12.get_u8()
         {
             message_sign.reset_message();
             message_sign
-                .append_message(&SPDM_VERSION_1_2_SIGNING_PREFIX_CONTEXT)
+                .append_message(spdm_signing_prefix_context(
+                    self.common.negotiate_info.spdm_version_sel,
+                ))
                 .ok_or(SPDM_STATUS_BUFFER_FULL)?;
             message_sign
                 .append_message(&SPDM_VERSION_1_2_SIGNING_CONTEXT_ZEROPAD_6)
@@ -301,10 +348,9 @@ impl<'a> RequesterContext<'a> {
             return Err(SPDM_STATUS_INVALID_STATE_LOCAL);
         }
 
-        crypto::asym_verify::verify(
-            self.common.negotiate_info.base_hash_sel,
-            self.common.negotiate_info.base_asym_sel,
-            cert_chain_data,
+        self.common.verify_peer_signature(
+            slot_id,
+            &SPDM_MEASUREMENTS_SIGN_CONTEXT,
             message_sign.as_ref(),
             signature,
         )
@@ -357,26 +403,23 @@ impl<'a> RequesterContext<'a> {
         .ok_or(SPDM_STATUS_CRYPTO_ERROR)?;
         debug!("message_l1l2_hash - {:02x?}", message_l1l2_hash.as_ref());
 
-        if self.common.peer_info.peer_cert_chain[slot_id as usize].is_none() {
+        if self
+            .common
+            .get_peer_cert_chain_or_public_key(slot_id)
+            .is_none()
+        {
             error!("peer_cert_chain is not populated!\n");
             return Err(SPDM_STATUS_INVALID_PARAMETER);
         }
 
-        let cert_chain_data = &self.common.peer_info.peer_cert_chain[slot_id as usize]
-            .as_ref()
-            .ok_or(SPDM_STATUS_INVALID_PARAMETER)?
-            .data[(4usize + self.common.negotiate_info.base_hash_sel.get_size() as usize)
-            ..(self.common.peer_info.peer_cert_chain[slot_id as usize]
-                .as_ref()
-                .ok_or(SPDM_STATUS_INVALID_PARAMETER)?
-                .data_size as usize)];
-
         if self.common.negotiate_info.spdm_version_sel.get_u8()
             >= SpdmVersion::SpdmVersion12.get_u8()
         {
             message_l1l2.reset_message();
             message_l1l2
-                .append_message(&SPDM_VERSION_1_2_SIGNING_PREFIX_CONTEXT)
+                .append_message(spdm_signing_prefix_context(
+                    self.common.negotiate_info.spdm_version_sel,
+                ))
                 .ok_or(SPDM_STATUS_BUFFER_FULL)?;
             message_l1l2
                 .append_message(&SPDM_VERSION_1_2_SIGNING_CONTEXT_ZEROPAD_6)
@@ -389,10 +432,9 @@ impl<'a> RequesterContext<'a> {
                 .ok_or(SPDM_STATUS_BUFFER_FULL)?;
         }
 
-        crypto::asym_verify::verify(
-            self.common.negotiate_info.base_hash_sel,
-            self.common.negotiate_info.base_asym_sel,
-            cert_chain_data,
+        self.common.verify_peer_signature(
+            slot_id,
+            &SPDM_MEASUREMENTS_SIGN_CONTEXT,
             message_l1l2.as_ref(),
             signature,
         )
@@ -4,13 +4,21 @@
 
 use codec::{Codec, Reader};
 
+use crate::common::SpdmCodec;
+
 use crate::common::session::SpdmSessionState;
 use crate::error::{
     SpdmResult, SPDM_STATUS_BUSY_PEER, SPDM_STATUS_ERROR_PEER, SPDM_STATUS_INVALID_MSG_FIELD,
-    SPDM_STATUS_INVALID_PARAMETER, SPDM_STATUS_NOT_READY_PEER, SPDM_STATUS_SESSION_MSG_ERROR,
+    SPDM_STATUS_INVALID_PARAMETER, SPDM_STATUS_NOT_READY_PEER, SPDM_STATUS_RESET_REQUIRED_PEER,
+    SPDM_STATUS_SESSION_MSG_ERROR,
 };
 use crate::message::*;
 use crate::requester::RequesterContext;
+use crate::time;
+
+// Minimum number of RESPOND_IF_READY requests sent for one ResponseNotReady
+// error before the requester gives up, even if WT_max has elapsed.
+pub const MAX_SPDM_RESPOND_IF_READY_RETRY: usize = 3;
 
 impl<'a> RequesterContext<'a> {
     fn spdm_handle_simple_error_response(
@@ -19,11 +27,13 @@ impl<'a> RequesterContext<'a> {
         error_code: u8,
     ) -> SpdmResult {
         /* NOT_READY is treated as error here.
-         * Use spdm_handle_error_response_main to handle NOT_READY message in long latency command.*/
+         * Use spdm_handle_error_response_respond_if_ready to handle NOT_READY message in long latency command.*/
         if error_code == SpdmErrorCode::SpdmErrorResponseNotReady.get_u8() {
             Err(SPDM_STATUS_NOT_READY_PEER)
         } else if error_code == SpdmErrorCode::SpdmErrorBusy.get_u8() {
             Err(SPDM_STATUS_BUSY_PEER)
+        } else if error_code == SpdmErrorCode::SpdmErrorResetRequired.get_u8() {
+            Err(SPDM_STATUS_RESET_REQUIRED_PEER)
         } else if error_code == SpdmErrorCode::SpdmErrorRequestResynch.get_u8() {
             if let Some(sid) = session_id {
                 let session = if let Some(s) = self.common.get_session_via_id(sid) {
@@ -43,7 +53,7 @@ impl<'a> RequesterContext<'a> {
         &mut self,
         session_id: Option<u32>,
         response: &[u8],
-        _original_request_code: SpdmRequestResponseCode,
+        original_request_code: SpdmRequestResponseCode,
         _expected_response_code: SpdmRequestResponseCode,
     ) -> SpdmResult {
         let mut spdm_message_header_reader = Reader::init(response);
@@ -81,8 +91,128 @@ impl<'a> RequesterContext<'a> {
                 let _ = session.teardown(sid);
             }
             Err(SPDM_STATUS_SESSION_MSG_ERROR)
+        } else if spdm_message_general_payload.param1
+            == SpdmErrorCode::SpdmErrorResponseNotReady.get_u8()
+        {
+            if self
+                .spdm_read_response_not_ready(response, original_request_code)
+                .is_none()
+            {
+                return Err(SPDM_STATUS_INVALID_MSG_FIELD);
+            }
+            Err(SPDM_STATUS_NOT_READY_PEER)
         } else {
             self.spdm_handle_simple_error_response(session_id, spdm_message_general_payload.param1)
         }
     }
+
+    /// Handle an ERROR response to a request which may take long on the responder.
+    ///
+    /// For ResponseNotReady the requester waits for RDT and sends RESPOND_IF_READY,
+    /// until WT_max = RDT * RDTM has elapsed and at least MAX_SPDM_RESPOND_IF_READY_RETRY
+    /// times. The response to the original request is returned, so that the caller
+    /// can process it as if it had been received right away. Any other error is
+    /// handled by spdm_handle_error_response_main.
+    pub fn spdm_handle_error_response_respond_if_ready(
+        &mut self,
+        session_id: Option<u32>,
+        response: &[u8],
+        original_request_code: SpdmRequestResponseCode,
+        expected_response_code: SpdmRequestResponseCode,
+    ) -> SpdmResult<ReceivedMessage> {
+        let mut not_ready = if let Some(not_ready) =
+            self.spdm_read_response_not_ready(response, original_request_code)
+        {
+            not_ready
+        } else {
+            return match self.spdm_handle_error_response_main(
+                session_id,
+                response,
+                original_request_code,
+                expected_response_code,
+            ) {
+                Err(status) => Err(status),
+                Ok(()) => Err(SPDM_STATUS_ERROR_PEER),
+            };
+        };
+
+        // The responder completes the request within WT_max = RDT * RDTM.
+        let wt_max = Self::spdm_response_not_ready_rdt(&not_ready)?
+            .checked_mul(not_ready.rdtm.max(1) as usize)
+            .ok_or(SPDM_STATUS_INVALID_MSG_FIELD)?;
+        let mut waited = 0usize;
+        let mut retry = 0usize;
+        while waited < wt_max || retry < MAX_SPDM_RESPOND_IF_READY_RETRY {
+            let rdt = Self::spdm_response_not_ready_rdt(&not_ready)?;
+            time::sleep(rdt);
+            waited = waited.saturating_add(rdt);
+            retry += 1;
+
+            let received = self.send_receive_spdm_respond_if_ready(
+                session_id,
+                original_request_code,
+                not_ready.token,
+            )?;
+            let received_response = &received.receive_buffer[..received.used];
+
+            let mut reader = Reader::init(received_response);
+            let message_header =
+                SpdmMessageHeader::read(&mut reader).ok_or(SPDM_STATUS_INVALID_MSG_FIELD)?;
+            if message_header.request_response_code == expected_response_code {
+                return Ok(received);
+            }
+            if message_header.request_response_code != SpdmRequestResponseCode::SpdmResponseError {
+                return Err(SPDM_STATUS_ERROR_PEER);
+            }
+
+            not_ready = if let Some(not_ready) =
+                self.spdm_read_response_not_ready(received_response, original_request_code)
+            {
+                not_ready
+            } else {
+                // Let the caller handle any other error.
+                return Ok(received);
+            };
+        }
+
+        error!("!!! respond_if_ready : retry exhausted !!!\n");
+        Err(SPDM_STATUS_NOT_READY_PEER)
+    }
+
+    // RDT = 2^RDTExponent microseconds
+    fn spdm_response_not_ready_rdt(
+        not_ready: &SpdmErrorResponseNotReadyExtData,
+    ) -> SpdmResult<usize> {
+        1usize
+            .checked_shl(not_ready.rdt_exponent as u32)
+            .ok_or(SPDM_STATUS_INVALID_MSG_FIELD)
+    }
+
+    // Return the extended error data if the response is ERROR(ResponseNotReady)
+    // for the original request.
+    fn spdm_read_response_not_ready(
+        &mut self,
+        response: &[u8],
+        original_request_code: SpdmRequestResponseCode,
+    ) -> Option<SpdmErrorResponseNotReadyExtData> {
+        let mut reader = Reader::init(response);
+        let message_header = SpdmMessageHeader::read(&mut reader)?;
+        if message_header.version != self.common.negotiate_info.spdm_version_sel
+            || message_header.request_response_code != SpdmRequestResponseCode::SpdmResponseError
+        {
+            return None;
+        }
+        let error_response = SpdmErrorResponsePayload::spdm_read(&mut self.common, &mut reader)?;
+        if let SpdmErrorResponseExtData::SpdmErrorExtDataNotReady(not_ready) =
+            error_response.extended_data
+        {
+            if not_ready.request_code != original_request_code.get_u8() {
+                error!("!!! response_not_ready : request code mismatch !!!\n");
+                return None;
+            }
+            Some(not_ready)
+        } else {
+            None
+        }
+    }
 }
@@ -33,7 +33,7 @@ impl<'a> RequesterContext<'a> {
     ) -> SpdmResult<u32> {
         info!("send spdm key exchange\n");
 
-        if slot_id >= SPDM_MAX_SLOT_NUMBER as u8 {
+        if slot_id >= SPDM_MAX_SLOT_NUMBER as u8 && !self.is_rsp_public_key_slot_id(slot_id) {
             return Err(SPDM_STATUS_INVALID_PARAMETER);
         }
 
@@ -76,9 +76,12 @@ impl<'a> RequesterContext<'a> {
         let mut random = [0u8; SPDM_RANDOM_SIZE];
         crypto::rand::get_random(&mut random)?;
 
-        let (exchange, key_exchange_context) =
+        let (exchange, key_exchange_context) = if self.common.negotiate_info.kem_sel.is_empty() {
             crypto::dhe::generate_key_pair(self.common.negotiate_info.dhe_sel)
-                .ok_or(SPDM_STATUS_CRYPTO_ERROR)?;
+        } else {
+            crypto::dhe::kem_generate_key_pair(self.common.negotiate_info.kem_sel)
+        }
+        .ok_or(SPDM_STATUS_CRYPTO_ERROR)?;
 
         debug!("!!! exchange data : {:02x?}\n", exchange);
 
@@ -217,7 +220,7 @@ impl<'a> RequesterContext<'a> {
                             let spdm_version_sel = self.common.negotiate_info.spdm_version_sel;
                             let message_a = self.common.runtime_info.message_a.clone();
                             let cert_chain_hash =
-                                self.common.get_certchain_hash_peer(false, slot_id as usize);
+                                self.common.get_certchain_hash_peer(false, slot_id);
                             if cert_chain_hash.is_none() {
                                 return Err(SPDM_STATUS_INVALID_MSG_FIELD);
                             }
@@ -243,8 +246,13 @@ impl<'a> RequesterContext<'a> {
                                 {
                                     return Err(SPDM_STATUS_INVALID_MSG_FIELD);
                                 }
+                                let req_slot_id = key_exchange_rsp.req_slot_id & 0xf;
                                 self.common.runtime_info.set_local_used_cert_chain_slot_id(
-                                    key_exchange_rsp.req_slot_id & 0xf,
+                                    if req_slot_id == SPDM_PUBLIC_KEY_SLOT_ID_PARAM {
+                                        SPDM_PUBLIC_KEY_SLOT_ID
+                                    } else {
+                                        req_slot_id
+                                    },
                                 );
                             }
 
@@ -256,6 +264,7 @@ impl<'a> RequesterContext<'a> {
                             session.setup(session_id)?;
 
                             session.set_use_psk(false);
+                            session.set_slot_id(slot_id);
                             session.set_mut_auth_requested(key_exchange_rsp.mut_auth_req);
 
                             session.set_crypto_param(
@@ -271,8 +280,7 @@ impl<'a> RequesterContext<'a> {
                             session.runtime_info.req_cert_hash = None;
 
                             // create transcript
-                            let base_asym_size =
-                                self.common.negotiate_info.base_asym_sel.get_size() as usize;
+                            let base_asym_size = self.common.get_asym_key_size() as usize;
                             let base_hash_size =
                                 self.common.negotiate_info.base_hash_sel.get_size() as usize;
                             let temp_receive_used = if in_clear_text {
@@ -397,16 +405,20 @@ impl<'a> RequesterContext<'a> {
                         }
                     }
                     SpdmRequestResponseCode::SpdmResponseError => {
-                        let status = self.spdm_handle_error_response_main(
+                        let received = self.spdm_handle_error_response_respond_if_ready(
                             None,
                             receive_buffer,
                             SpdmRequestResponseCode::SpdmRequestKeyExchange,
                             SpdmRequestResponseCode::SpdmResponseKeyExchangeRsp,
-                        );
-                        match status {
-                            Err(status) => Err(status),
-                            Ok(()) => Err(SPDM_STATUS_ERROR_PEER),
-                        }
+                        )?;
+                        self.handle_spdm_key_exhcange_response(
+                            req_session_id,
+                            slot_id,
+                            send_buffer,
+                            &received.receive_buffer[..received.used],
+                            measurement_summary_hash_type,
+                            key_exchange_context,
+                        )
                     }
                     _ => Err(SPDM_STATUS_ERROR_PEER),
                 }
@@ -428,27 +440,27 @@ impl<'a> RequesterContext<'a> {
 
         debug!("message_hash - {:02x?}", transcript_hash.as_ref());
 
-        if self.common.peer_info.peer_cert_chain[slot_id as usize].is_none() {
-            error!("peer_cert_chain is not populated!\n");
+        if self
+            .common
+            .get_peer_cert_chain_or_public_key(slot_id)
+            .is_none()
+        {
+            error!(
+                "peer_cert_chain is not populated!
+"
+            );
             return Err(SPDM_STATUS_INVALID_PARAMETER);
         }
 
-        let cert_chain_data = &self.common.peer_info.peer_cert_chain[slot_id as usize]
-            .as_ref()
-            .ok_or(SPDM_STATUS_INVALID_PARAMETER)?
-            .data[(4usize + self.common.negotiate_info.base_hash_sel.get_size() as usize)
-            ..(self.common.peer_info.peer_cert_chain[slot_id as usize]
-                .as_ref()
-                .ok_or(SPDM_STATUS_INVALID_PARAMETER)?
-                .data_size as usize)];
-
         let mut message_sign = ManagedBuffer12Sign::default();
         if self.common.negotiate_info.spdm_version_sel.get_u8()
             >= SpdmVersion::SpdmVersion12.get_u8()
         {
             message_sign.reset_message();
             message_sign
-                .append_message(&SPDM_VERSION_1_2_SIGNING_PREFIX_CONTEXT)
+                .append_message(spdm_signing_prefix_context(
+                    self.common.negotiate_info.spdm_version_sel,
+                ))
                 .ok_or(SPDM_STATUS_BUFFER_FULL)?;
             message_sign
                 .append_message(&SPDM_VERSION_1_2_SIGNING_CONTEXT_ZEROPAD_2)
@@ -464,10 +476,9 @@ impl<'a> RequesterContext<'a> {
             return Err(SPDM_STATUS_INVALID_STATE_LOCAL);
         }
 
-        crypto::asym_verify::verify(
-            self.common.negotiate_info.base_hash_sel,
-            self.common.negotiate_info.base_asym_sel,
-            cert_chain_data,
+        self.common.verify_peer_signature(
+            slot_id,
+            &SPDM_KEY_EXCHANGE_RESPONSE_SIGN_CONTEXT,
             message_sign.as_ref(),
             signature,
         )
@@ -487,20 +498,18 @@ impl<'a> RequesterContext<'a> {
         // we just print message hash for debug purpose
         debug!("message_hash - {:02x?}", message_hash.as_ref());
 
-        if self.common.peer_info.peer_cert_chain[slot_id as usize].is_none() {
-            error!("peer_cert_chain is not populated!\n");
+        if self
+            .common
+            .get_peer_cert_chain_or_public_key(slot_id)
+            .is_none()
+        {
+            error!(
+                "peer_cert_chain is not populated!
+"
+            );
             return Err(SPDM_STATUS_INVALID_PARAMETER);
         }
 
-        let cert_chain_data = &self.common.peer_info.peer_cert_chain[slot_id as usize]
-            .as_ref()
-            .ok_or(SPDM_STATUS_INVALID_PARAMETER)?
-            .data[(4usize + self.common.negotiate_info.base_hash_sel.get_size() as usize)
-            ..(self.common.peer_info.peer_cert_chain[slot_id as usize]
-                .as_ref()
-                .ok_or(SPDM_STATUS_INVALID_PARAMETER)?
-                .data_size as usize)];
-
         let mut message = self.common.calc_req_transcript_data(
             false,
             slot_id,
@@ -514,7 +523,9 @@ impl<'a> RequesterContext<'a> {
         {
             message.reset_message();
             message
-                .append_message(&SPDM_VERSION_1_2_SIGNING_PREFIX_CONTEXT)
+                .append_message(spdm_signing_prefix_context(
+                    self.common.negotiate_info.spdm_version_sel,
+                ))
                 .ok_or(SPDM_STATUS_BUFFER_FULL)?;
             message
                 .append_message(&SPDM_VERSION_1_2_SIGNING_CONTEXT_ZEROPAD_2)
@@ -527,10 +538,9 @@ impl<'a> RequesterContext<'a> {
                 .ok_or(SPDM_STATUS_BUFFER_FULL)?;
         }
 
-        crypto::asym_verify::verify(
-            self.common.negotiate_info.base_hash_sel,
-            self.common.negotiate_info.base_asym_sel,
-            cert_chain_data,
+        self.common.verify_peer_signature(
+            slot_id,
+            &SPDM_KEY_EXCHANGE_RESPONSE_SIGN_CONTEXT,
             message.as_ref(),
             signature,
         )
@@ -0,0 +1,239 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use crate::error::{
+    SpdmResult, SPDM_STATUS_ERROR_PEER, SPDM_STATUS_INVALID_MSG_FIELD, SPDM_STATUS_UNSUPPORTED_CAP,
+};
+use crate::message::*;
+use crate::protocol::*;
+use crate::requester::*;
+
+impl<'a> RequesterContext<'a> {
+    pub fn send_receive_spdm_get_key_pair_info(
+        &mut self,
+        session_id: Option<u32>,
+        key_pair_id: u8,
+    ) -> SpdmResult<SpdmKeyPairInfoResponsePayload> {
+        if self.common.negotiate_info.spdm_version_sel.get_u8()
+            < SpdmVersion::SpdmVersion13.get_u8()
+            || !self
+                .common
+                .negotiate_info
+                .rsp_capabilities_sel
+                .contains(SpdmResponseCapabilityFlags::GET_KEY_PAIR_INFO_CAP)
+        {
+            return Err(SPDM_STATUS_UNSUPPORTED_CAP);
+        }
+
+        self.common.reset_buffer_via_request_code(
+            SpdmRequestResponseCode::SpdmRequestGetKeyPairInfo,
+            session_id,
+        );
+
+        info!("send spdm get_key_pair_info\n");
+        let mut send_buffer = [0u8; config::MAX_SPDM_MSG_SIZE];
+        let send_used = self.encode_spdm_get_key_pair_info(key_pair_id, &mut send_buffer)?;
+
+        match session_id {
+            Some(session_id) => {
+                self.send_secured_message(session_id, &send_buffer[..send_used], false)?;
+            }
+            None => {
+                self.send_message(&send_buffer[..send_used])?;
+            }
+        }
+
+        let mut receive_buffer = [0u8; config::MAX_SPDM_MSG_SIZE];
+        let used = match session_id {
+            Some(session_id) => {
+                self.receive_secured_message(session_id, &mut receive_buffer, false)?
+            }
+            None => self.receive_message(&mut receive_buffer, false)?,
+        };
+
+        self.handle_spdm_key_pair_info_response(session_id, key_pair_id, &receive_buffer[..used])
+    }
+
+    pub fn encode_spdm_get_key_pair_info(
+        &mut self,
+        key_pair_id: u8,
+        buf: &mut [u8],
+    ) -> SpdmResult<usize> {
+        let mut writer = Writer::init(buf);
+        let request = SpdmMessage {
+            header: SpdmMessageHeader {
+                version: self.common.negotiate_info.spdm_version_sel,
+                request_response_code: SpdmRequestResponseCode::SpdmRequestGetKeyPairInfo,
+            },
+            payload: SpdmMessagePayload::SpdmGetKeyPairInfoRequest(
+                SpdmGetKeyPairInfoRequestPayload { key_pair_id },
+            ),
+        };
+        request.spdm_encode(&mut self.common, &mut writer)
+    }
+
+    pub fn handle_spdm_key_pair_info_response(
+        &mut self,
+        session_id: Option<u32>,
+        key_pair_id: u8,
+        receive_buffer: &[u8],
+    ) -> SpdmResult<SpdmKeyPairInfoResponsePayload> {
+        let mut reader = Reader::init(receive_buffer);
+        match SpdmMessageHeader::read(&mut reader) {
+            Some(message_header) => {
+                if message_header.version != self.common.negotiate_info.spdm_version_sel {
+                    return Err(SPDM_STATUS_INVALID_MSG_FIELD);
+                }
+                match message_header.request_response_code {
+                    SpdmRequestResponseCode::SpdmResponseKeyPairInfo => {
+                        let key_pair_info = SpdmKeyPairInfoResponsePayload::spdm_read(
+                            &mut self.common,
+                            &mut reader,
+                        );
+                        if let Some(key_pair_info) = key_pair_info {
+                            debug!("!!! key_pair_info : {:02x?}\n", key_pair_info);
+                            if key_pair_info.key_pair_id != key_pair_id {
+                                return Err(SPDM_STATUS_INVALID_MSG_FIELD);
+                            }
+                            Ok(key_pair_info)
+                        } else {
+                            error!("!!! key_pair_info : fail !!!\n");
+                            Err(SPDM_STATUS_INVALID_MSG_FIELD)
+                        }
+                    }
+                    SpdmRequestResponseCode::SpdmResponseError => {
+                        let status = self.spdm_handle_error_response_main(
+                            session_id,
+                            receive_buffer,
+                            SpdmRequestResponseCode::SpdmRequestGetKeyPairInfo,
+                            SpdmRequestResponseCode::SpdmResponseKeyPairInfo,
+                        );
+                        match status {
+                            Err(status) => Err(status),
+                            Ok(()) => Err(SPDM_STATUS_ERROR_PEER),
+                        }
+                    }
+                    _ => Err(SPDM_STATUS_ERROR_PEER),
+                }
+            }
+            None => Err(SPDM_STATUS_INVALID_MSG_FIELD),
+        }
+    }
+
+    pub fn send_receive_spdm_set_key_pair_info(
+        &mut self,
+        session_id: Option<u32>,
+        operation: SpdmSetKeyPairInfoOperation,
+        key_pair_id: u8,
+        desired_key_usage: SpdmKeyUsageMask,
+        desired_asym_algo: SpdmKeyPairAsymAlgo,
+        desired_assoc_cert_slot_mask: u8,
+    ) -> SpdmResult {
+        if self.common.negotiate_info.spdm_version_sel.get_u8()
+            < SpdmVersion::SpdmVersion13.get_u8()
+            || !self
+                .common
+                .negotiate_info
+                .rsp_capabilities_sel
+                .contains(SpdmResponseCapabilityFlags::SET_KEY_PAIR_INFO_CAP)
+        {
+            return Err(SPDM_STATUS_UNSUPPORTED_CAP);
+        }
+
+        self.common.reset_buffer_via_request_code(
+            SpdmRequestResponseCode::SpdmRequestSetKeyPairInfo,
+            session_id,
+        );
+
+        info!("send spdm set_key_pair_info\n");
+        let mut send_buffer = [0u8; config::MAX_SPDM_MSG_SIZE];
+        let send_used = self.encode_spdm_set_key_pair_info(
+            SpdmSetKeyPairInfoRequestPayload {
+                operation,
+                key_pair_id,
+                desired_key_usage,
+                desired_asym_algo,
+                desired_assoc_cert_slot_mask,
+            },
+            &mut send_buffer,
+        )?;
+
+        match session_id {
+            Some(session_id) => {
+                self.send_secured_message(session_id, &send_buffer[..send_used], false)?;
+            }
+            None => {
+                self.send_message(&send_buffer[..send_used])?;
+            }
+        }
+
+        let mut receive_buffer = [0u8; config::MAX_SPDM_MSG_SIZE];
+        let used = match session_id {
+            Some(session_id) => {
+                self.receive_secured_message(session_id, &mut receive_buffer, false)?
+            }
+            None => self.receive_message(&mut receive_buffer, false)?,
+        };
+
+        self.handle_spdm_set_key_pair_info_ack_response(session_id, &receive_buffer[..used])
+    }
+
+    pub fn encode_spdm_set_key_pair_info(
+        &mut self,
+        set_key_pair_info: SpdmSetKeyPairInfoRequestPayload,
+        buf: &mut [u8],
+    ) -> SpdmResult<usize> {
+        let mut writer = Writer::init(buf);
+        let request = SpdmMessage {
+            header: SpdmMessageHeader {
+                version: self.common.negotiate_info.spdm_version_sel,
+                request_response_code: SpdmRequestResponseCode::SpdmRequestSetKeyPairInfo,
+            },
+            payload: SpdmMessagePayload::SpdmSetKeyPairInfoRequest(set_key_pair_info),
+        };
+        request.spdm_encode(&mut self.common, &mut writer)
+    }
+
+    pub fn handle_spdm_set_key_pair_info_ack_response(
+        &mut self,
+        session_id: Option<u32>,
+        receive_buffer: &[u8],
+    ) -> SpdmResult {
+        let mut reader = Reader::init(receive_buffer);
+        match SpdmMessageHeader::read(&mut reader) {
+            Some(message_header) => {
+                if message_header.version != self.common.negotiate_info.spdm_version_sel {
+                    return Err(SPDM_STATUS_INVALID_MSG_FIELD);
+                }
+                match message_header.request_response_code {
+                    SpdmRequestResponseCode::SpdmResponseSetKeyPairInfoAck => {
+                        let set_key_pair_info_ack = SpdmSetKeyPairInfoAckResponsePayload::spdm_read(
+                            &mut self.common,
+                            &mut reader,
+                        );
+                        if let Some(set_key_pair_info_ack) = set_key_pair_info_ack {
+                            debug!(
+                                "!!! set_key_pair_info_ack : {:02x?}\n",
+                                set_key_pair_info_ack
+                            );
+                            Ok(())
+                        } else {
+                            error!("!!! set_key_pair_info_ack : fail !!!\n");
+                            Err(SPDM_STATUS_INVALID_MSG_FIELD)
+                        }
+                    }
+                    SpdmRequestResponseCode::SpdmResponseError => self
+                        .spdm_handle_error_response_main(
+                            session_id,
+                            receive_buffer,
+                            SpdmRequestResponseCode::SpdmRequestSetKeyPairInfo,
+                            SpdmRequestResponseCode::SpdmResponseSetKeyPairInfoAck,
+                        ),
+                    _ => Err(SPDM_STATUS_ERROR_PEER),
+                }
+            }
+            None => Err(SPDM_STATUS_INVALID_MSG_FIELD),
+        }
+    }
+}
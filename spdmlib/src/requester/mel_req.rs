@@ -0,0 +1,193 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use crate::error::{
+    SpdmResult, SPDM_STATUS_ERROR_PEER, SPDM_STATUS_INVALID_MSG_FIELD, SPDM_STATUS_UNSUPPORTED_CAP,
+};
+use crate::message::*;
+use crate::protocol::*;
+use crate::requester::*;
+
+impl<'a> RequesterContext<'a> {
+    // Retrieve the whole measurement extension log, MAX_SPDM_MEL_PORTION_LEN
+    // bytes at a time.
+    pub fn send_receive_spdm_measurement_extension_log(
+        &mut self,
+        session_id: Option<u32>,
+    ) -> SpdmResult<SpdmMeasurementExtensionLog> {
+        if self.common.negotiate_info.spdm_version_sel.get_u8()
+            < SpdmVersion::SpdmVersion13.get_u8()
+            || !self
+                .common
+                .negotiate_info
+                .rsp_capabilities_sel
+                .contains(SpdmResponseCapabilityFlags::MEL_CAP)
+        {
+            return Err(SPDM_STATUS_UNSUPPORTED_CAP);
+        }
+
+        self.common.reset_buffer_via_request_code(
+            SpdmRequestResponseCode::SpdmRequestGetMeasurementExtensionLog,
+            session_id,
+        );
+
+        let mut mel = SpdmMeasurementExtensionLog::default();
+        let mut offset = 0u32;
+        let mut length = MAX_SPDM_MEL_PORTION_LEN as u32;
+        let mut total_size = 0u32;
+        while length != 0 {
+            let (portion_length, remainder_length) = self
+                .send_receive_spdm_measurement_extension_log_partial(
+                    session_id, &mut mel, total_size, offset, length,
+                )?;
+            if total_size == 0 {
+                total_size = portion_length + remainder_length;
+            }
+            offset += portion_length;
+            length = remainder_length;
+            if length > MAX_SPDM_MEL_PORTION_LEN as u32 {
+                length = MAX_SPDM_MEL_PORTION_LEN as u32;
+            }
+        }
+        Ok(mel)
+    }
+
+    fn send_receive_spdm_measurement_extension_log_partial(
+        &mut self,
+        session_id: Option<u32>,
+        mel: &mut SpdmMeasurementExtensionLog,
+        total_size: u32,
+        offset: u32,
+        length: u32,
+    ) -> SpdmResult<(u32, u32)> {
+        info!("send spdm get_measurement_extension_log\n");
+        let mut send_buffer = [0u8; config::MAX_SPDM_MSG_SIZE];
+        let send_used =
+            self.encode_spdm_measurement_extension_log(offset, length, &mut send_buffer)?;
+
+        match session_id {
+            Some(session_id) => {
+                self.send_secured_message(session_id, &send_buffer[..send_used], false)?;
+            }
+            None => {
+                self.send_message(&send_buffer[..send_used])?;
+            }
+        }
+
+        let mut receive_buffer = [0u8; config::MAX_SPDM_MSG_SIZE];
+        let used = match session_id {
+            Some(session_id) => {
+                self.receive_secured_message(session_id, &mut receive_buffer, false)?
+            }
+            None => self.receive_message(&mut receive_buffer, false)?,
+        };
+
+        self.handle_spdm_measurement_extension_log_response(
+            session_id,
+            mel,
+            total_size,
+            offset,
+            length,
+            &receive_buffer[..used],
+        )
+    }
+
+    pub fn encode_spdm_measurement_extension_log(
+        &mut self,
+        offset: u32,
+        length: u32,
+        buf: &mut [u8],
+    ) -> SpdmResult<usize> {
+        let mut writer = Writer::init(buf);
+        let request = SpdmMessage {
+            header: SpdmMessageHeader {
+                version: self.common.negotiate_info.spdm_version_sel,
+                request_response_code:
+                    SpdmRequestResponseCode::SpdmRequestGetMeasurementExtensionLog,
+            },
+            payload: SpdmMessagePayload::SpdmGetMeasurementExtensionLogRequest(
+                SpdmGetMeasurementExtensionLogRequestPayload { offset, length },
+            ),
+        };
+        request.spdm_encode(&mut self.common, &mut writer)
+    }
+
+    pub fn handle_spdm_measurement_extension_log_response(
+        &mut self,
+        session_id: Option<u32>,
+        mel: &mut SpdmMeasurementExtensionLog,
+        total_size: u32,
+        offset: u32,
+        length: u32,
+        receive_buffer: &[u8],
+    ) -> SpdmResult<(u32, u32)> {
+        let mut reader = Reader::init(receive_buffer);
+        match SpdmMessageHeader::read(&mut reader) {
+            Some(message_header) => {
+                if message_header.version != self.common.negotiate_info.spdm_version_sel {
+                    return Err(SPDM_STATUS_INVALID_MSG_FIELD);
+                }
+                match message_header.request_response_code {
+                    SpdmRequestResponseCode::SpdmResponseMeasurementExtensionLog => {
+                        let mel_rsp = SpdmMeasurementExtensionLogResponsePayload::spdm_read(
+                            &mut self.common,
+                            &mut reader,
+                        );
+                        if let Some(mel_rsp) = mel_rsp {
+                            debug!("!!! measurement_extension_log : {:02x?}\n", mel_rsp);
+
+                            let max_size = config::MAX_SPDM_MEASUREMENT_EXTENSION_LOG_SIZE as u32;
+                            if mel_rsp.portion_length > length
+                                || mel_rsp.portion_length > max_size - offset
+                            {
+                                return Err(SPDM_STATUS_INVALID_MSG_FIELD);
+                            }
+                            if mel_rsp.remainder_length > max_size - offset - mel_rsp.portion_length
+                            {
+                                return Err(SPDM_STATUS_INVALID_MSG_FIELD);
+                            }
+                            if total_size != 0
+                                && total_size
+                                    != offset + mel_rsp.portion_length + mel_rsp.remainder_length
+                            {
+                                return Err(SPDM_STATUS_INVALID_MSG_FIELD);
+                            }
+                            // An empty portion before the end of the log would never finish.
+                            if mel_rsp.portion_length == 0 && mel_rsp.remainder_length != 0 {
+                                return Err(SPDM_STATUS_INVALID_MSG_FIELD);
+                            }
+
+                            mel.data[(offset as usize)
+                                ..(offset as usize + mel_rsp.portion_length as usize)]
+                                .copy_from_slice(
+                                    &mel_rsp.measurement_extension_log
+                                        [0..(mel_rsp.portion_length as usize)],
+                                );
+                            mel.data_size = offset + mel_rsp.portion_length;
+
+                            Ok((mel_rsp.portion_length, mel_rsp.remainder_length))
+                        } else {
+                            error!("!!! measurement_extension_log : fail !!!\n");
+                            Err(SPDM_STATUS_INVALID_MSG_FIELD)
+                        }
+                    }
+                    SpdmRequestResponseCode::SpdmResponseError => {
+                        let status = self.spdm_handle_error_response_main(
+                            session_id,
+                            receive_buffer,
+                            SpdmRequestResponseCode::SpdmRequestGetMeasurementExtensionLog,
+                            SpdmRequestResponseCode::SpdmResponseMeasurementExtensionLog,
+                        );
+                        match status {
+                            Err(status) => Err(status),
+                            Ok(()) => Err(SPDM_STATUS_ERROR_PEER),
+                        }
+                    }
+                    _ => Err(SPDM_STATUS_ERROR_PEER),
+                }
+            }
+            None => Err(SPDM_STATUS_INVALID_MSG_FIELD),
+        }
+    }
+}
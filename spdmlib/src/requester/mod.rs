@@ -5,30 +5,43 @@
 mod context;
 
 mod challenge_req;
+mod chunk_req;
 #[cfg(feature = "mut-auth")]
 mod encap_certificate;
 #[cfg(feature = "mut-auth")]
+mod encap_challenge;
+#[cfg(feature = "mut-auth")]
 mod encap_digest;
 #[cfg(feature = "mut-auth")]
 mod encap_error;
 #[cfg(feature = "mut-auth")]
+mod encap_key_update;
+#[cfg(feature = "mut-auth")]
 mod encap_req;
 mod end_session_req;
+mod endpoint_info_req;
+pub mod event_handler;
+mod event_req;
 mod finish_req;
 mod get_capabilities_req;
 mod get_certificate_req;
+mod get_csr_req;
 mod get_digests_req;
 pub mod get_measurements_req;
 mod get_version_req;
 mod handle_error_response_req;
 mod heartbeat_req;
 mod key_exchange_req;
+mod key_pair_info_req;
 pub mod key_update_req;
+mod mel_req;
 #[cfg(feature = "mut-auth")]
 mod mutual_authenticate;
 mod negotiate_algorithms_req;
 mod psk_exchange_req;
 mod psk_finish_req;
+mod respond_if_ready_req;
+mod set_certificate_req;
 mod vendor_req;
 
 pub use context::RequesterContext;
@@ -23,9 +23,18 @@ impl<'a> RequesterContext<'a> {
             SpdmKeyExchangeMutAuthAttributes::MUT_AUTH_REQ => Ok(()),
             SpdmKeyExchangeMutAuthAttributes::MUT_AUTH_REQ_WITH_ENCAP_REQUEST
             | SpdmKeyExchangeMutAuthAttributes::MUT_AUTH_REQ_WITH_GET_DIGESTS => {
-                self.get_encapsulated_request_response(session_id, mut_auth_requested)
+                self.get_encapsulated_request_response(Some(session_id), mut_auth_requested)
             }
             _ => Err(SPDM_STATUS_INVALID_MSG_FIELD),
         }
     }
+
+    pub fn basic_mutual_authenticate(&mut self) -> SpdmResult<()> {
+        self.common.construct_my_cert_chain()?;
+
+        self.get_encapsulated_request_response(
+            None,
+            SpdmKeyExchangeMutAuthAttributes::MUT_AUTH_REQ_WITH_ENCAP_REQUEST,
+        )
+    }
 }
@@ -2,6 +2,7 @@
 //
 // SPDX-License-Identifier: BSD-2-Clause-Patent
 
+use crate::crypto;
 use crate::error::{
     SpdmResult, SPDM_STATUS_ERROR_PEER, SPDM_STATUS_INVALID_MSG_FIELD, SPDM_STATUS_NEGOTIATION_FAIL,
 };
@@ -27,7 +28,68 @@ impl<'a> RequesterContext<'a> {
     }
 
     pub fn encode_spdm_algorithm(&mut self, buf: &mut [u8]) -> SpdmResult<usize> {
-        let other_params_support: SpdmOpaqueSupport = self.common.config_info.opaque_support;
+        let mut other_params_support: SpdmOpaqueSupport = self.common.config_info.opaque_support;
+        if self.common.negotiate_info.spdm_version_sel.get_u8()
+            < SpdmVersion::SpdmVersion13.get_u8()
+        {
+            other_params_support.remove(SpdmOpaqueSupport::MULTI_KEY_CONN);
+        }
+
+        // ExtAsymCount + ExtHashCount must not exceed SPDM_MAX_EXT_ALG_COUNT.
+        let mut ext_asym = [SpdmExtAlgStruct::default(); SPDM_MAX_EXT_ALG_COUNT];
+        let mut ext_asym_count = 0;
+        for ext_asym_algo in self.common.config_info.ext_asym_algo.iter().flatten() {
+            ext_asym[ext_asym_count] = *ext_asym_algo;
+            ext_asym_count += 1;
+        }
+        let mut ext_hash = [SpdmExtAlgStruct::default(); SPDM_MAX_EXT_ALG_COUNT];
+        let mut ext_hash_count = 0;
+        for ext_hash_algo in self
+            .common
+            .config_info
+            .ext_hash_algo
+            .iter()
+            .flatten()
+            .take(SPDM_MAX_EXT_ALG_COUNT - ext_asym_count)
+        {
+            ext_hash[ext_hash_count] = *ext_hash_algo;
+            ext_hash_count += 1;
+        }
+
+        let mut alg_struct = [
+            SpdmAlgStruct {
+                alg_type: SpdmAlgType::SpdmAlgTypeDHE,
+                alg_supported: SpdmAlg::SpdmAlgoDhe(self.common.config_info.dhe_algo),
+            },
+            SpdmAlgStruct {
+                alg_type: SpdmAlgType::SpdmAlgTypeAEAD,
+                alg_supported: SpdmAlg::SpdmAlgoAead(self.common.config_info.aead_algo),
+            },
+            SpdmAlgStruct {
+                alg_type: SpdmAlgType::SpdmAlgTypeReqAsym,
+                alg_supported: SpdmAlg::SpdmAlgoReqAsym(self.common.get_supported_req_asym_algo()),
+            },
+            SpdmAlgStruct {
+                alg_type: SpdmAlgType::SpdmAlgTypeKeySchedule,
+                alg_supported: SpdmAlg::SpdmAlgoKeySchedule(
+                    self.common.config_info.key_schedule_algo,
+                ),
+            },
+            #[cfg(feature = "pqc")]
+            SpdmAlgStruct::default(),
+        ];
+        let mut alg_struct_count = 4;
+        let mut pqc_asym_algo = SpdmPqcAsymAlgo::empty();
+        if self.common.is_pqc_supported() {
+            pqc_asym_algo = self.common.config_info.pqc_asym_algo;
+            if !self.common.config_info.kem_algo.is_empty() {
+                alg_struct[alg_struct_count] = SpdmAlgStruct {
+                    alg_type: SpdmAlgType::SpdmAlgTypeKEM,
+                    alg_supported: SpdmAlg::SpdmAlgoKem(self.common.config_info.kem_algo),
+                };
+                alg_struct_count += 1;
+            }
+        }
 
         let mut writer = Writer::init(buf);
         let request = SpdmMessage {
@@ -39,31 +101,16 @@ impl<'a> RequesterContext<'a> {
                 SpdmNegotiateAlgorithmsRequestPayload {
                     measurement_specification: self.common.config_info.measurement_specification,
                     other_params_support,
-                    base_asym_algo: self.common.config_info.base_asym_algo,
+                    base_asym_algo: self.common.get_supported_base_asym_algo(),
                     base_hash_algo: self.common.config_info.base_hash_algo,
-                    alg_struct_count: 4,
-                    alg_struct: [
-                        SpdmAlgStruct {
-                            alg_type: SpdmAlgType::SpdmAlgTypeDHE,
-                            alg_supported: SpdmAlg::SpdmAlgoDhe(self.common.config_info.dhe_algo),
-                        },
-                        SpdmAlgStruct {
-                            alg_type: SpdmAlgType::SpdmAlgTypeAEAD,
-                            alg_supported: SpdmAlg::SpdmAlgoAead(self.common.config_info.aead_algo),
-                        },
-                        SpdmAlgStruct {
-                            alg_type: SpdmAlgType::SpdmAlgTypeReqAsym,
-                            alg_supported: SpdmAlg::SpdmAlgoReqAsym(
-                                self.common.config_info.req_asym_algo,
-                            ),
-                        },
-                        SpdmAlgStruct {
-                            alg_type: SpdmAlgType::SpdmAlgTypeKeySchedule,
-                            alg_supported: SpdmAlg::SpdmAlgoKeySchedule(
-                                self.common.config_info.key_schedule_algo,
-                            ),
-                        },
-                    ],
+                    pqc_asym_algo,
+                    mel_specification: self.common.config_info.mel_specification,
+                    ext_asym_count: ext_asym_count as u8,
+                    ext_asym,
+                    ext_hash_count: ext_hash_count as u8,
+                    ext_hash,
+                    alg_struct_count: alg_struct_count as u8,
+                    alg_struct,
                 },
             ),
         };
@@ -93,8 +140,10 @@ impl<'a> RequesterContext<'a> {
                             self.common.negotiate_info.measurement_specification_sel =
                                 algorithms.measurement_specification_sel;
 
-                            self.common.negotiate_info.opaque_data_support =
-                                algorithms.other_params_selection;
+                            self.common.negotiate_info.opaque_data_support = algorithms
+                                .other_params_selection
+                                - SpdmOpaqueSupport::MULTI_KEY_CONN;
+                            self.set_multi_key_conn(algorithms.other_params_selection)?;
 
                             self.common.negotiate_info.measurement_hash_sel =
                                 algorithms.measurement_hash_algo;
@@ -102,10 +151,50 @@ impl<'a> RequesterContext<'a> {
                                 return Err(SPDM_STATUS_NEGOTIATION_FAIL);
                             }
                             self.common.negotiate_info.base_hash_sel = algorithms.base_hash_sel;
-                            if algorithms.base_asym_sel.bits() == 0 {
+                            if algorithms.ext_hash_sel.is_some() {
+                                return Err(SPDM_STATUS_NEGOTIATION_FAIL);
+                            }
+                            match algorithms.ext_asym_sel {
+                                Some(ext_asym_sel) => {
+                                    if !self
+                                        .common
+                                        .config_info
+                                        .ext_asym_algo
+                                        .contains(&Some(ext_asym_sel))
+                                        || !crypto::ext_asym::is_supported(ext_asym_sel)
+                                    {
+                                        return Err(SPDM_STATUS_NEGOTIATION_FAIL);
+                                    }
+                                }
+                                None => {
+                                    if algorithms.base_asym_sel.bits() == 0
+                                        && algorithms.pqc_asym_sel.bits() == 0
+                                    {
+                                        return Err(SPDM_STATUS_NEGOTIATION_FAIL);
+                                    }
+                                }
+                            }
+                            if !self
+                                .common
+                                .config_info
+                                .pqc_asym_algo
+                                .contains(algorithms.pqc_asym_sel)
+                            {
+                                return Err(SPDM_STATUS_NEGOTIATION_FAIL);
+                            }
+                            if !self
+                                .common
+                                .get_supported_base_asym_algo()
+                                .contains(algorithms.base_asym_sel)
+                            {
                                 return Err(SPDM_STATUS_NEGOTIATION_FAIL);
                             }
                             self.common.negotiate_info.base_asym_sel = algorithms.base_asym_sel;
+                            self.common.negotiate_info.ext_asym_sel = algorithms.ext_asym_sel;
+                            self.common.negotiate_info.pqc_asym_sel = algorithms.pqc_asym_sel;
+                            self.common.negotiate_info.kem_sel = SpdmKemAlgo::empty();
+                            self.common.negotiate_info.mel_specification_sel =
+                                algorithms.mel_specification_sel;
                             for alg in algorithms
                                 .alg_struct
                                 .iter()
@@ -119,11 +208,20 @@ impl<'a> RequesterContext<'a> {
                                         self.common.negotiate_info.aead_sel = *v
                                     }
                                     SpdmAlg::SpdmAlgoReqAsym(v) => {
+                                        if !self.common.get_supported_req_asym_algo().contains(*v) {
+                                            return Err(SPDM_STATUS_NEGOTIATION_FAIL);
+                                        }
                                         self.common.negotiate_info.req_asym_sel = *v
                                     }
                                     SpdmAlg::SpdmAlgoKeySchedule(v) => {
                                         self.common.negotiate_info.key_schedule_sel = *v
                                     }
+                                    SpdmAlg::SpdmAlgoKem(v) => {
+                                        if !self.common.config_info.kem_algo.contains(*v) {
+                                            return Err(SPDM_STATUS_NEGOTIATION_FAIL);
+                                        }
+                                        self.common.negotiate_info.kem_sel = *v
+                                    }
                                     SpdmAlg::SpdmAlgoUnknown(_v) => {}
                                 }
                             }
@@ -149,4 +247,43 @@ impl<'a> RequesterContext<'a> {
             None => Err(SPDM_STATUS_INVALID_MSG_FIELD),
         }
     }
+
+    // The requester selects the multi-key connection of the responder with
+    // MULTI_KEY_CAP_NEG, and the responder selects the one of the requester.
+    fn set_multi_key_conn(&mut self, other_params_selection: SpdmOpaqueSupport) -> SpdmResult {
+        if self.common.negotiate_info.spdm_version_sel.get_u8()
+            < SpdmVersion::SpdmVersion13.get_u8()
+        {
+            self.common.negotiate_info.local_multi_key_conn = false;
+            self.common.negotiate_info.peer_multi_key_conn = false;
+            return Ok(());
+        }
+
+        let rsp_capabilities = self.common.negotiate_info.rsp_capabilities_sel;
+        self.common.negotiate_info.peer_multi_key_conn = rsp_capabilities
+            .contains(SpdmResponseCapabilityFlags::MULTI_KEY_CAP_ONLY)
+            || (rsp_capabilities.contains(SpdmResponseCapabilityFlags::MULTI_KEY_CAP_NEG)
+                && self
+                    .common
+                    .config_info
+                    .opaque_support
+                    .contains(SpdmOpaqueSupport::MULTI_KEY_CONN));
+
+        let req_capabilities = self.common.negotiate_info.req_capabilities_sel;
+        let local_multi_key_conn =
+            other_params_selection.contains(SpdmOpaqueSupport::MULTI_KEY_CONN);
+        if req_capabilities.contains(SpdmRequestCapabilityFlags::MULTI_KEY_CAP_ONLY)
+            && !local_multi_key_conn
+        {
+            return Err(SPDM_STATUS_NEGOTIATION_FAIL);
+        }
+        if !req_capabilities.contains(SpdmRequestCapabilityFlags::MULTI_KEY_CAP_ONLY)
+            && !req_capabilities.contains(SpdmRequestCapabilityFlags::MULTI_KEY_CAP_NEG)
+            && local_multi_key_conn
+        {
+            return Err(SPDM_STATUS_INVALID_MSG_FIELD);
+        }
+        self.common.negotiate_info.local_multi_key_conn = local_multi_key_conn;
+        Ok(())
+    }
 }
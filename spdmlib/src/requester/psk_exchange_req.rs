@@ -319,16 +319,19 @@ impl<'a> RequesterContext<'a> {
                         }
                     }
                     SpdmRequestResponseCode::SpdmResponseError => {
-                        let status = self.spdm_handle_error_response_main(
+                        let received = self.spdm_handle_error_response_respond_if_ready(
                             None,
                             receive_buffer,
                             SpdmRequestResponseCode::SpdmRequestPskExchange,
                             SpdmRequestResponseCode::SpdmResponsePskExchangeRsp,
-                        );
-                        match status {
-                            Err(status) => Err(status),
-                            Ok(()) => Err(SPDM_STATUS_ERROR_PEER),
-                        }
+                        )?;
+                        self.handle_spdm_psk_exchange_response(
+                            half_session_id,
+                            measurement_summary_hash_type,
+                            psk_hint,
+                            send_buffer,
+                            &received.receive_buffer[..received.used],
+                        )
                     }
                     _ => Err(SPDM_STATUS_ERROR_PEER),
                 }
@@ -0,0 +1,66 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use crate::error::SpdmResult;
+use crate::message::*;
+use crate::requester::*;
+
+impl<'a> RequesterContext<'a> {
+    /// Ask the responder for the response to the request it answered with
+    /// ERROR(ResponseNotReady), and return whatever it sends back.
+    pub fn send_receive_spdm_respond_if_ready(
+        &mut self,
+        session_id: Option<u32>,
+        original_request_code: SpdmRequestResponseCode,
+        token: u8,
+    ) -> SpdmResult<ReceivedMessage> {
+        info!("send spdm respond_if_ready\n");
+
+        let mut send_buffer = [0u8; config::MAX_SPDM_MSG_SIZE];
+        let send_used =
+            self.encode_spdm_respond_if_ready(original_request_code, token, &mut send_buffer)?;
+        match session_id {
+            Some(session_id) => {
+                self.send_secured_message(session_id, &send_buffer[..send_used], false)?;
+            }
+            None => {
+                self.send_message(&send_buffer[..send_used])?;
+            }
+        }
+
+        let mut received = ReceivedMessage {
+            receive_buffer: [0u8; config::MAX_SPDM_MSG_SIZE],
+            used: 0,
+        };
+        received.used = match session_id {
+            Some(session_id) => {
+                self.receive_secured_message(session_id, &mut received.receive_buffer, true)?
+            }
+            None => self.receive_message(&mut received.receive_buffer, true)?,
+        };
+        Ok(received)
+    }
+
+    pub fn encode_spdm_respond_if_ready(
+        &mut self,
+        original_request_code: SpdmRequestResponseCode,
+        token: u8,
+        buf: &mut [u8],
+    ) -> SpdmResult<usize> {
+        let mut writer = Writer::init(buf);
+        let request = SpdmMessage {
+            header: SpdmMessageHeader {
+                version: self.common.negotiate_info.spdm_version_sel,
+                request_response_code: SpdmRequestResponseCode::SpdmRequestResponseIfReady,
+            },
+            payload: SpdmMessagePayload::SpdmRespondIfReadyRequest(
+                SpdmRespondIfReadyRequestPayload {
+                    request_code: original_request_code,
+                    token,
+                },
+            ),
+        };
+        request.spdm_encode(&mut self.common, &mut writer)
+    }
+}
@@ -0,0 +1,133 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use crate::error::{
+    SpdmResult, SPDM_STATUS_ERROR_PEER, SPDM_STATUS_INVALID_MSG_FIELD,
+    SPDM_STATUS_INVALID_PARAMETER, SPDM_STATUS_UNSUPPORTED_CAP,
+};
+use crate::message::*;
+use crate::protocol::*;
+use crate::requester::*;
+
+impl<'a> RequesterContext<'a> {
+    /// cert_chain is in the SPDM certificate chain format,
+    /// i.e. Length, Reserved, RootHash and the DER encoded certificates.
+    pub fn send_receive_spdm_set_certificate(
+        &mut self,
+        session_id: Option<u32>,
+        slot_id: u8,
+        cert_chain: &SpdmCertChainBuffer,
+    ) -> SpdmResult {
+        info!("send spdm set certificate\n");
+
+        if self.common.negotiate_info.spdm_version_sel.get_u8()
+            < SpdmVersion::SpdmVersion12.get_u8()
+            || !self
+                .common
+                .negotiate_info
+                .rsp_capabilities_sel
+                .contains(SpdmResponseCapabilityFlags::SET_CERT_CAP)
+        {
+            return Err(SPDM_STATUS_UNSUPPORTED_CAP);
+        }
+
+        // Only slot 0 may be provisioned outside of a secure session.
+        if slot_id >= SPDM_MAX_SLOT_NUMBER as u8 || (session_id.is_none() && slot_id != 0) {
+            return Err(SPDM_STATUS_INVALID_PARAMETER);
+        }
+
+        self.common.reset_buffer_via_request_code(
+            SpdmRequestResponseCode::SpdmRequestSetCertificate,
+            session_id,
+        );
+
+        let mut send_buffer = [0u8; config::MAX_SPDM_MSG_SIZE];
+        let send_used = self.encode_spdm_set_certificate(slot_id, cert_chain, &mut send_buffer)?;
+        match session_id {
+            Some(session_id) => {
+                self.send_secured_message(session_id, &send_buffer[..send_used], false)?;
+            }
+            None => {
+                self.send_message(&send_buffer[..send_used])?;
+            }
+        }
+
+        // Writing the certificate chain to the non-volatile storage may take a while.
+        let mut receive_buffer = [0u8; config::MAX_SPDM_MSG_SIZE];
+        let used = match session_id {
+            Some(session_id) => {
+                self.receive_secured_message(session_id, &mut receive_buffer, true)?
+            }
+            None => self.receive_message(&mut receive_buffer, true)?,
+        };
+
+        self.handle_spdm_set_certificate_response(session_id, slot_id, &receive_buffer[..used])
+    }
+
+    pub fn encode_spdm_set_certificate(
+        &mut self,
+        slot_id: u8,
+        cert_chain: &SpdmCertChainBuffer,
+        buf: &mut [u8],
+    ) -> SpdmResult<usize> {
+        let mut writer = Writer::init(buf);
+        let request = SpdmMessage {
+            header: SpdmMessageHeader {
+                version: self.common.negotiate_info.spdm_version_sel,
+                request_response_code: SpdmRequestResponseCode::SpdmRequestSetCertificate,
+            },
+            payload: SpdmMessagePayload::SpdmSetCertificateRequest(
+                SpdmSetCertificateRequestPayload {
+                    slot_id,
+                    cert_chain: cert_chain.clone(),
+                },
+            ),
+        };
+        request.spdm_encode(&mut self.common, &mut writer)
+    }
+
+    pub fn handle_spdm_set_certificate_response(
+        &mut self,
+        session_id: Option<u32>,
+        slot_id: u8,
+        receive_buffer: &[u8],
+    ) -> SpdmResult {
+        let mut reader = Reader::init(receive_buffer);
+        match SpdmMessageHeader::read(&mut reader) {
+            Some(message_header) => {
+                if message_header.version != self.common.negotiate_info.spdm_version_sel {
+                    return Err(SPDM_STATUS_INVALID_MSG_FIELD);
+                }
+                match message_header.request_response_code {
+                    SpdmRequestResponseCode::SpdmResponseSetCertificateRsp => {
+                        let set_certificate_rsp = SpdmSetCertificateResponsePayload::spdm_read(
+                            &mut self.common,
+                            &mut reader,
+                        );
+                        if let Some(set_certificate_rsp) = set_certificate_rsp {
+                            debug!("!!! set certificate rsp : {:02x?}\n", set_certificate_rsp);
+                            if set_certificate_rsp.slot_id != slot_id {
+                                error!("!!! set certificate rsp : slot id mismatch !!!\n");
+                                return Err(SPDM_STATUS_INVALID_MSG_FIELD);
+                            }
+                            Ok(())
+                        } else {
+                            error!("!!! set certificate rsp : fail !!!\n");
+                            Err(SPDM_STATUS_INVALID_MSG_FIELD)
+                        }
+                    }
+                    SpdmRequestResponseCode::SpdmResponseError => self
+                        .spdm_handle_error_response_main(
+                            session_id,
+                            receive_buffer,
+                            SpdmRequestResponseCode::SpdmRequestSetCertificate,
+                            SpdmRequestResponseCode::SpdmResponseSetCertificateRsp,
+                        ),
+                    _ => Err(SPDM_STATUS_ERROR_PEER),
+                }
+            }
+            None => Err(SPDM_STATUS_INVALID_MSG_FIELD),
+        }
+    }
+}
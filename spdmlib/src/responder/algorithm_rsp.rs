@@ -4,6 +4,8 @@
 
 use crate::common::SpdmCodec;
 use crate::common::SpdmConnectionState;
+use crate::common::SpdmOpaqueSupport;
+use crate::crypto;
 use crate::error::SpdmResult;
 use crate::message::*;
 use crate::protocol::*;
@@ -42,16 +44,25 @@ impl<'a> ResponderContext<'a> {
         );
 
         let other_params_support;
+        let ext_asym_algo;
+        let ext_asym_count;
+        let mut kem_requested = false;
 
         let negotiate_algorithms =
             SpdmNegotiateAlgorithmsRequestPayload::spdm_read(&mut self.common, &mut reader);
         if let Some(negotiate_algorithms) = negotiate_algorithms {
             debug!("!!! negotiate_algorithms : {:02x?}\n", negotiate_algorithms);
             other_params_support = negotiate_algorithms.other_params_support;
+            ext_asym_algo = negotiate_algorithms.ext_asym;
+            ext_asym_count = negotiate_algorithms.ext_asym_count as usize;
             self.common.negotiate_info.measurement_specification_sel =
                 negotiate_algorithms.measurement_specification;
             self.common.negotiate_info.base_hash_sel = negotiate_algorithms.base_hash_algo;
             self.common.negotiate_info.base_asym_sel = negotiate_algorithms.base_asym_algo;
+            self.common.negotiate_info.pqc_asym_sel = negotiate_algorithms.pqc_asym_algo;
+            self.common.negotiate_info.kem_sel = SpdmKemAlgo::empty();
+            self.common.negotiate_info.mel_specification_sel =
+                negotiate_algorithms.mel_specification;
             for alg in negotiate_algorithms
                 .alg_struct
                 .iter()
@@ -64,6 +75,10 @@ impl<'a> ResponderContext<'a> {
                     SpdmAlg::SpdmAlgoKeySchedule(v) => {
                         self.common.negotiate_info.key_schedule_sel = *v
                     }
+                    SpdmAlg::SpdmAlgoKem(v) => {
+                        self.common.negotiate_info.kem_sel = *v;
+                        kem_requested = true;
+                    }
                     SpdmAlg::SpdmAlgoUnknown(_v) => {}
                 }
             }
@@ -95,11 +110,49 @@ impl<'a> ResponderContext<'a> {
         self.common
             .negotiate_info
             .base_asym_sel
-            .prioritize(self.common.config_info.base_asym_algo);
+            .prioritize(self.common.get_supported_base_asym_algo());
+        // An extended asymmetric algorithm is only selected when no DMTF
+        // defined one is common. Extended hash algorithms are never selected.
+        self.common.negotiate_info.ext_asym_sel = None;
+        self.common.negotiate_info.ext_hash_sel = None;
+        if self.common.negotiate_info.base_asym_sel.bits() == 0 {
+            let requested = &ext_asym_algo[..ext_asym_count];
+            self.common.negotiate_info.ext_asym_sel = self
+                .common
+                .config_info
+                .ext_asym_algo
+                .iter()
+                .flatten()
+                .find(|ext_asym| {
+                    requested.contains(ext_asym) && crypto::ext_asym::is_supported(**ext_asym)
+                })
+                .copied();
+        }
+        // A PQC asymmetric algorithm is preferred over the base and extended
+        // ones, and a KEM over DHE, when common to both sides.
+        if !self.common.is_pqc_supported() {
+            self.common.negotiate_info.pqc_asym_sel = SpdmPqcAsymAlgo::empty();
+            self.common.negotiate_info.kem_sel = SpdmKemAlgo::empty();
+        }
+        self.common
+            .negotiate_info
+            .pqc_asym_sel
+            .prioritize(self.common.config_info.pqc_asym_algo);
+        if !self.common.negotiate_info.pqc_asym_sel.is_empty() {
+            self.common.negotiate_info.base_asym_sel = SpdmBaseAsymAlgo::empty();
+            self.common.negotiate_info.ext_asym_sel = None;
+        }
         self.common
             .negotiate_info
             .dhe_sel
             .prioritize(self.common.config_info.dhe_algo);
+        self.common
+            .negotiate_info
+            .kem_sel
+            .prioritize(self.common.config_info.kem_algo);
+        if !self.common.negotiate_info.kem_sel.is_empty() {
+            self.common.negotiate_info.dhe_sel = SpdmDheAlgo::empty();
+        }
         self.common
             .negotiate_info
             .aead_sel
@@ -107,11 +160,24 @@ impl<'a> ResponderContext<'a> {
         self.common
             .negotiate_info
             .req_asym_sel
-            .prioritize(self.common.config_info.req_asym_algo);
+            .prioritize(self.common.get_supported_req_asym_algo());
         self.common
             .negotiate_info
             .key_schedule_sel
             .prioritize(self.common.config_info.key_schedule_algo);
+        if self
+            .common
+            .negotiate_info
+            .rsp_capabilities_sel
+            .contains(SpdmResponseCapabilityFlags::MEL_CAP)
+        {
+            self.common
+                .negotiate_info
+                .mel_specification_sel
+                .prioritize(self.common.config_info.mel_specification);
+        } else {
+            self.common.negotiate_info.mel_specification_sel = SpdmMelSpecification::empty();
+        }
 
         //
         // update cert chain - append root cert hash
@@ -123,9 +189,67 @@ impl<'a> ResponderContext<'a> {
 
         info!("send spdm algorithm\n");
 
-        let other_params_selection = self.common.config_info.opaque_support & other_params_support;
+        let mut other_params_selection = (self.common.config_info.opaque_support
+            & other_params_support)
+            - SpdmOpaqueSupport::MULTI_KEY_CONN;
         self.common.negotiate_info.opaque_data_support = other_params_selection;
 
+        if self.common.negotiate_info.spdm_version_sel.get_u8()
+            >= SpdmVersion::SpdmVersion13.get_u8()
+        {
+            // With MULTI_KEY_CAP_NEG, the requester selects whether the responder
+            // uses a multi-key connection, and the responder selects it for the
+            // requester.
+            let rsp_capabilities = self.common.negotiate_info.rsp_capabilities_sel;
+            self.common.negotiate_info.local_multi_key_conn = rsp_capabilities
+                .contains(SpdmResponseCapabilityFlags::MULTI_KEY_CAP_ONLY)
+                || (rsp_capabilities.contains(SpdmResponseCapabilityFlags::MULTI_KEY_CAP_NEG)
+                    && other_params_support.contains(SpdmOpaqueSupport::MULTI_KEY_CONN));
+            let req_capabilities = self.common.negotiate_info.req_capabilities_sel;
+            self.common.negotiate_info.peer_multi_key_conn = req_capabilities
+                .contains(SpdmRequestCapabilityFlags::MULTI_KEY_CAP_ONLY)
+                || (req_capabilities.contains(SpdmRequestCapabilityFlags::MULTI_KEY_CAP_NEG)
+                    && self
+                        .common
+                        .config_info
+                        .opaque_support
+                        .contains(SpdmOpaqueSupport::MULTI_KEY_CONN));
+            if self.common.negotiate_info.peer_multi_key_conn {
+                other_params_selection.insert(SpdmOpaqueSupport::MULTI_KEY_CONN);
+            }
+        }
+
+        let mut alg_struct = [
+            SpdmAlgStruct {
+                alg_type: SpdmAlgType::SpdmAlgTypeDHE,
+                alg_supported: SpdmAlg::SpdmAlgoDhe(self.common.negotiate_info.dhe_sel),
+            },
+            SpdmAlgStruct {
+                alg_type: SpdmAlgType::SpdmAlgTypeAEAD,
+                alg_supported: SpdmAlg::SpdmAlgoAead(self.common.negotiate_info.aead_sel),
+            },
+            SpdmAlgStruct {
+                alg_type: SpdmAlgType::SpdmAlgTypeReqAsym,
+                alg_supported: SpdmAlg::SpdmAlgoReqAsym(self.common.negotiate_info.req_asym_sel),
+            },
+            SpdmAlgStruct {
+                alg_type: SpdmAlgType::SpdmAlgTypeKeySchedule,
+                alg_supported: SpdmAlg::SpdmAlgoKeySchedule(
+                    self.common.negotiate_info.key_schedule_sel,
+                ),
+            },
+            #[cfg(feature = "pqc")]
+            SpdmAlgStruct::default(),
+        ];
+        let mut alg_struct_count = 4;
+        if kem_requested && self.common.is_pqc_supported() {
+            alg_struct[alg_struct_count] = SpdmAlgStruct {
+                alg_type: SpdmAlgType::SpdmAlgTypeKEM,
+                alg_supported: SpdmAlg::SpdmAlgoKem(self.common.negotiate_info.kem_sel),
+            };
+            alg_struct_count += 1;
+        }
+
         let response = SpdmMessage {
             header: SpdmMessageHeader {
                 version: self.common.negotiate_info.spdm_version_sel,
@@ -140,29 +264,12 @@ impl<'a> ResponderContext<'a> {
                 measurement_hash_algo: self.common.negotiate_info.measurement_hash_sel,
                 base_asym_sel: self.common.negotiate_info.base_asym_sel,
                 base_hash_sel: self.common.negotiate_info.base_hash_sel,
-                alg_struct_count: 4,
-                alg_struct: [
-                    SpdmAlgStruct {
-                        alg_type: SpdmAlgType::SpdmAlgTypeDHE,
-                        alg_supported: SpdmAlg::SpdmAlgoDhe(self.common.negotiate_info.dhe_sel),
-                    },
-                    SpdmAlgStruct {
-                        alg_type: SpdmAlgType::SpdmAlgTypeAEAD,
-                        alg_supported: SpdmAlg::SpdmAlgoAead(self.common.negotiate_info.aead_sel),
-                    },
-                    SpdmAlgStruct {
-                        alg_type: SpdmAlgType::SpdmAlgTypeReqAsym,
-                        alg_supported: SpdmAlg::SpdmAlgoReqAsym(
-                            self.common.negotiate_info.req_asym_sel,
-                        ),
-                    },
-                    SpdmAlgStruct {
-                        alg_type: SpdmAlgType::SpdmAlgTypeKeySchedule,
-                        alg_supported: SpdmAlg::SpdmAlgoKeySchedule(
-                            self.common.negotiate_info.key_schedule_sel,
-                        ),
-                    },
-                ],
+                pqc_asym_sel: self.common.negotiate_info.pqc_asym_sel,
+                mel_specification_sel: self.common.negotiate_info.mel_specification_sel,
+                ext_asym_sel: self.common.negotiate_info.ext_asym_sel,
+                ext_hash_sel: self.common.negotiate_info.ext_hash_sel,
+                alg_struct_count: alg_struct_count as u8,
+                alg_struct,
             }),
         };
         let res = response.spdm_encode(&mut self.common, writer);
@@ -19,6 +19,7 @@ extern crate alloc;
 use crate::error::SPDM_STATUS_INVALID_STATE_LOCAL;
 use crate::error::{SPDM_STATUS_BUFFER_FULL, SPDM_STATUS_CRYPTO_ERROR};
 use crate::secret;
+use crate::secret::SpdmSecretResult;
 
 impl<'a> ResponderContext<'a> {
     pub fn handle_spdm_challenge(&mut self, bytes: &[u8]) -> SpdmResult {
@@ -61,19 +62,32 @@ impl<'a> ResponderContext<'a> {
                     == SpdmMeasurementSummaryHashType::SpdmMeasurementSummaryHashTypeAll)
             {
                 self.common.runtime_info.need_measurement_summary_hash = true;
-                let measurement_summary_hash_res =
-                    secret::measurement::generate_measurement_summary_hash(
+                measurement_summary_hash =
+                    match secret::measurement::generate_measurement_summary_hash(
                         self.common.negotiate_info.spdm_version_sel,
                         self.common.negotiate_info.base_hash_sel,
                         self.common.negotiate_info.measurement_specification_sel,
                         self.common.negotiate_info.measurement_hash_sel,
                         challenge.measurement_summary_hash_type,
-                    );
-                if measurement_summary_hash_res.is_none() {
-                    self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
-                    return;
-                }
-                measurement_summary_hash = measurement_summary_hash_res.unwrap();
+                    ) {
+                        SpdmSecretResult::Ready(measurement_summary_hash) => {
+                            measurement_summary_hash
+                        }
+                        SpdmSecretResult::NotReady { rdt_exponent, rdtm } => {
+                            self.write_spdm_response_not_ready(
+                                None,
+                                bytes,
+                                rdt_exponent,
+                                rdtm,
+                                writer,
+                            );
+                            return;
+                        }
+                        SpdmSecretResult::Failed => {
+                            self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
+                            return;
+                        }
+                    };
                 if measurement_summary_hash.data_size == 0 {
                     self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
                     return;
@@ -89,16 +103,27 @@ impl<'a> ResponderContext<'a> {
         }
 
         let challenge = challenge.unwrap();
-        let slot_id = challenge.slot_id as usize;
-        if slot_id >= SPDM_MAX_SLOT_NUMBER {
+        let slot_id = challenge.slot_id;
+        if slot_id >= SPDM_MAX_SLOT_NUMBER as u8 && !self.is_my_public_key_slot_id(slot_id) {
             self.write_spdm_error(SpdmErrorCode::SpdmErrorInvalidRequest, 0, writer);
             return;
         }
-        if self.common.provision_info.my_cert_chain[slot_id].is_none() {
+        if self
+            .common
+            .get_my_cert_chain_or_public_key(slot_id)
+            .is_none()
+        {
             self.write_spdm_error(SpdmErrorCode::SpdmErrorInvalidRequest, 0, writer);
             return;
         }
 
+        // The transcript is restored if the signature is deferred, as the
+        // request is processed again on RESPOND_IF_READY.
+        #[cfg(not(feature = "hashed-transcript-data"))]
+        let message_c = self.common.runtime_info.message_c.clone();
+        #[cfg(feature = "hashed-transcript-data")]
+        let digest_context_m1m2 = self.common.runtime_info.digest_context_m1m2.clone();
+
         if self
             .common
             .append_message_c(&bytes[..reader.used()])
@@ -108,14 +133,20 @@ impl<'a> ResponderContext<'a> {
             return;
         }
 
-        let my_cert_chain = self.common.provision_info.my_cert_chain[slot_id]
-            .as_ref()
+        let my_cert_chain = self
+            .common
+            .get_my_cert_chain_or_public_key(slot_id)
             .unwrap();
-        let cert_chain_hash = crypto::hash::hash_all(
-            self.common.negotiate_info.base_hash_sel,
-            my_cert_chain.as_ref(),
-        )
-        .unwrap();
+        let cert_chain_hash =
+            crypto::hash::hash_all(self.common.negotiate_info.base_hash_sel, my_cert_chain)
+                .unwrap();
+
+        // a provisioned public key is reported as slot 0xF with no slot mask
+        let (slot_id, slot_mask) = if slot_id == SPDM_PUBLIC_KEY_SLOT_ID {
+            (SPDM_PUBLIC_KEY_SLOT_ID_PARAM, 0)
+        } else {
+            (slot_id, 0x1)
+        };
 
         let mut nonce = [0u8; SPDM_NONCE_SIZE];
         let res = crypto::rand::get_random(&mut nonce);
@@ -124,6 +155,15 @@ impl<'a> ResponderContext<'a> {
             return;
         }
 
+        #[cfg(feature = "mut-auth")]
+        let challenge_auth_attribute = if self.is_basic_mut_auth_supported() {
+            SpdmChallengeAuthAttribute::BASIC_MUT_AUTH_REQ
+        } else {
+            SpdmChallengeAuthAttribute::empty()
+        };
+        #[cfg(not(feature = "mut-auth"))]
+        let challenge_auth_attribute = SpdmChallengeAuthAttribute::empty();
+
         info!("send spdm challenge_auth\n");
 
         let response = SpdmMessage {
@@ -133,9 +173,9 @@ impl<'a> ResponderContext<'a> {
             },
             payload: SpdmMessagePayload::SpdmChallengeAuthResponse(
                 SpdmChallengeAuthResponsePayload {
-                    slot_id: slot_id as u8,
-                    slot_mask: 0x1,
-                    challenge_auth_attribute: SpdmChallengeAuthAttribute::empty(),
+                    slot_id,
+                    slot_mask,
+                    challenge_auth_attribute,
                     cert_chain_hash,
                     nonce: SpdmNonceStruct { data: nonce },
                     measurement_summary_hash,
@@ -144,47 +184,86 @@ impl<'a> ResponderContext<'a> {
                         data: [0u8; MAX_SPDM_OPAQUE_SIZE],
                     },
                     signature: SpdmSignatureStruct {
-                        data_size: self.common.negotiate_info.base_asym_sel.get_size(),
+                        data_size: self.common.get_asym_key_size(),
                         data: [0xbb; SPDM_MAX_ASYM_KEY_SIZE],
                     },
                 },
             ),
         };
-        let res = response.spdm_encode(&mut self.common, writer);
+        // The response is built aside, so that ERROR(ResponseNotReady) can
+        // be written instead if the signature is deferred.
+        let mut response_buffer = [0u8; config::MAX_SPDM_MSG_SIZE];
+        let mut response_writer = Writer::init(&mut response_buffer);
+        let res = response.spdm_encode(&mut self.common, &mut response_writer);
         if res.is_err() {
             self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
             return;
         }
-        let used = writer.used();
+        let used = response_writer.used();
 
         // generat signature
-        let base_asym_size = self.common.negotiate_info.base_asym_sel.get_size() as usize;
+        let base_asym_size = self.common.get_asym_key_size() as usize;
         let temp_used = used - base_asym_size;
 
         if self
             .common
-            .append_message_c(&writer.used_slice()[..temp_used])
+            .append_message_c(&response_writer.used_slice()[..temp_used])
             .is_err()
         {
             self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
             return;
         }
 
-        let signature = self.generate_challenge_auth_signature();
-        if signature.is_err() {
-            self.send_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0);
-            return;
-        }
-        let signature = signature.unwrap();
+        let signature = match self.generate_challenge_auth_signature(challenge.slot_id) {
+            Ok(SpdmSecretResult::Ready(signature)) => signature,
+            Ok(SpdmSecretResult::NotReady { rdt_exponent, rdtm }) => {
+                #[cfg(not(feature = "hashed-transcript-data"))]
+                {
+                    self.common.runtime_info.message_c = message_c;
+                }
+                #[cfg(feature = "hashed-transcript-data")]
+                {
+                    self.common.runtime_info.digest_context_m1m2 = digest_context_m1m2;
+                }
+                self.write_spdm_response_not_ready(None, bytes, rdt_exponent, rdtm, writer);
+                return;
+            }
+            _ => {
+                self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
+                return;
+            }
+        };
         // patch the message before send
-        writer.mut_used_slice()[(used - base_asym_size)..used].copy_from_slice(signature.as_ref());
+        response_writer.mut_used_slice()[(used - base_asym_size)..used]
+            .copy_from_slice(signature.as_ref());
+        let _ = writer.extend_from_slice(response_writer.used_slice());
 
         self.common.reset_message_b();
         self.common.reset_message_c();
     }
 
+    // Basic mutual authentication, defined by SPDM 1.1 only, retrieves the
+    // requester certificate and challenges it with encapsulated requests out
+    // of session.
+    #[cfg(feature = "mut-auth")]
+    fn is_basic_mut_auth_supported(&self) -> bool {
+        self.common.encap_context.basic_mut_auth_requested
+            && self.common.negotiate_info.spdm_version_sel == SpdmVersion::SpdmVersion11
+            && self.common.negotiate_info.req_capabilities_sel.contains(
+                SpdmRequestCapabilityFlags::MUT_AUTH_CAP
+                    | SpdmRequestCapabilityFlags::ENCAP_CAP
+                    | SpdmRequestCapabilityFlags::CHAL_CAP,
+            )
+            && self.common.negotiate_info.rsp_capabilities_sel.contains(
+                SpdmResponseCapabilityFlags::MUT_AUTH_CAP | SpdmResponseCapabilityFlags::ENCAP_CAP,
+            )
+    }
+
     #[cfg(feature = "hashed-transcript-data")]
-    pub fn generate_challenge_auth_signature(&self) -> SpdmResult<SpdmSignatureStruct> {
+    pub fn generate_challenge_auth_signature(
+        &self,
+        slot_id: u8,
+    ) -> SpdmResult<SpdmSecretResult<SpdmSignatureStruct>> {
         let message_m1m2_hash = crypto::hash::hash_ctx_finalize(
             self.common
                 .runtime_info
@@ -203,7 +282,9 @@ impl<'a> ResponderContext<'a> {
         {
             message_sign.reset_message();
             message_sign
-                .append_message(&SPDM_VERSION_1_2_SIGNING_PREFIX_CONTEXT)
+                .append_message(spdm_signing_prefix_context(
+                    self.common.negotiate_info.spdm_version_sel,
+                ))
                 .ok_or(SPDM_STATUS_BUFFER_FULL)?;
             message_sign
                 .append_message(&SPDM_VERSION_1_2_SIGNING_CONTEXT_ZEROPAD_4)
@@ -219,16 +300,18 @@ impl<'a> ResponderContext<'a> {
             return Err(SPDM_STATUS_INVALID_STATE_LOCAL);
         }
 
-        crate::secret::asym_sign::sign(
-            self.common.negotiate_info.base_hash_sel,
-            self.common.negotiate_info.base_asym_sel,
+        Ok(self.common.sign_with_my_key_pair(
+            slot_id,
+            &SPDM_CHALLENGE_AUTH_SIGN_CONTEXT,
             message_sign.as_ref(),
-        )
-        .ok_or(SPDM_STATUS_CRYPTO_ERROR)
+        ))
     }
 
     #[cfg(not(feature = "hashed-transcript-data"))]
-    pub fn generate_challenge_auth_signature(&self) -> SpdmResult<SpdmSignatureStruct> {
+    pub fn generate_challenge_auth_signature(
+        &self,
+        slot_id: u8,
+    ) -> SpdmResult<SpdmSecretResult<SpdmSignatureStruct>> {
         let mut message_m1m2 = ManagedBufferM1M2::default();
         message_m1m2
             .append_message(self.common.runtime_info.message_a.as_ref())
@@ -253,7 +336,9 @@ impl<'a> ResponderContext<'a> {
         {
             message_m1m2.reset_message();
             message_m1m2
-                .append_message(&SPDM_VERSION_1_2_SIGNING_PREFIX_CONTEXT)
+                .append_message(spdm_signing_prefix_context(
+                    self.common.negotiate_info.spdm_version_sel,
+                ))
                 .ok_or(SPDM_STATUS_BUFFER_FULL)?;
             message_m1m2
                 .append_message(&SPDM_VERSION_1_2_SIGNING_CONTEXT_ZEROPAD_4)
@@ -266,11 +351,10 @@ impl<'a> ResponderContext<'a> {
                 .ok_or(SPDM_STATUS_BUFFER_FULL)?;
         }
 
-        crate::secret::asym_sign::sign(
-            self.common.negotiate_info.base_hash_sel,
-            self.common.negotiate_info.base_asym_sel,
+        Ok(self.common.sign_with_my_key_pair(
+            slot_id,
+            &SPDM_CHALLENGE_AUTH_SIGN_CONTEXT,
             message_m1m2.as_ref(),
-        )
-        .ok_or(SPDM_STATUS_CRYPTO_ERROR)
+        ))
     }
 }
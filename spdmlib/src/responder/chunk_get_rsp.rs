@@ -0,0 +1,145 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use crate::common::SpdmChunkStatus;
+use crate::common::SpdmCodec;
+use crate::error::SpdmResult;
+use crate::message::*;
+use crate::responder::*;
+
+impl<'a> ResponderContext<'a> {
+    pub fn handle_spdm_chunk_get(&mut self, session_id: Option<u32>, bytes: &[u8]) -> SpdmResult {
+        let mut send_buffer = [0u8; config::MAX_SPDM_MSG_SIZE];
+        let mut writer = Writer::init(&mut send_buffer);
+        self.write_spdm_chunk_response(bytes, &mut writer);
+
+        if let Some(session_id) = session_id {
+            self.send_secured_message(session_id, writer.used_slice(), false)
+        } else {
+            self.send_message(writer.used_slice())
+        }
+    }
+
+    fn write_spdm_chunk_response(&mut self, bytes: &[u8], writer: &mut Writer) {
+        if !self.common.is_chunk_supported() {
+            self.write_error_response(SpdmErrorCode::SpdmErrorUnsupportedRequest, bytes, writer);
+            return;
+        }
+
+        let mut reader = Reader::init(bytes);
+        let message_header = SpdmMessageHeader::read(&mut reader);
+        if let Some(message_header) = message_header {
+            if message_header.version != self.common.negotiate_info.spdm_version_sel {
+                self.write_spdm_error(SpdmErrorCode::SpdmErrorVersionMismatch, 0, writer);
+                return;
+            }
+        } else {
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorInvalidRequest, 0, writer);
+            return;
+        }
+
+        let chunk_get = SpdmChunkGetRequestPayload::spdm_read(&mut self.common, &mut reader);
+        let chunk_get = if let Some(chunk_get) = chunk_get {
+            debug!(
+                "!!! chunk get : handle {:02x}, seq {}\n",
+                chunk_get.handle, chunk_get.chunk_seq_num
+            );
+            chunk_get
+        } else {
+            error!("!!! chunk get : fail !!!\n");
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorInvalidRequest, 0, writer);
+            return;
+        };
+
+        if self.common.chunk_context.chunk_status != SpdmChunkStatus::SpdmChunkGetInProgress {
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorUnexpectedRequest, 0, writer);
+            return;
+        }
+        if chunk_get.handle != self.common.chunk_context.chunk_handle
+            || chunk_get.chunk_seq_num != self.common.chunk_context.chunk_seq_num
+        {
+            self.common.reset_chunk_context();
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorInvalidRequest, 0, writer);
+            return;
+        }
+
+        let header_size = if chunk_get.chunk_seq_num == 0 {
+            SPDM_CHUNK_HEADER_SIZE + SPDM_CHUNK_LARGE_MESSAGE_SIZE_FIELD_SIZE
+        } else {
+            SPDM_CHUNK_HEADER_SIZE
+        };
+        let data_transfer_size = self.common.negotiate_info.req_data_transfer_size_sel as usize;
+        let offset = self.common.chunk_context.chunk_message_offset;
+        let remaining = self.common.chunk_context.chunk_message_size - offset;
+        let chunk_size = data_transfer_size
+            .saturating_sub(header_size)
+            .min(MAX_SPDM_CHUNK_SIZE)
+            .min(remaining);
+        if chunk_size == 0 {
+            self.common.reset_chunk_context();
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
+            return;
+        }
+        let last_chunk = chunk_size == remaining;
+
+        let mut payload = SpdmChunkResponsePayload {
+            response_attributes: if last_chunk {
+                SpdmChunkSenderAttributes::LAST_CHUNK
+            } else {
+                SpdmChunkSenderAttributes::empty()
+            },
+            handle: chunk_get.handle,
+            chunk_seq_num: chunk_get.chunk_seq_num,
+            chunk_size: chunk_size as u32,
+            large_message_size: self.common.chunk_context.chunk_message_size as u32,
+            ..Default::default()
+        };
+        payload.chunk[..chunk_size].copy_from_slice(
+            &self.common.chunk_context.chunk_message_data[offset..(offset + chunk_size)],
+        );
+
+        if last_chunk {
+            self.common.reset_chunk_context();
+        } else {
+            self.common.chunk_context.chunk_message_offset = offset + chunk_size;
+            self.common.chunk_context.chunk_seq_num = chunk_get.chunk_seq_num.wrapping_add(1);
+        }
+
+        info!("send spdm chunk response\n");
+        let response = SpdmMessage {
+            header: SpdmMessageHeader {
+                version: self.common.negotiate_info.spdm_version_sel,
+                request_response_code: SpdmRequestResponseCode::SpdmResponseChunkResponse,
+            },
+            payload: SpdmMessagePayload::SpdmChunkResponse(payload),
+        };
+        let res = response.spdm_encode(&mut self.common, writer);
+        if res.is_err() {
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
+        }
+    }
+
+    // Keep a response which exceeds the DataTransferSize of the requester and
+    // tell the requester to retrieve it via CHUNK_GET.
+    pub(crate) fn write_spdm_large_response(&mut self, response: &[u8], writer: &mut Writer) {
+        let req_max_spdm_msg_size = self.common.negotiate_info.req_max_spdm_msg_size_sel as usize;
+        if !self.common.is_chunk_supported()
+            || response.len() > config::MAX_SPDM_MSG_SIZE
+            || (req_max_spdm_msg_size != 0 && response.len() > req_max_spdm_msg_size)
+        {
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorResponseTooLarge, 0, writer);
+            return;
+        }
+
+        let handle = self.common.chunk_context.chunk_handle.wrapping_add(1);
+        self.common.reset_chunk_context();
+        let chunk_context = &mut self.common.chunk_context;
+        chunk_context.chunk_status = SpdmChunkStatus::SpdmChunkGetInProgress;
+        chunk_context.chunk_handle = handle;
+        chunk_context.chunk_message_size = response.len();
+        chunk_context.chunk_message_data[..response.len()].copy_from_slice(response);
+
+        self.write_spdm_error(SpdmErrorCode::SpdmErrorLargeResponse, handle, writer);
+    }
+}
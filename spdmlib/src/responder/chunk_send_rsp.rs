@@ -0,0 +1,261 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use crate::common::SpdmChunkStatus;
+use crate::common::SpdmCodec;
+use crate::error::SpdmResult;
+use crate::message::*;
+use crate::responder::*;
+
+impl<'a> ResponderContext<'a> {
+    pub fn handle_spdm_chunk_send(&mut self, session_id: Option<u32>, bytes: &[u8]) -> SpdmResult {
+        let mut large_request = [0u8; config::MAX_SPDM_MSG_SIZE];
+        let mut send_buffer = [0u8; config::MAX_SPDM_MSG_SIZE];
+        let mut writer = Writer::init(&mut send_buffer);
+        let large_request_size =
+            self.write_spdm_chunk_send_ack_response(bytes, &mut large_request, &mut writer);
+
+        let large_request_size = if let Some(large_request_size) = large_request_size {
+            large_request_size
+        } else if let Some(session_id) = session_id {
+            return self.send_secured_message(session_id, writer.used_slice(), false);
+        } else {
+            return self.send_message(writer.used_slice());
+        };
+
+        // The large request is complete, process it as if it was received at once.
+        // Its response is carried by the CHUNK_SEND_ACK of the last chunk.
+        let large_request = &large_request[..large_request_size];
+        let result = match session_id {
+            Some(session_id) => self.dispatch_secured_message(session_id, large_request),
+            None => self.dispatch_message(large_request),
+        };
+
+        if self.common.chunk_context.chunk_status == SpdmChunkStatus::SpdmChunkSendAckPending {
+            // Nothing was sent for the large request.
+            let mut send_buffer = [0u8; config::MAX_SPDM_MSG_SIZE];
+            let mut writer = Writer::init(&mut send_buffer);
+            self.write_error_response(
+                SpdmErrorCode::SpdmErrorUnsupportedRequest,
+                large_request,
+                &mut writer,
+            );
+            if let Some(session_id) = session_id {
+                self.send_secured_message(session_id, writer.used_slice(), false)?;
+            } else {
+                self.send_message(writer.used_slice())?;
+            }
+        }
+
+        result
+    }
+
+    // Returns the size of the reassembled large request once the last chunk is received.
+    fn write_spdm_chunk_send_ack_response(
+        &mut self,
+        bytes: &[u8],
+        large_request: &mut [u8],
+        writer: &mut Writer,
+    ) -> Option<usize> {
+        if !self.common.is_chunk_supported() {
+            self.write_error_response(SpdmErrorCode::SpdmErrorUnsupportedRequest, bytes, writer);
+            return None;
+        }
+
+        let mut reader = Reader::init(bytes);
+        let message_header = SpdmMessageHeader::read(&mut reader);
+        if let Some(message_header) = message_header {
+            if message_header.version != self.common.negotiate_info.spdm_version_sel {
+                self.write_spdm_error(SpdmErrorCode::SpdmErrorVersionMismatch, 0, writer);
+                return None;
+            }
+        } else {
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorInvalidRequest, 0, writer);
+            return None;
+        }
+
+        let chunk_send = SpdmChunkSendRequestPayload::spdm_read(&mut self.common, &mut reader);
+        let chunk_send = if let Some(chunk_send) = chunk_send {
+            debug!(
+                "!!! chunk send : handle {:02x}, seq {}, size {}\n",
+                chunk_send.handle, chunk_send.chunk_seq_num, chunk_send.chunk_size
+            );
+            chunk_send
+        } else {
+            error!("!!! chunk send : fail !!!\n");
+            self.common.reset_chunk_context();
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorInvalidRequest, 0, writer);
+            return None;
+        };
+
+        let header_size = if chunk_send.chunk_seq_num == 0 {
+            SPDM_CHUNK_HEADER_SIZE + SPDM_CHUNK_LARGE_MESSAGE_SIZE_FIELD_SIZE
+        } else {
+            SPDM_CHUNK_HEADER_SIZE
+        };
+
+        if chunk_send.chunk_seq_num == 0 {
+            // A new large request discards the one in progress, if any.
+            self.common.reset_chunk_context();
+            let large_message_size = chunk_send.large_message_size as usize;
+            if large_message_size > config::MAX_SPDM_MSG_SIZE
+                || large_message_size > self.common.config_info.max_spdm_msg_size as usize
+                || large_message_size <= self.common.config_info.data_transfer_size as usize
+            {
+                self.write_spdm_chunk_send_early_error(&chunk_send, writer);
+                return None;
+            }
+            self.common.chunk_context.chunk_status = SpdmChunkStatus::SpdmChunkSendInProgress;
+            self.common.chunk_context.chunk_handle = chunk_send.handle;
+            self.common.chunk_context.chunk_message_size = large_message_size;
+        } else if self.common.chunk_context.chunk_status != SpdmChunkStatus::SpdmChunkSendInProgress
+            || self.common.chunk_context.chunk_handle != chunk_send.handle
+            || self.common.chunk_context.chunk_seq_num != chunk_send.chunk_seq_num
+        {
+            self.write_spdm_chunk_send_early_error(&chunk_send, writer);
+            return None;
+        }
+
+        let chunk_size = chunk_send.chunk_size as usize;
+        let offset = self.common.chunk_context.chunk_message_offset;
+        let remaining = self.common.chunk_context.chunk_message_size - offset;
+        if chunk_size == 0
+            || chunk_size > remaining
+            || chunk_size + header_size > self.common.config_info.data_transfer_size as usize
+        {
+            self.write_spdm_chunk_send_early_error(&chunk_send, writer);
+            return None;
+        }
+        self.common.chunk_context.chunk_message_data[offset..(offset + chunk_size)]
+            .copy_from_slice(&chunk_send.chunk[..chunk_size]);
+        self.common.chunk_context.chunk_message_offset = offset + chunk_size;
+
+        let last_chunk = chunk_send
+            .request_attributes
+            .contains(SpdmChunkSenderAttributes::LAST_CHUNK);
+        if last_chunk != (chunk_size == remaining) {
+            self.write_spdm_chunk_send_early_error(&chunk_send, writer);
+            return None;
+        }
+
+        if last_chunk {
+            let large_request_size = self.common.chunk_context.chunk_message_size;
+            large_request[..large_request_size].copy_from_slice(
+                &self.common.chunk_context.chunk_message_data[..large_request_size],
+            );
+            self.common.chunk_context.chunk_status = SpdmChunkStatus::SpdmChunkSendAckPending;
+            self.common.chunk_context.chunk_seq_num = chunk_send.chunk_seq_num;
+            return Some(large_request_size);
+        }
+
+        match self.common.chunk_context.chunk_seq_num.checked_add(1) {
+            Some(chunk_seq_num) => self.common.chunk_context.chunk_seq_num = chunk_seq_num,
+            None => {
+                self.write_spdm_chunk_send_early_error(&chunk_send, writer);
+                return None;
+            }
+        }
+
+        info!("send spdm chunk send ack\n");
+        self.write_spdm_chunk_send_ack(
+            chunk_send.handle,
+            chunk_send.chunk_seq_num,
+            SpdmChunkReceiverAttributes::empty(),
+            &[],
+            writer,
+        );
+        None
+    }
+
+    fn write_spdm_chunk_send_early_error(
+        &mut self,
+        chunk_send: &SpdmChunkSendRequestPayload,
+        writer: &mut Writer,
+    ) {
+        self.common.reset_chunk_context();
+
+        let mut error_buffer = [0u8; config::MAX_SPDM_MSG_SIZE];
+        let mut error_writer = Writer::init(&mut error_buffer);
+        self.write_spdm_error(SpdmErrorCode::SpdmErrorInvalidRequest, 0, &mut error_writer);
+        let error_used = error_writer.used();
+
+        self.write_spdm_chunk_send_ack(
+            chunk_send.handle,
+            chunk_send.chunk_seq_num,
+            SpdmChunkReceiverAttributes::EARLY_ERROR_DETECTED,
+            &error_buffer[..error_used],
+            writer,
+        );
+    }
+
+    // Wrap the response to the reassembled large request into the CHUNK_SEND_ACK
+    // of the last chunk. A response which does not fit is kept for CHUNK_GET.
+    pub(crate) fn write_spdm_chunk_send_ack_with_response(
+        &mut self,
+        response: &[u8],
+        writer: &mut Writer,
+    ) {
+        let handle = self.common.chunk_context.chunk_handle;
+        let chunk_seq_num = self.common.chunk_context.chunk_seq_num;
+        self.common.reset_chunk_context();
+
+        let data_transfer_size = match self.common.negotiate_info.req_data_transfer_size_sel {
+            0 => config::MAX_SPDM_MSG_SIZE,
+            size => (size as usize).min(config::MAX_SPDM_MSG_SIZE),
+        };
+        if SPDM_CHUNK_SEND_ACK_HEADER_SIZE + response.len() > data_transfer_size {
+            let mut error_buffer = [0u8; config::MAX_SPDM_MSG_SIZE];
+            let mut error_writer = Writer::init(&mut error_buffer);
+            self.write_spdm_large_response(response, &mut error_writer);
+            let error_used = error_writer.used();
+
+            self.write_spdm_chunk_send_ack(
+                handle,
+                chunk_seq_num,
+                SpdmChunkReceiverAttributes::empty(),
+                &error_buffer[..error_used],
+                writer,
+            );
+        } else {
+            self.write_spdm_chunk_send_ack(
+                handle,
+                chunk_seq_num,
+                SpdmChunkReceiverAttributes::empty(),
+                response,
+                writer,
+            );
+        }
+    }
+
+    fn write_spdm_chunk_send_ack(
+        &mut self,
+        handle: u8,
+        chunk_seq_num: u16,
+        response_attributes: SpdmChunkReceiverAttributes,
+        response_to_large_request: &[u8],
+        writer: &mut Writer,
+    ) {
+        let mut payload = SpdmChunkSendAckResponsePayload {
+            response_attributes,
+            handle,
+            chunk_seq_num,
+            response_to_large_request_size: response_to_large_request.len() as u32,
+            ..Default::default()
+        };
+        payload.response_to_large_request[..response_to_large_request.len()]
+            .copy_from_slice(response_to_large_request);
+
+        let response = SpdmMessage {
+            header: SpdmMessageHeader {
+                version: self.common.negotiate_info.spdm_version_sel,
+                request_response_code: SpdmRequestResponseCode::SpdmResponseChunkSendAck,
+            },
+            payload: SpdmMessagePayload::SpdmChunkSendAckResponse(payload),
+        };
+        let res = response.spdm_encode(&mut self.common, writer);
+        if res.is_err() {
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
+        }
+    }
+}
@@ -3,16 +3,20 @@
 // SPDX-License-Identifier: BSD-2-Clause-Patent
 
 use super::app_message_handler::dispatch_secured_app_message_cb;
-use crate::common::SpdmConnectionState;
+use super::respond_if_ready_rsp::SpdmDeferredRequest;
 use crate::common::{session::SpdmSessionState, SpdmDeviceIo, SpdmTransportEncap};
+use crate::common::{SpdmChunkStatus, SpdmConnectionState};
 use crate::config;
-use crate::error::{SpdmResult, SPDM_STATUS_UNSUPPORTED_CAP};
+use crate::error::{SpdmResult, SPDM_STATUS_NEGOTIATION_FAIL, SPDM_STATUS_UNSUPPORTED_CAP};
 use crate::message::*;
-use crate::protocol::{SpdmRequestCapabilityFlags, SpdmResponseCapabilityFlags};
+use crate::protocol::{
+    SpdmRequestCapabilityFlags, SpdmResponseCapabilityFlags, SPDM_PUBLIC_KEY_SLOT_ID,
+};
 use codec::{Codec, Reader, Writer};
 
 pub struct ResponderContext<'a> {
     pub common: crate::common::SpdmContext<'a>,
+    pub(crate) deferred_request: Option<SpdmDeferredRequest>,
 }
 
 impl<'a> ResponderContext<'a> {
@@ -29,20 +33,60 @@ impl<'a> ResponderContext<'a> {
                 config_info,
                 provision_info,
             ),
+            deferred_request: None,
         }
     }
 
-    pub fn send_message(&mut self, send_buffer: &[u8]) -> SpdmResult {
-        if self.common.negotiate_info.req_data_transfer_size_sel != 0
-            && (send_buffer.len() > self.common.negotiate_info.req_data_transfer_size_sel as usize)
+    // SlotID SPDM_PUBLIC_KEY_SLOT_ID selects the public key provisioned to the
+    // requester, if the responder supports PUB_KEY_ID_CAP.
+    pub(crate) fn is_my_public_key_slot_id(&self, slot_id: u8) -> bool {
+        slot_id == SPDM_PUBLIC_KEY_SLOT_ID
+            && self
+                .common
+                .negotiate_info
+                .rsp_capabilities_sel
+                .contains(SpdmResponseCapabilityFlags::PUB_KEY_ID_CAP)
+            && self.common.provision_info.my_public_key.is_some()
+    }
+
+    /// Resume the connection from a negotiated state cached before a reset, so
+    /// that the requester can skip GET_VERSION, GET_CAPABILITIES and
+    /// NEGOTIATE_ALGORITHMS.
+    pub fn init_connection_with_cache(
+        &mut self,
+        cached_state: &crate::common::SpdmCachedNegotiateState,
+    ) -> SpdmResult {
+        if cached_state.negotiate_info.rsp_capabilities_sel
+            != self.common.config_info.rsp_capabilities
         {
-            let mut err_buffer = [0u8; config::MAX_SPDM_MSG_SIZE];
-            let mut writer = Writer::init(&mut err_buffer);
-            self.write_spdm_error(SpdmErrorCode::SpdmErrorResponseTooLarge, 0, &mut writer);
-            return self.send_message(writer.used_slice());
+            return Err(SPDM_STATUS_NEGOTIATION_FAIL);
         }
+        self.common.restore_cached_negotiate_state(cached_state)?;
+        self.common.construct_my_cert_chain()
+    }
+
+    // A responder supporting CACHE_CAP which has no negotiated state, e.g.
+    // after a reset, asks the requester to restart from GET_VERSION.
+    fn is_resynch_required(&self, request_response_code: SpdmRequestResponseCode) -> bool {
+        self.common
+            .config_info
+            .rsp_capabilities
+            .contains(SpdmResponseCapabilityFlags::CACHE_CAP)
+            && self.common.runtime_info.get_connection_state().get_u8()
+                < SpdmConnectionState::SpdmConnectionNegotiated.get_u8()
+            && !matches!(
+                request_response_code,
+                SpdmRequestResponseCode::SpdmRequestGetVersion
+                    | SpdmRequestResponseCode::SpdmRequestGetCapabilities
+                    | SpdmRequestResponseCode::SpdmRequestNegotiateAlgorithms
+            )
+    }
+
+    pub fn send_message(&mut self, send_buffer: &[u8]) -> SpdmResult {
+        let mut chunk_buffer = [0u8; config::MAX_SPDM_MSG_SIZE];
+        let transfer_buffer = self.prepare_message_transfer(send_buffer, &mut chunk_buffer);
         let mut transport_buffer = [0u8; config::SENDER_BUFFER_SIZE];
-        let used = self.common.encap(send_buffer, &mut transport_buffer)?;
+        let used = self.common.encap(transfer_buffer, &mut transport_buffer)?;
         let result = self.common.device_io.send(&transport_buffer[..used]);
         if result.is_ok() {
             let opcode = send_buffer[1];
@@ -98,20 +142,17 @@ impl<'a> ResponderContext<'a> {
         send_buffer: &[u8],
         is_app_message: bool,
     ) -> SpdmResult {
-        if !is_app_message
-            && self.common.negotiate_info.req_data_transfer_size_sel != 0
-            && send_buffer.len() > self.common.negotiate_info.req_data_transfer_size_sel as usize
-        {
-            let mut err_buffer = [0u8; config::MAX_SPDM_MSG_SIZE];
-            let mut writer = Writer::init(&mut err_buffer);
-            self.write_spdm_error(SpdmErrorCode::SpdmErrorResponseTooLarge, 0, &mut writer);
-            return self.send_secured_message(session_id, writer.used_slice(), is_app_message);
-        }
+        let mut chunk_buffer = [0u8; config::MAX_SPDM_MSG_SIZE];
+        let transfer_buffer = if is_app_message {
+            send_buffer
+        } else {
+            self.prepare_message_transfer(send_buffer, &mut chunk_buffer)
+        };
 
         let mut transport_buffer = [0u8; config::SENDER_BUFFER_SIZE];
         let used = self.common.encode_secured_message(
             session_id,
-            send_buffer,
+            transfer_buffer,
             &mut transport_buffer,
             false,
             is_app_message,
@@ -136,6 +177,31 @@ impl<'a> ResponderContext<'a> {
         result
     }
 
+    // Make the SPDM response fit into the DataTransferSize of the requester.
+    // The response to a large request goes into the last CHUNK_SEND_ACK, and
+    // a large response is replaced by ERROR(LargeResponse) for CHUNK_GET.
+    fn prepare_message_transfer<'b>(
+        &mut self,
+        send_buffer: &'b [u8],
+        chunk_buffer: &'b mut [u8],
+    ) -> &'b [u8] {
+        if self.common.chunk_context.chunk_status == SpdmChunkStatus::SpdmChunkSendAckPending {
+            let mut writer = Writer::init(chunk_buffer);
+            self.write_spdm_chunk_send_ack_with_response(send_buffer, &mut writer);
+            let used = writer.used();
+            return &chunk_buffer[..used];
+        }
+        if self.common.negotiate_info.req_data_transfer_size_sel != 0
+            && send_buffer.len() > self.common.negotiate_info.req_data_transfer_size_sel as usize
+        {
+            let mut writer = Writer::init(chunk_buffer);
+            self.write_spdm_large_response(send_buffer, &mut writer);
+            let used = writer.used();
+            return &chunk_buffer[..used];
+        }
+        send_buffer
+    }
+
     pub fn process_message(
         &mut self,
         timeout: usize,
@@ -224,7 +290,7 @@ impl<'a> ResponderContext<'a> {
         Ok((used, secured_message))
     }
 
-    fn dispatch_secured_message(&mut self, session_id: u32, bytes: &[u8]) -> SpdmResult {
+    pub(crate) fn dispatch_secured_message(&mut self, session_id: u32, bytes: &[u8]) -> SpdmResult {
         let mut reader = Reader::init(bytes);
 
         let session = self.common.get_immutable_session_via_id(session_id);
@@ -253,11 +319,11 @@ impl<'a> ResponderContext<'a> {
                     Some(message_header) => match message_header.request_response_code {
                         #[cfg(feature = "mut-auth")]
                         SpdmRequestResponseCode::SpdmRequestGetEncapsulatedRequest => {
-                            self.handle_get_encapsulated_request(session_id, bytes)
+                            self.handle_get_encapsulated_request(Some(session_id), bytes)
                         }
                         #[cfg(feature = "mut-auth")]
                         SpdmRequestResponseCode::SpdmRequestDeliverEncapsulatedResponse => {
-                            self.handle_deliver_encapsulated_reponse(session_id, bytes)
+                            self.handle_deliver_encapsulated_reponse(Some(session_id), bytes)
                         }
                         SpdmRequestResponseCode::SpdmRequestFinish => {
                             self.handle_spdm_finish(session_id, bytes)
@@ -270,6 +336,12 @@ impl<'a> ResponderContext<'a> {
                         SpdmRequestResponseCode::SpdmRequestVendorDefinedRequest => {
                             self.handle_spdm_vendor_defined_request(Some(session_id), bytes)
                         }
+                        SpdmRequestResponseCode::SpdmRequestChunkSend => {
+                            self.handle_spdm_chunk_send(Some(session_id), bytes)
+                        }
+                        SpdmRequestResponseCode::SpdmRequestChunkGet => {
+                            self.handle_spdm_chunk_get(Some(session_id), bytes)
+                        }
 
                         SpdmRequestResponseCode::SpdmRequestGetVersion
                         | SpdmRequestResponseCode::SpdmRequestGetCapabilities
@@ -278,23 +350,28 @@ impl<'a> ResponderContext<'a> {
                         | SpdmRequestResponseCode::SpdmRequestGetCertificate
                         | SpdmRequestResponseCode::SpdmRequestChallenge
                         | SpdmRequestResponseCode::SpdmRequestGetMeasurements
+                        | SpdmRequestResponseCode::SpdmRequestGetCsr
+                        | SpdmRequestResponseCode::SpdmRequestGetMeasurementExtensionLog
+                        | SpdmRequestResponseCode::SpdmRequestGetEndpointInfo
+                        | SpdmRequestResponseCode::SpdmRequestGetKeyPairInfo
+                        | SpdmRequestResponseCode::SpdmRequestSetKeyPairInfo
+                        | SpdmRequestResponseCode::SpdmRequestSetCertificate
                         | SpdmRequestResponseCode::SpdmRequestKeyExchange
                         | SpdmRequestResponseCode::SpdmRequestPskExchange
                         | SpdmRequestResponseCode::SpdmRequestHeartbeat
                         | SpdmRequestResponseCode::SpdmRequestKeyUpdate
-                        | SpdmRequestResponseCode::SpdmRequestEndSession => self
+                        | SpdmRequestResponseCode::SpdmRequestEndSession
+                        | SpdmRequestResponseCode::SpdmRequestGetSupportedEventTypes
+                        | SpdmRequestResponseCode::SpdmRequestSubscribeEventTypes => self
                             .handle_error_request(
                                 SpdmErrorCode::SpdmErrorUnexpectedRequest,
                                 Some(session_id),
                                 bytes,
                             ),
 
-                        SpdmRequestResponseCode::SpdmRequestResponseIfReady => self
-                            .handle_error_request(
-                                SpdmErrorCode::SpdmErrorUnsupportedRequest,
-                                Some(session_id),
-                                bytes,
-                            ),
+                        SpdmRequestResponseCode::SpdmRequestResponseIfReady => {
+                            self.handle_spdm_respond_if_ready(Some(session_id), bytes)
+                        }
 
                         _ => Err(SPDM_STATUS_UNSUPPORTED_CAP),
                     },
@@ -313,6 +390,24 @@ impl<'a> ResponderContext<'a> {
                         SpdmRequestResponseCode::SpdmRequestGetMeasurements => {
                             self.handle_spdm_measurement(Some(session_id), bytes)
                         }
+                        SpdmRequestResponseCode::SpdmRequestGetCsr => {
+                            self.handle_spdm_csr(Some(session_id), bytes)
+                        }
+                        SpdmRequestResponseCode::SpdmRequestGetMeasurementExtensionLog => {
+                            self.handle_spdm_measurement_extension_log(Some(session_id), bytes)
+                        }
+                        SpdmRequestResponseCode::SpdmRequestGetEndpointInfo => {
+                            self.handle_spdm_endpoint_info(Some(session_id), bytes)
+                        }
+                        SpdmRequestResponseCode::SpdmRequestGetKeyPairInfo => {
+                            self.handle_spdm_get_key_pair_info(Some(session_id), bytes)
+                        }
+                        SpdmRequestResponseCode::SpdmRequestSetKeyPairInfo => {
+                            self.handle_spdm_set_key_pair_info(Some(session_id), bytes)
+                        }
+                        SpdmRequestResponseCode::SpdmRequestSetCertificate => {
+                            self.handle_spdm_set_certificate(Some(session_id), bytes)
+                        }
 
                         SpdmRequestResponseCode::SpdmRequestHeartbeat => {
                             self.handle_spdm_heartbeat(session_id, bytes)
@@ -321,13 +416,36 @@ impl<'a> ResponderContext<'a> {
                         SpdmRequestResponseCode::SpdmRequestKeyUpdate => {
                             self.handle_spdm_key_update(session_id, bytes)
                         }
+                        #[cfg(feature = "mut-auth")]
+                        SpdmRequestResponseCode::SpdmRequestGetEncapsulatedRequest => {
+                            self.handle_get_encapsulated_request(Some(session_id), bytes)
+                        }
+                        #[cfg(feature = "mut-auth")]
+                        SpdmRequestResponseCode::SpdmRequestDeliverEncapsulatedResponse => {
+                            self.handle_deliver_encapsulated_reponse(Some(session_id), bytes)
+                        }
 
                         SpdmRequestResponseCode::SpdmRequestEndSession => {
                             self.handle_spdm_end_session(session_id, bytes)
                         }
+                        SpdmRequestResponseCode::SpdmRequestGetSupportedEventTypes => {
+                            self.handle_spdm_get_supported_event_types(session_id, bytes)
+                        }
+                        SpdmRequestResponseCode::SpdmRequestSubscribeEventTypes => {
+                            self.handle_spdm_subscribe_event_types(session_id, bytes)
+                        }
+                        SpdmRequestResponseCode::SpdmResponseEventAck => {
+                            self.handle_spdm_event_ack(session_id, bytes)
+                        }
                         SpdmRequestResponseCode::SpdmRequestVendorDefinedRequest => {
                             self.handle_spdm_vendor_defined_request(Some(session_id), bytes)
                         }
+                        SpdmRequestResponseCode::SpdmRequestChunkSend => {
+                            self.handle_spdm_chunk_send(Some(session_id), bytes)
+                        }
+                        SpdmRequestResponseCode::SpdmRequestChunkGet => {
+                            self.handle_spdm_chunk_get(Some(session_id), bytes)
+                        }
 
                         SpdmRequestResponseCode::SpdmRequestGetVersion
                         | SpdmRequestResponseCode::SpdmRequestGetCapabilities
@@ -343,12 +461,9 @@ impl<'a> ResponderContext<'a> {
                                 bytes,
                             ),
 
-                        SpdmRequestResponseCode::SpdmRequestResponseIfReady => self
-                            .handle_error_request(
-                                SpdmErrorCode::SpdmErrorUnsupportedRequest,
-                                Some(session_id),
-                                bytes,
-                            ),
+                        SpdmRequestResponseCode::SpdmRequestResponseIfReady => {
+                            self.handle_spdm_respond_if_ready(Some(session_id), bytes)
+                        }
 
                         _ => Err(SPDM_STATUS_UNSUPPORTED_CAP),
                     },
@@ -375,6 +490,11 @@ impl<'a> ResponderContext<'a> {
     pub fn dispatch_message(&mut self, bytes: &[u8]) -> SpdmResult {
         let mut reader = Reader::init(bytes);
         match SpdmMessageHeader::read(&mut reader) {
+            Some(message_header)
+                if self.is_resynch_required(message_header.request_response_code) =>
+            {
+                self.handle_spdm_resynch(message_header.version)
+            }
             Some(message_header) => match message_header.request_response_code {
                 SpdmRequestResponseCode::SpdmRequestGetVersion => self.handle_spdm_version(bytes),
                 SpdmRequestResponseCode::SpdmRequestGetCapabilities => {
@@ -390,10 +510,36 @@ impl<'a> ResponderContext<'a> {
                     self.handle_spdm_certificate(bytes, None)
                 }
                 SpdmRequestResponseCode::SpdmRequestChallenge => self.handle_spdm_challenge(bytes),
+                #[cfg(feature = "mut-auth")]
+                SpdmRequestResponseCode::SpdmRequestGetEncapsulatedRequest => {
+                    self.handle_get_encapsulated_request(None, bytes)
+                }
+                #[cfg(feature = "mut-auth")]
+                SpdmRequestResponseCode::SpdmRequestDeliverEncapsulatedResponse => {
+                    self.handle_deliver_encapsulated_reponse(None, bytes)
+                }
                 SpdmRequestResponseCode::SpdmRequestGetMeasurements => {
                     self.handle_spdm_measurement(None, bytes)
                 }
 
+                SpdmRequestResponseCode::SpdmRequestGetCsr => self.handle_spdm_csr(None, bytes),
+                SpdmRequestResponseCode::SpdmRequestGetMeasurementExtensionLog => {
+                    self.handle_spdm_measurement_extension_log(None, bytes)
+                }
+                SpdmRequestResponseCode::SpdmRequestGetEndpointInfo => {
+                    self.handle_spdm_endpoint_info(None, bytes)
+                }
+                SpdmRequestResponseCode::SpdmRequestGetKeyPairInfo => {
+                    self.handle_spdm_get_key_pair_info(None, bytes)
+                }
+                SpdmRequestResponseCode::SpdmRequestSetKeyPairInfo => {
+                    self.handle_spdm_set_key_pair_info(None, bytes)
+                }
+
+                SpdmRequestResponseCode::SpdmRequestSetCertificate => {
+                    self.handle_spdm_set_certificate(None, bytes)
+                }
+
                 SpdmRequestResponseCode::SpdmRequestKeyExchange => {
                     self.handle_spdm_key_exchange(bytes)
                 }
@@ -406,6 +552,14 @@ impl<'a> ResponderContext<'a> {
                     self.handle_spdm_vendor_defined_request(None, bytes)
                 }
 
+                SpdmRequestResponseCode::SpdmRequestChunkSend => {
+                    self.handle_spdm_chunk_send(None, bytes)
+                }
+
+                SpdmRequestResponseCode::SpdmRequestChunkGet => {
+                    self.handle_spdm_chunk_get(None, bytes)
+                }
+
                 SpdmRequestResponseCode::SpdmRequestFinish => {
                     let in_clear_text = self
                         .common
@@ -441,17 +595,14 @@ impl<'a> ResponderContext<'a> {
                 SpdmRequestResponseCode::SpdmRequestPskFinish
                 | SpdmRequestResponseCode::SpdmRequestHeartbeat
                 | SpdmRequestResponseCode::SpdmRequestKeyUpdate
-                | SpdmRequestResponseCode::SpdmRequestEndSession => self.handle_error_request(
-                    SpdmErrorCode::SpdmErrorUnexpectedRequest,
-                    None,
-                    bytes,
-                ),
-
-                SpdmRequestResponseCode::SpdmRequestResponseIfReady => self.handle_error_request(
-                    SpdmErrorCode::SpdmErrorUnsupportedRequest,
-                    None,
-                    bytes,
-                ),
+                | SpdmRequestResponseCode::SpdmRequestEndSession
+                | SpdmRequestResponseCode::SpdmRequestGetSupportedEventTypes
+                | SpdmRequestResponseCode::SpdmRequestSubscribeEventTypes => self
+                    .handle_error_request(SpdmErrorCode::SpdmErrorUnexpectedRequest, None, bytes),
+
+                SpdmRequestResponseCode::SpdmRequestResponseIfReady => {
+                    self.handle_spdm_respond_if_ready(None, bytes)
+                }
 
                 _ => Err(SPDM_STATUS_UNSUPPORTED_CAP),
             },
@@ -0,0 +1,120 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use crate::common::SpdmCodec;
+use crate::common::SpdmConnectionState;
+use crate::error::SpdmResult;
+use crate::message::*;
+use crate::protocol::*;
+use crate::responder::*;
+use crate::secret;
+
+impl<'a> ResponderContext<'a> {
+    pub fn handle_spdm_csr(&mut self, session_id: Option<u32>, bytes: &[u8]) -> SpdmResult {
+        let mut send_buffer = [0u8; config::MAX_SPDM_MSG_SIZE];
+        let mut writer = Writer::init(&mut send_buffer);
+        self.write_spdm_csr_response(session_id, bytes, &mut writer);
+
+        if let Some(session_id) = session_id {
+            self.send_secured_message(session_id, writer.used_slice(), false)
+        } else {
+            self.send_message(writer.used_slice())
+        }
+    }
+
+    fn write_spdm_csr_response(
+        &mut self,
+        session_id: Option<u32>,
+        bytes: &[u8],
+        writer: &mut Writer,
+    ) {
+        if self.common.negotiate_info.spdm_version_sel.get_u8()
+            < SpdmVersion::SpdmVersion12.get_u8()
+            || !self
+                .common
+                .negotiate_info
+                .rsp_capabilities_sel
+                .contains(SpdmResponseCapabilityFlags::CSR_CAP)
+        {
+            self.write_error_response(SpdmErrorCode::SpdmErrorUnsupportedRequest, bytes, writer);
+            return;
+        }
+        if self.common.runtime_info.get_connection_state().get_u8()
+            < SpdmConnectionState::SpdmConnectionNegotiated.get_u8()
+        {
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorUnexpectedRequest, 0, writer);
+            return;
+        }
+        let mut reader = Reader::init(bytes);
+        let message_header = SpdmMessageHeader::read(&mut reader);
+        if let Some(message_header) = message_header {
+            if message_header.version != self.common.negotiate_info.spdm_version_sel {
+                self.write_spdm_error(SpdmErrorCode::SpdmErrorVersionMismatch, 0, writer);
+                return;
+            }
+        } else {
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorInvalidRequest, 0, writer);
+            return;
+        }
+
+        self.common
+            .reset_buffer_via_request_code(SpdmRequestResponseCode::SpdmRequestGetCsr, session_id);
+
+        let get_csr = SpdmGetCsrRequestPayload::spdm_read(&mut self.common, &mut reader);
+        let get_csr = if let Some(get_csr) = get_csr {
+            debug!(
+                "!!! get_csr : requester_info {:02x?}, opaque {:02x?}\n",
+                &get_csr.requester_info[..get_csr.requester_info_length as usize],
+                &get_csr.opaque.data[..get_csr.opaque.data_size as usize]
+            );
+            get_csr
+        } else {
+            error!("!!! get_csr : fail !!!\n");
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorInvalidRequest, 0, writer);
+            return;
+        };
+
+        let mut need_reset = false;
+        let csr = secret::csr::generate_csr(
+            self.common.negotiate_info.base_hash_sel,
+            self.common.negotiate_info.base_asym_sel,
+            &get_csr.requester_info[..get_csr.requester_info_length as usize],
+            &get_csr.opaque.data[..get_csr.opaque.data_size as usize],
+            &mut need_reset,
+        );
+        let csr = match csr {
+            Some(csr) if !need_reset => csr,
+            _ => {
+                if need_reset
+                    && self
+                        .common
+                        .negotiate_info
+                        .rsp_capabilities_sel
+                        .contains(SpdmResponseCapabilityFlags::CERT_INSTALL_RESET_CAP)
+                {
+                    info!("send spdm csr reset required\n");
+                    self.write_spdm_error(SpdmErrorCode::SpdmErrorResetRequired, 0, writer);
+                } else {
+                    error!("!!! generate csr : fail !!!\n");
+                    self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
+                }
+                return;
+            }
+        };
+
+        info!("send spdm csr\n");
+
+        let response = SpdmMessage {
+            header: SpdmMessageHeader {
+                version: self.common.negotiate_info.spdm_version_sel,
+                request_response_code: SpdmRequestResponseCode::SpdmResponseCsr,
+            },
+            payload: SpdmMessagePayload::SpdmCsrResponse(SpdmCsrResponsePayload { csr }),
+        };
+        let res = response.spdm_encode(&mut self.common, writer);
+        if res.is_err() {
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
+        }
+    }
+}
@@ -0,0 +1,213 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use codec::{Codec, Reader, Writer};
+
+use super::ResponderContext;
+
+#[cfg(feature = "hashed-transcript-data")]
+use crate::common::ManagedBuffer12Sign;
+#[cfg(not(feature = "hashed-transcript-data"))]
+use crate::common::ManagedBufferM1M2;
+use crate::common::SpdmCodec;
+use crate::crypto;
+#[cfg(feature = "hashed-transcript-data")]
+use crate::error::SPDM_STATUS_INVALID_STATE_LOCAL;
+use crate::error::{
+    SpdmResult, SPDM_STATUS_BUFFER_FULL, SPDM_STATUS_CRYPTO_ERROR, SPDM_STATUS_ERROR_PEER,
+    SPDM_STATUS_INVALID_MSG_FIELD, SPDM_STATUS_INVALID_MSG_SIZE, SPDM_STATUS_VERIF_FAIL,
+};
+use crate::message::*;
+use crate::protocol::*;
+
+impl<'a> ResponderContext<'a> {
+    pub fn encode_encap_request_challenge(&mut self, encap_request: &mut Writer) -> SpdmResult {
+        let mut nonce = [0u8; SPDM_NONCE_SIZE];
+        crypto::rand::get_random(&mut nonce)?;
+
+        let request = SpdmMessage {
+            header: SpdmMessageHeader {
+                version: self.common.negotiate_info.spdm_version_sel,
+                request_response_code: SpdmRequestResponseCode::SpdmRequestChallenge,
+            },
+            payload: SpdmMessagePayload::SpdmChallengeRequest(SpdmChallengeRequestPayload {
+                slot_id: self.common.encap_context.req_slot_id,
+                measurement_summary_hash_type:
+                    SpdmMeasurementSummaryHashType::SpdmMeasurementSummaryHashTypeNone,
+                nonce: SpdmNonceStruct { data: nonce },
+            }),
+        };
+
+        let start = encap_request.used();
+        let _ = request.spdm_encode(&mut self.common, encap_request)?;
+        self.common
+            .append_message_mut_c(&encap_request.used_slice()[start..])?;
+
+        Ok(())
+    }
+
+    pub fn handle_encap_response_challenge_auth(&mut self, encap_response: &[u8]) -> SpdmResult {
+        // The requester has no measurement to summarize.
+        self.common.runtime_info.need_measurement_summary_hash = false;
+
+        let mut reader = Reader::init(encap_response);
+        match SpdmMessageHeader::read(&mut reader) {
+            Some(header) => {
+                if header.version != self.common.negotiate_info.spdm_version_sel {
+                    return Err(SPDM_STATUS_INVALID_MSG_FIELD);
+                }
+                match header.request_response_code {
+                    SpdmRequestResponseCode::SpdmResponseChallengeAuth => {
+                        let challenge_auth = SpdmChallengeAuthResponsePayload::spdm_read(
+                            &mut self.common,
+                            &mut reader,
+                        );
+                        let used = reader.used();
+                        if let Some(challenge_auth) = challenge_auth {
+                            debug!("!!! mut_auth challenge_auth : {:02x?}\n", challenge_auth);
+                            if challenge_auth.slot_id != self.common.encap_context.req_slot_id {
+                                self.common.reset_message_mut_b();
+                                self.common.reset_message_mut_c();
+                                return Err(SPDM_STATUS_INVALID_MSG_FIELD);
+                            }
+
+                            let base_asym_size =
+                                self.common.negotiate_info.base_asym_sel.get_size() as usize;
+                            let temp_used = used - base_asym_size;
+                            self.common
+                                .append_message_mut_c(&encap_response[..temp_used])?;
+
+                            let result = self
+                                .verify_encap_challenge_auth_signature(&challenge_auth.signature);
+                            self.common.reset_message_mut_b();
+                            self.common.reset_message_mut_c();
+                            if result.is_err() {
+                                error!("verify_encap_challenge_auth_signature fail");
+                                return Err(SPDM_STATUS_VERIF_FAIL);
+                            }
+                            info!("verify_encap_challenge_auth_signature pass");
+                            Ok(())
+                        } else {
+                            error!("!!! mut_auth challenge_auth : fail !!!\n");
+                            Err(SPDM_STATUS_INVALID_MSG_FIELD)
+                        }
+                    }
+                    SpdmRequestResponseCode::SpdmResponseError => {
+                        let payload = SpdmMessageGeneralPayload::read(&mut reader)
+                            .ok_or(SPDM_STATUS_INVALID_MSG_SIZE)?;
+                        self.handle_encap_error_response_main(payload.param1)
+                    }
+                    _ => Err(SPDM_STATUS_ERROR_PEER),
+                }
+            }
+            None => Err(SPDM_STATUS_INVALID_MSG_FIELD),
+        }
+    }
+
+    #[cfg(feature = "hashed-transcript-data")]
+    pub fn verify_encap_challenge_auth_signature(
+        &self,
+        signature: &SpdmSignatureStruct,
+    ) -> SpdmResult {
+        let message_mut_m1m2_hash = crypto::hash::hash_ctx_finalize(
+            self.common
+                .runtime_info
+                .digest_context_mut_m1m2
+                .as_ref()
+                .cloned()
+                .ok_or(SPDM_STATUS_CRYPTO_ERROR)?,
+        )
+        .ok_or(SPDM_STATUS_CRYPTO_ERROR)?;
+        debug!(
+            "message_mut_m1m2_hash - {:02x?}",
+            message_mut_m1m2_hash.as_ref()
+        );
+
+        let mut message_sign = ManagedBuffer12Sign::default();
+        if self.common.negotiate_info.spdm_version_sel.get_u8()
+            >= SpdmVersion::SpdmVersion12.get_u8()
+        {
+            message_sign
+                .append_message(spdm_signing_prefix_context(
+                    self.common.negotiate_info.spdm_version_sel,
+                ))
+                .ok_or(SPDM_STATUS_BUFFER_FULL)?;
+            message_sign
+                .append_message(&SPDM_VERSION_1_2_SIGNING_CONTEXT_ZEROPAD_4)
+                .ok_or(SPDM_STATUS_BUFFER_FULL)?;
+            message_sign
+                .append_message(&SPDM_MUT_CHALLENGE_AUTH_SIGN_CONTEXT)
+                .ok_or(SPDM_STATUS_BUFFER_FULL)?;
+            message_sign
+                .append_message(message_mut_m1m2_hash.as_ref())
+                .ok_or(SPDM_STATUS_BUFFER_FULL)?;
+        } else {
+            error!("hashed-transcript-data is unsupported in SPDM 1.0/1.1 signing verification!\n");
+            return Err(SPDM_STATUS_INVALID_STATE_LOCAL);
+        }
+
+        self.common.verify_peer_signature(
+            self.common.encap_context.req_slot_id,
+            &SPDM_MUT_CHALLENGE_AUTH_SIGN_CONTEXT,
+            message_sign.as_ref(),
+            signature,
+        )
+    }
+
+    #[cfg(not(feature = "hashed-transcript-data"))]
+    pub fn verify_encap_challenge_auth_signature(
+        &self,
+        signature: &SpdmSignatureStruct,
+    ) -> SpdmResult {
+        let mut message_mut_m1m2 = ManagedBufferM1M2::default();
+        message_mut_m1m2
+            .append_message(self.common.runtime_info.message_a.as_ref())
+            .ok_or(SPDM_STATUS_BUFFER_FULL)?;
+        message_mut_m1m2
+            .append_message(self.common.runtime_info.message_mut_b.as_ref())
+            .ok_or(SPDM_STATUS_BUFFER_FULL)?;
+        message_mut_m1m2
+            .append_message(self.common.runtime_info.message_mut_c.as_ref())
+            .ok_or(SPDM_STATUS_BUFFER_FULL)?;
+
+        // we dont need create message hash for verify
+        // we just print message hash for debug purpose
+        let message_mut_m1m2_hash = crypto::hash::hash_all(
+            self.common.negotiate_info.base_hash_sel,
+            message_mut_m1m2.as_ref(),
+        )
+        .ok_or(SPDM_STATUS_CRYPTO_ERROR)?;
+        debug!(
+            "message_mut_m1m2_hash - {:02x?}",
+            message_mut_m1m2_hash.as_ref()
+        );
+
+        if self.common.negotiate_info.spdm_version_sel.get_u8()
+            >= SpdmVersion::SpdmVersion12.get_u8()
+        {
+            message_mut_m1m2.reset_message();
+            message_mut_m1m2
+                .append_message(spdm_signing_prefix_context(
+                    self.common.negotiate_info.spdm_version_sel,
+                ))
+                .ok_or(SPDM_STATUS_BUFFER_FULL)?;
+            message_mut_m1m2
+                .append_message(&SPDM_VERSION_1_2_SIGNING_CONTEXT_ZEROPAD_4)
+                .ok_or(SPDM_STATUS_BUFFER_FULL)?;
+            message_mut_m1m2
+                .append_message(&SPDM_MUT_CHALLENGE_AUTH_SIGN_CONTEXT)
+                .ok_or(SPDM_STATUS_BUFFER_FULL)?;
+            message_mut_m1m2
+                .append_message(message_mut_m1m2_hash.as_ref())
+                .ok_or(SPDM_STATUS_BUFFER_FULL)?;
+        }
+
+        self.common.verify_peer_signature(
+            self.common.encap_context.req_slot_id,
+            &SPDM_MUT_CHALLENGE_AUTH_SIGN_CONTEXT,
+            message_mut_m1m2.as_ref(),
+            signature,
+        )
+    }
+}
@@ -51,7 +51,10 @@ impl<'a> ResponderContext<'a> {
             ),
         };
 
+        let start = encap_request.used();
         let _ = encapsulated_request.spdm_encode(&mut self.common, encap_request)?;
+        self.common
+            .append_message_mut_b(&encap_request.used_slice()[start..])?;
 
         Ok(())
     }
@@ -121,6 +124,8 @@ impl<'a> ResponderContext<'a> {
                                 );
 
                             peer_cert_chain_temp.data_size = offset + certificate.portion_length;
+                            self.common
+                                .append_message_mut_b(&encap_response[..reader.used()])?;
 
                             if certificate.remainder_length == 0 {
                                 get_cert_completed = true;
@@ -203,6 +208,8 @@ impl<'a> ResponderContext<'a> {
         //
         if crypto::cert_operation::verify_cert_chain(
             &runtime_peer_cert_chain_data.data[..(runtime_peer_cert_chain_data.data_size as usize)],
+            true,
+            false,
         )
         .is_err()
         {
@@ -20,7 +20,10 @@ impl<'a> ResponderContext<'a> {
             payload: SpdmMessagePayload::SpdmGetDigestsRequest(SpdmGetDigestsRequestPayload {}),
         };
 
+        let start = encap_request.used();
         let _ = request.spdm_encode(&mut self.common, encap_request)?;
+        self.common
+            .append_message_mut_b(&encap_request.used_slice()[start..])?;
 
         Ok(())
     }
@@ -38,6 +41,9 @@ impl<'a> ResponderContext<'a> {
                             SpdmDigestsResponsePayload::spdm_read(&mut self.common, &mut reader);
                         if let Some(digests) = digests {
                             debug!("!!! digests : {:02x?}\n", digests);
+                            self.common
+                                .append_message_mut_b(&encap_response[..reader.used()])?;
+                            self.common.set_peer_key_pairs(&digests);
                             Ok(())
                         } else {
                             error!("!!! digests : fail !!!\n");
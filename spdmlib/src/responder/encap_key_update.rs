@@ -0,0 +1,172 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use codec::{Codec, Reader, Writer};
+
+use super::ResponderContext;
+
+use crate::common::SpdmCodec;
+use crate::error::{
+    SpdmResult, SPDM_STATUS_INVALID_MSG_FIELD, SPDM_STATUS_INVALID_PARAMETER,
+    SPDM_STATUS_INVALID_STATE_LOCAL, SPDM_STATUS_UNSUPPORTED_CAP,
+};
+use crate::message::*;
+use crate::protocol::*;
+
+impl<'a> ResponderContext<'a> {
+    /// Ask for a session key update. It is sent as an encapsulated KEY_UPDATE
+    /// on the next GET_ENCAPSULATED_REQUEST received in session.
+    pub fn init_encap_key_update(
+        &mut self,
+        key_update_operation: SpdmKeyUpdateOperation,
+    ) -> SpdmResult {
+        if key_update_operation != SpdmKeyUpdateOperation::SpdmUpdateAllKeys
+            && key_update_operation != SpdmKeyUpdateOperation::SpdmUpdateSingleKey
+        {
+            return Err(SPDM_STATUS_INVALID_PARAMETER);
+        }
+
+        if !self.common.negotiate_info.req_capabilities_sel.contains(
+            SpdmRequestCapabilityFlags::ENCAP_CAP | SpdmRequestCapabilityFlags::KEY_UPD_CAP,
+        ) || !self.common.negotiate_info.rsp_capabilities_sel.contains(
+            SpdmResponseCapabilityFlags::ENCAP_CAP | SpdmResponseCapabilityFlags::KEY_UPD_CAP,
+        ) {
+            return Err(SPDM_STATUS_UNSUPPORTED_CAP);
+        }
+
+        self.common.encap_context.key_update_op = Some(key_update_operation);
+        self.common.encap_context.key_update_pending = false;
+        Ok(())
+    }
+
+    pub fn encode_encap_request_key_update(&mut self, encap_request: &mut Writer) -> SpdmResult {
+        let (key_update_operation, tag) = match self.common.encap_context.key_update_op {
+            Some(SpdmKeyUpdateOperation::SpdmVerifyNewKey) => {
+                (SpdmKeyUpdateOperation::SpdmVerifyNewKey, 2)
+            }
+            Some(key_update_operation) => (key_update_operation, 1),
+            None => return Err(SPDM_STATUS_INVALID_STATE_LOCAL),
+        };
+
+        let request = SpdmMessage {
+            header: SpdmMessageHeader {
+                version: self.common.negotiate_info.spdm_version_sel,
+                request_response_code: SpdmRequestResponseCode::SpdmRequestKeyUpdate,
+            },
+            payload: SpdmMessagePayload::SpdmKeyUpdateRequest(SpdmKeyUpdateRequestPayload {
+                key_update_operation,
+                tag,
+            }),
+        };
+        let _ = request.spdm_encode(&mut self.common, encap_request)?;
+
+        self.common.encap_context.key_update_pending =
+            key_update_operation != SpdmKeyUpdateOperation::SpdmVerifyNewKey;
+        Ok(())
+    }
+
+    /// Derive the new key once the encapsulated KEY_UPDATE has been sent
+    /// with the old one.
+    pub fn create_encap_key_update(&mut self, session_id: u32) -> SpdmResult {
+        if !self.common.encap_context.key_update_pending {
+            return Ok(());
+        }
+        self.common.encap_context.key_update_pending = false;
+
+        let (update_requester, update_responder) = self.get_encap_key_update_direction();
+        let spdm_version_sel = self.common.negotiate_info.spdm_version_sel;
+        let session = self
+            .common
+            .get_session_via_id(session_id)
+            .ok_or(SPDM_STATUS_INVALID_PARAMETER)?;
+        session.create_data_secret_update(spdm_version_sel, update_requester, update_responder)
+    }
+
+    /// Returns true if the VerifyNewKey request still needs to be sent.
+    pub fn handle_encap_response_key_update(
+        &mut self,
+        session_id: u32,
+        encap_response: &[u8],
+    ) -> SpdmResult<bool> {
+        let key_update_operation = if let Some(op) = self.common.encap_context.key_update_op {
+            op
+        } else {
+            return Err(SPDM_STATUS_INVALID_STATE_LOCAL);
+        };
+        let verify_new_key = key_update_operation == SpdmKeyUpdateOperation::SpdmVerifyNewKey;
+        let tag = if verify_new_key { 2 } else { 1 };
+
+        let result = self.read_encap_key_update_ack(encap_response, key_update_operation, tag);
+
+        if verify_new_key {
+            self.common.encap_context.key_update_op = None;
+            return result.map(|_| false);
+        }
+
+        let (update_requester, update_responder) = self.get_encap_key_update_direction();
+        let spdm_version_sel = self.common.negotiate_info.spdm_version_sel;
+        let session = self
+            .common
+            .get_session_via_id(session_id)
+            .ok_or(SPDM_STATUS_INVALID_PARAMETER)?;
+        session.activate_data_secret_update(
+            spdm_version_sel,
+            update_requester,
+            update_responder,
+            result.is_ok(),
+        )?;
+
+        if result.is_ok() {
+            self.common.encap_context.key_update_op =
+                Some(SpdmKeyUpdateOperation::SpdmVerifyNewKey);
+        } else {
+            self.common.encap_context.key_update_op = None;
+        }
+        result.map(|_| true)
+    }
+
+    fn read_encap_key_update_ack(
+        &mut self,
+        encap_response: &[u8],
+        key_update_operation: SpdmKeyUpdateOperation,
+        tag: u8,
+    ) -> SpdmResult {
+        let mut reader = Reader::init(encap_response);
+        let header = SpdmMessageHeader::read(&mut reader).ok_or(SPDM_STATUS_INVALID_MSG_FIELD)?;
+        if header.version != self.common.negotiate_info.spdm_version_sel {
+            return Err(SPDM_STATUS_INVALID_MSG_FIELD);
+        }
+
+        match header.request_response_code {
+            SpdmRequestResponseCode::SpdmResponseKeyUpdateAck => {
+                match SpdmKeyUpdateResponsePayload::spdm_read(&mut self.common, &mut reader) {
+                    Some(key_update_rsp)
+                        if key_update_rsp.key_update_operation == key_update_operation
+                            && key_update_rsp.tag == tag =>
+                    {
+                        debug!("!!! encap key_update rsp : {:02x?}\n", key_update_rsp);
+                        Ok(())
+                    }
+                    _ => {
+                        error!("!!! encap key_update rsp : fail !!!\n");
+                        Err(SPDM_STATUS_INVALID_MSG_FIELD)
+                    }
+                }
+            }
+            SpdmRequestResponseCode::SpdmResponseError => {
+                let payload = SpdmMessageGeneralPayload::read(&mut reader)
+                    .ok_or(SPDM_STATUS_INVALID_MSG_FIELD)?;
+                self.handle_encap_error_response_main(payload.param1)
+            }
+            _ => Err(SPDM_STATUS_INVALID_MSG_FIELD),
+        }
+    }
+
+    // The responder sends the KEY_UPDATE, so its own key is always updated.
+    fn get_encap_key_update_direction(&self) -> (bool, bool) {
+        let update_requester = self.common.encap_context.key_update_op
+            == Some(SpdmKeyUpdateOperation::SpdmUpdateAllKeys);
+        (update_requester, true)
+    }
+}
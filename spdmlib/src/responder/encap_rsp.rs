@@ -22,7 +22,11 @@ use crate::{
 use super::ResponderContext;
 
 impl<'a> ResponderContext<'a> {
-    pub fn handle_get_encapsulated_request(&mut self, session_id: u32, bytes: &[u8]) -> SpdmResult {
+    pub fn handle_get_encapsulated_request(
+        &mut self,
+        session_id: Option<u32>,
+        bytes: &[u8],
+    ) -> SpdmResult {
         let mut encapsulated_request = [0u8; config::MAX_SPDM_MSG_SIZE];
         let mut writer = Writer::init(&mut encapsulated_request);
 
@@ -30,12 +34,29 @@ impl<'a> ResponderContext<'a> {
             SpdmRequestResponseCode::SpdmRequestGetEncapsulatedRequest.get_u8(),
             &mut writer,
         );
-        self.write_encap_request_response(bytes, &mut writer);
+        self.write_encap_request_response(session_id, bytes, &mut writer);
 
-        self.send_secured_message(session_id, writer.used_slice(), false)
+        if let Some(session_id) = session_id {
+            self.send_secured_message(session_id, writer.used_slice(), false)?;
+            self.create_encap_key_update(session_id)
+        } else {
+            self.send_message(writer.used_slice())
+        }
     }
 
-    fn write_encap_request_response(&mut self, bytes: &[u8], writer: &mut Writer) {
+    fn write_encap_request_response(
+        &mut self,
+        session_id: Option<u32>,
+        bytes: &[u8],
+        writer: &mut Writer,
+    ) {
+        // Out of session, the encapsulated flow is only allowed for the basic
+        // mutual authentication requested in CHALLENGE_AUTH.
+        if session_id.is_none() && !self.common.encap_context.basic_mut_auth_requested {
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorUnexpectedRequest, 0, writer);
+            return;
+        }
+
         let mut reader = Reader::init(bytes);
         if let Some(request_header) = SpdmMessageHeader::read(&mut reader) {
             if request_header.version != self.common.negotiate_info.spdm_version_sel {
@@ -67,6 +88,15 @@ impl<'a> ResponderContext<'a> {
             return;
         }
 
+        if session_id.is_some() && self.common.encap_context.key_update_op.is_some() {
+            if self.encode_encap_request_key_update(writer).is_err() {
+                self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
+            }
+            return;
+        }
+
+        self.common.reset_message_mut_b();
+        self.common.reset_message_mut_c();
         if self.encode_encap_request_get_digest(writer).is_err() {
             self.write_spdm_error(SpdmErrorCode::SpdmErrorInvalidResponseCode, 0, writer);
             return;
@@ -75,7 +105,7 @@ impl<'a> ResponderContext<'a> {
 
     pub fn handle_deliver_encapsulated_reponse(
         &mut self,
-        session_id: u32,
+        session_id: Option<u32>,
         bytes: &[u8],
     ) -> SpdmResult {
         let mut encap_response_ack = [0u8; config::MAX_SPDM_MSG_SIZE];
@@ -85,12 +115,21 @@ impl<'a> ResponderContext<'a> {
             SpdmRequestResponseCode::SpdmRequestGetEncapsulatedRequest.get_u8(),
             &mut writer,
         );
-        self.write_encap_response_ack_response(bytes, &mut writer);
+        self.write_encap_response_ack_response(session_id, bytes, &mut writer);
 
-        self.send_secured_message(session_id, writer.used_slice(), false)
+        if let Some(session_id) = session_id {
+            self.send_secured_message(session_id, writer.used_slice(), false)
+        } else {
+            self.send_message(writer.used_slice())
+        }
     }
 
-    fn write_encap_response_ack_response(&mut self, bytes: &[u8], writer: &mut Writer) {
+    fn write_encap_response_ack_response(
+        &mut self,
+        session_id: Option<u32>,
+        bytes: &[u8],
+        writer: &mut Writer,
+    ) {
         let mut reader = Reader::init(bytes);
         if let Some(request_header) = SpdmMessageHeader::read(&mut reader) {
             if request_header.version != self.common.negotiate_info.spdm_version_sel {
@@ -112,7 +151,12 @@ impl<'a> ResponderContext<'a> {
         };
 
         if self
-            .process_encapsulated_response(&encap_response_payload, &bytes[reader.used()..], writer)
+            .process_encapsulated_response(
+                session_id,
+                &encap_response_payload,
+                &bytes[reader.used()..],
+                writer,
+            )
             .is_err()
         {
             self.write_spdm_error(SpdmErrorCode::SpdmErrorInvalidResponseCode, 0, writer);
@@ -157,6 +201,7 @@ impl<'a> ResponderContext<'a> {
 
     fn process_encapsulated_response(
         &mut self,
+        session_id: Option<u32>,
         encap_response_payload: &SpdmDeliverEncapsulatedResponsePayload,
         encap_response: &[u8],
         encap_response_ack: &mut Writer,
@@ -171,6 +216,12 @@ impl<'a> ResponderContext<'a> {
             return Err(SPDM_STATUS_INVALID_MSG_SIZE);
         };
 
+        // The mutual authentication transcript is only signed out of session.
+        if session_id.is_some() {
+            self.common.reset_message_mut_b();
+            self.common.reset_message_mut_c();
+        }
+
         let header = SpdmMessageHeader {
             version: self.common.negotiate_info.spdm_version_sel,
             request_response_code: SpdmRequestResponseCode::SpdmResponseEncapsulatedResponseAck,
@@ -196,6 +247,10 @@ impl<'a> ResponderContext<'a> {
                         if need_continue {
                             let _ = ack_params.spdm_encode(&mut self.common, encap_response_ack)?;
                             self.encode_encap_requst_get_certificate(encap_response_ack)
+                        } else if session_id.is_none() {
+                            // Basic mutual authentication goes on with CHALLENGE.
+                            let _ = ack_params.spdm_encode(&mut self.common, encap_response_ack)?;
+                            self.encode_encap_request_challenge(encap_response_ack)
                         } else {
                             ack_params.payload_type =
                                 SpdmEncapsulatedResponseAckPayloadType::ReqSlotNumber;
@@ -221,6 +276,28 @@ impl<'a> ResponderContext<'a> {
                     }
                 }
             }
+            SpdmRequestResponseCode::SpdmResponseChallengeAuth if session_id.is_none() => {
+                self.handle_encap_response_challenge_auth(encap_response)?;
+
+                ack_params.payload_type = SpdmEncapsulatedResponseAckPayloadType::Absent;
+                let _ = ack_params.spdm_encode(&mut self.common, encap_response_ack)?;
+                Ok(())
+            }
+            SpdmRequestResponseCode::SpdmResponseKeyUpdateAck
+            | SpdmRequestResponseCode::SpdmResponseError
+                if self.common.encap_context.key_update_op.is_some() =>
+            {
+                // KEY_UPDATE is only allowed in session.
+                let session_id = session_id.ok_or(SPDM_STATUS_UNSUPPORTED_CAP)?;
+                if self.handle_encap_response_key_update(session_id, encap_response)? {
+                    let _ = ack_params.spdm_encode(&mut self.common, encap_response_ack)?;
+                    self.encode_encap_request_key_update(encap_response_ack)
+                } else {
+                    ack_params.payload_type = SpdmEncapsulatedResponseAckPayloadType::Absent;
+                    let _ = ack_params.spdm_encode(&mut self.common, encap_response_ack)?;
+                    Ok(())
+                }
+            }
             _ => Err(SPDM_STATUS_UNSUPPORTED_CAP),
         }
     }
@@ -0,0 +1,235 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use crate::common::ManagedBuffer12Sign;
+use crate::common::SpdmCodec;
+use crate::common::SpdmConnectionState;
+use crate::crypto;
+use crate::error::{SpdmResult, SPDM_STATUS_BUFFER_FULL, SPDM_STATUS_CRYPTO_ERROR};
+use crate::message::*;
+use crate::protocol::*;
+use crate::responder::*;
+use crate::secret;
+
+impl<'a> ResponderContext<'a> {
+    pub fn handle_spdm_endpoint_info(
+        &mut self,
+        session_id: Option<u32>,
+        bytes: &[u8],
+    ) -> SpdmResult {
+        let mut send_buffer = [0u8; config::MAX_SPDM_MSG_SIZE];
+        let mut writer = Writer::init(&mut send_buffer);
+        self.write_spdm_endpoint_info_response(session_id, bytes, &mut writer);
+
+        if let Some(session_id) = session_id {
+            self.send_secured_message(session_id, writer.used_slice(), false)
+        } else {
+            self.send_message(writer.used_slice())
+        }
+    }
+
+    fn write_spdm_endpoint_info_response(
+        &mut self,
+        session_id: Option<u32>,
+        bytes: &[u8],
+        writer: &mut Writer,
+    ) {
+        let rsp_capabilities_sel = self.common.negotiate_info.rsp_capabilities_sel;
+        if self.common.negotiate_info.spdm_version_sel.get_u8()
+            < SpdmVersion::SpdmVersion13.get_u8()
+            || !rsp_capabilities_sel.intersects(
+                SpdmResponseCapabilityFlags::EP_INFO_CAP_NO_SIG
+                    | SpdmResponseCapabilityFlags::EP_INFO_CAP_SIG,
+            )
+        {
+            self.write_error_response(SpdmErrorCode::SpdmErrorUnsupportedRequest, bytes, writer);
+            return;
+        }
+        if self.common.runtime_info.get_connection_state().get_u8()
+            < SpdmConnectionState::SpdmConnectionNegotiated.get_u8()
+        {
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorUnexpectedRequest, 0, writer);
+            return;
+        }
+        let mut reader = Reader::init(bytes);
+        let message_header = SpdmMessageHeader::read(&mut reader);
+        if let Some(message_header) = message_header {
+            if message_header.version != self.common.negotiate_info.spdm_version_sel {
+                self.write_spdm_error(SpdmErrorCode::SpdmErrorVersionMismatch, 0, writer);
+                return;
+            }
+        } else {
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorInvalidRequest, 0, writer);
+            return;
+        }
+
+        self.common.reset_buffer_via_request_code(
+            SpdmRequestResponseCode::SpdmRequestGetEndpointInfo,
+            session_id,
+        );
+
+        let get_endpoint_info =
+            SpdmGetEndpointInfoRequestPayload::spdm_read(&mut self.common, &mut reader);
+        let get_endpoint_info = if let Some(get_endpoint_info) = get_endpoint_info {
+            debug!("!!! get_endpoint_info : {:02x?}\n", get_endpoint_info);
+            get_endpoint_info
+        } else {
+            error!("!!! get_endpoint_info : fail !!!\n");
+            self.common.reset_message_e(session_id);
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorInvalidRequest, 0, writer);
+            return;
+        };
+
+        let signature_requested = get_endpoint_info
+            .request_attributes
+            .contains(SpdmEndpointInfoRequestAttributes::SIGNATURE_REQUESTED);
+        // SlotID 0xF selects the provisioned public key, it is reserved without signature.
+        let slot_id = if !signature_requested {
+            0
+        } else if get_endpoint_info.slot_id == SPDM_PUBLIC_KEY_SLOT_ID_PARAM {
+            SPDM_PUBLIC_KEY_SLOT_ID
+        } else {
+            get_endpoint_info.slot_id
+        };
+        if signature_requested
+            && (!rsp_capabilities_sel.contains(SpdmResponseCapabilityFlags::EP_INFO_CAP_SIG)
+                || (slot_id >= SPDM_MAX_SLOT_NUMBER as u8
+                    && !self.is_my_public_key_slot_id(slot_id))
+                || self
+                    .common
+                    .get_my_cert_chain_or_public_key(slot_id)
+                    .is_none())
+        {
+            self.common.reset_message_e(session_id);
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorInvalidRequest, 0, writer);
+            return;
+        }
+        self.common.runtime_info.need_endpoint_info_signature = signature_requested;
+
+        let spdm_version_sel = self.common.negotiate_info.spdm_version_sel;
+        let endpoint_info = if let Some(endpoint_info) =
+            secret::endpoint_info::endpoint_info(spdm_version_sel, get_endpoint_info.sub_code)
+        {
+            endpoint_info
+        } else {
+            self.common.reset_message_e(session_id);
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
+            return;
+        };
+
+        if self
+            .common
+            .append_message_e(session_id, &bytes[..reader.used()])
+            .is_err()
+        {
+            self.common.reset_message_e(session_id);
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
+            return;
+        }
+
+        let mut nonce = [0u8; SPDM_NONCE_SIZE];
+        if signature_requested && crypto::rand::get_random(&mut nonce).is_err() {
+            self.common.reset_message_e(session_id);
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
+            return;
+        }
+
+        info!("send spdm endpoint_info\n");
+
+        let base_asym_size = self.common.get_asym_key_size() as usize;
+        let response = SpdmMessage {
+            header: SpdmMessageHeader {
+                version: spdm_version_sel,
+                request_response_code: SpdmRequestResponseCode::SpdmResponseEndpointInfo,
+            },
+            payload: SpdmMessagePayload::SpdmEndpointInfoResponse(
+                SpdmEndpointInfoResponsePayload {
+                    slot_id: get_endpoint_info.slot_id,
+                    nonce: SpdmNonceStruct { data: nonce },
+                    endpoint_info,
+                    signature: SpdmSignatureStruct {
+                        data_size: base_asym_size as u16,
+                        data: [0x60u8; SPDM_MAX_ASYM_KEY_SIZE],
+                    },
+                },
+            ),
+        };
+
+        let res = response.spdm_encode(&mut self.common, writer);
+        if res.is_err() {
+            self.common.reset_message_e(session_id);
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
+            return;
+        }
+        let used = writer.used();
+
+        if signature_requested {
+            let temp_used = used - base_asym_size;
+            if self
+                .common
+                .append_message_e(session_id, &writer.used_slice()[..temp_used])
+                .is_err()
+            {
+                self.common.reset_message_e(session_id);
+                self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
+                return;
+            }
+
+            let signature = self.generate_endpoint_info_signature(session_id, slot_id);
+            self.common.reset_message_e(session_id);
+            let signature = if let Ok(signature) = signature {
+                signature
+            } else {
+                self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
+                return;
+            };
+            // patch the message before send
+            writer.mut_used_slice()[temp_used..used].copy_from_slice(signature.as_ref());
+        } else if self
+            .common
+            .append_message_e(session_id, writer.used_slice())
+            .is_err()
+        {
+            self.common.reset_message_e(session_id);
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
+        }
+    }
+
+    pub fn generate_endpoint_info_signature(
+        &self,
+        session_id: Option<u32>,
+        slot_id: u8,
+    ) -> SpdmResult<SpdmSignatureStruct> {
+        let message_il1il2_hash = self.common.calc_message_il1il2_hash(session_id)?;
+        debug!(
+            "message_il1il2_hash - {:02x?}",
+            message_il1il2_hash.as_ref()
+        );
+
+        let mut message_sign = ManagedBuffer12Sign::default();
+        message_sign
+            .append_message(spdm_signing_prefix_context(
+                self.common.negotiate_info.spdm_version_sel,
+            ))
+            .ok_or(SPDM_STATUS_BUFFER_FULL)?;
+        message_sign
+            .append_message(&SPDM_VERSION_1_2_SIGNING_CONTEXT_ZEROPAD_5)
+            .ok_or(SPDM_STATUS_BUFFER_FULL)?;
+        message_sign
+            .append_message(&SPDM_ENDPOINT_INFO_SIGN_CONTEXT)
+            .ok_or(SPDM_STATUS_BUFFER_FULL)?;
+        message_sign
+            .append_message(message_il1il2_hash.as_ref())
+            .ok_or(SPDM_STATUS_BUFFER_FULL)?;
+
+        self.common
+            .sign_with_my_key_pair(
+                slot_id,
+                &SPDM_ENDPOINT_INFO_SIGN_CONTEXT,
+                message_sign.as_ref(),
+            )
+            .ready()
+            .ok_or(SPDM_STATUS_CRYPTO_ERROR)
+    }
+}
@@ -5,6 +5,7 @@
 use crate::common::SpdmCodec;
 use crate::error::SpdmResult;
 use crate::message::*;
+use crate::protocol::SpdmVersion;
 use crate::responder::*;
 
 impl<'a> ResponderContext<'a> {
@@ -40,6 +41,18 @@ impl<'a> ResponderContext<'a> {
 }
 
 impl<'a> ResponderContext<'a> {
+    /// Answer a request received without negotiated state with
+    /// ErrorCode=RequestResynch, in the version of the request.
+    pub fn handle_spdm_resynch(&mut self, version: SpdmVersion) -> SpdmResult {
+        if self.common.config_info.spdm_version.contains(&version) {
+            self.common.negotiate_info.spdm_version_sel = version;
+        }
+        let mut send_buffer = [0u8; config::MAX_SPDM_MSG_SIZE];
+        let mut writer = Writer::init(&mut send_buffer);
+        self.write_spdm_error(SpdmErrorCode::SpdmErrorRequestResynch, 0, &mut writer);
+        self.send_message(writer.used_slice())
+    }
+
     pub fn handle_error_request(
         &mut self,
         error_code: SpdmErrorCode,
@@ -0,0 +1,327 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use crate::common::session::SpdmSessionState;
+use crate::common::SpdmCodec;
+use crate::error::{
+    SpdmResult, SPDM_STATUS_BUFFER_FULL, SPDM_STATUS_INVALID_MSG_FIELD,
+    SPDM_STATUS_INVALID_PARAMETER, SPDM_STATUS_INVALID_STATE_LOCAL, SPDM_STATUS_UNSUPPORTED_CAP,
+};
+use crate::message::*;
+use crate::protocol::*;
+use crate::responder::*;
+
+impl<'a> ResponderContext<'a> {
+    fn is_event_supported(&self) -> bool {
+        self.common.negotiate_info.spdm_version_sel.get_u8() >= SpdmVersion::SpdmVersion13.get_u8()
+            && self
+                .common
+                .negotiate_info
+                .rsp_capabilities_sel
+                .contains(SpdmResponseCapabilityFlags::EVENT_CAP)
+    }
+
+    pub fn handle_spdm_get_supported_event_types(
+        &mut self,
+        session_id: u32,
+        bytes: &[u8],
+    ) -> SpdmResult {
+        let mut send_buffer = [0u8; config::MAX_SPDM_MSG_SIZE];
+        let mut writer = Writer::init(&mut send_buffer);
+        self.write_spdm_supported_event_types_response(session_id, bytes, &mut writer);
+        self.send_secured_message(session_id, writer.used_slice(), false)
+    }
+
+    pub fn write_spdm_supported_event_types_response(
+        &mut self,
+        session_id: u32,
+        bytes: &[u8],
+        writer: &mut Writer,
+    ) {
+        if !self.is_event_supported() {
+            self.write_error_response(SpdmErrorCode::SpdmErrorUnsupportedRequest, bytes, writer);
+            return;
+        }
+        let mut reader = Reader::init(bytes);
+        let message_header = SpdmMessageHeader::read(&mut reader);
+        if let Some(message_header) = message_header {
+            if message_header.version != self.common.negotiate_info.spdm_version_sel {
+                self.write_spdm_error(SpdmErrorCode::SpdmErrorVersionMismatch, 0, writer);
+                return;
+            }
+        } else {
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorInvalidRequest, 0, writer);
+            return;
+        }
+
+        self.common.reset_buffer_via_request_code(
+            SpdmRequestResponseCode::SpdmRequestGetSupportedEventTypes,
+            Some(session_id),
+        );
+
+        let get_supported_event_types =
+            SpdmGetSupportedEventTypesRequestPayload::spdm_read(&mut self.common, &mut reader);
+        if let Some(get_supported_event_types) = get_supported_event_types {
+            debug!(
+                "!!! get_supported_event_types : {:02x?}\n",
+                get_supported_event_types
+            );
+        } else {
+            error!("!!! get_supported_event_types : fail !!!\n");
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorInvalidRequest, 0, writer);
+            return;
+        }
+
+        info!("send spdm supported_event_types\n");
+
+        let mut event_groups = [SpdmEventGroup::default(); MAX_SPDM_EVENT_GROUP_COUNT];
+        event_groups[0] = SpdmEventGroup::new_dmtf();
+        let response = SpdmMessage {
+            header: SpdmMessageHeader {
+                version: self.common.negotiate_info.spdm_version_sel,
+                request_response_code: SpdmRequestResponseCode::SpdmResponseSupportedEventTypes,
+            },
+            payload: SpdmMessagePayload::SpdmSupportedEventTypesResponse(
+                SpdmSupportedEventTypesResponsePayload {
+                    event_group_count: 1,
+                    event_groups,
+                },
+            ),
+        };
+        let res = response.spdm_encode(&mut self.common, writer);
+        if res.is_err() {
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
+        }
+    }
+
+    pub fn handle_spdm_subscribe_event_types(
+        &mut self,
+        session_id: u32,
+        bytes: &[u8],
+    ) -> SpdmResult {
+        let mut send_buffer = [0u8; config::MAX_SPDM_MSG_SIZE];
+        let mut writer = Writer::init(&mut send_buffer);
+        self.write_spdm_subscribe_event_types_ack_response(session_id, bytes, &mut writer);
+        self.send_secured_message(session_id, writer.used_slice(), false)
+    }
+
+    pub fn write_spdm_subscribe_event_types_ack_response(
+        &mut self,
+        session_id: u32,
+        bytes: &[u8],
+        writer: &mut Writer,
+    ) {
+        if !self.is_event_supported() {
+            self.write_error_response(SpdmErrorCode::SpdmErrorUnsupportedRequest, bytes, writer);
+            return;
+        }
+        let mut reader = Reader::init(bytes);
+        let message_header = SpdmMessageHeader::read(&mut reader);
+        if let Some(message_header) = message_header {
+            if message_header.version != self.common.negotiate_info.spdm_version_sel {
+                self.write_spdm_error(SpdmErrorCode::SpdmErrorVersionMismatch, 0, writer);
+                return;
+            }
+        } else {
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorInvalidRequest, 0, writer);
+            return;
+        }
+
+        self.common.reset_buffer_via_request_code(
+            SpdmRequestResponseCode::SpdmRequestSubscribeEventTypes,
+            Some(session_id),
+        );
+
+        let subscribe_event_types =
+            SpdmSubscribeEventTypesRequestPayload::spdm_read(&mut self.common, &mut reader);
+        let subscribe_event_types = if let Some(subscribe_event_types) = subscribe_event_types {
+            debug!(
+                "!!! subscribe_event_types : {:02x?}\n",
+                subscribe_event_types
+            );
+            subscribe_event_types
+        } else {
+            error!("!!! subscribe_event_types : fail !!!\n");
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorInvalidRequest, 0, writer);
+            return;
+        };
+
+        // Only the event types advertised in SUPPORTED_EVENT_TYPES can be subscribed.
+        let supported_group = SpdmEventGroup::new_dmtf();
+        for group in subscribe_event_types
+            .event_groups
+            .iter()
+            .take(subscribe_event_types.event_group_count as usize)
+        {
+            if group.group_id != supported_group.group_id
+                || group.group_version != supported_group.group_version
+                || group.event_type_count == 0
+                || group
+                    .event_types
+                    .iter()
+                    .take(group.event_type_count as usize)
+                    .any(|t| !supported_group.contains_event_type(*t))
+            {
+                error!("!!! subscribe_event_types : unsupported event group !!!\n");
+                self.write_spdm_error(SpdmErrorCode::SpdmErrorInvalidRequest, 0, writer);
+                return;
+            }
+        }
+
+        let session = self.common.get_session_via_id(session_id).unwrap();
+        session.event_info.subscribed_group_count = subscribe_event_types.event_group_count;
+        session.event_info.subscribed_groups = subscribe_event_types.event_groups;
+        if subscribe_event_types.event_group_count == 0 {
+            session.event_info.event_count = 0;
+            session.event_info.sent_event_count = 0;
+        }
+
+        info!("send spdm subscribe_event_types_ack\n");
+
+        let response = SpdmMessage {
+            header: SpdmMessageHeader {
+                version: self.common.negotiate_info.spdm_version_sel,
+                request_response_code: SpdmRequestResponseCode::SpdmResponseSubscribeEventTypesAck,
+            },
+            payload: SpdmMessagePayload::SpdmSubscribeEventTypesAckResponse(
+                SpdmSubscribeEventTypesAckResponsePayload {},
+            ),
+        };
+        let res = response.spdm_encode(&mut self.common, writer);
+        if res.is_err() {
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
+        }
+    }
+
+    // Queue an event for every established session that subscribed to it.
+    // When the queue of a session is full, its last entry is replaced by an
+    // EventLost event covering the events that could not be delivered.
+    pub fn queue_spdm_event(
+        &mut self,
+        group_id: &SpdmEventGroupId,
+        event_type_id: u16,
+        event_detail: &[u8],
+    ) -> SpdmResult {
+        if !self.is_event_supported() {
+            return Err(SPDM_STATUS_UNSUPPORTED_CAP);
+        }
+        if event_detail.len() > MAX_SPDM_EVENT_DETAIL_SIZE {
+            return Err(SPDM_STATUS_INVALID_PARAMETER);
+        }
+
+        for session in self.common.session.iter_mut() {
+            if session.get_session_state() != SpdmSessionState::SpdmSessionEstablished
+                || !session.event_info.is_subscribed(group_id, event_type_id)
+            {
+                continue;
+            }
+            let event_info = &mut session.event_info;
+            let event_instance_id = event_info.next_event_instance_id;
+            event_info.next_event_instance_id = event_instance_id.wrapping_add(1);
+
+            let mut event = SpdmEventData {
+                event_instance_id,
+                group_id: *group_id,
+                event_type_id,
+                event_detail_len: event_detail.len() as u16,
+                ..Default::default()
+            };
+            event.event_detail[..event_detail.len()].copy_from_slice(event_detail);
+
+            let event_count = event_info.event_count as usize;
+            if event_count < MAX_SPDM_EVENT_COUNT {
+                event_info.events[event_count] = event;
+                event_info.event_count += 1;
+            } else {
+                // The last slot is never in flight (see send_spdm_event), so
+                // it is replaced by EventLost.
+                // EventLost detail: LastAckedEventInstanceID (4), LastLostEventInstanceID (4).
+                let last_acked = event_info.events[0].event_instance_id.wrapping_sub(1);
+                let mut lost = SpdmEventData {
+                    event_instance_id: event_info.events[MAX_SPDM_EVENT_COUNT - 1]
+                        .event_instance_id,
+                    group_id: SPDM_DMTF_EVENT_GROUP_ID,
+                    event_type_id: SpdmDmtfEventType::EventLost.get_u16(),
+                    event_detail_len: 8,
+                    ..Default::default()
+                };
+                lost.event_detail[..4].copy_from_slice(&last_acked.to_le_bytes());
+                lost.event_detail[4..8].copy_from_slice(&event_instance_id.to_le_bytes());
+                event_info.events[MAX_SPDM_EVENT_COUNT - 1] = lost;
+            }
+        }
+        Ok(())
+    }
+
+    // Deliver the events queued for the session. The events stay queued until
+    // the requester returns EVENT_ACK.
+    pub fn send_spdm_event(&mut self, session_id: u32) -> SpdmResult {
+        let session = self
+            .common
+            .get_immutable_session_via_id(session_id)
+            .ok_or(SPDM_STATUS_INVALID_PARAMETER)?;
+        if session.get_session_state() != SpdmSessionState::SpdmSessionEstablished
+            || session.event_info.event_count == 0
+        {
+            return Err(SPDM_STATUS_INVALID_STATE_LOCAL);
+        }
+
+        info!("send spdm send_event\n");
+
+        // The last slot is held back until EVENT_ACK, so that an event lost
+        // meanwhile can be recorded without touching the events in flight.
+        let event_count = session
+            .event_info
+            .event_count
+            .min(MAX_SPDM_EVENT_COUNT as u32 - 1);
+        let request = SpdmMessage {
+            header: SpdmMessageHeader {
+                version: self.common.negotiate_info.spdm_version_sel,
+                request_response_code: SpdmRequestResponseCode::SpdmRequestSendEvent,
+            },
+            payload: SpdmMessagePayload::SpdmSendEventRequest(SpdmSendEventRequestPayload {
+                event_count,
+                events: session.event_info.events,
+            }),
+        };
+        let mut send_buffer = [0u8; config::MAX_SPDM_MSG_SIZE];
+        let mut writer = Writer::init(&mut send_buffer);
+        request
+            .spdm_encode(&mut self.common, &mut writer)
+            .map_err(|_| SPDM_STATUS_BUFFER_FULL)?;
+        self.send_secured_message(session_id, writer.used_slice(), false)?;
+
+        let session = self.common.get_session_via_id(session_id).unwrap();
+        session.event_info.sent_event_count = event_count;
+        Ok(())
+    }
+
+    pub fn handle_spdm_event_ack(&mut self, session_id: u32, bytes: &[u8]) -> SpdmResult {
+        let mut reader = Reader::init(bytes);
+        let message_header =
+            SpdmMessageHeader::read(&mut reader).ok_or(SPDM_STATUS_INVALID_MSG_FIELD)?;
+        if message_header.version != self.common.negotiate_info.spdm_version_sel {
+            return Err(SPDM_STATUS_INVALID_MSG_FIELD);
+        }
+        SpdmEventAckResponsePayload::spdm_read(&mut self.common, &mut reader)
+            .ok_or(SPDM_STATUS_INVALID_MSG_FIELD)?;
+
+        let session = self
+            .common
+            .get_session_via_id(session_id)
+            .ok_or(SPDM_STATUS_INVALID_PARAMETER)?;
+        let event_info = &mut session.event_info;
+        if event_info.sent_event_count == 0 {
+            return Err(SPDM_STATUS_INVALID_STATE_LOCAL);
+        }
+        debug!("!!! event_ack : {:?} events\n", event_info.sent_event_count);
+
+        // Events queued after SEND_EVENT went out stay pending.
+        let acked = event_info.sent_event_count as usize;
+        event_info.events.copy_within(acked.., 0);
+        event_info.event_count -= event_info.sent_event_count;
+        event_info.sent_event_count = 0;
+        Ok(())
+    }
+}
@@ -19,6 +19,7 @@ extern crate alloc;
 use crate::common::opaque::SpdmOpaqueStruct;
 use crate::message::*;
 use crate::secret;
+use crate::secret::SpdmSecretResult;
 use alloc::boxed::Box;
 
 impl<'a> ResponderContext<'a> {
@@ -74,19 +75,32 @@ impl<'a> ResponderContext<'a> {
                     == SpdmMeasurementSummaryHashType::SpdmMeasurementSummaryHashTypeAll)
             {
                 self.common.runtime_info.need_measurement_summary_hash = true;
-                let measurement_summary_hash_res =
-                    secret::measurement::generate_measurement_summary_hash(
+                measurement_summary_hash =
+                    match secret::measurement::generate_measurement_summary_hash(
                         self.common.negotiate_info.spdm_version_sel,
                         self.common.negotiate_info.base_hash_sel,
                         self.common.negotiate_info.measurement_specification_sel,
                         self.common.negotiate_info.measurement_hash_sel,
                         key_exchange_req.measurement_summary_hash_type,
-                    );
-                if measurement_summary_hash_res.is_none() {
-                    self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
-                    return Err(SPDM_STATUS_INVALID_MSG_FIELD);
-                }
-                measurement_summary_hash = measurement_summary_hash_res.unwrap();
+                    ) {
+                        SpdmSecretResult::Ready(measurement_summary_hash) => {
+                            measurement_summary_hash
+                        }
+                        SpdmSecretResult::NotReady { rdt_exponent, rdtm } => {
+                            self.write_spdm_response_not_ready(
+                                None,
+                                bytes,
+                                rdt_exponent,
+                                rdtm,
+                                writer,
+                            );
+                            return Ok(());
+                        }
+                        SpdmSecretResult::Failed => {
+                            self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
+                            return Err(SPDM_STATUS_INVALID_MSG_FIELD);
+                        }
+                    };
                 if measurement_summary_hash.data_size == 0 {
                     self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
                     return Err(SPDM_STATUS_INVALID_MSG_FIELD);
@@ -166,12 +180,16 @@ impl<'a> ResponderContext<'a> {
         }
 
         let key_exchange_req = key_exchange_req.unwrap();
-        let slot_id = key_exchange_req.slot_id as usize;
-        if slot_id >= SPDM_MAX_SLOT_NUMBER {
+        let slot_id = key_exchange_req.slot_id;
+        if slot_id >= SPDM_MAX_SLOT_NUMBER as u8 && !self.is_my_public_key_slot_id(slot_id) {
             self.write_spdm_error(SpdmErrorCode::SpdmErrorInvalidRequest, 0, writer);
             return Err(SPDM_STATUS_INVALID_MSG_FIELD);
         }
-        if self.common.provision_info.my_cert_chain[slot_id].is_none() {
+        if self
+            .common
+            .get_my_cert_chain_or_public_key(slot_id)
+            .is_none()
+        {
             self.write_spdm_error(SpdmErrorCode::SpdmErrorInvalidRequest, 0, writer);
             return Err(SPDM_STATUS_INVALID_MSG_FIELD);
         }
@@ -180,23 +198,32 @@ impl<'a> ResponderContext<'a> {
             .runtime_info
             .set_local_used_cert_chain_slot_id(key_exchange_req.slot_id);
 
-        let (exchange, key_exchange_context) =
-            crypto::dhe::generate_key_pair(self.common.negotiate_info.dhe_sel).unwrap();
-
-        debug!("!!! exchange data : {:02x?}\n", exchange);
-
         debug!(
             "!!! exchange data (peer) : {:02x?}\n",
             &key_exchange_req.exchange
         );
 
-        let final_key = key_exchange_context.compute_final_key(&key_exchange_req.exchange);
+        // With a KEM, the exchange data of the response is the cipher text
+        // encapsulated to the key of the requester.
+        let exchange_and_final_key = if self.common.negotiate_info.kem_sel.is_empty() {
+            let (exchange, key_exchange_context) =
+                crypto::dhe::generate_key_pair(self.common.negotiate_info.dhe_sel).unwrap();
+            key_exchange_context
+                .compute_final_key(&key_exchange_req.exchange)
+                .map(|final_key| (exchange, final_key))
+        } else {
+            crypto::dhe::kem_encapsulate(
+                self.common.negotiate_info.kem_sel,
+                &key_exchange_req.exchange,
+            )
+        };
 
-        if final_key.is_none() {
+        if exchange_and_final_key.is_none() {
             self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
             return Err(SPDM_STATUS_CRYPTO_ERROR);
         }
-        let final_key = final_key.unwrap();
+        let (exchange, final_key) = exchange_and_final_key.unwrap();
+        debug!("!!! exchange data : {:02x?}\n", exchange);
         debug!("!!! final_key : {:02x?}\n", final_key.as_ref());
 
         let rsp_session_id = self.common.get_next_half_session_id(false);
@@ -222,6 +249,32 @@ impl<'a> ResponderContext<'a> {
             return Err(SPDM_STATUS_INVALID_MSG_FIELD);
         }
 
+        // A requester identified by a provisioned public key has no certificate
+        // chain to retrieve, so it is asked to sign FINISH directly.
+        #[cfg(feature = "mut-auth")]
+        let (mut_auth_req, req_slot_id) = if self
+            .common
+            .negotiate_info
+            .req_capabilities_sel
+            .contains(SpdmRequestCapabilityFlags::PUB_KEY_ID_CAP)
+            && self.common.provision_info.peer_public_key.is_some()
+        {
+            self.common
+                .runtime_info
+                .set_peer_used_cert_chain_slot_id(SPDM_PUBLIC_KEY_SLOT_ID);
+            (
+                SpdmKeyExchangeMutAuthAttributes::MUT_AUTH_REQ,
+                SPDM_PUBLIC_KEY_SLOT_ID_PARAM,
+            )
+        } else {
+            (
+                SpdmKeyExchangeMutAuthAttributes::MUT_AUTH_REQ_WITH_GET_DIGESTS,
+                0x0,
+            )
+        };
+        #[cfg(not(feature = "mut-auth"))]
+        let (mut_auth_req, req_slot_id) = (SpdmKeyExchangeMutAuthAttributes::empty(), 0x0);
+
         let session = self.common.get_next_avaiable_session();
         if session.is_none() {
             error!("!!! too many sessions : fail !!!\n");
@@ -229,16 +282,11 @@ impl<'a> ResponderContext<'a> {
             return Err(SPDM_STATUS_INVALID_STATE_LOCAL);
         }
 
-        #[cfg(feature = "mut-auth")]
-        let mut_auth_req = SpdmKeyExchangeMutAuthAttributes::MUT_AUTH_REQ_WITH_GET_DIGESTS;
-        #[cfg(not(feature = "mut-auth"))]
-        let mut_auth_req = SpdmKeyExchangeMutAuthAttributes::empty();
-
         let session = session.unwrap();
         let session_id = ((rsp_session_id as u32) << 16) + key_exchange_req.req_session_id as u32;
         session.setup(session_id).unwrap();
         session.set_use_psk(false);
-        session.set_slot_id(slot_id as u8);
+        session.set_slot_id(slot_id);
         session.set_crypto_param(hash_algo, dhe_algo, aead_algo, key_schedule_algo);
         session.set_mut_auth_requested(mut_auth_req);
         session.set_transport_param(sequence_number_count, max_random_count);
@@ -282,13 +330,13 @@ impl<'a> ResponderContext<'a> {
                 heartbeat_period: self.common.config_info.heartbeat_period,
                 rsp_session_id,
                 mut_auth_req,
-                req_slot_id: 0x0,
+                req_slot_id,
                 random: SpdmRandomStruct { data: random },
                 exchange,
                 measurement_summary_hash,
                 opaque: return_opaque.clone(),
                 signature: SpdmSignatureStruct {
-                    data_size: self.common.negotiate_info.base_asym_sel.get_size(),
+                    data_size: self.common.get_asym_key_size(),
                     data: [0xbb; SPDM_MAX_ASYM_KEY_SIZE],
                 },
                 verify_data: SpdmDigestStruct {
@@ -298,15 +346,19 @@ impl<'a> ResponderContext<'a> {
             }),
         };
 
-        let res = response.spdm_encode(&mut self.common, writer);
+        // The response is built aside, so that ERROR(ResponseNotReady) can
+        // be written instead if the signature is deferred.
+        let mut response_buffer = [0u8; config::MAX_SPDM_MSG_SIZE];
+        let mut response_writer = Writer::init(&mut response_buffer);
+        let res = response.spdm_encode(&mut self.common, &mut response_writer);
         if res.is_err() {
             self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
             return Err(SPDM_STATUS_INVALID_STATE_LOCAL);
         }
-        let used = writer.used();
+        let used = response_writer.used();
 
         // generate signature
-        let base_asym_size = self.common.negotiate_info.base_asym_sel.get_size() as usize;
+        let base_asym_size = self.common.get_asym_key_size() as usize;
         let base_hash_size = self.common.negotiate_info.base_hash_sel.get_size() as usize;
         let temp_used = if in_clear_text {
             used - base_asym_size
@@ -324,7 +376,7 @@ impl<'a> ResponderContext<'a> {
         }
         if self
             .common
-            .append_message_k(session_id, &writer.used_slice()[..temp_used])
+            .append_message_k(session_id, &response_writer.used_slice()[..temp_used])
             .is_err()
         {
             self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
@@ -336,12 +388,20 @@ impl<'a> ResponderContext<'a> {
             .get_immutable_session_via_id(session_id)
             .unwrap();
 
-        let signature = self.generate_key_exchange_rsp_signature(slot_id as u8, session);
-        if signature.is_err() {
-            self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
-            return Err(SPDM_STATUS_CRYPTO_ERROR);
-        }
-        let signature = signature.unwrap();
+        let signature = match self.generate_key_exchange_rsp_signature(slot_id, session) {
+            Ok(SpdmSecretResult::Ready(signature)) => signature,
+            Ok(SpdmSecretResult::NotReady { rdt_exponent, rdtm }) => {
+                // The session is established again on RESPOND_IF_READY.
+                let session = self.common.get_session_via_id(session_id).unwrap();
+                let _ = session.teardown(session_id);
+                self.write_spdm_response_not_ready(None, bytes, rdt_exponent, rdtm, writer);
+                return Ok(());
+            }
+            _ => {
+                self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
+                return Err(SPDM_STATUS_CRYPTO_ERROR);
+            }
+        };
 
         if self
             .common
@@ -360,7 +420,7 @@ impl<'a> ResponderContext<'a> {
         // generate the handshake secret (including finished_key) before generate HMAC
         let th1 = self
             .common
-            .calc_rsp_transcript_hash(false, slot_id as u8, false, session);
+            .calc_rsp_transcript_hash(false, slot_id, false, session);
         if th1.is_err() {
             self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
             return Err(SPDM_STATUS_CRYPTO_ERROR);
@@ -378,9 +438,9 @@ impl<'a> ResponderContext<'a> {
                 .unwrap();
 
             // generate HMAC with finished_key
-            let transcript_hash =
-                self.common
-                    .calc_rsp_transcript_hash(false, slot_id as u8, false, session);
+            let transcript_hash = self
+                .common
+                .calc_rsp_transcript_hash(false, slot_id, false, session);
             if transcript_hash.is_err() {
                 self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
                 return Err(SPDM_STATUS_CRYPTO_ERROR);
@@ -410,11 +470,13 @@ impl<'a> ResponderContext<'a> {
             }
 
             // patch the message before send
-            writer.mut_used_slice()
+            response_writer.mut_used_slice()
                 [(used - base_hash_size - base_asym_size)..(used - base_hash_size)]
                 .copy_from_slice(signature.as_ref());
-            writer.mut_used_slice()[(used - base_hash_size)..used].copy_from_slice(hmac.as_ref());
+            response_writer.mut_used_slice()[(used - base_hash_size)..used]
+                .copy_from_slice(hmac.as_ref());
         }
+        let _ = writer.extend_from_slice(response_writer.used_slice());
 
         let heartbeat_period = self.common.config_info.heartbeat_period;
         let session = self.common.get_session_via_id(session_id).unwrap();
@@ -441,7 +503,7 @@ impl<'a> ResponderContext<'a> {
         &self,
         slot_id: u8,
         session: &SpdmSession,
-    ) -> SpdmResult<SpdmSignatureStruct> {
+    ) -> SpdmResult<SpdmSecretResult<SpdmSignatureStruct>> {
         let transcript_hash = self
             .common
             .calc_rsp_transcript_hash(false, slot_id, false, session)?;
@@ -454,7 +516,9 @@ impl<'a> ResponderContext<'a> {
         {
             message_sign.reset_message();
             message_sign
-                .append_message(&SPDM_VERSION_1_2_SIGNING_PREFIX_CONTEXT)
+                .append_message(spdm_signing_prefix_context(
+                    self.common.negotiate_info.spdm_version_sel,
+                ))
                 .ok_or(SPDM_STATUS_BUFFER_FULL)?;
             message_sign
                 .append_message(&SPDM_VERSION_1_2_SIGNING_CONTEXT_ZEROPAD_2)
@@ -470,12 +534,11 @@ impl<'a> ResponderContext<'a> {
             return Err(SPDM_STATUS_INVALID_STATE_LOCAL);
         }
 
-        crate::secret::asym_sign::sign(
-            self.common.negotiate_info.base_hash_sel,
-            self.common.negotiate_info.base_asym_sel,
+        Ok(self.common.sign_with_my_key_pair(
+            slot_id,
+            &SPDM_KEY_EXCHANGE_RESPONSE_SIGN_CONTEXT,
             message_sign.as_ref(),
-        )
-        .ok_or(SPDM_STATUS_CRYPTO_ERROR)
+        ))
     }
 
     #[cfg(not(feature = "hashed-transcript-data"))]
@@ -483,7 +546,7 @@ impl<'a> ResponderContext<'a> {
         &self,
         slot_id: u8,
         session: &SpdmSession,
-    ) -> SpdmResult<SpdmSignatureStruct> {
+    ) -> SpdmResult<SpdmSecretResult<SpdmSignatureStruct>> {
         let message_hash = self
             .common
             .calc_rsp_transcript_hash(false, slot_id, false, session)?;
@@ -503,7 +566,9 @@ impl<'a> ResponderContext<'a> {
         {
             message.reset_message();
             message
-                .append_message(&SPDM_VERSION_1_2_SIGNING_PREFIX_CONTEXT)
+                .append_message(spdm_signing_prefix_context(
+                    self.common.negotiate_info.spdm_version_sel,
+                ))
                 .ok_or(SPDM_STATUS_BUFFER_FULL)?;
             message
                 .append_message(&SPDM_VERSION_1_2_SIGNING_CONTEXT_ZEROPAD_2)
@@ -516,11 +581,10 @@ impl<'a> ResponderContext<'a> {
                 .ok_or(SPDM_STATUS_BUFFER_FULL)?;
         }
 
-        crate::secret::asym_sign::sign(
-            self.common.negotiate_info.base_hash_sel,
-            self.common.negotiate_info.base_asym_sel,
+        Ok(self.common.sign_with_my_key_pair(
+            slot_id,
+            &SPDM_KEY_EXCHANGE_RESPONSE_SIGN_CONTEXT,
             message.as_ref(),
-        )
-        .ok_or(SPDM_STATUS_CRYPTO_ERROR)
+        ))
     }
 }
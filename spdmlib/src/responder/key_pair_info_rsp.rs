@@ -0,0 +1,312 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use crate::common::SpdmCodec;
+use crate::common::SpdmConnectionState;
+use crate::error::SpdmResult;
+use crate::message::*;
+use crate::protocol::*;
+use crate::responder::*;
+use crate::secret;
+
+impl<'a> ResponderContext<'a> {
+    pub fn handle_spdm_get_key_pair_info(
+        &mut self,
+        session_id: Option<u32>,
+        bytes: &[u8],
+    ) -> SpdmResult {
+        let mut send_buffer = [0u8; config::MAX_SPDM_MSG_SIZE];
+        let mut writer = Writer::init(&mut send_buffer);
+        self.write_spdm_key_pair_info_response(session_id, bytes, &mut writer);
+
+        if let Some(session_id) = session_id {
+            self.send_secured_message(session_id, writer.used_slice(), false)
+        } else {
+            self.send_message(writer.used_slice())
+        }
+    }
+
+    fn write_spdm_key_pair_info_response(
+        &mut self,
+        session_id: Option<u32>,
+        bytes: &[u8],
+        writer: &mut Writer,
+    ) {
+        if self.common.negotiate_info.spdm_version_sel.get_u8()
+            < SpdmVersion::SpdmVersion13.get_u8()
+            || !self
+                .common
+                .negotiate_info
+                .rsp_capabilities_sel
+                .contains(SpdmResponseCapabilityFlags::GET_KEY_PAIR_INFO_CAP)
+        {
+            self.write_error_response(SpdmErrorCode::SpdmErrorUnsupportedRequest, bytes, writer);
+            return;
+        }
+        if self.common.runtime_info.get_connection_state().get_u8()
+            < SpdmConnectionState::SpdmConnectionNegotiated.get_u8()
+        {
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorUnexpectedRequest, 0, writer);
+            return;
+        }
+        let mut reader = Reader::init(bytes);
+        let message_header = SpdmMessageHeader::read(&mut reader);
+        if let Some(message_header) = message_header {
+            if message_header.version != self.common.negotiate_info.spdm_version_sel {
+                self.write_spdm_error(SpdmErrorCode::SpdmErrorVersionMismatch, 0, writer);
+                return;
+            }
+        } else {
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorInvalidRequest, 0, writer);
+            return;
+        }
+
+        self.common.reset_buffer_via_request_code(
+            SpdmRequestResponseCode::SpdmRequestGetKeyPairInfo,
+            session_id,
+        );
+
+        let get_key_pair_info =
+            SpdmGetKeyPairInfoRequestPayload::spdm_read(&mut self.common, &mut reader);
+        let get_key_pair_info = if let Some(get_key_pair_info) = get_key_pair_info {
+            debug!("!!! get_key_pair_info : {:02x?}\n", get_key_pair_info);
+            get_key_pair_info
+        } else {
+            error!("!!! get_key_pair_info : fail !!!\n");
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorInvalidRequest, 0, writer);
+            return;
+        };
+
+        let key_pair_id = get_key_pair_info.key_pair_id;
+        let key_pair_info = if key_pair_id == SPDM_DEFAULT_KEY_PAIR_ID {
+            None
+        } else {
+            secret::key_pair::get_key_pair_info(key_pair_id)
+        };
+        let key_pair_info = match key_pair_info {
+            Some(key_pair_info) if key_pair_id <= key_pair_info.total_key_pairs => key_pair_info,
+            _ => {
+                self.write_spdm_error(SpdmErrorCode::SpdmErrorInvalidRequest, 0, writer);
+                return;
+            }
+        };
+
+        info!("send spdm key_pair_info\n");
+        let response = SpdmMessage {
+            header: SpdmMessageHeader {
+                version: self.common.negotiate_info.spdm_version_sel,
+                request_response_code: SpdmRequestResponseCode::SpdmResponseKeyPairInfo,
+            },
+            payload: SpdmMessagePayload::SpdmKeyPairInfoResponse(SpdmKeyPairInfoResponsePayload {
+                key_pair_id,
+                key_pair_info,
+                assoc_cert_slot_mask: self.get_assoc_cert_slot_mask(key_pair_id),
+            }),
+        };
+        let res = response.spdm_encode(&mut self.common, writer);
+        if res.is_err() {
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
+        }
+    }
+
+    pub fn handle_spdm_set_key_pair_info(
+        &mut self,
+        session_id: Option<u32>,
+        bytes: &[u8],
+    ) -> SpdmResult {
+        let mut send_buffer = [0u8; config::MAX_SPDM_MSG_SIZE];
+        let mut writer = Writer::init(&mut send_buffer);
+        self.write_spdm_set_key_pair_info_ack_response(session_id, bytes, &mut writer);
+
+        if let Some(session_id) = session_id {
+            self.send_secured_message(session_id, writer.used_slice(), false)
+        } else {
+            self.send_message(writer.used_slice())
+        }
+    }
+
+    fn write_spdm_set_key_pair_info_ack_response(
+        &mut self,
+        session_id: Option<u32>,
+        bytes: &[u8],
+        writer: &mut Writer,
+    ) {
+        if self.common.negotiate_info.spdm_version_sel.get_u8()
+            < SpdmVersion::SpdmVersion13.get_u8()
+            || !self
+                .common
+                .negotiate_info
+                .rsp_capabilities_sel
+                .contains(SpdmResponseCapabilityFlags::SET_KEY_PAIR_INFO_CAP)
+        {
+            self.write_error_response(SpdmErrorCode::SpdmErrorUnsupportedRequest, bytes, writer);
+            return;
+        }
+        if self.common.runtime_info.get_connection_state().get_u8()
+            < SpdmConnectionState::SpdmConnectionNegotiated.get_u8()
+        {
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorUnexpectedRequest, 0, writer);
+            return;
+        }
+        let mut reader = Reader::init(bytes);
+        let message_header = SpdmMessageHeader::read(&mut reader);
+        if let Some(message_header) = message_header {
+            if message_header.version != self.common.negotiate_info.spdm_version_sel {
+                self.write_spdm_error(SpdmErrorCode::SpdmErrorVersionMismatch, 0, writer);
+                return;
+            }
+        } else {
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorInvalidRequest, 0, writer);
+            return;
+        }
+
+        self.common.reset_buffer_via_request_code(
+            SpdmRequestResponseCode::SpdmRequestSetKeyPairInfo,
+            session_id,
+        );
+
+        let set_key_pair_info =
+            SpdmSetKeyPairInfoRequestPayload::spdm_read(&mut self.common, &mut reader);
+        let set_key_pair_info = if let Some(set_key_pair_info) = set_key_pair_info {
+            debug!("!!! set_key_pair_info : {:02x?}\n", set_key_pair_info);
+            set_key_pair_info
+        } else {
+            error!("!!! set_key_pair_info : fail !!!\n");
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorInvalidRequest, 0, writer);
+            return;
+        };
+
+        let key_pair_id = set_key_pair_info.key_pair_id;
+        let key_pair_info = if key_pair_id == SPDM_DEFAULT_KEY_PAIR_ID {
+            None
+        } else {
+            secret::key_pair::get_key_pair_info(key_pair_id)
+        };
+        let key_pair_info = match key_pair_info {
+            Some(key_pair_info) if key_pair_id <= key_pair_info.total_key_pairs => key_pair_info,
+            _ => {
+                self.write_spdm_error(SpdmErrorCode::SpdmErrorInvalidRequest, 0, writer);
+                return;
+            }
+        };
+        if !self.is_set_key_pair_info_allowed(&set_key_pair_info, &key_pair_info) {
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorInvalidRequest, 0, writer);
+            return;
+        }
+
+        if !secret::key_pair::set_key_pair_info(
+            set_key_pair_info.operation,
+            key_pair_id,
+            set_key_pair_info.desired_key_usage,
+            set_key_pair_info.desired_asym_algo,
+            set_key_pair_info.desired_assoc_cert_slot_mask,
+        ) {
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorOperationFailed, 0, writer);
+            return;
+        }
+
+        // An erased key pair is no longer bound to any slot.
+        let assoc_cert_slot_mask = if set_key_pair_info.operation
+            == SpdmSetKeyPairInfoOperation::SpdmSetKeyPairInfoOperationErase
+        {
+            0
+        } else {
+            set_key_pair_info.desired_assoc_cert_slot_mask
+        };
+        for (slot_id, my_key_pair_id) in self
+            .common
+            .provision_info
+            .my_key_pair_id
+            .iter_mut()
+            .enumerate()
+        {
+            if assoc_cert_slot_mask & (1 << slot_id) != 0 {
+                *my_key_pair_id = key_pair_id;
+            } else if *my_key_pair_id == key_pair_id {
+                *my_key_pair_id = SPDM_DEFAULT_KEY_PAIR_ID;
+            }
+        }
+
+        info!("send spdm set_key_pair_info_ack\n");
+        let response = SpdmMessage {
+            header: SpdmMessageHeader {
+                version: self.common.negotiate_info.spdm_version_sel,
+                request_response_code: SpdmRequestResponseCode::SpdmResponseSetKeyPairInfoAck,
+            },
+            payload: SpdmMessagePayload::SpdmSetKeyPairInfoAckResponse(
+                SpdmSetKeyPairInfoAckResponsePayload {},
+            ),
+        };
+        let res = response.spdm_encode(&mut self.common, writer);
+        if res.is_err() {
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
+        }
+    }
+
+    fn get_assoc_cert_slot_mask(&self, key_pair_id: u8) -> u8 {
+        let mut assoc_cert_slot_mask = 0u8;
+        for (slot_id, my_key_pair_id) in
+            self.common.provision_info.my_key_pair_id.iter().enumerate()
+        {
+            if *my_key_pair_id == key_pair_id {
+                assoc_cert_slot_mask |= 1 << slot_id;
+            }
+        }
+        assoc_cert_slot_mask
+    }
+
+    // Every change asked by SET_KEY_PAIR_INFO must be allowed by the
+    // capabilities of the key pair.
+    fn is_set_key_pair_info_allowed(
+        &self,
+        set_key_pair_info: &SpdmSetKeyPairInfoRequestPayload,
+        key_pair_info: &SpdmKeyPairInfo,
+    ) -> bool {
+        let capabilities = key_pair_info.capabilities;
+        match set_key_pair_info.operation {
+            SpdmSetKeyPairInfoOperation::SpdmSetKeyPairInfoOperationErase => {
+                return capabilities.contains(SpdmKeyPairCapabilities::ERASABLE_CAP);
+            }
+            SpdmSetKeyPairInfoOperation::SpdmSetKeyPairInfoOperationGenerate => {
+                if !capabilities.contains(SpdmKeyPairCapabilities::GEN_KEY_CAP) {
+                    return false;
+                }
+            }
+            SpdmSetKeyPairInfoOperation::SpdmSetKeyPairInfoOperationChange => {}
+            SpdmSetKeyPairInfoOperation::Unknown(_) => return false,
+        }
+
+        let desired_key_usage = set_key_pair_info.desired_key_usage;
+        if desired_key_usage != key_pair_info.current_key_usage
+            && (!capabilities.contains(SpdmKeyPairCapabilities::KEY_USAGE_CAP)
+                || !key_pair_info
+                    .key_usage_capabilities
+                    .contains(desired_key_usage))
+        {
+            return false;
+        }
+        let desired_asym_algo = set_key_pair_info.desired_asym_algo;
+        if desired_asym_algo != key_pair_info.current_asym_algo
+            && (!capabilities.contains(SpdmKeyPairCapabilities::ASYM_ALGO_CAP)
+                || !key_pair_info
+                    .asym_algo_capabilities
+                    .contains(desired_asym_algo))
+        {
+            return false;
+        }
+        let desired_assoc_cert_slot_mask = set_key_pair_info.desired_assoc_cert_slot_mask;
+        if desired_assoc_cert_slot_mask
+            != self.get_assoc_cert_slot_mask(set_key_pair_info.key_pair_id)
+            && !capabilities.contains(SpdmKeyPairCapabilities::CERT_ASSOC_CAP)
+        {
+            return false;
+        }
+        if desired_assoc_cert_slot_mask & (desired_assoc_cert_slot_mask.wrapping_sub(1)) != 0
+            && !capabilities.contains(SpdmKeyPairCapabilities::SHAREABLE_CAP)
+        {
+            return false;
+        }
+        true
+    }
+}
@@ -22,6 +22,7 @@ use crate::message::*;
 use crate::protocol::*;
 use crate::responder::*;
 use crate::secret;
+use crate::secret::SpdmSecretResult;
 
 impl<'a> ResponderContext<'a> {
     pub fn handle_spdm_measurement(&mut self, session_id: Option<u32>, bytes: &[u8]) -> SpdmResult {
@@ -73,9 +74,14 @@ impl<'a> ResponderContext<'a> {
             return;
         }
         let get_measurements = get_measurements.unwrap();
-        let slot_id = get_measurements.slot_id as usize;
+        // SlotIDParam 0xF selects the provisioned public key.
+        let slot_id = if get_measurements.slot_id == SPDM_PUBLIC_KEY_SLOT_ID_PARAM {
+            SPDM_PUBLIC_KEY_SLOT_ID
+        } else {
+            get_measurements.slot_id
+        };
 
-        let signature_size = self.common.negotiate_info.base_asym_sel.get_size();
+        let signature_size = self.common.get_asym_key_size();
 
         if get_measurements
             .measurement_attributes
@@ -83,11 +89,13 @@ impl<'a> ResponderContext<'a> {
         {
             self.common.runtime_info.need_measurement_signature = true;
 
-            if slot_id >= SPDM_MAX_SLOT_NUMBER {
-                self.write_spdm_error(SpdmErrorCode::SpdmErrorInvalidRequest, 0, writer);
-                return;
-            }
-            if self.common.provision_info.my_cert_chain[slot_id].is_none() {
+            if (slot_id >= SPDM_MAX_SLOT_NUMBER as u8 && !self.is_my_public_key_slot_id(slot_id))
+                || self
+                    .common
+                    .get_my_cert_chain_or_public_key(slot_id)
+                    .is_none()
+            {
+                self.common.reset_message_m(session_id);
                 self.write_spdm_error(SpdmErrorCode::SpdmErrorInvalidRequest, 0, writer);
                 return;
             }
@@ -95,6 +103,7 @@ impl<'a> ResponderContext<'a> {
             self.common.runtime_info.need_measurement_signature = false;
 
             if slot_id != 0 {
+                self.common.reset_message_m(session_id);
                 self.write_spdm_error(SpdmErrorCode::SpdmErrorInvalidRequest, 0, writer);
                 return;
             }
@@ -106,63 +115,122 @@ impl<'a> ResponderContext<'a> {
             self.common.negotiate_info.measurement_specification_sel;
         let runtime_content_change_support = self.common.config_info.runtime_content_change_support;
         let content_changed = self.common.runtime_info.content_changed;
-        let base_asym_sel = self.common.negotiate_info.base_asym_sel;
+        let base_asym_size = self.common.get_asym_key_size() as usize;
 
-        if self
-            .common
-            .append_message_m(session_id, &bytes[..reader.used()])
-            .is_err()
-        {
-            self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
-            return;
-        }
-
-        let real_measurement_block_count = secret::measurement::measurement_collection(
+        let real_measurement_block_count = match secret::measurement::measurement_collection(
             spdm_version_sel,
             measurement_specification_sel,
             measurement_hash_sel,
             SpdmMeasurementOperation::SpdmMeasurementQueryTotalNumber.get_u8() as usize,
-        )
-        .unwrap()
-        .number_of_blocks;
-
-        let number_of_measurement: u8 = if get_measurements.measurement_operation
-            == SpdmMeasurementOperation::SpdmMeasurementRequestAll
-            || get_measurements.measurement_operation
-                == SpdmMeasurementOperation::SpdmMeasurementQueryTotalNumber
-        {
-            real_measurement_block_count
-        } else {
-            1
+        ) {
+            SpdmSecretResult::Ready(measurement_record) => measurement_record.number_of_blocks,
+            SpdmSecretResult::NotReady { rdt_exponent, rdtm } => {
+                self.write_spdm_response_not_ready(session_id, bytes, rdt_exponent, rdtm, writer);
+                return;
+            }
+            SpdmSecretResult::Failed => {
+                self.common.reset_message_m(session_id);
+                self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
+                return;
+            }
         };
-        let measurement_record = if get_measurements.measurement_operation
-            == SpdmMeasurementOperation::SpdmMeasurementRequestAll
-        {
-            secret::measurement::measurement_collection(
-                spdm_version_sel,
-                measurement_specification_sel,
-                measurement_hash_sel,
-                SpdmMeasurementOperation::SpdmMeasurementRequestAll.get_u8() as usize,
-            )
-            .unwrap()
-        } else if let SpdmMeasurementOperation::Unknown(index) =
-            get_measurements.measurement_operation
+
+        // Param1 of the response is the total number of measurement indices only
+        // when it is queried, and reserved otherwise.
+        let (number_of_measurement, measurement_record) = match get_measurements
+            .measurement_operation
         {
-            if index > real_measurement_block_count {
-                self.write_spdm_error(SpdmErrorCode::SpdmErrorInvalidRequest, 0, writer);
-                return;
+            SpdmMeasurementOperation::SpdmMeasurementQueryTotalNumber => (
+                real_measurement_block_count,
+                SpdmMeasurementRecordStructure::default(),
+            ),
+            SpdmMeasurementOperation::SpdmMeasurementRequestAll => {
+                match secret::measurement::measurement_collection(
+                    spdm_version_sel,
+                    measurement_specification_sel,
+                    measurement_hash_sel,
+                    SpdmMeasurementOperation::SpdmMeasurementRequestAll.get_u8() as usize,
+                ) {
+                    SpdmSecretResult::Ready(measurement_record) => (0, measurement_record),
+                    SpdmSecretResult::NotReady { rdt_exponent, rdtm } => {
+                        self.write_spdm_response_not_ready(
+                            session_id,
+                            bytes,
+                            rdt_exponent,
+                            rdtm,
+                            writer,
+                        );
+                        return;
+                    }
+                    SpdmSecretResult::Failed => {
+                        self.common.reset_message_m(session_id);
+                        self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
+                        return;
+                    }
+                }
             }
-            secret::measurement::measurement_collection(
-                spdm_version_sel,
-                measurement_specification_sel,
-                measurement_hash_sel,
-                index as usize,
-            )
-            .unwrap()
-        } else {
-            SpdmMeasurementRecordStructure::default()
+            SpdmMeasurementOperation::Unknown(index) => {
+                // The indices of the measurement blocks are not necessarily contiguous,
+                // so let the device tell whether the requested one exists.
+                match secret::measurement::measurement_collection(
+                    spdm_version_sel,
+                    measurement_specification_sel,
+                    measurement_hash_sel,
+                    index as usize,
+                ) {
+                    SpdmSecretResult::Ready(measurement_record)
+                        if measurement_record.number_of_blocks == 1 =>
+                    {
+                        (0, measurement_record)
+                    }
+                    SpdmSecretResult::NotReady { rdt_exponent, rdtm } => {
+                        self.write_spdm_response_not_ready(
+                            session_id,
+                            bytes,
+                            rdt_exponent,
+                            rdtm,
+                            writer,
+                        );
+                        return;
+                    }
+                    _ => {
+                        error!("!!! get_measurements : index {} not found !!!\n", index);
+                        self.common.reset_message_m(session_id);
+                        self.write_spdm_error(SpdmErrorCode::SpdmErrorInvalidRequest, 0, writer);
+                        return;
+                    }
+                }
+            }
+        };
+
+        // The transcript is restored if the signature is deferred, as the
+        // request is processed again on RESPOND_IF_READY.
+        #[cfg(not(feature = "hashed-transcript-data"))]
+        let message_m = match session_id {
+            None => Some(self.common.runtime_info.message_m.clone()),
+            Some(session_id) => self
+                .common
+                .get_immutable_session_via_id(session_id)
+                .map(|session| session.runtime_info.message_m.clone()),
+        };
+        #[cfg(feature = "hashed-transcript-data")]
+        let digest_context_l1l2 = match session_id {
+            None => Some(self.common.runtime_info.digest_context_l1l2.clone()),
+            Some(session_id) => self
+                .common
+                .get_immutable_session_via_id(session_id)
+                .map(|session| session.runtime_info.digest_context_l1l2.clone()),
         };
 
+        if self
+            .common
+            .append_message_m(session_id, &bytes[..reader.used()])
+            .is_err()
+        {
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
+            return;
+        }
+
         let content_changed = if runtime_content_change_support
             && (spdm_version_sel.get_u8() >= SpdmVersion::SpdmVersion12.get_u8())
         {
@@ -204,55 +272,97 @@ impl<'a> ResponderContext<'a> {
             ),
         };
 
-        let res = response.spdm_encode(&mut self.common, writer);
+        // The response is built aside, so that ERROR(ResponseNotReady) can
+        // be written instead if the signature is deferred.
+        let mut response_buffer = [0u8; config::MAX_SPDM_MSG_SIZE];
+        let mut response_writer = Writer::init(&mut response_buffer);
+        let res = response.spdm_encode(&mut self.common, &mut response_writer);
         if res.is_err() {
             self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
             return;
         }
-        let used = writer.used();
+        let used = response_writer.used();
 
         // generat signature
         if get_measurements
             .measurement_attributes
             .contains(SpdmMeasurementAttributes::SIGNATURE_REQUESTED)
         {
-            let base_asym_size = base_asym_sel.get_size() as usize;
             let temp_used = used - base_asym_size;
 
             if self
                 .common
-                .append_message_m(session_id, &writer.used_slice()[..temp_used])
+                .append_message_m(session_id, &response_writer.used_slice()[..temp_used])
                 .is_err()
             {
                 self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
                 return;
             }
 
-            let signature = self.generate_measurement_signature(session_id);
-            if signature.is_err() {
-                self.send_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0);
-                return;
-            }
-            let signature = signature.unwrap();
+            let signature = match self.generate_measurement_signature(session_id, slot_id) {
+                Ok(SpdmSecretResult::Ready(signature)) => signature,
+                Ok(SpdmSecretResult::NotReady { rdt_exponent, rdtm }) => {
+                    #[cfg(not(feature = "hashed-transcript-data"))]
+                    match session_id {
+                        None => self.common.runtime_info.message_m = message_m.unwrap(),
+                        Some(session_id) => {
+                            self.common
+                                .get_session_via_id(session_id)
+                                .unwrap()
+                                .runtime_info
+                                .message_m = message_m.unwrap()
+                        }
+                    }
+                    #[cfg(feature = "hashed-transcript-data")]
+                    match session_id {
+                        None => {
+                            self.common.runtime_info.digest_context_l1l2 =
+                                digest_context_l1l2.unwrap()
+                        }
+                        Some(session_id) => {
+                            self.common
+                                .get_session_via_id(session_id)
+                                .unwrap()
+                                .runtime_info
+                                .digest_context_l1l2 = digest_context_l1l2.unwrap()
+                        }
+                    }
+                    self.write_spdm_response_not_ready(
+                        session_id,
+                        bytes,
+                        rdt_exponent,
+                        rdtm,
+                        writer,
+                    );
+                    return;
+                }
+                _ => {
+                    self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
+                    return;
+                }
+            };
             // patch the message before send
-            writer.mut_used_slice()[(used - base_asym_size)..used]
+            response_writer.mut_used_slice()[(used - base_asym_size)..used]
                 .copy_from_slice(signature.as_ref());
 
             self.common.reset_message_m(session_id);
         } else if self
             .common
-            .append_message_m(session_id, writer.used_slice())
+            .append_message_m(session_id, response_writer.used_slice())
             .is_err()
         {
             self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
+            return;
         }
+        let _ = writer.extend_from_slice(response_writer.used_slice());
     }
 
     #[cfg(feature = "hashed-transcript-data")]
     pub fn generate_measurement_signature(
         &self,
         session_id: Option<u32>,
-    ) -> SpdmResult<SpdmSignatureStruct> {
+        slot_id: u8,
+    ) -> SpdmResult<SpdmSecretResult<SpdmSignatureStruct>> {
         let message_l1l2_hash = match session_id {
             Some(session_id) => crypto::hash::hash_ctx_finalize(
                 self.common
@@ -284,7 +394,9 @@ impl<'a> ResponderContext<'a> {
         {
             message_sign.reset_message();
             message_sign
-                .append_message(&SPDM_VERSION_1_2_SIGNING_PREFIX_CONTEXT)
+                .append_message(spdm_signing_prefix_context(
+                    self.common.negotiate_info.spdm_version_sel,
+                ))
                 .ok_or(SPDM_STATUS_BUFFER_FULL)?;
             message_sign
                 .append_message(&SPDM_VERSION_1_2_SIGNING_CONTEXT_ZEROPAD_6)
@@ -300,19 +412,19 @@ impl<'a> ResponderContext<'a> {
             return Err(SPDM_STATUS_INVALID_STATE_LOCAL);
         }
 
-        crate::secret::asym_sign::sign(
-            self.common.negotiate_info.base_hash_sel,
-            self.common.negotiate_info.base_asym_sel,
+        Ok(self.common.sign_with_my_key_pair(
+            slot_id,
+            &SPDM_MEASUREMENTS_SIGN_CONTEXT,
             message_sign.as_ref(),
-        )
-        .ok_or(SPDM_STATUS_CRYPTO_ERROR)
+        ))
     }
 
     #[cfg(not(feature = "hashed-transcript-data"))]
     pub fn generate_measurement_signature(
         &self,
         session_id: Option<u32>,
-    ) -> SpdmResult<SpdmSignatureStruct> {
+        slot_id: u8,
+    ) -> SpdmResult<SpdmSecretResult<SpdmSignatureStruct>> {
         let mut message_l1l2 = ManagedBufferL1L2::default();
         if self.common.negotiate_info.spdm_version_sel.get_u8()
             >= SpdmVersion::SpdmVersion12.get_u8()
@@ -356,7 +468,9 @@ impl<'a> ResponderContext<'a> {
         {
             message_l1l2.reset_message();
             message_l1l2
-                .append_message(&SPDM_VERSION_1_2_SIGNING_PREFIX_CONTEXT)
+                .append_message(spdm_signing_prefix_context(
+                    self.common.negotiate_info.spdm_version_sel,
+                ))
                 .ok_or(SPDM_STATUS_BUFFER_FULL)?;
             message_l1l2
                 .append_message(&SPDM_VERSION_1_2_SIGNING_CONTEXT_ZEROPAD_6)
@@ -369,11 +483,10 @@ impl<'a> ResponderContext<'a> {
                 .ok_or(SPDM_STATUS_BUFFER_FULL)?;
         }
 
-        crate::secret::asym_sign::sign(
-            self.common.negotiate_info.base_hash_sel,
-            self.common.negotiate_info.base_asym_sel,
+        Ok(self.common.sign_with_my_key_pair(
+            slot_id,
+            &SPDM_MEASUREMENTS_SIGN_CONTEXT,
             message_l1l2.as_ref(),
-        )
-        .ok_or(SPDM_STATUS_CRYPTO_ERROR)
+        ))
     }
 }
@@ -0,0 +1,134 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use crate::common::SpdmCodec;
+use crate::common::SpdmConnectionState;
+use crate::error::SpdmResult;
+use crate::message::*;
+use crate::protocol::*;
+use crate::responder::*;
+use crate::secret;
+
+impl<'a> ResponderContext<'a> {
+    pub fn handle_spdm_measurement_extension_log(
+        &mut self,
+        session_id: Option<u32>,
+        bytes: &[u8],
+    ) -> SpdmResult {
+        let mut send_buffer = [0u8; config::MAX_SPDM_MSG_SIZE];
+        let mut writer = Writer::init(&mut send_buffer);
+        self.write_spdm_measurement_extension_log_response(session_id, bytes, &mut writer);
+
+        if let Some(session_id) = session_id {
+            self.send_secured_message(session_id, writer.used_slice(), false)
+        } else {
+            self.send_message(writer.used_slice())
+        }
+    }
+
+    fn write_spdm_measurement_extension_log_response(
+        &mut self,
+        session_id: Option<u32>,
+        bytes: &[u8],
+        writer: &mut Writer,
+    ) {
+        if self.common.negotiate_info.spdm_version_sel.get_u8()
+            < SpdmVersion::SpdmVersion13.get_u8()
+            || !self
+                .common
+                .negotiate_info
+                .rsp_capabilities_sel
+                .contains(SpdmResponseCapabilityFlags::MEL_CAP)
+        {
+            self.write_error_response(SpdmErrorCode::SpdmErrorUnsupportedRequest, bytes, writer);
+            return;
+        }
+        if self.common.runtime_info.get_connection_state().get_u8()
+            < SpdmConnectionState::SpdmConnectionNegotiated.get_u8()
+        {
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorUnexpectedRequest, 0, writer);
+            return;
+        }
+        let mut reader = Reader::init(bytes);
+        let message_header = SpdmMessageHeader::read(&mut reader);
+        if let Some(message_header) = message_header {
+            if message_header.version != self.common.negotiate_info.spdm_version_sel {
+                self.write_spdm_error(SpdmErrorCode::SpdmErrorVersionMismatch, 0, writer);
+                return;
+            }
+        } else {
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorInvalidRequest, 0, writer);
+            return;
+        }
+
+        self.common.reset_buffer_via_request_code(
+            SpdmRequestResponseCode::SpdmRequestGetMeasurementExtensionLog,
+            session_id,
+        );
+
+        let get_mel =
+            SpdmGetMeasurementExtensionLogRequestPayload::spdm_read(&mut self.common, &mut reader);
+        let get_mel = if let Some(get_mel) = get_mel {
+            debug!("!!! get_measurement_extension_log : {:02x?}\n", get_mel);
+            get_mel
+        } else {
+            error!("!!! get_measurement_extension_log : fail !!!\n");
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorInvalidRequest, 0, writer);
+            return;
+        };
+
+        let mel = secret::mel::measurement_extension_log(
+            self.common.negotiate_info.spdm_version_sel,
+            self.common.negotiate_info.mel_specification_sel,
+            self.common.negotiate_info.measurement_hash_sel,
+        );
+        let mel = match mel {
+            Some(mel) if mel.data_size as usize <= mel.data.len() => mel,
+            _ => {
+                self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
+                return;
+            }
+        };
+
+        let offset = get_mel.offset;
+        if offset > mel.data_size {
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorInvalidRequest, 0, writer);
+            return;
+        }
+
+        let mut length = get_mel.length;
+        if length > MAX_SPDM_MEL_PORTION_LEN as u32 {
+            length = MAX_SPDM_MEL_PORTION_LEN as u32;
+        }
+        if length > mel.data_size - offset {
+            length = mel.data_size - offset;
+        }
+
+        let portion_length = length;
+        let remainder_length = mel.data_size - (length + offset);
+
+        let mel_data = &mel.data[(offset as usize)..(offset as usize + length as usize)];
+        let mut measurement_extension_log = [0u8; MAX_SPDM_MEL_PORTION_LEN];
+        measurement_extension_log[..mel_data.len()].copy_from_slice(mel_data);
+
+        info!("send spdm measurement_extension_log\n");
+        let response = SpdmMessage {
+            header: SpdmMessageHeader {
+                version: self.common.negotiate_info.spdm_version_sel,
+                request_response_code: SpdmRequestResponseCode::SpdmResponseMeasurementExtensionLog,
+            },
+            payload: SpdmMessagePayload::SpdmMeasurementExtensionLogResponse(
+                SpdmMeasurementExtensionLogResponsePayload {
+                    portion_length,
+                    remainder_length,
+                    measurement_extension_log,
+                },
+            ),
+        };
+        let res = response.spdm_encode(&mut self.common, writer);
+        if res.is_err() {
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
+        }
+    }
+}
@@ -8,21 +8,34 @@ mod algorithm_rsp;
 mod capability_rsp;
 mod certificate_rsp;
 mod challenge_rsp;
+mod chunk_get_rsp;
+mod chunk_send_rsp;
+mod csr_rsp;
 mod digest_rsp;
 #[cfg(feature = "mut-auth")]
+mod encap_challenge;
+#[cfg(feature = "mut-auth")]
 mod encap_get_certificate;
 #[cfg(feature = "mut-auth")]
 mod encap_get_digest;
 #[cfg(feature = "mut-auth")]
+mod encap_key_update;
+#[cfg(feature = "mut-auth")]
 mod encap_rsp;
 mod end_session_rsp;
+mod endpoint_info_rsp;
+mod event_rsp;
 mod finish_rsp;
 mod heartbeat_rsp;
 mod key_exchange_rsp;
+mod key_pair_info_rsp;
 mod key_update_rsp;
 mod measurement_rsp;
+mod mel_rsp;
 mod psk_exchange_rsp;
 mod psk_finish_rsp;
+mod respond_if_ready_rsp;
+mod set_certificate_rsp;
 mod version_rsp;
 
 mod error_rsp;
@@ -19,6 +19,7 @@ use crate::responder::*;
 use config::MAX_SPDM_PSK_CONTEXT_SIZE;
 extern crate alloc;
 use crate::secret;
+use crate::secret::SpdmSecretResult;
 use alloc::boxed::Box;
 
 impl<'a> ResponderContext<'a> {
@@ -75,19 +76,32 @@ impl<'a> ResponderContext<'a> {
                     == SpdmMeasurementSummaryHashType::SpdmMeasurementSummaryHashTypeAll)
             {
                 self.common.runtime_info.need_measurement_summary_hash = true;
-                let measurement_summary_hash_res =
-                    secret::measurement::generate_measurement_summary_hash(
+                measurement_summary_hash =
+                    match secret::measurement::generate_measurement_summary_hash(
                         self.common.negotiate_info.spdm_version_sel,
                         self.common.negotiate_info.base_hash_sel,
                         self.common.negotiate_info.measurement_specification_sel,
                         self.common.negotiate_info.measurement_hash_sel,
                         psk_exchange_req.measurement_summary_hash_type,
-                    );
-                if measurement_summary_hash_res.is_none() {
-                    self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
-                    return Err(SPDM_STATUS_CRYPTO_ERROR);
-                }
-                measurement_summary_hash = measurement_summary_hash_res.unwrap();
+                    ) {
+                        SpdmSecretResult::Ready(measurement_summary_hash) => {
+                            measurement_summary_hash
+                        }
+                        SpdmSecretResult::NotReady { rdt_exponent, rdtm } => {
+                            self.write_spdm_response_not_ready(
+                                None,
+                                bytes,
+                                rdt_exponent,
+                                rdtm,
+                                writer,
+                            );
+                            return Ok(());
+                        }
+                        SpdmSecretResult::Failed => {
+                            self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
+                            return Err(SPDM_STATUS_CRYPTO_ERROR);
+                        }
+                    };
                 if measurement_summary_hash.data_size == 0 {
                     self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
                     return Err(SPDM_STATUS_CRYPTO_ERROR);
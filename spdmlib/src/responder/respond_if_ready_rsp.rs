@@ -0,0 +1,130 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use crate::common::SpdmCodec;
+use crate::crypto;
+use crate::error::SpdmResult;
+use crate::message::*;
+use crate::responder::*;
+
+// A request answered with ERROR(ResponseNotReady). It is processed again
+// when RESPOND_IF_READY with the matching token arrives.
+#[derive(Debug, Clone)]
+pub(crate) struct SpdmDeferredRequest {
+    pub session_id: Option<u32>,
+    pub request_code: SpdmRequestResponseCode,
+    pub token: u8,
+    pub request_size: usize,
+    pub request: [u8; config::MAX_SPDM_MSG_SIZE],
+}
+
+impl<'a> ResponderContext<'a> {
+    // Reply ERROR(ResponseNotReady) in place of the response to a request
+    // that the signing or measurement callback cannot complete right away,
+    // and keep the request until RESPOND_IF_READY arrives.
+    pub(crate) fn write_spdm_response_not_ready(
+        &mut self,
+        session_id: Option<u32>,
+        bytes: &[u8],
+        rdt_exponent: u8,
+        rdtm: u8,
+        writer: &mut Writer,
+    ) {
+        let mut reader = Reader::init(bytes);
+        let request_code = if let Some(message_header) = SpdmMessageHeader::read(&mut reader) {
+            message_header.request_response_code
+        } else {
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
+            return;
+        };
+        let mut token = [0u8; 1];
+        if bytes.len() > config::MAX_SPDM_MSG_SIZE || crypto::rand::get_random(&mut token).is_err()
+        {
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
+            return;
+        }
+        let mut deferred_request = SpdmDeferredRequest {
+            session_id,
+            request_code,
+            token: token[0],
+            request_size: bytes.len(),
+            request: [0u8; config::MAX_SPDM_MSG_SIZE],
+        };
+        deferred_request.request[..bytes.len()].copy_from_slice(bytes);
+        self.deferred_request = Some(deferred_request);
+
+        info!("send spdm response not ready\n");
+
+        let error = SpdmMessage {
+            header: SpdmMessageHeader {
+                version: self.common.negotiate_info.spdm_version_sel,
+                request_response_code: SpdmRequestResponseCode::SpdmResponseError,
+            },
+            payload: SpdmMessagePayload::SpdmErrorResponse(SpdmErrorResponsePayload {
+                error_code: SpdmErrorCode::SpdmErrorResponseNotReady,
+                error_data: 0,
+                extended_data: SpdmErrorResponseExtData::SpdmErrorExtDataNotReady(
+                    SpdmErrorResponseNotReadyExtData {
+                        rdt_exponent,
+                        request_code: request_code.get_u8(),
+                        token: token[0],
+                        rdtm,
+                    },
+                ),
+            }),
+        };
+        let _ = error.spdm_encode(&mut self.common, writer);
+    }
+
+    pub fn handle_spdm_respond_if_ready(
+        &mut self,
+        session_id: Option<u32>,
+        bytes: &[u8],
+    ) -> SpdmResult {
+        let mut reader = Reader::init(bytes);
+        let message_header = SpdmMessageHeader::read(&mut reader);
+        let error_code = if let Some(message_header) = message_header {
+            if message_header.version != self.common.negotiate_info.spdm_version_sel {
+                SpdmErrorCode::SpdmErrorVersionMismatch
+            } else if let Some(respond_if_ready) =
+                SpdmRespondIfReadyRequestPayload::spdm_read(&mut self.common, &mut reader)
+            {
+                debug!("!!! respond_if_ready : {:02x?}\n", respond_if_ready);
+                match self.deferred_request.take() {
+                    Some(deferred_request)
+                        if deferred_request.session_id == session_id
+                            && deferred_request.request_code == respond_if_ready.request_code
+                            && deferred_request.token == respond_if_ready.token =>
+                    {
+                        // The request is processed as if it had just arrived,
+                        // so it may be deferred again if the callback is still busy.
+                        let request = &deferred_request.request[..deferred_request.request_size];
+                        return match session_id {
+                            Some(session_id) => self.dispatch_secured_message(session_id, request),
+                            None => self.dispatch_message(request),
+                        };
+                    }
+                    Some(deferred_request) => {
+                        error!("!!! respond_if_ready : request mismatch !!!\n");
+                        self.deferred_request = Some(deferred_request);
+                        SpdmErrorCode::SpdmErrorInvalidRequest
+                    }
+                    None => SpdmErrorCode::SpdmErrorUnexpectedRequest,
+                }
+            } else {
+                SpdmErrorCode::SpdmErrorInvalidRequest
+            }
+        } else {
+            SpdmErrorCode::SpdmErrorInvalidRequest
+        };
+
+        let mut send_buffer = [0u8; config::MAX_SPDM_MSG_SIZE];
+        let mut writer = Writer::init(&mut send_buffer);
+        self.write_spdm_error(error_code, 0, &mut writer);
+        match session_id {
+            Some(session_id) => self.send_secured_message(session_id, writer.used_slice(), false),
+            None => self.send_message(writer.used_slice()),
+        }
+    }
+}
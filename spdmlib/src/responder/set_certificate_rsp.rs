@@ -0,0 +1,211 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use crate::common::SpdmCodec;
+use crate::common::SpdmConnectionState;
+use crate::crypto;
+use crate::error::SpdmResult;
+use crate::message::*;
+use crate::protocol::*;
+use crate::responder::*;
+use crate::secret;
+
+impl<'a> ResponderContext<'a> {
+    pub fn handle_spdm_set_certificate(
+        &mut self,
+        session_id: Option<u32>,
+        bytes: &[u8],
+    ) -> SpdmResult {
+        let mut send_buffer = [0u8; config::MAX_SPDM_MSG_SIZE];
+        let mut writer = Writer::init(&mut send_buffer);
+        self.write_spdm_set_certificate_response(session_id, bytes, &mut writer);
+
+        if let Some(session_id) = session_id {
+            self.send_secured_message(session_id, writer.used_slice(), false)
+        } else {
+            self.send_message(writer.used_slice())
+        }
+    }
+
+    fn write_spdm_set_certificate_response(
+        &mut self,
+        session_id: Option<u32>,
+        bytes: &[u8],
+        writer: &mut Writer,
+    ) {
+        if self.common.negotiate_info.spdm_version_sel.get_u8()
+            < SpdmVersion::SpdmVersion12.get_u8()
+            || !self
+                .common
+                .negotiate_info
+                .rsp_capabilities_sel
+                .contains(SpdmResponseCapabilityFlags::SET_CERT_CAP)
+        {
+            self.write_error_response(SpdmErrorCode::SpdmErrorUnsupportedRequest, bytes, writer);
+            return;
+        }
+        if self.common.runtime_info.get_connection_state().get_u8()
+            < SpdmConnectionState::SpdmConnectionNegotiated.get_u8()
+        {
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorUnexpectedRequest, 0, writer);
+            return;
+        }
+        let mut reader = Reader::init(bytes);
+        let message_header = SpdmMessageHeader::read(&mut reader);
+        if let Some(message_header) = message_header {
+            if message_header.version != self.common.negotiate_info.spdm_version_sel {
+                self.write_spdm_error(SpdmErrorCode::SpdmErrorVersionMismatch, 0, writer);
+                return;
+            }
+        } else {
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorInvalidRequest, 0, writer);
+            return;
+        }
+
+        self.common.reset_buffer_via_request_code(
+            SpdmRequestResponseCode::SpdmRequestSetCertificate,
+            session_id,
+        );
+
+        let set_certificate =
+            SpdmSetCertificateRequestPayload::spdm_read(&mut self.common, &mut reader);
+        let set_certificate = if let Some(set_certificate) = set_certificate {
+            debug!(
+                "!!! set_certificate : slot {}, size {}\n",
+                set_certificate.slot_id, set_certificate.cert_chain.data_size
+            );
+            set_certificate
+        } else {
+            error!("!!! set_certificate : fail !!!\n");
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorInvalidRequest, 0, writer);
+            return;
+        };
+
+        let slot_id = set_certificate.slot_id as usize;
+        if slot_id >= SPDM_MAX_SLOT_NUMBER {
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorInvalidRequest, 0, writer);
+            return;
+        }
+        // Only slot 0 may be provisioned outside of a secure session.
+        if session_id.is_none() && slot_id != 0 {
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorUnexpectedRequest, 0, writer);
+            return;
+        }
+
+        let cert_chain_data = if let Some(cert_chain_data) =
+            self.verify_spdm_set_certificate_chain(&set_certificate.cert_chain)
+        {
+            cert_chain_data
+        } else {
+            error!("!!! set_certificate : invalid cert chain !!!\n");
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorInvalidRequest, 0, writer);
+            return;
+        };
+
+        let mut need_reset = false;
+        if !secret::cert_provision::write_cert_chain(
+            self.common.negotiate_info.base_hash_sel,
+            self.common.negotiate_info.base_asym_sel,
+            slot_id as u8,
+            cert_chain_data.as_ref(),
+            &mut need_reset,
+        ) {
+            error!("!!! set_certificate : write cert chain fail !!!\n");
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
+            return;
+        }
+
+        if need_reset {
+            if self
+                .common
+                .negotiate_info
+                .rsp_capabilities_sel
+                .contains(SpdmResponseCapabilityFlags::CERT_INSTALL_RESET_CAP)
+            {
+                // The new certificate chain takes effect after the reset.
+                info!("send spdm set certificate reset required\n");
+                self.write_spdm_error(SpdmErrorCode::SpdmErrorResetRequired, 0, writer);
+            } else {
+                self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
+            }
+            return;
+        }
+
+        //
+        // update cert chain - append root cert hash
+        //
+        self.common.provision_info.my_cert_chain_data[slot_id] = Some(cert_chain_data);
+        self.common.provision_info.my_cert_chain[slot_id] = None;
+        if self.common.construct_my_cert_chain().is_err() {
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
+            return;
+        }
+
+        info!("send spdm set certificate rsp\n");
+
+        let response = SpdmMessage {
+            header: SpdmMessageHeader {
+                version: self.common.negotiate_info.spdm_version_sel,
+                request_response_code: SpdmRequestResponseCode::SpdmResponseSetCertificateRsp,
+            },
+            payload: SpdmMessagePayload::SpdmSetCertificateResponse(
+                SpdmSetCertificateResponsePayload {
+                    slot_id: slot_id as u8,
+                },
+            ),
+        };
+        let res = response.spdm_encode(&mut self.common, writer);
+        if res.is_err() {
+            self.write_spdm_error(SpdmErrorCode::SpdmErrorUnspecified, 0, writer);
+        }
+    }
+
+    // Verify the received certificate chain and its RootHash, and return the
+    // DER encoded certificates. In the alias certificate model the chain ends
+    // at the device certificate, the alias certificate being generated by the
+    // device itself.
+    fn verify_spdm_set_certificate_chain(
+        &self,
+        cert_chain: &SpdmCertChainBuffer,
+    ) -> Option<SpdmCertChainData> {
+        let hash_size = self.common.negotiate_info.base_hash_sel.get_size() as usize;
+        let cert_chain = cert_chain.as_ref();
+        if cert_chain.len() <= 4 + hash_size
+            || cert_chain.len() - 4 - hash_size > config::MAX_SPDM_CERT_CHAIN_DATA_SIZE
+        {
+            return None;
+        }
+        let root_hash = &cert_chain[4..(4 + hash_size)];
+        let certs = &cert_chain[(4 + hash_size)..];
+
+        let is_alias_cert_model = self
+            .common
+            .negotiate_info
+            .rsp_capabilities_sel
+            .contains(SpdmResponseCapabilityFlags::ALIAS_CERT_CAP);
+        let is_partial_cert_chain =
+            crypto::check_cert_chain_model(certs, is_alias_cert_model).ok()?;
+        if is_partial_cert_chain != is_alias_cert_model {
+            return None;
+        }
+        crypto::cert_operation::verify_cert_chain(certs, false, is_alias_cert_model).ok()?;
+
+        let (root_cert_begin, root_cert_end) =
+            crypto::cert_operation::get_cert_from_cert_chain(certs, 0).ok()?;
+        let root_cert_hash = crypto::hash::hash_all(
+            self.common.negotiate_info.base_hash_sel,
+            &certs[root_cert_begin..root_cert_end],
+        )?;
+        if root_cert_hash.as_ref() != root_hash {
+            return None;
+        }
+
+        let mut cert_chain_data = SpdmCertChainData {
+            data_size: certs.len() as u16,
+            ..Default::default()
+        };
+        cert_chain_data.data[..certs.len()].copy_from_slice(certs);
+        Some(cert_chain_data)
+    }
+}
@@ -53,6 +53,22 @@ impl<'a> ResponderContext<'a> {
             return;
         }
 
+        let mut versions = gen_array_clone(SpdmVersionStruct::default(), MAX_SPDM_VERSION_COUNT);
+        let mut version_number_entry_count = 0usize;
+        for version in self
+            .common
+            .config_info
+            .spdm_version
+            .iter()
+            .take(MAX_SPDM_VERSION_COUNT)
+        {
+            versions[version_number_entry_count] = SpdmVersionStruct {
+                update: 0,
+                version: *version,
+            };
+            version_number_entry_count += 1;
+        }
+
         info!("send spdm version\n");
         let response = SpdmMessage {
             header: SpdmMessageHeader {
@@ -60,21 +76,8 @@ impl<'a> ResponderContext<'a> {
                 request_response_code: SpdmRequestResponseCode::SpdmResponseVersion,
             },
             payload: SpdmMessagePayload::SpdmVersionResponse(SpdmVersionResponsePayload {
-                version_number_entry_count: 3,
-                versions: [
-                    SpdmVersionStruct {
-                        update: 0,
-                        version: self.common.config_info.spdm_version[0],
-                    },
-                    SpdmVersionStruct {
-                        update: 0,
-                        version: self.common.config_info.spdm_version[1],
-                    },
-                    SpdmVersionStruct {
-                        update: 0,
-                        version: self.common.config_info.spdm_version[2],
-                    },
-                ],
+                version_number_entry_count: version_number_entry_count as u8,
+                versions,
             }),
         };
 
@@ -4,14 +4,27 @@
 mod secret_callback;
 
 use conquer_once::spin::OnceCell;
-pub use secret_callback::{SpdmSecretAsymSign, SpdmSecretMeasurement, SpdmSecretPsk};
+pub use secret_callback::{
+    SpdmSecretAsymSign, SpdmSecretCertProvision, SpdmSecretCsr, SpdmSecretEndpointInfo,
+    SpdmSecretExtAsymSign, SpdmSecretKeyPair, SpdmSecretMeasurement,
+    SpdmSecretMeasurementExtensionLog, SpdmSecretPqcAsymSign, SpdmSecretPsk, SpdmSecretResult,
+    SpdmSecretSm2Sign,
+};
 
 static SECRET_MEASUREMENT_INSTANCE: OnceCell<SpdmSecretMeasurement> = OnceCell::uninit();
+static SECRET_MEL_INSTANCE: OnceCell<SpdmSecretMeasurementExtensionLog> = OnceCell::uninit();
+static SECRET_ENDPOINT_INFO_INSTANCE: OnceCell<SpdmSecretEndpointInfo> = OnceCell::uninit();
 static SECRET_PSK_INSTANCE: OnceCell<SpdmSecretPsk> = OnceCell::uninit();
 static SECRET_ASYM_INSTANCE: OnceCell<SpdmSecretAsymSign> = OnceCell::uninit();
+static SECRET_EXT_ASYM_INSTANCE: OnceCell<SpdmSecretExtAsymSign> = OnceCell::uninit();
+static SECRET_PQC_ASYM_INSTANCE: OnceCell<SpdmSecretPqcAsymSign> = OnceCell::uninit();
+static SECRET_SM2_INSTANCE: OnceCell<SpdmSecretSm2Sign> = OnceCell::uninit();
+static SECRET_KEY_PAIR_INSTANCE: OnceCell<SpdmSecretKeyPair> = OnceCell::uninit();
+static SECRET_CSR_INSTANCE: OnceCell<SpdmSecretCsr> = OnceCell::uninit();
+static SECRET_CERT_PROVISION_INSTANCE: OnceCell<SpdmSecretCertProvision> = OnceCell::uninit();
 
 pub mod measurement {
-    use super::{SpdmSecretMeasurement, SECRET_MEASUREMENT_INSTANCE};
+    use super::{SpdmSecretMeasurement, SpdmSecretResult, SECRET_MEASUREMENT_INSTANCE};
     use crate::protocol::*;
 
     pub fn register(context: SpdmSecretMeasurement) -> bool {
@@ -25,7 +38,7 @@ pub mod measurement {
                                     _measurement_specification: SpdmMeasurementSpecification,
                                     _measurement_hash_algo: SpdmMeasurementHashAlgo,
                                     _measurement_index: usize|
-         -> Option<SpdmMeasurementRecordStructure> {
+         -> SpdmSecretResult<SpdmMeasurementRecordStructure> {
             unimplemented!()
         },
 
@@ -35,7 +48,7 @@ pub mod measurement {
              _measurement_specification: SpdmMeasurementSpecification,
              _measurement_hash_algo: SpdmMeasurementHashAlgo,
              _measurement_summary_hash_type: SpdmMeasurementSummaryHashType|
-             -> Option<SpdmDigestStruct> { unimplemented!() },
+             -> SpdmSecretResult<SpdmDigestStruct> { unimplemented!() },
     };
 
     /*
@@ -57,11 +70,15 @@ pub mod measurement {
         measurement_specification: SpdmMeasurementSpecification,
         measurement_hash_algo: SpdmMeasurementHashAlgo,
         measurement_index: usize,
-    ) -> Option<SpdmMeasurementRecordStructure> {
-        (SECRET_MEASUREMENT_INSTANCE
-            .try_get_or_init(|| UNIMPLETEMTED.clone())
-            .ok()?
-            .measurement_collection_cb)(
+    ) -> SpdmSecretResult<SpdmMeasurementRecordStructure> {
+        let instance = if let Ok(instance) =
+            SECRET_MEASUREMENT_INSTANCE.try_get_or_init(|| UNIMPLETEMTED.clone())
+        {
+            instance
+        } else {
+            return SpdmSecretResult::Failed;
+        };
+        (instance.measurement_collection_cb)(
             spdm_version,
             measurement_specification,
             measurement_hash_algo,
@@ -74,11 +91,15 @@ pub mod measurement {
         measurement_specification: SpdmMeasurementSpecification,
         measurement_hash_algo: SpdmMeasurementHashAlgo,
         measurement_summary_hash_type: SpdmMeasurementSummaryHashType,
-    ) -> Option<SpdmDigestStruct> {
-        (SECRET_MEASUREMENT_INSTANCE
-            .try_get_or_init(|| UNIMPLETEMTED.clone())
-            .ok()?
-            .generate_measurement_summary_hash_cb)(
+    ) -> SpdmSecretResult<SpdmDigestStruct> {
+        let instance = if let Ok(instance) =
+            SECRET_MEASUREMENT_INSTANCE.try_get_or_init(|| UNIMPLETEMTED.clone())
+        {
+            instance
+        } else {
+            return SpdmSecretResult::Failed;
+        };
+        (instance.generate_measurement_summary_hash_cb)(
             spdm_version,
             base_hash_algo,
             measurement_specification,
@@ -87,6 +108,76 @@ pub mod measurement {
         )
     }
 }
+pub mod mel {
+    use super::{SpdmSecretMeasurementExtensionLog, SECRET_MEL_INSTANCE};
+    use crate::protocol::*;
+
+    pub fn register(context: SpdmSecretMeasurementExtensionLog) -> bool {
+        SECRET_MEL_INSTANCE.try_init_once(|| context).is_ok()
+    }
+
+    static UNIMPLETEMTED: SpdmSecretMeasurementExtensionLog = SpdmSecretMeasurementExtensionLog {
+        measurement_extension_log_cb: |_spdm_version: SpdmVersion,
+                                       _mel_specification: SpdmMelSpecification,
+                                       _measurement_hash_algo: SpdmMeasurementHashAlgo|
+         -> Option<SpdmMeasurementExtensionLog> {
+            unimplemented!()
+        },
+    };
+
+    /*
+        Function to get the measurement extension log (MEL).
+
+        The whole log is returned in the format of mel_specification, the
+        responder pages it to the requester with GET_MEASUREMENT_EXTENSION_LOG.
+    */
+    pub fn measurement_extension_log(
+        spdm_version: SpdmVersion,
+        mel_specification: SpdmMelSpecification,
+        measurement_hash_algo: SpdmMeasurementHashAlgo,
+    ) -> Option<SpdmMeasurementExtensionLog> {
+        (SECRET_MEL_INSTANCE
+            .try_get_or_init(|| UNIMPLETEMTED.clone())
+            .ok()?
+            .measurement_extension_log_cb)(
+            spdm_version, mel_specification, measurement_hash_algo
+        )
+    }
+}
+
+pub mod endpoint_info {
+    use super::{SpdmSecretEndpointInfo, SECRET_ENDPOINT_INFO_INSTANCE};
+    use crate::protocol::*;
+
+    pub fn register(context: SpdmSecretEndpointInfo) -> bool {
+        SECRET_ENDPOINT_INFO_INSTANCE
+            .try_init_once(|| context)
+            .is_ok()
+    }
+
+    static UNIMPLETEMTED: SpdmSecretEndpointInfo = SpdmSecretEndpointInfo {
+        endpoint_info_cb: |_spdm_version: SpdmVersion,
+                           _sub_code: SpdmEndpointInfoSubCode|
+         -> Option<SpdmEndpointInfo> { unimplemented!() },
+    };
+
+    /*
+        Function to get the endpoint information selected by sub_code.
+
+        The responder returns it in ENDPOINT_INFO, signed when the requester
+        asks for a signature.
+    */
+    pub fn endpoint_info(
+        spdm_version: SpdmVersion,
+        sub_code: SpdmEndpointInfoSubCode,
+    ) -> Option<SpdmEndpointInfo> {
+        (SECRET_ENDPOINT_INFO_INSTANCE
+            .try_get_or_init(|| UNIMPLETEMTED.clone())
+            .ok()?
+            .endpoint_info_cb)(spdm_version, sub_code)
+    }
+}
+
 pub mod psk {
     use super::{SpdmSecretPsk, SECRET_PSK_INSTANCE};
     use crate::protocol::*;
@@ -140,7 +231,7 @@ pub mod psk {
 pub mod asym_sign {
     use super::SECRET_ASYM_INSTANCE;
     use crate::protocol::{SpdmBaseAsymAlgo, SpdmBaseHashAlgo, SpdmSignatureStruct};
-    use crate::secret::SpdmSecretAsymSign;
+    use crate::secret::{SpdmSecretAsymSign, SpdmSecretResult};
 
     pub fn register(context: SpdmSecretAsymSign) -> bool {
         SECRET_ASYM_INSTANCE.try_init_once(|| context).is_ok()
@@ -149,18 +240,295 @@ pub mod asym_sign {
     static DEFAULT: SpdmSecretAsymSign = SpdmSecretAsymSign {
         sign_cb: |_base_hash_algo: SpdmBaseHashAlgo,
                   _base_asym_algo: SpdmBaseAsymAlgo,
+                  _key_pair_id: u8,
                   _data: &[u8]|
-         -> Option<SpdmSignatureStruct> { unimplemented!() },
+         -> SpdmSecretResult<SpdmSignatureStruct> { unimplemented!() },
     };
 
+    /*
+        Function to sign data with the private key of key pair key_pair_id.
+
+        key_pair_id is the key pair bound to the certificate slot in use,
+        SPDM_DEFAULT_KEY_PAIR_ID when the slot is not bound to a key pair.
+    */
     pub fn sign(
         base_hash_algo: SpdmBaseHashAlgo,
         base_asym_algo: SpdmBaseAsymAlgo,
+        key_pair_id: u8,
         data: &[u8],
-    ) -> Option<SpdmSignatureStruct> {
-        (SECRET_ASYM_INSTANCE
-            .try_get_or_init(|| DEFAULT.clone())
+    ) -> SpdmSecretResult<SpdmSignatureStruct> {
+        let instance =
+            if let Ok(instance) = SECRET_ASYM_INSTANCE.try_get_or_init(|| DEFAULT.clone()) {
+                instance
+            } else {
+                return SpdmSecretResult::Failed;
+            };
+        (instance.sign_cb)(base_hash_algo, base_asym_algo, key_pair_id, data)
+    }
+}
+
+pub mod ext_asym_sign {
+    use super::SECRET_EXT_ASYM_INSTANCE;
+    use crate::protocol::{SpdmBaseHashAlgo, SpdmExtAlgStruct, SpdmSignatureStruct};
+    use crate::secret::{SpdmSecretExtAsymSign, SpdmSecretResult};
+
+    pub fn register(context: SpdmSecretExtAsymSign) -> bool {
+        SECRET_EXT_ASYM_INSTANCE.try_init_once(|| context).is_ok()
+    }
+
+    static DEFAULT: SpdmSecretExtAsymSign = SpdmSecretExtAsymSign {
+        sign_cb: |_base_hash_algo: SpdmBaseHashAlgo,
+                  _ext_asym_algo: SpdmExtAlgStruct,
+                  _key_pair_id: u8,
+                  _data: &[u8]|
+         -> SpdmSecretResult<SpdmSignatureStruct> { unimplemented!() },
+    };
+
+    /*
+        Function to sign data with the private key of key pair key_pair_id,
+        for an extended asymmetric algorithm selected through ExtAsymSel.
+    */
+    pub fn sign(
+        base_hash_algo: SpdmBaseHashAlgo,
+        ext_asym_algo: SpdmExtAlgStruct,
+        key_pair_id: u8,
+        data: &[u8],
+    ) -> SpdmSecretResult<SpdmSignatureStruct> {
+        let instance =
+            if let Ok(instance) = SECRET_EXT_ASYM_INSTANCE.try_get_or_init(|| DEFAULT.clone()) {
+                instance
+            } else {
+                return SpdmSecretResult::Failed;
+            };
+        (instance.sign_cb)(base_hash_algo, ext_asym_algo, key_pair_id, data)
+    }
+}
+
+pub mod pqc_asym_sign {
+    use super::SECRET_PQC_ASYM_INSTANCE;
+    use crate::protocol::{SpdmBaseHashAlgo, SpdmPqcAsymAlgo, SpdmSignatureStruct};
+    use crate::secret::{SpdmSecretPqcAsymSign, SpdmSecretResult};
+
+    pub fn register(context: SpdmSecretPqcAsymSign) -> bool {
+        SECRET_PQC_ASYM_INSTANCE.try_init_once(|| context).is_ok()
+    }
+
+    static DEFAULT: SpdmSecretPqcAsymSign = SpdmSecretPqcAsymSign {
+        sign_cb: |_base_hash_algo: SpdmBaseHashAlgo,
+                  _pqc_asym_algo: SpdmPqcAsymAlgo,
+                  _key_pair_id: u8,
+                  _data: &[u8]|
+         -> SpdmSecretResult<SpdmSignatureStruct> { unimplemented!() },
+    };
+
+    /*
+        Function to sign data with the private key of key pair key_pair_id,
+        for a PQC asymmetric algorithm selected through PqcAsymSel (SPDM 1.4).
+    */
+    pub fn sign(
+        base_hash_algo: SpdmBaseHashAlgo,
+        pqc_asym_algo: SpdmPqcAsymAlgo,
+        key_pair_id: u8,
+        data: &[u8],
+    ) -> SpdmSecretResult<SpdmSignatureStruct> {
+        let instance =
+            if let Ok(instance) = SECRET_PQC_ASYM_INSTANCE.try_get_or_init(|| DEFAULT.clone()) {
+                instance
+            } else {
+                return SpdmSecretResult::Failed;
+            };
+        (instance.sign_cb)(base_hash_algo, pqc_asym_algo, key_pair_id, data)
+    }
+}
+
+pub mod sm2_sign {
+    use super::SECRET_SM2_INSTANCE;
+    use crate::protocol::{SpdmBaseHashAlgo, SpdmSignatureStruct};
+    use crate::secret::{SpdmSecretResult, SpdmSecretSm2Sign};
+
+    pub fn register(context: SpdmSecretSm2Sign) -> bool {
+        SECRET_SM2_INSTANCE.try_init_once(|| context).is_ok()
+    }
+
+    static DEFAULT: SpdmSecretSm2Sign = SpdmSecretSm2Sign {
+        sign_cb: |_base_hash_algo: SpdmBaseHashAlgo,
+                  _key_pair_id: u8,
+                  _sm2_id: &[u8],
+                  _data: &[u8]|
+         -> SpdmSecretResult<SpdmSignatureStruct> { unimplemented!() },
+    };
+
+    /*
+        Function to sign data with the SM2 private key of key pair key_pair_id.
+
+        sm2_id is the signing ID of GB/T 32918.2: the signing context string
+        since SPDM 1.2, and empty before.
+    */
+    pub fn sign(
+        base_hash_algo: SpdmBaseHashAlgo,
+        key_pair_id: u8,
+        sm2_id: &[u8],
+        data: &[u8],
+    ) -> SpdmSecretResult<SpdmSignatureStruct> {
+        let instance = if let Ok(instance) = SECRET_SM2_INSTANCE.try_get_or_init(|| DEFAULT.clone())
+        {
+            instance
+        } else {
+            return SpdmSecretResult::Failed;
+        };
+        (instance.sign_cb)(base_hash_algo, key_pair_id, sm2_id, data)
+    }
+}
+
+pub mod key_pair {
+    use super::{SpdmSecretKeyPair, SECRET_KEY_PAIR_INSTANCE};
+    use crate::protocol::*;
+
+    pub fn register(context: SpdmSecretKeyPair) -> bool {
+        SECRET_KEY_PAIR_INSTANCE.try_init_once(|| context).is_ok()
+    }
+
+    static UNIMPLETEMTED: SpdmSecretKeyPair = SpdmSecretKeyPair {
+        get_key_pair_info_cb: |_key_pair_id: u8| -> Option<SpdmKeyPairInfo> { unimplemented!() },
+        set_key_pair_info_cb: |_operation: SpdmSetKeyPairInfoOperation,
+                               _key_pair_id: u8,
+                               _desired_key_usage: SpdmKeyUsageMask,
+                               _desired_asym_algo: SpdmKeyPairAsymAlgo,
+                               _desired_assoc_cert_slot_mask: u8|
+         -> bool { unimplemented!() },
+    };
+
+    /*
+        Function to get the state of key pair key_pair_id, 1 based.
+    */
+    pub fn get_key_pair_info(key_pair_id: u8) -> Option<SpdmKeyPairInfo> {
+        (SECRET_KEY_PAIR_INSTANCE
+            .try_get_or_init(|| UNIMPLETEMTED.clone())
             .ok()?
-            .sign_cb)(base_hash_algo, base_asym_algo, data)
+            .get_key_pair_info_cb)(key_pair_id)
+    }
+
+    /*
+        Function to apply a SET_KEY_PAIR_INFO operation to key pair key_pair_id.
+
+        desired_key_usage, desired_asym_algo and desired_assoc_cert_slot_mask
+        are zero for the erase operation.
+    */
+    pub fn set_key_pair_info(
+        operation: SpdmSetKeyPairInfoOperation,
+        key_pair_id: u8,
+        desired_key_usage: SpdmKeyUsageMask,
+        desired_asym_algo: SpdmKeyPairAsymAlgo,
+        desired_assoc_cert_slot_mask: u8,
+    ) -> bool {
+        if let Ok(instance) = SECRET_KEY_PAIR_INSTANCE.try_get_or_init(|| UNIMPLETEMTED.clone()) {
+            (instance.set_key_pair_info_cb)(
+                operation,
+                key_pair_id,
+                desired_key_usage,
+                desired_asym_algo,
+                desired_assoc_cert_slot_mask,
+            )
+        } else {
+            false
+        }
+    }
+}
+
+pub mod csr {
+    use super::SECRET_CSR_INSTANCE;
+    use crate::protocol::{SpdmBaseAsymAlgo, SpdmBaseHashAlgo, SpdmCsrStruct};
+    use crate::secret::SpdmSecretCsr;
+
+    pub fn register(context: SpdmSecretCsr) -> bool {
+        SECRET_CSR_INSTANCE.try_init_once(|| context).is_ok()
+    }
+
+    static UNIMPLETEMTED: SpdmSecretCsr = SpdmSecretCsr {
+        generate_csr_cb: |_base_hash_algo: SpdmBaseHashAlgo,
+                          _base_asym_algo: SpdmBaseAsymAlgo,
+                          _requester_info: &[u8],
+                          _opaque_data: &[u8],
+                          _need_reset: &mut bool|
+         -> Option<SpdmCsrStruct> { unimplemented!() },
+    };
+
+    /*
+        Function to generate a PKCS#10 certificate signing request for the
+        device identity key selected by base_asym_algo.
+
+        @When the device must be reset before the CSR can be generated,
+                need_reset is set to true and None is returned. The CSR is
+                expected to be available after the reset.
+    */
+    pub fn generate_csr(
+        base_hash_algo: SpdmBaseHashAlgo,
+        base_asym_algo: SpdmBaseAsymAlgo,
+        requester_info: &[u8],
+        opaque_data: &[u8],
+        need_reset: &mut bool,
+    ) -> Option<SpdmCsrStruct> {
+        (SECRET_CSR_INSTANCE
+            .try_get_or_init(|| UNIMPLETEMTED.clone())
+            .ok()?
+            .generate_csr_cb)(
+            base_hash_algo,
+            base_asym_algo,
+            requester_info,
+            opaque_data,
+            need_reset,
+        )
+    }
+}
+
+pub mod cert_provision {
+    use super::SECRET_CERT_PROVISION_INSTANCE;
+    use crate::protocol::{SpdmBaseAsymAlgo, SpdmBaseHashAlgo};
+    use crate::secret::SpdmSecretCertProvision;
+
+    pub fn register(context: SpdmSecretCertProvision) -> bool {
+        SECRET_CERT_PROVISION_INSTANCE
+            .try_init_once(|| context)
+            .is_ok()
+    }
+
+    static UNIMPLETEMTED: SpdmSecretCertProvision = SpdmSecretCertProvision {
+        write_cert_chain_cb: |_base_hash_algo: SpdmBaseHashAlgo,
+                              _base_asym_algo: SpdmBaseAsymAlgo,
+                              _slot_id: u8,
+                              _cert_chain: &[u8],
+                              _need_reset: &mut bool|
+         -> bool { unimplemented!() },
+    };
+
+    /*
+        Function to persist a certificate chain installed by SET_CERTIFICATE.
+
+        cert_chain is the DER encoded certificates, without the SPDM
+        certificate chain header and root hash.
+
+        @When the device must be reset before the certificate chain takes
+                effect, need_reset is set to true.
+    */
+    pub fn write_cert_chain(
+        base_hash_algo: SpdmBaseHashAlgo,
+        base_asym_algo: SpdmBaseAsymAlgo,
+        slot_id: u8,
+        cert_chain: &[u8],
+        need_reset: &mut bool,
+    ) -> bool {
+        if let Ok(instance) =
+            SECRET_CERT_PROVISION_INSTANCE.try_get_or_init(|| UNIMPLETEMTED.clone())
+        {
+            (instance.write_cert_chain_cb)(
+                base_hash_algo,
+                base_asym_algo,
+                slot_id,
+                cert_chain,
+                need_reset,
+            )
+        } else {
+            false
+        }
     }
 }
@@ -3,17 +3,54 @@
 // SPDX-License-Identifier: BSD-2-Clause-Patent
 
 use crate::protocol::{
-    SpdmBaseAsymAlgo, SpdmBaseHashAlgo, SpdmDigestStruct, SpdmHkdfOutputKeyingMaterial,
-    SpdmMeasurementHashAlgo, SpdmMeasurementRecordStructure, SpdmMeasurementSpecification,
-    SpdmMeasurementSummaryHashType, SpdmPskHintStruct, SpdmSignatureStruct, SpdmVersion,
+    SpdmBaseAsymAlgo, SpdmBaseHashAlgo, SpdmCsrStruct, SpdmDigestStruct, SpdmEndpointInfo,
+    SpdmEndpointInfoSubCode, SpdmExtAlgStruct, SpdmHkdfOutputKeyingMaterial, SpdmKeyPairAsymAlgo,
+    SpdmKeyPairInfo, SpdmKeyUsageMask, SpdmMeasurementExtensionLog, SpdmMeasurementHashAlgo,
+    SpdmMeasurementRecordStructure, SpdmMeasurementSpecification, SpdmMeasurementSummaryHashType,
+    SpdmMelSpecification, SpdmPqcAsymAlgo, SpdmPskHintStruct, SpdmSetKeyPairInfoOperation,
+    SpdmSignatureStruct, SpdmVersion,
 };
 
+/// The result of a signing or measurement callback, which may need more time
+/// than the requester waits for the response, e.g. to sign in a security module.
+#[derive(Debug, Clone)]
+pub enum SpdmSecretResult<T> {
+    Ready(T),
+    /// The responder replies ERROR(ResponseNotReady) with RDTExponent and RDTM,
+    /// and calls the callback again when RESPOND_IF_READY arrives. It is only
+    /// supported for CHALLENGE, GET_MEASUREMENTS, KEY_EXCHANGE and PSK_EXCHANGE,
+    /// and is a failure otherwise.
+    NotReady {
+        rdt_exponent: u8,
+        rdtm: u8,
+    },
+    Failed,
+}
+
+impl<T> SpdmSecretResult<T> {
+    pub fn ready(self) -> Option<T> {
+        match self {
+            SpdmSecretResult::Ready(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl<T> From<Option<T>> for SpdmSecretResult<T> {
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(value) => SpdmSecretResult::Ready(value),
+            None => SpdmSecretResult::Failed,
+        }
+    }
+}
+
 type SpdmMeasurementCollectionCbType = fn(
     spdm_version: SpdmVersion,
     measurement_specification: SpdmMeasurementSpecification,
     measurement_hash_algo: SpdmMeasurementHashAlgo,
     measurement_index: usize,
-) -> Option<SpdmMeasurementRecordStructure>;
+) -> SpdmSecretResult<SpdmMeasurementRecordStructure>;
 
 type SpdmGenerateMeasurementSummaryHashCbType = fn(
     spdm_version: SpdmVersion,
@@ -21,7 +58,7 @@ type SpdmGenerateMeasurementSummaryHashCbType = fn(
     measurement_specification: SpdmMeasurementSpecification,
     measurement_hash_algo: SpdmMeasurementHashAlgo,
     measurement_summary_hash_type: SpdmMeasurementSummaryHashType,
-) -> Option<SpdmDigestStruct>;
+) -> SpdmSecretResult<SpdmDigestStruct>;
 
 type SpdmPskHandshakeSecretHkdfExpandCbType = fn(
     spdm_version: SpdmVersion,
@@ -43,6 +80,25 @@ pub struct SpdmSecretMeasurement {
     pub generate_measurement_summary_hash_cb: SpdmGenerateMeasurementSummaryHashCbType,
 }
 
+type SpdmMeasurementExtensionLogCbType = fn(
+    spdm_version: SpdmVersion,
+    mel_specification: SpdmMelSpecification,
+    measurement_hash_algo: SpdmMeasurementHashAlgo,
+) -> Option<SpdmMeasurementExtensionLog>;
+
+#[derive(Clone)]
+pub struct SpdmSecretMeasurementExtensionLog {
+    pub measurement_extension_log_cb: SpdmMeasurementExtensionLogCbType,
+}
+
+type SpdmEndpointInfoCbType =
+    fn(spdm_version: SpdmVersion, sub_code: SpdmEndpointInfoSubCode) -> Option<SpdmEndpointInfo>;
+
+#[derive(Clone)]
+pub struct SpdmSecretEndpointInfo {
+    pub endpoint_info_cb: SpdmEndpointInfoCbType,
+}
+
 #[derive(Clone)]
 pub struct SpdmSecretPsk {
     pub handshake_secret_hkdf_expand_cb: SpdmPskHandshakeSecretHkdfExpandCbType,
@@ -50,11 +106,85 @@ pub struct SpdmSecretPsk {
     pub master_secret_hkdf_expand_cb: SpdmPskMasterSecretHkdfExpandCbType,
 }
 
+#[derive(Clone)]
+pub struct SpdmSecretExtAsymSign {
+    pub sign_cb: fn(
+        base_hash_algo: SpdmBaseHashAlgo,
+        ext_asym_algo: SpdmExtAlgStruct,
+        key_pair_id: u8,
+        data: &[u8],
+    ) -> SpdmSecretResult<SpdmSignatureStruct>,
+}
+
+#[derive(Clone)]
+pub struct SpdmSecretPqcAsymSign {
+    pub sign_cb: fn(
+        base_hash_algo: SpdmBaseHashAlgo,
+        pqc_asym_algo: SpdmPqcAsymAlgo,
+        key_pair_id: u8,
+        data: &[u8],
+    ) -> SpdmSecretResult<SpdmSignatureStruct>,
+}
+
+#[derive(Clone)]
+pub struct SpdmSecretSm2Sign {
+    pub sign_cb: fn(
+        base_hash_algo: SpdmBaseHashAlgo,
+        key_pair_id: u8,
+        sm2_id: &[u8],
+        data: &[u8],
+    ) -> SpdmSecretResult<SpdmSignatureStruct>,
+}
+
 #[derive(Clone)]
 pub struct SpdmSecretAsymSign {
     pub sign_cb: fn(
         base_hash_algo: SpdmBaseHashAlgo,
         base_asym_algo: SpdmBaseAsymAlgo,
+        key_pair_id: u8,
         data: &[u8],
-    ) -> Option<SpdmSignatureStruct>,
+    ) -> SpdmSecretResult<SpdmSignatureStruct>,
+}
+
+type SpdmGetKeyPairInfoCbType = fn(key_pair_id: u8) -> Option<SpdmKeyPairInfo>;
+
+type SpdmSetKeyPairInfoCbType = fn(
+    operation: SpdmSetKeyPairInfoOperation,
+    key_pair_id: u8,
+    desired_key_usage: SpdmKeyUsageMask,
+    desired_asym_algo: SpdmKeyPairAsymAlgo,
+    desired_assoc_cert_slot_mask: u8,
+) -> bool;
+
+#[derive(Clone)]
+pub struct SpdmSecretKeyPair {
+    pub get_key_pair_info_cb: SpdmGetKeyPairInfoCbType,
+
+    pub set_key_pair_info_cb: SpdmSetKeyPairInfoCbType,
+}
+
+type SpdmGenerateCsrCbType = fn(
+    base_hash_algo: SpdmBaseHashAlgo,
+    base_asym_algo: SpdmBaseAsymAlgo,
+    requester_info: &[u8],
+    opaque_data: &[u8],
+    need_reset: &mut bool,
+) -> Option<SpdmCsrStruct>;
+
+#[derive(Clone)]
+pub struct SpdmSecretCsr {
+    pub generate_csr_cb: SpdmGenerateCsrCbType,
+}
+
+type SpdmWriteCertChainCbType = fn(
+    base_hash_algo: SpdmBaseHashAlgo,
+    base_asym_algo: SpdmBaseAsymAlgo,
+    slot_id: u8,
+    cert_chain: &[u8],
+    need_reset: &mut bool,
+) -> bool;
+
+#[derive(Clone)]
+pub struct SpdmSecretCertProvision {
+    pub write_cert_chain_cb: SpdmWriteCertChainCbType,
 }
@@ -0,0 +1,88 @@
+/** @file
+ * EcDSA Sign Wrapper Implementation.
+ *
+ **/
+
+#include <mbedtls/ecdsa.h>
+#include <mbedtls/pk.h>
+
+/**
+ * Signs a hash with an EC private key.
+ *
+ * @param[in]       key                DER encoded EC private key, SEC1 or PKCS#8.
+ * @param[in]       key_size           Private key size in bytes.
+ * @param[in]       data               Pointer to octet data to be signed (hash).
+ * @param[in]       data_size          Size of the data in bytes.
+ * @param[out]      signature          Pointer to the buffer to receive the signature
+ *                                     in r || s format, each padded to the field size.
+ * @param[in, out]  sig_size           On input, the size of signature buffer in bytes.
+ *                                     On output, the size of signature in bytes.
+ * @param[in]       random_fn          The RNG function.
+ * @param[in]       random_fn_param    RNG function context pass to random_fn.
+ *
+ * @retval  0       Signature generation succeeded.
+ * @retval  not 0   Signature generation failed.
+ *
+ **/
+int spdm_ecdsa_sign(
+    const uint8_t *key, size_t key_size,
+    const uint8_t *data, size_t data_size,
+    uint8_t *signature, size_t *sig_size,
+    void *random_fn, void *random_fn_param)
+{
+    mbedtls_pk_context pk;
+    mbedtls_ecp_keypair *ec;
+    mbedtls_mpi r, s;
+    size_t half_size = 0;
+    int ret;
+
+    mbedtls_pk_init(&pk);
+    mbedtls_mpi_init(&r);
+    mbedtls_mpi_init(&s);
+
+    ret = mbedtls_pk_parse_key(&pk, key, key_size, NULL, 0);
+
+    if (ret == 0 && mbedtls_pk_get_type(&pk) != MBEDTLS_PK_ECKEY)
+    {
+        ret = MBEDTLS_ERR_PK_TYPE_MISMATCH;
+    }
+
+    if (ret == 0)
+    {
+        ec = mbedtls_pk_ec(pk);
+        half_size = (ec->grp.pbits + 7) / 8;
+        if (*sig_size < half_size * 2)
+        {
+            ret = MBEDTLS_ERR_PK_BAD_INPUT_DATA;
+        }
+    }
+
+    if (ret == 0)
+    {
+        ret = mbedtls_ecdsa_sign(
+            &ec->grp, &r, &s, &ec->d,
+            data, data_size,
+            random_fn, random_fn_param);
+    }
+
+    if (ret == 0)
+    {
+        ret = mbedtls_mpi_write_binary(&r, signature, half_size);
+    }
+
+    if (ret == 0)
+    {
+        ret = mbedtls_mpi_write_binary(&s, signature + half_size, half_size);
+    }
+
+    if (ret == 0)
+    {
+        *sig_size = half_size * 2;
+    }
+
+    mbedtls_mpi_free(&r);
+    mbedtls_mpi_free(&s);
+    mbedtls_pk_free(&pk);
+
+    return ret;
+}
@@ -0,0 +1,82 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use super::dhe_impl::f_rng;
+use super::ffi::spdm_ecdsa_sign;
+use core::ffi::c_void;
+use spdmlib::protocol::{SpdmBaseAsymAlgo, SpdmBaseHashAlgo, SpdmSignatureStruct};
+
+/// Sign `data` with a DER encoded (SEC1 or PKCS#8) EC private key.
+///
+/// ring has no P-521, so this lets the emulators and tests produce
+/// TPM_ALG_ECDSA_ECC_NIST_P521 signatures. P-256 and P-384 are accepted too.
+pub fn sign(
+    base_hash_algo: SpdmBaseHashAlgo,
+    base_asym_algo: SpdmBaseAsymAlgo,
+    private_key_der: &[u8],
+    data: &[u8],
+) -> Option<SpdmSignatureStruct> {
+    match base_asym_algo {
+        SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P256
+        | SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384
+        | SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P521 => {}
+        _ => return None,
+    }
+
+    let data_hash = (super::hash_impl::DEFAULT.hash_all_cb)(base_hash_algo, data)?;
+
+    let mut signature = SpdmSignatureStruct::default();
+    let mut signature_size = signature.data.len();
+    let ret = unsafe {
+        spdm_ecdsa_sign(
+            private_key_der.as_ptr(),
+            private_key_der.len(),
+            data_hash.data.as_ptr(),
+            data_hash.data_size as usize,
+            signature.data.as_mut_ptr(),
+            &mut signature_size,
+            f_rng as *const c_void,
+            core::ptr::null(),
+        )
+    };
+    if ret != 0 || signature_size != base_asym_algo.get_size() as usize {
+        return None;
+    }
+    signature.data_size = signature_size as u16;
+    Some(signature)
+}
+
+#[cfg(all(test,))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_case0_sign() {
+        let private_key_der = &include_bytes!("../../test_key/ecp521/end_responder.key.der")[..];
+        let public_cert_der =
+            &include_bytes!("../../test_key/ecp521/bundle_responder.certchain.der")[..];
+        let base_hash_algo = SpdmBaseHashAlgo::TPM_ALG_SHA_512;
+        let base_asym_algo = SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P521;
+        let data = &mut [0x10u8; 4096];
+
+        let signature = sign(base_hash_algo, base_asym_algo, private_key_der, data).unwrap();
+        assert_eq!(signature.data_size, base_asym_algo.get_size());
+        assert!((super::super::asym_verify_impl::DEFAULT.verify_cb)(
+            base_hash_algo,
+            base_asym_algo,
+            public_cert_der,
+            data,
+            &signature,
+        )
+        .is_ok());
+
+        assert!(sign(
+            base_hash_algo,
+            SpdmBaseAsymAlgo::TPM_ALG_RSASSA_2048,
+            private_key_der,
+            data
+        )
+        .is_none());
+    }
+}
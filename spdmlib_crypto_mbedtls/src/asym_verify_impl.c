@@ -102,3 +102,59 @@ int spdm_rsa_pss_verify(
 
     return ret;
 }
+
+/**
+ * Verifies RSASSA, RSA-PSS and Ecdsa signature with a public key.
+ *
+ * @param[in]  md_type          Hash algorithm used.
+ * @param[in]  public_key       DER encoded SubjectPublicKeyInfo.
+ * @param[in]  public_key_size  Public key size in bytes.
+ * @param[in]  data             Pointer to octet data to be checked (hash).
+ * @param[in]  data_size        Size of the data in bytes.
+ * @param[in]  signature        Pointer to signature to be verified.
+ * @param[in]  sig_size         Size of signature in bytes.
+ * @param[in]  is_rsa_pss       Non zero for RSA-PSS signature.
+ *
+ * @retval  0       Valid signature.
+ * @retval  not 0   Invalid signature.
+ *
+ **/
+int spdm_pk_verify_public_key(
+    const int md_type,
+    const uint8_t *public_key, size_t public_key_size,
+    const uint8_t *data, size_t data_size,
+    const uint8_t *signature, size_t signature_size,
+    const int is_rsa_pss)
+{
+    mbedtls_pk_context pk;
+    mbedtls_rsa_context *rsa_context;
+    int ret;
+
+    mbedtls_pk_init(&pk);
+
+    ret = mbedtls_pk_parse_public_key(&pk, public_key, public_key_size);
+
+    if (ret == 0 && is_rsa_pss)
+    {
+        rsa_context = mbedtls_pk_rsa(pk);
+        if (rsa_context == NULL)
+        {
+            ret = MBEDTLS_ERR_PK_INVALID_PUBKEY;
+        }
+        else
+        {
+            ret = mbedtls_rsa_rsassa_pss_verify(
+                rsa_context, NULL, NULL,
+                MBEDTLS_RSA_PUBLIC,
+                md_type, data_size, data, signature);
+        }
+    }
+    else if (ret == 0)
+    {
+        ret = mbedtls_pk_verify(&pk, md_type, data, data_size, signature, signature_size);
+    }
+
+    mbedtls_pk_free(&pk);
+
+    return ret;
+}
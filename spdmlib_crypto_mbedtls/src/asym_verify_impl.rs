@@ -3,18 +3,21 @@
 // SPDX-License-Identifier: BSD-2-Clause-Patent
 
 use spdmlib::crypto::SpdmAsymVerify;
-use spdmlib::error::{SpdmResult, SPDM_STATUS_CRYPTO_ERROR};
-use spdmlib::protocol::{SpdmBaseAsymAlgo, SpdmBaseHashAlgo, SpdmSignatureStruct};
+use spdmlib::error::{SpdmResult, SPDM_STATUS_CRYPTO_ERROR, SPDM_STATUS_UNSUPPORTED_CAP};
+use spdmlib::protocol::{SpdmBaseAsymAlgo, SpdmBaseHashAlgo, SpdmPqcAsymAlgo, SpdmSignatureStruct};
 
 pub static DEFAULT: SpdmAsymVerify = SpdmAsymVerify {
     verify_cb: asym_verify,
+    verify_with_public_key_cb: asym_verify_with_public_key,
+    pqc_verify_cb: pqc_asym_verify,
 };
 
 use core::ffi::c_int;
 
 const MBEDTLS_MD_SHA256: c_int = 6;
 const MBEDTLS_MD_SHA384: c_int = 7;
-use super::ffi::{spdm_pk_verify, spdm_rsa_pss_verify};
+const MBEDTLS_MD_SHA512: c_int = 8;
+use super::ffi::{spdm_pk_verify, spdm_pk_verify_public_key, spdm_rsa_pss_verify};
 
 fn asym_verify(
     base_hash_algo: SpdmBaseHashAlgo,
@@ -28,6 +31,7 @@ fn asym_verify(
     }
 
     let mbedtls_hash_algo = match base_hash_algo {
+        SpdmBaseHashAlgo::TPM_ALG_SHA_512 => MBEDTLS_MD_SHA512,
         SpdmBaseHashAlgo::TPM_ALG_SHA_384 => MBEDTLS_MD_SHA384,
         SpdmBaseHashAlgo::TPM_ALG_SHA_256 => MBEDTLS_MD_SHA256,
         _ => {
@@ -36,11 +40,12 @@ fn asym_verify(
     };
 
     // DER has this format: 0x30 size 0x02 r_size 0x00 [r_size] 0x02 s_size 0x00 [s_size]
-    let mut der_signature = [0u8; spdmlib::protocol::ECDSA_ECC_NIST_P384_KEY_SIZE + 8];
+    let mut der_signature = [0u8; spdmlib::protocol::ECDSA_ECC_NIST_P521_KEY_SIZE + 9];
 
     let signature = match base_asym_algo {
         SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P256
-        | SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384 => {
+        | SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384
+        | SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P521 => {
             let der_sign_size = ecc_signature_bin_to_der(signature.as_ref(), &mut der_signature)?;
             &der_signature[0..der_sign_size]
         }
@@ -64,6 +69,7 @@ fn asym_verify(
     let ret = match base_asym_algo {
         SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P256
         | SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384
+        | SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P521
         | SpdmBaseAsymAlgo::TPM_ALG_RSASSA_2048
         | SpdmBaseAsymAlgo::TPM_ALG_RSASSA_3072
         | SpdmBaseAsymAlgo::TPM_ALG_RSASSA_4096 => unsafe {
@@ -100,6 +106,75 @@ fn asym_verify(
     }
 }
 
+fn asym_verify_with_public_key(
+    base_hash_algo: SpdmBaseHashAlgo,
+    base_asym_algo: SpdmBaseAsymAlgo,
+    public_key_der: &[u8],
+    data: &[u8],
+    signature: &SpdmSignatureStruct,
+) -> SpdmResult {
+    if signature.data_size != base_asym_algo.get_size() {
+        return Err(SPDM_STATUS_CRYPTO_ERROR);
+    }
+
+    let mbedtls_hash_algo = match base_hash_algo {
+        SpdmBaseHashAlgo::TPM_ALG_SHA_512 => MBEDTLS_MD_SHA512,
+        SpdmBaseHashAlgo::TPM_ALG_SHA_384 => MBEDTLS_MD_SHA384,
+        SpdmBaseHashAlgo::TPM_ALG_SHA_256 => MBEDTLS_MD_SHA256,
+        _ => {
+            return Err(SPDM_STATUS_CRYPTO_ERROR);
+        }
+    };
+
+    let mut der_signature = [0u8; spdmlib::protocol::ECDSA_ECC_NIST_P521_KEY_SIZE + 9];
+    let (signature, is_rsa_pss) = match base_asym_algo {
+        SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P256
+        | SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384
+        | SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P521 => {
+            let der_sign_size = ecc_signature_bin_to_der(signature.as_ref(), &mut der_signature)?;
+            (&der_signature[0..der_sign_size], 0)
+        }
+        SpdmBaseAsymAlgo::TPM_ALG_RSASSA_2048
+        | SpdmBaseAsymAlgo::TPM_ALG_RSASSA_3072
+        | SpdmBaseAsymAlgo::TPM_ALG_RSASSA_4096 => (signature.as_ref(), 0),
+        SpdmBaseAsymAlgo::TPM_ALG_RSAPSS_2048
+        | SpdmBaseAsymAlgo::TPM_ALG_RSAPSS_3072
+        | SpdmBaseAsymAlgo::TPM_ALG_RSAPSS_4096 => (signature.as_ref(), 1),
+        _ => {
+            return Err(SPDM_STATUS_CRYPTO_ERROR);
+        }
+    };
+
+    let data_hash = (super::hash_impl::DEFAULT.hash_all_cb)(base_hash_algo, data).unwrap();
+
+    let ret = unsafe {
+        spdm_pk_verify_public_key(
+            mbedtls_hash_algo,
+            public_key_der.as_ptr(),
+            public_key_der.len(),
+            data_hash.data.as_ptr(),
+            data_hash.data_size as usize,
+            signature.as_ptr(),
+            signature.len(),
+            is_rsa_pss,
+        )
+    };
+    match ret {
+        0 => Ok(()),
+        _ => Err(SPDM_STATUS_CRYPTO_ERROR),
+    }
+}
+
+fn pqc_asym_verify(
+    _base_hash_algo: SpdmBaseHashAlgo,
+    _pqc_asym_algo: SpdmPqcAsymAlgo,
+    _public_cert_der: &[u8],
+    _data: &[u8],
+    _signature: &SpdmSignatureStruct,
+) -> SpdmResult {
+    Err(SPDM_STATUS_UNSUPPORTED_CAP)
+}
+
 // add ASN.1 for the ECDSA binary signature
 fn ecc_signature_bin_to_der(signature: &[u8], der_signature: &mut [u8]) -> SpdmResult<usize> {
     let sign_size = signature.len();
@@ -131,37 +206,43 @@ fn ecc_signature_bin_to_der(signature: &[u8], der_signature: &mut [u8]) -> SpdmR
     let der_r_size = if r[0] < 0x80 { r_size } else { r_size + 1 };
     let der_s_size = if s[0] < 0x80 { s_size } else { s_size + 1 };
     // der_sign_size includes: 0x30 _ 0x02 _ [der_r_size] 0x02 _ [der_s_size]
-    let der_sign_size = der_r_size + der_s_size + 6;
+    // P-521 signatures need the long form (0x81 _) for the sequence length.
+    let der_seq_size = der_r_size + der_s_size + 4;
+    let der_header_size = if der_seq_size < 0x80 { 2 } else { 3 };
+    let der_sign_size = der_seq_size + der_header_size;
 
     if der_signature.len() < der_sign_size {
         return Err(SPDM_STATUS_CRYPTO_ERROR);
     }
 
-    if der_r_size > u8::MAX as usize
-        || der_s_size > u8::MAX as usize
-        || der_sign_size > u8::MAX as usize
-    {
+    if der_r_size >= 0x80 || der_s_size >= 0x80 || der_seq_size > u8::MAX as usize {
         return Err(SPDM_STATUS_CRYPTO_ERROR);
     }
 
     der_signature[0] = 0x30u8;
-    der_signature[1] = (der_sign_size - 2) as u8;
-    der_signature[2] = 0x02u8;
-    der_signature[3] = der_r_size as u8;
+    if der_header_size == 2 {
+        der_signature[1] = der_seq_size as u8;
+    } else {
+        der_signature[1] = 0x81u8;
+        der_signature[2] = der_seq_size as u8;
+    }
+    let der_signature = &mut der_signature[der_header_size..];
+    der_signature[0] = 0x02u8;
+    der_signature[1] = der_r_size as u8;
     if r[0] < 0x80 {
-        der_signature[4..(4 + r_size)].copy_from_slice(r);
+        der_signature[2..(2 + r_size)].copy_from_slice(r);
     } else {
-        der_signature[4] = 0u8;
-        der_signature[5..(5 + r_size)].copy_from_slice(r);
+        der_signature[2] = 0u8;
+        der_signature[3..(3 + r_size)].copy_from_slice(r);
     }
-    der_signature[4 + der_r_size] = 0x02u8;
-    der_signature[5 + der_r_size] = der_s_size as u8;
+    der_signature[2 + der_r_size] = 0x02u8;
+    der_signature[3 + der_r_size] = der_s_size as u8;
 
     if s[0] < 0x80 {
-        der_signature[(6 + der_r_size)..(6 + der_r_size + s_size)].copy_from_slice(s);
+        der_signature[(4 + der_r_size)..(4 + der_r_size + s_size)].copy_from_slice(s);
     } else {
-        der_signature[6 + der_r_size] = 0u8;
-        der_signature[(7 + der_r_size)..(7 + der_r_size + s_size)].copy_from_slice(s);
+        der_signature[4 + der_r_size] = 0u8;
+        der_signature[(5 + der_r_size)..(5 + der_r_size + s_size)].copy_from_slice(s);
     }
 
     Ok(der_sign_size)
@@ -211,6 +292,17 @@ mod tests {
         ecc_signature_bin_to_der(signature, der_signature).unwrap();
     }
     #[test]
+    fn test_case4_ecc_signature_bin_to_der() {
+        let signature = &mut [0xffu8; spdmlib::protocol::ECDSA_ECC_NIST_P521_KEY_SIZE];
+        signature[0] = 0x01;
+        signature[66] = 0x01;
+        let der_signature = &mut [0u8; spdmlib::protocol::ECDSA_ECC_NIST_P521_KEY_SIZE + 9];
+
+        let der_sign_size = ecc_signature_bin_to_der(signature, der_signature).unwrap();
+        assert_eq!(der_sign_size, 139);
+        assert_eq!(&der_signature[0..5], &[0x30, 0x81, 136, 0x02, 66]);
+    }
+    #[test]
     fn test_case0_asym_verify() {
         let base_hash_algo = SpdmBaseHashAlgo::TPM_ALG_SHA_256;
         let base_asym_algo = SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P256;
@@ -5,6 +5,14 @@
 #include <mbedtls/x509.h>
 #include <mbedtls/x509_crt.h>
 #include <mbedtls/asn1.h>
+#include <mbedtls/oid.h>
+
+#define SPDM_MAX_CERT_COUNT 16
+
+/* id-DMTF-eku-responder-auth 1.3.6.1.4.1.412.274.3.1 */
+static const char spdm_oid_dmtf_responder_auth[] = "\x2b\x06\x01\x04\x01\x83\x1c\x82\x12\x03\x01";
+/* id-DMTF-eku-requester-auth 1.3.6.1.4.1.412.274.3.2 */
+static const char spdm_oid_dmtf_requester_auth[] = "\x2b\x06\x01\x04\x01\x83\x1c\x82\x12\x03\x02";
 
 /**
  * Verify X509 certificate chains
@@ -15,72 +23,89 @@
  *                                      subsequent cerificate is signed by the preceding
  *                                      cerificate.
  * @param[in]      cert_chain_length    Total length of the certificate chain, in bytes.
- * 
- * @retval  0       All cerificates was issued by the first certificate in X509Certchain.
- * @retval  1       Invalid certificate or the certificate was not issued by the given
+ * @param[in]      is_requester_cert    The leaf is a requester certificate, otherwise a
+ *                                      responder certificate.
+ * @param[in]      is_partial_chain     The chain ends at the device certificate of the
+ *                                      alias certificate model, whose EKU is not checked.
+ *
+ * @retval  0       All cerificates was issued by the first certificate in X509Certchain,
+ *                  are valid now and the leaf allows serverAuth or the DMTF EKU of its role.
+ * @retval  other   Invalid certificate or the certificate was not issued by the given
  *                  first CA cert.
  **/
-int spdm_verify_cert_chain(char *certchain, size_t certchain_size)
+int spdm_verify_cert_chain(char *certchain, size_t certchain_size,
+                           int is_requester_cert, int is_partial_chain)
 {
     int ret;
     mbedtls_x509_crt crt, ca;
-    unsigned char *p, *end, *pcert;
-    size_t len;
+    unsigned char *certs[SPDM_MAX_CERT_COUNT];
+    size_t cert_sizes[SPDM_MAX_CERT_COUNT];
+    size_t cert_count, index, len;
+    unsigned char *p, *end;
+    const char *role_oid;
     uint32_t flags;
 
-    ret = 0;
+    /*
+     * Certificate  ::=  SEQUENCE  {
+     *      tbsCertificate       TBSCertificate,
+     *      signatureAlgorithm   AlgorithmIdentifier,
+     *      signatureValue       BIT STRING  }
+     */
+    cert_count = 0;
+    p = (unsigned char *)certchain;
+    end = (unsigned char *)certchain + certchain_size;
+    while (p < end)
+    {
+        if (cert_count == SPDM_MAX_CERT_COUNT)
+        {
+            return -1;
+        }
+        certs[cert_count] = p;
+        ret = mbedtls_asn1_get_tag(&p, end, &len,
+                                   MBEDTLS_ASN1_CONSTRUCTED | MBEDTLS_ASN1_SEQUENCE);
+        if (ret != 0)
+        {
+            return ret;
+        }
+        p += len;
+        cert_sizes[cert_count] = p - certs[cert_count];
+        cert_count++;
+    }
+    if (cert_count == 0)
+    {
+        return -1;
+    }
+
     mbedtls_x509_crt_init(&crt);
     mbedtls_x509_crt_init(&ca);
 
-    // Use the certchain_size until we figure out the actual length.
-    pcert = p = (unsigned char *)certchain;
-    end = (unsigned char *)certchain + certchain_size;
+    /* The first certificate is the trust anchor. */
+    ret = mbedtls_x509_crt_parse_der_nocopy(&ca, certs[0], cert_sizes[0]);
 
-    // Parse CA cert in certchain.
-    if (ret == 0)
+    /* The leaf comes first, followed by its issuers up to the root. */
+    for (index = cert_count; ret == 0 && index > 0; index--)
     {
-        ret = mbedtls_x509_crt_parse_der_nocopy(&ca, pcert, end - pcert);
+        ret = mbedtls_x509_crt_parse_der_nocopy(&crt, certs[index - 1], cert_sizes[index - 1]);
     }
+
+    /* Verify the signatures, the validity periods and the CA constraints of the issuers. */
     if (ret == 0)
     {
-        p = pcert;
-        ret = mbedtls_asn1_get_tag((unsigned char **)&p, end, &len,
-                                   MBEDTLS_ASN1_CONSTRUCTED | MBEDTLS_ASN1_SEQUENCE);
+        ret = mbedtls_x509_crt_verify(&crt, &ca, NULL, NULL, &flags, NULL, NULL);
     }
 
-    /* 
-     * Parse certchain include CA cert.
-     */
-    p = pcert;
-    len = 0;
-
-    /* parse certs in certchain.
-     * pcert point to next certificate.
-     */
-    while (ret == 0 && (pcert = p + len) < end)
+    /* A leaf without the extension may be used for any purpose. */
+    if (ret == 0 && !is_partial_chain)
     {
-        /*
-         * Certificate  ::=  SEQUENCE  {
-         *      tbsCertificate       TBSCertificate,
-         *      signatureAlgorithm   AlgorithmIdentifier,
-         *      signatureValue       BIT STRING  }
-         */
-        if (ret == 0)
-        {
-            ret = mbedtls_x509_crt_parse_der_nocopy(&crt, pcert, end - pcert);
-        }
-
-        if (ret == 0)
+        role_oid = is_requester_cert ? spdm_oid_dmtf_requester_auth : spdm_oid_dmtf_responder_auth;
+        if (mbedtls_x509_crt_check_extended_key_usage(
+                &crt, MBEDTLS_OID_SERVER_AUTH, MBEDTLS_OID_SIZE(MBEDTLS_OID_SERVER_AUTH)) != 0 &&
+            mbedtls_x509_crt_check_extended_key_usage(
+                &crt, role_oid, sizeof(spdm_oid_dmtf_responder_auth) - 1) != 0)
         {
-            p = pcert;
-            ret = mbedtls_asn1_get_tag((unsigned char **)&p, end, &len,
-                                       MBEDTLS_ASN1_CONSTRUCTED | MBEDTLS_ASN1_SEQUENCE);
+            ret = MBEDTLS_ERR_X509_CERT_VERIFY_FAILED;
         }
-    };
-
-    /* Verify certificate chains.
-     */
-    ret = mbedtls_x509_crt_verify(&crt, &ca, NULL, NULL, &flags, NULL, NULL);
+    }
 
     mbedtls_x509_crt_free(&crt);
     mbedtls_x509_crt_free(&ca);
@@ -131,7 +156,7 @@ int main(int argc, char **argv)
 
     if (certchain_buffer_size > 0)
     {
-        ret = spdm_verify_cert_chain(certchain_buffer, certchain_buffer_size);
+        ret = spdm_verify_cert_chain(certchain_buffer, certchain_buffer_size, 0, 0);
         assert(ret == 0);
     }
 }
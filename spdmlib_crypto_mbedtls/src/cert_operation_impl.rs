@@ -4,7 +4,8 @@
 
 extern crate alloc;
 
-use spdmlib::crypto::SpdmCertOperation;
+use core::ffi::c_int;
+use spdmlib::crypto::{is_ca_certificate, SpdmCertOperation};
 use spdmlib::error::{SpdmResult, SPDM_STATUS_INVALID_CERT};
 
 pub static DEFAULT: SpdmCertOperation = SpdmCertOperation {
@@ -43,8 +44,27 @@ fn get_cert_from_cert_chain(cert_chain: &[u8], index: isize) -> SpdmResult<(usiz
     }
 }
 
-fn verify_cert_chain(cert_chain: &[u8]) -> SpdmResult {
-    let ret = unsafe { spdm_verify_cert_chain(cert_chain.as_ptr(), cert_chain.len()) };
+// Only in the alias certificate model may the chain be partial, ending at the
+// device certificate, a CA whose EKU is not checked.
+fn verify_cert_chain(
+    cert_chain: &[u8],
+    is_requester_cert: bool,
+    is_alias_cert_model: bool,
+) -> SpdmResult {
+    let (leaf_begin, _) = get_cert_from_cert_chain(cert_chain, -1)?;
+    let is_partial_chain = is_ca_certificate(&cert_chain[leaf_begin..])?;
+    if is_partial_chain && !is_alias_cert_model {
+        return Err(SPDM_STATUS_INVALID_CERT);
+    }
+
+    let ret = unsafe {
+        spdm_verify_cert_chain(
+            cert_chain.as_ptr(),
+            cert_chain.len(),
+            is_requester_cert as c_int,
+            is_partial_chain as c_int,
+        )
+    };
     if ret == 0 {
         Ok(())
     } else {
@@ -95,7 +115,7 @@ mod tests {
         let status = get_cert_from_cert_chain(cert_chain, -1).is_ok();
         assert!(status);
 
-        let status = verify_cert_chain(cert_chain).is_ok();
+        let status = verify_cert_chain(cert_chain, false, false).is_ok();
         assert!(status);
     }
 }
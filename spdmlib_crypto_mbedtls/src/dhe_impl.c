@@ -1,8 +1,9 @@
 /** @file
- * ECDH Wrapper Implementation.
+ * ECDH and FFDHE Wrapper Implementation.
  **/
 
 #include <mbedtls/ecdh.h>
+#include <mbedtls/dhm.h>
 
 /**
  * Generates EC private key and EC public key (X, Y).
@@ -136,7 +137,8 @@ int spdm_ecdh_compute_shared(
     if (ret == 0)
     {
 
-        size_t bufferlen = mbedtls_mpi_size(&shared_key);
+        /* The shared secret is the X coordinate, padded to the field size. */
+        size_t bufferlen = (grp.pbits + 7) / 8;
         if (*out_len < bufferlen)
         {
             return MBEDTLS_ERR_MPI_BUFFER_TOO_SMALL;
@@ -180,6 +182,17 @@ int spdm_ecdh_gen_public_p384(
         random_fn, random_fn_param);
 }
 
+int spdm_ecdh_gen_public_p521(
+    unsigned char *pubkey, size_t *publen,
+    unsigned char *prikey, size_t *prilen,
+    void *random_fn, void *random_fn_param)
+{
+    return spdm_ecdh_gen_public(
+        MBEDTLS_ECP_DP_SECP521R1,
+        pubkey, publen, prikey, prilen,
+        random_fn, random_fn_param);
+}
+
 int spdm_ecdh_compute_shared_p256(
     unsigned char *prikey,
     size_t prilen,
@@ -214,6 +227,218 @@ int spdm_ecdh_compute_shared_p384(
         random_fn, random_fn_param);
 }
 
+int spdm_ecdh_compute_shared_p521(
+    unsigned char *prikey,
+    size_t prilen,
+    unsigned char *peer_pubkey,
+    size_t peer_pubkey_len,
+    unsigned char *out_buffer,
+    size_t *out_len,
+    void *random_fn, void *random_fn_param)
+{
+    return spdm_ecdh_compute_shared(
+        MBEDTLS_ECP_DP_SECP521R1,
+        prikey, prilen,
+        peer_pubkey, peer_pubkey_len,
+        out_buffer, out_len,
+        random_fn, random_fn_param);
+}
+
+/**
+ * Loads the RFC 7919 FFDHE group of the given size.
+ *
+ * @param[in, out]  ctx          DHM context.
+ * @param[in]       group_size   Size of the prime in bytes, 256, 384 or 512.
+ *
+ * @retval 0          group loaded.
+ * @retval not 0      unsupported group size.
+ *
+ **/
+static int spdm_ffdhe_set_group(mbedtls_dhm_context *ctx, size_t group_size)
+{
+    static const unsigned char ffdhe2048_p[] = MBEDTLS_DHM_RFC7919_FFDHE2048_P_BIN;
+    static const unsigned char ffdhe2048_g[] = MBEDTLS_DHM_RFC7919_FFDHE2048_G_BIN;
+    static const unsigned char ffdhe3072_p[] = MBEDTLS_DHM_RFC7919_FFDHE3072_P_BIN;
+    static const unsigned char ffdhe3072_g[] = MBEDTLS_DHM_RFC7919_FFDHE3072_G_BIN;
+    static const unsigned char ffdhe4096_p[] = MBEDTLS_DHM_RFC7919_FFDHE4096_P_BIN;
+    static const unsigned char ffdhe4096_g[] = MBEDTLS_DHM_RFC7919_FFDHE4096_G_BIN;
+
+    const unsigned char *p;
+    const unsigned char *g;
+    size_t g_len;
+    mbedtls_mpi P, G;
+    int ret;
+
+    switch (group_size)
+    {
+    case sizeof(ffdhe2048_p):
+        p = ffdhe2048_p;
+        g = ffdhe2048_g;
+        g_len = sizeof(ffdhe2048_g);
+        break;
+    case sizeof(ffdhe3072_p):
+        p = ffdhe3072_p;
+        g = ffdhe3072_g;
+        g_len = sizeof(ffdhe3072_g);
+        break;
+    case sizeof(ffdhe4096_p):
+        p = ffdhe4096_p;
+        g = ffdhe4096_g;
+        g_len = sizeof(ffdhe4096_g);
+        break;
+    default:
+        return MBEDTLS_ERR_DHM_BAD_INPUT_DATA;
+    }
+
+    mbedtls_mpi_init(&P);
+    mbedtls_mpi_init(&G);
+    ret = mbedtls_mpi_read_binary(&P, p, group_size);
+    if (ret == 0)
+    {
+        ret = mbedtls_mpi_read_binary(&G, g, g_len);
+    }
+    if (ret == 0)
+    {
+        ret = mbedtls_dhm_set_group(ctx, &P, &G);
+    }
+    mbedtls_mpi_free(&P);
+    mbedtls_mpi_free(&G);
+    return ret;
+}
+
+/**
+ * Generates FFDHE private key and public key.
+ *
+ * @param[in]       group_size         Size of the prime in bytes, 256, 384 or 512.
+ * @param[out]      pubkey             Pointer to the buffer to receive the public key,
+ *                                     padded to group_size bytes.
+ * @param[in,out]   publen             Max pubkey buffer len for input.
+ *                                     Actual pubkey len for output.
+ * @param[out]      prikey             Pointer to prikey buffer to receive generated private key.
+ * @param[in,out]   prilen             Max prikey buffer len for input.
+ *                                     Actual prikey len for output.
+ * @param[in]       random_fn          The RNG function.
+ * @param[in]       random_fn_param    RNG function context pass to random_fn.
+ *
+ * @retval 0          FFDHE key generation succeeded.
+ * @retval not 0      FFDHE key generation failed.
+ *
+ **/
+int spdm_ffdhe_gen_public(
+    size_t group_size,
+    unsigned char *pubkey, size_t *publen,
+    unsigned char *prikey, size_t *prilen,
+    void *random_fn, void *random_fn_param)
+{
+    mbedtls_dhm_context ctx;
+    int ret;
+
+    if (*publen < group_size || *prilen < group_size)
+    {
+        return MBEDTLS_ERR_DHM_BAD_INPUT_DATA;
+    }
+
+    mbedtls_dhm_init(&ctx);
+    ret = spdm_ffdhe_set_group(&ctx, group_size);
+
+    if (ret == 0)
+    {
+        ret = mbedtls_dhm_make_public(
+            &ctx, (int)group_size, pubkey, group_size,
+            random_fn, random_fn_param);
+    }
+
+    if (ret == 0)
+    {
+        size_t outlen = mbedtls_mpi_size(&ctx.X);
+        ret = mbedtls_mpi_write_binary(&ctx.X, prikey, outlen);
+        if (ret == 0)
+        {
+            *publen = group_size;
+            *prilen = outlen;
+        }
+    }
+
+    mbedtls_dhm_free(&ctx);
+    return ret;
+}
+
+/**
+ * Computes FFDHE exchanged common key.
+ *
+ * @param[in]       group_size         Size of the prime in bytes, 256, 384 or 512.
+ * @param[in]       prikey             Private key.
+ * @param[in]       prilen             Private key len.
+ * @param[in]       peer_pubkey        Pointer to the peer's public key.
+ * @param[in]       peer_pubkey_len    Size of peer's public key in bytes.
+ * @param[out]      out_buffer         Pointer to the buffer to receive the shared secret,
+ *                                     padded to group_size bytes.
+ * @param[in, out]  out_len            On input, the size of key buffer in bytes.
+ *                                     On output, the size of data returned in key buffer in bytes.
+ * @param[in]       random_fn          The RNG function, used for blinding.
+ * @param[in]       random_fn_param    RNG function context pass to random_fn.
+ *
+ * @retval 0          FFDHE exchanged key generation succeeded.
+ * @retval not 0      FFDHE exchanged key generation failed.
+ *
+ **/
+int spdm_ffdhe_compute_shared(
+    size_t group_size,
+    unsigned char *prikey, size_t prilen,
+    unsigned char *peer_pubkey, size_t peer_pubkey_len,
+    unsigned char *out_buffer, size_t *out_len,
+    void *random_fn, void *random_fn_param)
+{
+    mbedtls_dhm_context ctx;
+    mbedtls_mpi K;
+    int ret;
+
+    if (*out_len < group_size || peer_pubkey_len != group_size)
+    {
+        return MBEDTLS_ERR_DHM_BAD_INPUT_DATA;
+    }
+
+    mbedtls_dhm_init(&ctx);
+    mbedtls_mpi_init(&K);
+    ret = spdm_ffdhe_set_group(&ctx, group_size);
+
+    if (ret == 0)
+    {
+        ret = mbedtls_mpi_read_binary(&ctx.X, prikey, prilen);
+    }
+
+    if (ret == 0)
+    {
+        ret = mbedtls_dhm_read_public(&ctx, peer_pubkey, peer_pubkey_len);
+    }
+
+    if (ret == 0)
+    {
+        size_t olen = 0;
+        ret = mbedtls_dhm_calc_secret(
+            &ctx, out_buffer, *out_len, &olen,
+            random_fn, random_fn_param);
+        if (ret == 0)
+        {
+            // The shared secret is padded to the size of the prime.
+            ret = mbedtls_mpi_read_binary(&K, out_buffer, olen);
+        }
+    }
+
+    if (ret == 0)
+    {
+        ret = mbedtls_mpi_write_binary(&K, out_buffer, group_size);
+        if (ret == 0)
+        {
+            *out_len = group_size;
+        }
+    }
+
+    mbedtls_mpi_free(&K);
+    mbedtls_dhm_free(&ctx);
+    return ret;
+}
+
 #if SELF_DEBUG
 #include <stdio.h>
 #include <stdlib.h>
@@ -6,17 +6,18 @@ extern crate alloc;
 use alloc::boxed::Box;
 
 use super::ffi::{
-    spdm_ecdh_compute_shared_p256, spdm_ecdh_compute_shared_p384, spdm_ecdh_gen_public_p256,
-    spdm_ecdh_gen_public_p384,
+    spdm_ecdh_compute_shared_p256, spdm_ecdh_compute_shared_p384, spdm_ecdh_compute_shared_p521,
+    spdm_ecdh_gen_public_p256, spdm_ecdh_gen_public_p384, spdm_ecdh_gen_public_p521,
+    spdm_ffdhe_compute_shared, spdm_ffdhe_gen_public,
 };
 use core::ffi::{c_int, c_uchar, c_void};
 use spdmlib::crypto::{SpdmDhe, SpdmDheKeyExchange};
 use spdmlib::protocol::{
-    SpdmDheAlgo, SpdmDheExchangeStruct, SpdmDheFinalKeyStruct, ECDSA_ECC_NIST_P384_KEY_SIZE,
+    SpdmDheAlgo, SpdmDheExchangeStruct, SpdmDheFinalKeyStruct, SpdmKemAlgo, SPDM_MAX_DHE_KEY_SIZE,
 };
 use zeroize::ZeroizeOnDrop;
 
-const MAX_KEY_LEN: usize = ECDSA_ECC_NIST_P384_KEY_SIZE + 1;
+const MAX_KEY_LEN: usize = SPDM_MAX_DHE_KEY_SIZE + 1;
 #[derive(ZeroizeOnDrop)]
 struct MbedTlsDheExchangeStruct {
     data_size: usize,
@@ -64,6 +65,8 @@ impl AsRef<[u8]> for MbedTlsDheExchangeStruct {
 
 pub static DEFAULT: SpdmDhe = SpdmDhe {
     generate_key_pair_cb: generate_key_pair,
+    kem_generate_key_pair_cb: kem_generate_key_pair,
+    kem_encapsulate_cb: kem_encapsulate,
 };
 
 fn generate_key_pair(
@@ -72,11 +75,28 @@ fn generate_key_pair(
     match dhe_algo {
         SpdmDheAlgo::SECP_256_R1 => SpdmDheKeyExchangeP256::generate_key_pair(),
         SpdmDheAlgo::SECP_384_R1 => SpdmDheKeyExchangeP384::generate_key_pair(),
+        SpdmDheAlgo::SECP_521_R1 => SpdmDheKeyExchangeP521::generate_key_pair(),
+        SpdmDheAlgo::FFDHE_2048 | SpdmDheAlgo::FFDHE_3072 | SpdmDheAlgo::FFDHE_4096 => {
+            SpdmDheKeyExchangeFfdhe::generate_key_pair(dhe_algo)
+        }
         _ => None,
     }
 }
 
-extern "C" fn f_rng(_rng_state: *mut c_void, output: *mut c_uchar, len: usize) -> c_int {
+fn kem_generate_key_pair(
+    _kem_algo: SpdmKemAlgo,
+) -> Option<(SpdmDheExchangeStruct, Box<dyn SpdmDheKeyExchange>)> {
+    None
+}
+
+fn kem_encapsulate(
+    _kem_algo: SpdmKemAlgo,
+    _peer_encap_key: &SpdmDheExchangeStruct,
+) -> Option<(SpdmDheExchangeStruct, SpdmDheFinalKeyStruct)> {
+    None
+}
+
+pub(crate) extern "C" fn f_rng(_rng_state: *mut c_void, output: *mut c_uchar, len: usize) -> c_int {
     use core::arch::x86_64::_rdrand64_step;
     let mut remain = len;
     while remain > 8 {
@@ -238,13 +258,146 @@ impl SpdmDheKeyExchange for SpdmDheKeyExchangeP384 {
     }
 }
 
+struct SpdmDheKeyExchangeP521(EphemeralPrivateKey);
+
+impl SpdmDheKeyExchangeP521 {
+    fn generate_key_pair() -> Option<(SpdmDheExchangeStruct, Box<dyn SpdmDheKeyExchange>)> {
+        let mut private_key = EphemeralPrivateKey::default();
+        let mut public_key = MbedTlsDheExchangeStruct::default();
+        unsafe {
+            public_key.data_size = MAX_KEY_LEN;
+            private_key.key_len = MAX_KEY_LEN;
+            let ret = spdm_ecdh_gen_public_p521(
+                public_key.data.as_mut_ptr(),
+                &mut public_key.data_size,
+                private_key.key.as_mut_ptr(),
+                &mut private_key.key_len,
+                f_rng as *const c_void,
+                core::ptr::null(),
+            );
+            if ret == 0 {
+                // convert mbedtls public_key to spdm public key format
+                let public_key = public_key.into();
+                let res: Box<dyn SpdmDheKeyExchange> = Box::new(Self(private_key));
+                Some((public_key, res))
+            } else {
+                None
+            }
+        }
+    }
+}
+
+impl SpdmDheKeyExchange for SpdmDheKeyExchangeP521 {
+    fn compute_final_key(
+        self: Box<Self>,
+        peer_pub_key: &SpdmDheExchangeStruct,
+    ) -> Option<SpdmDheFinalKeyStruct> {
+        let peer_pub_key = peer_pub_key.clone();
+        let peer_pub_key = MbedTlsDheExchangeStruct::from(&peer_pub_key);
+        let mut final_key = SpdmDheFinalKeyStruct::default();
+        unsafe {
+            let mut final_key_size = MAX_KEY_LEN;
+            let res = spdm_ecdh_compute_shared_p521(
+                self.0.key.as_ptr(),
+                self.0.key_len,
+                peer_pub_key.data.as_ptr(),
+                peer_pub_key.data_size,
+                final_key.data.as_mut_ptr(),
+                &mut final_key_size,
+                f_rng as *const c_void,
+                core::ptr::null(),
+            );
+            if res == 0 {
+                final_key.data_size = final_key_size as u16;
+                Some(final_key)
+            } else {
+                None
+            }
+        }
+    }
+}
+
+// FFDHE public keys and shared secrets are big-endian, padded to the prime size.
+struct SpdmDheKeyExchangeFfdhe {
+    group_size: usize,
+    private_key: EphemeralPrivateKey,
+}
+
+impl SpdmDheKeyExchangeFfdhe {
+    fn generate_key_pair(
+        dhe_algo: SpdmDheAlgo,
+    ) -> Option<(SpdmDheExchangeStruct, Box<dyn SpdmDheKeyExchange>)> {
+        let group_size = dhe_algo.get_size() as usize;
+        let mut private_key = EphemeralPrivateKey::default();
+        let mut public_key = SpdmDheExchangeStruct::default();
+        unsafe {
+            let mut public_key_len = public_key.data.len();
+            private_key.key_len = MAX_KEY_LEN;
+            let ret = spdm_ffdhe_gen_public(
+                group_size,
+                public_key.data.as_mut_ptr(),
+                &mut public_key_len,
+                private_key.key.as_mut_ptr(),
+                &mut private_key.key_len,
+                f_rng as *const c_void,
+                core::ptr::null(),
+            );
+            if ret == 0 && public_key_len == group_size {
+                public_key.data_size = group_size as u16;
+                let res: Box<dyn SpdmDheKeyExchange> = Box::new(Self {
+                    group_size,
+                    private_key,
+                });
+                Some((public_key, res))
+            } else {
+                None
+            }
+        }
+    }
+}
+
+impl SpdmDheKeyExchange for SpdmDheKeyExchangeFfdhe {
+    fn compute_final_key(
+        self: Box<Self>,
+        peer_pub_key: &SpdmDheExchangeStruct,
+    ) -> Option<SpdmDheFinalKeyStruct> {
+        let mut final_key = SpdmDheFinalKeyStruct::default();
+        unsafe {
+            let mut final_key_size = final_key.data.len();
+            let res = spdm_ffdhe_compute_shared(
+                self.group_size,
+                self.private_key.key.as_ptr(),
+                self.private_key.key_len,
+                peer_pub_key.data.as_ptr(),
+                peer_pub_key.data_size as usize,
+                final_key.data.as_mut_ptr(),
+                &mut final_key_size,
+                f_rng as *const c_void,
+                core::ptr::null(),
+            );
+            if res == 0 {
+                final_key.data_size = final_key_size as u16;
+                Some(final_key)
+            } else {
+                None
+            }
+        }
+    }
+}
+
 #[cfg(all(test,))]
 mod tests {
     use super::*;
 
     #[test]
     fn test_case0_dhe() {
-        for dhe_algo in [SpdmDheAlgo::SECP_256_R1, SpdmDheAlgo::SECP_384_R1].iter() {
+        for dhe_algo in [
+            SpdmDheAlgo::SECP_256_R1,
+            SpdmDheAlgo::SECP_384_R1,
+            SpdmDheAlgo::SECP_521_R1,
+        ]
+        .iter()
+        {
             let (exchange1, private1) = generate_key_pair(*dhe_algo).unwrap();
             let (exchange2, private2) = generate_key_pair(*dhe_algo).unwrap();
 
@@ -260,4 +413,24 @@ mod tests {
             assert_eq!(generate_key_pair(*dhe_algo).is_none(), true);
         }
     }
+    #[test]
+    fn test_case2_dhe() {
+        for dhe_algo in [
+            SpdmDheAlgo::FFDHE_2048,
+            SpdmDheAlgo::FFDHE_3072,
+            SpdmDheAlgo::FFDHE_4096,
+        ]
+        .iter()
+        {
+            let (exchange1, private1) = generate_key_pair(*dhe_algo).unwrap();
+            let (exchange2, private2) = generate_key_pair(*dhe_algo).unwrap();
+            assert_eq!(exchange1.data_size, dhe_algo.get_size());
+
+            let peer1 = private1.compute_final_key(&exchange2).unwrap();
+            let peer2 = private2.compute_final_key(&exchange1).unwrap();
+
+            assert_eq!(peer1.data_size, dhe_algo.get_size());
+            assert_eq!(peer1.as_ref(), peer2.as_ref());
+        }
+    }
 }
@@ -85,7 +85,34 @@ extern "C" {
         signature_size: usize,
     ) -> c_int;
 
-    pub fn spdm_verify_cert_chain(certchain: *const c_uchar, certchain_size: usize) -> c_int;
+    pub fn spdm_pk_verify_public_key(
+        md_type: c_int,
+        public_key: *const c_uchar,
+        public_key_size: usize,
+        data: *const c_uchar,
+        data_size: usize,
+        signature: *const c_uchar,
+        signature_size: usize,
+        is_rsa_pss: c_int,
+    ) -> c_int;
+
+    pub fn spdm_ecdsa_sign(
+        key: *const c_uchar,
+        key_size: usize,
+        data: *const c_uchar,
+        data_size: usize,
+        signature: *mut c_uchar,
+        signature_size: *mut usize,
+        random_fn: *const c_void,
+        random_fn_param: *const c_void,
+    ) -> c_int;
+
+    pub fn spdm_verify_cert_chain(
+        certchain: *const c_uchar,
+        certchain_size: usize,
+        is_requester_cert: c_int,
+        is_partial_chain: c_int,
+    ) -> c_int;
 
     pub fn spdm_ecdh_compute_shared_p256(
         private_key: *const c_uchar,
@@ -127,6 +154,48 @@ extern "C" {
         random_fn_param: *const c_void,
     ) -> c_int;
 
+    pub fn spdm_ecdh_compute_shared_p521(
+        private_key: *const c_uchar,
+        private_key_len: usize,
+        peer_public_key: *const c_uchar,
+        peer_public_key_len: usize,
+        shared_key: *mut c_uchar,
+        shared_ken_len: *mut usize,
+        random_fn: *const c_void,
+        random_fn_param: *const c_void,
+    ) -> c_int;
+
+    pub fn spdm_ecdh_gen_public_p521(
+        public_key: *mut c_uchar,
+        public_key_len: *mut usize,
+        private_key: *mut c_uchar,
+        private_key_len: *mut usize,
+        random_fn: *const c_void,
+        random_fn_param: *const c_void,
+    ) -> c_int;
+
+    pub fn spdm_ffdhe_gen_public(
+        group_size: usize,
+        public_key: *mut c_uchar,
+        public_key_len: *mut usize,
+        private_key: *mut c_uchar,
+        private_key_len: *mut usize,
+        random_fn: *const c_void,
+        random_fn_param: *const c_void,
+    ) -> c_int;
+
+    pub fn spdm_ffdhe_compute_shared(
+        group_size: usize,
+        private_key: *const c_uchar,
+        private_key_len: usize,
+        peer_public_key: *const c_uchar,
+        peer_public_key_len: usize,
+        shared_key: *mut c_uchar,
+        shared_key_len: *mut usize,
+        random_fn: *const c_void,
+        random_fn_param: *const c_void,
+    ) -> c_int;
+
     pub fn mbedtls_sha256(
         data: *const c_uchar,
         data_len: usize,
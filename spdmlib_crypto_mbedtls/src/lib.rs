@@ -11,6 +11,7 @@ mod ffi;
 mod ffi_ext;
 
 pub mod aead_impl;
+pub mod asym_sign_impl;
 pub mod asym_verify_impl;
 pub mod cert_operation_impl;
 pub mod dhe_impl;
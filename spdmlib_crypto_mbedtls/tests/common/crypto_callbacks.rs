@@ -4,7 +4,7 @@
 
 #![allow(unused)]
 
-use spdmlib::secret::SpdmSecretAsymSign;
+use spdmlib::secret::{SpdmSecretAsymSign, SpdmSecretResult};
 
 use spdmlib::protocol::{
     SpdmBaseAsymAlgo, SpdmBaseHashAlgo, SpdmSignatureStruct, RSAPSS_2048_KEY_SIZE,
@@ -20,9 +20,10 @@ pub static SECRET_ASYM_IMPL_INSTANCE: SpdmSecretAsymSign =
 fn asym_sign(
     base_hash_algo: SpdmBaseHashAlgo,
     base_asym_algo: SpdmBaseAsymAlgo,
+    _key_pair_id: u8,
     data: &[u8],
-) -> Option<SpdmSignatureStruct> {
-    match (base_hash_algo, base_asym_algo) {
+) -> SpdmSecretResult<SpdmSignatureStruct> {
+    let signature = match (base_hash_algo, base_asym_algo) {
         (SpdmBaseHashAlgo::TPM_ALG_SHA_256, SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P256) => {
             sign_ecdsa_asym_algo(&ring::signature::ECDSA_P256_SHA256_FIXED_SIGNING, data)
         }
@@ -86,7 +87,8 @@ fn asym_sign(
         _ => {
             panic!();
         }
-    }
+    };
+    signature.into()
 }
 
 fn sign_ecdsa_asym_algo(
@@ -21,10 +21,11 @@ pub fn get_test_key_directory() -> PathBuf {
 /// Create requester config and provision info
 pub fn req_create_info() -> (common::SpdmConfigInfo, common::SpdmProvisionInfo) {
     let config_info = common::SpdmConfigInfo {
-        spdm_version: [
+        spdm_version: &[
             SpdmVersion::SpdmVersion10,
             SpdmVersion::SpdmVersion11,
             SpdmVersion::SpdmVersion12,
+            SpdmVersion::SpdmVersion13,
         ],
         req_capabilities: SpdmRequestCapabilityFlags::CERT_CAP
         | SpdmRequestCapabilityFlags::CHAL_CAP
@@ -82,6 +83,9 @@ pub fn req_create_info() -> (common::SpdmConfigInfo, common::SpdmProvisionInfo)
         my_cert_chain_data: [None, None, None, None, None, None, None, None],
         my_cert_chain: [None, None, None, None, None, None, None, None],
         peer_root_cert_data: Some(peer_root_cert_data),
+        my_public_key: None,
+        peer_public_key: None,
+        my_key_pair_id: [0u8; 8],
     };
 
     (config_info, provision_info)
@@ -89,10 +93,11 @@ pub fn req_create_info() -> (common::SpdmConfigInfo, common::SpdmProvisionInfo)
 
 pub fn rsp_create_info() -> (common::SpdmConfigInfo, common::SpdmProvisionInfo) {
     let config_info = common::SpdmConfigInfo {
-        spdm_version: [
+        spdm_version: &[
             SpdmVersion::SpdmVersion10,
             SpdmVersion::SpdmVersion11,
             SpdmVersion::SpdmVersion12,
+            SpdmVersion::SpdmVersion13,
         ],
         rsp_capabilities: SpdmResponseCapabilityFlags::CERT_CAP
         | SpdmResponseCapabilityFlags::CHAL_CAP
@@ -166,6 +171,9 @@ pub fn rsp_create_info() -> (common::SpdmConfigInfo, common::SpdmProvisionInfo)
         ],
         my_cert_chain: [None, None, None, None, None, None, None, None],
         peer_root_cert_data: None,
+        my_public_key: None,
+        peer_public_key: None,
+        my_key_pair_id: [0u8; 8],
     };
 
     (config_info, provision_info)
@@ -89,8 +89,10 @@ mod test_mbedtls {
         cert_chain: &[u8],
     ) {
         let data = &b"hello"[..];
-        let sig = (crypto_callbacks::SECRET_ASYM_IMPL_INSTANCE.sign_cb)(hash_algo, asym_algo, data)
-            .unwrap();
+        let sig =
+            (crypto_callbacks::SECRET_ASYM_IMPL_INSTANCE.sign_cb)(hash_algo, asym_algo, 0, data)
+                .ready()
+                .unwrap();
 
         spdmlib::crypto::asym_verify::verify(hash_algo, asym_algo, cert_chain, data, &sig).unwrap();
 
@@ -0,0 +1,149 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use openssl::symm::{decrypt_aead, encrypt_aead, Cipher};
+use spdmlib::crypto::SpdmAead;
+use spdmlib::error::{SpdmResult, SPDM_STATUS_CRYPTO_ERROR, SPDM_STATUS_INVALID_PARAMETER};
+use spdmlib::protocol::{SpdmAeadAlgo, SpdmAeadIvStruct, SpdmAeadKeyStruct};
+
+pub static DEFAULT: SpdmAead = SpdmAead {
+    encrypt_cb: encrypt,
+    decrypt_cb: decrypt,
+};
+
+fn get_cipher(
+    aead_algo: SpdmAeadAlgo,
+    key: &SpdmAeadKeyStruct,
+    iv: &SpdmAeadIvStruct,
+    tag: &[u8],
+) -> SpdmResult<Cipher> {
+    let cipher = match aead_algo {
+        SpdmAeadAlgo::AES_128_GCM => Cipher::aes_128_gcm(),
+        SpdmAeadAlgo::AES_256_GCM => Cipher::aes_256_gcm(),
+        SpdmAeadAlgo::CHACHA20_POLY1305 => Cipher::chacha20_poly1305(),
+        _ => return Err(SPDM_STATUS_INVALID_PARAMETER),
+    };
+    if key.data_size != aead_algo.get_key_size()
+        || iv.data_size != aead_algo.get_iv_size()
+        || tag.len() != aead_algo.get_tag_size() as usize
+    {
+        return Err(SPDM_STATUS_INVALID_PARAMETER);
+    }
+    Ok(cipher)
+}
+
+fn encrypt(
+    aead_algo: SpdmAeadAlgo,
+    key: &SpdmAeadKeyStruct,
+    iv: &SpdmAeadIvStruct,
+    aad: &[u8],
+    plain_text: &[u8],
+    tag: &mut [u8],
+    cipher_text: &mut [u8],
+) -> SpdmResult<(usize, usize)> {
+    let cipher = get_cipher(aead_algo, key, iv, tag)?;
+    if cipher_text.len() < plain_text.len() {
+        return Err(SPDM_STATUS_INVALID_PARAMETER);
+    }
+
+    let out = encrypt_aead(
+        cipher,
+        key.as_ref(),
+        Some(iv.as_ref()),
+        aad,
+        plain_text,
+        tag,
+    )
+    .map_err(|_| SPDM_STATUS_CRYPTO_ERROR)?;
+    cipher_text[..out.len()].copy_from_slice(&out);
+    Ok((out.len(), tag.len()))
+}
+
+fn decrypt(
+    aead_algo: SpdmAeadAlgo,
+    key: &SpdmAeadKeyStruct,
+    iv: &SpdmAeadIvStruct,
+    aad: &[u8],
+    cipher_text: &[u8],
+    tag: &[u8],
+    plain_text: &mut [u8],
+) -> SpdmResult<usize> {
+    let cipher = get_cipher(aead_algo, key, iv, tag)?;
+    if plain_text.len() < cipher_text.len() {
+        return Err(SPDM_STATUS_INVALID_PARAMETER);
+    }
+
+    let out = decrypt_aead(
+        cipher,
+        key.as_ref(),
+        Some(iv.as_ref()),
+        aad,
+        cipher_text,
+        tag,
+    )
+    .map_err(|_| SPDM_STATUS_CRYPTO_ERROR)?;
+    plain_text[..out.len()].copy_from_slice(&out);
+    Ok(out.len())
+}
+
+#[cfg(all(test,))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_case0_encrypt_decrypt() {
+        for aead_algo in [
+            SpdmAeadAlgo::AES_128_GCM,
+            SpdmAeadAlgo::AES_256_GCM,
+            SpdmAeadAlgo::CHACHA20_POLY1305,
+        ]
+        .iter()
+        {
+            let aead_algo = *aead_algo;
+            let key_size = aead_algo.get_key_size() as usize;
+            let key = &SpdmAeadKeyStruct::from(&[1u8; 32][..key_size]);
+            let iv = &SpdmAeadIvStruct::from(&[2u8; 12][..]);
+            let aad = &[3u8; 16];
+            let plain_text = &[4u8; 60];
+            let tag = &mut [0u8; 16];
+            let cipher_text = &mut [0u8; 60];
+
+            let (cipher_len, tag_len) =
+                encrypt(aead_algo, key, iv, aad, plain_text, tag, cipher_text).unwrap();
+            assert_eq!(cipher_len, 60);
+            assert_eq!(tag_len, 16);
+            assert_ne!(&cipher_text[..], &plain_text[..]);
+
+            let decrypted = &mut [0u8; 60];
+            let plain_len = decrypt(aead_algo, key, iv, aad, cipher_text, tag, decrypted).unwrap();
+            assert_eq!(plain_len, 60);
+            assert_eq!(&decrypted[..], &plain_text[..]);
+
+            tag[0] ^= 1;
+            assert!(decrypt(aead_algo, key, iv, aad, cipher_text, tag, decrypted).is_err());
+        }
+    }
+    #[test]
+    fn test_case1_encrypt() {
+        let key = &SpdmAeadKeyStruct::from(&[1u8; 16][..]);
+        let iv = &SpdmAeadIvStruct::from(&[2u8; 12][..]);
+        let tag = &mut [0u8; 16];
+        let cipher_text = &mut [0u8; 16];
+
+        // wrong key size and unsupported algorithm
+        for aead_algo in [SpdmAeadAlgo::AES_256_GCM, SpdmAeadAlgo::SM4_GCM].iter() {
+            assert!(encrypt(*aead_algo, key, iv, &[], &[4u8; 16], tag, cipher_text).is_err());
+        }
+        assert!(encrypt(
+            SpdmAeadAlgo::AES_128_GCM,
+            key,
+            iv,
+            &[],
+            &[4u8; 32],
+            tag,
+            cipher_text
+        )
+        .is_err());
+    }
+}
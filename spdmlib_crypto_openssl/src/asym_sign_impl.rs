@@ -0,0 +1,206 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use lazy_static::lazy_static;
+use openssl::ecdsa::EcdsaSig;
+use openssl::pkey::{PKey, PKeyRef, Private};
+use openssl::rsa::Padding;
+use openssl::sign::{RsaPssSaltlen, Signer};
+use spdmlib::protocol::{SpdmBaseAsymAlgo, SpdmBaseHashAlgo, SpdmSignatureStruct};
+use spdmlib::secret::{SpdmSecretAsymSign, SpdmSecretResult};
+use spin::Mutex;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::asym_verify_impl::{is_ecdsa, is_eddsa, is_matching_key, is_rsa_pss};
+use crate::hash_impl::get_message_digest;
+
+lazy_static! {
+    static ref KEY_FILE_TABLE: Mutex<BTreeMap<u8, PathBuf>> = Mutex::new(BTreeMap::new());
+}
+
+/// Signs with the key file set by `set_key_file` for the key pair.
+pub static SECRET_ASYM_IMPL_INSTANCE: SpdmSecretAsymSign =
+    SpdmSecretAsymSign { sign_cb: asym_sign };
+
+/// Set the private key file of key pair `key_pair_id`, used by
+/// `SECRET_ASYM_IMPL_INSTANCE`. Without key pairs, the key shall be set for
+/// `SPDM_DEFAULT_KEY_PAIR_ID`.
+///
+/// The file is read on every signing, so it can be replaced at runtime.
+pub fn set_key_file<P: AsRef<Path>>(key_pair_id: u8, key_file: P) {
+    KEY_FILE_TABLE
+        .lock()
+        .insert(key_pair_id, key_file.as_ref().to_path_buf());
+}
+
+fn asym_sign(
+    base_hash_algo: SpdmBaseHashAlgo,
+    base_asym_algo: SpdmBaseAsymAlgo,
+    key_pair_id: u8,
+    data: &[u8],
+) -> SpdmSecretResult<SpdmSignatureStruct> {
+    let key_file = KEY_FILE_TABLE.lock().get(&key_pair_id).cloned();
+    key_file
+        .and_then(|key_file| std::fs::read(key_file).ok())
+        .and_then(|private_key| sign(base_hash_algo, base_asym_algo, &private_key, data))
+        .into()
+}
+
+/// Sign `data` with a PEM or DER encoded private key, in the PKCS#8 or the
+/// traditional RSA and EC formats.
+///
+/// The key shall be of the algorithm and size of `base_asym_algo`.
+pub fn sign(
+    base_hash_algo: SpdmBaseHashAlgo,
+    base_asym_algo: SpdmBaseAsymAlgo,
+    private_key: &[u8],
+    data: &[u8],
+) -> Option<SpdmSignatureStruct> {
+    let private_key = if private_key.starts_with(b"-----BEGIN") {
+        PKey::private_key_from_pem(private_key).ok()?
+    } else {
+        PKey::private_key_from_der(private_key).ok()?
+    };
+    if !is_matching_key(base_asym_algo, &private_key) {
+        return None;
+    }
+
+    let signature = sign_with_key(base_hash_algo, base_asym_algo, &private_key, data)?;
+    let mut full_signature = SpdmSignatureStruct {
+        data_size: base_asym_algo.get_size(),
+        ..Default::default()
+    };
+    if signature.len() != full_signature.data_size as usize {
+        return None;
+    }
+    full_signature.data[..signature.len()].copy_from_slice(&signature);
+    Some(full_signature)
+}
+
+fn sign_with_key(
+    base_hash_algo: SpdmBaseHashAlgo,
+    base_asym_algo: SpdmBaseAsymAlgo,
+    private_key: &PKeyRef<Private>,
+    data: &[u8],
+) -> Option<Vec<u8>> {
+    // EdDSA signs the message itself, whatever the hash algorithm.
+    if is_eddsa(base_asym_algo) {
+        let mut signer = Signer::new_without_digest(private_key).ok()?;
+        return signer.sign_oneshot_to_vec(data).ok();
+    }
+
+    let message_digest = get_message_digest(base_hash_algo)?;
+    let mut signer = Signer::new(message_digest, private_key).ok()?;
+    if is_rsa_pss(base_asym_algo) {
+        // The salt length of RSASSA-PSS is the length of the hash.
+        signer.set_rsa_padding(Padding::PKCS1_PSS).ok()?;
+        signer
+            .set_rsa_pss_saltlen(RsaPssSaltlen::DIGEST_LENGTH)
+            .ok()?;
+        signer.set_rsa_mgf1_md(message_digest).ok()?;
+    }
+    let signature = signer.sign_oneshot_to_vec(data).ok()?;
+    if !is_ecdsa(base_asym_algo) {
+        return Some(signature);
+    }
+
+    // The ECDSA signature of SPDM is the fixed size r || s.
+    let half_size = base_asym_algo.get_size() as i32 / 2;
+    let ecdsa_sig = EcdsaSig::from_der(&signature).ok()?;
+    let mut signature = ecdsa_sig.r().to_vec_padded(half_size).ok()?;
+    signature.extend_from_slice(&ecdsa_sig.s().to_vec_padded(half_size).ok()?);
+    Some(signature)
+}
+
+#[cfg(all(test,))]
+mod tests {
+    use super::*;
+    use spdmlib::protocol::SPDM_DEFAULT_KEY_PAIR_ID;
+
+    #[test]
+    fn test_case0_sign() {
+        let base_hash_algo = SpdmBaseHashAlgo::TPM_ALG_SHA_384;
+        let data = [0x5au8; 100];
+        for key_file in [
+            "end_responder.key",
+            "end_responder.key.der",
+            "end_responder.key.p8",
+        ]
+        .iter()
+        {
+            let private_key = std::fs::read(std::format!("../test_key/ecp384/{}", key_file))
+                .expect("unable to read private key!");
+            let signature = sign(
+                base_hash_algo,
+                SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384,
+                &private_key,
+                &data,
+            )
+            .unwrap();
+            assert_eq!(signature.data_size, 96);
+            assert!(sign(
+                base_hash_algo,
+                SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P256,
+                &private_key,
+                &data
+            )
+            .is_none());
+        }
+
+        let private_key = std::fs::read("../test_key/rsa2048/end_responder.key.der")
+            .expect("unable to read private key!");
+        for base_asym_algo in [
+            SpdmBaseAsymAlgo::TPM_ALG_RSASSA_2048,
+            SpdmBaseAsymAlgo::TPM_ALG_RSAPSS_2048,
+        ]
+        .iter()
+        {
+            assert!(sign(base_hash_algo, *base_asym_algo, &private_key, &data).is_some());
+        }
+        assert!(sign(
+            base_hash_algo,
+            SpdmBaseAsymAlgo::TPM_ALG_RSASSA_3072,
+            &private_key,
+            &data
+        )
+        .is_none());
+        assert!(sign(
+            base_hash_algo,
+            SpdmBaseAsymAlgo::TPM_ALG_RSASSA_2048,
+            &private_key[1..],
+            &data
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_case0_asym_sign() {
+        let base_hash_algo = SpdmBaseHashAlgo::TPM_ALG_SHA_256;
+        let base_asym_algo = SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P256;
+        let data = [0x5au8; 100];
+
+        assert!(asym_sign(base_hash_algo, base_asym_algo, 7, &data)
+            .ready()
+            .is_none());
+        set_key_file(7, "../test_key/ecp256/no_such.key");
+        assert!(asym_sign(base_hash_algo, base_asym_algo, 7, &data)
+            .ready()
+            .is_none());
+
+        set_key_file(
+            SPDM_DEFAULT_KEY_PAIR_ID,
+            "../test_key/ecp256/end_responder.key",
+        );
+        let signature = (SECRET_ASYM_IMPL_INSTANCE.sign_cb)(
+            base_hash_algo,
+            base_asym_algo,
+            SPDM_DEFAULT_KEY_PAIR_ID,
+            &data,
+        )
+        .ready()
+        .unwrap();
+        assert_eq!(signature.data_size, 64);
+    }
+}
@@ -0,0 +1,309 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use openssl::bn::BigNum;
+use openssl::ecdsa::EcdsaSig;
+use openssl::nid::Nid;
+use openssl::pkey::{HasPublic, Id, PKey, PKeyRef, Public};
+use openssl::rsa::Padding;
+use openssl::sign::{RsaPssSaltlen, Verifier};
+use openssl::x509::X509;
+use spdmlib::crypto::SpdmAsymVerify;
+use spdmlib::error::{
+    SpdmResult, SPDM_STATUS_INVALID_CERT, SPDM_STATUS_UNSUPPORTED_CAP, SPDM_STATUS_VERIF_FAIL,
+};
+use spdmlib::protocol::{SpdmBaseAsymAlgo, SpdmBaseHashAlgo, SpdmPqcAsymAlgo, SpdmSignatureStruct};
+
+use crate::cert_operation_impl::get_cert_from_cert_chain;
+use crate::hash_impl::get_message_digest;
+
+pub static DEFAULT: SpdmAsymVerify = SpdmAsymVerify {
+    verify_cb: asym_verify,
+    verify_with_public_key_cb: asym_verify_with_public_key,
+    pqc_verify_cb: pqc_asym_verify,
+};
+
+fn asym_verify(
+    base_hash_algo: SpdmBaseHashAlgo,
+    base_asym_algo: SpdmBaseAsymAlgo,
+    public_cert_der: &[u8],
+    data: &[u8],
+    signature: &SpdmSignatureStruct,
+) -> SpdmResult {
+    let (leaf_begin, leaf_end) = get_cert_from_cert_chain(public_cert_der, -1)?;
+    let cert = X509::from_der(&public_cert_der[leaf_begin..leaf_end])
+        .map_err(|_| SPDM_STATUS_INVALID_CERT)?;
+    let public_key = cert.public_key().map_err(|_| SPDM_STATUS_INVALID_CERT)?;
+    verify_signature(base_hash_algo, base_asym_algo, &public_key, data, signature)
+}
+
+fn asym_verify_with_public_key(
+    base_hash_algo: SpdmBaseHashAlgo,
+    base_asym_algo: SpdmBaseAsymAlgo,
+    public_key_der: &[u8],
+    data: &[u8],
+    signature: &SpdmSignatureStruct,
+) -> SpdmResult {
+    let public_key =
+        PKey::public_key_from_der(public_key_der).map_err(|_| SPDM_STATUS_INVALID_CERT)?;
+    verify_signature(base_hash_algo, base_asym_algo, &public_key, data, signature)
+}
+
+fn pqc_asym_verify(
+    _base_hash_algo: SpdmBaseHashAlgo,
+    _pqc_asym_algo: SpdmPqcAsymAlgo,
+    _public_cert_der: &[u8],
+    _data: &[u8],
+    _signature: &SpdmSignatureStruct,
+) -> SpdmResult {
+    Err(SPDM_STATUS_UNSUPPORTED_CAP)
+}
+
+pub(crate) fn is_rsa_pss(base_asym_algo: SpdmBaseAsymAlgo) -> bool {
+    matches!(
+        base_asym_algo,
+        SpdmBaseAsymAlgo::TPM_ALG_RSAPSS_2048
+            | SpdmBaseAsymAlgo::TPM_ALG_RSAPSS_3072
+            | SpdmBaseAsymAlgo::TPM_ALG_RSAPSS_4096
+    )
+}
+
+pub(crate) fn is_ecdsa(base_asym_algo: SpdmBaseAsymAlgo) -> bool {
+    matches!(
+        base_asym_algo,
+        SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P256
+            | SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384
+            | SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P521
+    )
+}
+
+pub(crate) fn is_eddsa(base_asym_algo: SpdmBaseAsymAlgo) -> bool {
+    matches!(
+        base_asym_algo,
+        SpdmBaseAsymAlgo::EDDSA_ED25519 | SpdmBaseAsymAlgo::EDDSA_ED448
+    )
+}
+
+// The key shall be of the algorithm and size of base_asym_algo.
+pub(crate) fn is_matching_key<T: HasPublic>(
+    base_asym_algo: SpdmBaseAsymAlgo,
+    key: &PKeyRef<T>,
+) -> bool {
+    let curve_name = || key.ec_key().ok()?.group().curve_name();
+    match base_asym_algo {
+        SpdmBaseAsymAlgo::TPM_ALG_RSASSA_2048
+        | SpdmBaseAsymAlgo::TPM_ALG_RSASSA_3072
+        | SpdmBaseAsymAlgo::TPM_ALG_RSASSA_4096
+        | SpdmBaseAsymAlgo::TPM_ALG_RSAPSS_2048
+        | SpdmBaseAsymAlgo::TPM_ALG_RSAPSS_3072
+        | SpdmBaseAsymAlgo::TPM_ALG_RSAPSS_4096 => {
+            key.id() == Id::RSA && key.size() == base_asym_algo.get_size() as usize
+        }
+        SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P256 => {
+            key.id() == Id::EC && curve_name() == Some(Nid::X9_62_PRIME256V1)
+        }
+        SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384 => {
+            key.id() == Id::EC && curve_name() == Some(Nid::SECP384R1)
+        }
+        SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P521 => {
+            key.id() == Id::EC && curve_name() == Some(Nid::SECP521R1)
+        }
+        SpdmBaseAsymAlgo::EDDSA_ED25519 => key.id() == Id::ED25519,
+        SpdmBaseAsymAlgo::EDDSA_ED448 => key.id() == Id::ED448,
+        _ => false,
+    }
+}
+
+pub(crate) fn verify_signature(
+    base_hash_algo: SpdmBaseHashAlgo,
+    base_asym_algo: SpdmBaseAsymAlgo,
+    public_key: &PKeyRef<Public>,
+    data: &[u8],
+    signature: &SpdmSignatureStruct,
+) -> SpdmResult {
+    if signature.data_size != base_asym_algo.get_size()
+        || !is_matching_key(base_asym_algo, public_key)
+    {
+        return Err(SPDM_STATUS_VERIF_FAIL);
+    }
+
+    // EdDSA signs the message itself, whatever the hash algorithm.
+    if is_eddsa(base_asym_algo) {
+        let mut verifier =
+            Verifier::new_without_digest(public_key).map_err(|_| SPDM_STATUS_VERIF_FAIL)?;
+        return match verifier.verify_oneshot(signature.as_ref(), data) {
+            Ok(true) => Ok(()),
+            _ => Err(SPDM_STATUS_VERIF_FAIL),
+        };
+    }
+
+    let message_digest = get_message_digest(base_hash_algo).ok_or(SPDM_STATUS_VERIF_FAIL)?;
+    let mut verifier =
+        Verifier::new(message_digest, public_key).map_err(|_| SPDM_STATUS_VERIF_FAIL)?;
+    let res = if is_ecdsa(base_asym_algo) {
+        // The ECDSA signature of SPDM is the fixed size r || s.
+        let half_size = signature.as_ref().len() / 2;
+        let r = BigNum::from_slice(&signature.as_ref()[..half_size]);
+        let s = BigNum::from_slice(&signature.as_ref()[half_size..]);
+        let der_signature = r
+            .and_then(|r| s.and_then(|s| EcdsaSig::from_private_components(r, s)))
+            .and_then(|ecdsa_sig| ecdsa_sig.to_der())
+            .map_err(|_| SPDM_STATUS_VERIF_FAIL)?;
+        verifier.verify_oneshot(&der_signature, data)
+    } else {
+        if is_rsa_pss(base_asym_algo) {
+            // The salt length of RSASSA-PSS is the length of the hash.
+            verifier
+                .set_rsa_padding(Padding::PKCS1_PSS)
+                .and_then(|_| verifier.set_rsa_pss_saltlen(RsaPssSaltlen::DIGEST_LENGTH))
+                .and_then(|_| verifier.set_rsa_mgf1_md(message_digest))
+                .map_err(|_| SPDM_STATUS_VERIF_FAIL)?;
+        }
+        verifier.verify_oneshot(signature.as_ref(), data)
+    };
+    match res {
+        Ok(true) => Ok(()),
+        _ => Err(SPDM_STATUS_VERIF_FAIL),
+    }
+}
+
+#[cfg(all(test,))]
+mod tests {
+    use super::*;
+    use crate::asym_sign_impl::sign;
+
+    #[test]
+    fn test_case0_asym_verify() {
+        for (dir, key_file, base_hash_algo, base_asym_algo) in [
+            (
+                "ecp256",
+                "end_responder.key",
+                SpdmBaseHashAlgo::TPM_ALG_SHA_256,
+                SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P256,
+            ),
+            (
+                "ecp384",
+                "end_responder.key.p8",
+                SpdmBaseHashAlgo::TPM_ALG_SHA_384,
+                SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384,
+            ),
+            (
+                "ecp521",
+                "end_responder.key.der",
+                SpdmBaseHashAlgo::TPM_ALG_SHA_512,
+                SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P521,
+            ),
+            (
+                "rsa2048",
+                "end_responder.key.der",
+                SpdmBaseHashAlgo::TPM_ALG_SHA_256,
+                SpdmBaseAsymAlgo::TPM_ALG_RSASSA_2048,
+            ),
+            (
+                "rsa3072",
+                "end_responder.key",
+                SpdmBaseHashAlgo::TPM_ALG_SHA3_384,
+                SpdmBaseAsymAlgo::TPM_ALG_RSAPSS_3072,
+            ),
+            (
+                "ed25519",
+                "end_responder.key.p8",
+                SpdmBaseHashAlgo::TPM_ALG_SHA_512,
+                SpdmBaseAsymAlgo::EDDSA_ED25519,
+            ),
+            (
+                "ed448",
+                "end_responder.key",
+                SpdmBaseHashAlgo::TPM_ALG_SHA_512,
+                SpdmBaseAsymAlgo::EDDSA_ED448,
+            ),
+        ]
+        .iter()
+        {
+            let private_key = std::fs::read(std::format!("../test_key/{}/{}", dir, key_file))
+                .expect("unable to read private key!");
+            let cert_chain = std::fs::read(std::format!(
+                "../test_key/{}/bundle_responder.certchain.der",
+                dir
+            ))
+            .expect("unable to read cert chain!");
+            let public_key = std::fs::read(std::format!(
+                "../test_key/{}/end_responder.key.pub.der",
+                dir
+            ))
+            .expect("unable to read public key!");
+
+            let data = &mut [0x5au8; 100];
+            let signature = sign(*base_hash_algo, *base_asym_algo, &private_key, data).unwrap();
+            assert_eq!(signature.data_size, base_asym_algo.get_size(), "{}", dir);
+
+            assert!(
+                asym_verify(
+                    *base_hash_algo,
+                    *base_asym_algo,
+                    &cert_chain,
+                    data,
+                    &signature
+                )
+                .is_ok(),
+                "{}",
+                dir
+            );
+            assert!(
+                asym_verify_with_public_key(
+                    *base_hash_algo,
+                    *base_asym_algo,
+                    &public_key,
+                    data,
+                    &signature
+                )
+                .is_ok(),
+                "{}",
+                dir
+            );
+
+            data[0] ^= 1;
+            assert!(
+                asym_verify(
+                    *base_hash_algo,
+                    *base_asym_algo,
+                    &cert_chain,
+                    data,
+                    &signature
+                )
+                .is_err(),
+                "{}",
+                dir
+            );
+        }
+    }
+    #[test]
+    fn test_case1_asym_verify() {
+        let cert_chain = std::fs::read("../test_key/ecp384/bundle_responder.certchain.der")
+            .expect("unable to read cert chain!");
+        let data = &[0x5au8; 100];
+        let signature = SpdmSignatureStruct {
+            data_size: 96,
+            ..Default::default()
+        };
+
+        for base_asym_algo in [
+            SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P256,
+            SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384,
+            SpdmBaseAsymAlgo::TPM_ALG_RSASSA_3072,
+            SpdmBaseAsymAlgo::EDDSA_ED448,
+        ]
+        .iter()
+        {
+            assert!(asym_verify(
+                SpdmBaseHashAlgo::TPM_ALG_SHA_384,
+                *base_asym_algo,
+                &cert_chain,
+                data,
+                &signature
+            )
+            .is_err());
+        }
+    }
+}
@@ -0,0 +1,226 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use openssl::stack::Stack;
+use openssl::x509::store::X509StoreBuilder;
+use openssl::x509::verify::X509VerifyFlags;
+use openssl::x509::{X509Ref, X509StoreContext, X509};
+use spdmlib::crypto::{check_extended_key_usage, is_ca_certificate, SpdmCertOperation};
+use spdmlib::error::{SpdmResult, SPDM_STATUS_INVALID_CERT};
+
+// id-kp-serverAuth 1.3.6.1.5.5.7.3.1, accepted for both roles
+const OID_EKU_SERVER_AUTH: &[u8] = &[0x2b, 0x06, 0x01, 0x05, 0x05, 0x07, 0x03, 0x01];
+// id-DMTF-eku-responder-auth 1.3.6.1.4.1.412.274.3.1
+const OID_EKU_DMTF_RESPONDER_AUTH: &[u8] = &[
+    0x2b, 0x06, 0x01, 0x04, 0x01, 0x83, 0x1c, 0x82, 0x12, 0x03, 0x01,
+];
+// id-DMTF-eku-requester-auth 1.3.6.1.4.1.412.274.3.2
+const OID_EKU_DMTF_REQUESTER_AUTH: &[u8] = &[
+    0x2b, 0x06, 0x01, 0x04, 0x01, 0x83, 0x1c, 0x82, 0x12, 0x03, 0x02,
+];
+
+pub static DEFAULT: SpdmCertOperation = SpdmCertOperation {
+    get_cert_from_cert_chain_cb: get_cert_from_cert_chain,
+    verify_cert_chain_cb: verify_cert_chain,
+};
+
+pub(crate) fn get_cert_from_cert_chain(
+    cert_chain: &[u8],
+    index: isize,
+) -> SpdmResult<(usize, usize)> {
+    let mut offset = 0usize;
+    let mut this_index = 0isize;
+    let cert_chain_size = cert_chain.len();
+    loop {
+        if offset > cert_chain_size || cert_chain[offset..].len() < 4 {
+            return Err(SPDM_STATUS_INVALID_CERT);
+        }
+        if cert_chain[offset] != 0x30 || cert_chain[offset + 1] != 0x82 {
+            return Err(SPDM_STATUS_INVALID_CERT);
+        }
+        let this_cert_len =
+            ((cert_chain[offset + 2] as usize) << 8) + (cert_chain[offset + 3] as usize) + 4;
+        if this_cert_len > cert_chain_size - offset {
+            return Err(SPDM_STATUS_INVALID_CERT);
+        }
+        if this_index == index {
+            // return the this one
+            return Ok((offset, offset + this_cert_len));
+        }
+        this_index += 1;
+        if (offset + this_cert_len == cert_chain_size) && (index == -1) {
+            // return the last one
+            return Ok((offset, offset + this_cert_len));
+        }
+        offset += this_cert_len;
+    }
+}
+
+// The first certificate is the trust anchor and the last one is verified by
+// OpenSSL, which requires a self-signed root and checks the signatures, the
+// validity periods and the CA constraints of the issuers. The leaf shall
+// allow the SPDM authentication EKU of its role.
+//
+// Only in the alias certificate model may the chain be partial, ending at the
+// device certificate, a CA whose EKU is not checked. The certificate model
+// and the leaf fields are checked by spdmlib itself.
+fn verify_cert_chain(
+    cert_chain: &[u8],
+    is_requester_cert: bool,
+    is_alias_cert_model: bool,
+) -> SpdmResult {
+    let mut certs = Vec::new();
+    let mut offset = 0usize;
+    let mut leaf_begin = 0usize;
+    while offset < cert_chain.len() {
+        let (_, end) = get_cert_from_cert_chain(&cert_chain[offset..], 0)?;
+        let cert = X509::from_der(&cert_chain[offset..(offset + end)])
+            .map_err(|_| SPDM_STATUS_INVALID_CERT)?;
+        certs.push(cert);
+        leaf_begin = offset;
+        offset += end;
+    }
+    let leaf = certs.pop().ok_or(SPDM_STATUS_INVALID_CERT)?;
+    let is_partial_chain = is_ca_certificate(&cert_chain[leaf_begin..])?;
+    if is_partial_chain && !is_alias_cert_model {
+        return Err(SPDM_STATUS_INVALID_CERT);
+    }
+
+    let root = certs.first().unwrap_or(&leaf).clone();
+    let mut store_builder = X509StoreBuilder::new().map_err(|_| SPDM_STATUS_INVALID_CERT)?;
+    store_builder
+        .add_cert(root)
+        .map_err(|_| SPDM_STATUS_INVALID_CERT)?;
+    if is_partial_chain {
+        store_builder
+            .set_flags(X509VerifyFlags::PARTIAL_CHAIN)
+            .map_err(|_| SPDM_STATUS_INVALID_CERT)?;
+    }
+    let store = store_builder.build();
+
+    let mut chain = Stack::new().map_err(|_| SPDM_STATUS_INVALID_CERT)?;
+    for cert in certs.into_iter().skip(1) {
+        chain.push(cert).map_err(|_| SPDM_STATUS_INVALID_CERT)?;
+    }
+
+    let mut store_ctx = X509StoreContext::new().map_err(|_| SPDM_STATUS_INVALID_CERT)?;
+    let verified = store_ctx
+        .init(&store, &leaf, &chain, |ctx| ctx.verify_cert())
+        .map_err(|_| SPDM_STATUS_INVALID_CERT)?;
+    if !verified {
+        return Err(SPDM_STATUS_INVALID_CERT);
+    }
+
+    if is_partial_chain {
+        Ok(())
+    } else {
+        check_eku(&leaf, is_requester_cert)
+    }
+}
+
+// A leaf without the extension may be used for any purpose.
+fn check_eku(cert: &X509Ref, is_requester_cert: bool) -> SpdmResult {
+    let cert = cert.to_der().map_err(|_| SPDM_STATUS_INVALID_CERT)?;
+    let role_eku = if is_requester_cert {
+        OID_EKU_DMTF_REQUESTER_AUTH
+    } else {
+        OID_EKU_DMTF_RESPONDER_AUTH
+    };
+    if check_extended_key_usage(&cert, &[OID_EKU_SERVER_AUTH, role_eku])? {
+        Ok(())
+    } else {
+        Err(SPDM_STATUS_INVALID_CERT)
+    }
+}
+
+#[cfg(all(test,))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_case0_get_cert_from_cert_chain() {
+        let cert_chain = std::fs::read("../test_key/ecp384/bundle_responder.certchain.der")
+            .expect("unable to read cert chain!");
+        let (start, end) = get_cert_from_cert_chain(&cert_chain, -1).unwrap();
+        assert_eq!(end, cert_chain.len());
+        assert!(X509::from_der(&cert_chain[start..end]).is_ok());
+        assert!(get_cert_from_cert_chain(&cert_chain, 3).is_err());
+    }
+
+    #[test]
+    fn test_case0_verify_cert_chain() {
+        for dir in [
+            "ecp256", "ecp384", "ecp521", "rsa2048", "rsa3072", "rsa4096", "ed25519", "ed448",
+        ]
+        .iter()
+        {
+            for (bundle, is_requester_cert) in
+                [("bundle_responder", false), ("bundle_requester", true)].iter()
+            {
+                let cert_chain =
+                    std::fs::read(std::format!("../test_key/{}/{}.certchain.der", dir, bundle))
+                        .expect("unable to read cert chain!");
+                assert!(
+                    verify_cert_chain(&cert_chain, *is_requester_cert, false).is_ok(),
+                    "{} {}",
+                    dir,
+                    bundle
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_case1_verify_cert_chain() {
+        let cert_chain = std::fs::read("../test_key/ecp384/bundle_responder.certchain.der")
+            .expect("unable to read cert chain!");
+
+        // The intermediate cert is not self-signed.
+        let (start, _) = get_cert_from_cert_chain(&cert_chain, 1).unwrap();
+        assert!(verify_cert_chain(&cert_chain[start..], false, false).is_err());
+        assert!(verify_cert_chain(&[], false, false).is_err());
+
+        // A different root
+        let mut wrong_chain =
+            std::fs::read("../test_key/ecp384/ca1.cert.der").expect("unable to read ca!");
+        wrong_chain.extend_from_slice(&cert_chain[start..]);
+        assert!(verify_cert_chain(&wrong_chain, false, false).is_err());
+
+        let cert_chain =
+            std::fs::read("../test_key/rsa3072_Expiration/bundle_responder.certchain.der")
+                .expect("unable to read cert chain!");
+        assert!(verify_cert_chain(&cert_chain, false, false).is_err());
+    }
+
+    #[test]
+    fn test_case2_verify_cert_chain() {
+        let requester_cert_chain =
+            std::fs::read("../test_key/crypto_chains/bundle_dmtf_requester_cert.der")
+                .expect("unable to read cert chain!");
+        assert!(verify_cert_chain(&requester_cert_chain, true, false).is_ok());
+        assert!(verify_cert_chain(&requester_cert_chain, false, false).is_err());
+
+        let responder_cert_chain =
+            std::fs::read("../test_key/crypto_chains/bundle_dmtf_responder_cert.der")
+                .expect("unable to read cert chain!");
+        assert!(verify_cert_chain(&responder_cert_chain, false, false).is_ok());
+        assert!(verify_cert_chain(&responder_cert_chain, true, false).is_err());
+    }
+
+    #[test]
+    fn test_case3_verify_cert_chain() {
+        let ca = std::fs::read("../test_key/ecp384/ca.cert.der").expect("unable to read ca!");
+        let inter =
+            std::fs::read("../test_key/ecp384/inter.cert.der").expect("unable to read inter!");
+        let partial_chain = [ca, inter.clone()].concat();
+        assert!(verify_cert_chain(&partial_chain, false, true).is_ok());
+        // Only the alias certificate model allows a partial chain.
+        assert!(verify_cert_chain(&partial_chain, false, false).is_err());
+
+        // The issuer of the device certificate shall be a CA.
+        let leaf = std::fs::read("../test_key/ecp384/end_responder.cert.der")
+            .expect("unable to read leaf!");
+        assert!(verify_cert_chain(&[leaf, inter].concat(), false, true).is_err());
+    }
+}
@@ -0,0 +1,139 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use openssl::bn::BigNumContext;
+use openssl::derive::Deriver;
+use openssl::ec::{EcGroup, EcKey, EcPoint, PointConversionForm};
+use openssl::nid::Nid;
+use openssl::pkey::{PKey, Private};
+use spdmlib::crypto::{SpdmDhe, SpdmDheKeyExchange};
+use spdmlib::protocol::{
+    SpdmDheAlgo, SpdmDheExchangeStruct, SpdmDheFinalKeyStruct, SpdmKemAlgo, SPDM_MAX_DHE_KEY_SIZE,
+};
+
+pub static DEFAULT: SpdmDhe = SpdmDhe {
+    generate_key_pair_cb: generate_key_pair,
+    kem_generate_key_pair_cb: kem_generate_key_pair,
+    kem_encapsulate_cb: kem_encapsulate,
+};
+
+// FFDHE is not implemented, as in the ring backend.
+fn generate_key_pair(
+    dhe_algo: SpdmDheAlgo,
+) -> Option<(SpdmDheExchangeStruct, Box<dyn SpdmDheKeyExchange>)> {
+    match dhe_algo {
+        SpdmDheAlgo::SECP_256_R1 => SpdmDheKeyExchangeEc::generate_key_pair(Nid::X9_62_PRIME256V1),
+        SpdmDheAlgo::SECP_384_R1 => SpdmDheKeyExchangeEc::generate_key_pair(Nid::SECP384R1),
+        SpdmDheAlgo::SECP_521_R1 => SpdmDheKeyExchangeEc::generate_key_pair(Nid::SECP521R1),
+        _ => None,
+    }
+}
+
+fn kem_generate_key_pair(
+    _kem_algo: SpdmKemAlgo,
+) -> Option<(SpdmDheExchangeStruct, Box<dyn SpdmDheKeyExchange>)> {
+    None
+}
+
+fn kem_encapsulate(
+    _kem_algo: SpdmKemAlgo,
+    _peer_encap_key: &SpdmDheExchangeStruct,
+) -> Option<(SpdmDheExchangeStruct, SpdmDheFinalKeyStruct)> {
+    None
+}
+
+struct SpdmDheKeyExchangeEc(EcKey<Private>);
+
+impl SpdmDheKeyExchangeEc {
+    fn generate_key_pair(nid: Nid) -> Option<(SpdmDheExchangeStruct, Box<dyn SpdmDheKeyExchange>)> {
+        let group = EcGroup::from_curve_name(nid).ok()?;
+        let private_key = EcKey::generate(&group).ok()?;
+        let mut ctx = BigNumContext::new().ok()?;
+        let point = private_key
+            .public_key()
+            .to_bytes(&group, PointConversionForm::UNCOMPRESSED, &mut ctx)
+            .ok()?;
+
+        // SPDM carries the uncompressed point without the leading 0x04.
+        let point = &point[1..];
+        let mut public_key = SpdmDheExchangeStruct {
+            data_size: point.len() as u16,
+            ..Default::default()
+        };
+        public_key.data[..point.len()].copy_from_slice(point);
+
+        let res: Box<dyn SpdmDheKeyExchange> = Box::new(Self(private_key));
+        Some((public_key, res))
+    }
+}
+
+impl SpdmDheKeyExchange for SpdmDheKeyExchangeEc {
+    fn compute_final_key(
+        self: Box<Self>,
+        peer_pub_key: &SpdmDheExchangeStruct,
+    ) -> Option<SpdmDheFinalKeyStruct> {
+        let peer_pub_key = peer_pub_key.as_ref();
+        if peer_pub_key.len() >= SPDM_MAX_DHE_KEY_SIZE {
+            return None;
+        }
+        let mut pubkey = [0u8; SPDM_MAX_DHE_KEY_SIZE + 1];
+        pubkey[0] = 0x4u8;
+        pubkey[1..(peer_pub_key.len() + 1)].copy_from_slice(peer_pub_key);
+
+        let group = self.0.group();
+        let mut ctx = BigNumContext::new().ok()?;
+        let point =
+            EcPoint::from_bytes(group, &pubkey[..(peer_pub_key.len() + 1)], &mut ctx).ok()?;
+        let peer_public_key = EcKey::from_public_key(group, &point).ok()?;
+        peer_public_key.check_key().ok()?;
+        let peer_public_key = PKey::from_ec_key(peer_public_key).ok()?;
+
+        let private_key = PKey::from_ec_key(self.0).ok()?;
+        let mut deriver = Deriver::new(&private_key).ok()?;
+        deriver.set_peer(&peer_public_key).ok()?;
+        let shared_secret = deriver.derive_to_vec().ok()?;
+        Some(SpdmDheFinalKeyStruct::from(shared_secret.as_slice()))
+    }
+}
+
+#[cfg(all(test,))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_case0_dhe() {
+        for dhe_algo in [
+            SpdmDheAlgo::SECP_256_R1,
+            SpdmDheAlgo::SECP_384_R1,
+            SpdmDheAlgo::SECP_521_R1,
+        ]
+        .iter()
+        {
+            let (exchange1, private1) = generate_key_pair(*dhe_algo).unwrap();
+            let (exchange2, private2) = generate_key_pair(*dhe_algo).unwrap();
+            assert_eq!(exchange1.data_size, dhe_algo.get_size());
+
+            let peer1 = private1.compute_final_key(&exchange2).unwrap();
+            let peer2 = private2.compute_final_key(&exchange1).unwrap();
+
+            assert_eq!(peer1.as_ref(), peer2.as_ref());
+        }
+    }
+    #[test]
+    fn test_case1_dhe() {
+        for dhe_algo in [
+            SpdmDheAlgo::empty(),
+            SpdmDheAlgo::FFDHE_2048,
+            SpdmDheAlgo::SM2_P256,
+        ]
+        .iter()
+        {
+            assert!(generate_key_pair(*dhe_algo).is_none());
+        }
+
+        let (_, private1) = generate_key_pair(SpdmDheAlgo::SECP_384_R1).unwrap();
+        let (exchange2, _) = generate_key_pair(SpdmDheAlgo::SECP_256_R1).unwrap();
+        assert!(private1.compute_final_key(&exchange2).is_none());
+    }
+}
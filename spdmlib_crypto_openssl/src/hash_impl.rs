@@ -0,0 +1,161 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use openssl::hash::MessageDigest;
+use spdmlib::crypto::SpdmHash;
+use spdmlib::protocol::{SpdmBaseHashAlgo, SpdmDigestStruct};
+
+pub(crate) fn get_message_digest(base_hash_algo: SpdmBaseHashAlgo) -> Option<MessageDigest> {
+    match base_hash_algo {
+        SpdmBaseHashAlgo::TPM_ALG_SHA_256 => Some(MessageDigest::sha256()),
+        SpdmBaseHashAlgo::TPM_ALG_SHA_384 => Some(MessageDigest::sha384()),
+        SpdmBaseHashAlgo::TPM_ALG_SHA_512 => Some(MessageDigest::sha512()),
+        SpdmBaseHashAlgo::TPM_ALG_SHA3_256 => Some(MessageDigest::sha3_256()),
+        SpdmBaseHashAlgo::TPM_ALG_SHA3_384 => Some(MessageDigest::sha3_384()),
+        SpdmBaseHashAlgo::TPM_ALG_SHA3_512 => Some(MessageDigest::sha3_512()),
+        _ => None,
+    }
+}
+
+#[cfg(feature = "hashed-transcript-data")]
+mod hash_ext {
+    use super::*;
+    use lazy_static::lazy_static;
+    use openssl::hash::Hasher;
+    use spdmlib::error::{SpdmResult, SPDM_STATUS_CRYPTO_ERROR};
+    use spin::Mutex;
+    use std::collections::BTreeMap;
+    pub(crate) type HashCtxConcrete = Hasher;
+    lazy_static! {
+        static ref HASH_CTX_TABLE: Mutex<BTreeMap<usize, Box<HashCtxConcrete>>> =
+            Mutex::new(BTreeMap::new());
+    }
+
+    pub static DEFAULT: SpdmHash = SpdmHash {
+        hash_all_cb: hash_all,
+        hash_ctx_init_cb: hash_ctx_init,
+        hash_ctx_update_cb: hash_ctx_update,
+        hash_ctx_finalize_cb: hash_ctx_finalize,
+        hash_ctx_dup_cb: hash_ctx_dup,
+    };
+
+    pub(crate) fn hash_ctx_init(base_hash_algo: SpdmBaseHashAlgo) -> Option<usize> {
+        let ctx = Box::new(Hasher::new(get_message_digest(base_hash_algo)?).ok()?);
+        Some(insert_to_table(ctx))
+    }
+
+    pub(crate) fn hash_ctx_update(handle: usize, data: &[u8]) -> SpdmResult {
+        let mut table = HASH_CTX_TABLE.lock();
+        let ctx = table.get_mut(&handle).ok_or(SPDM_STATUS_CRYPTO_ERROR)?;
+        ctx.update(data).map_err(|_| SPDM_STATUS_CRYPTO_ERROR)
+    }
+
+    pub(crate) fn hash_ctx_finalize(handle: usize) -> Option<SpdmDigestStruct> {
+        let mut ctx = HASH_CTX_TABLE.lock().remove(&handle)?;
+        let digest = ctx.finish().ok()?;
+        Some(SpdmDigestStruct::from(digest.as_ref()))
+    }
+
+    pub(crate) fn hash_ctx_dup(handle: usize) -> Option<usize> {
+        let ctx_new = {
+            let table = HASH_CTX_TABLE.lock();
+            let ctx = table.get(&handle)?;
+            ctx.clone()
+        };
+        Some(insert_to_table(ctx_new))
+    }
+
+    pub(crate) fn insert_to_table(value: Box<HashCtxConcrete>) -> usize {
+        let handle_ptr: *const HashCtxConcrete = &*value;
+        let handle = handle_ptr as usize;
+        HASH_CTX_TABLE.lock().insert(handle, value);
+        handle
+    }
+
+    #[allow(dead_code)]
+    #[cfg(test)]
+    pub fn get_hash_ctx_count() -> usize {
+        HASH_CTX_TABLE.lock().len()
+    }
+}
+#[cfg(feature = "hashed-transcript-data")]
+pub use hash_ext::DEFAULT;
+
+#[cfg(not(feature = "hashed-transcript-data"))]
+pub static DEFAULT: SpdmHash = SpdmHash {
+    hash_all_cb: hash_all,
+};
+
+pub(crate) fn hash_all(base_hash_algo: SpdmBaseHashAlgo, data: &[u8]) -> Option<SpdmDigestStruct> {
+    let digest = openssl::hash::hash(get_message_digest(base_hash_algo)?, data).ok()?;
+    Some(SpdmDigestStruct::from(digest.as_ref()))
+}
+
+#[cfg(all(test,))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_case0_hash_all() {
+        // FIPS 180-2 and FIPS 202 test vectors for "abc"
+        let hash_all_256 = hash_all(SpdmBaseHashAlgo::TPM_ALG_SHA_256, b"abc").unwrap();
+        assert_eq!(
+            hash_all_256.as_ref(),
+            &[
+                0xba, 0x78, 0x16, 0xbf, 0x8f, 0x01, 0xcf, 0xea, 0x41, 0x41, 0x40, 0xde, 0x5d, 0xae,
+                0x22, 0x23, 0xb0, 0x03, 0x61, 0xa3, 0x96, 0x17, 0x7a, 0x9c, 0xb4, 0x10, 0xff, 0x61,
+                0xf2, 0x00, 0x15, 0xad
+            ]
+        );
+        let hash_all_sha3_256 = hash_all(SpdmBaseHashAlgo::TPM_ALG_SHA3_256, b"abc").unwrap();
+        assert_eq!(
+            hash_all_sha3_256.as_ref(),
+            &[
+                0x3a, 0x98, 0x5d, 0xa7, 0x4f, 0xe2, 0x25, 0xb2, 0x04, 0x5c, 0x17, 0x2d, 0x6b, 0xd3,
+                0x90, 0xbd, 0x85, 0x5f, 0x08, 0x6e, 0x3e, 0x9d, 0x52, 0x5b, 0x46, 0xbf, 0xe2, 0x45,
+                0x11, 0x43, 0x15, 0x32
+            ]
+        );
+        for (base_hash_algo, size) in [
+            (SpdmBaseHashAlgo::TPM_ALG_SHA_384, 48),
+            (SpdmBaseHashAlgo::TPM_ALG_SHA_512, 64),
+            (SpdmBaseHashAlgo::TPM_ALG_SHA3_384, 48),
+            (SpdmBaseHashAlgo::TPM_ALG_SHA3_512, 64),
+        ]
+        .iter()
+        {
+            assert_eq!(hash_all(*base_hash_algo, b"abc").unwrap().data_size, *size);
+        }
+    }
+    #[test]
+    fn test_case1_hash_all() {
+        for base_hash_algo in [SpdmBaseHashAlgo::empty(), SpdmBaseHashAlgo::TPM_ALG_SM3_256].iter()
+        {
+            assert!(hash_all(*base_hash_algo, b"abc").is_none());
+        }
+    }
+    #[cfg(feature = "hashed-transcript-data")]
+    #[test]
+    fn test_case2_hash_ctx() {
+        use hash_ext::*;
+        let base_hash_algo = SpdmBaseHashAlgo::TPM_ALG_SHA_384;
+
+        let handle = hash_ctx_init(base_hash_algo).unwrap();
+        assert!(hash_ctx_update(handle, b"hello, ").is_ok());
+        let dup = hash_ctx_dup(handle).unwrap();
+        assert!(hash_ctx_update(handle, b"world").is_ok());
+        assert!(hash_ctx_update(dup, b"buddy").is_ok());
+
+        assert_eq!(
+            hash_ctx_finalize(handle).unwrap().as_ref(),
+            hash_all(base_hash_algo, b"hello, world").unwrap().as_ref()
+        );
+        assert_eq!(
+            hash_ctx_finalize(dup).unwrap().as_ref(),
+            hash_all(base_hash_algo, b"hello, buddy").unwrap().as_ref()
+        );
+        assert!(hash_ctx_finalize(handle).is_none());
+        assert!(hash_ctx_init(SpdmBaseHashAlgo::TPM_ALG_SM3_256).is_none());
+    }
+}
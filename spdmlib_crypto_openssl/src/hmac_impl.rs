@@ -0,0 +1,90 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use openssl::pkey::PKey;
+use openssl::sign::Signer;
+use spdmlib::crypto::SpdmHmac;
+use spdmlib::error::{SpdmResult, SPDM_STATUS_CRYPTO_ERROR, SPDM_STATUS_VERIF_FAIL};
+use spdmlib::protocol::{SpdmBaseHashAlgo, SpdmDigestStruct};
+
+use crate::hash_impl::get_message_digest;
+
+pub static DEFAULT: SpdmHmac = SpdmHmac {
+    hmac_cb: hmac,
+    hmac_verify_cb: hmac_verify,
+};
+
+fn hmac(base_hash_algo: SpdmBaseHashAlgo, key: &[u8], data: &[u8]) -> Option<SpdmDigestStruct> {
+    let message_digest = get_message_digest(base_hash_algo)?;
+    let key = PKey::hmac(key).ok()?;
+    let mut signer = Signer::new(message_digest, &key).ok()?;
+    signer.update(data).ok()?;
+    let tag = signer.sign_to_vec().ok()?;
+    Some(SpdmDigestStruct::from(tag.as_slice()))
+}
+
+fn hmac_verify(
+    base_hash_algo: SpdmBaseHashAlgo,
+    key: &[u8],
+    data: &[u8],
+    message_digest: &SpdmDigestStruct,
+) -> SpdmResult {
+    let tag = hmac(base_hash_algo, key, data).ok_or(SPDM_STATUS_CRYPTO_ERROR)?;
+    if tag.data_size == message_digest.data_size
+        && openssl::memcmp::eq(tag.as_ref(), message_digest.as_ref())
+    {
+        Ok(())
+    } else {
+        Err(SPDM_STATUS_VERIF_FAIL)
+    }
+}
+
+#[cfg(all(test,))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_case0_hmac() {
+        // RFC 4231 test case 2
+        let spdm_digest = hmac(
+            SpdmBaseHashAlgo::TPM_ALG_SHA_256,
+            b"Jefe",
+            b"what do ya want for nothing?",
+        )
+        .unwrap();
+        assert_eq!(
+            spdm_digest.as_ref(),
+            &[
+                0x5b, 0xdc, 0xc1, 0x46, 0xbf, 0x60, 0x75, 0x4e, 0x6a, 0x04, 0x24, 0x26, 0x08, 0x95,
+                0x75, 0xc7, 0x5a, 0x00, 0x3f, 0x08, 0x9d, 0x27, 0x39, 0x83, 0x9d, 0xec, 0x58, 0xb9,
+                0x64, 0xec, 0x38, 0x43
+            ]
+        );
+    }
+    #[test]
+    fn test_case0_hmac_verify() {
+        let key = &[10u8; 32];
+        let data = &[100u8; 64];
+        let wrong_data = &[101u8; 64];
+
+        for base_hash_algo in [
+            SpdmBaseHashAlgo::TPM_ALG_SHA_384,
+            SpdmBaseHashAlgo::TPM_ALG_SHA_512,
+            SpdmBaseHashAlgo::TPM_ALG_SHA3_256,
+            SpdmBaseHashAlgo::TPM_ALG_SHA3_384,
+        ]
+        .iter()
+        {
+            let spdm_digest = hmac(*base_hash_algo, key, data).unwrap();
+            assert_eq!(spdm_digest.data_size, base_hash_algo.get_size());
+            assert!(hmac_verify(*base_hash_algo, key, data, &spdm_digest).is_ok());
+            assert!(hmac_verify(*base_hash_algo, key, wrong_data, &spdm_digest).is_err());
+        }
+    }
+    #[test]
+    fn test_case1_hmac() {
+        let base_hash_algo = SpdmBaseHashAlgo::TPM_ALG_SM3_256;
+        assert!(hmac(base_hash_algo, &[10u8; 32], &[100u8; 64]).is_none());
+    }
+}
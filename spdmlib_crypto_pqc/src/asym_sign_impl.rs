@@ -0,0 +1,94 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use core::convert::TryFrom;
+use ml_dsa::{KeyGen, MlDsa44, MlDsa65, MlDsa87, MlDsaParams, B32};
+use pkcs8::der::Decode;
+use pkcs8::PrivateKeyInfo;
+use spdmlib::protocol::{SpdmPqcAsymAlgo, SpdmSignatureStruct};
+
+use crate::cert_operation_impl::get_pqc_asym_algo_by_oid;
+
+const ML_DSA_SEED_SIZE: usize = 32;
+
+/// Sign `data` with a PKCS#8 DER encoded ML-DSA private key.
+///
+/// The key must carry the seed, that is the `seed` or `both` private key
+/// formats of OpenSSL. The signature is a deterministic pure ML-DSA
+/// signature with an empty context string.
+pub fn sign(
+    pqc_asym_algo: SpdmPqcAsymAlgo,
+    private_key_der: &[u8],
+    data: &[u8],
+) -> Option<SpdmSignatureStruct> {
+    let private_key_info = PrivateKeyInfo::from_der(private_key_der).ok()?;
+    if get_pqc_asym_algo_by_oid(&private_key_info.algorithm.oid) != Some(pqc_asym_algo) {
+        return None;
+    }
+    let seed = get_seed(private_key_info.private_key)?;
+
+    match pqc_asym_algo {
+        SpdmPqcAsymAlgo::ML_DSA_44 => sign_with_seed::<MlDsa44>(&seed, data),
+        SpdmPqcAsymAlgo::ML_DSA_65 => sign_with_seed::<MlDsa65>(&seed, data),
+        SpdmPqcAsymAlgo::ML_DSA_87 => sign_with_seed::<MlDsa87>(&seed, data),
+        _ => None,
+    }
+}
+
+// The private key is one of
+//   seed [0] IMPLICIT OCTET STRING (SIZE (32))
+//   both SEQUENCE { seed OCTET STRING (SIZE (32)), expandedKey OCTET STRING }
+// or the bare seed of earlier drafts.
+fn get_seed(private_key: &[u8]) -> Option<B32> {
+    let seed = match private_key {
+        [0x80, 0x20, seed @ ..] => seed,
+        [0x30, 0x82, _, _, 0x04, 0x20, rest @ ..] if rest.len() > ML_DSA_SEED_SIZE => {
+            &rest[..ML_DSA_SEED_SIZE]
+        }
+        _ => private_key,
+    };
+    B32::try_from(seed).ok()
+}
+
+fn sign_with_seed<P: MlDsaParams>(seed: &B32, data: &[u8]) -> Option<SpdmSignatureStruct> {
+    let key_pair = P::key_gen_internal(seed);
+    let signature = key_pair
+        .signing_key()
+        .sign_deterministic(data, &[])
+        .ok()?
+        .encode();
+
+    let mut full_signature = SpdmSignatureStruct {
+        data_size: signature.len() as u16,
+        ..Default::default()
+    };
+    full_signature.data[..signature.len()].copy_from_slice(&signature);
+    Some(full_signature)
+}
+
+#[cfg(all(test,))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_case0_get_seed() {
+        let seed = [0x5au8; ML_DSA_SEED_SIZE];
+        let mut private_key = [0u8; ML_DSA_SEED_SIZE + 2];
+        private_key[..2].copy_from_slice(&[0x80, 0x20]);
+        private_key[2..].copy_from_slice(&seed);
+        assert_eq!(get_seed(&private_key).unwrap().as_slice(), &seed);
+        assert_eq!(get_seed(&seed).unwrap().as_slice(), &seed);
+        assert!(get_seed(&private_key[..ML_DSA_SEED_SIZE]).is_none());
+    }
+
+    #[test]
+    fn test_case0_sign() {
+        let private_key = std::fs::read("../test_key/mldsa65/end_responder.key.p8")
+            .expect("unable to read private key!");
+        let data = [0x5au8; 100];
+        assert!(sign(SpdmPqcAsymAlgo::ML_DSA_65, &private_key, &data).is_some());
+        assert!(sign(SpdmPqcAsymAlgo::ML_DSA_87, &private_key, &data).is_none());
+        assert!(sign(SpdmPqcAsymAlgo::ML_DSA_65, &private_key[1..], &data).is_none());
+    }
+}
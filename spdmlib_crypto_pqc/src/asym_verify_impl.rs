@@ -0,0 +1,154 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use core::convert::TryFrom;
+use ml_dsa::{
+    EncodedVerifyingKey, MlDsa44, MlDsa65, MlDsa87, MlDsaParams, Signature, VerifyingKey,
+};
+use spdmlib::crypto::SpdmAsymVerify;
+use spdmlib::error::{
+    SpdmResult, SPDM_STATUS_INVALID_CERT, SPDM_STATUS_UNSUPPORTED_CAP, SPDM_STATUS_VERIF_FAIL,
+};
+use spdmlib::protocol::{SpdmBaseAsymAlgo, SpdmBaseHashAlgo, SpdmPqcAsymAlgo, SpdmSignatureStruct};
+use x509_cert::der::Decode;
+use x509_cert::Certificate;
+
+use crate::cert_operation_impl::{get_cert_from_cert_chain, get_pqc_asym_algo_by_oid};
+
+pub static DEFAULT: SpdmAsymVerify = SpdmAsymVerify {
+    verify_cb: asym_verify,
+    verify_with_public_key_cb: asym_verify_with_public_key,
+    pqc_verify_cb: pqc_asym_verify,
+};
+
+// The classical algorithms are left to the backend this one is combined with.
+fn asym_verify(
+    _base_hash_algo: SpdmBaseHashAlgo,
+    _base_asym_algo: SpdmBaseAsymAlgo,
+    _public_cert_der: &[u8],
+    _data: &[u8],
+    _signature: &SpdmSignatureStruct,
+) -> SpdmResult {
+    Err(SPDM_STATUS_UNSUPPORTED_CAP)
+}
+
+fn asym_verify_with_public_key(
+    _base_hash_algo: SpdmBaseHashAlgo,
+    _base_asym_algo: SpdmBaseAsymAlgo,
+    _public_key_der: &[u8],
+    _data: &[u8],
+    _signature: &SpdmSignatureStruct,
+) -> SpdmResult {
+    Err(SPDM_STATUS_UNSUPPORTED_CAP)
+}
+
+/// Verify an ML-DSA signature with the leaf certificate of `public_cert_der`.
+///
+/// The signature is a pure ML-DSA signature of the message with an empty
+/// context string, so the hash algorithm doesn't matter.
+pub fn pqc_asym_verify(
+    _base_hash_algo: SpdmBaseHashAlgo,
+    pqc_asym_algo: SpdmPqcAsymAlgo,
+    public_cert_der: &[u8],
+    data: &[u8],
+    signature: &SpdmSignatureStruct,
+) -> SpdmResult {
+    let (leaf_begin, leaf_end) = get_cert_from_cert_chain(public_cert_der, -1)?;
+    let cert = Certificate::from_der(&public_cert_der[leaf_begin..leaf_end])
+        .map_err(|_| SPDM_STATUS_INVALID_CERT)?;
+    let spki = &cert.tbs_certificate.subject_public_key_info;
+    if get_pqc_asym_algo_by_oid(&spki.algorithm.oid) != Some(pqc_asym_algo) {
+        return Err(SPDM_STATUS_VERIF_FAIL);
+    }
+    if signature.data_size != pqc_asym_algo.get_size() {
+        return Err(SPDM_STATUS_VERIF_FAIL);
+    }
+    let public_key = spki
+        .subject_public_key
+        .as_bytes()
+        .ok_or(SPDM_STATUS_INVALID_CERT)?;
+
+    ml_dsa_verify(pqc_asym_algo, public_key, data, signature.as_ref())
+}
+
+// IN raw public key, message and encoded signature
+pub(crate) fn ml_dsa_verify(
+    pqc_asym_algo: SpdmPqcAsymAlgo,
+    public_key: &[u8],
+    data: &[u8],
+    signature: &[u8],
+) -> SpdmResult {
+    match pqc_asym_algo {
+        SpdmPqcAsymAlgo::ML_DSA_44 => verify::<MlDsa44>(public_key, data, signature),
+        SpdmPqcAsymAlgo::ML_DSA_65 => verify::<MlDsa65>(public_key, data, signature),
+        SpdmPqcAsymAlgo::ML_DSA_87 => verify::<MlDsa87>(public_key, data, signature),
+        _ => Err(SPDM_STATUS_VERIF_FAIL),
+    }
+}
+
+fn verify<P: MlDsaParams>(public_key: &[u8], data: &[u8], signature: &[u8]) -> SpdmResult {
+    let public_key =
+        EncodedVerifyingKey::<P>::try_from(public_key).map_err(|_| SPDM_STATUS_VERIF_FAIL)?;
+    let verifying_key = VerifyingKey::<P>::decode(&public_key);
+    let signature = Signature::<P>::try_from(signature).map_err(|_| SPDM_STATUS_VERIF_FAIL)?;
+    if verifying_key.verify_with_context(data, &[], &signature) {
+        Ok(())
+    } else {
+        Err(SPDM_STATUS_VERIF_FAIL)
+    }
+}
+
+#[cfg(all(test,))]
+mod tests {
+    use super::*;
+    use crate::asym_sign_impl::sign;
+    use spdmlib::protocol::{
+        SPDM_CHALLENGE_AUTH_SIGN_CONTEXT, SPDM_VERSION_1_2_SIGNING_CONTEXT_SIZE,
+        SPDM_VERSION_1_4_SIGNING_PREFIX_CONTEXT,
+    };
+
+    #[test]
+    fn test_case0_pqc_asym_verify() {
+        let base_hash_algo = SpdmBaseHashAlgo::TPM_ALG_SHA_384;
+        let pqc_asym_algo = SpdmPqcAsymAlgo::ML_DSA_65;
+        let private_key = std::fs::read("../test_key/mldsa65/end_responder.key.p8")
+            .expect("unable to read private key!");
+        let cert_chain = std::fs::read("../test_key/mldsa65/bundle_responder.certchain.der")
+            .expect("unable to read cert chain!");
+
+        let mut data = [0x5au8; SPDM_VERSION_1_2_SIGNING_CONTEXT_SIZE + 48];
+        data[..64].copy_from_slice(&SPDM_VERSION_1_4_SIGNING_PREFIX_CONTEXT);
+        data[64..68].fill(0);
+        data[68..100].copy_from_slice(&SPDM_CHALLENGE_AUTH_SIGN_CONTEXT);
+
+        let signature = sign(pqc_asym_algo, &private_key, &data).unwrap();
+        assert_eq!(signature.data_size, pqc_asym_algo.get_size());
+        assert!(pqc_asym_verify(
+            base_hash_algo,
+            pqc_asym_algo,
+            &cert_chain,
+            &data,
+            &signature
+        )
+        .is_ok());
+
+        data[100] ^= 1;
+        assert!(pqc_asym_verify(
+            base_hash_algo,
+            pqc_asym_algo,
+            &cert_chain,
+            &data,
+            &signature
+        )
+        .is_err());
+        assert!(pqc_asym_verify(
+            base_hash_algo,
+            SpdmPqcAsymAlgo::ML_DSA_87,
+            &cert_chain,
+            &data,
+            &signature
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,148 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+extern crate alloc;
+use alloc::vec::Vec;
+
+use crate::asym_verify_impl::ml_dsa_verify;
+use spdmlib::crypto::{is_ca_certificate, SpdmCertOperation};
+use spdmlib::error::{SpdmResult, SPDM_STATUS_INVALID_CERT};
+use spdmlib::protocol::SpdmPqcAsymAlgo;
+use x509_cert::der::asn1::ObjectIdentifier;
+use x509_cert::der::{Decode, Encode};
+use x509_cert::Certificate;
+
+// id-ml-dsa-44/65/87 2.16.840.1.101.3.4.3.17-19
+const OID_ML_DSA_44: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.3.17");
+const OID_ML_DSA_65: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.3.18");
+const OID_ML_DSA_87: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.3.19");
+
+pub static DEFAULT: SpdmCertOperation = SpdmCertOperation {
+    get_cert_from_cert_chain_cb: get_cert_from_cert_chain,
+    verify_cert_chain_cb: verify_cert_chain,
+};
+
+pub(crate) fn get_pqc_asym_algo_by_oid(oid: &ObjectIdentifier) -> Option<SpdmPqcAsymAlgo> {
+    match *oid {
+        OID_ML_DSA_44 => Some(SpdmPqcAsymAlgo::ML_DSA_44),
+        OID_ML_DSA_65 => Some(SpdmPqcAsymAlgo::ML_DSA_65),
+        OID_ML_DSA_87 => Some(SpdmPqcAsymAlgo::ML_DSA_87),
+        _ => None,
+    }
+}
+
+pub(crate) fn get_cert_from_cert_chain(
+    cert_chain: &[u8],
+    index: isize,
+) -> SpdmResult<(usize, usize)> {
+    let mut offset = 0usize;
+    let mut this_index = 0isize;
+    let cert_chain_size = cert_chain.len();
+    loop {
+        if offset > cert_chain_size || cert_chain[offset..].len() < 4 {
+            return Err(SPDM_STATUS_INVALID_CERT);
+        }
+        if cert_chain[offset] != 0x30 || cert_chain[offset + 1] != 0x82 {
+            return Err(SPDM_STATUS_INVALID_CERT);
+        }
+        let this_cert_len =
+            ((cert_chain[offset + 2] as usize) << 8) + (cert_chain[offset + 3] as usize) + 4;
+        if this_cert_len > cert_chain_size - offset {
+            return Err(SPDM_STATUS_INVALID_CERT);
+        }
+        if this_index == index {
+            // return the this one
+            return Ok((offset, offset + this_cert_len));
+        }
+        this_index += 1;
+        if (offset + this_cert_len == cert_chain_size) && (index == -1) {
+            // return the last one
+            return Ok((offset, offset + this_cert_len));
+        }
+        offset += this_cert_len;
+    }
+}
+
+// Checks that every certificate is signed by its predecessor with ML-DSA,
+// starting from a self-signed root. Only in the alias certificate model may
+// the chain be partial, ending at the device certificate, a CA. The
+// certificate model and the leaf fields are checked by spdmlib itself.
+fn verify_cert_chain(
+    cert_chain: &[u8],
+    _is_requester_cert: bool,
+    is_alias_cert_model: bool,
+) -> SpdmResult {
+    let mut certs = Vec::new();
+    let mut offset = 0usize;
+    let mut leaf_begin = 0usize;
+    while offset < cert_chain.len() {
+        let (_, end) = get_cert_from_cert_chain(&cert_chain[offset..], 0)?;
+        let cert = Certificate::from_der(&cert_chain[offset..(offset + end)])
+            .map_err(|_| SPDM_STATUS_INVALID_CERT)?;
+        certs.push(cert);
+        leaf_begin = offset;
+        offset += end;
+    }
+    if certs.is_empty() {
+        return Err(SPDM_STATUS_INVALID_CERT);
+    }
+    if !is_alias_cert_model && is_ca_certificate(&cert_chain[leaf_begin..])? {
+        return Err(SPDM_STATUS_INVALID_CERT);
+    }
+
+    verify_cert_signature(&certs[0], &certs[0])?;
+    for pair in certs.windows(2) {
+        verify_cert_signature(&pair[0], &pair[1])?;
+    }
+    Ok(())
+}
+
+fn verify_cert_signature(issuer: &Certificate, cert: &Certificate) -> SpdmResult {
+    let issuer_spki = &issuer.tbs_certificate.subject_public_key_info;
+    let pqc_asym_algo =
+        get_pqc_asym_algo_by_oid(&cert.signature_algorithm.oid).ok_or(SPDM_STATUS_INVALID_CERT)?;
+    if issuer_spki.algorithm.oid != cert.signature_algorithm.oid
+        || cert.tbs_certificate.issuer != issuer.tbs_certificate.subject
+    {
+        return Err(SPDM_STATUS_INVALID_CERT);
+    }
+    let public_key = issuer_spki
+        .subject_public_key
+        .as_bytes()
+        .ok_or(SPDM_STATUS_INVALID_CERT)?;
+    let tbs = cert
+        .tbs_certificate
+        .to_der()
+        .map_err(|_| SPDM_STATUS_INVALID_CERT)?;
+    let signature = cert.signature.as_bytes().ok_or(SPDM_STATUS_INVALID_CERT)?;
+
+    ml_dsa_verify(pqc_asym_algo, public_key, &tbs, signature).map_err(|_| SPDM_STATUS_INVALID_CERT)
+}
+
+#[cfg(all(test,))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_case0_get_cert_from_cert_chain() {
+        let cert_chain = std::fs::read("../test_key/mldsa65/bundle_responder.certchain.der")
+            .expect("unable to read cert chain!");
+        let (start, end) = get_cert_from_cert_chain(&cert_chain, -1).unwrap();
+        assert_eq!(end, cert_chain.len());
+        assert!(Certificate::from_der(&cert_chain[start..end]).is_ok());
+        assert!(get_cert_from_cert_chain(&cert_chain, 3).is_err());
+    }
+
+    #[test]
+    fn test_case0_verify_cert_chain() {
+        let cert_chain = std::fs::read("../test_key/mldsa65/bundle_responder.certchain.der")
+            .expect("unable to read cert chain!");
+        assert!(verify_cert_chain(&cert_chain, false, false).is_ok());
+
+        // The intermediate cert is not self-signed.
+        let (start, _) = get_cert_from_cert_chain(&cert_chain, 1).unwrap();
+        assert!(verify_cert_chain(&cert_chain[start..], false, false).is_err());
+        assert!(verify_cert_chain(&[], false, false).is_err());
+    }
+}
@@ -0,0 +1,134 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+extern crate alloc;
+use alloc::boxed::Box;
+
+use core::convert::TryFrom;
+use kem::{Decapsulate, Encapsulate};
+use ml_kem::array::Array;
+use ml_kem::{Ciphertext, EncodedSizeUser, KemCore, MlKem1024, MlKem512, MlKem768};
+use rand_core::OsRng;
+use spdmlib::crypto::{SpdmDhe, SpdmDheKeyExchange};
+use spdmlib::protocol::{SpdmDheAlgo, SpdmDheExchangeStruct, SpdmDheFinalKeyStruct, SpdmKemAlgo};
+
+pub static DEFAULT: SpdmDhe = SpdmDhe {
+    generate_key_pair_cb: generate_key_pair,
+    kem_generate_key_pair_cb: kem_generate_key_pair,
+    kem_encapsulate_cb: kem_encapsulate,
+};
+
+// The classical groups are left to the backend this one is combined with.
+fn generate_key_pair(
+    _dhe_algo: SpdmDheAlgo,
+) -> Option<(SpdmDheExchangeStruct, Box<dyn SpdmDheKeyExchange>)> {
+    None
+}
+
+/// Generate an ML-KEM key pair. The exchange data is the encapsulation key,
+/// and `compute_final_key` decapsulates the cipher text of the responder.
+pub fn kem_generate_key_pair(
+    kem_algo: SpdmKemAlgo,
+) -> Option<(SpdmDheExchangeStruct, Box<dyn SpdmDheKeyExchange>)> {
+    match kem_algo {
+        SpdmKemAlgo::ML_KEM_512 => SpdmKemDecapsulation::<MlKem512>::generate_key_pair(),
+        SpdmKemAlgo::ML_KEM_768 => SpdmKemDecapsulation::<MlKem768>::generate_key_pair(),
+        SpdmKemAlgo::ML_KEM_1024 => SpdmKemDecapsulation::<MlKem1024>::generate_key_pair(),
+        _ => None,
+    }
+}
+
+/// Encapsulate a shared secret to the encapsulation key of the requester,
+/// returning the cipher text and the shared secret.
+pub fn kem_encapsulate(
+    kem_algo: SpdmKemAlgo,
+    peer_encap_key: &SpdmDheExchangeStruct,
+) -> Option<(SpdmDheExchangeStruct, SpdmDheFinalKeyStruct)> {
+    match kem_algo {
+        SpdmKemAlgo::ML_KEM_512 => encapsulate::<MlKem512>(peer_encap_key),
+        SpdmKemAlgo::ML_KEM_768 => encapsulate::<MlKem768>(peer_encap_key),
+        SpdmKemAlgo::ML_KEM_1024 => encapsulate::<MlKem1024>(peer_encap_key),
+        _ => None,
+    }
+}
+
+fn encapsulate<K: KemCore>(
+    peer_encap_key: &SpdmDheExchangeStruct,
+) -> Option<(SpdmDheExchangeStruct, SpdmDheFinalKeyStruct)> {
+    let encap_key = Array::try_from(peer_encap_key.as_ref()).ok()?;
+    let encap_key = K::EncapsulationKey::from_bytes(&encap_key);
+    let (cipher_text, shared_secret) = encap_key.encapsulate(&mut OsRng).ok()?;
+
+    Some((
+        exchange_from_slice(&cipher_text),
+        SpdmDheFinalKeyStruct::from(shared_secret.as_slice()),
+    ))
+}
+
+fn exchange_from_slice(data: &[u8]) -> SpdmDheExchangeStruct {
+    let mut exchange = SpdmDheExchangeStruct {
+        data_size: data.len() as u16,
+        ..Default::default()
+    };
+    exchange.data[..data.len()].copy_from_slice(data);
+    exchange
+}
+
+struct SpdmKemDecapsulation<K: KemCore>(K::DecapsulationKey);
+
+impl<K: KemCore + 'static> SpdmKemDecapsulation<K> {
+    fn generate_key_pair() -> Option<(SpdmDheExchangeStruct, Box<dyn SpdmDheKeyExchange>)> {
+        let (decap_key, encap_key) = K::generate(&mut OsRng);
+        let public_key = exchange_from_slice(&encap_key.as_bytes());
+
+        let res: Box<dyn SpdmDheKeyExchange> = Box::new(Self(decap_key));
+        Some((public_key, res))
+    }
+}
+
+impl<K: KemCore> SpdmDheKeyExchange for SpdmKemDecapsulation<K> {
+    fn compute_final_key(
+        self: Box<Self>,
+        peer_pub_key: &SpdmDheExchangeStruct,
+    ) -> Option<SpdmDheFinalKeyStruct> {
+        let cipher_text = Ciphertext::<K>::try_from(peer_pub_key.as_ref()).ok()?;
+        let shared_secret = self.0.decapsulate(&cipher_text).ok()?;
+        Some(SpdmDheFinalKeyStruct::from(shared_secret.as_slice()))
+    }
+}
+
+#[cfg(all(test,))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_case0_kem() {
+        for kem_algo in [
+            SpdmKemAlgo::ML_KEM_512,
+            SpdmKemAlgo::ML_KEM_768,
+            SpdmKemAlgo::ML_KEM_1024,
+        ]
+        .iter()
+        {
+            let (encap_key, private) = kem_generate_key_pair(*kem_algo).unwrap();
+            assert_eq!(encap_key.data_size, kem_algo.get_size());
+
+            let (cipher_text, secret1) = kem_encapsulate(*kem_algo, &encap_key).unwrap();
+            assert_eq!(cipher_text.data_size, kem_algo.get_cipher_text_size());
+
+            let secret2 = private.compute_final_key(&cipher_text).unwrap();
+            assert_eq!(secret1.as_ref(), secret2.as_ref());
+        }
+    }
+    #[test]
+    fn test_case1_kem() {
+        let (encap_key, private) = kem_generate_key_pair(SpdmKemAlgo::ML_KEM_768).unwrap();
+        assert!(kem_encapsulate(SpdmKemAlgo::ML_KEM_1024, &encap_key).is_none());
+        assert!(kem_encapsulate(SpdmKemAlgo::empty(), &encap_key).is_none());
+        assert!(private.compute_final_key(&encap_key).is_none());
+
+        assert!(kem_generate_key_pair(SpdmKemAlgo::empty()).is_none());
+        assert!(generate_key_pair(SpdmDheAlgo::SECP_256_R1).is_none());
+    }
+}
@@ -0,0 +1,175 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use aes_gcm::aead::consts::{U12, U16};
+use aes_gcm::aead::{AeadCore, AeadInPlace, KeyInit};
+use aes_gcm::{Aes128Gcm, Aes256Gcm, Nonce, Tag};
+use chacha20poly1305::ChaCha20Poly1305;
+use spdmlib::crypto::SpdmAead;
+use spdmlib::error::{SpdmResult, SPDM_STATUS_CRYPTO_ERROR, SPDM_STATUS_INVALID_PARAMETER};
+use spdmlib::protocol::{SpdmAeadAlgo, SpdmAeadIvStruct, SpdmAeadKeyStruct};
+
+pub static DEFAULT: SpdmAead = SpdmAead {
+    encrypt_cb: encrypt,
+    decrypt_cb: decrypt,
+};
+
+fn check_sizes(
+    aead_algo: SpdmAeadAlgo,
+    key: &SpdmAeadKeyStruct,
+    iv: &SpdmAeadIvStruct,
+    tag: &[u8],
+) -> SpdmResult {
+    match aead_algo {
+        SpdmAeadAlgo::AES_128_GCM | SpdmAeadAlgo::AES_256_GCM | SpdmAeadAlgo::CHACHA20_POLY1305 => {
+        }
+        _ => return Err(SPDM_STATUS_INVALID_PARAMETER),
+    }
+    if key.data_size != aead_algo.get_key_size()
+        || iv.data_size != aead_algo.get_iv_size()
+        || tag.len() != aead_algo.get_tag_size() as usize
+    {
+        return Err(SPDM_STATUS_INVALID_PARAMETER);
+    }
+    Ok(())
+}
+
+fn seal<C: KeyInit + AeadInPlace + AeadCore<NonceSize = U12, TagSize = U16>>(
+    key: &[u8],
+    iv: &[u8],
+    aad: &[u8],
+    buffer: &mut [u8],
+    tag: &mut [u8],
+) -> SpdmResult {
+    let cipher = C::new_from_slice(key).map_err(|_| SPDM_STATUS_CRYPTO_ERROR)?;
+    let tag_out = cipher
+        .encrypt_in_place_detached(Nonce::from_slice(iv), aad, buffer)
+        .map_err(|_| SPDM_STATUS_CRYPTO_ERROR)?;
+    tag.copy_from_slice(tag_out.as_slice());
+    Ok(())
+}
+
+fn open<C: KeyInit + AeadInPlace + AeadCore<NonceSize = U12, TagSize = U16>>(
+    key: &[u8],
+    iv: &[u8],
+    aad: &[u8],
+    buffer: &mut [u8],
+    tag: &[u8],
+) -> SpdmResult {
+    let cipher = C::new_from_slice(key).map_err(|_| SPDM_STATUS_CRYPTO_ERROR)?;
+    cipher
+        .decrypt_in_place_detached(Nonce::from_slice(iv), aad, buffer, Tag::from_slice(tag))
+        .map_err(|_| SPDM_STATUS_CRYPTO_ERROR)
+}
+
+fn encrypt(
+    aead_algo: SpdmAeadAlgo,
+    key: &SpdmAeadKeyStruct,
+    iv: &SpdmAeadIvStruct,
+    aad: &[u8],
+    plain_text: &[u8],
+    tag: &mut [u8],
+    cipher_text: &mut [u8],
+) -> SpdmResult<(usize, usize)> {
+    check_sizes(aead_algo, key, iv, tag)?;
+    if cipher_text.len() < plain_text.len() {
+        return Err(SPDM_STATUS_INVALID_PARAMETER);
+    }
+
+    let cipher_text = &mut cipher_text[..plain_text.len()];
+    cipher_text.copy_from_slice(plain_text);
+    let (key, iv) = (key.as_ref(), iv.as_ref());
+    match aead_algo {
+        SpdmAeadAlgo::AES_128_GCM => seal::<Aes128Gcm>(key, iv, aad, cipher_text, tag)?,
+        SpdmAeadAlgo::AES_256_GCM => seal::<Aes256Gcm>(key, iv, aad, cipher_text, tag)?,
+        _ => seal::<ChaCha20Poly1305>(key, iv, aad, cipher_text, tag)?,
+    }
+    Ok((plain_text.len(), tag.len()))
+}
+
+fn decrypt(
+    aead_algo: SpdmAeadAlgo,
+    key: &SpdmAeadKeyStruct,
+    iv: &SpdmAeadIvStruct,
+    aad: &[u8],
+    cipher_text: &[u8],
+    tag: &[u8],
+    plain_text: &mut [u8],
+) -> SpdmResult<usize> {
+    check_sizes(aead_algo, key, iv, tag)?;
+    if plain_text.len() < cipher_text.len() {
+        return Err(SPDM_STATUS_INVALID_PARAMETER);
+    }
+
+    let plain_text = &mut plain_text[..cipher_text.len()];
+    plain_text.copy_from_slice(cipher_text);
+    let (key, iv) = (key.as_ref(), iv.as_ref());
+    match aead_algo {
+        SpdmAeadAlgo::AES_128_GCM => open::<Aes128Gcm>(key, iv, aad, plain_text, tag)?,
+        SpdmAeadAlgo::AES_256_GCM => open::<Aes256Gcm>(key, iv, aad, plain_text, tag)?,
+        _ => open::<ChaCha20Poly1305>(key, iv, aad, plain_text, tag)?,
+    }
+    Ok(cipher_text.len())
+}
+
+#[cfg(all(test,))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_case0_encrypt_decrypt() {
+        for aead_algo in [
+            SpdmAeadAlgo::AES_128_GCM,
+            SpdmAeadAlgo::AES_256_GCM,
+            SpdmAeadAlgo::CHACHA20_POLY1305,
+        ]
+        .iter()
+        {
+            let aead_algo = *aead_algo;
+            let key_size = aead_algo.get_key_size() as usize;
+            let key = &SpdmAeadKeyStruct::from(&[1u8; 32][..key_size]);
+            let iv = &SpdmAeadIvStruct::from(&[2u8; 12][..]);
+            let aad = &[3u8; 16];
+            let plain_text = &[4u8; 60];
+            let tag = &mut [0u8; 16];
+            let cipher_text = &mut [0u8; 60];
+
+            let (cipher_len, tag_len) =
+                encrypt(aead_algo, key, iv, aad, plain_text, tag, cipher_text).unwrap();
+            assert_eq!(cipher_len, 60);
+            assert_eq!(tag_len, 16);
+            assert_ne!(&cipher_text[..], &plain_text[..]);
+
+            let decrypted = &mut [0u8; 60];
+            let plain_len = decrypt(aead_algo, key, iv, aad, cipher_text, tag, decrypted).unwrap();
+            assert_eq!(plain_len, 60);
+            assert_eq!(&decrypted[..], &plain_text[..]);
+
+            tag[0] ^= 1;
+            assert!(decrypt(aead_algo, key, iv, aad, cipher_text, tag, decrypted).is_err());
+        }
+    }
+    #[test]
+    fn test_case1_encrypt() {
+        let key = &SpdmAeadKeyStruct::from(&[1u8; 16][..]);
+        let iv = &SpdmAeadIvStruct::from(&[2u8; 12][..]);
+        let tag = &mut [0u8; 16];
+        let cipher_text = &mut [0u8; 16];
+
+        // wrong key size and unsupported algorithm
+        for aead_algo in [SpdmAeadAlgo::AES_256_GCM, SpdmAeadAlgo::SM4_GCM].iter() {
+            assert!(encrypt(*aead_algo, key, iv, &[], &[4u8; 16], tag, cipher_text).is_err());
+        }
+        assert!(encrypt(
+            SpdmAeadAlgo::AES_128_GCM,
+            key,
+            iv,
+            &[],
+            &[4u8; 32],
+            tag,
+            cipher_text
+        )
+        .is_err());
+    }
+}
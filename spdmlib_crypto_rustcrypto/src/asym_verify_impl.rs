@@ -0,0 +1,285 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use core::convert::TryFrom;
+use ed25519_dalek::Verifier;
+use p256::ecdsa::signature::hazmat::PrehashVerifier;
+use rsa::pkcs1::DecodeRsaPublicKey;
+use rsa::traits::PublicKeyParts;
+use rsa::{Pkcs1v15Sign, Pss, RsaPublicKey};
+use sha2::{Sha256, Sha384, Sha512};
+use spdmlib::crypto::{get_public_key_from_spki, SpdmAsymVerify};
+use spdmlib::error::{
+    SpdmResult, SPDM_STATUS_INVALID_CERT, SPDM_STATUS_UNSUPPORTED_CAP, SPDM_STATUS_VERIF_FAIL,
+};
+use spdmlib::protocol::{SpdmBaseAsymAlgo, SpdmBaseHashAlgo, SpdmPqcAsymAlgo, SpdmSignatureStruct};
+use x509_cert::der::Decode;
+use x509_cert::Certificate;
+
+use crate::cert_operation_impl::get_cert_from_cert_chain;
+use crate::hash_impl::hash_all;
+
+pub static DEFAULT: SpdmAsymVerify = SpdmAsymVerify {
+    verify_cb: asym_verify,
+    verify_with_public_key_cb: asym_verify_with_public_key,
+    pqc_verify_cb: pqc_asym_verify,
+};
+
+fn asym_verify(
+    base_hash_algo: SpdmBaseHashAlgo,
+    base_asym_algo: SpdmBaseAsymAlgo,
+    public_cert_der: &[u8],
+    data: &[u8],
+    signature: &SpdmSignatureStruct,
+) -> SpdmResult {
+    let (leaf_begin, leaf_end) = get_cert_from_cert_chain(public_cert_der, -1)?;
+    let cert = Certificate::from_der(&public_cert_der[leaf_begin..leaf_end])
+        .map_err(|_| SPDM_STATUS_INVALID_CERT)?;
+    let public_key = cert
+        .tbs_certificate
+        .subject_public_key_info
+        .subject_public_key
+        .as_bytes()
+        .ok_or(SPDM_STATUS_INVALID_CERT)?;
+    verify_signature(base_hash_algo, base_asym_algo, public_key, data, signature)
+}
+
+fn asym_verify_with_public_key(
+    base_hash_algo: SpdmBaseHashAlgo,
+    base_asym_algo: SpdmBaseAsymAlgo,
+    public_key_der: &[u8],
+    data: &[u8],
+    signature: &SpdmSignatureStruct,
+) -> SpdmResult {
+    let public_key = get_public_key_from_spki(public_key_der)?;
+    verify_signature(base_hash_algo, base_asym_algo, public_key, data, signature)
+}
+
+fn pqc_asym_verify(
+    _base_hash_algo: SpdmBaseHashAlgo,
+    _pqc_asym_algo: SpdmPqcAsymAlgo,
+    _public_cert_der: &[u8],
+    _data: &[u8],
+    _signature: &SpdmSignatureStruct,
+) -> SpdmResult {
+    Err(SPDM_STATUS_UNSUPPORTED_CAP)
+}
+
+// IN the content of the subjectPublicKey BIT STRING, that is the PKCS#1
+// RSAPublicKey, the uncompressed EC point or the raw EdDSA key.
+pub(crate) fn verify_signature(
+    base_hash_algo: SpdmBaseHashAlgo,
+    base_asym_algo: SpdmBaseAsymAlgo,
+    public_key: &[u8],
+    data: &[u8],
+    signature: &SpdmSignatureStruct,
+) -> SpdmResult {
+    if signature.data_size != base_asym_algo.get_size() {
+        return Err(SPDM_STATUS_VERIF_FAIL);
+    }
+
+    match base_asym_algo {
+        SpdmBaseAsymAlgo::TPM_ALG_RSASSA_2048
+        | SpdmBaseAsymAlgo::TPM_ALG_RSASSA_3072
+        | SpdmBaseAsymAlgo::TPM_ALG_RSASSA_4096
+        | SpdmBaseAsymAlgo::TPM_ALG_RSAPSS_2048
+        | SpdmBaseAsymAlgo::TPM_ALG_RSAPSS_3072
+        | SpdmBaseAsymAlgo::TPM_ALG_RSAPSS_4096 => {
+            rsa_verify(base_hash_algo, base_asym_algo, public_key, data, signature)
+        }
+        SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P256
+        | SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384
+        | SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P521 => {
+            ecdsa_verify(base_hash_algo, base_asym_algo, public_key, data, signature)
+        }
+        SpdmBaseAsymAlgo::EDDSA_ED25519 => ed25519_verify(public_key, data, signature),
+        _ => Err(SPDM_STATUS_VERIF_FAIL),
+    }
+}
+
+fn rsa_verify(
+    base_hash_algo: SpdmBaseHashAlgo,
+    base_asym_algo: SpdmBaseAsymAlgo,
+    public_key: &[u8],
+    data: &[u8],
+    signature: &SpdmSignatureStruct,
+) -> SpdmResult {
+    let public_key =
+        RsaPublicKey::from_pkcs1_der(public_key).map_err(|_| SPDM_STATUS_VERIF_FAIL)?;
+    if public_key.size() != signature.data_size as usize {
+        return Err(SPDM_STATUS_VERIF_FAIL);
+    }
+    let digest = hash_all(base_hash_algo, data).ok_or(SPDM_STATUS_VERIF_FAIL)?;
+    let is_pss = matches!(
+        base_asym_algo,
+        SpdmBaseAsymAlgo::TPM_ALG_RSAPSS_2048
+            | SpdmBaseAsymAlgo::TPM_ALG_RSAPSS_3072
+            | SpdmBaseAsymAlgo::TPM_ALG_RSAPSS_4096
+    );
+
+    // The salt length of RSASSA-PSS is the length of the hash.
+    let res = match (base_hash_algo, is_pss) {
+        (SpdmBaseHashAlgo::TPM_ALG_SHA_256, false) => public_key.verify(
+            Pkcs1v15Sign::new::<Sha256>(),
+            digest.as_ref(),
+            signature.as_ref(),
+        ),
+        (SpdmBaseHashAlgo::TPM_ALG_SHA_384, false) => public_key.verify(
+            Pkcs1v15Sign::new::<Sha384>(),
+            digest.as_ref(),
+            signature.as_ref(),
+        ),
+        (SpdmBaseHashAlgo::TPM_ALG_SHA_512, false) => public_key.verify(
+            Pkcs1v15Sign::new::<Sha512>(),
+            digest.as_ref(),
+            signature.as_ref(),
+        ),
+        (SpdmBaseHashAlgo::TPM_ALG_SHA_256, true) => {
+            public_key.verify(Pss::new::<Sha256>(), digest.as_ref(), signature.as_ref())
+        }
+        (SpdmBaseHashAlgo::TPM_ALG_SHA_384, true) => {
+            public_key.verify(Pss::new::<Sha384>(), digest.as_ref(), signature.as_ref())
+        }
+        (SpdmBaseHashAlgo::TPM_ALG_SHA_512, true) => {
+            public_key.verify(Pss::new::<Sha512>(), digest.as_ref(), signature.as_ref())
+        }
+        _ => return Err(SPDM_STATUS_VERIF_FAIL),
+    };
+    res.map_err(|_| SPDM_STATUS_VERIF_FAIL)
+}
+
+// The ECDSA signature of SPDM is the fixed size r || s.
+fn ecdsa_verify(
+    base_hash_algo: SpdmBaseHashAlgo,
+    base_asym_algo: SpdmBaseAsymAlgo,
+    public_key: &[u8],
+    data: &[u8],
+    signature: &SpdmSignatureStruct,
+) -> SpdmResult {
+    let digest = hash_all(base_hash_algo, data).ok_or(SPDM_STATUS_VERIF_FAIL)?;
+    let (digest, signature) = (digest.as_ref(), signature.as_ref());
+    let res = match base_asym_algo {
+        SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P256 => {
+            let verifying_key = p256::ecdsa::VerifyingKey::from_sec1_bytes(public_key)
+                .map_err(|_| SPDM_STATUS_VERIF_FAIL)?;
+            let signature = p256::ecdsa::Signature::from_slice(signature)
+                .map_err(|_| SPDM_STATUS_VERIF_FAIL)?;
+            verifying_key.verify_prehash(digest, &signature)
+        }
+        SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384 => {
+            let verifying_key = p384::ecdsa::VerifyingKey::from_sec1_bytes(public_key)
+                .map_err(|_| SPDM_STATUS_VERIF_FAIL)?;
+            let signature = p384::ecdsa::Signature::from_slice(signature)
+                .map_err(|_| SPDM_STATUS_VERIF_FAIL)?;
+            verifying_key.verify_prehash(digest, &signature)
+        }
+        SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P521 => {
+            let verifying_key = p521::ecdsa::VerifyingKey::from_sec1_bytes(public_key)
+                .map_err(|_| SPDM_STATUS_VERIF_FAIL)?;
+            let signature = p521::ecdsa::Signature::from_slice(signature)
+                .map_err(|_| SPDM_STATUS_VERIF_FAIL)?;
+            verifying_key.verify_prehash(digest, &signature)
+        }
+        _ => return Err(SPDM_STATUS_VERIF_FAIL),
+    };
+    res.map_err(|_| SPDM_STATUS_VERIF_FAIL)
+}
+
+// Ed25519 signs the message itself, whatever the hash algorithm.
+fn ed25519_verify(public_key: &[u8], data: &[u8], signature: &SpdmSignatureStruct) -> SpdmResult {
+    let public_key = <&[u8; ed25519_dalek::PUBLIC_KEY_LENGTH]>::try_from(public_key)
+        .map_err(|_| SPDM_STATUS_VERIF_FAIL)?;
+    let verifying_key =
+        ed25519_dalek::VerifyingKey::from_bytes(public_key).map_err(|_| SPDM_STATUS_VERIF_FAIL)?;
+    let signature = ed25519_dalek::Signature::from_slice(signature.as_ref())
+        .map_err(|_| SPDM_STATUS_VERIF_FAIL)?;
+    verifying_key
+        .verify(data, &signature)
+        .map_err(|_| SPDM_STATUS_VERIF_FAIL)
+}
+
+#[cfg(all(test,))]
+mod tests {
+    use super::*;
+    use p384::ecdsa::signature::hazmat::PrehashSigner;
+    use p384::pkcs8::DecodePrivateKey;
+
+    #[test]
+    fn test_case0_asym_verify() {
+        let base_hash_algo = SpdmBaseHashAlgo::TPM_ALG_SHA_384;
+        let base_asym_algo = SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384;
+        let private_key = std::fs::read("../test_key/ecp384/end_responder.key.p8")
+            .expect("unable to read private key!");
+        let cert_chain = std::fs::read("../test_key/ecp384/bundle_responder.certchain.der")
+            .expect("unable to read cert chain!");
+        let public_key = std::fs::read("../test_key/ecp384/end_responder.key.pub.der")
+            .expect("unable to read public key!");
+
+        let data = &mut [0x5au8; 100];
+        let signing_key = p384::ecdsa::SigningKey::from_pkcs8_der(&private_key).unwrap();
+        let digest = hash_all(base_hash_algo, data).unwrap();
+        let ecdsa_signature: p384::ecdsa::Signature =
+            signing_key.sign_prehash(digest.as_ref()).unwrap();
+        let mut signature = SpdmSignatureStruct {
+            data_size: base_asym_algo.get_size(),
+            ..Default::default()
+        };
+        signature.data[..96].copy_from_slice(&ecdsa_signature.to_bytes());
+
+        assert!(asym_verify(
+            base_hash_algo,
+            base_asym_algo,
+            &cert_chain,
+            data,
+            &signature
+        )
+        .is_ok());
+        assert!(asym_verify_with_public_key(
+            base_hash_algo,
+            base_asym_algo,
+            &public_key,
+            data,
+            &signature
+        )
+        .is_ok());
+
+        data[0] ^= 1;
+        assert!(asym_verify(
+            base_hash_algo,
+            base_asym_algo,
+            &cert_chain,
+            data,
+            &signature
+        )
+        .is_err());
+    }
+    #[test]
+    fn test_case1_asym_verify() {
+        let cert_chain = std::fs::read("../test_key/ecp384/bundle_responder.certchain.der")
+            .expect("unable to read cert chain!");
+        let data = &[0x5au8; 100];
+        let signature = SpdmSignatureStruct {
+            data_size: 96,
+            ..Default::default()
+        };
+
+        for base_asym_algo in [
+            SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P256,
+            SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384,
+            SpdmBaseAsymAlgo::TPM_ALG_RSASSA_3072,
+            SpdmBaseAsymAlgo::EDDSA_ED448,
+        ]
+        .iter()
+        {
+            assert!(asym_verify(
+                SpdmBaseHashAlgo::TPM_ALG_SHA_384,
+                *base_asym_algo,
+                &cert_chain,
+                data,
+                &signature
+            )
+            .is_err());
+        }
+    }
+}
@@ -0,0 +1,432 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+extern crate alloc;
+use alloc::vec::Vec;
+
+use crate::asym_verify_impl::verify_signature;
+use rsa::pkcs1::DecodeRsaPublicKey;
+use rsa::traits::PublicKeyParts;
+use rsa::RsaPublicKey;
+use spdmlib::crypto::{is_ca_certificate, SpdmCertOperation};
+use spdmlib::error::{SpdmResult, SPDM_STATUS_INVALID_CERT, SPDM_STATUS_INVALID_STATE_LOCAL};
+use spdmlib::protocol::{SpdmBaseAsymAlgo, SpdmBaseHashAlgo, SpdmSignatureStruct};
+use x509_cert::der::asn1::ObjectIdentifier;
+use x509_cert::der::{Decode, Encode};
+use x509_cert::ext::pkix::{BasicConstraints, ExtendedKeyUsage};
+use x509_cert::spki::SubjectPublicKeyInfoOwned;
+use x509_cert::Certificate;
+
+const OID_RSA_ENCRYPTION: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.1");
+const OID_SHA256_WITH_RSA: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.11");
+const OID_SHA384_WITH_RSA: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.12");
+const OID_SHA512_WITH_RSA: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.13");
+const OID_EC_PUBLIC_KEY: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.2.1");
+const OID_ECDSA_WITH_SHA256: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.4.3.2");
+const OID_ECDSA_WITH_SHA384: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.4.3.3");
+const OID_ECDSA_WITH_SHA512: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.4.3.4");
+const OID_SECP256R1: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.3.1.7");
+const OID_SECP384R1: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.132.0.34");
+const OID_SECP521R1: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.132.0.35");
+const OID_ED25519: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.101.112");
+const OID_BASIC_CONSTRAINTS: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.5.29.19");
+const OID_EXTENDED_KEY_USAGE: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.5.29.37");
+
+// id-kp-serverAuth 1.3.6.1.5.5.7.3.1, accepted for both roles
+const OID_EKU_SERVER_AUTH: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.3.1");
+// id-DMTF-eku-responder-auth 1.3.6.1.4.1.412.274.3.1
+const OID_EKU_DMTF_RESPONDER_AUTH: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.3.6.1.4.1.412.274.3.1");
+// id-DMTF-eku-requester-auth 1.3.6.1.4.1.412.274.3.2
+const OID_EKU_DMTF_REQUESTER_AUTH: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.3.6.1.4.1.412.274.3.2");
+
+pub static DEFAULT: SpdmCertOperation = SpdmCertOperation {
+    get_cert_from_cert_chain_cb: get_cert_from_cert_chain,
+    verify_cert_chain_cb: verify_cert_chain,
+};
+
+pub(crate) fn get_cert_from_cert_chain(
+    cert_chain: &[u8],
+    index: isize,
+) -> SpdmResult<(usize, usize)> {
+    let mut offset = 0usize;
+    let mut this_index = 0isize;
+    let cert_chain_size = cert_chain.len();
+    loop {
+        if offset > cert_chain_size || cert_chain[offset..].len() < 4 {
+            return Err(SPDM_STATUS_INVALID_CERT);
+        }
+        if cert_chain[offset] != 0x30 || cert_chain[offset + 1] != 0x82 {
+            return Err(SPDM_STATUS_INVALID_CERT);
+        }
+        let this_cert_len =
+            ((cert_chain[offset + 2] as usize) << 8) + (cert_chain[offset + 3] as usize) + 4;
+        if this_cert_len > cert_chain_size - offset {
+            return Err(SPDM_STATUS_INVALID_CERT);
+        }
+        if this_index == index {
+            // return the this one
+            return Ok((offset, offset + this_cert_len));
+        }
+        this_index += 1;
+        if (offset + this_cert_len == cert_chain_size) && (index == -1) {
+            // return the last one
+            return Ok((offset, offset + this_cert_len));
+        }
+        offset += this_cert_len;
+    }
+}
+
+// Checks that every certificate is signed by its predecessor, starting from
+// a self-signed root, and is valid now. The issuers shall be CAs and the
+// leaf shall allow the SPDM authentication EKU of its role.
+//
+// Only in the alias certificate model may the chain be partial, ending at the
+// device certificate, a CA whose EKU is not checked. The certificate model
+// and the leaf fields are checked by spdmlib itself.
+fn verify_cert_chain(
+    cert_chain: &[u8],
+    is_requester_cert: bool,
+    is_alias_cert_model: bool,
+) -> SpdmResult {
+    let mut certs = Vec::new();
+    let mut offset = 0usize;
+    let mut leaf_begin = 0usize;
+    while offset < cert_chain.len() {
+        let (_, end) = get_cert_from_cert_chain(&cert_chain[offset..], 0)?;
+        let cert = Certificate::from_der(&cert_chain[offset..(offset + end)])
+            .map_err(|_| SPDM_STATUS_INVALID_CERT)?;
+        certs.push(cert);
+        leaf_begin = offset;
+        offset += end;
+    }
+    if certs.is_empty() {
+        return Err(SPDM_STATUS_INVALID_CERT);
+    }
+
+    let is_partial_chain = is_ca_certificate(&cert_chain[leaf_begin..])?;
+    if is_partial_chain && !is_alias_cert_model {
+        return Err(SPDM_STATUS_INVALID_CERT);
+    }
+
+    verify_cert_signature(&certs[0], &certs[0])?;
+    for pair in certs.windows(2) {
+        if !is_ca(&pair[0])? {
+            return Err(SPDM_STATUS_INVALID_CERT);
+        }
+        verify_cert_signature(&pair[0], &pair[1])?;
+    }
+
+    let now = get_timestamp()?;
+    for cert in certs.iter() {
+        let validity = &cert.tbs_certificate.validity;
+        if now < validity.not_before.to_unix_duration().as_secs()
+            || now > validity.not_after.to_unix_duration().as_secs()
+        {
+            return Err(SPDM_STATUS_INVALID_CERT);
+        }
+    }
+
+    if is_partial_chain {
+        Ok(())
+    } else {
+        check_eku(&certs[certs.len() - 1], is_requester_cert)
+    }
+}
+
+fn get_timestamp() -> SpdmResult<u64> {
+    #[cfg(any(target_os = "uefi", target_os = "none"))]
+    {
+        sys_time::get_sys_time()
+            .map(|ts| ts as u64)
+            .ok_or(SPDM_STATUS_INVALID_STATE_LOCAL)
+    }
+    #[cfg(not(any(target_os = "uefi", target_os = "none")))]
+    {
+        extern crate std;
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|ds| ds.as_secs())
+            .map_err(|_| SPDM_STATUS_INVALID_STATE_LOCAL)
+    }
+}
+
+fn get_extension<'a>(cert: &'a Certificate, oid: &ObjectIdentifier) -> Option<&'a [u8]> {
+    cert.tbs_certificate
+        .extensions
+        .as_ref()?
+        .iter()
+        .find(|extension| extension.extn_id == *oid)
+        .map(|extension| extension.extn_value.as_bytes())
+}
+
+fn is_ca(cert: &Certificate) -> SpdmResult<bool> {
+    match get_extension(cert, &OID_BASIC_CONSTRAINTS) {
+        Some(value) => Ok(BasicConstraints::from_der(value)
+            .map_err(|_| SPDM_STATUS_INVALID_CERT)?
+            .ca),
+        None => Ok(false),
+    }
+}
+
+// A leaf without the extension may be used for any purpose.
+fn check_eku(cert: &Certificate, is_requester_cert: bool) -> SpdmResult {
+    let value = match get_extension(cert, &OID_EXTENDED_KEY_USAGE) {
+        Some(value) => value,
+        None => return Ok(()),
+    };
+    let eku = ExtendedKeyUsage::from_der(value).map_err(|_| SPDM_STATUS_INVALID_CERT)?;
+    let role_eku = if is_requester_cert {
+        OID_EKU_DMTF_REQUESTER_AUTH
+    } else {
+        OID_EKU_DMTF_RESPONDER_AUTH
+    };
+    if eku
+        .0
+        .iter()
+        .any(|oid| *oid == OID_EKU_SERVER_AUTH || *oid == role_eku)
+    {
+        Ok(())
+    } else {
+        Err(SPDM_STATUS_INVALID_CERT)
+    }
+}
+
+// The certificate signature is verified as an SPDM signature of the
+// algorithms matching the signature algorithm and the issuer key.
+fn verify_cert_signature(issuer: &Certificate, cert: &Certificate) -> SpdmResult {
+    if cert.tbs_certificate.issuer != issuer.tbs_certificate.subject {
+        return Err(SPDM_STATUS_INVALID_CERT);
+    }
+    let issuer_spki = &issuer.tbs_certificate.subject_public_key_info;
+    let public_key = issuer_spki
+        .subject_public_key
+        .as_bytes()
+        .ok_or(SPDM_STATUS_INVALID_CERT)?;
+    let base_asym_algo = get_asym_algo(issuer_spki)?;
+    let base_hash_algo = match (cert.signature_algorithm.oid, base_asym_algo) {
+        (OID_ED25519, SpdmBaseAsymAlgo::EDDSA_ED25519) => SpdmBaseHashAlgo::TPM_ALG_SHA_512,
+        (OID_SHA256_WITH_RSA, _) if is_rsa(base_asym_algo) => SpdmBaseHashAlgo::TPM_ALG_SHA_256,
+        (OID_SHA384_WITH_RSA, _) if is_rsa(base_asym_algo) => SpdmBaseHashAlgo::TPM_ALG_SHA_384,
+        (OID_SHA512_WITH_RSA, _) if is_rsa(base_asym_algo) => SpdmBaseHashAlgo::TPM_ALG_SHA_512,
+        (OID_ECDSA_WITH_SHA256, _) if !is_rsa(base_asym_algo) => SpdmBaseHashAlgo::TPM_ALG_SHA_256,
+        (OID_ECDSA_WITH_SHA384, _) if !is_rsa(base_asym_algo) => SpdmBaseHashAlgo::TPM_ALG_SHA_384,
+        (OID_ECDSA_WITH_SHA512, _) if !is_rsa(base_asym_algo) => SpdmBaseHashAlgo::TPM_ALG_SHA_512,
+        _ => return Err(SPDM_STATUS_INVALID_CERT),
+    };
+
+    let tbs = cert
+        .tbs_certificate
+        .to_der()
+        .map_err(|_| SPDM_STATUS_INVALID_CERT)?;
+    let cert_signature = cert.signature.as_bytes().ok_or(SPDM_STATUS_INVALID_CERT)?;
+    let mut signature = SpdmSignatureStruct {
+        data_size: base_asym_algo.get_size(),
+        ..Default::default()
+    };
+    match cert.signature_algorithm.oid {
+        OID_ECDSA_WITH_SHA256 | OID_ECDSA_WITH_SHA384 | OID_ECDSA_WITH_SHA512 => {
+            ecc_signature_der_to_bin(
+                cert_signature,
+                &mut signature.data[..base_asym_algo.get_size() as usize],
+            )?;
+        }
+        _ => {
+            if cert_signature.len() != base_asym_algo.get_size() as usize {
+                return Err(SPDM_STATUS_INVALID_CERT);
+            }
+            signature.data[..cert_signature.len()].copy_from_slice(cert_signature);
+        }
+    }
+
+    verify_signature(base_hash_algo, base_asym_algo, public_key, &tbs, &signature)
+        .map_err(|_| SPDM_STATUS_INVALID_CERT)
+}
+
+fn is_rsa(base_asym_algo: SpdmBaseAsymAlgo) -> bool {
+    matches!(
+        base_asym_algo,
+        SpdmBaseAsymAlgo::TPM_ALG_RSASSA_2048
+            | SpdmBaseAsymAlgo::TPM_ALG_RSASSA_3072
+            | SpdmBaseAsymAlgo::TPM_ALG_RSASSA_4096
+    )
+}
+
+fn get_asym_algo(spki: &SubjectPublicKeyInfoOwned) -> SpdmResult<SpdmBaseAsymAlgo> {
+    match spki.algorithm.oid {
+        OID_RSA_ENCRYPTION => {
+            let public_key = spki
+                .subject_public_key
+                .as_bytes()
+                .ok_or(SPDM_STATUS_INVALID_CERT)?;
+            let public_key =
+                RsaPublicKey::from_pkcs1_der(public_key).map_err(|_| SPDM_STATUS_INVALID_CERT)?;
+            match public_key.size() {
+                256 => Ok(SpdmBaseAsymAlgo::TPM_ALG_RSASSA_2048),
+                384 => Ok(SpdmBaseAsymAlgo::TPM_ALG_RSASSA_3072),
+                512 => Ok(SpdmBaseAsymAlgo::TPM_ALG_RSASSA_4096),
+                _ => Err(SPDM_STATUS_INVALID_CERT),
+            }
+        }
+        OID_EC_PUBLIC_KEY => {
+            let curve = spki
+                .algorithm
+                .parameters
+                .as_ref()
+                .ok_or(SPDM_STATUS_INVALID_CERT)?
+                .decode_as::<ObjectIdentifier>()
+                .map_err(|_| SPDM_STATUS_INVALID_CERT)?;
+            match curve {
+                OID_SECP256R1 => Ok(SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P256),
+                OID_SECP384R1 => Ok(SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384),
+                OID_SECP521R1 => Ok(SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P521),
+                _ => Err(SPDM_STATUS_INVALID_CERT),
+            }
+        }
+        OID_ED25519 => Ok(SpdmBaseAsymAlgo::EDDSA_ED25519),
+        _ => Err(SPDM_STATUS_INVALID_CERT),
+    }
+}
+
+// remove ASN.1 from the DER signature, SEQUENCE { INTEGER r, INTEGER s }
+fn ecc_signature_der_to_bin(der_signature: &[u8], signature: &mut [u8]) -> SpdmResult {
+    let half_size = signature.len() / 2;
+    // The P-521 signature is longer than 127 bytes.
+    let der_signature = match der_signature {
+        [0x30, 0x81, len, rest @ ..] if *len as usize == rest.len() => rest,
+        [0x30, len, rest @ ..] if *len < 0x80 && *len as usize == rest.len() => rest,
+        _ => return Err(SPDM_STATUS_INVALID_CERT),
+    };
+
+    let mut walker = 0usize;
+    for i in 0..2 {
+        if der_signature.len() < walker + 2 || der_signature[walker] != 0x02 {
+            return Err(SPDM_STATUS_INVALID_CERT);
+        }
+        let int_size = der_signature[walker + 1] as usize;
+        walker += 2;
+        if int_size >= 0x80 || der_signature.len() < walker + int_size {
+            return Err(SPDM_STATUS_INVALID_CERT);
+        }
+        let mut int = &der_signature[walker..(walker + int_size)];
+        // strip the sign octet
+        while int.len() > half_size && int[0] == 0 {
+            int = &int[1..];
+        }
+        if int.len() > half_size {
+            return Err(SPDM_STATUS_INVALID_CERT);
+        }
+        let end = half_size * (i + 1);
+        signature[(end - int.len())..end].copy_from_slice(int);
+        walker += int_size;
+    }
+
+    if walker == der_signature.len() {
+        Ok(())
+    } else {
+        Err(SPDM_STATUS_INVALID_CERT)
+    }
+}
+
+#[cfg(all(test,))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_case0_get_cert_from_cert_chain() {
+        let cert_chain = std::fs::read("../test_key/ecp384/bundle_responder.certchain.der")
+            .expect("unable to read cert chain!");
+        let (start, end) = get_cert_from_cert_chain(&cert_chain, -1).unwrap();
+        assert_eq!(end, cert_chain.len());
+        assert!(Certificate::from_der(&cert_chain[start..end]).is_ok());
+        assert!(get_cert_from_cert_chain(&cert_chain, 3).is_err());
+    }
+
+    #[test]
+    fn test_case0_verify_cert_chain() {
+        for dir in [
+            "ecp256", "ecp384", "ecp521", "rsa2048", "rsa3072", "rsa4096", "ed25519",
+        ]
+        .iter()
+        {
+            for (bundle, is_requester_cert) in
+                [("bundle_responder", false), ("bundle_requester", true)].iter()
+            {
+                let cert_chain =
+                    std::fs::read(std::format!("../test_key/{}/{}.certchain.der", dir, bundle))
+                        .expect("unable to read cert chain!");
+                assert!(
+                    verify_cert_chain(&cert_chain, *is_requester_cert, false).is_ok(),
+                    "{} {}",
+                    dir,
+                    bundle
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_case1_verify_cert_chain() {
+        let cert_chain = std::fs::read("../test_key/ecp384/bundle_responder.certchain.der")
+            .expect("unable to read cert chain!");
+
+        // The intermediate cert is not self-signed.
+        let (start, _) = get_cert_from_cert_chain(&cert_chain, 1).unwrap();
+        assert!(verify_cert_chain(&cert_chain[start..], false, false).is_err());
+        assert!(verify_cert_chain(&[], false, false).is_err());
+
+        // A different root
+        let mut wrong_chain =
+            std::fs::read("../test_key/ecp384/ca1.cert.der").expect("unable to read ca!");
+        wrong_chain.extend_from_slice(&cert_chain[start..]);
+        assert!(verify_cert_chain(&wrong_chain, false, false).is_err());
+
+        let cert_chain =
+            std::fs::read("../test_key/rsa3072_Expiration/bundle_responder.certchain.der")
+                .expect("unable to read cert chain!");
+        assert!(verify_cert_chain(&cert_chain, false, false).is_err());
+    }
+
+    #[test]
+    fn test_case2_verify_cert_chain() {
+        let requester_cert_chain =
+            std::fs::read("../test_key/crypto_chains/bundle_dmtf_requester_cert.der")
+                .expect("unable to read cert chain!");
+        assert!(verify_cert_chain(&requester_cert_chain, true, false).is_ok());
+        assert!(verify_cert_chain(&requester_cert_chain, false, false).is_err());
+
+        let responder_cert_chain =
+            std::fs::read("../test_key/crypto_chains/bundle_dmtf_responder_cert.der")
+                .expect("unable to read cert chain!");
+        assert!(verify_cert_chain(&responder_cert_chain, false, false).is_ok());
+        assert!(verify_cert_chain(&responder_cert_chain, true, false).is_err());
+    }
+
+    #[test]
+    fn test_case3_verify_cert_chain() {
+        let ca = std::fs::read("../test_key/ecp384/ca.cert.der").expect("unable to read ca!");
+        let inter =
+            std::fs::read("../test_key/ecp384/inter.cert.der").expect("unable to read inter!");
+        let partial_chain = [ca, inter.clone()].concat();
+        assert!(verify_cert_chain(&partial_chain, false, true).is_ok());
+        // Only the alias certificate model allows a partial chain.
+        assert!(verify_cert_chain(&partial_chain, false, false).is_err());
+
+        // The issuer of the device certificate shall be a CA.
+        let leaf = std::fs::read("../test_key/ecp384/end_responder.cert.der")
+            .expect("unable to read leaf!");
+        assert!(verify_cert_chain(&[leaf, inter].concat(), false, true).is_err());
+    }
+
+    #[test]
+    fn test_case0_ecc_signature_der_to_bin() {
+        let der_signature = [0x30, 0x08, 0x02, 0x02, 0x00, 0x81, 0x02, 0x02, 0x12, 0x34];
+        let signature = &mut [0u8; 8];
+        assert!(ecc_signature_der_to_bin(&der_signature, signature).is_ok());
+        assert_eq!(signature, &[0, 0, 0, 0x81, 0, 0, 0x12, 0x34]);
+
+        assert!(ecc_signature_der_to_bin(&der_signature[..9], signature).is_err());
+    }
+}
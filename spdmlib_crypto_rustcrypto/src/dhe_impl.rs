@@ -0,0 +1,142 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+extern crate alloc;
+use alloc::boxed::Box;
+
+use elliptic_curve::ecdh::EphemeralSecret;
+use elliptic_curve::sec1::{FromEncodedPoint, ModulusSize, ToEncodedPoint};
+use elliptic_curve::{AffinePoint, CurveArithmetic, FieldBytesSize, PublicKey};
+use p256::NistP256;
+use p384::NistP384;
+use p521::NistP521;
+use rand_core::OsRng;
+use spdmlib::crypto::{SpdmDhe, SpdmDheKeyExchange};
+use spdmlib::protocol::{
+    SpdmDheAlgo, SpdmDheExchangeStruct, SpdmDheFinalKeyStruct, SpdmKemAlgo, SPDM_MAX_DHE_KEY_SIZE,
+};
+
+pub static DEFAULT: SpdmDhe = SpdmDhe {
+    generate_key_pair_cb: generate_key_pair,
+    kem_generate_key_pair_cb: kem_generate_key_pair,
+    kem_encapsulate_cb: kem_encapsulate,
+};
+
+// FFDHE is not implemented, as in the ring backend.
+fn generate_key_pair(
+    dhe_algo: SpdmDheAlgo,
+) -> Option<(SpdmDheExchangeStruct, Box<dyn SpdmDheKeyExchange>)> {
+    match dhe_algo {
+        SpdmDheAlgo::SECP_256_R1 => SpdmDheKeyExchangeEc::<NistP256>::generate_key_pair(),
+        SpdmDheAlgo::SECP_384_R1 => SpdmDheKeyExchangeEc::<NistP384>::generate_key_pair(),
+        SpdmDheAlgo::SECP_521_R1 => SpdmDheKeyExchangeEc::<NistP521>::generate_key_pair(),
+        _ => None,
+    }
+}
+
+fn kem_generate_key_pair(
+    _kem_algo: SpdmKemAlgo,
+) -> Option<(SpdmDheExchangeStruct, Box<dyn SpdmDheKeyExchange>)> {
+    None
+}
+
+fn kem_encapsulate(
+    _kem_algo: SpdmKemAlgo,
+    _peer_encap_key: &SpdmDheExchangeStruct,
+) -> Option<(SpdmDheExchangeStruct, SpdmDheFinalKeyStruct)> {
+    None
+}
+
+struct SpdmDheKeyExchangeEc<C: CurveArithmetic>(EphemeralSecret<C>);
+
+impl<C> SpdmDheKeyExchangeEc<C>
+where
+    C: CurveArithmetic,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    FieldBytesSize<C>: ModulusSize,
+{
+    fn generate_key_pair() -> Option<(SpdmDheExchangeStruct, Box<dyn SpdmDheKeyExchange>)> {
+        let private_key = EphemeralSecret::<C>::random(&mut OsRng);
+        let point = private_key.public_key().to_encoded_point(false);
+
+        // SPDM carries the uncompressed point without the leading 0x04.
+        let point = &point.as_bytes()[1..];
+        let mut public_key = SpdmDheExchangeStruct {
+            data_size: point.len() as u16,
+            ..Default::default()
+        };
+        public_key.data[..point.len()].copy_from_slice(point);
+
+        let res: Box<dyn SpdmDheKeyExchange> = Box::new(Self(private_key));
+        Some((public_key, res))
+    }
+}
+
+impl<C> SpdmDheKeyExchange for SpdmDheKeyExchangeEc<C>
+where
+    C: CurveArithmetic,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    FieldBytesSize<C>: ModulusSize,
+{
+    fn compute_final_key(
+        self: Box<Self>,
+        peer_pub_key: &SpdmDheExchangeStruct,
+    ) -> Option<SpdmDheFinalKeyStruct> {
+        let peer_pub_key = peer_pub_key.as_ref();
+        if peer_pub_key.len() >= SPDM_MAX_DHE_KEY_SIZE {
+            return None;
+        }
+        let mut pubkey = [0u8; SPDM_MAX_DHE_KEY_SIZE + 1];
+        pubkey[0] = 0x4u8;
+        pubkey[1..(peer_pub_key.len() + 1)].copy_from_slice(peer_pub_key);
+
+        let peer_public_key =
+            PublicKey::<C>::from_sec1_bytes(&pubkey[..(peer_pub_key.len() + 1)]).ok()?;
+        let shared_secret = self.0.diffie_hellman(&peer_public_key);
+        Some(SpdmDheFinalKeyStruct::from(
+            shared_secret.raw_secret_bytes().as_slice(),
+        ))
+    }
+}
+
+#[cfg(all(test,))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_case0_dhe() {
+        for dhe_algo in [
+            SpdmDheAlgo::SECP_256_R1,
+            SpdmDheAlgo::SECP_384_R1,
+            SpdmDheAlgo::SECP_521_R1,
+        ]
+        .iter()
+        {
+            let (exchange1, private1) = generate_key_pair(*dhe_algo).unwrap();
+            let (exchange2, private2) = generate_key_pair(*dhe_algo).unwrap();
+            assert_eq!(exchange1.data_size, dhe_algo.get_size());
+
+            let peer1 = private1.compute_final_key(&exchange2).unwrap();
+            let peer2 = private2.compute_final_key(&exchange1).unwrap();
+
+            assert_eq!(peer1.as_ref(), peer2.as_ref());
+        }
+    }
+    #[test]
+    fn test_case1_dhe() {
+        for dhe_algo in [
+            SpdmDheAlgo::empty(),
+            SpdmDheAlgo::FFDHE_2048,
+            SpdmDheAlgo::SM2_P256,
+        ]
+        .iter()
+        {
+            assert!(generate_key_pair(*dhe_algo).is_none());
+        }
+
+        let (_, private1) = generate_key_pair(SpdmDheAlgo::SECP_384_R1).unwrap();
+        let (exchange2, _) = generate_key_pair(SpdmDheAlgo::SECP_256_R1).unwrap();
+        assert!(private1.compute_final_key(&exchange2).is_none());
+    }
+}
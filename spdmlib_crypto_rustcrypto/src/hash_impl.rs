@@ -0,0 +1,198 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use sha2::{Digest, Sha256, Sha384, Sha512};
+use sha3::{Sha3_256, Sha3_384, Sha3_512};
+use spdmlib::crypto::SpdmHash;
+use spdmlib::protocol::{SpdmBaseHashAlgo, SpdmDigestStruct};
+
+#[derive(Clone)]
+pub(crate) enum HashCtx {
+    Sha256(Sha256),
+    Sha384(Sha384),
+    Sha512(Sha512),
+    Sha3_256(Sha3_256),
+    Sha3_384(Sha3_384),
+    Sha3_512(Sha3_512),
+}
+
+impl HashCtx {
+    pub(crate) fn new(base_hash_algo: SpdmBaseHashAlgo) -> Option<Self> {
+        match base_hash_algo {
+            SpdmBaseHashAlgo::TPM_ALG_SHA_256 => Some(HashCtx::Sha256(Sha256::new())),
+            SpdmBaseHashAlgo::TPM_ALG_SHA_384 => Some(HashCtx::Sha384(Sha384::new())),
+            SpdmBaseHashAlgo::TPM_ALG_SHA_512 => Some(HashCtx::Sha512(Sha512::new())),
+            SpdmBaseHashAlgo::TPM_ALG_SHA3_256 => Some(HashCtx::Sha3_256(Sha3_256::new())),
+            SpdmBaseHashAlgo::TPM_ALG_SHA3_384 => Some(HashCtx::Sha3_384(Sha3_384::new())),
+            SpdmBaseHashAlgo::TPM_ALG_SHA3_512 => Some(HashCtx::Sha3_512(Sha3_512::new())),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn update(&mut self, data: &[u8]) {
+        match self {
+            HashCtx::Sha256(ctx) => ctx.update(data),
+            HashCtx::Sha384(ctx) => ctx.update(data),
+            HashCtx::Sha512(ctx) => ctx.update(data),
+            HashCtx::Sha3_256(ctx) => ctx.update(data),
+            HashCtx::Sha3_384(ctx) => ctx.update(data),
+            HashCtx::Sha3_512(ctx) => ctx.update(data),
+        }
+    }
+
+    pub(crate) fn finalize(self) -> SpdmDigestStruct {
+        match self {
+            HashCtx::Sha256(ctx) => SpdmDigestStruct::from(ctx.finalize().as_slice()),
+            HashCtx::Sha384(ctx) => SpdmDigestStruct::from(ctx.finalize().as_slice()),
+            HashCtx::Sha512(ctx) => SpdmDigestStruct::from(ctx.finalize().as_slice()),
+            HashCtx::Sha3_256(ctx) => SpdmDigestStruct::from(ctx.finalize().as_slice()),
+            HashCtx::Sha3_384(ctx) => SpdmDigestStruct::from(ctx.finalize().as_slice()),
+            HashCtx::Sha3_512(ctx) => SpdmDigestStruct::from(ctx.finalize().as_slice()),
+        }
+    }
+}
+
+#[cfg(feature = "hashed-transcript-data")]
+mod hash_ext {
+    extern crate alloc;
+    use super::*;
+    use alloc::boxed::Box;
+    use alloc::collections::BTreeMap;
+    use lazy_static::lazy_static;
+    use spdmlib::error::{SpdmResult, SPDM_STATUS_CRYPTO_ERROR};
+    use spin::Mutex;
+    pub(crate) type HashCtxConcrete = HashCtx;
+    lazy_static! {
+        static ref HASH_CTX_TABLE: Mutex<BTreeMap<usize, Box<HashCtxConcrete>>> =
+            Mutex::new(BTreeMap::new());
+    }
+
+    pub static DEFAULT: SpdmHash = SpdmHash {
+        hash_all_cb: hash_all,
+        hash_ctx_init_cb: hash_ctx_init,
+        hash_ctx_update_cb: hash_ctx_update,
+        hash_ctx_finalize_cb: hash_ctx_finalize,
+        hash_ctx_dup_cb: hash_ctx_dup,
+    };
+
+    pub(crate) fn hash_ctx_init(base_hash_algo: SpdmBaseHashAlgo) -> Option<usize> {
+        let ctx = Box::new(HashCtx::new(base_hash_algo)?);
+        Some(insert_to_table(ctx))
+    }
+
+    pub(crate) fn hash_ctx_update(handle: usize, data: &[u8]) -> SpdmResult {
+        let mut table = HASH_CTX_TABLE.lock();
+        let ctx = table.get_mut(&handle).ok_or(SPDM_STATUS_CRYPTO_ERROR)?;
+        ctx.update(data);
+        Ok(())
+    }
+
+    pub(crate) fn hash_ctx_finalize(handle: usize) -> Option<SpdmDigestStruct> {
+        let ctx = HASH_CTX_TABLE.lock().remove(&handle)?;
+        Some(ctx.finalize())
+    }
+
+    pub(crate) fn hash_ctx_dup(handle: usize) -> Option<usize> {
+        let ctx_new = {
+            let table = HASH_CTX_TABLE.lock();
+            let ctx = table.get(&handle)?;
+            ctx.clone()
+        };
+        Some(insert_to_table(ctx_new))
+    }
+
+    pub(crate) fn insert_to_table(value: Box<HashCtxConcrete>) -> usize {
+        let handle_ptr: *const HashCtxConcrete = &*value;
+        let handle = handle_ptr as usize;
+        HASH_CTX_TABLE.lock().insert(handle, value);
+        handle
+    }
+
+    #[allow(dead_code)]
+    #[cfg(test)]
+    pub fn get_hash_ctx_count() -> usize {
+        HASH_CTX_TABLE.lock().len()
+    }
+}
+#[cfg(feature = "hashed-transcript-data")]
+pub use hash_ext::DEFAULT;
+
+#[cfg(not(feature = "hashed-transcript-data"))]
+pub static DEFAULT: SpdmHash = SpdmHash {
+    hash_all_cb: hash_all,
+};
+
+pub(crate) fn hash_all(base_hash_algo: SpdmBaseHashAlgo, data: &[u8]) -> Option<SpdmDigestStruct> {
+    let mut ctx = HashCtx::new(base_hash_algo)?;
+    ctx.update(data);
+    Some(ctx.finalize())
+}
+
+#[cfg(all(test,))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_case0_hash_all() {
+        // FIPS 180-2 and FIPS 202 test vectors for "abc"
+        let hash_all_256 = hash_all(SpdmBaseHashAlgo::TPM_ALG_SHA_256, b"abc").unwrap();
+        assert_eq!(
+            hash_all_256.as_ref(),
+            &[
+                0xba, 0x78, 0x16, 0xbf, 0x8f, 0x01, 0xcf, 0xea, 0x41, 0x41, 0x40, 0xde, 0x5d, 0xae,
+                0x22, 0x23, 0xb0, 0x03, 0x61, 0xa3, 0x96, 0x17, 0x7a, 0x9c, 0xb4, 0x10, 0xff, 0x61,
+                0xf2, 0x00, 0x15, 0xad
+            ]
+        );
+        let hash_all_sha3_256 = hash_all(SpdmBaseHashAlgo::TPM_ALG_SHA3_256, b"abc").unwrap();
+        assert_eq!(
+            hash_all_sha3_256.as_ref(),
+            &[
+                0x3a, 0x98, 0x5d, 0xa7, 0x4f, 0xe2, 0x25, 0xb2, 0x04, 0x5c, 0x17, 0x2d, 0x6b, 0xd3,
+                0x90, 0xbd, 0x85, 0x5f, 0x08, 0x6e, 0x3e, 0x9d, 0x52, 0x5b, 0x46, 0xbf, 0xe2, 0x45,
+                0x11, 0x43, 0x15, 0x32
+            ]
+        );
+        for (base_hash_algo, size) in [
+            (SpdmBaseHashAlgo::TPM_ALG_SHA_384, 48),
+            (SpdmBaseHashAlgo::TPM_ALG_SHA_512, 64),
+            (SpdmBaseHashAlgo::TPM_ALG_SHA3_384, 48),
+            (SpdmBaseHashAlgo::TPM_ALG_SHA3_512, 64),
+        ]
+        .iter()
+        {
+            assert_eq!(hash_all(*base_hash_algo, b"abc").unwrap().data_size, *size);
+        }
+    }
+    #[test]
+    fn test_case1_hash_all() {
+        for base_hash_algo in [SpdmBaseHashAlgo::empty(), SpdmBaseHashAlgo::TPM_ALG_SM3_256].iter()
+        {
+            assert!(hash_all(*base_hash_algo, b"abc").is_none());
+        }
+    }
+    #[cfg(feature = "hashed-transcript-data")]
+    #[test]
+    fn test_case2_hash_ctx() {
+        use hash_ext::*;
+        let base_hash_algo = SpdmBaseHashAlgo::TPM_ALG_SHA_384;
+
+        let handle = hash_ctx_init(base_hash_algo).unwrap();
+        assert!(hash_ctx_update(handle, b"hello, ").is_ok());
+        let dup = hash_ctx_dup(handle).unwrap();
+        assert!(hash_ctx_update(handle, b"world").is_ok());
+        assert!(hash_ctx_update(dup, b"buddy").is_ok());
+
+        assert_eq!(
+            hash_ctx_finalize(handle).unwrap().as_ref(),
+            hash_all(base_hash_algo, b"hello, world").unwrap().as_ref()
+        );
+        assert_eq!(
+            hash_ctx_finalize(dup).unwrap().as_ref(),
+            hash_all(base_hash_algo, b"hello, buddy").unwrap().as_ref()
+        );
+        assert!(hash_ctx_finalize(handle).is_none());
+        assert!(hash_ctx_init(SpdmBaseHashAlgo::TPM_ALG_SM3_256).is_none());
+    }
+}
@@ -0,0 +1,145 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use hkdf::hmac::digest::core_api::BlockSizeUser;
+use hkdf::hmac::digest::Digest;
+use hkdf::SimpleHkdf;
+use sha2::{Sha256, Sha384, Sha512};
+use sha3::{Sha3_256, Sha3_384, Sha3_512};
+use spdmlib::crypto::SpdmHkdf;
+use spdmlib::protocol::{
+    SpdmBaseHashAlgo, SpdmHkdfInputKeyingMaterial, SpdmHkdfOutputKeyingMaterial,
+    SpdmHkdfPseudoRandomKey, SPDM_MAX_HKDF_OKM_SIZE,
+};
+
+pub static DEFAULT: SpdmHkdf = SpdmHkdf {
+    hkdf_extract_cb: hkdf_extract,
+    hkdf_expand_cb: hkdf_expand,
+};
+
+fn extract<D: Digest + BlockSizeUser + Clone>(salt: &[u8], ikm: &[u8]) -> SpdmHkdfPseudoRandomKey {
+    let (prk, _) = SimpleHkdf::<D>::extract(Some(salt), ikm);
+    SpdmHkdfPseudoRandomKey::from(prk.as_slice())
+}
+
+fn expand<D: Digest + BlockSizeUser + Clone>(
+    prk: &[u8],
+    info: &[u8],
+    okm: &mut [u8],
+) -> Option<()> {
+    let hkdf = SimpleHkdf::<D>::from_prk(prk).ok()?;
+    hkdf.expand(info, okm).ok()
+}
+
+fn hkdf_extract(
+    hash_algo: SpdmBaseHashAlgo,
+    salt: &[u8],
+    ikm: &SpdmHkdfInputKeyingMaterial,
+) -> Option<SpdmHkdfPseudoRandomKey> {
+    let ikm = ikm.as_ref();
+    match hash_algo {
+        SpdmBaseHashAlgo::TPM_ALG_SHA_256 => Some(extract::<Sha256>(salt, ikm)),
+        SpdmBaseHashAlgo::TPM_ALG_SHA_384 => Some(extract::<Sha384>(salt, ikm)),
+        SpdmBaseHashAlgo::TPM_ALG_SHA_512 => Some(extract::<Sha512>(salt, ikm)),
+        SpdmBaseHashAlgo::TPM_ALG_SHA3_256 => Some(extract::<Sha3_256>(salt, ikm)),
+        SpdmBaseHashAlgo::TPM_ALG_SHA3_384 => Some(extract::<Sha3_384>(salt, ikm)),
+        SpdmBaseHashAlgo::TPM_ALG_SHA3_512 => Some(extract::<Sha3_512>(salt, ikm)),
+        _ => None,
+    }
+}
+
+fn hkdf_expand(
+    hash_algo: SpdmBaseHashAlgo,
+    prk: &SpdmHkdfPseudoRandomKey,
+    info: &[u8],
+    out_size: u16,
+) -> Option<SpdmHkdfOutputKeyingMaterial> {
+    if out_size as usize > SPDM_MAX_HKDF_OKM_SIZE || prk.data_size != hash_algo.get_size() {
+        return None;
+    }
+
+    let mut okm = SpdmHkdfOutputKeyingMaterial::default();
+    let out = &mut okm.data[..out_size as usize];
+    match hash_algo {
+        SpdmBaseHashAlgo::TPM_ALG_SHA_256 => expand::<Sha256>(prk.as_ref(), info, out)?,
+        SpdmBaseHashAlgo::TPM_ALG_SHA_384 => expand::<Sha384>(prk.as_ref(), info, out)?,
+        SpdmBaseHashAlgo::TPM_ALG_SHA_512 => expand::<Sha512>(prk.as_ref(), info, out)?,
+        SpdmBaseHashAlgo::TPM_ALG_SHA3_256 => expand::<Sha3_256>(prk.as_ref(), info, out)?,
+        SpdmBaseHashAlgo::TPM_ALG_SHA3_384 => expand::<Sha3_384>(prk.as_ref(), info, out)?,
+        SpdmBaseHashAlgo::TPM_ALG_SHA3_512 => expand::<Sha3_512>(prk.as_ref(), info, out)?,
+        _ => return None,
+    }
+    okm.data_size = out_size;
+    Some(okm)
+}
+
+#[cfg(all(test,))]
+mod tests {
+    use super::*;
+    use spdmlib::protocol::SpdmFinishedKeyStruct;
+
+    #[test]
+    fn test_case0_hkdf() {
+        // RFC 5869 test case 1
+        let hash_algo = SpdmBaseHashAlgo::TPM_ALG_SHA_256;
+        let ikm = SpdmFinishedKeyStruct::from(&[0x0bu8; 22][..]);
+        let salt = &[
+            0x00u8, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c,
+        ];
+        let info = &[0xf0u8, 0xf1, 0xf2, 0xf3, 0xf4, 0xf5, 0xf6, 0xf7, 0xf8, 0xf9];
+
+        let prk = hkdf_extract(
+            hash_algo,
+            salt,
+            &SpdmHkdfInputKeyingMaterial::SpdmFinishedKey(&ikm),
+        )
+        .unwrap();
+        assert_eq!(prk.data_size, 32);
+
+        let okm = hkdf_expand(hash_algo, &prk, info, 42).unwrap();
+        assert_eq!(
+            okm.as_ref(),
+            &[
+                0x3c, 0xb2, 0x5f, 0x25, 0xfa, 0xac, 0xd5, 0x7a, 0x90, 0x43, 0x4f, 0x64, 0xd0, 0x36,
+                0x2f, 0x2a, 0x2d, 0x2d, 0x0a, 0x90, 0xcf, 0x1a, 0x5a, 0x4c, 0x5d, 0xb0, 0x2d, 0x56,
+                0xec, 0xc4, 0xc5, 0xbf, 0x34, 0x00, 0x72, 0x08, 0xd5, 0xb8, 0x87, 0x18, 0x58, 0x65
+            ]
+        );
+    }
+    #[test]
+    fn test_case1_hkdf() {
+        let ikm = SpdmFinishedKeyStruct::from(&[100u8; 48][..]);
+        for hash_algo in [
+            SpdmBaseHashAlgo::TPM_ALG_SHA_384,
+            SpdmBaseHashAlgo::TPM_ALG_SHA3_384,
+        ]
+        .iter()
+        {
+            let prk = hkdf_extract(
+                *hash_algo,
+                &[0u8; 48],
+                &SpdmHkdfInputKeyingMaterial::SpdmFinishedKey(&ikm),
+            )
+            .unwrap();
+            assert_eq!(prk.data_size, 48);
+            let okm = hkdf_expand(*hash_algo, &prk, b"spdm", 64).unwrap();
+            let okm_short = hkdf_expand(*hash_algo, &prk, b"spdm", 16).unwrap();
+            assert_eq!(okm_short.as_ref(), &okm.as_ref()[..16]);
+        }
+    }
+    #[test]
+    fn test_case2_hkdf() {
+        let prk = SpdmHkdfPseudoRandomKey::from(&[100u8; 32][..]);
+
+        assert!(hkdf_expand(SpdmBaseHashAlgo::TPM_ALG_SHA_384, &prk, b"spdm", 32).is_none());
+        assert!(hkdf_expand(SpdmBaseHashAlgo::TPM_ALG_SM3_256, &prk, b"spdm", 32).is_none());
+        assert!(hkdf_expand(
+            SpdmBaseHashAlgo::TPM_ALG_SHA_256,
+            &prk,
+            b"spdm",
+            SPDM_MAX_HKDF_OKM_SIZE as u16 + 1
+        )
+        .is_none());
+    }
+}
@@ -0,0 +1,114 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use hmac::digest::core_api::BlockSizeUser;
+use hmac::digest::Digest;
+use hmac::{Mac, SimpleHmac};
+use sha2::{Sha256, Sha384, Sha512};
+use sha3::{Sha3_256, Sha3_384, Sha3_512};
+use spdmlib::crypto::SpdmHmac;
+use spdmlib::error::{SpdmResult, SPDM_STATUS_CRYPTO_ERROR, SPDM_STATUS_VERIF_FAIL};
+use spdmlib::protocol::{SpdmBaseHashAlgo, SpdmDigestStruct};
+
+pub static DEFAULT: SpdmHmac = SpdmHmac {
+    hmac_cb: hmac,
+    hmac_verify_cb: hmac_verify,
+};
+
+// SimpleHmac works with both the SHA-2 and the SHA-3 digests.
+fn new_hmac<D: Digest + BlockSizeUser>(key: &[u8], data: &[u8]) -> SimpleHmac<D> {
+    // HMAC accepts keys of any length.
+    let mut ctx = <SimpleHmac<D> as Mac>::new_from_slice(key).unwrap();
+    ctx.update(data);
+    ctx
+}
+
+fn sign<D: Digest + BlockSizeUser>(key: &[u8], data: &[u8]) -> SpdmDigestStruct {
+    SpdmDigestStruct::from(new_hmac::<D>(key, data).finalize().into_bytes().as_slice())
+}
+
+fn verify<D: Digest + BlockSizeUser>(key: &[u8], data: &[u8], tag: &[u8]) -> SpdmResult {
+    new_hmac::<D>(key, data)
+        .verify_slice(tag)
+        .map_err(|_| SPDM_STATUS_VERIF_FAIL)
+}
+
+fn hmac(base_hash_algo: SpdmBaseHashAlgo, key: &[u8], data: &[u8]) -> Option<SpdmDigestStruct> {
+    match base_hash_algo {
+        SpdmBaseHashAlgo::TPM_ALG_SHA_256 => Some(sign::<Sha256>(key, data)),
+        SpdmBaseHashAlgo::TPM_ALG_SHA_384 => Some(sign::<Sha384>(key, data)),
+        SpdmBaseHashAlgo::TPM_ALG_SHA_512 => Some(sign::<Sha512>(key, data)),
+        SpdmBaseHashAlgo::TPM_ALG_SHA3_256 => Some(sign::<Sha3_256>(key, data)),
+        SpdmBaseHashAlgo::TPM_ALG_SHA3_384 => Some(sign::<Sha3_384>(key, data)),
+        SpdmBaseHashAlgo::TPM_ALG_SHA3_512 => Some(sign::<Sha3_512>(key, data)),
+        _ => None,
+    }
+}
+
+fn hmac_verify(
+    base_hash_algo: SpdmBaseHashAlgo,
+    key: &[u8],
+    data: &[u8],
+    message_digest: &SpdmDigestStruct,
+) -> SpdmResult {
+    let tag = message_digest.as_ref();
+    match base_hash_algo {
+        SpdmBaseHashAlgo::TPM_ALG_SHA_256 => verify::<Sha256>(key, data, tag),
+        SpdmBaseHashAlgo::TPM_ALG_SHA_384 => verify::<Sha384>(key, data, tag),
+        SpdmBaseHashAlgo::TPM_ALG_SHA_512 => verify::<Sha512>(key, data, tag),
+        SpdmBaseHashAlgo::TPM_ALG_SHA3_256 => verify::<Sha3_256>(key, data, tag),
+        SpdmBaseHashAlgo::TPM_ALG_SHA3_384 => verify::<Sha3_384>(key, data, tag),
+        SpdmBaseHashAlgo::TPM_ALG_SHA3_512 => verify::<Sha3_512>(key, data, tag),
+        _ => Err(SPDM_STATUS_CRYPTO_ERROR),
+    }
+}
+
+#[cfg(all(test,))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_case0_hmac() {
+        // RFC 4231 test case 2
+        let spdm_digest = hmac(
+            SpdmBaseHashAlgo::TPM_ALG_SHA_256,
+            b"Jefe",
+            b"what do ya want for nothing?",
+        )
+        .unwrap();
+        assert_eq!(
+            spdm_digest.as_ref(),
+            &[
+                0x5b, 0xdc, 0xc1, 0x46, 0xbf, 0x60, 0x75, 0x4e, 0x6a, 0x04, 0x24, 0x26, 0x08, 0x95,
+                0x75, 0xc7, 0x5a, 0x00, 0x3f, 0x08, 0x9d, 0x27, 0x39, 0x83, 0x9d, 0xec, 0x58, 0xb9,
+                0x64, 0xec, 0x38, 0x43
+            ]
+        );
+    }
+    #[test]
+    fn test_case0_hmac_verify() {
+        let key = &[10u8; 32];
+        let data = &[100u8; 64];
+        let wrong_data = &[101u8; 64];
+
+        for base_hash_algo in [
+            SpdmBaseHashAlgo::TPM_ALG_SHA_384,
+            SpdmBaseHashAlgo::TPM_ALG_SHA_512,
+            SpdmBaseHashAlgo::TPM_ALG_SHA3_256,
+            SpdmBaseHashAlgo::TPM_ALG_SHA3_384,
+        ]
+        .iter()
+        {
+            let spdm_digest = hmac(*base_hash_algo, key, data).unwrap();
+            assert_eq!(spdm_digest.data_size, base_hash_algo.get_size());
+            assert!(hmac_verify(*base_hash_algo, key, data, &spdm_digest).is_ok());
+            assert!(hmac_verify(*base_hash_algo, key, wrong_data, &spdm_digest).is_err());
+        }
+    }
+    #[test]
+    fn test_case1_hmac() {
+        let base_hash_algo = SpdmBaseHashAlgo::TPM_ALG_SM3_256;
+        assert!(hmac(base_hash_algo, &[10u8; 32], &[100u8; 64]).is_none());
+    }
+}
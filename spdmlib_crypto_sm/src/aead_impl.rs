@@ -0,0 +1,126 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use aes_gcm::aead::consts::U12;
+use aes_gcm::aead::{AeadInPlace, KeyInit};
+use aes_gcm::{AesGcm, Nonce, Tag};
+use sm4::Sm4;
+use spdmlib::crypto::SpdmAead;
+use spdmlib::error::{SpdmResult, SPDM_STATUS_CRYPTO_ERROR, SPDM_STATUS_INVALID_PARAMETER};
+use spdmlib::protocol::{SpdmAeadAlgo, SpdmAeadIvStruct, SpdmAeadKeyStruct};
+
+type Sm4Gcm = AesGcm<Sm4, U12>;
+
+pub static DEFAULT: SpdmAead = SpdmAead {
+    encrypt_cb: encrypt,
+    decrypt_cb: decrypt,
+};
+
+fn encrypt(
+    aead_algo: SpdmAeadAlgo,
+    key: &SpdmAeadKeyStruct,
+    iv: &SpdmAeadIvStruct,
+    aad: &[u8],
+    plain_text: &[u8],
+    tag: &mut [u8],
+    cipher_text: &mut [u8],
+) -> SpdmResult<(usize, usize)> {
+    match aead_algo {
+        SpdmAeadAlgo::SM4_GCM => {
+            if key.data_size != aead_algo.get_key_size()
+                || iv.data_size != aead_algo.get_iv_size()
+                || tag.len() != aead_algo.get_tag_size() as usize
+                || cipher_text.len() < plain_text.len()
+            {
+                return Err(SPDM_STATUS_INVALID_PARAMETER);
+            }
+            let cipher =
+                Sm4Gcm::new_from_slice(key.as_ref()).map_err(|_| SPDM_STATUS_CRYPTO_ERROR)?;
+            let cipher_text = &mut cipher_text[..plain_text.len()];
+            cipher_text.copy_from_slice(plain_text);
+            let tag_out = cipher
+                .encrypt_in_place_detached(Nonce::from_slice(iv.as_ref()), aad, cipher_text)
+                .map_err(|_| SPDM_STATUS_CRYPTO_ERROR)?;
+            tag.copy_from_slice(tag_out.as_slice());
+            Ok((plain_text.len(), tag.len()))
+        }
+        _ => Err(SPDM_STATUS_INVALID_PARAMETER),
+    }
+}
+
+fn decrypt(
+    aead_algo: SpdmAeadAlgo,
+    key: &SpdmAeadKeyStruct,
+    iv: &SpdmAeadIvStruct,
+    aad: &[u8],
+    cipher_text: &[u8],
+    tag: &[u8],
+    plain_text: &mut [u8],
+) -> SpdmResult<usize> {
+    match aead_algo {
+        SpdmAeadAlgo::SM4_GCM => {
+            if key.data_size != aead_algo.get_key_size()
+                || iv.data_size != aead_algo.get_iv_size()
+                || tag.len() != aead_algo.get_tag_size() as usize
+                || plain_text.len() < cipher_text.len()
+            {
+                return Err(SPDM_STATUS_INVALID_PARAMETER);
+            }
+            let cipher =
+                Sm4Gcm::new_from_slice(key.as_ref()).map_err(|_| SPDM_STATUS_CRYPTO_ERROR)?;
+            let plain_text = &mut plain_text[..cipher_text.len()];
+            plain_text.copy_from_slice(cipher_text);
+            cipher
+                .decrypt_in_place_detached(
+                    Nonce::from_slice(iv.as_ref()),
+                    aad,
+                    plain_text,
+                    Tag::from_slice(tag),
+                )
+                .map_err(|_| SPDM_STATUS_CRYPTO_ERROR)?;
+            Ok(cipher_text.len())
+        }
+        _ => Err(SPDM_STATUS_INVALID_PARAMETER),
+    }
+}
+
+#[cfg(all(test,))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_case0_encrypt_decrypt() {
+        let aead_algo = SpdmAeadAlgo::SM4_GCM;
+        let key = &SpdmAeadKeyStruct::from(&[1u8; 16][..]);
+        let iv = &SpdmAeadIvStruct::from(&[2u8; 12][..]);
+        let aad = &[3u8; 16];
+        let plain_text = &[4u8; 60];
+        let tag = &mut [0u8; 16];
+        let cipher_text = &mut [0u8; 60];
+
+        let (cipher_len, tag_len) =
+            encrypt(aead_algo, key, iv, aad, plain_text, tag, cipher_text).unwrap();
+        assert_eq!(cipher_len, 60);
+        assert_eq!(tag_len, 16);
+        assert_ne!(&cipher_text[..], &plain_text[..]);
+
+        let decrypted = &mut [0u8; 60];
+        let plain_len = decrypt(aead_algo, key, iv, aad, cipher_text, tag, decrypted).unwrap();
+        assert_eq!(plain_len, 60);
+        assert_eq!(&decrypted[..], &plain_text[..]);
+
+        tag[0] ^= 1;
+        assert!(decrypt(aead_algo, key, iv, aad, cipher_text, tag, decrypted).is_err());
+    }
+    #[test]
+    fn test_case1_encrypt() {
+        let aead_algo = SpdmAeadAlgo::AES_128_GCM;
+        let key = &SpdmAeadKeyStruct::from(&[1u8; 16][..]);
+        let iv = &SpdmAeadIvStruct::from(&[2u8; 12][..]);
+        let tag = &mut [0u8; 16];
+        let cipher_text = &mut [0u8; 16];
+
+        assert!(encrypt(aead_algo, key, iv, &[], &[4u8; 16], tag, cipher_text).is_err());
+    }
+}
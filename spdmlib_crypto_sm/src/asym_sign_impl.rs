@@ -0,0 +1,33 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use sm2::dsa::signature::Signer;
+use sm2::dsa::{Signature, SigningKey};
+use sm2::pkcs8::DecodePrivateKey;
+use sm2::SecretKey;
+use spdmlib::protocol::{SpdmBaseHashAlgo, SpdmSignatureStruct, SM2_ECC_SM2_P256_KEY_SIZE};
+
+/// Sign `data` with a PKCS#8 DER encoded SM2 private key and the signing ID
+/// `sm2_id`, as handed to the `SpdmSecretSm2Sign` callback.
+pub fn sign(
+    base_hash_algo: SpdmBaseHashAlgo,
+    private_key_der: &[u8],
+    sm2_id: &[u8],
+    data: &[u8],
+) -> Option<SpdmSignatureStruct> {
+    if base_hash_algo != SpdmBaseHashAlgo::TPM_ALG_SM3_256 {
+        return None;
+    }
+    let id = core::str::from_utf8(sm2_id).ok()?;
+    let secret_key = SecretKey::from_pkcs8_der(private_key_der).ok()?;
+    let signing_key = SigningKey::new(id, &secret_key).ok()?;
+    let signature: Signature = signing_key.try_sign(data).ok()?;
+
+    let mut full_signature = SpdmSignatureStruct {
+        data_size: SM2_ECC_SM2_P256_KEY_SIZE as u16,
+        ..Default::default()
+    };
+    full_signature.data[..SM2_ECC_SM2_P256_KEY_SIZE].copy_from_slice(&signature.to_bytes());
+    Some(full_signature)
+}
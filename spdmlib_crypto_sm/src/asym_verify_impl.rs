@@ -0,0 +1,133 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use core::convert::TryFrom;
+use sm2::dsa::signature::Verifier;
+use sm2::dsa::{Signature, VerifyingKey};
+use spdmlib::crypto::{get_public_key_from_spki, SpdmSm2Verify};
+use spdmlib::error::{SpdmResult, SPDM_STATUS_INVALID_CERT, SPDM_STATUS_VERIF_FAIL};
+use spdmlib::protocol::{SpdmBaseHashAlgo, SpdmSignatureStruct, SM2_ECC_SM2_P256_KEY_SIZE};
+use x509_cert::der::Decode;
+use x509_cert::Certificate;
+
+use crate::cert_operation_impl::get_cert_from_cert_chain;
+
+pub static DEFAULT: SpdmSm2Verify = SpdmSm2Verify {
+    verify_cb: asym_verify,
+    verify_with_public_key_cb: asym_verify_with_public_key,
+};
+
+fn asym_verify(
+    base_hash_algo: SpdmBaseHashAlgo,
+    sm2_id: &[u8],
+    public_cert_der: &[u8],
+    data: &[u8],
+    signature: &SpdmSignatureStruct,
+) -> SpdmResult {
+    let (leaf_begin, leaf_end) = get_cert_from_cert_chain(public_cert_der, -1)?;
+    let cert = Certificate::from_der(&public_cert_der[leaf_begin..leaf_end])
+        .map_err(|_| SPDM_STATUS_INVALID_CERT)?;
+    let public_key = cert
+        .tbs_certificate
+        .subject_public_key_info
+        .subject_public_key
+        .as_bytes()
+        .ok_or(SPDM_STATUS_INVALID_CERT)?;
+    verify_signature(base_hash_algo, sm2_id, public_key, data, signature)
+}
+
+fn asym_verify_with_public_key(
+    base_hash_algo: SpdmBaseHashAlgo,
+    sm2_id: &[u8],
+    public_key_der: &[u8],
+    data: &[u8],
+    signature: &SpdmSignatureStruct,
+) -> SpdmResult {
+    let public_key = get_public_key_from_spki(public_key_der)?;
+    verify_signature(base_hash_algo, sm2_id, public_key, data, signature)
+}
+
+fn verify_signature(
+    base_hash_algo: SpdmBaseHashAlgo,
+    sm2_id: &[u8],
+    public_key: &[u8],
+    data: &[u8],
+    signature: &SpdmSignatureStruct,
+) -> SpdmResult {
+    // SM2 is only defined together with SM3.
+    if base_hash_algo != SpdmBaseHashAlgo::TPM_ALG_SM3_256 {
+        return Err(SPDM_STATUS_VERIF_FAIL);
+    }
+    if signature.data_size as usize != SM2_ECC_SM2_P256_KEY_SIZE {
+        return Err(SPDM_STATUS_VERIF_FAIL);
+    }
+
+    sm2_verify(sm2_id, public_key, data, signature.as_ref())
+}
+
+// IN signing ID, uncompressed SEC1 public key, message and r || s signature
+pub(crate) fn sm2_verify(
+    id: &[u8],
+    public_key: &[u8],
+    data: &[u8],
+    signature: &[u8],
+) -> SpdmResult {
+    let id = core::str::from_utf8(id).map_err(|_| SPDM_STATUS_VERIF_FAIL)?;
+    let verifying_key =
+        VerifyingKey::from_sec1_bytes(id, public_key).map_err(|_| SPDM_STATUS_VERIF_FAIL)?;
+    let signature = Signature::try_from(signature).map_err(|_| SPDM_STATUS_VERIF_FAIL)?;
+    verifying_key
+        .verify(data, &signature)
+        .map_err(|_| SPDM_STATUS_VERIF_FAIL)
+}
+
+#[cfg(all(test,))]
+mod tests {
+    use super::*;
+    use crate::asym_sign_impl::sign;
+    use spdmlib::protocol::SPDM_CHALLENGE_AUTH_SIGN_CONTEXT;
+
+    #[test]
+    fn test_case0_asym_verify() {
+        let base_hash_algo = SpdmBaseHashAlgo::TPM_ALG_SM3_256;
+        let private_key = std::fs::read("../test_key/sm2/end_responder.key.p8")
+            .expect("unable to read private key!");
+        let public_cert_der = std::fs::read("../test_key/sm2/bundle_responder.certchain.der")
+            .expect("unable to read cert chain!");
+        let public_key_der = std::fs::read("../test_key/sm2/end_responder.key.pub.der")
+            .expect("unable to read public key!");
+        let sm2_id = &SPDM_CHALLENGE_AUTH_SIGN_CONTEXT[..];
+        let data = [0x5au8; 100];
+
+        let signature = sign(base_hash_algo, &private_key, sm2_id, &data).unwrap();
+        assert_eq!(signature.data_size, 64);
+        assert!(
+            (DEFAULT.verify_cb)(base_hash_algo, sm2_id, &public_cert_der, &data, &signature)
+                .is_ok()
+        );
+        assert!((DEFAULT.verify_with_public_key_cb)(
+            base_hash_algo,
+            sm2_id,
+            &public_key_der,
+            &data,
+            &signature
+        )
+        .is_ok());
+
+        // The signature is bound to the signing ID.
+        assert!(asym_verify(base_hash_algo, b"", &public_cert_der, &data, &signature).is_err());
+        let signature = sign(base_hash_algo, &private_key, b"", &data).unwrap();
+        assert!(asym_verify(base_hash_algo, b"", &public_cert_der, &data, &signature).is_ok());
+        assert!(asym_verify(base_hash_algo, sm2_id, &public_cert_der, &data, &signature).is_err());
+
+        assert!(asym_verify(
+            SpdmBaseHashAlgo::TPM_ALG_SHA_256,
+            b"",
+            &public_cert_der,
+            &data,
+            &signature
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,187 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+extern crate alloc;
+use alloc::vec::Vec;
+
+use crate::asym_verify_impl::sm2_verify;
+use crate::SM2_DEFAULT_ID;
+use spdmlib::crypto::{is_ca_certificate, SpdmCertOperation};
+use spdmlib::error::{SpdmResult, SPDM_STATUS_INVALID_CERT};
+use spdmlib::protocol::SM2_ECC_SM2_P256_KEY_SIZE;
+use x509_cert::der::asn1::ObjectIdentifier;
+use x509_cert::der::{Decode, Encode};
+use x509_cert::Certificate;
+
+// SM2-with-SM3 1.2.156.10197.1.501
+const OID_SM2_SM3: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.156.10197.1.501");
+
+pub static DEFAULT: SpdmCertOperation = SpdmCertOperation {
+    get_cert_from_cert_chain_cb: get_cert_from_cert_chain,
+    verify_cert_chain_cb: verify_cert_chain,
+};
+
+pub(crate) fn get_cert_from_cert_chain(
+    cert_chain: &[u8],
+    index: isize,
+) -> SpdmResult<(usize, usize)> {
+    let mut offset = 0usize;
+    let mut this_index = 0isize;
+    let cert_chain_size = cert_chain.len();
+    loop {
+        if offset > cert_chain_size || cert_chain[offset..].len() < 4 {
+            return Err(SPDM_STATUS_INVALID_CERT);
+        }
+        if cert_chain[offset] != 0x30 || cert_chain[offset + 1] != 0x82 {
+            return Err(SPDM_STATUS_INVALID_CERT);
+        }
+        let this_cert_len =
+            ((cert_chain[offset + 2] as usize) << 8) + (cert_chain[offset + 3] as usize) + 4;
+        if this_cert_len > cert_chain_size - offset {
+            return Err(SPDM_STATUS_INVALID_CERT);
+        }
+        if this_index == index {
+            // return the this one
+            return Ok((offset, offset + this_cert_len));
+        }
+        this_index += 1;
+        if (offset + this_cert_len == cert_chain_size) && (index == -1) {
+            // return the last one
+            return Ok((offset, offset + this_cert_len));
+        }
+        offset += this_cert_len;
+    }
+}
+
+// Checks that every certificate is signed by its predecessor, starting from
+// a self-signed root. Only in the alias certificate model may the chain be
+// partial, ending at the device certificate, a CA. The certificate model and
+// the leaf fields are checked by spdmlib itself.
+fn verify_cert_chain(
+    cert_chain: &[u8],
+    _is_requester_cert: bool,
+    is_alias_cert_model: bool,
+) -> SpdmResult {
+    let mut certs = Vec::new();
+    let mut offset = 0usize;
+    let mut leaf_begin = 0usize;
+    while offset < cert_chain.len() {
+        let (_, end) = get_cert_from_cert_chain(&cert_chain[offset..], 0)?;
+        let cert = Certificate::from_der(&cert_chain[offset..(offset + end)])
+            .map_err(|_| SPDM_STATUS_INVALID_CERT)?;
+        certs.push(cert);
+        leaf_begin = offset;
+        offset += end;
+    }
+    if certs.is_empty() {
+        return Err(SPDM_STATUS_INVALID_CERT);
+    }
+    if !is_alias_cert_model && is_ca_certificate(&cert_chain[leaf_begin..])? {
+        return Err(SPDM_STATUS_INVALID_CERT);
+    }
+
+    verify_cert_signature(&certs[0], &certs[0])?;
+    for pair in certs.windows(2) {
+        verify_cert_signature(&pair[0], &pair[1])?;
+    }
+    Ok(())
+}
+
+fn verify_cert_signature(issuer: &Certificate, cert: &Certificate) -> SpdmResult {
+    if cert.signature_algorithm.oid != OID_SM2_SM3
+        || cert.tbs_certificate.issuer != issuer.tbs_certificate.subject
+    {
+        return Err(SPDM_STATUS_INVALID_CERT);
+    }
+    let public_key = issuer
+        .tbs_certificate
+        .subject_public_key_info
+        .subject_public_key
+        .as_bytes()
+        .ok_or(SPDM_STATUS_INVALID_CERT)?;
+    let tbs = cert
+        .tbs_certificate
+        .to_der()
+        .map_err(|_| SPDM_STATUS_INVALID_CERT)?;
+    let der_signature = cert.signature.as_bytes().ok_or(SPDM_STATUS_INVALID_CERT)?;
+
+    let mut signature = [0u8; SM2_ECC_SM2_P256_KEY_SIZE];
+    ecc_signature_der_to_bin(der_signature, &mut signature)?;
+    sm2_verify(SM2_DEFAULT_ID.as_bytes(), public_key, &tbs, &signature)
+        .map_err(|_| SPDM_STATUS_INVALID_CERT)
+}
+
+// remove ASN.1 from the DER signature, SEQUENCE { INTEGER r, INTEGER s }
+fn ecc_signature_der_to_bin(der_signature: &[u8], signature: &mut [u8]) -> SpdmResult {
+    let half_size = signature.len() / 2;
+    if der_signature.len() < 2
+        || der_signature[0] != 0x30
+        || der_signature[1] as usize != der_signature.len() - 2
+    {
+        return Err(SPDM_STATUS_INVALID_CERT);
+    }
+
+    let mut walker = 2usize;
+    for i in 0..2 {
+        if der_signature.len() < walker + 2 || der_signature[walker] != 0x02 {
+            return Err(SPDM_STATUS_INVALID_CERT);
+        }
+        let int_size = der_signature[walker + 1] as usize;
+        walker += 2;
+        if der_signature.len() < walker + int_size {
+            return Err(SPDM_STATUS_INVALID_CERT);
+        }
+        let mut int = &der_signature[walker..(walker + int_size)];
+        // strip the sign octet
+        while int.len() > half_size && int[0] == 0 {
+            int = &int[1..];
+        }
+        if int.len() > half_size {
+            return Err(SPDM_STATUS_INVALID_CERT);
+        }
+        let end = half_size * (i + 1);
+        signature[(end - int.len())..end].copy_from_slice(int);
+        walker += int_size;
+    }
+
+    if walker == der_signature.len() {
+        Ok(())
+    } else {
+        Err(SPDM_STATUS_INVALID_CERT)
+    }
+}
+
+#[cfg(all(test,))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_case0_get_cert_from_cert_chain() {
+        let cert_chain = std::fs::read("../test_key/sm2/bundle_responder.certchain.der")
+            .expect("unable to read cert chain!");
+        let (start, end) = get_cert_from_cert_chain(&cert_chain, -1).unwrap();
+        assert_eq!(end, cert_chain.len());
+        assert!(Certificate::from_der(&cert_chain[start..end]).is_ok());
+        assert!(get_cert_from_cert_chain(&cert_chain, 3).is_err());
+    }
+
+    #[test]
+    fn test_case0_verify_cert_chain() {
+        // The libspdm SM2 test chain carries ecdsa-with-SHA256 signatures.
+        let cert_chain = std::fs::read("../test_key/sm2/bundle_responder.certchain.der")
+            .expect("unable to read cert chain!");
+        assert!(verify_cert_chain(&cert_chain, false, false).is_err());
+        assert!(verify_cert_chain(&[], false, false).is_err());
+    }
+
+    #[test]
+    fn test_case0_ecc_signature_der_to_bin() {
+        let der_signature = [0x30, 0x08, 0x02, 0x02, 0x00, 0x81, 0x02, 0x02, 0x12, 0x34];
+        let signature = &mut [0u8; 8];
+        assert!(ecc_signature_der_to_bin(&der_signature, signature).is_ok());
+        assert_eq!(signature, &[0, 0, 0, 0x81, 0, 0, 0x12, 0x34]);
+
+        assert!(ecc_signature_der_to_bin(&der_signature[..9], signature).is_err());
+    }
+}
@@ -0,0 +1,106 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+extern crate alloc;
+use alloc::boxed::Box;
+
+use elliptic_curve::ecdh::EphemeralSecret;
+use elliptic_curve::sec1::ToEncodedPoint;
+use rand_core::OsRng;
+use sm2::{PublicKey, Sm2};
+use spdmlib::crypto::{SpdmDhe, SpdmDheKeyExchange};
+use spdmlib::protocol::{
+    SpdmDheAlgo, SpdmDheExchangeStruct, SpdmDheFinalKeyStruct, SpdmKemAlgo, SM2_P256_KEY_SIZE,
+};
+
+pub static DEFAULT: SpdmDhe = SpdmDhe {
+    generate_key_pair_cb: generate_key_pair,
+    kem_generate_key_pair_cb: kem_generate_key_pair,
+    kem_encapsulate_cb: kem_encapsulate,
+};
+
+fn generate_key_pair(
+    dhe_algo: SpdmDheAlgo,
+) -> Option<(SpdmDheExchangeStruct, Box<dyn SpdmDheKeyExchange>)> {
+    match dhe_algo {
+        SpdmDheAlgo::SM2_P256 => SpdmDheKeyExchangeSm2::generate_key_pair(),
+        _ => None,
+    }
+}
+
+fn kem_generate_key_pair(
+    _kem_algo: SpdmKemAlgo,
+) -> Option<(SpdmDheExchangeStruct, Box<dyn SpdmDheKeyExchange>)> {
+    None
+}
+
+fn kem_encapsulate(
+    _kem_algo: SpdmKemAlgo,
+    _peer_encap_key: &SpdmDheExchangeStruct,
+) -> Option<(SpdmDheExchangeStruct, SpdmDheFinalKeyStruct)> {
+    None
+}
+
+struct SpdmDheKeyExchangeSm2(EphemeralSecret<Sm2>);
+
+impl SpdmDheKeyExchangeSm2 {
+    fn generate_key_pair() -> Option<(SpdmDheExchangeStruct, Box<dyn SpdmDheKeyExchange>)> {
+        let private_key = EphemeralSecret::<Sm2>::random(&mut OsRng);
+        let point = private_key.public_key().to_encoded_point(false);
+
+        // SPDM carries the uncompressed point without the leading 0x04.
+        let mut public_key = SpdmDheExchangeStruct {
+            data_size: SM2_P256_KEY_SIZE as u16,
+            ..Default::default()
+        };
+        public_key.data[..SM2_P256_KEY_SIZE].copy_from_slice(&point.as_bytes()[1..]);
+
+        let res: Box<dyn SpdmDheKeyExchange> = Box::new(Self(private_key));
+        Some((public_key, res))
+    }
+}
+
+impl SpdmDheKeyExchange for SpdmDheKeyExchangeSm2 {
+    fn compute_final_key(
+        self: Box<Self>,
+        peer_pub_key: &SpdmDheExchangeStruct,
+    ) -> Option<SpdmDheFinalKeyStruct> {
+        if peer_pub_key.data_size as usize != SM2_P256_KEY_SIZE {
+            return None;
+        }
+        let mut pubkey = [0u8; SM2_P256_KEY_SIZE + 1];
+        pubkey[0] = 0x4u8;
+        pubkey[1..].copy_from_slice(peer_pub_key.as_ref());
+
+        let peer_public_key = PublicKey::from_sec1_bytes(&pubkey).ok()?;
+        let shared_secret = self.0.diffie_hellman(&peer_public_key);
+        Some(SpdmDheFinalKeyStruct::from(
+            shared_secret.raw_secret_bytes().as_slice(),
+        ))
+    }
+}
+
+#[cfg(all(test,))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_case0_dhe() {
+        let dhe_algo = SpdmDheAlgo::SM2_P256;
+        let (exchange1, private1) = generate_key_pair(dhe_algo).unwrap();
+        let (exchange2, private2) = generate_key_pair(dhe_algo).unwrap();
+        assert_eq!(exchange1.data_size as usize, SM2_P256_KEY_SIZE);
+
+        let peer1 = private1.compute_final_key(&exchange2).unwrap();
+        let peer2 = private2.compute_final_key(&exchange1).unwrap();
+
+        assert_eq!(peer1.as_ref(), peer2.as_ref());
+    }
+    #[test]
+    fn test_case1_dhe() {
+        for dhe_algo in [SpdmDheAlgo::empty(), SpdmDheAlgo::SECP_256_R1].iter() {
+            assert!(generate_key_pair(*dhe_algo).is_none());
+        }
+    }
+}
@@ -0,0 +1,141 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use sm3::{Digest, Sm3};
+use spdmlib::crypto::SpdmHash;
+use spdmlib::protocol::{SpdmBaseHashAlgo, SpdmDigestStruct};
+
+#[cfg(feature = "hashed-transcript-data")]
+mod hash_ext {
+    extern crate alloc;
+    use super::*;
+    use alloc::boxed::Box;
+    use alloc::collections::BTreeMap;
+    use lazy_static::lazy_static;
+    use spdmlib::error::{SpdmResult, SPDM_STATUS_CRYPTO_ERROR};
+    use spin::Mutex;
+    pub type HashCtxConcrete = Sm3;
+    lazy_static! {
+        static ref HASH_CTX_TABLE: Mutex<BTreeMap<usize, Box<HashCtxConcrete>>> =
+            Mutex::new(BTreeMap::new());
+    }
+
+    pub static DEFAULT: SpdmHash = SpdmHash {
+        hash_all_cb: hash_all,
+        hash_ctx_init_cb: hash_ctx_init,
+        hash_ctx_update_cb: hash_ctx_update,
+        hash_ctx_finalize_cb: hash_ctx_finalize,
+        hash_ctx_dup_cb: hash_ctx_dup,
+    };
+
+    pub(crate) fn hash_ctx_init(base_hash_algo: SpdmBaseHashAlgo) -> Option<usize> {
+        match base_hash_algo {
+            SpdmBaseHashAlgo::TPM_ALG_SM3_256 => {}
+            _ => return None,
+        };
+        let ctx = Box::new(Sm3::new());
+        Some(insert_to_table(ctx))
+    }
+
+    pub(crate) fn hash_ctx_update(handle: usize, data: &[u8]) -> SpdmResult {
+        let mut table = HASH_CTX_TABLE.lock();
+        let ctx = table.get_mut(&handle).ok_or(SPDM_STATUS_CRYPTO_ERROR)?;
+        ctx.update(data);
+        Ok(())
+    }
+
+    pub(crate) fn hash_ctx_finalize(handle: usize) -> Option<SpdmDigestStruct> {
+        let ctx = HASH_CTX_TABLE.lock().remove(&handle)?;
+        Some(SpdmDigestStruct::from(ctx.finalize().as_slice()))
+    }
+
+    pub(crate) fn hash_ctx_dup(handle: usize) -> Option<usize> {
+        let ctx_new = {
+            let table = HASH_CTX_TABLE.lock();
+            let ctx = table.get(&handle)?;
+            ctx.clone()
+        };
+        Some(insert_to_table(ctx_new))
+    }
+
+    pub(crate) fn insert_to_table(value: Box<HashCtxConcrete>) -> usize {
+        let handle_ptr: *const HashCtxConcrete = &*value;
+        let handle = handle_ptr as usize;
+        HASH_CTX_TABLE.lock().insert(handle, value);
+        handle
+    }
+
+    #[allow(dead_code)]
+    #[cfg(test)]
+    pub fn get_hash_ctx_count() -> usize {
+        HASH_CTX_TABLE.lock().len()
+    }
+}
+#[cfg(feature = "hashed-transcript-data")]
+pub use hash_ext::DEFAULT;
+
+#[cfg(not(feature = "hashed-transcript-data"))]
+pub static DEFAULT: SpdmHash = SpdmHash {
+    hash_all_cb: hash_all,
+};
+
+fn hash_all(base_hash_algo: SpdmBaseHashAlgo, data: &[u8]) -> Option<SpdmDigestStruct> {
+    match base_hash_algo {
+        SpdmBaseHashAlgo::TPM_ALG_SM3_256 => {
+            Some(SpdmDigestStruct::from(Sm3::digest(data).as_slice()))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(all(test,))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_case0_hash_all() {
+        let base_hash_algo = SpdmBaseHashAlgo::TPM_ALG_SM3_256;
+        let data = &b"abc"[..];
+
+        let hash_all = hash_all(base_hash_algo, data).unwrap();
+        assert_eq!(hash_all.data_size, 32);
+        // GB/T 32905-2016 example 1
+        assert_eq!(
+            hash_all.as_ref(),
+            &[
+                0x66, 0xc7, 0xf0, 0xf4, 0x62, 0xee, 0xed, 0xd9, 0xd1, 0xf2, 0xd4, 0x6b, 0xdc, 0x10,
+                0xe4, 0xe2, 0x41, 0x67, 0xc4, 0x87, 0x5c, 0xf2, 0xf7, 0xa2, 0x29, 0x7d, 0xa0, 0x2b,
+                0x8f, 0x4b, 0xa8, 0xe0
+            ]
+        );
+    }
+    #[test]
+    fn test_case1_hash_all() {
+        let base_hash_algo = SpdmBaseHashAlgo::TPM_ALG_SHA_256;
+        let data = &mut [0u8; 64];
+
+        let hash_all = hash_all(base_hash_algo, data);
+        assert!(hash_all.is_none());
+    }
+    #[cfg(feature = "hashed-transcript-data")]
+    #[test]
+    fn test_case2_hash_ctx() {
+        use hash_ext::*;
+        let base_hash_algo = SpdmBaseHashAlgo::TPM_ALG_SM3_256;
+
+        let handle = hash_ctx_init(base_hash_algo).unwrap();
+        assert!(hash_ctx_update(handle, b"a").is_ok());
+        let dup = hash_ctx_dup(handle).unwrap();
+        assert!(hash_ctx_update(handle, b"bc").is_ok());
+        assert!(hash_ctx_update(dup, b"bc").is_ok());
+
+        let expected = hash_all(base_hash_algo, b"abc").unwrap();
+        assert_eq!(
+            hash_ctx_finalize(handle).unwrap().as_ref(),
+            expected.as_ref()
+        );
+        assert_eq!(hash_ctx_finalize(dup).unwrap().as_ref(), expected.as_ref());
+        assert!(hash_ctx_init(SpdmBaseHashAlgo::TPM_ALG_SHA_384).is_none());
+    }
+}
@@ -0,0 +1,89 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use hkdf::Hkdf;
+use sm3::Sm3;
+use spdmlib::crypto::SpdmHkdf;
+use spdmlib::protocol::{
+    SpdmBaseHashAlgo, SpdmHkdfInputKeyingMaterial, SpdmHkdfOutputKeyingMaterial,
+    SpdmHkdfPseudoRandomKey, SPDM_MAX_HKDF_OKM_SIZE,
+};
+
+pub static DEFAULT: SpdmHkdf = SpdmHkdf {
+    hkdf_extract_cb: hkdf_extract,
+    hkdf_expand_cb: hkdf_expand,
+};
+
+fn hkdf_extract(
+    hash_algo: SpdmBaseHashAlgo,
+    salt: &[u8],
+    ikm: &SpdmHkdfInputKeyingMaterial,
+) -> Option<SpdmHkdfPseudoRandomKey> {
+    match hash_algo {
+        SpdmBaseHashAlgo::TPM_ALG_SM3_256 => {
+            let (prk, _) = Hkdf::<Sm3>::extract(Some(salt), ikm.as_ref());
+            Some(SpdmHkdfPseudoRandomKey::from(prk.as_slice()))
+        }
+        _ => None,
+    }
+}
+
+fn hkdf_expand(
+    hash_algo: SpdmBaseHashAlgo,
+    prk: &SpdmHkdfPseudoRandomKey,
+    info: &[u8],
+    out_size: u16,
+) -> Option<SpdmHkdfOutputKeyingMaterial> {
+    if out_size as usize > SPDM_MAX_HKDF_OKM_SIZE {
+        return None;
+    }
+
+    match hash_algo {
+        SpdmBaseHashAlgo::TPM_ALG_SM3_256 => {
+            if prk.data_size != hash_algo.get_size() {
+                return None;
+            }
+            let hkdf = Hkdf::<Sm3>::from_prk(prk.as_ref()).ok()?;
+            let mut okm = SpdmHkdfOutputKeyingMaterial::default();
+            hkdf.expand(info, &mut okm.data[..out_size as usize]).ok()?;
+            okm.data_size = out_size;
+            Some(okm)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(all(test,))]
+mod tests {
+    use super::*;
+    use spdmlib::protocol::SpdmFinishedKeyStruct;
+
+    #[test]
+    fn test_case0_hkdf() {
+        let hash_algo = SpdmBaseHashAlgo::TPM_ALG_SM3_256;
+        let ikm = SpdmFinishedKeyStruct::from(&[100u8; 32][..]);
+        let salt = &[0u8; 32];
+
+        let prk = hkdf_extract(
+            hash_algo,
+            salt,
+            &SpdmHkdfInputKeyingMaterial::SpdmFinishedKey(&ikm),
+        )
+        .unwrap();
+        assert_eq!(prk.data_size, 32);
+
+        let okm = hkdf_expand(hash_algo, &prk, b"spdm", 64).unwrap();
+        assert_eq!(okm.data_size, 64);
+        let okm_short = hkdf_expand(hash_algo, &prk, b"spdm", 16).unwrap();
+        assert_eq!(okm_short.as_ref(), &okm.as_ref()[..16]);
+    }
+    #[test]
+    fn test_case1_hkdf() {
+        let hash_algo = SpdmBaseHashAlgo::TPM_ALG_SM3_256;
+        let prk = SpdmHkdfPseudoRandomKey::from(&[100u8; 32][..]);
+
+        assert!(hkdf_expand(hash_algo, &prk, b"spdm", 100).is_none());
+        assert!(hkdf_expand(SpdmBaseHashAlgo::TPM_ALG_SHA_256, &prk, b"spdm", 32).is_none());
+    }
+}
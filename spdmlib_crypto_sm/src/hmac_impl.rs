@@ -0,0 +1,68 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use hmac::{Hmac, Mac};
+use sm3::Sm3;
+use spdmlib::crypto::SpdmHmac;
+use spdmlib::error::{SpdmResult, SPDM_STATUS_CRYPTO_ERROR, SPDM_STATUS_VERIF_FAIL};
+use spdmlib::protocol::{SpdmBaseHashAlgo, SpdmDigestStruct};
+
+pub static DEFAULT: SpdmHmac = SpdmHmac {
+    hmac_cb: hmac,
+    hmac_verify_cb: hmac_verify,
+};
+
+fn hmac(base_hash_algo: SpdmBaseHashAlgo, key: &[u8], data: &[u8]) -> Option<SpdmDigestStruct> {
+    match base_hash_algo {
+        SpdmBaseHashAlgo::TPM_ALG_SM3_256 => {
+            let mut ctx = Hmac::<Sm3>::new_from_slice(key).ok()?;
+            ctx.update(data);
+            Some(SpdmDigestStruct::from(
+                ctx.finalize().into_bytes().as_slice(),
+            ))
+        }
+        _ => None,
+    }
+}
+
+fn hmac_verify(
+    base_hash_algo: SpdmBaseHashAlgo,
+    key: &[u8],
+    data: &[u8],
+    message_digest: &SpdmDigestStruct,
+) -> SpdmResult {
+    match base_hash_algo {
+        SpdmBaseHashAlgo::TPM_ALG_SM3_256 => {
+            let mut ctx = Hmac::<Sm3>::new_from_slice(key).map_err(|_| SPDM_STATUS_CRYPTO_ERROR)?;
+            ctx.update(data);
+            ctx.verify_slice(message_digest.as_ref())
+                .map_err(|_| SPDM_STATUS_VERIF_FAIL)
+        }
+        _ => Err(SPDM_STATUS_CRYPTO_ERROR),
+    }
+}
+
+#[cfg(all(test,))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_case0_hmac_verify() {
+        let base_hash_algo = SpdmBaseHashAlgo::TPM_ALG_SM3_256;
+        let key = &[10u8; 32];
+        let data = &[100u8; 64];
+
+        let spdm_digest = hmac(base_hash_algo, key, data).unwrap();
+        assert_eq!(spdm_digest.data_size, 32);
+        assert!(hmac_verify(base_hash_algo, key, data, &spdm_digest).is_ok());
+
+        let wrong_data = &[101u8; 64];
+        assert!(hmac_verify(base_hash_algo, key, wrong_data, &spdm_digest).is_err());
+    }
+    #[test]
+    fn test_case1_hmac() {
+        let base_hash_algo = SpdmBaseHashAlgo::TPM_ALG_SHA_256;
+        assert!(hmac(base_hash_algo, &[10u8; 32], &[100u8; 64]).is_none());
+    }
+}
@@ -0,0 +1,20 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+#![cfg_attr(not(test), no_std)]
+
+extern crate alloc;
+
+pub mod aead_impl;
+pub mod asym_sign_impl;
+pub mod asym_verify_impl;
+pub mod cert_operation_impl;
+pub mod dhe_impl;
+pub mod hash_impl;
+pub mod hkdf_impl;
+pub mod hmac_impl;
+pub mod rand_impl;
+
+/// Default SM2 ID of GM/T 0009, used for X.509 certificate signatures.
+pub(crate) const SM2_DEFAULT_ID: &str = "1234567812345678";
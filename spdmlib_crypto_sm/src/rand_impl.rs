@@ -0,0 +1,29 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use rand_core::{OsRng, RngCore};
+use spdmlib::crypto::SpdmCryptoRandom;
+use spdmlib::error::{SpdmResult, SPDM_STATUS_CRYPTO_ERROR};
+
+pub static DEFAULT: SpdmCryptoRandom = SpdmCryptoRandom {
+    get_random_cb: get_random,
+};
+
+fn get_random(data: &mut [u8]) -> SpdmResult<usize> {
+    OsRng
+        .try_fill_bytes(data)
+        .map_err(|_| SPDM_STATUS_CRYPTO_ERROR)?;
+    Ok(data.len())
+}
+
+#[cfg(all(test,))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_case0_get_random() {
+        let data = &mut [0u8; 64];
+        assert_eq!(get_random(data), Ok(64));
+    }
+}
@@ -24,3 +24,37 @@ pub fn crypto_mbedtls_register_handles() {
 
     spdmlib::crypto::rand::register(spdmlib_crypto_mbedtls::rand_impl::DEFAULT.clone());
 }
+
+#[cfg(feature = "spdm-openssl")]
+pub fn crypto_openssl_register_handles() {
+    spdmlib::crypto::aead::register(spdmlib_crypto_openssl::aead_impl::DEFAULT.clone());
+
+    spdmlib::crypto::asym_verify::register(
+        spdmlib_crypto_openssl::asym_verify_impl::DEFAULT.clone(),
+    );
+
+    spdmlib::crypto::cert_operation::register(
+        spdmlib_crypto_openssl::cert_operation_impl::DEFAULT.clone(),
+    );
+
+    spdmlib::crypto::dhe::register(spdmlib_crypto_openssl::dhe_impl::DEFAULT.clone());
+
+    spdmlib::crypto::hash::register(spdmlib_crypto_openssl::hash_impl::DEFAULT.clone());
+
+    spdmlib::crypto::hkdf::register(spdmlib_crypto_openssl::hkdf_impl::DEFAULT.clone());
+
+    spdmlib::crypto::hmac::register(spdmlib_crypto_openssl::hmac_impl::DEFAULT.clone());
+
+    spdmlib::crypto::rand::register(spdmlib_crypto_openssl::rand_impl::DEFAULT.clone());
+
+    // Both emulators sign with the responder key of the provisioned chain.
+    let key_file = if crate::spdm_emu::USE_ECDSA {
+        "test_key/ecp384/end_responder.key"
+    } else {
+        "test_key/rsa3072/end_responder.key"
+    };
+    spdmlib_crypto_openssl::asym_sign_impl::set_key_file(
+        spdmlib::protocol::SPDM_DEFAULT_KEY_PAIR_ID,
+        crate::crypto_callback::get_test_key_directory().join(key_file),
+    );
+}
@@ -2,9 +2,14 @@
 //
 // SPDX-License-Identifier: BSD-2-Clause-Patent
 
+// The ring signer is not used with the OpenSSL backend.
+#![cfg_attr(feature = "spdm-openssl", allow(dead_code))]
+
 use std::path::PathBuf;
 
+#[cfg(not(feature = "spdm-openssl"))]
 use spdmlib::secret::SpdmSecretAsymSign;
+use spdmlib::secret::SpdmSecretResult;
 
 use spdmlib::protocol::{
     SpdmBaseAsymAlgo, SpdmBaseHashAlgo, SpdmSignatureStruct, RSAPSS_2048_KEY_SIZE,
@@ -12,15 +17,22 @@ use spdmlib::protocol::{
     RSASSA_4096_KEY_SIZE, SPDM_MAX_ASYM_KEY_SIZE,
 };
 
+#[cfg(not(feature = "spdm-openssl"))]
 pub static SECRET_ASYM_IMPL_INSTANCE: SpdmSecretAsymSign =
     SpdmSecretAsymSign { sign_cb: asym_sign };
 
+// The OpenSSL backend signs with the key file set in
+// crypto::crypto_openssl_register_handles.
+#[cfg(feature = "spdm-openssl")]
+pub use spdmlib_crypto_openssl::asym_sign_impl::SECRET_ASYM_IMPL_INSTANCE;
+
 fn asym_sign(
     base_hash_algo: SpdmBaseHashAlgo,
     base_asym_algo: SpdmBaseAsymAlgo,
+    _key_pair_id: u8,
     data: &[u8],
-) -> Option<SpdmSignatureStruct> {
-    match (base_hash_algo, base_asym_algo) {
+) -> SpdmSecretResult<SpdmSignatureStruct> {
+    let signature = match (base_hash_algo, base_asym_algo) {
         (SpdmBaseHashAlgo::TPM_ALG_SHA_256, SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P256) => {
             sign_ecdsa_asym_algo(&ring::signature::ECDSA_P256_SHA256_FIXED_SIGNING, data)
         }
@@ -81,10 +93,16 @@ fn asym_sign(
                 data,
             )
         }
+        (_, SpdmBaseAsymAlgo::EDDSA_ED25519) => sign_eddsa_asym_algo(data),
+        #[cfg(feature = "spdm-mbedtls")]
+        (SpdmBaseHashAlgo::TPM_ALG_SHA_512, SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P521) => {
+            sign_ecdsa_p521_asym_algo(base_hash_algo, data)
+        }
         _ => {
             panic!();
         }
-    }
+    };
+    signature.into()
 }
 
 fn sign_ecdsa_asym_algo(
@@ -124,6 +142,47 @@ fn sign_ecdsa_asym_algo(
     })
 }
 
+// ring doesn't implement P-521, so it is signed by the mbedtls backend.
+#[cfg(feature = "spdm-mbedtls")]
+fn sign_ecdsa_p521_asym_algo(
+    base_hash_algo: SpdmBaseHashAlgo,
+    data: &[u8],
+) -> Option<SpdmSignatureStruct> {
+    let crate_dir = get_test_key_directory();
+    let key_file_path = crate_dir.join("test_key/ecp521/end_responder.key.der");
+    let der_file = std::fs::read(key_file_path).expect("unable to read key der!");
+
+    spdmlib_crypto_mbedtls::asym_sign_impl::sign(
+        base_hash_algo,
+        SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P521,
+        der_file.as_slice(),
+        data,
+    )
+}
+
+fn sign_eddsa_asym_algo(data: &[u8]) -> Option<SpdmSignatureStruct> {
+    // openssl genpkey -algorithm ed25519 -outform DER > private.der
+    // ring doesn't implement Ed448, so only Ed25519 is supported.
+    let crate_dir = get_test_key_directory();
+    let key_file_path = crate_dir.join("test_key/ed25519/end_responder.key.p8");
+    let der_file = std::fs::read(key_file_path).expect("unable to read key der!");
+    let key_bytes = der_file.as_slice();
+
+    let key_pair: ring::signature::Ed25519KeyPair =
+        ring::signature::Ed25519KeyPair::from_pkcs8_maybe_unchecked(key_bytes).ok()?;
+
+    let signature = key_pair.sign(data);
+    let signature = signature.as_ref();
+
+    let mut full_signature: [u8; SPDM_MAX_ASYM_KEY_SIZE] = [0u8; SPDM_MAX_ASYM_KEY_SIZE];
+    full_signature[..signature.len()].copy_from_slice(signature);
+
+    Some(SpdmSignatureStruct {
+        data_size: signature.len() as u16,
+        data: full_signature,
+    })
+}
+
 fn sign_rsa_asym_algo(
     padding_alg: &'static dyn ring::signature::RsaEncoding,
     key_len: usize,
@@ -170,7 +229,7 @@ fn sign_rsa_asym_algo(
     })
 }
 
-fn get_test_key_directory() -> PathBuf {
+pub(crate) fn get_test_key_directory() -> PathBuf {
     let crate_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
     let crate_dir = crate_dir
         .parent()
@@ -29,12 +29,44 @@ pub static SECRET_PSK_IMPL_INSTANCE: SpdmSecretPsk = SpdmSecretPsk {
     master_secret_hkdf_expand_cb: master_secret_hkdf_expand_impl,
 };
 
-#[allow(clippy::field_reassign_with_default)]
 fn measurement_collection_impl(
     spdm_version: SpdmVersion,
     measurement_specification: SpdmMeasurementSpecification,
     measurement_hash_algo: SpdmMeasurementHashAlgo,
     measurement_index: usize,
+) -> SpdmSecretResult<SpdmMeasurementRecordStructure> {
+    collect_measurement_record(
+        spdm_version,
+        measurement_specification,
+        measurement_hash_algo,
+        measurement_index,
+    )
+    .into()
+}
+
+fn generate_measurement_summary_hash_impl(
+    spdm_version: SpdmVersion,
+    base_hash_algo: SpdmBaseHashAlgo,
+    measurement_specification: SpdmMeasurementSpecification,
+    measurement_hash_algo: SpdmMeasurementHashAlgo,
+    measurement_summary_hash_type: SpdmMeasurementSummaryHashType,
+) -> SpdmSecretResult<SpdmDigestStruct> {
+    get_measurement_summary_hash(
+        spdm_version,
+        base_hash_algo,
+        measurement_specification,
+        measurement_hash_algo,
+        measurement_summary_hash_type,
+    )
+    .into()
+}
+
+#[allow(clippy::field_reassign_with_default)]
+fn collect_measurement_record(
+    spdm_version: SpdmVersion,
+    measurement_specification: SpdmMeasurementSpecification,
+    measurement_hash_algo: SpdmMeasurementHashAlgo,
+    measurement_index: usize,
 ) -> Option<SpdmMeasurementRecordStructure> {
     if measurement_specification != SpdmMeasurementSpecification::DMTF {
         None
@@ -43,11 +75,12 @@ fn measurement_collection_impl(
             SpdmMeasurementHashAlgo::TPM_ALG_SHA_256 => SpdmBaseHashAlgo::TPM_ALG_SHA_256,
             SpdmMeasurementHashAlgo::TPM_ALG_SHA_384 => SpdmBaseHashAlgo::TPM_ALG_SHA_384,
             SpdmMeasurementHashAlgo::TPM_ALG_SHA_512 => SpdmBaseHashAlgo::TPM_ALG_SHA_512,
-            SpdmMeasurementHashAlgo::RAW_BIT_STREAM
-            | SpdmMeasurementHashAlgo::TPM_ALG_SHA3_256
-            | SpdmMeasurementHashAlgo::TPM_ALG_SHA3_384
-            | SpdmMeasurementHashAlgo::TPM_ALG_SHA3_512
-            | SpdmMeasurementHashAlgo::TPM_ALG_SM3 => return None,
+            SpdmMeasurementHashAlgo::TPM_ALG_SHA3_256 => SpdmBaseHashAlgo::TPM_ALG_SHA3_256,
+            SpdmMeasurementHashAlgo::TPM_ALG_SHA3_384 => SpdmBaseHashAlgo::TPM_ALG_SHA3_384,
+            SpdmMeasurementHashAlgo::TPM_ALG_SHA3_512 => SpdmBaseHashAlgo::TPM_ALG_SHA3_512,
+            SpdmMeasurementHashAlgo::RAW_BIT_STREAM | SpdmMeasurementHashAlgo::TPM_ALG_SM3 => {
+                return None
+            }
             _ => return None,
         };
         let hashsize = base_hash_algo.get_size();
@@ -185,7 +218,7 @@ fn measurement_collection_impl(
     }
 }
 
-fn generate_measurement_summary_hash_impl(
+fn get_measurement_summary_hash(
     spdm_version: SpdmVersion,
     base_hash_algo: SpdmBaseHashAlgo,
     measurement_specification: SpdmMeasurementSpecification,
@@ -294,7 +327,8 @@ mod tests {
             SpdmMeasurementSpecification::DMTF,
             SpdmBaseHashAlgo::TPM_ALG_SHA_512,
             1,
-        );
+        )
+        .ready();
         let deadbeefsha512 = [
             17, 58, 59, 199, 131, 216, 81, 252, 3, 115, 33, 75, 25, 234, 123, 233, 250, 61, 229,
             65, 236, 185, 254, 2, 109, 82, 198, 3, 232, 234, 25, 193, 116, 204, 14, 151, 5, 248,
@@ -93,10 +93,11 @@ fn test_spdm(
     };
 
     let config_info = common::SpdmConfigInfo {
-        spdm_version: [
+        spdm_version: &[
             SpdmVersion::SpdmVersion10,
             SpdmVersion::SpdmVersion11,
             SpdmVersion::SpdmVersion12,
+            SpdmVersion::SpdmVersion13,
         ],
         req_capabilities,
         req_ct_exponent: 0,
@@ -157,6 +158,30 @@ fn test_spdm(
     peer_root_cert_data.data_size = (ca_len) as u16;
     peer_root_cert_data.data[0..ca_len].copy_from_slice(ca_cert.as_ref());
 
+    // With a responder public key provisioned, there is no certificate chain to
+    // retrieve, and SlotID 0xFF selects the public key.
+    let peer_public_key = if cfg!(feature = "pub-key-id") {
+        let public_key_file_path = if USE_ECDSA {
+            "test_key/ecp384/end_responder.key.pub.der"
+        } else {
+            "test_key/rsa3072/end_responder.key.pub.der"
+        };
+        let public_key = std::fs::read(public_key_file_path).expect("unable to read public key!");
+        let mut peer_public_key = SpdmPublicKeyData {
+            data_size: public_key.len() as u16,
+            ..Default::default()
+        };
+        peer_public_key.data[..public_key.len()].copy_from_slice(public_key.as_ref());
+        Some(peer_public_key)
+    } else {
+        None
+    };
+    let slot_id = if peer_public_key.is_some() {
+        SPDM_PUBLIC_KEY_SLOT_ID
+    } else {
+        0
+    };
+
     let provision_info = if cfg!(feature = "mut-auth") {
         spdmlib::secret::asym_sign::register(SECRET_ASYM_IMPL_INSTANCE.clone());
         let mut my_cert_chain_data = SpdmCertChainData {
@@ -182,12 +207,18 @@ fn test_spdm(
             ],
             my_cert_chain: [None, None, None, None, None, None, None, None],
             peer_root_cert_data: Some(peer_root_cert_data),
+            my_public_key: None,
+            peer_public_key,
+            my_key_pair_id: [0u8; 8],
         }
     } else {
         common::SpdmProvisionInfo {
             my_cert_chain_data: [None, None, None, None, None, None, None, None],
             my_cert_chain: [None, None, None, None, None, None, None, None],
             peer_root_cert_data: Some(peer_root_cert_data),
+            my_public_key: None,
+            peer_public_key,
+            my_key_pair_id: [0u8; 8],
         }
     };
 
@@ -202,17 +233,22 @@ fn test_spdm(
         panic!("init_connection failed!");
     }
 
-    if context.send_receive_spdm_digest(None).is_err() {
-        panic!("send_receive_spdm_digest failed!");
-    }
+    if slot_id != SPDM_PUBLIC_KEY_SLOT_ID {
+        if context.send_receive_spdm_digest(None).is_err() {
+            panic!("send_receive_spdm_digest failed!");
+        }
 
-    if context.send_receive_spdm_certificate(None, 0).is_err() {
-        panic!("send_receive_spdm_certificate failed!");
+        if context
+            .send_receive_spdm_certificate(None, slot_id)
+            .is_err()
+        {
+            panic!("send_receive_spdm_certificate failed!");
+        }
     }
 
     if context
         .send_receive_spdm_challenge(
-            0,
+            slot_id,
             SpdmMeasurementSummaryHashType::SpdmMeasurementSummaryHashTypeNone,
         )
         .is_err()
@@ -225,7 +261,7 @@ fn test_spdm(
     if context
         .send_receive_spdm_measurement(
             None,
-            0,
+            slot_id,
             SpdmMeasurementAttributes::SIGNATURE_REQUESTED,
             SpdmMeasurementOperation::SpdmMeasurementRequestAll,
             &mut total_number,
@@ -238,7 +274,7 @@ fn test_spdm(
 
     let result = context.start_session(
         false,
-        0,
+        slot_id,
         SpdmMeasurementSummaryHashType::SpdmMeasurementSummaryHashTypeNone,
     );
     if let Ok(session_id) = result {
@@ -278,7 +314,7 @@ fn test_spdm(
         if context
             .send_receive_spdm_measurement(
                 Some(session_id),
-                0,
+                slot_id,
                 SpdmMeasurementAttributes::SIGNATURE_REQUESTED,
                 SpdmMeasurementOperation::SpdmMeasurementQueryTotalNumber,
                 &mut total_number,
@@ -289,15 +325,17 @@ fn test_spdm(
             panic!("send_receive_spdm_measurement failed");
         }
 
-        if context.send_receive_spdm_digest(Some(session_id)).is_err() {
-            panic!("send_receive_spdm_digest failed");
-        }
-
-        if context
-            .send_receive_spdm_certificate(Some(session_id), 0)
-            .is_err()
-        {
-            panic!("send_receive_spdm_certificate failed");
+        if slot_id != SPDM_PUBLIC_KEY_SLOT_ID {
+            if context.send_receive_spdm_digest(Some(session_id)).is_err() {
+                panic!("send_receive_spdm_digest failed");
+            }
+
+            if context
+                .send_receive_spdm_certificate(Some(session_id), slot_id)
+                .is_err()
+            {
+                panic!("send_receive_spdm_certificate failed");
+            }
         }
 
         if context.end_session(session_id).is_err() {
@@ -346,6 +384,9 @@ fn main() {
     #[cfg(feature = "spdm-mbedtls")]
     spdm_emu::crypto::crypto_mbedtls_register_handles();
 
+    #[cfg(feature = "spdm-openssl")]
+    spdm_emu::crypto::crypto_openssl_register_handles();
+
     let since_the_epoch = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .expect("Time went backwards");
@@ -86,6 +86,9 @@ fn main() {
     #[cfg(feature = "spdm-mbedtls")]
     spdm_emu::crypto::crypto_mbedtls_register_handles();
 
+    #[cfg(feature = "spdm-openssl")]
+    spdm_emu::crypto::crypto_openssl_register_handles();
+
     spdmlib::secret::measurement::register(SECRET_MEASUREMENT_IMPL_INSTANCE.clone());
     spdmlib::secret::psk::register(SECRET_PSK_IMPL_INSTANCE.clone());
 
@@ -139,8 +142,7 @@ fn handle_message(
 ) -> Result<bool, (usize, [u8; config::RECEIVER_BUFFER_SIZE])> {
     println!("handle_message!");
     let mut socket_io_transport = SocketIoTransport::new(stream);
-    let rsp_capabilities = SpdmResponseCapabilityFlags::CERT_CAP
-        | SpdmResponseCapabilityFlags::CHAL_CAP
+    let rsp_capabilities = SpdmResponseCapabilityFlags::CHAL_CAP
         | SpdmResponseCapabilityFlags::MEAS_CAP_SIG
         | SpdmResponseCapabilityFlags::MEAS_FRESH_CAP
         | SpdmResponseCapabilityFlags::ENCRYPT_CAP
@@ -151,7 +153,13 @@ fn handle_message(
         | SpdmResponseCapabilityFlags::HBEAT_CAP
         | SpdmResponseCapabilityFlags::KEY_UPD_CAP;
     // | SpdmResponseCapabilityFlags::HANDSHAKE_IN_THE_CLEAR_CAP
-    // | SpdmResponseCapabilityFlags::PUB_KEY_ID_CAP
+    // The responder is identified by either a certificate chain or a public key
+    // provisioned to the requester.
+    let rsp_capabilities = if cfg!(feature = "pub-key-id") {
+        rsp_capabilities | SpdmResponseCapabilityFlags::PUB_KEY_ID_CAP
+    } else {
+        rsp_capabilities | SpdmResponseCapabilityFlags::CERT_CAP
+    };
     let rsp_capabilities = if cfg!(feature = "mut-auth") {
         rsp_capabilities | SpdmResponseCapabilityFlags::MUT_AUTH_CAP
     } else {
@@ -159,10 +167,11 @@ fn handle_message(
     };
 
     let config_info = common::SpdmConfigInfo {
-        spdm_version: [
+        spdm_version: &[
             SpdmVersion::SpdmVersion10,
             SpdmVersion::SpdmVersion11,
             SpdmVersion::SpdmVersion12,
+            SpdmVersion::SpdmVersion13,
         ],
         rsp_capabilities,
         rsp_ct_exponent: 0,
@@ -229,6 +238,23 @@ fn handle_message(
     my_cert_chain_data.data[(ca_len + inter_len)..(ca_len + inter_len + leaf_len)]
         .copy_from_slice(leaf_cert.as_ref());
 
+    let my_public_key = if cfg!(feature = "pub-key-id") {
+        let public_key_file_path = if USE_ECDSA {
+            "test_key/ecp384/end_responder.key.pub.der"
+        } else {
+            "test_key/rsa3072/end_responder.key.pub.der"
+        };
+        let public_key = std::fs::read(public_key_file_path).expect("unable to read public key!");
+        let mut my_public_key = SpdmPublicKeyData {
+            data_size: public_key.len() as u16,
+            ..Default::default()
+        };
+        my_public_key.data[..public_key.len()].copy_from_slice(public_key.as_ref());
+        Some(my_public_key)
+    } else {
+        None
+    };
+
     let provision_info = common::SpdmProvisionInfo {
         my_cert_chain_data: [
             Some(my_cert_chain_data),
@@ -242,6 +268,9 @@ fn handle_message(
         ],
         my_cert_chain: [None, None, None, None, None, None, None, None],
         peer_root_cert_data: None,
+        my_public_key,
+        peer_public_key: None,
+        my_key_pair_id: [0u8; 8],
     };
 
     spdmlib::secret::asym_sign::register(SECRET_ASYM_IMPL_INSTANCE.clone());
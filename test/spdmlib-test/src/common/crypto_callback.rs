@@ -4,7 +4,7 @@
 
 use spdmlib::crypto::SpdmCertOperation;
 use spdmlib::crypto::SpdmCryptoRandom;
-use spdmlib::crypto::{SpdmAead, SpdmAsymVerify, SpdmHkdf, SpdmHmac};
+use spdmlib::crypto::{SpdmAead, SpdmAsymVerify, SpdmExtAsym, SpdmHkdf, SpdmHmac, SpdmSm2Verify};
 use spdmlib::error::{SpdmResult, SPDM_STATUS_VERIF_FAIL};
 use spdmlib::protocol::*;
 
@@ -24,6 +24,28 @@ pub static FAKE_RAND: SpdmCryptoRandom = SpdmCryptoRandom {
 
 pub static FAKE_ASYM_VERIFY: SpdmAsymVerify = SpdmAsymVerify {
     verify_cb: fake_asym_verify,
+    verify_with_public_key_cb: fake_asym_verify,
+    pqc_verify_cb: fake_pqc_asym_verify,
+};
+
+pub static FAKE_SM2_VERIFY: SpdmSm2Verify = SpdmSm2Verify {
+    verify_cb: fake_sm2_verify,
+    verify_with_public_key_cb: fake_sm2_verify,
+};
+
+pub static FAKE_EXT_ASYM: SpdmExtAsym = SpdmExtAsym {
+    get_signature_size_cb: fake_ext_asym_get_signature_size,
+    verify_cb: fake_ext_asym_verify,
+};
+
+// The IANA algorithms of the extended algorithm test cases.
+pub const FAKE_EXT_ASYM_ALGO: SpdmExtAlgStruct = SpdmExtAlgStruct {
+    registry_id: SpdmStandardId::SpdmStandardIdIANA,
+    algorithm_id: 0x1234,
+};
+pub const FAKE_EXT_ASYM_ALGO_OVERSIZED: SpdmExtAlgStruct = SpdmExtAlgStruct {
+    registry_id: SpdmStandardId::SpdmStandardIdIANA,
+    algorithm_id: 0x5678,
 };
 
 pub static FAKE_HKDF: SpdmHkdf = SpdmHkdf {
@@ -116,6 +138,53 @@ fn fake_asym_verify(
     Ok(())
 }
 
+fn fake_pqc_asym_verify(
+    _base_hash_algo: SpdmBaseHashAlgo,
+    _pqc_asym_algo: SpdmPqcAsymAlgo,
+    _public_cert_der: &[u8],
+    _data: &[u8],
+    _signature: &SpdmSignatureStruct,
+) -> SpdmResult {
+    Ok(())
+}
+
+fn fake_ext_asym_get_signature_size(ext_asym_algo: SpdmExtAlgStruct) -> u16 {
+    if ext_asym_algo == FAKE_EXT_ASYM_ALGO {
+        ECDSA_ECC_NIST_P384_KEY_SIZE as u16
+    } else if ext_asym_algo == FAKE_EXT_ASYM_ALGO_OVERSIZED {
+        SPDM_MAX_ASYM_KEY_SIZE as u16 + 1
+    } else {
+        0
+    }
+}
+
+fn fake_ext_asym_verify(
+    _base_hash_algo: SpdmBaseHashAlgo,
+    _ext_asym_algo: SpdmExtAlgStruct,
+    _public_cert_der: &[u8],
+    _data: &[u8],
+    _signature: &SpdmSignatureStruct,
+) -> SpdmResult {
+    Ok(())
+}
+
+// Accepts the fake SM2 signature of secret_callback::SECRET_SM2_IMPL_INSTANCE.
+fn fake_sm2_verify(
+    _base_hash_algo: SpdmBaseHashAlgo,
+    sm2_id: &[u8],
+    _public_key_der: &[u8],
+    _data: &[u8],
+    signature: &SpdmSignatureStruct,
+) -> SpdmResult {
+    let mut expected = [0u8; SM2_ECC_SM2_P256_KEY_SIZE];
+    expected[..sm2_id.len()].copy_from_slice(sm2_id);
+    if signature.as_ref() == expected {
+        Ok(())
+    } else {
+        Err(SPDM_STATUS_VERIF_FAIL)
+    }
+}
+
 fn fake_hkdf_extract(
     hash_algo: SpdmBaseHashAlgo,
     _salt: &[u8],
@@ -165,12 +234,36 @@ fn fake_get_cert_from_cert_chain(cert_chain: &[u8], _index: isize) -> SpdmResult
     return Ok((0, cert_chain.len()));
 }
 
-fn fake_verify_cert_chain(_cert_chain: &[u8]) -> SpdmResult {
+fn fake_verify_cert_chain(
+    _cert_chain: &[u8],
+    _is_requester_cert: bool,
+    _is_alias_cert_model: bool,
+) -> SpdmResult {
     Ok(())
 }
 
+// Registers the RustCrypto backend. It has to run before any fake callback is
+// registered, as the first registration is kept.
+#[cfg(feature = "rustcrypto")]
+pub fn register_rustcrypto() {
+    use spdmlib::crypto;
+    use spdmlib_crypto_rustcrypto::*;
+
+    crypto::hash::register(hash_impl::DEFAULT.clone());
+    crypto::hmac::register(hmac_impl::DEFAULT.clone());
+    crypto::aead::register(aead_impl::DEFAULT.clone());
+    crypto::asym_verify::register(asym_verify_impl::DEFAULT.clone());
+    crypto::dhe::register(dhe_impl::DEFAULT.clone());
+    crypto::cert_operation::register(cert_operation_impl::DEFAULT.clone());
+    crypto::hkdf::register(hkdf_impl::DEFAULT.clone());
+    crypto::rand::register(rand_impl::DEFAULT.clone());
+}
+
 #[test]
 // Make sure this is the first test case running by `cargo test`
 fn test_0_crypto_init() {
+    #[cfg(feature = "rustcrypto")]
+    register_rustcrypto();
+    #[cfg(not(feature = "rustcrypto"))]
     spdmlib::crypto::aead::register(FAKE_AEAD.clone());
 }
@@ -12,13 +12,26 @@ use spdmlib::crypto;
 use spdmlib::crypto::hash;
 use spdmlib::message::*;
 use spdmlib::protocol::*;
-use spdmlib::secret::{SpdmSecretAsymSign, SpdmSecretMeasurement, SpdmSecretPsk};
+use spdmlib::secret::{
+    SpdmSecretAsymSign, SpdmSecretCertProvision, SpdmSecretCsr, SpdmSecretEndpointInfo,
+    SpdmSecretKeyPair, SpdmSecretMeasurement, SpdmSecretMeasurementExtensionLog, SpdmSecretPsk,
+    SpdmSecretResult, SpdmSecretSm2Sign,
+};
+use std::cell::Cell;
 
 pub static SECRET_MEASUREMENT_IMPL_INSTANCE: SpdmSecretMeasurement = SpdmSecretMeasurement {
     measurement_collection_cb: measurement_collection_impl,
     generate_measurement_summary_hash_cb: generate_measurement_summary_hash_impl,
 };
 
+pub static SECRET_MEL_IMPL_INSTANCE: SpdmSecretMeasurementExtensionLog =
+    SpdmSecretMeasurementExtensionLog {
+        measurement_extension_log_cb: measurement_extension_log_impl,
+    };
+
+// Size of the test MEL, it takes three GET_MEASUREMENT_EXTENSION_LOG to read.
+pub const TEST_MEL_SIZE: usize = 1300;
+
 pub static SECRET_PSK_IMPL_INSTANCE: SpdmSecretPsk = SpdmSecretPsk {
     handshake_secret_hkdf_expand_cb: handshake_secret_hkdf_expand_impl,
     master_secret_hkdf_expand_cb: master_secret_hkdf_expand_impl,
@@ -27,12 +40,104 @@ pub static SECRET_PSK_IMPL_INSTANCE: SpdmSecretPsk = SpdmSecretPsk {
 pub static SECRET_ASYM_IMPL_INSTANCE: SpdmSecretAsymSign =
     SpdmSecretAsymSign { sign_cb: asym_sign };
 
-#[allow(clippy::field_reassign_with_default)]
+// Signs with the fake SM2 signature of crypto_callback::FAKE_SM2_VERIFY.
+pub static SECRET_SM2_IMPL_INSTANCE: SpdmSecretSm2Sign = SpdmSecretSm2Sign {
+    sign_cb: fake_sm2_sign,
+};
+
+pub static SECRET_CSR_IMPL_INSTANCE: SpdmSecretCsr = SpdmSecretCsr {
+    generate_csr_cb: generate_csr_impl,
+};
+
+pub static SECRET_CERT_PROVISION_IMPL_INSTANCE: SpdmSecretCertProvision = SpdmSecretCertProvision {
+    write_cert_chain_cb: write_cert_chain_impl,
+};
+
+pub static SECRET_KEY_PAIR_IMPL_INSTANCE: SpdmSecretKeyPair = SpdmSecretKeyPair {
+    get_key_pair_info_cb: get_key_pair_info_impl,
+    set_key_pair_info_cb: set_key_pair_info_impl,
+};
+
+// Number of key pairs held by the test device.
+pub const TEST_TOTAL_KEY_PAIRS: u8 = 2;
+
+pub static SECRET_ENDPOINT_INFO_IMPL_INSTANCE: SpdmSecretEndpointInfo = SpdmSecretEndpointInfo {
+    endpoint_info_cb: endpoint_info_impl,
+};
+
+// Device class identifier reported by the test device.
+pub const TEST_ENDPOINT_INFO: &[u8] = b"spdm-test-endpoint";
+
+thread_local! {
+    // Number of times the signing and measurement callbacks on this thread
+    // report the result not ready.
+    pub static RESPONSE_NOT_READY_COUNT: Cell<usize> = Cell::new(0);
+}
+
+pub const TEST_RDT_EXPONENT: u8 = 1;
+pub const TEST_RDTM: u8 = 2;
+
+fn is_response_ready() -> bool {
+    RESPONSE_NOT_READY_COUNT.with(|count| {
+        if count.get() == 0 {
+            true
+        } else {
+            count.set(count.get() - 1);
+            false
+        }
+    })
+}
+
 fn measurement_collection_impl(
     spdm_version: SpdmVersion,
     measurement_specification: SpdmMeasurementSpecification,
     measurement_hash_algo: SpdmMeasurementHashAlgo,
     measurement_index: usize,
+) -> SpdmSecretResult<SpdmMeasurementRecordStructure> {
+    if !is_response_ready() {
+        return SpdmSecretResult::NotReady {
+            rdt_exponent: TEST_RDT_EXPONENT,
+            rdtm: TEST_RDTM,
+        };
+    }
+    collect_measurement_record(
+        spdm_version,
+        measurement_specification,
+        measurement_hash_algo,
+        measurement_index,
+    )
+    .into()
+}
+
+fn generate_measurement_summary_hash_impl(
+    spdm_version: SpdmVersion,
+    base_hash_algo: SpdmBaseHashAlgo,
+    measurement_specification: SpdmMeasurementSpecification,
+    measurement_hash_algo: SpdmMeasurementHashAlgo,
+    measurement_summary_hash_type: SpdmMeasurementSummaryHashType,
+) -> SpdmSecretResult<SpdmDigestStruct> {
+    if !is_response_ready() {
+        return SpdmSecretResult::NotReady {
+            rdt_exponent: TEST_RDT_EXPONENT,
+            rdtm: TEST_RDTM,
+        };
+    }
+    get_measurement_summary_hash(
+        spdm_version,
+        base_hash_algo,
+        measurement_specification,
+        measurement_hash_algo,
+        measurement_summary_hash_type,
+    )
+    .into()
+}
+
+#[allow(clippy::field_reassign_with_default)]
+fn collect_measurement_record(
+    spdm_version: SpdmVersion,
+    measurement_specification: SpdmMeasurementSpecification,
+    measurement_hash_algo: SpdmMeasurementHashAlgo,
+    measurement_index: usize,
 ) -> Option<SpdmMeasurementRecordStructure> {
     if measurement_specification != SpdmMeasurementSpecification::DMTF {
         None
@@ -41,11 +146,12 @@ fn measurement_collection_impl(
             SpdmMeasurementHashAlgo::TPM_ALG_SHA_256 => SpdmBaseHashAlgo::TPM_ALG_SHA_256,
             SpdmMeasurementHashAlgo::TPM_ALG_SHA_384 => SpdmBaseHashAlgo::TPM_ALG_SHA_384,
             SpdmMeasurementHashAlgo::TPM_ALG_SHA_512 => SpdmBaseHashAlgo::TPM_ALG_SHA_512,
-            SpdmMeasurementHashAlgo::RAW_BIT_STREAM
-            | SpdmMeasurementHashAlgo::TPM_ALG_SHA3_256
-            | SpdmMeasurementHashAlgo::TPM_ALG_SHA3_384
-            | SpdmMeasurementHashAlgo::TPM_ALG_SHA3_512
-            | SpdmMeasurementHashAlgo::TPM_ALG_SM3 => return None,
+            SpdmMeasurementHashAlgo::TPM_ALG_SHA3_256 => SpdmBaseHashAlgo::TPM_ALG_SHA3_256,
+            SpdmMeasurementHashAlgo::TPM_ALG_SHA3_384 => SpdmBaseHashAlgo::TPM_ALG_SHA3_384,
+            SpdmMeasurementHashAlgo::TPM_ALG_SHA3_512 => SpdmBaseHashAlgo::TPM_ALG_SHA3_512,
+            SpdmMeasurementHashAlgo::RAW_BIT_STREAM | SpdmMeasurementHashAlgo::TPM_ALG_SM3 => {
+                return None
+            }
             _ => return None,
         };
         let hashsize = base_hash_algo.get_size();
@@ -183,7 +289,7 @@ fn measurement_collection_impl(
     }
 }
 
-fn generate_measurement_summary_hash_impl(
+fn get_measurement_summary_hash(
     spdm_version: SpdmVersion,
     base_hash_algo: SpdmBaseHashAlgo,
     measurement_specification: SpdmMeasurementSpecification,
@@ -213,6 +319,65 @@ const SALT_0: [u8; SPDM_MAX_HASH_SIZE] = [0u8; SPDM_MAX_HASH_SIZE];
 const ZERO_FILLED: [u8; SPDM_MAX_HASH_SIZE] = [0u8; SPDM_MAX_HASH_SIZE];
 const BIN_STR0_LABEL: &[u8] = b"derived";
 
+fn measurement_extension_log_impl(
+    _spdm_version: SpdmVersion,
+    _mel_specification: SpdmMelSpecification,
+    _measurement_hash_algo: SpdmMeasurementHashAlgo,
+) -> Option<SpdmMeasurementExtensionLog> {
+    let mut mel = SpdmMeasurementExtensionLog {
+        data_size: TEST_MEL_SIZE as u32,
+        ..Default::default()
+    };
+    for (i, d) in mel.data.iter_mut().take(TEST_MEL_SIZE).enumerate() {
+        *d = i as u8;
+    }
+    Some(mel)
+}
+
+fn get_key_pair_info_impl(key_pair_id: u8) -> Option<SpdmKeyPairInfo> {
+    if key_pair_id == SPDM_DEFAULT_KEY_PAIR_ID || key_pair_id > TEST_TOTAL_KEY_PAIRS {
+        return None;
+    }
+    Some(SpdmKeyPairInfo {
+        total_key_pairs: TEST_TOTAL_KEY_PAIRS,
+        capabilities: SpdmKeyPairCapabilities::CERT_ASSOC_CAP
+            | SpdmKeyPairCapabilities::KEY_USAGE_CAP
+            | SpdmKeyPairCapabilities::ERASABLE_CAP,
+        key_usage_capabilities: SpdmKeyUsageMask::KEY_EX_USE
+            | SpdmKeyUsageMask::CHALLENGE_USE
+            | SpdmKeyUsageMask::MEASUREMENT_USE,
+        current_key_usage: SpdmKeyUsageMask::CHALLENGE_USE | SpdmKeyUsageMask::MEASUREMENT_USE,
+        asym_algo_capabilities: SpdmKeyPairAsymAlgo::ECC384,
+        current_asym_algo: SpdmKeyPairAsymAlgo::ECC384,
+        ..Default::default()
+    })
+}
+
+fn set_key_pair_info_impl(
+    operation: SpdmSetKeyPairInfoOperation,
+    key_pair_id: u8,
+    desired_key_usage: SpdmKeyUsageMask,
+    desired_asym_algo: SpdmKeyPairAsymAlgo,
+    desired_assoc_cert_slot_mask: u8,
+) -> bool {
+    key_pair_id != SPDM_DEFAULT_KEY_PAIR_ID && key_pair_id <= TEST_TOTAL_KEY_PAIRS
+}
+
+fn endpoint_info_impl(
+    _spdm_version: SpdmVersion,
+    sub_code: SpdmEndpointInfoSubCode,
+) -> Option<SpdmEndpointInfo> {
+    if sub_code != SpdmEndpointInfoSubCode::SpdmEndpointInfoSubCodeDeviceClassIdentifier {
+        return None;
+    }
+    let mut endpoint_info = SpdmEndpointInfo {
+        data_size: TEST_ENDPOINT_INFO.len() as u32,
+        ..Default::default()
+    };
+    endpoint_info.data[..TEST_ENDPOINT_INFO.len()].copy_from_slice(TEST_ENDPOINT_INFO);
+    Some(endpoint_info)
+}
+
 fn handshake_secret_hkdf_expand_impl(
     spdm_version: SpdmVersion,
     base_hash_algo: SpdmBaseHashAlgo,
@@ -277,21 +442,51 @@ fn master_secret_hkdf_expand_impl(
 fn asym_sign(
     base_hash_algo: SpdmBaseHashAlgo,
     base_asym_algo: SpdmBaseAsymAlgo,
+    _key_pair_id: u8,
     data: &[u8],
-) -> Option<SpdmSignatureStruct> {
-    match (base_hash_algo, base_asym_algo) {
+) -> SpdmSecretResult<SpdmSignatureStruct> {
+    if !is_response_ready() {
+        return SpdmSecretResult::NotReady {
+            rdt_exponent: TEST_RDT_EXPONENT,
+            rdtm: TEST_RDTM,
+        };
+    }
+    let signature = match (base_hash_algo, base_asym_algo) {
+        #[cfg(feature = "spdm-ring")]
         (SpdmBaseHashAlgo::TPM_ALG_SHA_256, SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P256) => {
             sign_ecdsa_asym_algo(&ring::signature::ECDSA_P256_SHA256_FIXED_SIGNING, data)
         }
+        #[cfg(feature = "spdm-ring")]
         (SpdmBaseHashAlgo::TPM_ALG_SHA_384, SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384) => {
             sign_ecdsa_asym_algo(&ring::signature::ECDSA_P384_SHA384_FIXED_SIGNING, data)
         }
+        #[cfg(not(feature = "spdm-ring"))]
+        (SpdmBaseHashAlgo::TPM_ALG_SHA_384, SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384) => {
+            sign_ecdsa_p384(data)
+        }
         _ => {
             panic!();
         }
-    }
+    };
+    signature.into()
+}
+
+// The fake SM2 signature is the signing ID, zero padded.
+fn fake_sm2_sign(
+    _base_hash_algo: SpdmBaseHashAlgo,
+    _key_pair_id: u8,
+    sm2_id: &[u8],
+    _data: &[u8],
+) -> SpdmSecretResult<SpdmSignatureStruct> {
+    let mut signature = SpdmSignatureStruct {
+        data_size: SM2_ECC_SM2_P256_KEY_SIZE as u16,
+        ..Default::default()
+    };
+    signature.data[..sm2_id.len()].copy_from_slice(sm2_id);
+    SpdmSecretResult::Ready(signature)
 }
 
+#[cfg(feature = "spdm-ring")]
 fn sign_ecdsa_asym_algo(
     algorithm: &'static ring::signature::EcdsaSigningAlgorithm,
     data: &[u8],
@@ -317,3 +512,61 @@ fn sign_ecdsa_asym_algo(
         data: full_signature,
     })
 }
+
+// Signs with the ECDSA P-384 test key when ring is not available.
+#[cfg(not(feature = "spdm-ring"))]
+fn sign_ecdsa_p384(data: &[u8]) -> Option<SpdmSignatureStruct> {
+    use p384::ecdsa::signature::Signer;
+    use p384::pkcs8::DecodePrivateKey;
+
+    let crate_dir = get_test_key_directory();
+    let key_file_path = crate_dir.join("test_key/ecp384/end_responder.key.p8");
+    let der_file = std::fs::read(key_file_path).expect("unable to read key der!");
+
+    let signing_key = p384::ecdsa::SigningKey::from_pkcs8_der(&der_file).ok()?;
+    let signature: p384::ecdsa::Signature = signing_key.sign(data);
+    let signature = signature.to_bytes();
+
+    let mut full_signature: [u8; SPDM_MAX_ASYM_KEY_SIZE] = [0u8; SPDM_MAX_ASYM_KEY_SIZE];
+    full_signature[..signature.len()].copy_from_slice(&signature);
+
+    Some(SpdmSignatureStruct {
+        data_size: signature.len() as u16,
+        data: full_signature,
+    })
+}
+
+fn generate_csr_impl(
+    base_hash_algo: SpdmBaseHashAlgo,
+    base_asym_algo: SpdmBaseAsymAlgo,
+    requester_info: &[u8],
+    opaque_data: &[u8],
+    need_reset: &mut bool,
+) -> Option<SpdmCsrStruct> {
+    let csr_file = match base_asym_algo {
+        SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P256 => "test_key/test_csr/ecp256.csr",
+        SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384 => "test_key/test_csr/ecp384.csr",
+        _ => return None,
+    };
+    let crate_dir = get_test_key_directory();
+    let csr = std::fs::read(crate_dir.join(csr_file)).expect("unable to read csr!");
+
+    *need_reset = false;
+    let mut csr_struct = SpdmCsrStruct {
+        data_size: csr.len() as u16,
+        ..Default::default()
+    };
+    csr_struct.data[..csr.len()].copy_from_slice(&csr);
+    Some(csr_struct)
+}
+
+fn write_cert_chain_impl(
+    base_hash_algo: SpdmBaseHashAlgo,
+    base_asym_algo: SpdmBaseAsymAlgo,
+    slot_id: u8,
+    cert_chain: &[u8],
+    need_reset: &mut bool,
+) -> bool {
+    *need_reset = false;
+    true
+}
@@ -20,11 +20,15 @@ use spdmlib::protocol::*;
 use std::path::PathBuf;
 
 pub fn create_info() -> (SpdmConfigInfo, SpdmProvisionInfo) {
+    #[cfg(feature = "rustcrypto")]
+    crate::common::crypto_callback::register_rustcrypto();
+
     let config_info = SpdmConfigInfo {
-        spdm_version: [
+        spdm_version: &[
             SpdmVersion::SpdmVersion10,
             SpdmVersion::SpdmVersion11,
             SpdmVersion::SpdmVersion12,
+            SpdmVersion::SpdmVersion13,
         ],
         rsp_capabilities: SpdmResponseCapabilityFlags::CERT_CAP
             | SpdmResponseCapabilityFlags::CHAL_CAP
@@ -106,6 +110,9 @@ pub fn create_info() -> (SpdmConfigInfo, SpdmProvisionInfo) {
         ],
         my_cert_chain: [None, None, None, None, None, None, None, None],
         peer_root_cert_data: Some(peer_root_cert_data),
+        my_public_key: None,
+        peer_public_key: None,
+        my_key_pair_id: [0u8; 8],
     };
 
     (config_info, provision_info)
@@ -136,6 +143,9 @@ pub fn new_spdm_message(value: SpdmMessage, mut context: SpdmContext) -> SpdmMes
 }
 
 pub fn req_create_info() -> (SpdmConfigInfo, SpdmProvisionInfo) {
+    #[cfg(feature = "rustcrypto")]
+    crate::common::crypto_callback::register_rustcrypto();
+
     let req_capabilities = SpdmRequestCapabilityFlags::CERT_CAP
         | SpdmRequestCapabilityFlags::CHAL_CAP
         | SpdmRequestCapabilityFlags::ENCRYPT_CAP
@@ -153,10 +163,11 @@ pub fn req_create_info() -> (SpdmConfigInfo, SpdmProvisionInfo) {
         req_capabilities
     };
     let config_info = SpdmConfigInfo {
-        spdm_version: [
+        spdm_version: &[
             SpdmVersion::SpdmVersion10,
             SpdmVersion::SpdmVersion11,
             SpdmVersion::SpdmVersion12,
+            SpdmVersion::SpdmVersion13,
         ],
         req_capabilities: req_capabilities,
         req_ct_exponent: 0,
@@ -243,12 +254,18 @@ pub fn req_create_info() -> (SpdmConfigInfo, SpdmProvisionInfo) {
             ],
             my_cert_chain: [None, None, None, None, None, None, None, None],
             peer_root_cert_data: Some(peer_root_cert_data),
+            my_public_key: None,
+            peer_public_key: None,
+            my_key_pair_id: [0u8; 8],
         }
     } else {
         SpdmProvisionInfo {
             my_cert_chain_data: [None, None, None, None, None, None, None, None],
             my_cert_chain: [None, None, None, None, None, None, None, None],
             peer_root_cert_data: Some(peer_root_cert_data),
+            my_public_key: None,
+            peer_public_key: None,
+            my_key_pair_id: [0u8; 8],
         }
     };
 
@@ -256,6 +273,9 @@ pub fn req_create_info() -> (SpdmConfigInfo, SpdmProvisionInfo) {
 }
 
 pub fn rsp_create_info() -> (SpdmConfigInfo, SpdmProvisionInfo) {
+    #[cfg(feature = "rustcrypto")]
+    crate::common::crypto_callback::register_rustcrypto();
+
     let rsp_capabilities = SpdmResponseCapabilityFlags::CERT_CAP
         | SpdmResponseCapabilityFlags::CHAL_CAP
         | SpdmResponseCapabilityFlags::MEAS_CAP_SIG
@@ -275,10 +295,11 @@ pub fn rsp_create_info() -> (SpdmConfigInfo, SpdmProvisionInfo) {
         rsp_capabilities
     };
     let config_info = SpdmConfigInfo {
-        spdm_version: [
+        spdm_version: &[
             SpdmVersion::SpdmVersion10,
             SpdmVersion::SpdmVersion11,
             SpdmVersion::SpdmVersion12,
+            SpdmVersion::SpdmVersion13,
         ],
         rsp_capabilities: rsp_capabilities,
         rsp_ct_exponent: 0,
@@ -360,6 +381,9 @@ pub fn rsp_create_info() -> (SpdmConfigInfo, SpdmProvisionInfo) {
         ],
         my_cert_chain: [None, None, None, None, None, None, None, None],
         peer_root_cert_data: None,
+        my_public_key: None,
+        peer_public_key: None,
+        my_key_pair_id: [0u8; 8],
     };
 
     (config_info, provision_info)
@@ -0,0 +1,210 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use crate::common::device_io::{FakeSpdmDeviceIo, FakeSpdmDeviceIoReceve, SharedBuffer};
+use crate::common::secret_callback::*;
+use crate::common::transport::PciDoeTransportEncap;
+use crate::common::util::{create_info, get_rsp_cert_chain_buff};
+use spdmlib::common::{SpdmChunkStatus, SpdmConnectionState, SpdmContext, SpdmOpaqueSupport};
+use spdmlib::protocol::*;
+use spdmlib::requester::RequesterContext;
+use spdmlib::{responder, secret};
+
+const TEST_DATA_TRANSFER_SIZE: u32 = 64;
+
+fn setup_chunk_negotiate_info(context: &mut SpdmContext, chunk_cap: bool) {
+    context.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion12;
+    context.negotiate_info.base_hash_sel = SpdmBaseHashAlgo::TPM_ALG_SHA_384;
+    context.negotiate_info.aead_sel = SpdmAeadAlgo::AES_128_GCM;
+    context.negotiate_info.dhe_sel = SpdmDheAlgo::SECP_384_R1;
+    context.negotiate_info.base_asym_sel = SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384;
+    context.negotiate_info.opaque_data_support = SpdmOpaqueSupport::OPAQUE_DATA_FMT1;
+    if chunk_cap {
+        context.negotiate_info.req_capabilities_sel |= SpdmRequestCapabilityFlags::CHUNK_CAP;
+        context.negotiate_info.rsp_capabilities_sel |= SpdmResponseCapabilityFlags::CHUNK_CAP;
+    }
+    #[cfg(feature = "mut-auth")]
+    {
+        context.negotiate_info.rsp_capabilities_sel |= SpdmResponseCapabilityFlags::MUT_AUTH_CAP;
+        context.negotiate_info.req_capabilities_sel |= SpdmRequestCapabilityFlags::MUT_AUTH_CAP;
+    }
+
+    context.config_info.data_transfer_size = TEST_DATA_TRANSFER_SIZE;
+    context.negotiate_info.req_data_transfer_size_sel = TEST_DATA_TRANSFER_SIZE;
+    context.negotiate_info.rsp_data_transfer_size_sel = TEST_DATA_TRANSFER_SIZE;
+    context.negotiate_info.req_max_spdm_msg_size_sel = context.config_info.max_spdm_msg_size;
+    context.negotiate_info.rsp_max_spdm_msg_size_sel = context.config_info.max_spdm_msg_size;
+}
+
+#[test]
+fn test_case0_send_receive_spdm_key_exchange_in_chunks() {
+    let (rsp_config_info, rsp_provision_info) = create_info();
+    let (req_config_info, req_provision_info) = create_info();
+
+    let shared_buffer = SharedBuffer::new();
+    let mut device_io_responder = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+
+    secret::asym_sign::register(SECRET_ASYM_IMPL_INSTANCE.clone());
+
+    let mut responder = responder::ResponderContext::new(
+        &mut device_io_responder,
+        pcidoe_transport_encap,
+        rsp_config_info,
+        rsp_provision_info,
+    );
+
+    setup_chunk_negotiate_info(&mut responder.common, true);
+    responder.common.reset_runtime_info();
+    responder.common.provision_info.my_cert_chain = [
+        Some(get_rsp_cert_chain_buff()),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    ];
+    responder
+        .common
+        .runtime_info
+        .set_connection_state(SpdmConnectionState::SpdmConnectionNegotiated);
+
+    let pcidoe_transport_encap2 = &mut PciDoeTransportEncap {};
+    let mut device_io_requester = FakeSpdmDeviceIo::new(&shared_buffer, &mut responder);
+
+    let mut requester = RequesterContext::new(
+        &mut device_io_requester,
+        pcidoe_transport_encap2,
+        req_config_info,
+        req_provision_info,
+    );
+
+    setup_chunk_negotiate_info(&mut requester.common, true);
+    requester.common.reset_runtime_info();
+    requester.common.peer_info.peer_cert_chain[0] = Some(get_rsp_cert_chain_buff());
+
+    // Both KEY_EXCHANGE and KEY_EXCHANGE_RSP exceed the DataTransferSize,
+    // so the request goes via CHUNK_SEND and the response via CHUNK_GET.
+    let measurement_summary_hash_type =
+        SpdmMeasurementSummaryHashType::SpdmMeasurementSummaryHashTypeNone;
+    let status = requester
+        .send_receive_spdm_key_exchange(0, measurement_summary_hash_type)
+        .is_ok();
+    assert!(status);
+    assert_eq!(
+        requester.common.chunk_context.chunk_status,
+        SpdmChunkStatus::SpdmChunkIdle
+    );
+}
+
+#[test]
+fn test_case1_send_receive_spdm_key_exchange_in_chunks() {
+    let (rsp_config_info, rsp_provision_info) = create_info();
+    let (req_config_info, req_provision_info) = create_info();
+
+    let shared_buffer = SharedBuffer::new();
+    let mut device_io_responder = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+
+    secret::asym_sign::register(SECRET_ASYM_IMPL_INSTANCE.clone());
+
+    let mut responder = responder::ResponderContext::new(
+        &mut device_io_responder,
+        pcidoe_transport_encap,
+        rsp_config_info,
+        rsp_provision_info,
+    );
+
+    setup_chunk_negotiate_info(&mut responder.common, false);
+    responder.common.reset_runtime_info();
+    responder
+        .common
+        .runtime_info
+        .set_connection_state(SpdmConnectionState::SpdmConnectionNegotiated);
+
+    let pcidoe_transport_encap2 = &mut PciDoeTransportEncap {};
+    let mut device_io_requester = FakeSpdmDeviceIo::new(&shared_buffer, &mut responder);
+
+    let mut requester = RequesterContext::new(
+        &mut device_io_requester,
+        pcidoe_transport_encap2,
+        req_config_info,
+        req_provision_info,
+    );
+
+    setup_chunk_negotiate_info(&mut requester.common, false);
+    requester.common.reset_runtime_info();
+    requester.common.peer_info.peer_cert_chain[0] = Some(get_rsp_cert_chain_buff());
+
+    // Without CHUNK_CAP a request larger than the DataTransferSize can not be sent.
+    let measurement_summary_hash_type =
+        SpdmMeasurementSummaryHashType::SpdmMeasurementSummaryHashTypeNone;
+    let status = requester
+        .send_receive_spdm_key_exchange(0, measurement_summary_hash_type)
+        .is_ok();
+    assert!(!status);
+}
+
+#[test]
+#[cfg(feature = "hashed-transcript-data")]
+fn test_case0_send_receive_spdm_certificate_in_chunks() {
+    let (rsp_config_info, rsp_provision_info) = create_info();
+    let (req_config_info, req_provision_info) = create_info();
+
+    let shared_buffer = SharedBuffer::new();
+    let mut device_io_responder = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+
+    secret::asym_sign::register(SECRET_ASYM_IMPL_INSTANCE.clone());
+
+    let mut responder = responder::ResponderContext::new(
+        &mut device_io_responder,
+        pcidoe_transport_encap,
+        rsp_config_info,
+        rsp_provision_info,
+    );
+
+    setup_chunk_negotiate_info(&mut responder.common, true);
+    responder.common.reset_runtime_info();
+    responder.common.provision_info.my_cert_chain = [
+        Some(get_rsp_cert_chain_buff()),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    ];
+    responder
+        .common
+        .runtime_info
+        .set_connection_state(SpdmConnectionState::SpdmConnectionNegotiated);
+
+    let pcidoe_transport_encap2 = &mut PciDoeTransportEncap {};
+    let mut device_io_requester = FakeSpdmDeviceIo::new(&shared_buffer, &mut responder);
+
+    let mut requester = RequesterContext::new(
+        &mut device_io_requester,
+        pcidoe_transport_encap2,
+        req_config_info,
+        req_provision_info,
+    );
+
+    setup_chunk_negotiate_info(&mut requester.common, true);
+    requester.common.reset_runtime_info();
+
+    // Every CERTIFICATE response exceeds the DataTransferSize and is retrieved via CHUNK_GET.
+    let status = requester.send_receive_spdm_certificate(None, 0).is_ok();
+    assert!(status);
+    assert_eq!(
+        requester.common.peer_info.peer_cert_chain[0]
+            .as_ref()
+            .unwrap()
+            .data_size,
+        get_rsp_cert_chain_buff().data_size
+    );
+}
@@ -5,10 +5,11 @@
 use crate::common::device_io::{FakeSpdmDeviceIo, FakeSpdmDeviceIoReceve, SharedBuffer};
 use crate::common::secret_callback::*;
 use crate::common::transport::PciDoeTransportEncap;
-use crate::common::util::create_info;
-use codec::Writer;
+use crate::common::util::{create_info, get_test_key_directory};
+use codec::{Codec, Reader, Writer};
 use spdmlib::common::session::{SpdmSession, SpdmSessionState};
-use spdmlib::common::SpdmCodec;
+use spdmlib::common::{SpdmCachedNegotiateState, SpdmCodec, SpdmConnectionState};
+use spdmlib::error::SPDM_STATUS_INVALID_PARAMETER;
 use spdmlib::message::*;
 use spdmlib::protocol::*;
 use spdmlib::requester::RequesterContext;
@@ -80,6 +81,104 @@ fn test_case0_start_session() {
     assert!(result.is_ok());
 }
 
+#[test]
+fn test_case1_start_session_with_public_key() {
+    let (mut rsp_config_info, mut rsp_provision_info) = create_info();
+    let (mut req_config_info, mut req_provision_info) = create_info();
+
+    rsp_config_info
+        .rsp_capabilities
+        .remove(SpdmResponseCapabilityFlags::CERT_CAP);
+    rsp_config_info.rsp_capabilities |= SpdmResponseCapabilityFlags::PUB_KEY_ID_CAP;
+    req_config_info
+        .req_capabilities
+        .remove(SpdmRequestCapabilityFlags::CERT_CAP);
+    req_config_info.req_capabilities |= SpdmRequestCapabilityFlags::PUB_KEY_ID_CAP;
+    rsp_config_info.req_asym_algo = SpdmReqAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384;
+    req_config_info.req_asym_algo = SpdmReqAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384;
+
+    // The test signing callback uses the responder key on both sides.
+    let public_key_file_path =
+        get_test_key_directory().join("test_key/ecp384/end_responder.key.pub.der");
+    let public_key_der = std::fs::read(public_key_file_path).expect("unable to read public key!");
+    let mut public_key = SpdmPublicKeyData::default();
+    public_key.data_size = public_key_der.len() as u16;
+    public_key.data[..public_key_der.len()].copy_from_slice(&public_key_der);
+
+    rsp_provision_info.my_cert_chain_data = Default::default();
+    rsp_provision_info.my_public_key = Some(public_key.clone());
+    rsp_provision_info.peer_public_key = Some(public_key.clone());
+    req_provision_info.my_cert_chain_data = Default::default();
+    req_provision_info.my_public_key = Some(public_key.clone());
+    req_provision_info.peer_public_key = Some(public_key);
+
+    let shared_buffer = SharedBuffer::new();
+    let mut device_io_responder = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+
+    secret::asym_sign::register(SECRET_ASYM_IMPL_INSTANCE.clone());
+    secret::measurement::register(SECRET_MEASUREMENT_IMPL_INSTANCE.clone());
+    secret::psk::register(SECRET_PSK_IMPL_INSTANCE.clone());
+
+    let mut responder = responder::ResponderContext::new(
+        &mut device_io_responder,
+        pcidoe_transport_encap,
+        rsp_config_info,
+        rsp_provision_info,
+    );
+
+    let pcidoe_transport_encap2 = &mut PciDoeTransportEncap {};
+    let mut device_io_requester = FakeSpdmDeviceIo::new(&shared_buffer, &mut responder);
+
+    let mut requester = RequesterContext::new(
+        &mut device_io_requester,
+        pcidoe_transport_encap2,
+        req_config_info,
+        req_provision_info,
+    );
+
+    let status = requester.init_connection().is_ok();
+    assert!(status);
+
+    // No certificate chain is provisioned in slot 0.
+    let status = requester
+        .send_receive_spdm_challenge(
+            0,
+            SpdmMeasurementSummaryHashType::SpdmMeasurementSummaryHashTypeNone,
+        )
+        .is_err();
+    assert!(status);
+
+    let status = requester
+        .send_receive_spdm_challenge(
+            SPDM_PUBLIC_KEY_SLOT_ID,
+            SpdmMeasurementSummaryHashType::SpdmMeasurementSummaryHashTypeAll,
+        )
+        .is_ok();
+    assert!(status);
+
+    let mut total_number = 0u8;
+    let mut spdm_measurement_record_structure = SpdmMeasurementRecordStructure::default();
+    let status = requester
+        .send_receive_spdm_measurement(
+            None,
+            SPDM_PUBLIC_KEY_SLOT_ID,
+            SpdmMeasurementAttributes::SIGNATURE_REQUESTED,
+            SpdmMeasurementOperation::SpdmMeasurementRequestAll,
+            &mut total_number,
+            &mut spdm_measurement_record_structure,
+        )
+        .is_ok();
+    assert!(status);
+
+    let result = requester.start_session(
+        false,
+        SPDM_PUBLIC_KEY_SLOT_ID,
+        SpdmMeasurementSummaryHashType::SpdmMeasurementSummaryHashTypeAll,
+    );
+    assert!(result.is_ok());
+}
+
 #[test]
 fn test_case0_get_next_half_session() {
     let (rsp_config_info, rsp_provision_info) = create_info();
@@ -308,3 +407,204 @@ fn test_case0_receive_secured_message() {
         .is_ok();
     assert!(status);
 }
+
+// Run VCA between a requester and a responder supporting CACHE_CAP, and
+// return the negotiated state saved by each side.
+fn get_cached_negotiate_states() -> (SpdmCachedNegotiateState, SpdmCachedNegotiateState) {
+    let (mut rsp_config_info, rsp_provision_info) = create_info();
+    let (mut req_config_info, req_provision_info) = create_info();
+    rsp_config_info.rsp_capabilities |= SpdmResponseCapabilityFlags::CACHE_CAP;
+    req_config_info.rsp_capabilities |= SpdmResponseCapabilityFlags::CACHE_CAP;
+
+    let shared_buffer = SharedBuffer::new();
+    let mut device_io_responder = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+
+    let mut responder = responder::ResponderContext::new(
+        &mut device_io_responder,
+        pcidoe_transport_encap,
+        rsp_config_info,
+        rsp_provision_info,
+    );
+
+    let pcidoe_transport_encap2 = &mut PciDoeTransportEncap {};
+    let mut device_io_requester = FakeSpdmDeviceIo::new(&shared_buffer, &mut responder);
+
+    let mut requester = RequesterContext::new(
+        &mut device_io_requester,
+        pcidoe_transport_encap2,
+        req_config_info,
+        req_provision_info,
+    );
+
+    assert!(requester.init_connection().is_ok());
+    let req_cached_state = requester.common.get_cached_negotiate_state().unwrap();
+
+    let rsp_cached_state = device_io_requester
+        .responder
+        .common
+        .get_cached_negotiate_state()
+        .unwrap();
+    (req_cached_state, rsp_cached_state)
+}
+
+// The connection after the reset runs in its own stack frame, the contexts
+// being too large to hold two pairs at once.
+#[test]
+fn test_case0_init_connection_with_cache() {
+    secret::asym_sign::register(SECRET_ASYM_IMPL_INSTANCE.clone());
+
+    let (req_cached_state, rsp_cached_state) = get_cached_negotiate_states();
+
+    // The cached state is persisted across the reset.
+    let buffer = &mut [0u8; 1024];
+    let mut writer = Writer::init(buffer);
+    assert!(req_cached_state.encode(&mut writer).is_ok());
+    let mut reader = Reader::init(buffer);
+    let req_cached_state = SpdmCachedNegotiateState::read(&mut reader).unwrap();
+
+    init_connection_with_cache_after_reset(&req_cached_state, &rsp_cached_state);
+}
+
+fn init_connection_with_cache_after_reset(
+    req_cached_state: &SpdmCachedNegotiateState,
+    rsp_cached_state: &SpdmCachedNegotiateState,
+) {
+    let (mut rsp_config_info, rsp_provision_info) = create_info();
+    let (mut req_config_info, req_provision_info) = create_info();
+    rsp_config_info.rsp_capabilities |= SpdmResponseCapabilityFlags::CACHE_CAP;
+    req_config_info.rsp_capabilities |= SpdmResponseCapabilityFlags::CACHE_CAP;
+
+    let shared_buffer = SharedBuffer::new();
+    let mut device_io_responder = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+
+    let mut responder = responder::ResponderContext::new(
+        &mut device_io_responder,
+        pcidoe_transport_encap,
+        rsp_config_info,
+        rsp_provision_info,
+    );
+    assert!(responder
+        .init_connection_with_cache(rsp_cached_state)
+        .is_ok());
+
+    let pcidoe_transport_encap2 = &mut PciDoeTransportEncap {};
+    let mut device_io_requester = FakeSpdmDeviceIo::new(&shared_buffer, &mut responder);
+
+    let mut requester = RequesterContext::new(
+        &mut device_io_requester,
+        pcidoe_transport_encap2,
+        req_config_info,
+        req_provision_info,
+    );
+
+    assert!(requester
+        .init_connection_with_cache(Some(req_cached_state))
+        .is_ok());
+    assert_eq!(
+        requester.common.runtime_info.get_connection_state(),
+        SpdmConnectionState::SpdmConnectionNegotiated
+    );
+    assert_eq!(
+        requester.common.runtime_info.message_a.as_ref(),
+        rsp_cached_state.message_a.as_ref()
+    );
+
+    assert!(requester.send_receive_spdm_digest(None).is_ok());
+    assert!(requester.send_receive_spdm_certificate(None, 0).is_ok());
+}
+
+#[test]
+fn test_case1_init_connection_with_cache_resynch() {
+    secret::asym_sign::register(SECRET_ASYM_IMPL_INSTANCE.clone());
+
+    let (req_cached_state, _) = get_cached_negotiate_states();
+    init_connection_with_cache_resynch_after_reset(&req_cached_state);
+}
+
+fn init_connection_with_cache_resynch_after_reset(req_cached_state: &SpdmCachedNegotiateState) {
+    let (mut rsp_config_info, rsp_provision_info) = create_info();
+    let (mut req_config_info, req_provision_info) = create_info();
+    rsp_config_info.rsp_capabilities |= SpdmResponseCapabilityFlags::CACHE_CAP;
+    req_config_info.rsp_capabilities |= SpdmResponseCapabilityFlags::CACHE_CAP;
+
+    let shared_buffer = SharedBuffer::new();
+    let mut device_io_responder = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+
+    // The responder lost its cached state.
+    let mut responder = responder::ResponderContext::new(
+        &mut device_io_responder,
+        pcidoe_transport_encap,
+        rsp_config_info,
+        rsp_provision_info,
+    );
+
+    let pcidoe_transport_encap2 = &mut PciDoeTransportEncap {};
+    let mut device_io_requester = FakeSpdmDeviceIo::new(&shared_buffer, &mut responder);
+
+    let mut requester = RequesterContext::new(
+        &mut device_io_requester,
+        pcidoe_transport_encap2,
+        req_config_info,
+        req_provision_info,
+    );
+
+    assert!(requester
+        .init_connection_with_cache(Some(req_cached_state))
+        .is_ok());
+    assert_eq!(
+        requester.send_receive_spdm_digest(None),
+        Err(SPDM_STATUS_INVALID_PARAMETER)
+    );
+
+    assert!(requester.init_connection().is_ok());
+    assert!(requester.send_receive_spdm_digest(None).is_ok());
+}
+
+#[test]
+fn test_case2_init_connection_with_cache_mismatch() {
+    let (req_cached_state, _) = get_cached_negotiate_states();
+    init_connection_with_cache_mismatch_after_reset(&req_cached_state);
+}
+
+fn init_connection_with_cache_mismatch_after_reset(req_cached_state: &SpdmCachedNegotiateState) {
+    let (mut rsp_config_info, rsp_provision_info) = create_info();
+    let (mut req_config_info, req_provision_info) = create_info();
+    rsp_config_info.rsp_capabilities |= SpdmResponseCapabilityFlags::CACHE_CAP;
+    req_config_info.rsp_capabilities |= SpdmResponseCapabilityFlags::CACHE_CAP;
+    // The requester configuration changed across the reset.
+    req_config_info.base_hash_algo = SpdmBaseHashAlgo::TPM_ALG_SHA_256;
+    rsp_config_info.base_hash_algo = SpdmBaseHashAlgo::TPM_ALG_SHA_256;
+
+    let shared_buffer = SharedBuffer::new();
+    let mut device_io_responder = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+
+    let mut responder = responder::ResponderContext::new(
+        &mut device_io_responder,
+        pcidoe_transport_encap,
+        rsp_config_info,
+        rsp_provision_info,
+    );
+
+    let pcidoe_transport_encap2 = &mut PciDoeTransportEncap {};
+    let mut device_io_requester = FakeSpdmDeviceIo::new(&shared_buffer, &mut responder);
+
+    let mut requester = RequesterContext::new(
+        &mut device_io_requester,
+        pcidoe_transport_encap2,
+        req_config_info,
+        req_provision_info,
+    );
+
+    // Full negotiation instead.
+    assert!(requester
+        .init_connection_with_cache(Some(req_cached_state))
+        .is_ok());
+    assert_eq!(
+        requester.common.negotiate_info.base_hash_sel,
+        SpdmBaseHashAlgo::TPM_ALG_SHA_256
+    );
+}
@@ -0,0 +1,202 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use crate::common::crypto_callback::FAKE_RAND;
+#[cfg(not(feature = "hashed-transcript-data"))]
+use crate::common::device_io::FakeSpdmDeviceIo;
+use crate::common::device_io::{FakeSpdmDeviceIoReceve, SharedBuffer};
+use crate::common::secret_callback::SECRET_ASYM_IMPL_INSTANCE;
+use crate::common::transport::PciDoeTransportEncap;
+use crate::common::util::create_info;
+#[cfg(not(feature = "hashed-transcript-data"))]
+use crate::common::util::get_rsp_cert_chain_buff;
+use codec::{Codec, Reader, Writer};
+use spdmlib::common::SpdmCodec;
+#[cfg(not(feature = "hashed-transcript-data"))]
+use spdmlib::common::SpdmConnectionState;
+use spdmlib::config;
+use spdmlib::protocol::*;
+#[cfg(not(feature = "hashed-transcript-data"))]
+use spdmlib::responder;
+use spdmlib::{crypto, message::*, requester, secret};
+
+#[test]
+fn test_encap_handle_challenge() {
+    let (config_info, provision_info) = create_info();
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+    let shared_buffer = SharedBuffer::new();
+    let mut socket_io_transport = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+
+    secret::asym_sign::register(SECRET_ASYM_IMPL_INSTANCE.clone());
+    crypto::rand::register(FAKE_RAND.clone());
+
+    let mut context = requester::RequesterContext::new(
+        &mut socket_io_transport,
+        pcidoe_transport_encap,
+        config_info,
+        provision_info,
+    );
+    context.common.reset_runtime_info();
+    context.common.negotiate_info.base_hash_sel = SpdmBaseHashAlgo::TPM_ALG_SHA_384;
+    context.common.negotiate_info.base_asym_sel = SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384;
+    assert!(context.common.construct_my_cert_chain().is_ok());
+    context.common.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion12;
+    context.common.negotiate_info.req_capabilities_sel |= SpdmRequestCapabilityFlags::CHAL_CAP;
+
+    let encap_request = &mut [0u8; 1024];
+    let mut writer = Writer::init(encap_request);
+    let challenge = SpdmMessage {
+        header: SpdmMessageHeader {
+            version: SpdmVersion::SpdmVersion12,
+            request_response_code: SpdmRequestResponseCode::SpdmRequestChallenge,
+        },
+        payload: SpdmMessagePayload::SpdmChallengeRequest(SpdmChallengeRequestPayload {
+            slot_id: 0,
+            measurement_summary_hash_type:
+                SpdmMeasurementSummaryHashType::SpdmMeasurementSummaryHashTypeNone,
+            nonce: SpdmNonceStruct {
+                data: [100u8; SPDM_NONCE_SIZE],
+            },
+        }),
+    };
+    assert!(challenge
+        .spdm_encode(&mut context.common, &mut writer)
+        .is_ok());
+
+    let encap_response = &mut [0u8; config::MAX_SPDM_MSG_SIZE];
+    let mut writer = Writer::init(encap_response);
+
+    context.encap_handle_challenge(encap_request, &mut writer);
+    let mut reader = Reader::init(encap_response);
+    let header = SpdmMessageHeader::read(&mut reader).unwrap();
+    assert_eq!(header.version, SpdmVersion::SpdmVersion12);
+    assert_eq!(
+        header.request_response_code,
+        SpdmRequestResponseCode::SpdmResponseChallengeAuth
+    );
+
+    context.common.runtime_info.need_measurement_summary_hash = false;
+    let challenge_auth =
+        SpdmChallengeAuthResponsePayload::spdm_read(&mut context.common, &mut reader).unwrap();
+    assert_eq!(challenge_auth.slot_id, 0);
+    assert_eq!(challenge_auth.slot_mask, 1);
+    assert_eq!(
+        challenge_auth.signature.data_size,
+        SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384.get_size()
+    );
+}
+
+#[test]
+fn test_encap_handle_challenge_unsupported() {
+    let (config_info, provision_info) = create_info();
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+    let shared_buffer = SharedBuffer::new();
+    let mut socket_io_transport = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+
+    let mut context = requester::RequesterContext::new(
+        &mut socket_io_transport,
+        pcidoe_transport_encap,
+        config_info,
+        provision_info,
+    );
+    context.common.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion12;
+    context.common.negotiate_info.req_capabilities_sel -= SpdmRequestCapabilityFlags::CHAL_CAP;
+
+    let encap_request = &mut [0u8; 1024];
+    let mut writer = Writer::init(encap_request);
+    let challenge = SpdmMessage {
+        header: SpdmMessageHeader {
+            version: SpdmVersion::SpdmVersion12,
+            request_response_code: SpdmRequestResponseCode::SpdmRequestChallenge,
+        },
+        payload: SpdmMessagePayload::SpdmChallengeRequest(SpdmChallengeRequestPayload {
+            slot_id: 0,
+            measurement_summary_hash_type:
+                SpdmMeasurementSummaryHashType::SpdmMeasurementSummaryHashTypeNone,
+            nonce: SpdmNonceStruct {
+                data: [100u8; SPDM_NONCE_SIZE],
+            },
+        }),
+    };
+    assert!(challenge
+        .spdm_encode(&mut context.common, &mut writer)
+        .is_ok());
+
+    let encap_response = &mut [0u8; config::MAX_SPDM_MSG_SIZE];
+    let mut writer = Writer::init(encap_response);
+
+    context.encap_handle_challenge(encap_request, &mut writer);
+    let mut reader = Reader::init(encap_response);
+    let header = SpdmMessageHeader::read(&mut reader).unwrap();
+    assert_eq!(
+        header.request_response_code,
+        SpdmRequestResponseCode::SpdmResponseError
+    );
+}
+
+#[test]
+#[cfg(not(feature = "hashed-transcript-data"))]
+fn test_basic_mutual_authenticate() {
+    let (rsp_config_info, rsp_provision_info) = create_info();
+    let (mut req_config_info, req_provision_info) = create_info();
+    req_config_info.req_capabilities |= SpdmRequestCapabilityFlags::CHAL_CAP;
+
+    let shared_buffer = SharedBuffer::new();
+    let mut device_io_responder = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+
+    secret::asym_sign::register(SECRET_ASYM_IMPL_INSTANCE.clone());
+    crypto::rand::register(FAKE_RAND.clone());
+
+    let mut responder = responder::ResponderContext::new(
+        &mut device_io_responder,
+        pcidoe_transport_encap,
+        rsp_config_info,
+        rsp_provision_info,
+    );
+
+    responder.common.reset_runtime_info();
+    responder.common.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion11;
+    responder.common.negotiate_info.base_hash_sel = SpdmBaseHashAlgo::TPM_ALG_SHA_384;
+    responder.common.negotiate_info.base_asym_sel = SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384;
+    assert!(responder.common.construct_my_cert_chain().is_ok());
+    responder.common.negotiate_info.req_capabilities_sel =
+        responder.common.config_info.req_capabilities | SpdmRequestCapabilityFlags::CHAL_CAP;
+    responder.common.negotiate_info.rsp_capabilities_sel =
+        responder.common.config_info.rsp_capabilities;
+    responder.common.encap_context.basic_mut_auth_requested = true;
+    responder
+        .common
+        .runtime_info
+        .set_connection_state(SpdmConnectionState::SpdmConnectionNegotiated);
+
+    let pcidoe_transport_encap2 = &mut PciDoeTransportEncap {};
+    let mut device_io_requester = FakeSpdmDeviceIo::new(&shared_buffer, &mut responder);
+
+    let mut requester = requester::RequesterContext::new(
+        &mut device_io_requester,
+        pcidoe_transport_encap2,
+        req_config_info,
+        req_provision_info,
+    );
+    requester.common.reset_runtime_info();
+    requester.common.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion11;
+    requester.common.negotiate_info.base_hash_sel = SpdmBaseHashAlgo::TPM_ALG_SHA_384;
+    requester.common.negotiate_info.base_asym_sel = SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384;
+    requester.common.negotiate_info.req_capabilities_sel =
+        requester.common.config_info.req_capabilities;
+    requester.common.negotiate_info.rsp_capabilities_sel =
+        requester.common.config_info.rsp_capabilities;
+    requester.common.peer_info.peer_cert_chain[0] = Some(get_rsp_cert_chain_buff());
+
+    // CHALLENGE_AUTH of SPDM 1.1 asks for basic mutual authentication, which
+    // runs the encapsulated GET_DIGESTS, GET_CERTIFICATE and CHALLENGE flow.
+    assert!(requester
+        .send_receive_spdm_challenge(
+            0,
+            SpdmMeasurementSummaryHashType::SpdmMeasurementSummaryHashTypeNone,
+        )
+        .is_ok());
+}
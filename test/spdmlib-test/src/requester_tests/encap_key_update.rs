@@ -0,0 +1,128 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use crate::common::device_io::{FakeSpdmDeviceIo, FakeSpdmDeviceIoReceve, SharedBuffer};
+use crate::common::secret_callback::*;
+use crate::common::transport::PciDoeTransportEncap;
+use crate::common::util::create_info;
+use spdmlib::common::session::{SpdmSession, SpdmSessionState};
+use spdmlib::common::{SpdmConnectionState, SpdmContext};
+use spdmlib::message::key_update::SpdmKeyUpdateOperation;
+use spdmlib::protocol::*;
+use spdmlib::requester::RequesterContext;
+use spdmlib::{responder, secret};
+
+const SESSION_ID: u32 = 0xffu32 << 16 | 0xFFFE;
+
+fn setup_session(common: &mut SpdmContext) {
+    common.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion12;
+    common.negotiate_info.base_hash_sel = SpdmBaseHashAlgo::TPM_ALG_SHA_384;
+    common.negotiate_info.req_capabilities_sel = common.config_info.req_capabilities;
+    common.negotiate_info.rsp_capabilities_sel = common.config_info.rsp_capabilities;
+    common
+        .runtime_info
+        .set_connection_state(SpdmConnectionState::SpdmConnectionAuthenticated);
+
+    common.session = gen_array_clone(SpdmSession::new(), 4);
+    common.session[0].setup(SESSION_ID).unwrap();
+    common.session[0].set_crypto_param(
+        SpdmBaseHashAlgo::TPM_ALG_SHA_384,
+        SpdmDheAlgo::SECP_384_R1,
+        SpdmAeadAlgo::AES_256_GCM,
+        SpdmKeyScheduleAlgo::SPDM_KEY_SCHEDULE,
+    );
+    common.session[0].set_session_state(SpdmSessionState::SpdmSessionEstablished);
+    let dhe_secret = SpdmDheFinalKeyStruct {
+        data_size: 48,
+        data: Box::new([0; SPDM_MAX_DHE_KEY_SIZE]),
+    };
+    let _ = common.session[0].set_dhe_secret(SpdmVersion::SpdmVersion12, dhe_secret);
+    let _ = common.session[0].generate_handshake_secret(
+        SpdmVersion::SpdmVersion12,
+        &SpdmDigestStruct {
+            data_size: 48,
+            data: Box::new([0; SPDM_MAX_HASH_SIZE]),
+        },
+    );
+    let _ = common.session[0].generate_data_secret(
+        SpdmVersion::SpdmVersion12,
+        &SpdmDigestStruct {
+            data_size: 48,
+            data: Box::new([0; SPDM_MAX_HASH_SIZE]),
+        },
+    );
+}
+
+fn run_encap_key_update(key_update_operation: SpdmKeyUpdateOperation) {
+    let (rsp_config_info, rsp_provision_info) = create_info();
+    let (req_config_info, req_provision_info) = create_info();
+
+    let shared_buffer = SharedBuffer::new();
+    let mut device_io_responder = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+
+    secret::asym_sign::register(SECRET_ASYM_IMPL_INSTANCE.clone());
+
+    let mut responder = responder::ResponderContext::new(
+        &mut device_io_responder,
+        pcidoe_transport_encap,
+        rsp_config_info,
+        rsp_provision_info,
+    );
+    setup_session(&mut responder.common);
+    assert!(responder
+        .init_encap_key_update(key_update_operation)
+        .is_ok());
+
+    let pcidoe_transport_encap2 = &mut PciDoeTransportEncap {};
+    let mut device_io_requester = FakeSpdmDeviceIo::new(&shared_buffer, &mut responder);
+
+    let mut requester = RequesterContext::new(
+        &mut device_io_requester,
+        pcidoe_transport_encap2,
+        req_config_info,
+        req_provision_info,
+    );
+    setup_session(&mut requester.common);
+
+    assert!(requester
+        .send_receive_encapsulated_request(SESSION_ID)
+        .is_ok());
+
+    // Both sides must agree on the new keys.
+    assert!(requester
+        .send_receive_spdm_key_update(SESSION_ID, SpdmKeyUpdateOperation::SpdmUpdateAllKeys)
+        .is_ok());
+}
+
+#[test]
+fn test_case0_encap_key_update_single_key() {
+    run_encap_key_update(SpdmKeyUpdateOperation::SpdmUpdateSingleKey);
+}
+
+#[test]
+fn test_case1_encap_key_update_all_keys() {
+    run_encap_key_update(SpdmKeyUpdateOperation::SpdmUpdateAllKeys);
+}
+
+#[test]
+fn test_case2_init_encap_key_update_invalid_operation() {
+    let (rsp_config_info, rsp_provision_info) = create_info();
+    let shared_buffer = SharedBuffer::new();
+    let mut device_io_responder = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+
+    let mut responder = responder::ResponderContext::new(
+        &mut device_io_responder,
+        pcidoe_transport_encap,
+        rsp_config_info,
+        rsp_provision_info,
+    );
+    setup_session(&mut responder.common);
+
+    assert!(responder
+        .init_encap_key_update(SpdmKeyUpdateOperation::SpdmVerifyNewKey)
+        .is_err());
+    assert!(responder.common.encap_context.key_update_op.is_none());
+}
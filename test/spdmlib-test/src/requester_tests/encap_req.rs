@@ -35,7 +35,9 @@ fn test_send_get_encapsulated_request() {
         provision_info,
     );
 
-    assert!(context.send_get_encapsulated_request(SESSION_ID).is_ok());
+    assert!(context
+        .send_get_encapsulated_request(Some(SESSION_ID))
+        .is_ok());
 
     // Get data sent by requester and decode the secured message
     let receive = &mut [0u8; config::MAX_SPDM_MSG_SIZE];
@@ -101,7 +103,9 @@ fn test_receive_encapsulated_request() {
         .unwrap();
     assert!(context.common.device_io.send(&send[..size]).is_ok());
 
-    assert!(context.receive_encapsulated_request(SESSION_ID).is_ok());
+    assert!(context
+        .receive_encapsulated_request(Some(SESSION_ID))
+        .is_ok());
 
     // Get data sent by requester and decode the secured message
     let receive = &mut [0u8; config::RECEIVER_BUFFER_SIZE];
@@ -189,7 +193,7 @@ fn test_receive_encapsulated_response_ack() {
     assert!(context.common.device_io.send(&send[..size]).is_ok());
 
     assert!(context
-        .receive_encapsulated_response_ack(SESSION_ID)
+        .receive_encapsulated_response_ack(Some(SESSION_ID))
         .is_ok());
 
     // Get data sent by requester and decode the secured message
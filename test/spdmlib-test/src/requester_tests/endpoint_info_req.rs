@@ -0,0 +1,150 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use crate::common::device_io::{FakeSpdmDeviceIo, FakeSpdmDeviceIoReceve, SharedBuffer};
+use crate::common::secret_callback::*;
+use crate::common::transport::PciDoeTransportEncap;
+use crate::common::util::{create_info, get_rsp_cert_chain_buff};
+use spdmlib::common::{SpdmConnectionState, SpdmContext};
+use spdmlib::error::SPDM_STATUS_UNSUPPORTED_CAP;
+use spdmlib::message::SpdmEndpointInfoRequestAttributes;
+use spdmlib::protocol::*;
+use spdmlib::requester::RequesterContext;
+use spdmlib::{config, responder, secret};
+
+fn setup_endpoint_info_negotiate_info(context: &mut SpdmContext) {
+    context.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion13;
+    context.negotiate_info.rsp_capabilities_sel |=
+        SpdmResponseCapabilityFlags::CERT_CAP | SpdmResponseCapabilityFlags::EP_INFO_CAP_SIG;
+    context.negotiate_info.base_hash_sel = SpdmBaseHashAlgo::TPM_ALG_SHA_384;
+    context.negotiate_info.base_asym_sel = SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384;
+}
+
+#[test]
+fn test_case0_send_receive_spdm_endpoint_info() {
+    let (rsp_config_info, mut rsp_provision_info) = create_info();
+    let (req_config_info, req_provision_info) = create_info();
+    rsp_provision_info.my_cert_chain[0] = Some(SpdmCertChainBuffer {
+        data_size: 512u16,
+        data: [0u8; 4 + SPDM_MAX_HASH_SIZE + config::MAX_SPDM_CERT_CHAIN_DATA_SIZE],
+    });
+
+    let shared_buffer = SharedBuffer::new();
+    let mut device_io_responder = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+
+    secret::asym_sign::register(SECRET_ASYM_IMPL_INSTANCE.clone());
+    secret::endpoint_info::register(SECRET_ENDPOINT_INFO_IMPL_INSTANCE.clone());
+
+    let mut responder = responder::ResponderContext::new(
+        &mut device_io_responder,
+        pcidoe_transport_encap,
+        rsp_config_info,
+        rsp_provision_info,
+    );
+
+    setup_endpoint_info_negotiate_info(&mut responder.common);
+    responder.common.reset_runtime_info();
+    responder
+        .common
+        .runtime_info
+        .set_connection_state(SpdmConnectionState::SpdmConnectionNegotiated);
+
+    let pcidoe_transport_encap2 = &mut PciDoeTransportEncap {};
+    let mut device_io_requester = FakeSpdmDeviceIo::new(&shared_buffer, &mut responder);
+
+    let mut requester = RequesterContext::new(
+        &mut device_io_requester,
+        pcidoe_transport_encap2,
+        req_config_info,
+        req_provision_info,
+    );
+
+    setup_endpoint_info_negotiate_info(&mut requester.common);
+    requester.common.peer_info.peer_cert_chain[0] = Some(get_rsp_cert_chain_buff());
+    requester.common.reset_runtime_info();
+
+    let endpoint_info = requester
+        .send_receive_spdm_endpoint_info(
+            None,
+            SpdmEndpointInfoSubCode::SpdmEndpointInfoSubCodeDeviceClassIdentifier,
+            SpdmEndpointInfoRequestAttributes::empty(),
+            0,
+        )
+        .unwrap();
+    assert_eq!(endpoint_info.as_ref(), TEST_ENDPOINT_INFO);
+
+    // The signature covers the unsigned exchange above as well.
+    let endpoint_info = requester
+        .send_receive_spdm_endpoint_info(
+            None,
+            SpdmEndpointInfoSubCode::SpdmEndpointInfoSubCodeDeviceClassIdentifier,
+            SpdmEndpointInfoRequestAttributes::SIGNATURE_REQUESTED,
+            0,
+        )
+        .unwrap();
+    assert_eq!(endpoint_info.as_ref(), TEST_ENDPOINT_INFO);
+}
+
+#[test]
+fn test_case1_send_receive_spdm_endpoint_info() {
+    let (rsp_config_info, rsp_provision_info) = create_info();
+    let (req_config_info, req_provision_info) = create_info();
+
+    let shared_buffer = SharedBuffer::new();
+    let mut device_io_responder = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+
+    secret::endpoint_info::register(SECRET_ENDPOINT_INFO_IMPL_INSTANCE.clone());
+
+    let mut responder = responder::ResponderContext::new(
+        &mut device_io_responder,
+        pcidoe_transport_encap,
+        rsp_config_info,
+        rsp_provision_info,
+    );
+    responder
+        .common
+        .runtime_info
+        .set_connection_state(SpdmConnectionState::SpdmConnectionNegotiated);
+
+    let pcidoe_transport_encap2 = &mut PciDoeTransportEncap {};
+    let mut device_io_requester = FakeSpdmDeviceIo::new(&shared_buffer, &mut responder);
+
+    let mut requester = RequesterContext::new(
+        &mut device_io_requester,
+        pcidoe_transport_encap2,
+        req_config_info,
+        req_provision_info,
+    );
+
+    // Only the unsigned form is supported by the responder.
+    requester.common.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion13;
+    requester.common.negotiate_info.rsp_capabilities_sel |=
+        SpdmResponseCapabilityFlags::EP_INFO_CAP_NO_SIG;
+    assert_eq!(
+        requester
+            .send_receive_spdm_endpoint_info(
+                None,
+                SpdmEndpointInfoSubCode::SpdmEndpointInfoSubCodeDeviceClassIdentifier,
+                SpdmEndpointInfoRequestAttributes::SIGNATURE_REQUESTED,
+                0,
+            )
+            .err(),
+        Some(SPDM_STATUS_UNSUPPORTED_CAP)
+    );
+
+    requester.common.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion12;
+    assert_eq!(
+        requester
+            .send_receive_spdm_endpoint_info(
+                None,
+                SpdmEndpointInfoSubCode::SpdmEndpointInfoSubCodeDeviceClassIdentifier,
+                SpdmEndpointInfoRequestAttributes::empty(),
+                0,
+            )
+            .err(),
+        Some(SPDM_STATUS_UNSUPPORTED_CAP)
+    );
+}
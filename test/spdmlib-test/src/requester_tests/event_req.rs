@@ -0,0 +1,185 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use crate::common::device_io::{FakeSpdmDeviceIo, FakeSpdmDeviceIoReceve, SharedBuffer};
+use crate::common::secret_callback::*;
+use crate::common::transport::PciDoeTransportEncap;
+use crate::common::util::create_info;
+use core::sync::atomic::{AtomicU16, Ordering};
+use spdmlib::common::session::{SpdmSession, SpdmSessionState};
+use spdmlib::error::SpdmResult;
+use spdmlib::message::*;
+use spdmlib::protocol::*;
+use spdmlib::requester::{event_handler, RequesterContext};
+use spdmlib::{responder, secret};
+
+fn setup_established_session(session: &mut SpdmSession, session_id: u32) {
+    session.setup(session_id).unwrap();
+    session.set_crypto_param(
+        SpdmBaseHashAlgo::TPM_ALG_SHA_384,
+        SpdmDheAlgo::SECP_384_R1,
+        SpdmAeadAlgo::AES_256_GCM,
+        SpdmKeyScheduleAlgo::SPDM_KEY_SCHEDULE,
+    );
+    assert!(session
+        .set_dhe_secret(
+            SpdmVersion::SpdmVersion13,
+            SpdmDheFinalKeyStruct {
+                data_size: 5,
+                data: Box::new([100u8; SPDM_MAX_DHE_KEY_SIZE])
+            }
+        )
+        .is_ok());
+    assert!(session
+        .generate_handshake_secret(
+            SpdmVersion::SpdmVersion13,
+            &SpdmDigestStruct {
+                data_size: 5,
+                data: Box::new([100u8; SPDM_MAX_HASH_SIZE])
+            }
+        )
+        .is_ok());
+    assert!(session
+        .generate_data_secret(
+            SpdmVersion::SpdmVersion13,
+            &SpdmDigestStruct {
+                data_size: 5,
+                data: Box::new([100u8; SPDM_MAX_HASH_SIZE])
+            }
+        )
+        .is_ok());
+    session.set_session_state(SpdmSessionState::SpdmSessionEstablished);
+}
+
+#[test]
+fn test_case0_send_receive_spdm_subscribe_event_types() {
+    let (rsp_config_info, rsp_provision_info) = create_info();
+    let (req_config_info, req_provision_info) = create_info();
+
+    let shared_buffer = SharedBuffer::new();
+    let mut device_io_responder = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+
+    secret::asym_sign::register(SECRET_ASYM_IMPL_INSTANCE.clone());
+
+    let mut responder = responder::ResponderContext::new(
+        &mut device_io_responder,
+        pcidoe_transport_encap,
+        rsp_config_info,
+        rsp_provision_info,
+    );
+
+    let session_id = (0x11u32 << 16) + 0x11;
+    responder.common.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion13;
+    responder.common.negotiate_info.rsp_capabilities_sel = SpdmResponseCapabilityFlags::EVENT_CAP;
+    responder.common.negotiate_info.base_hash_sel = SpdmBaseHashAlgo::TPM_ALG_SHA_384;
+    responder.common.session = gen_array_clone(SpdmSession::new(), 4);
+    setup_established_session(&mut responder.common.session[0], session_id);
+
+    let pcidoe_transport_encap2 = &mut PciDoeTransportEncap {};
+    let mut device_io_requester = FakeSpdmDeviceIo::new(&shared_buffer, &mut responder);
+
+    let mut requester = RequesterContext::new(
+        &mut device_io_requester,
+        pcidoe_transport_encap2,
+        req_config_info,
+        req_provision_info,
+    );
+
+    requester.common.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion13;
+    requester.common.negotiate_info.rsp_capabilities_sel = SpdmResponseCapabilityFlags::EVENT_CAP;
+    requester.common.negotiate_info.base_hash_sel = SpdmBaseHashAlgo::TPM_ALG_SHA_384;
+    requester.common.session = gen_array_clone(SpdmSession::new(), 4);
+    setup_established_session(&mut requester.common.session[0], session_id);
+
+    let supported = requester
+        .send_receive_spdm_get_supported_event_types(session_id)
+        .unwrap();
+    assert_eq!(supported.event_group_count, 1);
+    let dmtf_group = supported.event_groups[0];
+    assert_eq!(dmtf_group.group_id, SPDM_DMTF_EVENT_GROUP_ID);
+    assert!(dmtf_group.contains_event_type(SpdmDmtfEventType::MeasurementChanged.get_u16()));
+
+    assert!(requester
+        .send_receive_spdm_subscribe_event_types(session_id, &[dmtf_group])
+        .is_ok());
+    assert!(requester
+        .send_receive_spdm_subscribe_event_types(session_id, &[])
+        .is_ok());
+
+    // Unknown event types are rejected.
+    let mut unknown_group = dmtf_group;
+    unknown_group.event_types[0] = 0xFFFF;
+    assert!(requester
+        .send_receive_spdm_subscribe_event_types(session_id, &[unknown_group])
+        .is_err());
+}
+
+static RECEIVED_EVENT_TYPE: AtomicU16 = AtomicU16::new(0);
+
+#[test]
+fn test_case1_receive_spdm_event() {
+    let (rsp_config_info, rsp_provision_info) = create_info();
+    let (req_config_info, req_provision_info) = create_info();
+
+    let shared_buffer = SharedBuffer::new();
+    let mut device_io_responder = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+
+    secret::asym_sign::register(SECRET_ASYM_IMPL_INSTANCE.clone());
+    event_handler::register(event_handler::SpdmEventHandler {
+        handle_event_cb: |_session_id: u32, event: &SpdmEventData| -> SpdmResult {
+            RECEIVED_EVENT_TYPE.store(event.event_type_id, Ordering::SeqCst);
+            Ok(())
+        },
+    });
+
+    let mut responder = responder::ResponderContext::new(
+        &mut device_io_responder,
+        pcidoe_transport_encap,
+        rsp_config_info,
+        rsp_provision_info,
+    );
+
+    let session_id = (0x11u32 << 16) + 0x11;
+    responder.common.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion13;
+    responder.common.negotiate_info.rsp_capabilities_sel = SpdmResponseCapabilityFlags::EVENT_CAP;
+    responder.common.negotiate_info.base_hash_sel = SpdmBaseHashAlgo::TPM_ALG_SHA_384;
+    responder.common.session = gen_array_clone(SpdmSession::new(), 4);
+    setup_established_session(&mut responder.common.session[0], session_id);
+    let event_info = &mut responder.common.session[0].event_info;
+    event_info.subscribed_group_count = 1;
+    event_info.subscribed_groups[0] = SpdmEventGroup::new_dmtf();
+
+    assert!(responder
+        .queue_spdm_event(
+            &SPDM_DMTF_EVENT_GROUP_ID,
+            SpdmDmtfEventType::CertificateChanged.get_u16(),
+            &[0]
+        )
+        .is_ok());
+    assert!(responder.send_spdm_event(session_id).is_ok());
+
+    let pcidoe_transport_encap2 = &mut PciDoeTransportEncap {};
+    let mut device_io_requester = FakeSpdmDeviceIo::new(&shared_buffer, &mut responder);
+
+    let mut requester = RequesterContext::new(
+        &mut device_io_requester,
+        pcidoe_transport_encap2,
+        req_config_info,
+        req_provision_info,
+    );
+
+    requester.common.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion13;
+    requester.common.negotiate_info.rsp_capabilities_sel = SpdmResponseCapabilityFlags::EVENT_CAP;
+    requester.common.negotiate_info.base_hash_sel = SpdmBaseHashAlgo::TPM_ALG_SHA_384;
+    requester.common.session = gen_array_clone(SpdmSession::new(), 4);
+    setup_established_session(&mut requester.common.session[0], session_id);
+
+    assert!(requester.receive_spdm_event(session_id).is_ok());
+    assert_eq!(
+        RECEIVED_EVENT_TYPE.load(Ordering::SeqCst),
+        SpdmDmtfEventType::CertificateChanged.get_u16()
+    );
+}
@@ -0,0 +1,115 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use crate::common::device_io::{FakeSpdmDeviceIo, FakeSpdmDeviceIoReceve, SharedBuffer};
+use crate::common::secret_callback::*;
+use crate::common::transport::PciDoeTransportEncap;
+use crate::common::util::create_info;
+use spdmlib::common::{SpdmConnectionState, SpdmContext};
+use spdmlib::error::SPDM_STATUS_UNSUPPORTED_CAP;
+use spdmlib::protocol::*;
+use spdmlib::requester::RequesterContext;
+use spdmlib::{responder, secret};
+
+fn setup_key_pair_negotiate_info(context: &mut SpdmContext) {
+    context.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion13;
+    context.negotiate_info.rsp_capabilities_sel |=
+        SpdmResponseCapabilityFlags::GET_KEY_PAIR_INFO_CAP
+            | SpdmResponseCapabilityFlags::SET_KEY_PAIR_INFO_CAP;
+}
+
+#[test]
+fn test_case0_send_receive_spdm_key_pair_info() {
+    let (rsp_config_info, mut rsp_provision_info) = create_info();
+    let (req_config_info, req_provision_info) = create_info();
+    rsp_provision_info.my_key_pair_id[0] = 1;
+
+    let shared_buffer = SharedBuffer::new();
+    let mut device_io_responder = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+
+    secret::asym_sign::register(SECRET_ASYM_IMPL_INSTANCE.clone());
+    secret::key_pair::register(SECRET_KEY_PAIR_IMPL_INSTANCE.clone());
+
+    let mut responder = responder::ResponderContext::new(
+        &mut device_io_responder,
+        pcidoe_transport_encap,
+        rsp_config_info,
+        rsp_provision_info,
+    );
+
+    setup_key_pair_negotiate_info(&mut responder.common);
+    responder
+        .common
+        .runtime_info
+        .set_connection_state(SpdmConnectionState::SpdmConnectionNegotiated);
+
+    let pcidoe_transport_encap2 = &mut PciDoeTransportEncap {};
+    let mut device_io_requester = FakeSpdmDeviceIo::new(&shared_buffer, &mut responder);
+
+    let mut requester = RequesterContext::new(
+        &mut device_io_requester,
+        pcidoe_transport_encap2,
+        req_config_info,
+        req_provision_info,
+    );
+
+    setup_key_pair_negotiate_info(&mut requester.common);
+
+    let key_pair_info = requester
+        .send_receive_spdm_get_key_pair_info(None, 1)
+        .unwrap();
+    assert_eq!(
+        key_pair_info.key_pair_info.total_key_pairs,
+        TEST_TOTAL_KEY_PAIRS
+    );
+    assert_eq!(
+        key_pair_info.key_pair_info.current_asym_algo,
+        SpdmKeyPairAsymAlgo::ECC384
+    );
+    assert_eq!(key_pair_info.assoc_cert_slot_mask, 0b0000_0001);
+
+    assert!(requester
+        .send_receive_spdm_set_key_pair_info(
+            None,
+            SpdmSetKeyPairInfoOperation::SpdmSetKeyPairInfoOperationErase,
+            1,
+            SpdmKeyUsageMask::empty(),
+            SpdmKeyPairAsymAlgo::empty(),
+            0,
+        )
+        .is_ok());
+
+    let key_pair_info = requester
+        .send_receive_spdm_get_key_pair_info(None, 1)
+        .unwrap();
+    assert_eq!(key_pair_info.assoc_cert_slot_mask, 0);
+
+    assert!(requester
+        .send_receive_spdm_get_key_pair_info(None, TEST_TOTAL_KEY_PAIRS + 1)
+        .is_err());
+}
+
+#[test]
+fn test_case1_send_receive_spdm_key_pair_info() {
+    let (req_config_info, req_provision_info) = create_info();
+
+    let shared_buffer = SharedBuffer::new();
+    let mut device_io_responder = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+
+    let mut requester = RequesterContext::new(
+        &mut device_io_responder,
+        pcidoe_transport_encap,
+        req_config_info,
+        req_provision_info,
+    );
+
+    // GET_KEY_PAIR_INFO_CAP is not set by the responder.
+    requester.common.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion13;
+    assert_eq!(
+        requester.send_receive_spdm_get_key_pair_info(None, 1).err(),
+        Some(SPDM_STATUS_UNSUPPORTED_CAP)
+    );
+}
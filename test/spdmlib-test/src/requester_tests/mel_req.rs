@@ -0,0 +1,91 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use crate::common::device_io::{FakeSpdmDeviceIo, FakeSpdmDeviceIoReceve, SharedBuffer};
+use crate::common::secret_callback::*;
+use crate::common::transport::PciDoeTransportEncap;
+use crate::common::util::create_info;
+use spdmlib::common::{SpdmConnectionState, SpdmContext};
+use spdmlib::error::SPDM_STATUS_UNSUPPORTED_CAP;
+use spdmlib::protocol::*;
+use spdmlib::requester::RequesterContext;
+use spdmlib::{responder, secret};
+
+fn setup_mel_negotiate_info(context: &mut SpdmContext) {
+    context.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion13;
+    context.negotiate_info.measurement_hash_sel = SpdmMeasurementHashAlgo::TPM_ALG_SHA_384;
+    context.negotiate_info.mel_specification_sel = SpdmMelSpecification::DMTF;
+    context.negotiate_info.rsp_capabilities_sel |= SpdmResponseCapabilityFlags::MEL_CAP;
+}
+
+#[test]
+fn test_case0_send_receive_spdm_measurement_extension_log() {
+    let (rsp_config_info, rsp_provision_info) = create_info();
+    let (req_config_info, req_provision_info) = create_info();
+
+    let shared_buffer = SharedBuffer::new();
+    let mut device_io_responder = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+
+    secret::asym_sign::register(SECRET_ASYM_IMPL_INSTANCE.clone());
+    secret::mel::register(SECRET_MEL_IMPL_INSTANCE.clone());
+
+    let mut responder = responder::ResponderContext::new(
+        &mut device_io_responder,
+        pcidoe_transport_encap,
+        rsp_config_info,
+        rsp_provision_info,
+    );
+
+    setup_mel_negotiate_info(&mut responder.common);
+    responder
+        .common
+        .runtime_info
+        .set_connection_state(SpdmConnectionState::SpdmConnectionNegotiated);
+
+    let pcidoe_transport_encap2 = &mut PciDoeTransportEncap {};
+    let mut device_io_requester = FakeSpdmDeviceIo::new(&shared_buffer, &mut responder);
+
+    let mut requester = RequesterContext::new(
+        &mut device_io_requester,
+        pcidoe_transport_encap2,
+        req_config_info,
+        req_provision_info,
+    );
+
+    setup_mel_negotiate_info(&mut requester.common);
+
+    let mel = requester
+        .send_receive_spdm_measurement_extension_log(None)
+        .unwrap();
+    assert_eq!(mel.data_size as usize, TEST_MEL_SIZE);
+    for (i, d) in mel.as_ref().iter().enumerate() {
+        assert_eq!(*d, i as u8);
+    }
+}
+
+#[test]
+fn test_case1_send_receive_spdm_measurement_extension_log() {
+    let (req_config_info, req_provision_info) = create_info();
+
+    let shared_buffer = SharedBuffer::new();
+    let mut device_io_responder = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+
+    let mut requester = RequesterContext::new(
+        &mut device_io_responder,
+        pcidoe_transport_encap,
+        req_config_info,
+        req_provision_info,
+    );
+
+    // MEL_CAP is not set by the responder.
+    requester.common.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion13;
+    assert_eq!(
+        requester
+            .send_receive_spdm_measurement_extension_log(None)
+            .err(),
+        Some(SPDM_STATUS_UNSUPPORTED_CAP)
+    );
+}
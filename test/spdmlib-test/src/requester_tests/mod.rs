@@ -6,13 +6,22 @@
 
 mod challenge_req;
 
+mod chunk_req;
+
 mod context;
 
 mod end_session_req;
 
+mod endpoint_info_req;
+
+mod event_req;
+
 #[cfg(feature = "mut-auth")]
 mod encap_certificate;
 
+#[cfg(feature = "mut-auth")]
+mod encap_challenge;
+
 #[cfg(feature = "mut-auth")]
 mod encap_digest;
 
@@ -20,6 +29,11 @@ mod encap_digest;
 mod encap_error;
 
 #[cfg(feature = "mut-auth")]
+mod encap_key_update;
+
+// The secured messages are looped back within one context, which only the
+// fake AEAD decodes, so the tests do not run with the RustCrypto backend.
+#[cfg(all(feature = "mut-auth", not(feature = "rustcrypto")))]
 mod encap_req;
 
 mod finish_req;
@@ -28,6 +42,8 @@ mod get_capabilities_req;
 
 mod get_certificate_req;
 
+mod get_csr_req;
+
 mod get_digests_req;
 
 mod get_measurements_req;
@@ -38,12 +54,20 @@ mod heartbeat_req;
 
 mod key_exchange_req;
 
+mod key_pair_info_req;
+
 mod key_update_req;
 
+mod mel_req;
+
 mod negotiate_algorithms_req;
 
 mod psk_exchange_req;
 
 mod psk_finish_req;
 
+mod respond_if_ready_req;
+
+mod set_certificate_req;
+
 mod vendor_req;
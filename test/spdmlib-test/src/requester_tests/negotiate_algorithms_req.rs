@@ -2,13 +2,20 @@
 //
 // SPDX-License-Identifier: BSD-2-Clause-Patent
 
+use crate::common::crypto_callback::{
+    FAKE_EXT_ASYM, FAKE_EXT_ASYM_ALGO, FAKE_EXT_ASYM_ALGO_OVERSIZED,
+};
 use crate::common::device_io::{FakeSpdmDeviceIo, FakeSpdmDeviceIoReceve, SharedBuffer};
 use crate::common::secret_callback::*;
 use crate::common::transport::PciDoeTransportEncap;
 use crate::common::util::create_info;
-use spdmlib::common::SpdmConnectionState;
+use codec::Writer;
+use spdmlib::common::{SpdmCodec, SpdmConnectionState, SpdmOpaqueSupport};
+use spdmlib::error::SPDM_STATUS_NEGOTIATION_FAIL;
+use spdmlib::message::*;
+use spdmlib::protocol::*;
 use spdmlib::requester::RequesterContext;
-use spdmlib::{responder, secret};
+use spdmlib::{config, crypto, responder, secret};
 
 #[test]
 fn test_case0_send_receive_spdm_algorithm() {
@@ -45,3 +52,90 @@ fn test_case0_send_receive_spdm_algorithm() {
     let status = requester.send_receive_spdm_algorithm().is_ok();
     assert!(status);
 }
+
+#[test]
+fn test_case1_handle_spdm_algorithm_response_ext_asym() {
+    let (mut req_config_info, req_provision_info) = create_info();
+    let shared_buffer = SharedBuffer::new();
+    let mut device_io_requester = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+
+    crypto::ext_asym::register(FAKE_EXT_ASYM.clone());
+
+    let unknown_alg = SpdmExtAlgStruct {
+        registry_id: SpdmStandardId::SpdmStandardIdIANA,
+        algorithm_id: 0x9abc,
+    };
+    req_config_info.ext_asym_algo[0] = Some(FAKE_EXT_ASYM_ALGO_OVERSIZED);
+    req_config_info.ext_asym_algo[1] = Some(unknown_alg);
+    req_config_info.ext_asym_algo[2] = Some(FAKE_EXT_ASYM_ALGO);
+
+    let mut requester = RequesterContext::new(
+        &mut device_io_requester,
+        pcidoe_transport_encap,
+        req_config_info,
+        req_provision_info,
+    );
+
+    // Only an algorithm with a signature size of 1 to SPDM_MAX_ASYM_KEY_SIZE
+    // can be selected by the responder.
+    for (ext_asym_sel, result) in [
+        (
+            FAKE_EXT_ASYM_ALGO_OVERSIZED,
+            Err(SPDM_STATUS_NEGOTIATION_FAIL),
+        ),
+        (unknown_alg, Err(SPDM_STATUS_NEGOTIATION_FAIL)),
+        (FAKE_EXT_ASYM_ALGO, Ok(())),
+    ] {
+        requester.common.reset_runtime_info();
+        requester.common.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion12;
+
+        let send_buffer = &mut [0u8; config::MAX_SPDM_MSG_SIZE];
+        let send_used = requester.encode_spdm_algorithm(send_buffer).unwrap();
+
+        let receive_buffer = &mut [0u8; config::MAX_SPDM_MSG_SIZE];
+        let mut writer = Writer::init(receive_buffer);
+        let response = SpdmMessage {
+            header: SpdmMessageHeader {
+                version: SpdmVersion::SpdmVersion12,
+                request_response_code: SpdmRequestResponseCode::SpdmResponseAlgorithms,
+            },
+            payload: SpdmMessagePayload::SpdmAlgorithmsResponse(SpdmAlgorithmsResponsePayload {
+                measurement_specification_sel: SpdmMeasurementSpecification::DMTF,
+                other_params_selection: SpdmOpaqueSupport::OPAQUE_DATA_FMT1,
+                measurement_hash_algo: SpdmMeasurementHashAlgo::TPM_ALG_SHA_384,
+                base_asym_sel: SpdmBaseAsymAlgo::empty(),
+                base_hash_sel: SpdmBaseHashAlgo::TPM_ALG_SHA_384,
+                pqc_asym_sel: SpdmPqcAsymAlgo::empty(),
+                mel_specification_sel: SpdmMelSpecification::empty(),
+                ext_asym_sel: Some(ext_asym_sel),
+                ext_hash_sel: None,
+                alg_struct_count: 0,
+                alg_struct: Default::default(),
+            }),
+        };
+        assert!(response
+            .spdm_encode(&mut requester.common, &mut writer)
+            .is_ok());
+        let receive_used = writer.used();
+
+        assert_eq!(
+            requester.handle_spdm_algorithm_response(
+                0,
+                &send_buffer[..send_used],
+                &receive_buffer[..receive_used]
+            ),
+            result
+        );
+        if result.is_ok() {
+            assert_eq!(
+                requester.common.negotiate_info.ext_asym_sel,
+                Some(FAKE_EXT_ASYM_ALGO)
+            );
+            assert_eq!(
+                requester.common.get_asym_key_size() as usize,
+                ECDSA_ECC_NIST_P384_KEY_SIZE
+            );
+        }
+    }
+}
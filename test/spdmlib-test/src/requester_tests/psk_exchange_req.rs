@@ -10,7 +10,9 @@ use spdmlib::common::SpdmConnectionState;
 use spdmlib::config::MAX_SPDM_PSK_HINT_SIZE;
 use spdmlib::protocol::*;
 use spdmlib::requester::RequesterContext;
-use spdmlib::{responder, secret};
+use spdmlib::{responder, secret, time};
+
+fn sleep_impl(_us: usize) {}
 
 #[test]
 fn test_case0_send_receive_spdm_psk_exchange() {
@@ -65,3 +67,77 @@ fn test_case0_send_receive_spdm_psk_exchange() {
         .is_ok();
     assert!(status);
 }
+
+#[test]
+fn test_case1_send_receive_spdm_psk_exchange_not_ready() {
+    let (rsp_config_info, rsp_provision_info) = create_info();
+    let (req_config_info, req_provision_info) = create_info();
+
+    let shared_buffer = SharedBuffer::new();
+    let mut device_io_responder = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+
+    secret::psk::register(SECRET_PSK_IMPL_INSTANCE.clone());
+    secret::measurement::register(SECRET_MEASUREMENT_IMPL_INSTANCE.clone());
+    time::register(time::SpdmTime {
+        sleep_cb: sleep_impl,
+    });
+
+    let mut responder = responder::ResponderContext::new(
+        &mut device_io_responder,
+        pcidoe_transport_encap,
+        rsp_config_info,
+        rsp_provision_info,
+    );
+
+    responder.common.negotiate_info.base_hash_sel = SpdmBaseHashAlgo::TPM_ALG_SHA_384;
+    responder.common.negotiate_info.aead_sel = SpdmAeadAlgo::AES_128_GCM;
+    responder.common.negotiate_info.rsp_capabilities_sel =
+        SpdmResponseCapabilityFlags::MEAS_CAP_SIG;
+    responder
+        .common
+        .negotiate_info
+        .measurement_specification_sel = SpdmMeasurementSpecification::DMTF;
+    responder.common.negotiate_info.measurement_hash_sel = SpdmMeasurementHashAlgo::TPM_ALG_SHA_384;
+    responder.common.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion11;
+    responder
+        .common
+        .runtime_info
+        .set_connection_state(SpdmConnectionState::SpdmConnectionNegotiated);
+
+    let pcidoe_transport_encap2 = &mut PciDoeTransportEncap {};
+    let mut device_io_requester = FakeSpdmDeviceIo::new(&shared_buffer, &mut responder);
+
+    let mut requester = RequesterContext::new(
+        &mut device_io_requester,
+        pcidoe_transport_encap2,
+        req_config_info,
+        req_provision_info,
+    );
+
+    requester.common.negotiate_info.base_hash_sel = SpdmBaseHashAlgo::TPM_ALG_SHA_384;
+    requester.common.negotiate_info.aead_sel = SpdmAeadAlgo::AES_128_GCM;
+    requester.common.negotiate_info.rsp_capabilities_sel =
+        SpdmResponseCapabilityFlags::MEAS_CAP_SIG;
+    requester
+        .common
+        .negotiate_info
+        .measurement_specification_sel = SpdmMeasurementSpecification::DMTF;
+    requester.common.negotiate_info.measurement_hash_sel = SpdmMeasurementHashAlgo::TPM_ALG_SHA_384;
+    requester.common.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion11;
+
+    let mut psk_key = SpdmPskHintStruct {
+        data_size: b"TestPskHint\0".len() as u16,
+        data: [0u8; MAX_SPDM_PSK_HINT_SIZE],
+    };
+    psk_key.data[0..(psk_key.data_size as usize)].copy_from_slice(b"TestPskHint\0");
+
+    // The measurement summary hash is produced on the first RESPOND_IF_READY.
+    RESPONSE_NOT_READY_COUNT.with(|count| count.set(1));
+    let status = requester.send_receive_spdm_psk_exchange(
+        SpdmMeasurementSummaryHashType::SpdmMeasurementSummaryHashTypeAll,
+        Some(&psk_key),
+    );
+    assert!(status.is_ok());
+    assert_eq!(RESPONSE_NOT_READY_COUNT.with(|count| count.get()), 0);
+}
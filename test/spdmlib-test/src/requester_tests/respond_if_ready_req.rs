@@ -0,0 +1,241 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use crate::common::device_io::{SharedBuffer, SpdmDeviceIoReceve};
+use crate::common::transport::PciDoeTransportEncap;
+use crate::common::util::create_info;
+use spdmlib::common::{SpdmDeviceIo, SpdmTransportEncap};
+use spdmlib::error::{SpdmResult, SPDM_STATUS_INVALID_MSG_FIELD, SPDM_STATUS_NOT_READY_PEER};
+use spdmlib::message::*;
+use spdmlib::protocol::*;
+use spdmlib::requester::RequesterContext;
+use spdmlib::{config, time};
+use std::collections::VecDeque;
+
+fn sleep_impl(_us: usize) {}
+
+fn encode_not_ready(request_code: SpdmRequestResponseCode, token: u8) -> [u8; 8] {
+    encode_not_ready_with_rdtm(request_code, token, 2)
+}
+
+fn encode_not_ready_with_rdtm(
+    request_code: SpdmRequestResponseCode,
+    token: u8,
+    rdtm: u8,
+) -> [u8; 8] {
+    [
+        SpdmVersion::SpdmVersion12.get_u8(),
+        SpdmRequestResponseCode::SpdmResponseError.get_u8(),
+        SpdmErrorCode::SpdmErrorResponseNotReady.get_u8(),
+        0,
+        1, // RDTExponent
+        request_code.get_u8(),
+        token,
+        rdtm,
+    ]
+}
+
+// Answers each request with the next scripted response.
+struct ScriptedDeviceIo {
+    responses: VecDeque<Vec<u8>>,
+    sent: usize,
+}
+
+impl SpdmDeviceIo for ScriptedDeviceIo {
+    fn send(&mut self, _buffer: &[u8]) -> SpdmResult {
+        self.sent += 1;
+        Ok(())
+    }
+
+    fn receive(&mut self, buffer: &mut [u8], _timeout: usize) -> Result<usize, usize> {
+        let response = self.responses.pop_front().ok_or(0usize)?;
+        buffer[..response.len()].copy_from_slice(&response);
+        Ok(response.len())
+    }
+
+    fn flush_all(&mut self) -> SpdmResult {
+        Ok(())
+    }
+}
+
+fn pci_doe_encap(spdm_buffer: &[u8]) -> Vec<u8> {
+    let mut transport_buffer = [0u8; config::SENDER_BUFFER_SIZE];
+    let used = PciDoeTransportEncap {}
+        .encap(spdm_buffer, &mut transport_buffer, false)
+        .unwrap();
+    transport_buffer[..used].to_vec()
+}
+
+#[test]
+fn test_case0_respond_if_ready() {
+    let (req_config_info, req_provision_info) = create_info();
+    time::register(time::SpdmTime {
+        sleep_cb: sleep_impl,
+    });
+
+    // MEASUREMENTS with the total number of measurement blocks.
+    let mut measurements = [0u8; 4 + 4 + SPDM_NONCE_SIZE + 2];
+    measurements[0] = SpdmVersion::SpdmVersion12.get_u8();
+    measurements[1] = SpdmRequestResponseCode::SpdmResponseMeasurements.get_u8();
+    measurements[2] = 10;
+    let respond_if_ready_response = pci_doe_encap(&measurements);
+
+    let shared_buffer = SharedBuffer::new();
+    let mut device_io = SpdmDeviceIoReceve::new(&shared_buffer, &respond_if_ready_response);
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+
+    let mut requester = RequesterContext::new(
+        &mut device_io,
+        pcidoe_transport_encap,
+        req_config_info,
+        req_provision_info,
+    );
+    requester.common.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion12;
+    requester.common.negotiate_info.base_hash_sel = SpdmBaseHashAlgo::TPM_ALG_SHA_384;
+    requester.common.negotiate_info.base_asym_sel = SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384;
+    requester.common.reset_runtime_info();
+
+    let mut send_buffer = [0u8; config::MAX_SPDM_MSG_SIZE];
+    let send_used = requester
+        .encode_spdm_measurement_record(
+            SpdmMeasurementAttributes::empty(),
+            SpdmMeasurementOperation::SpdmMeasurementQueryTotalNumber,
+            0,
+            &mut send_buffer,
+        )
+        .unwrap();
+
+    // The response to GET_MEASUREMENTS is picked up with RESPOND_IF_READY.
+    let mut spdm_measurement_record_structure = SpdmMeasurementRecordStructure::default();
+    let status = requester.handle_spdm_measurement_record_response(
+        None,
+        0,
+        SpdmMeasurementAttributes::empty(),
+        SpdmMeasurementOperation::SpdmMeasurementQueryTotalNumber,
+        &mut spdm_measurement_record_structure,
+        &send_buffer[..send_used],
+        &encode_not_ready(SpdmRequestResponseCode::SpdmRequestGetMeasurements, 1),
+    );
+    assert_eq!(status, Ok(10));
+
+    // ResponseNotReady must refer to the original request.
+    let status = requester.handle_spdm_measurement_record_response(
+        None,
+        0,
+        SpdmMeasurementAttributes::empty(),
+        SpdmMeasurementOperation::SpdmMeasurementQueryTotalNumber,
+        &mut spdm_measurement_record_structure,
+        &send_buffer[..send_used],
+        &encode_not_ready(SpdmRequestResponseCode::SpdmRequestChallenge, 1),
+    );
+    assert_eq!(status, Err(SPDM_STATUS_INVALID_MSG_FIELD));
+}
+
+#[test]
+fn test_case1_respond_if_ready() {
+    let (req_config_info, req_provision_info) = create_info();
+    time::register(time::SpdmTime {
+        sleep_cb: sleep_impl,
+    });
+
+    // The responder never gets ready.
+    let respond_if_ready_response = pci_doe_encap(&encode_not_ready(
+        SpdmRequestResponseCode::SpdmRequestGetMeasurements,
+        2,
+    ));
+
+    let shared_buffer = SharedBuffer::new();
+    let mut device_io = SpdmDeviceIoReceve::new(&shared_buffer, &respond_if_ready_response);
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+
+    let mut requester = RequesterContext::new(
+        &mut device_io,
+        pcidoe_transport_encap,
+        req_config_info,
+        req_provision_info,
+    );
+    requester.common.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion12;
+    requester.common.negotiate_info.base_hash_sel = SpdmBaseHashAlgo::TPM_ALG_SHA_384;
+    requester.common.negotiate_info.base_asym_sel = SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384;
+    requester.common.reset_runtime_info();
+
+    let status = requester.spdm_handle_error_response_respond_if_ready(
+        None,
+        &encode_not_ready(SpdmRequestResponseCode::SpdmRequestGetMeasurements, 2),
+        SpdmRequestResponseCode::SpdmRequestGetMeasurements,
+        SpdmRequestResponseCode::SpdmResponseMeasurements,
+    );
+    assert_eq!(status.err(), Some(SPDM_STATUS_NOT_READY_PEER));
+}
+
+#[test]
+fn test_case2_respond_if_ready_rdtm() {
+    let (req_config_info, req_provision_info) = create_info();
+    time::register(time::SpdmTime {
+        sleep_cb: sleep_impl,
+    });
+
+    let not_ready =
+        encode_not_ready_with_rdtm(SpdmRequestResponseCode::SpdmRequestGetMeasurements, 3, 10);
+    let mut measurements = [0u8; 4 + 4 + SPDM_NONCE_SIZE + 2];
+    measurements[0] = SpdmVersion::SpdmVersion12.get_u8();
+    measurements[1] = SpdmRequestResponseCode::SpdmResponseMeasurements.get_u8();
+    measurements[2] = 10;
+
+    // WT_max is 10 RDT, so the responder is polled beyond
+    // MAX_SPDM_RESPOND_IF_READY_RETRY and gets ready at the 8th request.
+    let mut responses: VecDeque<Vec<u8>> = (0..7).map(|_| pci_doe_encap(&not_ready)).collect();
+    responses.push_back(pci_doe_encap(&measurements));
+    let mut device_io = ScriptedDeviceIo { responses, sent: 0 };
+    {
+        let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+        let mut requester = RequesterContext::new(
+            &mut device_io,
+            pcidoe_transport_encap,
+            req_config_info,
+            req_provision_info,
+        );
+        requester.common.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion12;
+        requester.common.reset_runtime_info();
+
+        let received = requester
+            .spdm_handle_error_response_respond_if_ready(
+                None,
+                &not_ready,
+                SpdmRequestResponseCode::SpdmRequestGetMeasurements,
+                SpdmRequestResponseCode::SpdmResponseMeasurements,
+            )
+            .unwrap();
+        assert_eq!(
+            received.receive_buffer[1],
+            SpdmRequestResponseCode::SpdmResponseMeasurements.get_u8()
+        );
+    }
+    assert_eq!(device_io.sent, 8);
+
+    // The requester gives up once WT_max has elapsed.
+    let (req_config_info, req_provision_info) = create_info();
+    let responses: VecDeque<Vec<u8>> = (0..20).map(|_| pci_doe_encap(&not_ready)).collect();
+    let mut device_io = ScriptedDeviceIo { responses, sent: 0 };
+    {
+        let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+        let mut requester = RequesterContext::new(
+            &mut device_io,
+            pcidoe_transport_encap,
+            req_config_info,
+            req_provision_info,
+        );
+        requester.common.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion12;
+        requester.common.reset_runtime_info();
+
+        let status = requester.spdm_handle_error_response_respond_if_ready(
+            None,
+            &not_ready,
+            SpdmRequestResponseCode::SpdmRequestGetMeasurements,
+            SpdmRequestResponseCode::SpdmResponseMeasurements,
+        );
+        assert_eq!(status.err(), Some(SPDM_STATUS_NOT_READY_PEER));
+    }
+    assert_eq!(device_io.sent, 10);
+}
@@ -0,0 +1,104 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use crate::common::device_io::{FakeSpdmDeviceIo, FakeSpdmDeviceIoReceve, SharedBuffer};
+use crate::common::secret_callback::*;
+use crate::common::transport::PciDoeTransportEncap;
+use crate::common::util::{create_info, get_rsp_cert_chain_buff};
+use spdmlib::common::{SpdmConnectionState, SpdmContext};
+use spdmlib::error::{SPDM_STATUS_INVALID_PARAMETER, SPDM_STATUS_RESET_REQUIRED_PEER};
+use spdmlib::message::*;
+use spdmlib::protocol::*;
+use spdmlib::requester::RequesterContext;
+use spdmlib::{responder, secret};
+
+fn setup_set_certificate_negotiate_info(context: &mut SpdmContext) {
+    context.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion12;
+    context.negotiate_info.base_hash_sel = SpdmBaseHashAlgo::TPM_ALG_SHA_384;
+    context.negotiate_info.base_asym_sel = SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384;
+    context.negotiate_info.rsp_capabilities_sel |= SpdmResponseCapabilityFlags::SET_CERT_CAP;
+}
+
+#[test]
+fn test_case0_send_receive_spdm_set_certificate() {
+    let (rsp_config_info, rsp_provision_info) = create_info();
+    let (req_config_info, req_provision_info) = create_info();
+
+    let shared_buffer = SharedBuffer::new();
+    let mut device_io_responder = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+
+    secret::asym_sign::register(SECRET_ASYM_IMPL_INSTANCE.clone());
+    secret::cert_provision::register(SECRET_CERT_PROVISION_IMPL_INSTANCE.clone());
+
+    let mut responder = responder::ResponderContext::new(
+        &mut device_io_responder,
+        pcidoe_transport_encap,
+        rsp_config_info,
+        rsp_provision_info,
+    );
+
+    setup_set_certificate_negotiate_info(&mut responder.common);
+    responder
+        .common
+        .runtime_info
+        .set_connection_state(SpdmConnectionState::SpdmConnectionNegotiated);
+    responder.common.provision_info.my_cert_chain_data[0] = None;
+    responder.common.provision_info.my_cert_chain[0] = None;
+
+    let pcidoe_transport_encap2 = &mut PciDoeTransportEncap {};
+    let mut device_io_requester = FakeSpdmDeviceIo::new(&shared_buffer, &mut responder);
+
+    let mut requester = RequesterContext::new(
+        &mut device_io_requester,
+        pcidoe_transport_encap2,
+        req_config_info,
+        req_provision_info,
+    );
+
+    setup_set_certificate_negotiate_info(&mut requester.common);
+
+    let cert_chain = get_rsp_cert_chain_buff();
+    let status = requester.send_receive_spdm_set_certificate(None, 0, &cert_chain);
+    assert!(status.is_ok());
+}
+
+#[test]
+fn test_case1_send_receive_spdm_set_certificate() {
+    let (req_config_info, req_provision_info) = create_info();
+
+    let shared_buffer = SharedBuffer::new();
+    let mut device_io_responder = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+
+    let mut requester = RequesterContext::new(
+        &mut device_io_responder,
+        pcidoe_transport_encap,
+        req_config_info,
+        req_provision_info,
+    );
+
+    let cert_chain = get_rsp_cert_chain_buff();
+
+    // SET_CERTIFICATE is rejected locally if the responder does not report SET_CERT_CAP.
+    requester.common.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion12;
+    assert!(requester
+        .send_receive_spdm_set_certificate(None, 0, &cert_chain)
+        .is_err());
+
+    // A non-zero slot can only be provisioned inside a secure session.
+    setup_set_certificate_negotiate_info(&mut requester.common);
+    let status = requester.send_receive_spdm_set_certificate(None, 1, &cert_chain);
+    assert_eq!(status.err(), Some(SPDM_STATUS_INVALID_PARAMETER));
+
+    // ERROR(ResetRequired) means the chain takes effect after a reset.
+    let error_response = [
+        SpdmVersion::SpdmVersion12.get_u8(),
+        SpdmRequestResponseCode::SpdmResponseError.get_u8(),
+        SpdmErrorCode::SpdmErrorResetRequired.get_u8(),
+        0u8,
+    ];
+    let status = requester.handle_spdm_set_certificate_response(None, 0, &error_response);
+    assert_eq!(status.err(), Some(SPDM_STATUS_RESET_REQUIRED_PEER));
+}
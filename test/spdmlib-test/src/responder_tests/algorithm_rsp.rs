@@ -2,6 +2,9 @@
 //
 // SPDX-License-Identifier: BSD-2-Clause-Patent
 
+use crate::common::crypto_callback::{
+    FAKE_EXT_ASYM, FAKE_EXT_ASYM_ALGO, FAKE_EXT_ASYM_ALGO_OVERSIZED,
+};
 use crate::common::device_io::{FakeSpdmDeviceIoReceve, SharedBuffer};
 use crate::common::secret_callback::SECRET_ASYM_IMPL_INSTANCE;
 use crate::common::transport::PciDoeTransportEncap;
@@ -11,7 +14,7 @@ use log::debug;
 use spdmlib::common::*;
 use spdmlib::message::*;
 use spdmlib::protocol::*;
-use spdmlib::{responder, secret};
+use spdmlib::{crypto, responder, secret};
 
 #[test]
 fn test_case0_handle_spdm_algorithm() {
@@ -51,6 +54,12 @@ fn test_case0_handle_spdm_algorithm() {
         other_params_support: SpdmOpaqueSupport::empty(),
         base_asym_algo: SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384,
         base_hash_algo: SpdmBaseHashAlgo::TPM_ALG_SHA_384,
+        pqc_asym_algo: SpdmPqcAsymAlgo::empty(),
+        mel_specification: SpdmMelSpecification::empty(),
+        ext_asym_count: 0,
+        ext_asym: [SpdmExtAlgStruct::default(); SPDM_MAX_EXT_ALG_COUNT],
+        ext_hash_count: 0,
+        ext_hash: [SpdmExtAlgStruct::default(); SPDM_MAX_EXT_ALG_COUNT],
         alg_struct_count: 4,
         alg_struct: [
             SpdmAlgStruct {
@@ -71,6 +80,8 @@ fn test_case0_handle_spdm_algorithm() {
                 alg_type: SpdmAlgType::SpdmAlgTypeKeySchedule,
                 alg_supported: SpdmAlg::SpdmAlgoKeySchedule(SpdmKeyScheduleAlgo::SPDM_KEY_SCHEDULE),
             },
+            #[cfg(feature = "pqc")]
+            SpdmAlgStruct::default(),
         ],
     };
     assert!(value.spdm_encode(&mut context.common, &mut writer).is_ok());
@@ -204,3 +215,187 @@ fn test_case0_handle_spdm_algorithm() {
         );
     }
 }
+
+#[test]
+fn test_case1_handle_spdm_algorithm_ext_asym() {
+    let (mut config_info, provision_info) = create_info();
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+
+    crypto::ext_asym::register(FAKE_EXT_ASYM.clone());
+
+    let vendor_alg = FAKE_EXT_ASYM_ALGO;
+    config_info.base_asym_algo = SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384;
+    // The algorithm with a signature larger than supported is skipped.
+    config_info.ext_asym_algo[0] = Some(FAKE_EXT_ASYM_ALGO_OVERSIZED);
+    config_info.ext_asym_algo[1] = Some(vendor_alg);
+
+    let shared_buffer = SharedBuffer::new();
+    let mut socket_io_transport = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+
+    let mut context = responder::ResponderContext::new(
+        &mut socket_io_transport,
+        pcidoe_transport_encap,
+        config_info,
+        provision_info,
+    );
+
+    let mut ext_asym = [SpdmExtAlgStruct::default(); SPDM_MAX_EXT_ALG_COUNT];
+    ext_asym[0] = SpdmExtAlgStruct {
+        registry_id: SpdmStandardId::SpdmStandardIdTCG,
+        algorithm_id: 0x1234,
+    };
+    ext_asym[1] = FAKE_EXT_ASYM_ALGO_OVERSIZED;
+    ext_asym[2] = vendor_alg;
+
+    // A common base algorithm takes precedence over the extended algorithm.
+    for (base_asym_algo, ext_asym_sel) in [
+        (SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384, None),
+        (SpdmBaseAsymAlgo::TPM_ALG_RSASSA_3072, Some(vendor_alg)),
+    ] {
+        context.common.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion11;
+        context.common.reset_message_a();
+        context
+            .common
+            .runtime_info
+            .set_connection_state(SpdmConnectionState::SpdmConnectionAfterCapabilities);
+
+        let bytes = &mut [0u8; 1024];
+        let mut writer = Writer::init(bytes);
+        let value = SpdmMessageHeader {
+            version: SpdmVersion::SpdmVersion11,
+            request_response_code: SpdmRequestResponseCode::SpdmRequestNegotiateAlgorithms,
+        };
+        assert!(value.encode(&mut writer).is_ok());
+        let value = SpdmNegotiateAlgorithmsRequestPayload {
+            measurement_specification: SpdmMeasurementSpecification::DMTF,
+            other_params_support: SpdmOpaqueSupport::empty(),
+            base_asym_algo,
+            base_hash_algo: SpdmBaseHashAlgo::TPM_ALG_SHA_384,
+            pqc_asym_algo: SpdmPqcAsymAlgo::empty(),
+            mel_specification: SpdmMelSpecification::empty(),
+            ext_asym_count: 3,
+            ext_asym,
+            ext_hash_count: 0,
+            ext_hash: [SpdmExtAlgStruct::default(); SPDM_MAX_EXT_ALG_COUNT],
+            alg_struct_count: 0,
+            alg_struct: Default::default(),
+        };
+        assert!(value.spdm_encode(&mut context.common, &mut writer).is_ok());
+        let used = writer.used();
+
+        assert!(context.handle_spdm_algorithm(&bytes[..used]).is_ok());
+
+        assert_eq!(context.common.negotiate_info.ext_asym_sel, ext_asym_sel);
+        let message_a = context.common.runtime_info.message_a.clone();
+        let mut reader = Reader::init(&message_a.as_ref()[used..]);
+        let spdm_message = SpdmMessage::spdm_read(&mut context.common, &mut reader).unwrap();
+        if let SpdmMessagePayload::SpdmAlgorithmsResponse(payload) = &spdm_message.payload {
+            assert_eq!(payload.ext_asym_sel, ext_asym_sel);
+            assert_eq!(payload.base_asym_sel.bits() == 0, ext_asym_sel.is_some());
+        } else {
+            panic!("unexpected response");
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "pqc")]
+fn test_case2_handle_spdm_algorithm_kem() {
+    let (mut config_info, provision_info) = create_info();
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+
+    config_info.pqc_asym_algo = SpdmPqcAsymAlgo::ML_DSA_65;
+    config_info.kem_algo = SpdmKemAlgo::ML_KEM_768;
+
+    let shared_buffer = SharedBuffer::new();
+    let mut socket_io_transport = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+
+    let mut context = responder::ResponderContext::new(
+        &mut socket_io_transport,
+        pcidoe_transport_encap,
+        config_info,
+        provision_info,
+    );
+    context.common.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion14;
+    context
+        .common
+        .runtime_info
+        .set_connection_state(SpdmConnectionState::SpdmConnectionAfterCapabilities);
+
+    let bytes = &mut [0u8; 1024];
+    let mut writer = Writer::init(bytes);
+    let value = SpdmMessageHeader {
+        version: SpdmVersion::SpdmVersion14,
+        request_response_code: SpdmRequestResponseCode::SpdmRequestNegotiateAlgorithms,
+    };
+    assert!(value.encode(&mut writer).is_ok());
+    let value = SpdmNegotiateAlgorithmsRequestPayload {
+        measurement_specification: SpdmMeasurementSpecification::DMTF,
+        other_params_support: SpdmOpaqueSupport::empty(),
+        base_asym_algo: SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384,
+        base_hash_algo: SpdmBaseHashAlgo::TPM_ALG_SHA_384,
+        pqc_asym_algo: SpdmPqcAsymAlgo::ML_DSA_65 | SpdmPqcAsymAlgo::ML_DSA_87,
+        mel_specification: SpdmMelSpecification::empty(),
+        ext_asym_count: 0,
+        ext_asym: [SpdmExtAlgStruct::default(); SPDM_MAX_EXT_ALG_COUNT],
+        ext_hash_count: 0,
+        ext_hash: [SpdmExtAlgStruct::default(); SPDM_MAX_EXT_ALG_COUNT],
+        alg_struct_count: 5,
+        alg_struct: [
+            SpdmAlgStruct {
+                alg_type: SpdmAlgType::SpdmAlgTypeDHE,
+                alg_supported: SpdmAlg::SpdmAlgoDhe(SpdmDheAlgo::SECP_384_R1),
+            },
+            SpdmAlgStruct {
+                alg_type: SpdmAlgType::SpdmAlgTypeAEAD,
+                alg_supported: SpdmAlg::SpdmAlgoAead(SpdmAeadAlgo::AES_256_GCM),
+            },
+            SpdmAlgStruct {
+                alg_type: SpdmAlgType::SpdmAlgTypeReqAsym,
+                alg_supported: SpdmAlg::SpdmAlgoReqAsym(
+                    SpdmReqAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384,
+                ),
+            },
+            SpdmAlgStruct {
+                alg_type: SpdmAlgType::SpdmAlgTypeKeySchedule,
+                alg_supported: SpdmAlg::SpdmAlgoKeySchedule(SpdmKeyScheduleAlgo::SPDM_KEY_SCHEDULE),
+            },
+            SpdmAlgStruct {
+                alg_type: SpdmAlgType::SpdmAlgTypeKEM,
+                alg_supported: SpdmAlg::SpdmAlgoKem(
+                    SpdmKemAlgo::ML_KEM_768 | SpdmKemAlgo::ML_KEM_1024,
+                ),
+            },
+        ],
+    };
+    assert!(value.spdm_encode(&mut context.common, &mut writer).is_ok());
+    let used = writer.used();
+
+    assert!(context.handle_spdm_algorithm(&bytes[..used]).is_ok());
+
+    // The PQC algorithms are selected over the common base ones.
+    assert_eq!(
+        context.common.negotiate_info.pqc_asym_sel,
+        SpdmPqcAsymAlgo::ML_DSA_65
+    );
+    assert!(context.common.negotiate_info.base_asym_sel.is_empty());
+    assert_eq!(
+        context.common.negotiate_info.kem_sel,
+        SpdmKemAlgo::ML_KEM_768
+    );
+    assert!(context.common.negotiate_info.dhe_sel.is_empty());
+
+    let message_a = context.common.runtime_info.message_a.clone();
+    let mut reader = Reader::init(&message_a.as_ref()[used..]);
+    let spdm_message = SpdmMessage::spdm_read(&mut context.common, &mut reader).unwrap();
+    if let SpdmMessagePayload::SpdmAlgorithmsResponse(payload) = &spdm_message.payload {
+        assert_eq!(payload.pqc_asym_sel, SpdmPqcAsymAlgo::ML_DSA_65);
+        assert_eq!(payload.alg_struct_count, 5);
+        assert_eq!(
+            payload.alg_struct[4].alg_supported,
+            SpdmAlg::SpdmAlgoKem(SpdmKemAlgo::ML_KEM_768)
+        );
+    } else {
+        panic!("unexpected response");
+    }
+}
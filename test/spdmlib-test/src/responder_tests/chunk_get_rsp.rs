@@ -0,0 +1,126 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use crate::common::device_io::{FakeSpdmDeviceIoReceve, SharedBuffer};
+use crate::common::secret_callback::*;
+use crate::common::transport::PciDoeTransportEncap;
+use crate::common::util::create_info;
+use codec::{Codec, Writer};
+use spdmlib::common::{SpdmChunkStatus, SpdmCodec};
+use spdmlib::message::*;
+use spdmlib::protocol::*;
+use spdmlib::{responder, secret};
+
+fn encode_chunk_get(
+    context: &mut responder::ResponderContext,
+    handle: u8,
+    chunk_seq_num: u16,
+    bytes: &mut [u8],
+) -> usize {
+    let mut writer = Writer::init(bytes);
+    let value = SpdmMessageHeader {
+        version: SpdmVersion::SpdmVersion12,
+        request_response_code: SpdmRequestResponseCode::SpdmRequestChunkGet,
+    };
+    assert!(value.encode(&mut writer).is_ok());
+    let payload = SpdmChunkGetRequestPayload {
+        handle,
+        chunk_seq_num,
+    };
+    assert!(payload
+        .spdm_encode(&mut context.common, &mut writer)
+        .is_ok());
+    writer.used()
+}
+
+#[test]
+fn test_case0_handle_spdm_chunk_get() {
+    let (config_info, provision_info) = create_info();
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+    let shared_buffer = SharedBuffer::new();
+    let mut socket_io_transport = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    secret::asym_sign::register(SECRET_ASYM_IMPL_INSTANCE.clone());
+    let mut context = responder::ResponderContext::new(
+        &mut socket_io_transport,
+        pcidoe_transport_encap,
+        config_info,
+        provision_info,
+    );
+
+    context.common.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion12;
+    context.common.negotiate_info.req_capabilities_sel = SpdmRequestCapabilityFlags::CHUNK_CAP;
+    context.common.negotiate_info.rsp_capabilities_sel = SpdmResponseCapabilityFlags::CHUNK_CAP;
+    context.common.negotiate_info.req_data_transfer_size_sel = 64;
+    context.common.negotiate_info.req_max_spdm_msg_size_sel = 0x1200;
+
+    // CHUNK_GET without a large response is unexpected.
+    let bytes = &mut [0u8; 16];
+    let used = encode_chunk_get(&mut context, 0, 0, bytes);
+    assert!(context.handle_spdm_chunk_get(None, &bytes[..used]).is_ok());
+    assert_eq!(
+        context.common.chunk_context.chunk_status,
+        SpdmChunkStatus::SpdmChunkIdle
+    );
+
+    // A response larger than the DataTransferSize is kept for CHUNK_GET.
+    let mut large_response = [0xa5u8; 200];
+    large_response[0] = SpdmVersion::SpdmVersion12.get_u8();
+    large_response[1] = SpdmRequestResponseCode::SpdmResponseVendorDefinedResponse.get_u8();
+    assert!(context.send_message(&large_response).is_ok());
+    assert_eq!(
+        context.common.chunk_context.chunk_status,
+        SpdmChunkStatus::SpdmChunkGetInProgress
+    );
+    let handle = context.common.chunk_context.chunk_handle;
+
+    // 48 + 52 + 52 + 48 bytes
+    for chunk_seq_num in 0..4u16 {
+        assert_eq!(
+            context.common.chunk_context.chunk_status,
+            SpdmChunkStatus::SpdmChunkGetInProgress
+        );
+        let used = encode_chunk_get(&mut context, handle, chunk_seq_num, bytes);
+        assert!(context.handle_spdm_chunk_get(None, &bytes[..used]).is_ok());
+    }
+    assert_eq!(
+        context.common.chunk_context.chunk_status,
+        SpdmChunkStatus::SpdmChunkIdle
+    );
+}
+
+#[test]
+fn test_case1_handle_spdm_chunk_get() {
+    let (config_info, provision_info) = create_info();
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+    let shared_buffer = SharedBuffer::new();
+    let mut socket_io_transport = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    secret::asym_sign::register(SECRET_ASYM_IMPL_INSTANCE.clone());
+    let mut context = responder::ResponderContext::new(
+        &mut socket_io_transport,
+        pcidoe_transport_encap,
+        config_info,
+        provision_info,
+    );
+
+    context.common.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion12;
+    context.common.negotiate_info.req_capabilities_sel = SpdmRequestCapabilityFlags::CHUNK_CAP;
+    context.common.negotiate_info.rsp_capabilities_sel = SpdmResponseCapabilityFlags::CHUNK_CAP;
+    context.common.negotiate_info.req_data_transfer_size_sel = 64;
+    context.common.negotiate_info.req_max_spdm_msg_size_sel = 0x1200;
+
+    let mut large_response = [0xa5u8; 200];
+    large_response[0] = SpdmVersion::SpdmVersion12.get_u8();
+    large_response[1] = SpdmRequestResponseCode::SpdmResponseVendorDefinedResponse.get_u8();
+    assert!(context.send_message(&large_response).is_ok());
+    let handle = context.common.chunk_context.chunk_handle;
+
+    // A mismatched ChunkSeqNo discards the large response.
+    let bytes = &mut [0u8; 16];
+    let used = encode_chunk_get(&mut context, handle, 1, bytes);
+    assert!(context.handle_spdm_chunk_get(None, &bytes[..used]).is_ok());
+    assert_eq!(
+        context.common.chunk_context.chunk_status,
+        SpdmChunkStatus::SpdmChunkIdle
+    );
+}
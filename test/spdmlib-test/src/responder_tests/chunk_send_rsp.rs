@@ -0,0 +1,250 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use crate::common::device_io::{FakeSpdmDeviceIoReceve, SharedBuffer};
+use crate::common::secret_callback::*;
+use crate::common::transport::PciDoeTransportEncap;
+use crate::common::util::create_info;
+use codec::{Codec, Writer};
+use spdmlib::common::{SpdmChunkStatus, SpdmCodec, SpdmContext};
+use spdmlib::message::*;
+use spdmlib::protocol::*;
+use spdmlib::{responder, secret};
+
+const TEST_DATA_TRANSFER_SIZE: u32 = 64;
+const TEST_LARGE_REQUEST_SIZE: usize = 100;
+
+fn setup_chunk_negotiate_info(context: &mut SpdmContext) {
+    context.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion12;
+    context.negotiate_info.req_capabilities_sel = SpdmRequestCapabilityFlags::CHUNK_CAP;
+    context.negotiate_info.rsp_capabilities_sel = SpdmResponseCapabilityFlags::CHUNK_CAP;
+    context.config_info.data_transfer_size = TEST_DATA_TRANSFER_SIZE;
+    context.negotiate_info.req_data_transfer_size_sel = TEST_DATA_TRANSFER_SIZE;
+    context.negotiate_info.req_max_spdm_msg_size_sel = context.config_info.max_spdm_msg_size;
+}
+
+fn get_large_request() -> [u8; TEST_LARGE_REQUEST_SIZE] {
+    let mut large_request = [0x5au8; TEST_LARGE_REQUEST_SIZE];
+    large_request[0] = SpdmVersion::SpdmVersion12.get_u8();
+    large_request[1] = SpdmRequestResponseCode::SpdmRequestVendorDefinedRequest.get_u8();
+    large_request
+}
+
+fn encode_chunk_send(
+    context: &mut responder::ResponderContext,
+    last_chunk: bool,
+    handle: u8,
+    chunk_seq_num: u16,
+    large_message_size: u32,
+    chunk: &[u8],
+    bytes: &mut [u8],
+) -> usize {
+    let mut writer = Writer::init(bytes);
+    let value = SpdmMessageHeader {
+        version: SpdmVersion::SpdmVersion12,
+        request_response_code: SpdmRequestResponseCode::SpdmRequestChunkSend,
+    };
+    assert!(value.encode(&mut writer).is_ok());
+    let mut payload = SpdmChunkSendRequestPayload {
+        request_attributes: if last_chunk {
+            SpdmChunkSenderAttributes::LAST_CHUNK
+        } else {
+            SpdmChunkSenderAttributes::empty()
+        },
+        handle,
+        chunk_seq_num,
+        chunk_size: chunk.len() as u32,
+        large_message_size,
+        ..Default::default()
+    };
+    payload.chunk[..chunk.len()].copy_from_slice(chunk);
+    assert!(payload
+        .spdm_encode(&mut context.common, &mut writer)
+        .is_ok());
+    writer.used()
+}
+
+#[test]
+fn test_case0_handle_spdm_chunk_send() {
+    let (config_info, provision_info) = create_info();
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+    let shared_buffer = SharedBuffer::new();
+    let mut socket_io_transport = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    secret::asym_sign::register(SECRET_ASYM_IMPL_INSTANCE.clone());
+    let mut context = responder::ResponderContext::new(
+        &mut socket_io_transport,
+        pcidoe_transport_encap,
+        config_info,
+        provision_info,
+    );
+    setup_chunk_negotiate_info(&mut context.common);
+
+    let large_request = get_large_request();
+    let bytes = &mut [0u8; 128];
+
+    // 48 + 52 bytes
+    let used = encode_chunk_send(
+        &mut context,
+        false,
+        1,
+        0,
+        TEST_LARGE_REQUEST_SIZE as u32,
+        &large_request[..48],
+        bytes,
+    );
+    assert!(context.handle_spdm_chunk_send(None, &bytes[..used]).is_ok());
+    assert_eq!(
+        context.common.chunk_context.chunk_status,
+        SpdmChunkStatus::SpdmChunkSendInProgress
+    );
+    assert_eq!(context.common.chunk_context.chunk_handle, 1);
+    assert_eq!(context.common.chunk_context.chunk_seq_num, 1);
+    assert_eq!(context.common.chunk_context.chunk_message_offset, 48);
+    assert_eq!(
+        context.common.chunk_context.chunk_message_data[..48],
+        large_request[..48]
+    );
+
+    // The response to the reassembled request is carried by the last CHUNK_SEND_ACK.
+    let used = encode_chunk_send(&mut context, true, 1, 1, 0, &large_request[48..], bytes);
+    assert!(context.handle_spdm_chunk_send(None, &bytes[..used]).is_ok());
+    assert_eq!(
+        context.common.chunk_context.chunk_status,
+        SpdmChunkStatus::SpdmChunkIdle
+    );
+    assert_eq!(context.common.chunk_context.chunk_message_offset, 0);
+}
+
+#[test]
+fn test_case1_handle_spdm_chunk_send() {
+    let (config_info, provision_info) = create_info();
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+    let shared_buffer = SharedBuffer::new();
+    let mut socket_io_transport = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    secret::asym_sign::register(SECRET_ASYM_IMPL_INSTANCE.clone());
+    let mut context = responder::ResponderContext::new(
+        &mut socket_io_transport,
+        pcidoe_transport_encap,
+        config_info,
+        provision_info,
+    );
+    setup_chunk_negotiate_info(&mut context.common);
+
+    let large_request = get_large_request();
+    let bytes = &mut [0u8; 128];
+
+    // A chunk other than the first one without a large request in progress.
+    let used = encode_chunk_send(&mut context, true, 1, 1, 0, &large_request[48..], bytes);
+    assert!(context.handle_spdm_chunk_send(None, &bytes[..used]).is_ok());
+    assert_eq!(
+        context.common.chunk_context.chunk_status,
+        SpdmChunkStatus::SpdmChunkIdle
+    );
+
+    // An out of order ChunkSeqNo discards the large request.
+    let used = encode_chunk_send(
+        &mut context,
+        false,
+        1,
+        0,
+        TEST_LARGE_REQUEST_SIZE as u32,
+        &large_request[..48],
+        bytes,
+    );
+    assert!(context.handle_spdm_chunk_send(None, &bytes[..used]).is_ok());
+    assert_eq!(
+        context.common.chunk_context.chunk_status,
+        SpdmChunkStatus::SpdmChunkSendInProgress
+    );
+    let used = encode_chunk_send(&mut context, true, 1, 2, 0, &large_request[48..], bytes);
+    assert!(context.handle_spdm_chunk_send(None, &bytes[..used]).is_ok());
+    assert_eq!(
+        context.common.chunk_context.chunk_status,
+        SpdmChunkStatus::SpdmChunkIdle
+    );
+
+    // So does a mismatched handle.
+    let used = encode_chunk_send(
+        &mut context,
+        false,
+        1,
+        0,
+        TEST_LARGE_REQUEST_SIZE as u32,
+        &large_request[..48],
+        bytes,
+    );
+    assert!(context.handle_spdm_chunk_send(None, &bytes[..used]).is_ok());
+    let used = encode_chunk_send(&mut context, true, 2, 1, 0, &large_request[48..], bytes);
+    assert!(context.handle_spdm_chunk_send(None, &bytes[..used]).is_ok());
+    assert_eq!(
+        context.common.chunk_context.chunk_status,
+        SpdmChunkStatus::SpdmChunkIdle
+    );
+}
+
+#[test]
+fn test_case2_handle_spdm_chunk_send() {
+    let (config_info, provision_info) = create_info();
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+    let shared_buffer = SharedBuffer::new();
+    let mut socket_io_transport = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    secret::asym_sign::register(SECRET_ASYM_IMPL_INSTANCE.clone());
+    let mut context = responder::ResponderContext::new(
+        &mut socket_io_transport,
+        pcidoe_transport_encap,
+        config_info,
+        provision_info,
+    );
+    setup_chunk_negotiate_info(&mut context.common);
+
+    let large_request = get_large_request();
+    let bytes = &mut [0u8; 128];
+
+    // A LargeMessageSize above the MaxSPDMmsgSize.
+    let max_spdm_msg_size = context.common.config_info.max_spdm_msg_size;
+    let used = encode_chunk_send(
+        &mut context,
+        false,
+        1,
+        0,
+        max_spdm_msg_size + 1,
+        &large_request[..48],
+        bytes,
+    );
+    assert!(context.handle_spdm_chunk_send(None, &bytes[..used]).is_ok());
+    assert_eq!(
+        context.common.chunk_context.chunk_status,
+        SpdmChunkStatus::SpdmChunkIdle
+    );
+
+    // A chunk above the DataTransferSize.
+    let used = encode_chunk_send(
+        &mut context,
+        false,
+        1,
+        0,
+        TEST_LARGE_REQUEST_SIZE as u32,
+        &large_request[..49],
+        bytes,
+    );
+    assert!(context.handle_spdm_chunk_send(None, &bytes[..used]).is_ok());
+    assert_eq!(
+        context.common.chunk_context.chunk_status,
+        SpdmChunkStatus::SpdmChunkIdle
+    );
+
+    // A chunk beyond the LargeMessageSize.
+    let used = encode_chunk_send(&mut context, false, 1, 0, 80, &large_request[..48], bytes);
+    assert!(context.handle_spdm_chunk_send(None, &bytes[..used]).is_ok());
+    assert_eq!(
+        context.common.chunk_context.chunk_status,
+        SpdmChunkStatus::SpdmChunkSendInProgress
+    );
+    let used = encode_chunk_send(&mut context, true, 1, 1, 0, &large_request[48..], bytes);
+    assert!(context.handle_spdm_chunk_send(None, &bytes[..used]).is_ok());
+    assert_eq!(
+        context.common.chunk_context.chunk_status,
+        SpdmChunkStatus::SpdmChunkIdle
+    );
+}
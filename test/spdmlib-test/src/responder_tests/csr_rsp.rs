@@ -0,0 +1,122 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use crate::common::device_io::{FakeSpdmDeviceIoReceve, SharedBuffer};
+use crate::common::secret_callback::*;
+use crate::common::transport::PciDoeTransportEncap;
+use crate::common::util::{create_info, get_test_key_directory};
+use codec::{Codec, Reader, Writer};
+use spdmlib::common::{SpdmCodec, SpdmConnectionState, SpdmContext};
+use spdmlib::config;
+use spdmlib::message::*;
+use spdmlib::protocol::*;
+use spdmlib::{responder, secret};
+
+fn setup_csr_negotiate_info(context: &mut SpdmContext) {
+    context.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion12;
+    context.negotiate_info.base_hash_sel = SpdmBaseHashAlgo::TPM_ALG_SHA_384;
+    context.negotiate_info.base_asym_sel = SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384;
+    context.negotiate_info.rsp_capabilities_sel |= SpdmResponseCapabilityFlags::CSR_CAP;
+    context
+        .runtime_info
+        .set_connection_state(SpdmConnectionState::SpdmConnectionNegotiated);
+}
+
+fn encode_get_csr(context: &mut SpdmContext, bytes: &mut [u8]) -> usize {
+    let mut writer = Writer::init(bytes);
+    let request = SpdmMessage {
+        header: SpdmMessageHeader {
+            version: SpdmVersion::SpdmVersion12,
+            request_response_code: SpdmRequestResponseCode::SpdmRequestGetCsr,
+        },
+        payload: SpdmMessagePayload::SpdmGetCsrRequest(SpdmGetCsrRequestPayload::default()),
+    };
+    assert!(request.spdm_encode(context, &mut writer).is_ok());
+    writer.used()
+}
+
+#[test]
+fn test_case0_handle_spdm_csr() {
+    let (config_info, provision_info) = create_info();
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+    let shared_buffer = SharedBuffer::new();
+    let mut socket_io_transport = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    secret::asym_sign::register(SECRET_ASYM_IMPL_INSTANCE.clone());
+    secret::csr::register(SECRET_CSR_IMPL_INSTANCE.clone());
+    let mut context = responder::ResponderContext::new(
+        &mut socket_io_transport,
+        pcidoe_transport_encap,
+        config_info,
+        provision_info,
+    );
+    setup_csr_negotiate_info(&mut context.common);
+
+    let bytes = &mut [0u8; 1024];
+    let used = encode_get_csr(&mut context.common, bytes);
+    assert!(context.handle_spdm_csr(None, &bytes[..used]).is_ok());
+
+    let response = &mut [0u8; config::MAX_SPDM_MSG_SIZE];
+    let size = shared_buffer.get_buffer(response);
+    // Skip the PCI DOE header.
+    let mut reader = Reader::init(&response[8..size]);
+    let header = SpdmMessageHeader::read(&mut reader).unwrap();
+    assert_eq!(
+        header.request_response_code,
+        SpdmRequestResponseCode::SpdmResponseCsr
+    );
+    let csr_response = SpdmCsrResponsePayload::spdm_read(&mut context.common, &mut reader).unwrap();
+    let expected = std::fs::read(get_test_key_directory().join("test_key/test_csr/ecp384.csr"))
+        .expect("unable to read csr!");
+    assert_eq!(
+        &csr_response.csr.data[..csr_response.csr.data_size as usize],
+        expected.as_slice()
+    );
+}
+
+#[test]
+fn test_case1_handle_spdm_csr() {
+    let (config_info, provision_info) = create_info();
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+    let shared_buffer = SharedBuffer::new();
+    let mut socket_io_transport = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    secret::asym_sign::register(SECRET_ASYM_IMPL_INSTANCE.clone());
+    secret::csr::register(SECRET_CSR_IMPL_INSTANCE.clone());
+    let mut context = responder::ResponderContext::new(
+        &mut socket_io_transport,
+        pcidoe_transport_encap,
+        config_info,
+        provision_info,
+    );
+    setup_csr_negotiate_info(&mut context.common);
+    let bytes = &mut [0u8; 1024];
+    let used = encode_get_csr(&mut context.common, bytes);
+    let response = &mut [0u8; config::MAX_SPDM_MSG_SIZE];
+
+    // GET_CSR without CSR_CAP is unsupported.
+    context.common.negotiate_info.rsp_capabilities_sel -= SpdmResponseCapabilityFlags::CSR_CAP;
+    assert!(context.handle_spdm_csr(None, &bytes[..used]).is_ok());
+    let _ = shared_buffer.get_buffer(response);
+    assert_eq!(
+        response[8 + 1],
+        SpdmRequestResponseCode::SpdmResponseError.get_u8()
+    );
+    assert_eq!(
+        response[8 + 2],
+        SpdmErrorCode::SpdmErrorUnsupportedRequest.get_u8()
+    );
+
+    // The CSR generation fails for an algorithm without a test key.
+    context.common.negotiate_info.rsp_capabilities_sel |= SpdmResponseCapabilityFlags::CSR_CAP;
+    context.common.negotiate_info.base_asym_sel = SpdmBaseAsymAlgo::TPM_ALG_RSASSA_3072;
+    assert!(context.handle_spdm_csr(None, &bytes[..used]).is_ok());
+    let _ = shared_buffer.get_buffer(response);
+    assert_eq!(
+        response[8 + 1],
+        SpdmRequestResponseCode::SpdmResponseError.get_u8()
+    );
+    assert_eq!(
+        response[8 + 2],
+        SpdmErrorCode::SpdmErrorUnspecified.get_u8()
+    );
+}
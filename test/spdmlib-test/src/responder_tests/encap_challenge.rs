@@ -0,0 +1,156 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use crate::common::crypto_callback::FAKE_RAND;
+use crate::common::device_io::{FakeSpdmDeviceIoReceve, SharedBuffer};
+use crate::common::secret_callback::SECRET_ASYM_IMPL_INSTANCE;
+use crate::common::transport::PciDoeTransportEncap;
+use crate::common::util::create_info;
+use codec::{Codec, Reader, Writer};
+use spdmlib::common::SpdmCodec;
+use spdmlib::config;
+use spdmlib::error::{
+    SPDM_STATUS_ERROR_PEER, SPDM_STATUS_INVALID_MSG_FIELD, SPDM_STATUS_VERIF_FAIL,
+};
+use spdmlib::protocol::*;
+use spdmlib::responder::ResponderContext;
+use spdmlib::{crypto, message::*, requester, secret};
+
+#[test]
+fn test_encode_encap_request_challenge() {
+    let (config_info, provision_info) = create_info();
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+    let shared_buffer = SharedBuffer::new();
+    let mut socket_io_transport = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+
+    crypto::rand::register(FAKE_RAND.clone());
+
+    let mut context = ResponderContext::new(
+        &mut socket_io_transport,
+        pcidoe_transport_encap,
+        config_info,
+        provision_info,
+    );
+    context.common.reset_runtime_info();
+    context.common.negotiate_info.base_hash_sel = SpdmBaseHashAlgo::TPM_ALG_SHA_384;
+    context.common.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion12;
+    context.common.encap_context.req_slot_id = 1;
+
+    let encap_request = &mut [0u8; config::MAX_SPDM_MSG_SIZE];
+    let mut writer = Writer::init(encap_request);
+    assert!(context.encode_encap_request_challenge(&mut writer).is_ok());
+    assert_eq!(writer.used(), 4 + SPDM_NONCE_SIZE);
+
+    let mut reader = Reader::init(writer.used_slice());
+    let header = SpdmMessageHeader::read(&mut reader).unwrap();
+    assert_eq!(header.version, SpdmVersion::SpdmVersion12);
+    assert_eq!(
+        header.request_response_code,
+        SpdmRequestResponseCode::SpdmRequestChallenge
+    );
+    let challenge =
+        SpdmChallengeRequestPayload::spdm_read(&mut context.common, &mut reader).unwrap();
+    assert_eq!(challenge.slot_id, 1);
+    assert_eq!(
+        challenge.measurement_summary_hash_type,
+        SpdmMeasurementSummaryHashType::SpdmMeasurementSummaryHashTypeNone
+    );
+}
+
+#[test]
+fn test_handle_encap_response_challenge_auth() {
+    let (rsp_config_info, rsp_provision_info) = create_info();
+    let (req_config_info, req_provision_info) = create_info();
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+    let pcidoe_transport_encap2 = &mut PciDoeTransportEncap {};
+    let shared_buffer = SharedBuffer::new();
+    let mut socket_io_transport = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    let mut socket_io_transport2 = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+
+    secret::asym_sign::register(SECRET_ASYM_IMPL_INSTANCE.clone());
+    crypto::rand::register(FAKE_RAND.clone());
+
+    let mut requester = requester::RequesterContext::new(
+        &mut socket_io_transport2,
+        pcidoe_transport_encap2,
+        req_config_info,
+        req_provision_info,
+    );
+    requester.common.reset_runtime_info();
+    requester.common.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion12;
+    requester.common.negotiate_info.base_hash_sel = SpdmBaseHashAlgo::TPM_ALG_SHA_384;
+    requester.common.negotiate_info.base_asym_sel = SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384;
+    requester.common.negotiate_info.req_capabilities_sel |= SpdmRequestCapabilityFlags::CHAL_CAP;
+    assert!(requester.common.construct_my_cert_chain().is_ok());
+
+    let mut context = ResponderContext::new(
+        &mut socket_io_transport,
+        pcidoe_transport_encap,
+        rsp_config_info,
+        rsp_provision_info,
+    );
+    context.common.reset_runtime_info();
+    context.common.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion12;
+    context.common.negotiate_info.base_hash_sel = SpdmBaseHashAlgo::TPM_ALG_SHA_384;
+    context.common.negotiate_info.base_asym_sel = SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384;
+    context.common.encap_context.req_slot_id = 0;
+    context.common.peer_info.peer_cert_chain[0] =
+        requester.common.provision_info.my_cert_chain[0].clone();
+
+    let encap_request = &mut [0u8; config::MAX_SPDM_MSG_SIZE];
+    let encap_response = &mut [0u8; config::MAX_SPDM_MSG_SIZE];
+
+    // A CHALLENGE_AUTH signed by the requester.
+    let mut writer = Writer::init(encap_request);
+    assert!(context.encode_encap_request_challenge(&mut writer).is_ok());
+    let request_size = writer.used();
+    let mut writer = Writer::init(encap_response);
+    requester.encap_handle_challenge(&encap_request[..request_size], &mut writer);
+    let response_size = writer.used();
+    assert!(context
+        .handle_encap_response_challenge_auth(&encap_response[..response_size])
+        .is_ok());
+
+    // A CHALLENGE_AUTH with a corrupted signature.
+    requester.common.reset_runtime_info();
+    let mut writer = Writer::init(encap_request);
+    assert!(context.encode_encap_request_challenge(&mut writer).is_ok());
+    let request_size = writer.used();
+    let mut writer = Writer::init(encap_response);
+    requester.encap_handle_challenge(&encap_request[..request_size], &mut writer);
+    let response_size = writer.used();
+    encap_response[response_size - 1] ^= 0xff;
+    assert_eq!(
+        context.handle_encap_response_challenge_auth(&encap_response[..response_size]),
+        Err(SPDM_STATUS_VERIF_FAIL)
+    );
+
+    // A CHALLENGE_AUTH for another slot.
+    requester.common.reset_runtime_info();
+    context.common.encap_context.req_slot_id = 1;
+    let mut writer = Writer::init(encap_request);
+    assert!(context.encode_encap_request_challenge(&mut writer).is_ok());
+    let request_size = writer.used();
+    encap_request[2] = 0;
+    let mut writer = Writer::init(encap_response);
+    requester.encap_handle_challenge(&encap_request[..request_size], &mut writer);
+    let response_size = writer.used();
+    assert_eq!(
+        context.handle_encap_response_challenge_auth(&encap_response[..response_size]),
+        Err(SPDM_STATUS_INVALID_MSG_FIELD)
+    );
+
+    // A response other than CHALLENGE_AUTH.
+    let mut writer = Writer::init(encap_response);
+    let digests_rsp = SpdmMessageHeader {
+        version: SpdmVersion::SpdmVersion12,
+        request_response_code: SpdmRequestResponseCode::SpdmResponseDigests,
+    };
+    assert!(digests_rsp.encode(&mut writer).is_ok());
+    let response_size = writer.used();
+    assert_eq!(
+        context.handle_encap_response_challenge_auth(&encap_response[..response_size]),
+        Err(SPDM_STATUS_ERROR_PEER)
+    );
+}
@@ -0,0 +1,297 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use crate::common::device_io::{FakeSpdmDeviceIoReceve, SharedBuffer};
+use crate::common::secret_callback::SECRET_ASYM_IMPL_INSTANCE;
+use crate::common::transport::PciDoeTransportEncap;
+use crate::common::util::create_info;
+use codec::{Codec, Reader, Writer};
+use spdmlib::common::session::{SpdmSession, SpdmSessionState};
+use spdmlib::common::{SpdmCodec, SpdmConnectionState, SpdmContext};
+use spdmlib::config;
+use spdmlib::error::{
+    SPDM_STATUS_INVALID_MSG_FIELD, SPDM_STATUS_INVALID_STATE_LOCAL, SPDM_STATUS_UNSUPPORTED_CAP,
+};
+use spdmlib::protocol::*;
+use spdmlib::responder::ResponderContext;
+use spdmlib::{message::*, secret};
+
+const SESSION_ID: u32 = 0xffu32 << 16 | 0xFFFE;
+
+fn setup_session(common: &mut SpdmContext) {
+    common.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion12;
+    common.negotiate_info.base_hash_sel = SpdmBaseHashAlgo::TPM_ALG_SHA_384;
+    common.negotiate_info.req_capabilities_sel = common.config_info.req_capabilities;
+    common.negotiate_info.rsp_capabilities_sel = common.config_info.rsp_capabilities;
+    common
+        .runtime_info
+        .set_connection_state(SpdmConnectionState::SpdmConnectionAuthenticated);
+
+    common.session = gen_array_clone(SpdmSession::new(), 4);
+    common.session[0].setup(SESSION_ID).unwrap();
+    common.session[0].set_crypto_param(
+        SpdmBaseHashAlgo::TPM_ALG_SHA_384,
+        SpdmDheAlgo::SECP_384_R1,
+        SpdmAeadAlgo::AES_256_GCM,
+        SpdmKeyScheduleAlgo::SPDM_KEY_SCHEDULE,
+    );
+    common.session[0].set_session_state(SpdmSessionState::SpdmSessionEstablished);
+    let dhe_secret = SpdmDheFinalKeyStruct {
+        data_size: 48,
+        data: Box::new([0; SPDM_MAX_DHE_KEY_SIZE]),
+    };
+    let _ = common.session[0].set_dhe_secret(SpdmVersion::SpdmVersion12, dhe_secret);
+    let _ = common.session[0].generate_handshake_secret(
+        SpdmVersion::SpdmVersion12,
+        &SpdmDigestStruct {
+            data_size: 48,
+            data: Box::new([0; SPDM_MAX_HASH_SIZE]),
+        },
+    );
+    let _ = common.session[0].generate_data_secret(
+        SpdmVersion::SpdmVersion12,
+        &SpdmDigestStruct {
+            data_size: 48,
+            data: Box::new([0; SPDM_MAX_HASH_SIZE]),
+        },
+    );
+}
+
+// The request and response direction encryption keys of the session.
+fn get_session_keys(context: &mut ResponderContext) -> (Vec<u8>, Vec<u8>) {
+    let session = context.common.get_session_via_id(SESSION_ID).unwrap();
+    let (request_direction, response_direction) = session.export_keys();
+    (
+        request_direction.encryption_key.as_ref().to_vec(),
+        response_direction.encryption_key.as_ref().to_vec(),
+    )
+}
+
+fn encode_key_update_ack(
+    context: &mut ResponderContext,
+    key_update_operation: SpdmKeyUpdateOperation,
+    tag: u8,
+    encap_response: &mut [u8],
+) -> usize {
+    let mut writer = Writer::init(encap_response);
+    let key_update_ack = SpdmMessage {
+        header: SpdmMessageHeader {
+            version: SpdmVersion::SpdmVersion12,
+            request_response_code: SpdmRequestResponseCode::SpdmResponseKeyUpdateAck,
+        },
+        payload: SpdmMessagePayload::SpdmKeyUpdateResponse(SpdmKeyUpdateResponsePayload {
+            key_update_operation,
+            tag,
+        }),
+    };
+    assert!(key_update_ack
+        .spdm_encode(&mut context.common, &mut writer)
+        .is_ok());
+    writer.used()
+}
+
+#[test]
+fn test_case0_init_encap_key_update() {
+    let (config_info, provision_info) = create_info();
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+    let shared_buffer = SharedBuffer::new();
+    let mut socket_io_transport = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+
+    let mut context = ResponderContext::new(
+        &mut socket_io_transport,
+        pcidoe_transport_encap,
+        config_info,
+        provision_info,
+    );
+    setup_session(&mut context.common);
+
+    context.common.negotiate_info.req_capabilities_sel -= SpdmRequestCapabilityFlags::KEY_UPD_CAP;
+    assert_eq!(
+        context.init_encap_key_update(SpdmKeyUpdateOperation::SpdmUpdateAllKeys),
+        Err(SPDM_STATUS_UNSUPPORTED_CAP)
+    );
+    assert!(context.common.encap_context.key_update_op.is_none());
+
+    context.common.negotiate_info.req_capabilities_sel |= SpdmRequestCapabilityFlags::KEY_UPD_CAP;
+    assert!(context
+        .init_encap_key_update(SpdmKeyUpdateOperation::SpdmUpdateSingleKey)
+        .is_ok());
+    assert_eq!(
+        context.common.encap_context.key_update_op,
+        Some(SpdmKeyUpdateOperation::SpdmUpdateSingleKey)
+    );
+}
+
+#[test]
+fn test_case1_encap_key_update() {
+    let (config_info, provision_info) = create_info();
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+    let shared_buffer = SharedBuffer::new();
+    let mut socket_io_transport = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+
+    secret::asym_sign::register(SECRET_ASYM_IMPL_INSTANCE.clone());
+
+    let mut context = ResponderContext::new(
+        &mut socket_io_transport,
+        pcidoe_transport_encap,
+        config_info,
+        provision_info,
+    );
+    setup_session(&mut context.common);
+    let (old_request_key, old_response_key) = get_session_keys(&mut context);
+
+    assert!(context
+        .init_encap_key_update(SpdmKeyUpdateOperation::SpdmUpdateAllKeys)
+        .is_ok());
+
+    let encap_request = &mut [0u8; config::MAX_SPDM_MSG_SIZE];
+    let mut writer = Writer::init(encap_request);
+    assert!(context.encode_encap_request_key_update(&mut writer).is_ok());
+    let mut reader = Reader::init(writer.used_slice());
+    let header = SpdmMessageHeader::read(&mut reader).unwrap();
+    assert_eq!(
+        header.request_response_code,
+        SpdmRequestResponseCode::SpdmRequestKeyUpdate
+    );
+    let key_update =
+        SpdmKeyUpdateRequestPayload::spdm_read(&mut context.common, &mut reader).unwrap();
+    assert_eq!(
+        key_update.key_update_operation,
+        SpdmKeyUpdateOperation::SpdmUpdateAllKeys
+    );
+    assert_eq!(key_update.tag, 1);
+    assert!(context.common.encap_context.key_update_pending);
+
+    // The KEY_UPDATE is sent with the old keys, then both keys are updated.
+    assert!(context.create_encap_key_update(SESSION_ID).is_ok());
+    assert!(!context.common.encap_context.key_update_pending);
+    let (new_request_key, new_response_key) = get_session_keys(&mut context);
+    assert_ne!(new_request_key, old_request_key);
+    assert_ne!(new_response_key, old_response_key);
+
+    let encap_response = &mut [0u8; config::MAX_SPDM_MSG_SIZE];
+    let used = encode_key_update_ack(
+        &mut context,
+        SpdmKeyUpdateOperation::SpdmUpdateAllKeys,
+        1,
+        encap_response,
+    );
+    assert_eq!(
+        context.handle_encap_response_key_update(SESSION_ID, &encap_response[..used]),
+        Ok(true)
+    );
+    assert_eq!(
+        context.common.encap_context.key_update_op,
+        Some(SpdmKeyUpdateOperation::SpdmVerifyNewKey)
+    );
+    assert_eq!(
+        get_session_keys(&mut context),
+        (new_request_key.clone(), new_response_key.clone())
+    );
+
+    // VerifyNewKey is sent with the new keys and does not update them.
+    let mut writer = Writer::init(encap_request);
+    assert!(context.encode_encap_request_key_update(&mut writer).is_ok());
+    let mut reader = Reader::init(writer.used_slice());
+    let _ = SpdmMessageHeader::read(&mut reader).unwrap();
+    let key_update =
+        SpdmKeyUpdateRequestPayload::spdm_read(&mut context.common, &mut reader).unwrap();
+    assert_eq!(
+        key_update.key_update_operation,
+        SpdmKeyUpdateOperation::SpdmVerifyNewKey
+    );
+    assert_eq!(key_update.tag, 2);
+    assert!(!context.common.encap_context.key_update_pending);
+
+    let used = encode_key_update_ack(
+        &mut context,
+        SpdmKeyUpdateOperation::SpdmVerifyNewKey,
+        2,
+        encap_response,
+    );
+    assert_eq!(
+        context.handle_encap_response_key_update(SESSION_ID, &encap_response[..used]),
+        Ok(false)
+    );
+    assert!(context.common.encap_context.key_update_op.is_none());
+    assert_eq!(
+        get_session_keys(&mut context),
+        (new_request_key, new_response_key)
+    );
+}
+
+#[test]
+fn test_case2_encap_key_update_mismatched_response() {
+    let (config_info, provision_info) = create_info();
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+    let shared_buffer = SharedBuffer::new();
+    let mut socket_io_transport = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+
+    secret::asym_sign::register(SECRET_ASYM_IMPL_INSTANCE.clone());
+
+    let mut context = ResponderContext::new(
+        &mut socket_io_transport,
+        pcidoe_transport_encap,
+        config_info,
+        provision_info,
+    );
+    setup_session(&mut context.common);
+    let old_keys = get_session_keys(&mut context);
+
+    // No key update in progress.
+    let encap_request = &mut [0u8; config::MAX_SPDM_MSG_SIZE];
+    let encap_response = &mut [0u8; config::MAX_SPDM_MSG_SIZE];
+    let mut writer = Writer::init(encap_request);
+    assert_eq!(
+        context.encode_encap_request_key_update(&mut writer),
+        Err(SPDM_STATUS_INVALID_STATE_LOCAL)
+    );
+    let used = encode_key_update_ack(
+        &mut context,
+        SpdmKeyUpdateOperation::SpdmUpdateSingleKey,
+        1,
+        encap_response,
+    );
+    assert_eq!(
+        context.handle_encap_response_key_update(SESSION_ID, &encap_response[..used]),
+        Err(SPDM_STATUS_INVALID_STATE_LOCAL)
+    );
+
+    // An acknowledgement of another operation, another tag or another
+    // response rolls the keys back.
+    for (key_update_operation, tag, request_response_code) in [
+        (
+            SpdmKeyUpdateOperation::SpdmUpdateAllKeys,
+            1,
+            SpdmRequestResponseCode::SpdmResponseKeyUpdateAck,
+        ),
+        (
+            SpdmKeyUpdateOperation::SpdmUpdateSingleKey,
+            2,
+            SpdmRequestResponseCode::SpdmResponseKeyUpdateAck,
+        ),
+        (
+            SpdmKeyUpdateOperation::SpdmUpdateSingleKey,
+            1,
+            SpdmRequestResponseCode::SpdmResponseHeartbeatAck,
+        ),
+    ] {
+        assert!(context
+            .init_encap_key_update(SpdmKeyUpdateOperation::SpdmUpdateSingleKey)
+            .is_ok());
+        let mut writer = Writer::init(encap_request);
+        assert!(context.encode_encap_request_key_update(&mut writer).is_ok());
+        assert!(context.create_encap_key_update(SESSION_ID).is_ok());
+        assert_ne!(get_session_keys(&mut context), old_keys);
+
+        let used = encode_key_update_ack(&mut context, key_update_operation, tag, encap_response);
+        encap_response[1] = request_response_code.get_u8();
+        assert_eq!(
+            context.handle_encap_response_key_update(SESSION_ID, &encap_response[..used]),
+            Err(SPDM_STATUS_INVALID_MSG_FIELD)
+        );
+        assert!(context.common.encap_context.key_update_op.is_none());
+        assert_eq!(get_session_keys(&mut context), old_keys);
+    }
+}
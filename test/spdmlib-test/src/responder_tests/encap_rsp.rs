@@ -50,7 +50,7 @@ fn test_handle_get_encapsulated_request() {
         .is_ok());
 
     assert!(context
-        .handle_get_encapsulated_request(SESSION_ID, writer.used_slice())
+        .handle_get_encapsulated_request(Some(SESSION_ID), writer.used_slice())
         .is_ok());
 
     let receive = &mut [0u8; config::RECEIVER_BUFFER_SIZE];
@@ -114,7 +114,7 @@ fn test_handle_deliver_encapsulated_reponse_digest() {
     assert!(write_spdm_get_digest_response(&mut context, &mut writer).is_ok());
 
     assert!(context
-        .handle_deliver_encapsulated_reponse(SESSION_ID, request)
+        .handle_deliver_encapsulated_reponse(Some(SESSION_ID), request)
         .is_ok());
 
     // Get data sent by responder and decode the secured message
@@ -181,7 +181,7 @@ fn test_handle_deliver_encapsulated_reponse_cert() {
     assert!(write_spdm_get_certificate_response(&mut context, &mut writer).is_ok());
 
     assert!(context
-        .handle_deliver_encapsulated_reponse(SESSION_ID, request)
+        .handle_deliver_encapsulated_reponse(Some(SESSION_ID), request)
         .is_ok());
 
     let mut receive = [0u8; config::RECEIVER_BUFFER_SIZE];
@@ -272,6 +272,7 @@ fn write_spdm_get_digest_response(
                 },
                 SPDM_MAX_SLOT_NUMBER,
             ),
+            ..Default::default()
         }),
     };
     let _ = response
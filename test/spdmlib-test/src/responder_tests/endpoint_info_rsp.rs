@@ -0,0 +1,179 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use crate::common::device_io::{FakeSpdmDeviceIoReceve, SharedBuffer};
+use crate::common::secret_callback::*;
+use crate::common::transport::PciDoeTransportEncap;
+use crate::common::util::create_info;
+use codec::{Codec, Reader, Writer};
+use spdmlib::common::{SpdmCodec, SpdmConnectionState, SpdmContext};
+use spdmlib::message::*;
+use spdmlib::protocol::*;
+use spdmlib::{config, responder, secret};
+
+fn setup_endpoint_info_negotiate_info(context: &mut SpdmContext) {
+    context.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion13;
+    context.negotiate_info.rsp_capabilities_sel |=
+        SpdmResponseCapabilityFlags::CERT_CAP | SpdmResponseCapabilityFlags::EP_INFO_CAP_SIG;
+    context.negotiate_info.base_hash_sel = SpdmBaseHashAlgo::TPM_ALG_SHA_384;
+    context.negotiate_info.base_asym_sel = SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384;
+    context
+        .runtime_info
+        .set_connection_state(SpdmConnectionState::SpdmConnectionNegotiated);
+}
+
+fn encode_get_endpoint_info(
+    context: &mut SpdmContext,
+    request_attributes: SpdmEndpointInfoRequestAttributes,
+    bytes: &mut [u8],
+) -> usize {
+    let request = SpdmMessage {
+        header: SpdmMessageHeader {
+            version: SpdmVersion::SpdmVersion13,
+            request_response_code: SpdmRequestResponseCode::SpdmRequestGetEndpointInfo,
+        },
+        payload: SpdmMessagePayload::SpdmGetEndpointInfoRequest(
+            SpdmGetEndpointInfoRequestPayload {
+                sub_code: SpdmEndpointInfoSubCode::SpdmEndpointInfoSubCodeDeviceClassIdentifier,
+                request_attributes,
+                slot_id: 0,
+                nonce: SpdmNonceStruct {
+                    data: [0x5a; SPDM_NONCE_SIZE],
+                },
+            },
+        ),
+    };
+    let mut writer = Writer::init(bytes);
+    assert!(request.spdm_encode(context, &mut writer).is_ok());
+    writer.used()
+}
+
+#[test]
+fn test_case0_handle_spdm_endpoint_info() {
+    let (config_info, provision_info) = create_info();
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+    let shared_buffer = SharedBuffer::new();
+    let mut socket_io_transport = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    secret::asym_sign::register(SECRET_ASYM_IMPL_INSTANCE.clone());
+    secret::endpoint_info::register(SECRET_ENDPOINT_INFO_IMPL_INSTANCE.clone());
+    let mut context = responder::ResponderContext::new(
+        &mut socket_io_transport,
+        pcidoe_transport_encap,
+        config_info,
+        provision_info,
+    );
+    setup_endpoint_info_negotiate_info(&mut context.common);
+
+    let bytes = &mut [0u8; 64];
+    let used = encode_get_endpoint_info(
+        &mut context.common,
+        SpdmEndpointInfoRequestAttributes::empty(),
+        bytes,
+    );
+    assert!(context
+        .handle_spdm_endpoint_info(None, &bytes[..used])
+        .is_ok());
+
+    let mut response = [0u8; 1024];
+    let size = shared_buffer.get_buffer(&mut response);
+    // Skip the PCI DOE header.
+    let mut reader = Reader::init(&response[8..size]);
+    let header = SpdmMessageHeader::read(&mut reader).unwrap();
+    assert_eq!(
+        header.request_response_code,
+        SpdmRequestResponseCode::SpdmResponseEndpointInfo
+    );
+    let endpoint_info =
+        SpdmEndpointInfoResponsePayload::spdm_read(&mut context.common, &mut reader).unwrap();
+    assert_eq!(endpoint_info.endpoint_info.as_ref(), TEST_ENDPOINT_INFO);
+}
+
+#[test]
+fn test_case1_handle_spdm_endpoint_info() {
+    let (config_info, mut provision_info) = create_info();
+    provision_info.my_cert_chain[0] = Some(SpdmCertChainBuffer {
+        data_size: 512u16,
+        data: [0u8; 4 + SPDM_MAX_HASH_SIZE + config::MAX_SPDM_CERT_CHAIN_DATA_SIZE],
+    });
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+    let shared_buffer = SharedBuffer::new();
+    let mut socket_io_transport = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    secret::asym_sign::register(SECRET_ASYM_IMPL_INSTANCE.clone());
+    secret::endpoint_info::register(SECRET_ENDPOINT_INFO_IMPL_INSTANCE.clone());
+    let mut context = responder::ResponderContext::new(
+        &mut socket_io_transport,
+        pcidoe_transport_encap,
+        config_info,
+        provision_info,
+    );
+    setup_endpoint_info_negotiate_info(&mut context.common);
+
+    let bytes = &mut [0u8; 64];
+    let used = encode_get_endpoint_info(
+        &mut context.common,
+        SpdmEndpointInfoRequestAttributes::SIGNATURE_REQUESTED,
+        bytes,
+    );
+    assert!(context
+        .handle_spdm_endpoint_info(None, &bytes[..used])
+        .is_ok());
+
+    let mut response = [0u8; 1024];
+    let size = shared_buffer.get_buffer(&mut response);
+    let mut reader = Reader::init(&response[8..size]);
+    let header = SpdmMessageHeader::read(&mut reader).unwrap();
+    assert_eq!(
+        header.request_response_code,
+        SpdmRequestResponseCode::SpdmResponseEndpointInfo
+    );
+    let endpoint_info =
+        SpdmEndpointInfoResponsePayload::spdm_read(&mut context.common, &mut reader).unwrap();
+    assert_eq!(endpoint_info.slot_id, 0);
+    assert_eq!(endpoint_info.endpoint_info.as_ref(), TEST_ENDPOINT_INFO);
+    assert_eq!(
+        endpoint_info.signature.data_size,
+        SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384.get_size()
+    );
+}
+
+#[test]
+fn test_case2_handle_spdm_endpoint_info() {
+    let (config_info, provision_info) = create_info();
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+    let shared_buffer = SharedBuffer::new();
+    let mut socket_io_transport = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    secret::asym_sign::register(SECRET_ASYM_IMPL_INSTANCE.clone());
+    secret::endpoint_info::register(SECRET_ENDPOINT_INFO_IMPL_INSTANCE.clone());
+    let mut context = responder::ResponderContext::new(
+        &mut socket_io_transport,
+        pcidoe_transport_encap,
+        config_info,
+        provision_info,
+    );
+    setup_endpoint_info_negotiate_info(&mut context.common);
+
+    // No certificate chain is provisioned in slot 0, the signature can't be generated.
+    let bytes = &mut [0u8; 64];
+    let used = encode_get_endpoint_info(
+        &mut context.common,
+        SpdmEndpointInfoRequestAttributes::SIGNATURE_REQUESTED,
+        bytes,
+    );
+    assert!(context
+        .handle_spdm_endpoint_info(None, &bytes[..used])
+        .is_ok());
+
+    let mut response = [0u8; 1024];
+    let size = shared_buffer.get_buffer(&mut response);
+    let mut reader = Reader::init(&response[8..size]);
+    let header = SpdmMessageHeader::read(&mut reader).unwrap();
+    assert_eq!(
+        header.request_response_code,
+        SpdmRequestResponseCode::SpdmResponseError
+    );
+    assert_eq!(
+        u8::read(&mut reader),
+        Some(SpdmErrorCode::SpdmErrorInvalidRequest.get_u8())
+    );
+}
@@ -6,9 +6,18 @@ use crate::common::device_io::{FakeSpdmDeviceIoReceve, SharedBuffer};
 use crate::common::secret_callback::*;
 use crate::common::transport::PciDoeTransportEncap;
 use crate::common::util::create_info;
+use codec::{Codec, Reader, Writer};
+use spdmlib::common::{
+    ManagedBufferA, SpdmCachedNegotiateState, SpdmConfigInfo, SpdmConnectionState,
+    SpdmNegotiateInfo,
+};
+use spdmlib::error::SPDM_STATUS_NEGOTIATION_FAIL;
 use spdmlib::message::*;
 use spdmlib::protocol::*;
-use spdmlib::{responder, secret};
+use spdmlib::{config, responder, secret};
+
+const GET_VERSION: [u8; 4] = [0x10, 0x84, 0x00, 0x00];
+const GET_DIGESTS: [u8; 4] = [0x12, 0x81, 0x00, 0x00];
 
 #[test]
 fn test_case0_send_spdm_error() {
@@ -28,3 +37,188 @@ fn test_case0_send_spdm_error() {
 
     context.send_spdm_error(SpdmErrorCode::SpdmErrorInvalidRequest, 0);
 }
+
+fn get_cached_negotiate_state(config_info: &SpdmConfigInfo) -> SpdmCachedNegotiateState {
+    let mut message_a = ManagedBufferA::default();
+    assert!(message_a
+        .append_message(&[0x10, 0x84, 0x00, 0x00])
+        .is_some());
+    SpdmCachedNegotiateState {
+        negotiate_info: SpdmNegotiateInfo {
+            spdm_version_sel: SpdmVersion::SpdmVersion12,
+            req_capabilities_sel: config_info.req_capabilities,
+            rsp_capabilities_sel: config_info.rsp_capabilities,
+            measurement_specification_sel: config_info.measurement_specification,
+            measurement_hash_sel: config_info.measurement_hash_algo,
+            base_hash_sel: config_info.base_hash_algo,
+            base_asym_sel: config_info.base_asym_algo,
+            dhe_sel: config_info.dhe_algo,
+            aead_sel: config_info.aead_algo,
+            req_asym_sel: config_info.req_asym_algo,
+            key_schedule_sel: config_info.key_schedule_algo,
+            ..Default::default()
+        },
+        message_a,
+    }
+}
+
+// Dispatch a request and return the response code and the first parameter.
+fn dispatch_request(
+    context: &mut responder::ResponderContext,
+    shared_buffer: &SharedBuffer,
+    request: &[u8],
+) -> (SpdmVersion, SpdmRequestResponseCode, u8) {
+    assert!(context.dispatch_message(request).is_ok());
+    let mut response = [0u8; config::MAX_SPDM_MSG_SIZE];
+    let size = shared_buffer.get_buffer(&mut response);
+    // Skip the PCI DOE header.
+    let mut reader = Reader::init(&response[8..size]);
+    let header = SpdmMessageHeader::read(&mut reader).unwrap();
+    let param1 = u8::read(&mut reader).unwrap();
+    (header.version, header.request_response_code, param1)
+}
+
+#[test]
+fn test_case1_handle_spdm_resynch() {
+    let (mut config_info, provision_info) = create_info();
+    config_info.rsp_capabilities |= SpdmResponseCapabilityFlags::CACHE_CAP;
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+    let shared_buffer = SharedBuffer::new();
+    let mut socket_io_transport = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    let mut context = responder::ResponderContext::new(
+        &mut socket_io_transport,
+        pcidoe_transport_encap,
+        config_info,
+        provision_info,
+    );
+
+    let mut response = [0u8; config::MAX_SPDM_MSG_SIZE];
+    for (version, expected_version) in [
+        (SpdmVersion::SpdmVersion11, 0x11),
+        // Not supported, the previous version is kept.
+        (SpdmVersion::SpdmVersion14, 0x11),
+        (SpdmVersion::SpdmVersion13, 0x13),
+    ] {
+        assert!(context.handle_spdm_resynch(version).is_ok());
+        let size = shared_buffer.get_buffer(&mut response);
+        assert_eq!(
+            &response[8..size],
+            &[
+                expected_version,
+                SpdmRequestResponseCode::SpdmResponseError.get_u8(),
+                SpdmErrorCode::SpdmErrorRequestResynch.get_u8(),
+                0
+            ]
+        );
+    }
+}
+
+#[test]
+fn test_case2_is_resynch_required() {
+    let (mut config_info, provision_info) = create_info();
+    config_info.rsp_capabilities |= SpdmResponseCapabilityFlags::CACHE_CAP;
+    let cached_state = get_cached_negotiate_state(&config_info);
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+    let shared_buffer = SharedBuffer::new();
+    let mut socket_io_transport = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    secret::asym_sign::register(SECRET_ASYM_IMPL_INSTANCE.clone());
+    let mut context = responder::ResponderContext::new(
+        &mut socket_io_transport,
+        pcidoe_transport_encap,
+        config_info,
+        provision_info,
+    );
+
+    // No negotiated state, e.g. after a reset.
+    assert_eq!(
+        dispatch_request(&mut context, &shared_buffer, &GET_DIGESTS),
+        (
+            SpdmVersion::SpdmVersion12,
+            SpdmRequestResponseCode::SpdmResponseError,
+            SpdmErrorCode::SpdmErrorRequestResynch.get_u8()
+        )
+    );
+    assert_eq!(
+        context.common.runtime_info.get_connection_state(),
+        SpdmConnectionState::SpdmConnectionNotStarted
+    );
+
+    // The negotiation is not refused.
+    let (_, response_code, _) = dispatch_request(&mut context, &shared_buffer, &GET_VERSION);
+    assert_eq!(response_code, SpdmRequestResponseCode::SpdmResponseVersion);
+
+    // The cached state is restored.
+    assert!(context.init_connection_with_cache(&cached_state).is_ok());
+    let (_, response_code, _) = dispatch_request(&mut context, &shared_buffer, &GET_DIGESTS);
+    assert_eq!(response_code, SpdmRequestResponseCode::SpdmResponseDigests);
+
+    // Without CACHE_CAP, the request is only unexpected.
+    context.common.config_info.rsp_capabilities -= SpdmResponseCapabilityFlags::CACHE_CAP;
+    context.common.reset_runtime_info();
+    assert_eq!(
+        dispatch_request(&mut context, &shared_buffer, &GET_DIGESTS),
+        (
+            SpdmVersion::SpdmVersion12,
+            SpdmRequestResponseCode::SpdmResponseError,
+            SpdmErrorCode::SpdmErrorUnexpectedRequest.get_u8()
+        )
+    );
+}
+
+#[test]
+fn test_case3_init_connection_with_corrupted_cache() {
+    let (mut config_info, provision_info) = create_info();
+    config_info.rsp_capabilities |= SpdmResponseCapabilityFlags::CACHE_CAP;
+    let cached_state = get_cached_negotiate_state(&config_info);
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+    let shared_buffer = SharedBuffer::new();
+    let mut socket_io_transport = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    let mut context = responder::ResponderContext::new(
+        &mut socket_io_transport,
+        pcidoe_transport_encap,
+        config_info,
+        provision_info,
+    );
+
+    // A truncated state or a message A longer than the state cannot be read.
+    let buffer = &mut [0u8; 1024];
+    let mut writer = Writer::init(buffer);
+    assert!(cached_state.encode(&mut writer).is_ok());
+    let used = writer.used();
+    let message_a_size = cached_state.message_a.as_ref().len();
+    let mut reader = Reader::init(&buffer[..used - 1]);
+    assert!(SpdmCachedNegotiateState::read(&mut reader).is_none());
+    buffer[used - message_a_size - 2] += 1;
+    let mut reader = Reader::init(&buffer[..used]);
+    assert!(SpdmCachedNegotiateState::read(&mut reader).is_none());
+
+    // A state which does not match the configuration is not restored.
+    let mut wrong_version = cached_state.clone();
+    wrong_version.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion14;
+    let mut wrong_capabilities = cached_state.clone();
+    wrong_capabilities.negotiate_info.rsp_capabilities_sel -= SpdmResponseCapabilityFlags::CHAL_CAP;
+    let mut wrong_hash = cached_state.clone();
+    wrong_hash.negotiate_info.base_hash_sel = SpdmBaseHashAlgo::TPM_ALG_SHA_512;
+    let mut wrong_dhe = cached_state.clone();
+    wrong_dhe.negotiate_info.dhe_sel = SpdmDheAlgo::SECP_256_R1;
+    let mut empty_message_a = cached_state;
+    empty_message_a.message_a.reset_message();
+    for corrupted_state in [
+        wrong_version,
+        wrong_capabilities,
+        wrong_hash,
+        wrong_dhe,
+        empty_message_a,
+    ] {
+        assert_eq!(
+            context.init_connection_with_cache(&corrupted_state),
+            Err(SPDM_STATUS_NEGOTIATION_FAIL)
+        );
+        assert_eq!(
+            context.common.runtime_info.get_connection_state(),
+            SpdmConnectionState::SpdmConnectionNotStarted
+        );
+        assert!(context.common.negotiate_info.base_hash_sel.is_empty());
+        assert!(context.common.runtime_info.message_a.as_ref().is_empty());
+    }
+}
@@ -0,0 +1,300 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use crate::common::device_io::{FakeSpdmDeviceIoReceve, SharedBuffer};
+use crate::common::secret_callback::*;
+use crate::common::transport::PciDoeTransportEncap;
+use crate::common::util::create_info;
+use codec::{Codec, Reader, Writer};
+use spdmlib::common::session::{SpdmSession, SpdmSessionState};
+use spdmlib::common::SpdmCodec;
+use spdmlib::message::*;
+use spdmlib::protocol::*;
+use spdmlib::{responder, secret};
+
+fn setup_established_session(session: &mut SpdmSession, session_id: u32) {
+    session.setup(session_id).unwrap();
+    session.set_crypto_param(
+        SpdmBaseHashAlgo::TPM_ALG_SHA_384,
+        SpdmDheAlgo::SECP_384_R1,
+        SpdmAeadAlgo::AES_256_GCM,
+        SpdmKeyScheduleAlgo::SPDM_KEY_SCHEDULE,
+    );
+    assert!(session
+        .set_dhe_secret(
+            SpdmVersion::SpdmVersion13,
+            SpdmDheFinalKeyStruct {
+                data_size: 5,
+                data: Box::new([100u8; SPDM_MAX_DHE_KEY_SIZE])
+            }
+        )
+        .is_ok());
+    assert!(session
+        .generate_handshake_secret(
+            SpdmVersion::SpdmVersion13,
+            &SpdmDigestStruct {
+                data_size: 5,
+                data: Box::new([100u8; SPDM_MAX_HASH_SIZE])
+            }
+        )
+        .is_ok());
+    assert!(session
+        .generate_data_secret(
+            SpdmVersion::SpdmVersion13,
+            &SpdmDigestStruct {
+                data_size: 5,
+                data: Box::new([100u8; SPDM_MAX_HASH_SIZE])
+            }
+        )
+        .is_ok());
+    session.set_session_state(SpdmSessionState::SpdmSessionEstablished);
+}
+
+#[test]
+fn test_case0_handle_spdm_subscribe_and_send_event() {
+    let (config_info, provision_info) = create_info();
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+    let shared_buffer = SharedBuffer::new();
+    let mut socket_io_transport = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    secret::asym_sign::register(SECRET_ASYM_IMPL_INSTANCE.clone());
+    let mut context = responder::ResponderContext::new(
+        &mut socket_io_transport,
+        pcidoe_transport_encap,
+        config_info,
+        provision_info,
+    );
+
+    let session_id = (0xffu32 << 16) + 0xff;
+    context.common.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion13;
+    context.common.negotiate_info.rsp_capabilities_sel = SpdmResponseCapabilityFlags::EVENT_CAP;
+    context.common.negotiate_info.base_hash_sel = SpdmBaseHashAlgo::TPM_ALG_SHA_384;
+    context.common.session = gen_array_clone(SpdmSession::new(), 4);
+    setup_established_session(&mut context.common.session[0], session_id);
+
+    // GET_SUPPORTED_EVENT_TYPES advertises the DMTF event group.
+    let bytes = &mut [0u8; 4];
+    let mut writer = Writer::init(bytes);
+    let value = SpdmMessageHeader {
+        version: SpdmVersion::SpdmVersion13,
+        request_response_code: SpdmRequestResponseCode::SpdmRequestGetSupportedEventTypes,
+    };
+    assert!(value.encode(&mut writer).is_ok());
+    let rsp = &mut [0u8; 1024];
+    let mut writer = Writer::init(rsp);
+    context.write_spdm_supported_event_types_response(session_id, bytes, &mut writer);
+    let used = writer.used();
+    let mut reader = Reader::init(&rsp[..used]);
+    let header = SpdmMessageHeader::read(&mut reader).unwrap();
+    assert_eq!(
+        header.request_response_code,
+        SpdmRequestResponseCode::SpdmResponseSupportedEventTypes
+    );
+    let supported =
+        SpdmSupportedEventTypesResponsePayload::spdm_read(&mut context.common, &mut reader)
+            .unwrap();
+    assert_eq!(supported.event_group_count, 1);
+    assert_eq!(supported.event_groups[0].group_id, SPDM_DMTF_EVENT_GROUP_ID);
+
+    // Subscribe to MeasurementChanged only.
+    let mut event_groups = [SpdmEventGroup::default(); MAX_SPDM_EVENT_GROUP_COUNT];
+    event_groups[0] = SpdmEventGroup {
+        event_type_count: 1,
+        ..SpdmEventGroup::new_dmtf()
+    };
+    event_groups[0].event_types[0] = SpdmDmtfEventType::MeasurementChanged.get_u16();
+    let request = SpdmMessage {
+        header: SpdmMessageHeader {
+            version: SpdmVersion::SpdmVersion13,
+            request_response_code: SpdmRequestResponseCode::SpdmRequestSubscribeEventTypes,
+        },
+        payload: SpdmMessagePayload::SpdmSubscribeEventTypesRequest(
+            SpdmSubscribeEventTypesRequestPayload {
+                event_group_count: 1,
+                event_groups,
+            },
+        ),
+    };
+    let bytes = &mut [0u8; 1024];
+    let mut writer = Writer::init(bytes);
+    assert!(request
+        .spdm_encode(&mut context.common, &mut writer)
+        .is_ok());
+    let used = writer.used();
+    assert!(context
+        .handle_spdm_subscribe_event_types(session_id, &bytes[..used])
+        .is_ok());
+    assert_eq!(
+        context.common.session[0].event_info.subscribed_group_count,
+        1
+    );
+
+    // Only the subscribed event type is queued.
+    let group_id = SPDM_DMTF_EVENT_GROUP_ID;
+    assert!(context
+        .queue_spdm_event(
+            &group_id,
+            SpdmDmtfEventType::CertificateChanged.get_u16(),
+            &[]
+        )
+        .is_ok());
+    assert_eq!(context.common.session[0].event_info.event_count, 0);
+    assert!(context.send_spdm_event(session_id).is_err());
+    assert!(context
+        .queue_spdm_event(
+            &group_id,
+            SpdmDmtfEventType::MeasurementChanged.get_u16(),
+            &[1]
+        )
+        .is_ok());
+    assert_eq!(context.common.session[0].event_info.event_count, 1);
+
+    assert!(context.send_spdm_event(session_id).is_ok());
+    assert!(context
+        .queue_spdm_event(
+            &group_id,
+            SpdmDmtfEventType::MeasurementChanged.get_u16(),
+            &[2]
+        )
+        .is_ok());
+
+    // EVENT_ACK only releases the events that were sent.
+    let bytes = &mut [0u8; 4];
+    let mut writer = Writer::init(bytes);
+    let value = SpdmMessageHeader {
+        version: SpdmVersion::SpdmVersion13,
+        request_response_code: SpdmRequestResponseCode::SpdmResponseEventAck,
+    };
+    assert!(value.encode(&mut writer).is_ok());
+    assert!(context.handle_spdm_event_ack(session_id, bytes).is_ok());
+    let event_info = &context.common.session[0].event_info;
+    assert_eq!(event_info.event_count, 1);
+    assert_eq!(event_info.events[0].event_instance_id, 1);
+    assert_eq!(event_info.events[0].event_detail(), &[2]);
+    assert!(context.handle_spdm_event_ack(session_id, bytes).is_err());
+}
+
+#[test]
+fn test_case1_handle_spdm_event_unsupported() {
+    let (config_info, provision_info) = create_info();
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+    let shared_buffer = SharedBuffer::new();
+    let mut socket_io_transport = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    secret::asym_sign::register(SECRET_ASYM_IMPL_INSTANCE.clone());
+    let mut context = responder::ResponderContext::new(
+        &mut socket_io_transport,
+        pcidoe_transport_encap,
+        config_info,
+        provision_info,
+    );
+
+    let session_id = (0xffu32 << 16) + 0xff;
+    context.common.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion12;
+    context.common.negotiate_info.rsp_capabilities_sel = SpdmResponseCapabilityFlags::EVENT_CAP;
+    context.common.negotiate_info.base_hash_sel = SpdmBaseHashAlgo::TPM_ALG_SHA_384;
+    context.common.session = gen_array_clone(SpdmSession::new(), 4);
+    setup_established_session(&mut context.common.session[0], session_id);
+
+    let bytes = &mut [0u8; 4];
+    let mut writer = Writer::init(bytes);
+    let value = SpdmMessageHeader {
+        version: SpdmVersion::SpdmVersion12,
+        request_response_code: SpdmRequestResponseCode::SpdmRequestGetSupportedEventTypes,
+    };
+    assert!(value.encode(&mut writer).is_ok());
+    let rsp = &mut [0u8; 1024];
+    let mut writer = Writer::init(rsp);
+    context.write_spdm_supported_event_types_response(session_id, bytes, &mut writer);
+    let used = writer.used();
+    let mut reader = Reader::init(&rsp[..used]);
+    let header = SpdmMessageHeader::read(&mut reader).unwrap();
+    assert_eq!(
+        header.request_response_code,
+        SpdmRequestResponseCode::SpdmResponseError
+    );
+    let error = SpdmErrorResponsePayload::spdm_read(&mut context.common, &mut reader).unwrap();
+    assert_eq!(error.error_code, SpdmErrorCode::SpdmErrorUnsupportedRequest);
+
+    assert!(context
+        .queue_spdm_event(
+            &SPDM_DMTF_EVENT_GROUP_ID,
+            SpdmDmtfEventType::MeasurementChanged.get_u16(),
+            &[]
+        )
+        .is_err());
+}
+
+#[test]
+fn test_case2_queue_spdm_event_full_while_unacked() {
+    let (config_info, provision_info) = create_info();
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+    let shared_buffer = SharedBuffer::new();
+    let mut socket_io_transport = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    secret::asym_sign::register(SECRET_ASYM_IMPL_INSTANCE.clone());
+    let mut context = responder::ResponderContext::new(
+        &mut socket_io_transport,
+        pcidoe_transport_encap,
+        config_info,
+        provision_info,
+    );
+
+    let session_id = (0xffu32 << 16) + 0xff;
+    context.common.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion13;
+    context.common.negotiate_info.rsp_capabilities_sel = SpdmResponseCapabilityFlags::EVENT_CAP;
+    context.common.negotiate_info.base_hash_sel = SpdmBaseHashAlgo::TPM_ALG_SHA_384;
+    context.common.session = gen_array_clone(SpdmSession::new(), 4);
+    setup_established_session(&mut context.common.session[0], session_id);
+    context.common.session[0].event_info.subscribed_group_count = 1;
+    context.common.session[0].event_info.subscribed_groups[0] = SpdmEventGroup::new_dmtf();
+
+    let group_id = SPDM_DMTF_EVENT_GROUP_ID;
+    let event_type_id = SpdmDmtfEventType::MeasurementChanged.get_u16();
+    for i in 0..MAX_SPDM_EVENT_COUNT {
+        assert!(context
+            .queue_spdm_event(&group_id, event_type_id, &[i as u8])
+            .is_ok());
+    }
+
+    // The last slot is held back from SEND_EVENT.
+    assert!(context.send_spdm_event(session_id).is_ok());
+    let event_info = &context.common.session[0].event_info;
+    assert_eq!(
+        event_info.sent_event_count as usize,
+        MAX_SPDM_EVENT_COUNT - 1
+    );
+
+    // A full queue records EventLost in the slot that is not in flight.
+    assert!(context
+        .queue_spdm_event(&group_id, event_type_id, &[0xff])
+        .is_ok());
+    let event_info = &context.common.session[0].event_info;
+    assert_eq!(event_info.event_count as usize, MAX_SPDM_EVENT_COUNT);
+    for i in 0..MAX_SPDM_EVENT_COUNT - 1 {
+        assert_eq!(event_info.events[i].event_instance_id, i as u32);
+        assert_eq!(event_info.events[i].event_detail(), &[i as u8]);
+    }
+    let lost = &event_info.events[MAX_SPDM_EVENT_COUNT - 1];
+    assert_eq!(lost.event_type_id, SpdmDmtfEventType::EventLost.get_u16());
+    assert_eq!(
+        &lost.event_detail()[4..8],
+        &(MAX_SPDM_EVENT_COUNT as u32).to_le_bytes()
+    );
+
+    // EVENT_ACK releases the sent events and EventLost goes out next.
+    let bytes = &mut [0u8; 4];
+    let mut writer = Writer::init(bytes);
+    let value = SpdmMessageHeader {
+        version: SpdmVersion::SpdmVersion13,
+        request_response_code: SpdmRequestResponseCode::SpdmResponseEventAck,
+    };
+    assert!(value.encode(&mut writer).is_ok());
+    assert!(context.handle_spdm_event_ack(session_id, bytes).is_ok());
+    let event_info = &context.common.session[0].event_info;
+    assert_eq!(event_info.event_count, 1);
+    assert_eq!(
+        event_info.events[0].event_type_id,
+        SpdmDmtfEventType::EventLost.get_u16()
+    );
+    assert!(context.send_spdm_event(session_id).is_ok());
+    assert_eq!(context.common.session[0].event_info.sent_event_count, 1);
+}
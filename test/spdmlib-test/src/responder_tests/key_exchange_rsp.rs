@@ -7,6 +7,7 @@ use crate::common::device_io::{FakeSpdmDeviceIoReceve, SharedBuffer};
 use crate::common::secret_callback::*;
 use crate::common::transport::PciDoeTransportEncap;
 use crate::common::util::create_info;
+#[cfg(feature = "spdm-ring")]
 use bytes::BytesMut;
 use codec::{Codec, Writer};
 use spdmlib::common::opaque;
@@ -17,7 +18,7 @@ use spdmlib::protocol::*;
 use spdmlib::{crypto, responder, secret};
 
 #[test]
-#[cfg(not(feature = "hashed-transcript-data"))]
+#[cfg(all(feature = "spdm-ring", not(feature = "hashed-transcript-data")))]
 fn test_case0_handle_spdm_key_exchange() {
     let (config_info, provision_info) = create_info();
     let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
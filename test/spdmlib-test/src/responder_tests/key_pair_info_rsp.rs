@@ -0,0 +1,240 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use crate::common::device_io::{FakeSpdmDeviceIoReceve, SharedBuffer};
+use crate::common::secret_callback::*;
+use crate::common::transport::PciDoeTransportEncap;
+use crate::common::util::create_info;
+use codec::{Codec, Reader, Writer};
+use spdmlib::common::{SpdmCodec, SpdmConnectionState, SpdmContext};
+use spdmlib::message::*;
+use spdmlib::protocol::*;
+use spdmlib::{responder, secret};
+
+fn setup_key_pair_negotiate_info(context: &mut SpdmContext) {
+    context.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion13;
+    context.negotiate_info.rsp_capabilities_sel |=
+        SpdmResponseCapabilityFlags::GET_KEY_PAIR_INFO_CAP
+            | SpdmResponseCapabilityFlags::SET_KEY_PAIR_INFO_CAP;
+    context
+        .runtime_info
+        .set_connection_state(SpdmConnectionState::SpdmConnectionNegotiated);
+}
+
+#[test]
+fn test_case0_handle_spdm_get_key_pair_info() {
+    let (config_info, mut provision_info) = create_info();
+    provision_info.my_key_pair_id[0] = 1;
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+    let shared_buffer = SharedBuffer::new();
+    let mut socket_io_transport = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    secret::asym_sign::register(SECRET_ASYM_IMPL_INSTANCE.clone());
+    secret::key_pair::register(SECRET_KEY_PAIR_IMPL_INSTANCE.clone());
+    let mut context = responder::ResponderContext::new(
+        &mut socket_io_transport,
+        pcidoe_transport_encap,
+        config_info,
+        provision_info,
+    );
+    setup_key_pair_negotiate_info(&mut context.common);
+
+    let request = SpdmMessage {
+        header: SpdmMessageHeader {
+            version: SpdmVersion::SpdmVersion13,
+            request_response_code: SpdmRequestResponseCode::SpdmRequestGetKeyPairInfo,
+        },
+        payload: SpdmMessagePayload::SpdmGetKeyPairInfoRequest(SpdmGetKeyPairInfoRequestPayload {
+            key_pair_id: 1,
+        }),
+    };
+    let bytes = &mut [0u8; 16];
+    let mut writer = Writer::init(bytes);
+    assert!(request
+        .spdm_encode(&mut context.common, &mut writer)
+        .is_ok());
+    let used = writer.used();
+
+    assert!(context
+        .handle_spdm_get_key_pair_info(None, &bytes[..used])
+        .is_ok());
+
+    let mut response = [0u8; 1024];
+    let size = shared_buffer.get_buffer(&mut response);
+    // Skip the PCI DOE header.
+    let mut reader = Reader::init(&response[8..size]);
+    let header = SpdmMessageHeader::read(&mut reader).unwrap();
+    assert_eq!(
+        header.request_response_code,
+        SpdmRequestResponseCode::SpdmResponseKeyPairInfo
+    );
+    let key_pair_info =
+        SpdmKeyPairInfoResponsePayload::spdm_read(&mut context.common, &mut reader).unwrap();
+    assert_eq!(key_pair_info.key_pair_id, 1);
+    assert_eq!(
+        key_pair_info.key_pair_info.total_key_pairs,
+        TEST_TOTAL_KEY_PAIRS
+    );
+    assert_eq!(key_pair_info.assoc_cert_slot_mask, 0b0000_0001);
+}
+
+#[test]
+fn test_case1_handle_spdm_get_key_pair_info() {
+    let (config_info, provision_info) = create_info();
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+    let shared_buffer = SharedBuffer::new();
+    let mut socket_io_transport = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    secret::asym_sign::register(SECRET_ASYM_IMPL_INSTANCE.clone());
+    secret::key_pair::register(SECRET_KEY_PAIR_IMPL_INSTANCE.clone());
+    let mut context = responder::ResponderContext::new(
+        &mut socket_io_transport,
+        pcidoe_transport_encap,
+        config_info,
+        provision_info,
+    );
+    setup_key_pair_negotiate_info(&mut context.common);
+
+    // KeyPairID beyond TotalKeyPairs.
+    let request = SpdmMessage {
+        header: SpdmMessageHeader {
+            version: SpdmVersion::SpdmVersion13,
+            request_response_code: SpdmRequestResponseCode::SpdmRequestGetKeyPairInfo,
+        },
+        payload: SpdmMessagePayload::SpdmGetKeyPairInfoRequest(SpdmGetKeyPairInfoRequestPayload {
+            key_pair_id: TEST_TOTAL_KEY_PAIRS + 1,
+        }),
+    };
+    let bytes = &mut [0u8; 16];
+    let mut writer = Writer::init(bytes);
+    assert!(request
+        .spdm_encode(&mut context.common, &mut writer)
+        .is_ok());
+    let used = writer.used();
+
+    assert!(context
+        .handle_spdm_get_key_pair_info(None, &bytes[..used])
+        .is_ok());
+
+    let mut response = [0u8; 1024];
+    let size = shared_buffer.get_buffer(&mut response);
+    let mut reader = Reader::init(&response[8..size]);
+    let header = SpdmMessageHeader::read(&mut reader).unwrap();
+    assert_eq!(
+        header.request_response_code,
+        SpdmRequestResponseCode::SpdmResponseError
+    );
+    assert_eq!(
+        u8::read(&mut reader),
+        Some(SpdmErrorCode::SpdmErrorInvalidRequest.get_u8())
+    );
+}
+
+#[test]
+fn test_case0_handle_spdm_set_key_pair_info() {
+    let (config_info, mut provision_info) = create_info();
+    provision_info.my_key_pair_id[0] = 2;
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+    let shared_buffer = SharedBuffer::new();
+    let mut socket_io_transport = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    secret::asym_sign::register(SECRET_ASYM_IMPL_INSTANCE.clone());
+    secret::key_pair::register(SECRET_KEY_PAIR_IMPL_INSTANCE.clone());
+    let mut context = responder::ResponderContext::new(
+        &mut socket_io_transport,
+        pcidoe_transport_encap,
+        config_info,
+        provision_info,
+    );
+    setup_key_pair_negotiate_info(&mut context.common);
+
+    // Move key pair 2 from slot 0 to slot 1.
+    let request = SpdmMessage {
+        header: SpdmMessageHeader {
+            version: SpdmVersion::SpdmVersion13,
+            request_response_code: SpdmRequestResponseCode::SpdmRequestSetKeyPairInfo,
+        },
+        payload: SpdmMessagePayload::SpdmSetKeyPairInfoRequest(SpdmSetKeyPairInfoRequestPayload {
+            operation: SpdmSetKeyPairInfoOperation::SpdmSetKeyPairInfoOperationChange,
+            key_pair_id: 2,
+            desired_key_usage: SpdmKeyUsageMask::KEY_EX_USE,
+            desired_asym_algo: SpdmKeyPairAsymAlgo::ECC384,
+            desired_assoc_cert_slot_mask: 0b0000_0010,
+        }),
+    };
+    let bytes = &mut [0u8; 16];
+    let mut writer = Writer::init(bytes);
+    assert!(request
+        .spdm_encode(&mut context.common, &mut writer)
+        .is_ok());
+    let used = writer.used();
+
+    assert!(context
+        .handle_spdm_set_key_pair_info(None, &bytes[..used])
+        .is_ok());
+
+    let mut response = [0u8; 1024];
+    let size = shared_buffer.get_buffer(&mut response);
+    let mut reader = Reader::init(&response[8..size]);
+    let header = SpdmMessageHeader::read(&mut reader).unwrap();
+    assert_eq!(
+        header.request_response_code,
+        SpdmRequestResponseCode::SpdmResponseSetKeyPairInfoAck
+    );
+    assert_eq!(context.common.provision_info.my_key_pair_id[0], 0);
+    assert_eq!(context.common.provision_info.my_key_pair_id[1], 2);
+    assert_eq!(context.common.get_my_key_pair_id(1), 2);
+}
+
+#[test]
+fn test_case1_handle_spdm_set_key_pair_info() {
+    let (config_info, provision_info) = create_info();
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+    let shared_buffer = SharedBuffer::new();
+    let mut socket_io_transport = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    secret::asym_sign::register(SECRET_ASYM_IMPL_INSTANCE.clone());
+    secret::key_pair::register(SECRET_KEY_PAIR_IMPL_INSTANCE.clone());
+    let mut context = responder::ResponderContext::new(
+        &mut socket_io_transport,
+        pcidoe_transport_encap,
+        config_info,
+        provision_info,
+    );
+    setup_key_pair_negotiate_info(&mut context.common);
+
+    // The test key pairs have no GEN_KEY_CAP.
+    let request = SpdmMessage {
+        header: SpdmMessageHeader {
+            version: SpdmVersion::SpdmVersion13,
+            request_response_code: SpdmRequestResponseCode::SpdmRequestSetKeyPairInfo,
+        },
+        payload: SpdmMessagePayload::SpdmSetKeyPairInfoRequest(SpdmSetKeyPairInfoRequestPayload {
+            operation: SpdmSetKeyPairInfoOperation::SpdmSetKeyPairInfoOperationGenerate,
+            key_pair_id: 1,
+            desired_key_usage: SpdmKeyUsageMask::CHALLENGE_USE,
+            desired_asym_algo: SpdmKeyPairAsymAlgo::ECC384,
+            desired_assoc_cert_slot_mask: 0,
+        }),
+    };
+    let bytes = &mut [0u8; 16];
+    let mut writer = Writer::init(bytes);
+    assert!(request
+        .spdm_encode(&mut context.common, &mut writer)
+        .is_ok());
+    let used = writer.used();
+
+    assert!(context
+        .handle_spdm_set_key_pair_info(None, &bytes[..used])
+        .is_ok());
+
+    let mut response = [0u8; 1024];
+    let size = shared_buffer.get_buffer(&mut response);
+    let mut reader = Reader::init(&response[8..size]);
+    let header = SpdmMessageHeader::read(&mut reader).unwrap();
+    assert_eq!(
+        header.request_response_code,
+        SpdmRequestResponseCode::SpdmResponseError
+    );
+    assert_eq!(
+        u8::read(&mut reader),
+        Some(SpdmErrorCode::SpdmErrorInvalidRequest.get_u8())
+    );
+}
@@ -9,6 +9,7 @@ use crate::common::util::create_info;
 use codec::{Codec, Reader, Writer};
 use spdmlib::common::SpdmCodec;
 use spdmlib::common::SpdmConnectionState;
+use spdmlib::config;
 use spdmlib::message::*;
 use spdmlib::protocol::*;
 use spdmlib::{responder, secret};
@@ -209,3 +210,83 @@ fn test_case1_handle_spdm_measurement() {
         }
     }
 }
+
+#[test]
+fn test_case2_handle_spdm_measurement_single_index() {
+    let (config_info, provision_info) = create_info();
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+    let shared_buffer = SharedBuffer::new();
+    let mut socket_io_transport = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    let mut context = responder::ResponderContext::new(
+        &mut socket_io_transport,
+        pcidoe_transport_encap,
+        config_info,
+        provision_info,
+    );
+
+    secret::asym_sign::register(SECRET_ASYM_IMPL_INSTANCE.clone());
+    secret::measurement::register(SECRET_MEASUREMENT_IMPL_INSTANCE.clone());
+
+    context.common.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion12;
+    context.common.negotiate_info.base_hash_sel = SpdmBaseHashAlgo::TPM_ALG_SHA_384;
+    context.common.negotiate_info.base_asym_sel = SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384;
+    context.common.negotiate_info.measurement_hash_sel = SpdmMeasurementHashAlgo::TPM_ALG_SHA_384;
+    context.common.negotiate_info.measurement_specification_sel =
+        SpdmMeasurementSpecification::DMTF;
+    context
+        .common
+        .runtime_info
+        .set_connection_state(SpdmConnectionState::SpdmConnectionNegotiated);
+
+    let bytes = &mut [0u8; 1024];
+    let mut writer = Writer::init(bytes);
+    let request = SpdmMessage {
+        header: SpdmMessageHeader {
+            version: SpdmVersion::SpdmVersion12,
+            request_response_code: SpdmRequestResponseCode::SpdmRequestGetMeasurements,
+        },
+        payload: SpdmMessagePayload::SpdmGetMeasurementsRequest(
+            SpdmGetMeasurementsRequestPayload {
+                measurement_attributes: SpdmMeasurementAttributes::empty(),
+                measurement_operation: SpdmMeasurementOperation::from_index(5),
+                nonce: SpdmNonceStruct {
+                    data: [100u8; SPDM_NONCE_SIZE],
+                },
+                slot_id: 0,
+            },
+        ),
+    };
+    assert!(request
+        .spdm_encode(&mut context.common, &mut writer)
+        .is_ok());
+    let used = writer.used();
+    assert!(context
+        .handle_spdm_measurement(None, &bytes[..used])
+        .is_ok());
+
+    let response = &mut [0u8; config::MAX_SPDM_MSG_SIZE];
+    let size = shared_buffer.get_buffer(response);
+    // Skip the PCI DOE header.
+    let mut reader = Reader::init(&response[8..size]);
+    let header = SpdmMessageHeader::read(&mut reader).unwrap();
+    assert_eq!(
+        header.request_response_code,
+        SpdmRequestResponseCode::SpdmResponseMeasurements
+    );
+    let measurements =
+        SpdmMeasurementsResponsePayload::spdm_read(&mut context.common, &mut reader).unwrap();
+    assert_eq!(measurements.measurement_record.number_of_blocks, 1);
+
+    // The only block is the requested one.
+    let record = &measurements.measurement_record;
+    let mut reader = Reader::init(
+        &record.measurement_record_data[..record.measurement_record_length.get() as usize],
+    );
+    let block = SpdmMeasurementBlockStructure::read(&mut reader).unwrap();
+    assert_eq!(block.index, 5);
+    assert_eq!(
+        block.measurement.value_size,
+        SpdmBaseHashAlgo::TPM_ALG_SHA_384.get_size()
+    );
+    assert_eq!(0, reader.left());
+}
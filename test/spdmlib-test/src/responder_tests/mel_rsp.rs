@@ -0,0 +1,84 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use crate::common::device_io::{FakeSpdmDeviceIoReceve, SharedBuffer};
+use crate::common::secret_callback::*;
+use crate::common::transport::PciDoeTransportEncap;
+use crate::common::util::create_info;
+use codec::{Codec, Reader, Writer};
+use spdmlib::common::{SpdmCodec, SpdmConnectionState};
+use spdmlib::message::*;
+use spdmlib::protocol::*;
+use spdmlib::{responder, secret};
+
+#[test]
+fn test_case0_handle_spdm_measurement_extension_log() {
+    let (config_info, provision_info) = create_info();
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+    let shared_buffer = SharedBuffer::new();
+    let mut socket_io_transport = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    secret::asym_sign::register(SECRET_ASYM_IMPL_INSTANCE.clone());
+    secret::mel::register(SECRET_MEL_IMPL_INSTANCE.clone());
+    let mut context = responder::ResponderContext::new(
+        &mut socket_io_transport,
+        pcidoe_transport_encap,
+        config_info,
+        provision_info,
+    );
+
+    context.common.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion13;
+    context.common.negotiate_info.rsp_capabilities_sel |= SpdmResponseCapabilityFlags::MEL_CAP;
+    context
+        .common
+        .runtime_info
+        .set_connection_state(SpdmConnectionState::SpdmConnectionNegotiated);
+
+    // Ask for the last bytes of the log, past the end of a full portion.
+    let request = SpdmMessage {
+        header: SpdmMessageHeader {
+            version: SpdmVersion::SpdmVersion13,
+            request_response_code: SpdmRequestResponseCode::SpdmRequestGetMeasurementExtensionLog,
+        },
+        payload: SpdmMessagePayload::SpdmGetMeasurementExtensionLogRequest(
+            SpdmGetMeasurementExtensionLogRequestPayload {
+                offset: TEST_MEL_SIZE as u32 - 4,
+                length: 0x1000,
+            },
+        ),
+    };
+    let bytes = &mut [0u8; 16];
+    let mut writer = Writer::init(bytes);
+    assert!(request
+        .spdm_encode(&mut context.common, &mut writer)
+        .is_ok());
+    let used = writer.used();
+
+    assert!(context
+        .handle_spdm_measurement_extension_log(None, &bytes[..used])
+        .is_ok());
+
+    let mut response = [0u8; 1024];
+    let size = shared_buffer.get_buffer(&mut response);
+    // Skip the PCI DOE header.
+    let mut reader = Reader::init(&response[8..size]);
+    let header = SpdmMessageHeader::read(&mut reader).unwrap();
+    assert_eq!(
+        header.request_response_code,
+        SpdmRequestResponseCode::SpdmResponseMeasurementExtensionLog
+    );
+    let mel =
+        SpdmMeasurementExtensionLogResponsePayload::spdm_read(&mut context.common, &mut reader)
+            .unwrap();
+    assert_eq!(mel.portion_length, 4);
+    assert_eq!(mel.remainder_length, 0);
+    assert_eq!(
+        &mel.measurement_extension_log[..4],
+        &[
+            (TEST_MEL_SIZE - 4) as u8,
+            (TEST_MEL_SIZE - 3) as u8,
+            (TEST_MEL_SIZE - 2) as u8,
+            (TEST_MEL_SIZE - 1) as u8
+        ]
+    );
+}
@@ -0,0 +1,258 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use crate::common::device_io::{FakeSpdmDeviceIo, FakeSpdmDeviceIoReceve, SharedBuffer};
+use crate::common::secret_callback::*;
+use crate::common::transport::PciDoeTransportEncap;
+use crate::common::util::{create_info, get_rsp_cert_chain_buff};
+use spdmlib::common::SpdmConnectionState;
+use spdmlib::error::SPDM_STATUS_NOT_READY_PEER;
+use spdmlib::message::*;
+use spdmlib::protocol::*;
+use spdmlib::requester::RequesterContext;
+use spdmlib::{config, responder, secret, time};
+
+fn sleep_impl(_us: usize) {}
+
+fn setup_responder(responder: &mut responder::ResponderContext) {
+    responder.common.negotiate_info.req_capabilities_sel = SpdmRequestCapabilityFlags::CERT_CAP;
+    responder.common.negotiate_info.rsp_capabilities_sel = SpdmResponseCapabilityFlags::CERT_CAP;
+    responder
+        .common
+        .negotiate_info
+        .measurement_specification_sel = SpdmMeasurementSpecification::DMTF;
+    responder.common.negotiate_info.base_hash_sel = SpdmBaseHashAlgo::TPM_ALG_SHA_384;
+    responder.common.negotiate_info.base_asym_sel = SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384;
+    responder.common.negotiate_info.measurement_hash_sel = SpdmMeasurementHashAlgo::TPM_ALG_SHA_384;
+    responder.common.reset_runtime_info();
+    responder.common.provision_info.my_cert_chain = [
+        Some(SpdmCertChainBuffer {
+            data_size: 512u16,
+            data: [0u8; 4 + SPDM_MAX_HASH_SIZE + config::MAX_SPDM_CERT_CHAIN_DATA_SIZE],
+        }),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    ];
+    responder.common.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion12;
+    responder
+        .common
+        .runtime_info
+        .set_connection_state(SpdmConnectionState::SpdmConnectionNegotiated);
+}
+
+fn setup_requester(requester: &mut RequesterContext) {
+    requester.common.negotiate_info.req_capabilities_sel = SpdmRequestCapabilityFlags::CERT_CAP;
+    requester.common.negotiate_info.rsp_capabilities_sel = SpdmResponseCapabilityFlags::CERT_CAP;
+    requester
+        .common
+        .negotiate_info
+        .measurement_specification_sel = SpdmMeasurementSpecification::DMTF;
+    requester.common.negotiate_info.base_hash_sel = SpdmBaseHashAlgo::TPM_ALG_SHA_384;
+    requester.common.negotiate_info.base_asym_sel = SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384;
+    requester.common.negotiate_info.measurement_hash_sel = SpdmMeasurementHashAlgo::TPM_ALG_SHA_384;
+    requester.common.peer_info.peer_cert_chain[0] = Some(get_rsp_cert_chain_buff());
+    requester.common.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion12;
+    requester.common.reset_runtime_info();
+}
+
+#[test]
+fn test_case0_handle_spdm_respond_if_ready() {
+    let (rsp_config_info, rsp_provision_info) = create_info();
+    let (req_config_info, req_provision_info) = create_info();
+
+    let shared_buffer = SharedBuffer::new();
+    let mut device_io_responder = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+
+    secret::asym_sign::register(SECRET_ASYM_IMPL_INSTANCE.clone());
+    secret::measurement::register(SECRET_MEASUREMENT_IMPL_INSTANCE.clone());
+    time::register(time::SpdmTime {
+        sleep_cb: sleep_impl,
+    });
+
+    let mut responder = responder::ResponderContext::new(
+        &mut device_io_responder,
+        pcidoe_transport_encap,
+        rsp_config_info,
+        rsp_provision_info,
+    );
+    setup_responder(&mut responder);
+
+    let pcidoe_transport_encap2 = &mut PciDoeTransportEncap {};
+    let mut device_io_requester = FakeSpdmDeviceIo::new(&shared_buffer, &mut responder);
+
+    let mut requester = RequesterContext::new(
+        &mut device_io_requester,
+        pcidoe_transport_encap2,
+        req_config_info,
+        req_provision_info,
+    );
+    setup_requester(&mut requester);
+
+    // The signed response is produced on the second RESPOND_IF_READY.
+    RESPONSE_NOT_READY_COUNT.with(|count| count.set(2));
+    let mut total_number: u8 = 0;
+    let mut spdm_measurement_record_structure = SpdmMeasurementRecordStructure::default();
+    let status = requester.send_receive_spdm_measurement(
+        None,
+        0,
+        SpdmMeasurementAttributes::SIGNATURE_REQUESTED,
+        SpdmMeasurementOperation::from_index(1),
+        &mut total_number,
+        &mut spdm_measurement_record_structure,
+    );
+    assert!(status.is_ok());
+    assert_eq!(RESPONSE_NOT_READY_COUNT.with(|count| count.get()), 0);
+
+    // The transcript is intact for the next signed request.
+    let status = requester.send_receive_spdm_measurement(
+        None,
+        0,
+        SpdmMeasurementAttributes::SIGNATURE_REQUESTED,
+        SpdmMeasurementOperation::from_index(2),
+        &mut total_number,
+        &mut spdm_measurement_record_structure,
+    );
+    assert!(status.is_ok());
+}
+
+#[test]
+fn test_case1_handle_spdm_respond_if_ready() {
+    let (rsp_config_info, rsp_provision_info) = create_info();
+    let (req_config_info, req_provision_info) = create_info();
+
+    let shared_buffer = SharedBuffer::new();
+    let mut device_io_responder = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+
+    secret::asym_sign::register(SECRET_ASYM_IMPL_INSTANCE.clone());
+    secret::measurement::register(SECRET_MEASUREMENT_IMPL_INSTANCE.clone());
+    time::register(time::SpdmTime {
+        sleep_cb: sleep_impl,
+    });
+
+    let mut responder = responder::ResponderContext::new(
+        &mut device_io_responder,
+        pcidoe_transport_encap,
+        rsp_config_info,
+        rsp_provision_info,
+    );
+    setup_responder(&mut responder);
+
+    let pcidoe_transport_encap2 = &mut PciDoeTransportEncap {};
+    let mut device_io_requester = FakeSpdmDeviceIo::new(&shared_buffer, &mut responder);
+
+    let mut requester = RequesterContext::new(
+        &mut device_io_requester,
+        pcidoe_transport_encap2,
+        req_config_info,
+        req_provision_info,
+    );
+    setup_requester(&mut requester);
+
+    // RESPOND_IF_READY without a deferred request is unexpected.
+    let received = requester
+        .send_receive_spdm_respond_if_ready(
+            None,
+            SpdmRequestResponseCode::SpdmRequestGetMeasurements,
+            0,
+        )
+        .unwrap();
+    assert_eq!(
+        received.receive_buffer[1],
+        SpdmRequestResponseCode::SpdmResponseError.get_u8()
+    );
+    assert_eq!(
+        received.receive_buffer[2],
+        SpdmErrorCode::SpdmErrorUnexpectedRequest.get_u8()
+    );
+
+    // The requester gives up while the responder stays busy.
+    RESPONSE_NOT_READY_COUNT.with(|count| count.set(8));
+    let mut total_number: u8 = 0;
+    let mut spdm_measurement_record_structure = SpdmMeasurementRecordStructure::default();
+    let status = requester.send_receive_spdm_measurement(
+        None,
+        0,
+        SpdmMeasurementAttributes::empty(),
+        SpdmMeasurementOperation::SpdmMeasurementQueryTotalNumber,
+        &mut total_number,
+        &mut spdm_measurement_record_structure,
+    );
+    assert_eq!(status, Err(SPDM_STATUS_NOT_READY_PEER));
+    RESPONSE_NOT_READY_COUNT.with(|count| count.set(0));
+
+    // RESPOND_IF_READY for another request code does not match the deferred request.
+    let received = requester
+        .send_receive_spdm_respond_if_ready(None, SpdmRequestResponseCode::SpdmRequestChallenge, 0)
+        .unwrap();
+    assert_eq!(
+        received.receive_buffer[2],
+        SpdmErrorCode::SpdmErrorInvalidRequest.get_u8()
+    );
+}
+
+#[test]
+fn test_case2_handle_spdm_respond_if_ready() {
+    let (rsp_config_info, rsp_provision_info) = create_info();
+    let (req_config_info, req_provision_info) = create_info();
+
+    let shared_buffer = SharedBuffer::new();
+    let mut device_io_responder = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+
+    secret::asym_sign::register(SECRET_ASYM_IMPL_INSTANCE.clone());
+    secret::measurement::register(SECRET_MEASUREMENT_IMPL_INSTANCE.clone());
+    time::register(time::SpdmTime {
+        sleep_cb: sleep_impl,
+    });
+
+    let mut responder = responder::ResponderContext::new(
+        &mut device_io_responder,
+        pcidoe_transport_encap,
+        rsp_config_info,
+        rsp_provision_info,
+    );
+    setup_responder(&mut responder);
+    responder.common.negotiate_info.rsp_capabilities_sel |=
+        SpdmResponseCapabilityFlags::CHAL_CAP | SpdmResponseCapabilityFlags::MEAS_CAP_SIG;
+
+    let pcidoe_transport_encap2 = &mut PciDoeTransportEncap {};
+    let mut device_io_requester = FakeSpdmDeviceIo::new(&shared_buffer, &mut responder);
+
+    let mut requester = RequesterContext::new(
+        &mut device_io_requester,
+        pcidoe_transport_encap2,
+        req_config_info,
+        req_provision_info,
+    );
+    setup_requester(&mut requester);
+    requester.common.negotiate_info.req_capabilities_sel |= SpdmRequestCapabilityFlags::CHAL_CAP;
+    requester.common.negotiate_info.rsp_capabilities_sel |=
+        SpdmResponseCapabilityFlags::CHAL_CAP | SpdmResponseCapabilityFlags::MEAS_CAP_SIG;
+
+    // The signature is deferred after the request is added to the transcript,
+    // which is restored for the request processed again.
+    RESPONSE_NOT_READY_COUNT.with(|count| count.set(1));
+    let status = requester.send_receive_spdm_challenge(
+        0,
+        SpdmMeasurementSummaryHashType::SpdmMeasurementSummaryHashTypeNone,
+    );
+    assert!(status.is_ok());
+    assert_eq!(RESPONSE_NOT_READY_COUNT.with(|count| count.get()), 0);
+
+    // The measurement summary hash is deferred before.
+    RESPONSE_NOT_READY_COUNT.with(|count| count.set(1));
+    let status = requester.send_receive_spdm_challenge(
+        0,
+        SpdmMeasurementSummaryHashType::SpdmMeasurementSummaryHashTypeAll,
+    );
+    assert!(status.is_ok());
+    assert_eq!(RESPONSE_NOT_READY_COUNT.with(|count| count.get()), 0);
+}
@@ -0,0 +1,156 @@
+// Copyright (c) 2023 Intel Corporation
+//
+// SPDX-License-Identifier: BSD-2-Clause-Patent
+
+use crate::common::device_io::{FakeSpdmDeviceIoReceve, SharedBuffer};
+use crate::common::secret_callback::*;
+use crate::common::transport::PciDoeTransportEncap;
+use crate::common::util::{create_info, get_rsp_cert_chain_buff};
+use codec::{Codec, Reader, Writer};
+use spdmlib::common::{SpdmCodec, SpdmConnectionState, SpdmContext};
+use spdmlib::config;
+use spdmlib::message::*;
+use spdmlib::protocol::*;
+use spdmlib::{responder, secret};
+
+fn setup_set_certificate_negotiate_info(context: &mut SpdmContext) {
+    context.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion12;
+    context.negotiate_info.base_hash_sel = SpdmBaseHashAlgo::TPM_ALG_SHA_384;
+    context.negotiate_info.base_asym_sel = SpdmBaseAsymAlgo::TPM_ALG_ECDSA_ECC_NIST_P384;
+    context.negotiate_info.rsp_capabilities_sel |= SpdmResponseCapabilityFlags::SET_CERT_CAP;
+    context
+        .runtime_info
+        .set_connection_state(SpdmConnectionState::SpdmConnectionNegotiated);
+}
+
+fn encode_set_certificate(
+    context: &mut SpdmContext,
+    slot_id: u8,
+    cert_chain: SpdmCertChainBuffer,
+    bytes: &mut [u8],
+) -> usize {
+    let mut writer = Writer::init(bytes);
+    let request = SpdmMessage {
+        header: SpdmMessageHeader {
+            version: SpdmVersion::SpdmVersion12,
+            request_response_code: SpdmRequestResponseCode::SpdmRequestSetCertificate,
+        },
+        payload: SpdmMessagePayload::SpdmSetCertificateRequest(SpdmSetCertificateRequestPayload {
+            slot_id,
+            cert_chain,
+        }),
+    };
+    assert!(request.spdm_encode(context, &mut writer).is_ok());
+    writer.used()
+}
+
+#[test]
+fn test_case0_handle_spdm_set_certificate() {
+    let (config_info, provision_info) = create_info();
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+    let shared_buffer = SharedBuffer::new();
+    let mut socket_io_transport = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    secret::asym_sign::register(SECRET_ASYM_IMPL_INSTANCE.clone());
+    secret::cert_provision::register(SECRET_CERT_PROVISION_IMPL_INSTANCE.clone());
+    let mut context = responder::ResponderContext::new(
+        &mut socket_io_transport,
+        pcidoe_transport_encap,
+        config_info,
+        provision_info,
+    );
+    setup_set_certificate_negotiate_info(&mut context.common);
+    context.common.provision_info.my_cert_chain_data[0] = None;
+    context.common.provision_info.my_cert_chain[0] = None;
+
+    let bytes = &mut [0u8; config::MAX_SPDM_MSG_SIZE];
+    let used = encode_set_certificate(&mut context.common, 0, get_rsp_cert_chain_buff(), bytes);
+    assert!(context
+        .handle_spdm_set_certificate(None, &bytes[..used])
+        .is_ok());
+
+    let response = &mut [0u8; config::MAX_SPDM_MSG_SIZE];
+    let size = shared_buffer.get_buffer(response);
+    // Skip the PCI DOE header.
+    let mut reader = Reader::init(&response[8..size]);
+    let header = SpdmMessageHeader::read(&mut reader).unwrap();
+    assert_eq!(
+        header.request_response_code,
+        SpdmRequestResponseCode::SpdmResponseSetCertificateRsp
+    );
+    let set_certificate_rsp =
+        SpdmSetCertificateResponsePayload::spdm_read(&mut context.common, &mut reader).unwrap();
+    assert_eq!(set_certificate_rsp.slot_id, 0);
+
+    // The installed chain is the one served by GET_CERTIFICATE.
+    assert!(context.common.provision_info.my_cert_chain[0].is_some());
+    assert_eq!(
+        context.common.provision_info.my_cert_chain[0]
+            .as_ref()
+            .unwrap()
+            .as_ref(),
+        get_rsp_cert_chain_buff().as_ref()
+    );
+}
+
+#[test]
+fn test_case1_handle_spdm_set_certificate() {
+    let (config_info, provision_info) = create_info();
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+    let shared_buffer = SharedBuffer::new();
+    let mut socket_io_transport = FakeSpdmDeviceIoReceve::new(&shared_buffer);
+    secret::asym_sign::register(SECRET_ASYM_IMPL_INSTANCE.clone());
+    secret::cert_provision::register(SECRET_CERT_PROVISION_IMPL_INSTANCE.clone());
+    let mut context = responder::ResponderContext::new(
+        &mut socket_io_transport,
+        pcidoe_transport_encap,
+        config_info,
+        provision_info,
+    );
+    setup_set_certificate_negotiate_info(&mut context.common);
+    let response = &mut [0u8; config::MAX_SPDM_MSG_SIZE];
+
+    // A slot beyond SPDM_MAX_SLOT_NUMBER, and a slot other than 0 outside of
+    // a secure session.
+    for (slot_id, error_code) in [
+        (8, SpdmErrorCode::SpdmErrorInvalidRequest),
+        (1, SpdmErrorCode::SpdmErrorUnexpectedRequest),
+    ] {
+        let bytes = &mut [0u8; config::MAX_SPDM_MSG_SIZE];
+        let used = encode_set_certificate(
+            &mut context.common,
+            slot_id,
+            get_rsp_cert_chain_buff(),
+            bytes,
+        );
+        assert!(context
+            .handle_spdm_set_certificate(None, &bytes[..used])
+            .is_ok());
+        let _ = shared_buffer.get_buffer(response);
+        assert_eq!(
+            response[8 + 1],
+            SpdmRequestResponseCode::SpdmResponseError.get_u8()
+        );
+        assert_eq!(response[8 + 2], error_code.get_u8());
+    }
+
+    // A certificate chain larger than MAX_SPDM_CERT_CHAIN_DATA_SIZE.
+    let mut cert_chain = get_rsp_cert_chain_buff();
+    let size = cert_chain.data.len();
+    cert_chain.data_size = size as u16;
+    cert_chain.data[..2].copy_from_slice(&(size as u16).to_le_bytes());
+    let bytes = &mut vec![0u8; 4 + size];
+    let used = encode_set_certificate(&mut context.common, 0, cert_chain, bytes);
+    assert!(context
+        .handle_spdm_set_certificate(None, &bytes[..used])
+        .is_ok());
+    let _ = shared_buffer.get_buffer(response);
+    assert_eq!(
+        response[8 + 1],
+        SpdmRequestResponseCode::SpdmResponseError.get_u8()
+    );
+    assert_eq!(
+        response[8 + 2],
+        SpdmErrorCode::SpdmErrorInvalidRequest.get_u8()
+    );
+    assert!(context.common.provision_info.my_cert_chain_data[0].is_some());
+}
@@ -2,14 +2,20 @@
 //
 // SPDX-License-Identifier: BSD-2-Clause-Patent
 
+use crate::common::crypto_callback::FAKE_SM2_VERIFY;
 use crate::common::device_io::MySpdmDeviceIo;
+use crate::common::secret_callback::SECRET_SM2_IMPL_INSTANCE;
 use crate::common::transport::PciDoeTransportEncap;
 use crate::common::util::new_context;
 use codec::{u24, Codec, Reader, Writer};
 use spdmlib::common::opaque::*;
 use spdmlib::common::SpdmCodec;
 use spdmlib::config::{MAX_SPDM_MEASUREMENT_RECORD_SIZE, MAX_SPDM_MEASUREMENT_VALUE_LEN};
+use spdmlib::message::{
+    SpdmDigestsResponsePayload, SPDM_CERT_MODEL_ALIAS_CERT, SPDM_CERT_MODEL_DEVICE_CERT,
+};
 use spdmlib::protocol::*;
+use spdmlib::{crypto, secret};
 
 #[test]
 fn test_case0_spdm_opaque_struct() {
@@ -59,6 +65,7 @@ fn test_case0_spdm_digest_struct() {
     assert_eq!(0, reader.left());
 }
 #[test]
+#[cfg(not(feature = "pqc"))]
 fn test_case0_spdm_signature_struct() {
     let u8_slice = &mut [0u8; SPDM_MAX_ASYM_KEY_SIZE];
     let mut writer = Writer::init(u8_slice);
@@ -82,6 +89,35 @@ fn test_case0_spdm_signature_struct() {
     }
 }
 #[test]
+#[cfg(feature = "pqc")]
+fn test_case1_spdm_signature_struct() {
+    let u8_slice = &mut [0u8; SPDM_MAX_ASYM_KEY_SIZE];
+    let mut writer = Writer::init(u8_slice);
+    let value = SpdmSignatureStruct {
+        data_size: SPDM_MAX_ASYM_KEY_SIZE as u16,
+        data: [100u8; SPDM_MAX_ASYM_KEY_SIZE],
+    };
+
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+    let my_spdm_device_io = &mut MySpdmDeviceIo;
+    let mut context = new_context(my_spdm_device_io, pcidoe_transport_encap);
+    context.negotiate_info.base_asym_sel = SpdmBaseAsymAlgo::TPM_ALG_RSASSA_4096;
+    context.negotiate_info.pqc_asym_sel = SpdmPqcAsymAlgo::ML_DSA_87;
+
+    assert!(value.spdm_encode(&mut context, &mut writer).is_ok());
+    let mut reader = Reader::init(u8_slice);
+    assert_eq!(SPDM_MAX_ASYM_KEY_SIZE, reader.left());
+    let spdm_signature_struct = SpdmSignatureStruct::spdm_read(&mut context, &mut reader).unwrap();
+    assert_eq!(
+        spdm_signature_struct.data_size,
+        ML_DSA_87_SIGNATURE_SIZE as u16
+    );
+    for i in 0..ML_DSA_87_SIGNATURE_SIZE {
+        assert_eq!(spdm_signature_struct.data[i], 100);
+    }
+    assert_eq!(0, reader.left());
+}
+#[test]
 fn test_case0_spdm_measurement_record_structure() {
     let u8_slice = &mut [0u8; 512];
     let mut writer = Writer::init(u8_slice);
@@ -165,11 +201,11 @@ fn test_case1_spdm_measurement_record_structure() {
 }
 #[test]
 fn test_case0_spdm_dhe_exchange_struct() {
-    let u8_slice = &mut [0u8; SPDM_MAX_DHE_KEY_SIZE];
+    let u8_slice = &mut [0u8; ECDSA_ECC_NIST_P384_KEY_SIZE];
     let mut writer = Writer::init(u8_slice);
     SpdmDheExchangeStruct::default();
     let value = SpdmDheExchangeStruct {
-        data_size: SPDM_MAX_DHE_KEY_SIZE as u16,
+        data_size: ECDSA_ECC_NIST_P384_KEY_SIZE as u16,
         data: [100u8; SPDM_MAX_DHE_KEY_SIZE],
     };
 
@@ -180,7 +216,7 @@ fn test_case0_spdm_dhe_exchange_struct() {
 
     assert!(value.spdm_encode(&mut context, &mut writer).is_ok());
     let mut reader = Reader::init(u8_slice);
-    assert_eq!(SPDM_MAX_DHE_KEY_SIZE, reader.left());
+    assert_eq!(ECDSA_ECC_NIST_P384_KEY_SIZE, reader.left());
     let spdm_dhe_exchange_struct =
         SpdmDheExchangeStruct::spdm_read(&mut context, &mut reader).unwrap();
     assert_eq!(
@@ -193,6 +229,65 @@ fn test_case0_spdm_dhe_exchange_struct() {
     assert_eq!(0, reader.left());
 }
 #[test]
+fn test_case1_spdm_dhe_exchange_struct() {
+    let u8_slice = &mut [0u8; FFDHE_4096_KEY_SIZE];
+    let mut writer = Writer::init(u8_slice);
+    SpdmDheExchangeStruct::default();
+    let value = SpdmDheExchangeStruct {
+        data_size: FFDHE_4096_KEY_SIZE as u16,
+        data: [100u8; SPDM_MAX_DHE_KEY_SIZE],
+    };
+
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+    let my_spdm_device_io = &mut MySpdmDeviceIo;
+    let mut context = new_context(my_spdm_device_io, pcidoe_transport_encap);
+    context.negotiate_info.dhe_sel = SpdmDheAlgo::FFDHE_4096;
+
+    assert!(value.spdm_encode(&mut context, &mut writer).is_ok());
+    let mut reader = Reader::init(u8_slice);
+    assert_eq!(FFDHE_4096_KEY_SIZE, reader.left());
+    let spdm_dhe_exchange_struct =
+        SpdmDheExchangeStruct::spdm_read(&mut context, &mut reader).unwrap();
+    assert_eq!(
+        spdm_dhe_exchange_struct.data_size,
+        FFDHE_4096_KEY_SIZE as u16
+    );
+    for i in 0..FFDHE_4096_KEY_SIZE {
+        assert_eq!(spdm_dhe_exchange_struct.data[i], 100);
+    }
+    assert_eq!(0, reader.left());
+}
+#[test]
+#[cfg(feature = "pqc")]
+fn test_case2_spdm_dhe_exchange_struct() {
+    let u8_slice = &mut [0u8; SPDM_MAX_DHE_KEY_SIZE];
+    let mut writer = Writer::init(u8_slice);
+    let value = SpdmDheExchangeStruct {
+        data_size: SPDM_MAX_DHE_KEY_SIZE as u16,
+        data: [100u8; SPDM_MAX_DHE_KEY_SIZE],
+    };
+
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+    let my_spdm_device_io = &mut MySpdmDeviceIo;
+    let mut context = new_context(my_spdm_device_io, pcidoe_transport_encap);
+    context.negotiate_info.dhe_sel = SpdmDheAlgo::SECP_384_R1;
+    context.negotiate_info.kem_sel = SpdmKemAlgo::ML_KEM_1024;
+
+    assert!(value.spdm_encode(&mut context, &mut writer).is_ok());
+    let mut reader = Reader::init(u8_slice);
+    assert_eq!(SPDM_MAX_DHE_KEY_SIZE, reader.left());
+    let spdm_dhe_exchange_struct =
+        SpdmDheExchangeStruct::spdm_read(&mut context, &mut reader).unwrap();
+    assert_eq!(
+        spdm_dhe_exchange_struct.data_size,
+        ML_KEM_1024_ENCAP_KEY_SIZE as u16
+    );
+    for i in 0..ML_KEM_1024_ENCAP_KEY_SIZE {
+        assert_eq!(spdm_dhe_exchange_struct.data[i], 100);
+    }
+    assert_eq!(0, reader.left());
+}
+#[test]
 fn test_case0_spdm_dmtf_measurement_structure() {
     let mut value = SpdmDmtfMeasurementStructure::default();
     let r#type = [
@@ -294,3 +389,89 @@ fn test_case0_spdm_measurement_block_structure() {
     }
     assert_eq!(0, reader.left());
 }
+
+#[test]
+fn test_case0_is_peer_alias_cert_model() {
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+    let my_spdm_device_io = &mut MySpdmDeviceIo;
+    let mut context = new_context(my_spdm_device_io, pcidoe_transport_encap);
+
+    assert!(!context.is_peer_alias_cert_model(0));
+    context.negotiate_info.rsp_capabilities_sel |= SpdmResponseCapabilityFlags::ALIAS_CERT_CAP;
+    // The certificate model is not reported without a multi-key connection.
+    assert!(context.is_peer_alias_cert_model(0));
+
+    let mut digests = SpdmDigestsResponsePayload {
+        slot_mask: 0b101,
+        ..Default::default()
+    };
+    digests.certificate_info[0] = SPDM_CERT_MODEL_DEVICE_CERT;
+    digests.certificate_info[1] = SPDM_CERT_MODEL_ALIAS_CERT;
+    context.set_peer_key_pairs(&digests);
+    assert!(!context.is_peer_alias_cert_model(0));
+    assert!(context.is_peer_alias_cert_model(1));
+    assert!(context.is_peer_alias_cert_model(2));
+    assert!(!context.is_peer_alias_cert_model(SPDM_PUBLIC_KEY_SLOT_ID));
+}
+
+#[test]
+fn test_case0_sm2_signing_id() {
+    let pcidoe_transport_encap = &mut PciDoeTransportEncap {};
+    let my_spdm_device_io = &mut MySpdmDeviceIo;
+    let mut context = new_context(my_spdm_device_io, pcidoe_transport_encap);
+
+    crypto::sm2_verify::register(FAKE_SM2_VERIFY.clone());
+    secret::sm2_sign::register(SECRET_SM2_IMPL_INSTANCE.clone());
+
+    context.negotiate_info.base_hash_sel = SpdmBaseHashAlgo::TPM_ALG_SM3_256;
+    context.negotiate_info.base_asym_sel = SpdmBaseAsymAlgo::TPM_ALG_SM2_ECC_SM2_P256;
+    context.provision_info.peer_public_key = Some(SpdmPublicKeyData {
+        data_size: 1,
+        ..Default::default()
+    });
+    let data = [0x5au8; 32];
+
+    // Since SPDM 1.2 the signing ID is the signing context string.
+    context.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion12;
+    let signature = context
+        .sign_with_my_key_pair(0, &SPDM_CHALLENGE_AUTH_SIGN_CONTEXT, &data)
+        .ready()
+        .unwrap();
+    assert_eq!(signature.data_size as usize, SM2_ECC_SM2_P256_KEY_SIZE);
+    assert_eq!(
+        &signature.data[..SPDM_CHALLENGE_AUTH_SIGN_CONTEXT.len()],
+        &SPDM_CHALLENGE_AUTH_SIGN_CONTEXT[..]
+    );
+    assert!(context
+        .verify_peer_signature(
+            SPDM_PUBLIC_KEY_SLOT_ID,
+            &SPDM_CHALLENGE_AUTH_SIGN_CONTEXT,
+            &data,
+            &signature
+        )
+        .is_ok());
+    assert!(context
+        .verify_peer_signature(
+            SPDM_PUBLIC_KEY_SLOT_ID,
+            &SPDM_MEASUREMENTS_SIGN_CONTEXT,
+            &data,
+            &signature
+        )
+        .is_err());
+
+    // Earlier versions sign with an empty ID.
+    context.negotiate_info.spdm_version_sel = SpdmVersion::SpdmVersion11;
+    let signature = context
+        .sign_with_my_key_pair(0, &SPDM_CHALLENGE_AUTH_SIGN_CONTEXT, &data)
+        .ready()
+        .unwrap();
+    assert!(signature.as_ref().iter().all(|b| *b == 0));
+    assert!(context
+        .verify_peer_signature(
+            SPDM_PUBLIC_KEY_SLOT_ID,
+            &SPDM_MEASUREMENTS_SIGN_CONTEXT,
+            &data,
+            &signature
+        )
+        .is_ok());
+}
@@ -24,3 +24,13 @@ cat ca.crt.der end_two_level.crt.der > bundle_two_level_cert.der
 
 openssl req -x509 -sha256 -days 356 -nodes -newkey rsa:2048 -subj "/CN=intel test RSA" -keyout ca_selfsigned.key -out ca_selfsigned.crt -config openssl.cnf -extensions v3_selfsigned
 openssl x509 -in ca_selfsigned.crt -out ca_selfsigned.crt.der -outform DER
+
+openssl req -nodes -newkey rsa:2048 -keyout end_dmtf_requester.key -out end_dmtf_requester.req -sha256 -batch -subj "/CN=intel test RSA DMTF requester cert"
+openssl x509 -req -in end_dmtf_requester.req -out end_dmtf_requester.crt -CA ca.crt -CAkey ca.key -sha256 -days 3650 -set_serial 5 -extfile ./openssl.cnf -extensions v3_dmtf_requester
+openssl x509 -in end_dmtf_requester.crt -out end_dmtf_requester.crt.der -outform DER
+cat ca.crt.der end_dmtf_requester.crt.der > bundle_dmtf_requester_cert.der
+
+openssl req -nodes -newkey rsa:2048 -keyout end_dmtf_responder.key -out end_dmtf_responder.req -sha256 -batch -subj "/CN=intel test RSA DMTF responder cert"
+openssl x509 -req -in end_dmtf_responder.req -out end_dmtf_responder.crt -CA ca.crt -CAkey ca.key -sha256 -days 3650 -set_serial 6 -extfile ./openssl.cnf -extensions v3_dmtf_responder
+openssl x509 -in end_dmtf_responder.crt -out end_dmtf_responder.crt.der -outform DER
+cat ca.crt.der end_dmtf_responder.crt.der > bundle_dmtf_responder_cert.der